@@ -11,10 +11,11 @@
 //! to change all four aliases to point to concrete types that work with each other. Failure to do
 //! so will result in a ton of compilation errors, and worse: it will not make sense!
 
+use curve25519_dalek::edwards::CompressedEdwardsY;
 use fastcrypto::{
     bls12381, ed25519,
     error::FastCryptoError,
-    hash::{Blake2b256, HashFunction},
+    hash::{Blake2b256, HashFunction, Sha512},
     traits::{KeyPair as _, Signer as _, ToFromBytes as _, VerifyingKey as _},
 };
 use serde::{Deserialize, Serialize};
@@ -31,6 +32,10 @@ impl NetworkPublicKey {
         Self(key)
     }
 
+    pub fn into_inner(self) -> ed25519::Ed25519PublicKey {
+        self.0
+    }
+
     pub fn to_bytes(&self) -> [u8; 32] {
         self.0 .0.to_bytes()
     }
@@ -52,6 +57,20 @@ impl NetworkKeyPair {
     pub fn private_key_bytes(self) -> [u8; 32] {
         self.0.private().0.to_bytes()
     }
+
+    /// Computes the Diffie-Hellman secret shared with the owner of `peer`, by converting both
+    /// ed25519 keys to their X25519 counterparts. Returns None if `peer` is not a valid point.
+    pub fn shared_secret(&self, peer: &NetworkPublicKey) -> Option<[u8; 32]> {
+        let peer = CompressedEdwardsY(peer.to_bytes())
+            .decompress()?
+            .to_montgomery();
+        // The X25519 scalar is derived from the ed25519 seed the same way ed25519 derives its
+        // signing scalar, so both keys share the same secret.
+        let expanded = Sha512::digest(self.clone().private_key_bytes()).digest;
+        let mut scalar = [0u8; 32];
+        scalar.copy_from_slice(&expanded[..32]);
+        Some(peer.mul_clamped(scalar).to_bytes())
+    }
 }
 
 impl Clone for NetworkKeyPair {
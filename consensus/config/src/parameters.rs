@@ -36,13 +36,114 @@ pub struct Parameters {
     #[serde(default = "Parameters::default_max_forward_time_drift")]
     pub max_forward_time_drift: Duration,
 
+    /// The maximum number of events kept in the consensus event log. Older events are evicted in
+    /// batches of a tenth of the capacity as new ones are recorded.
+    #[serde(default = "Parameters::default_event_log_capacity")]
+    pub event_log_capacity: u64,
+
+    /// The maximum number of blocks suspended until their missing ancestors are accepted. Beyond
+    /// it, the blocks suspended the longest ago are evicted, and fetched again if needed.
+    #[serde(default = "Parameters::default_max_suspended_blocks")]
+    pub max_suspended_blocks: usize,
+
+    /// The maximum total size in bytes of the suspended blocks, beyond which the blocks suspended
+    /// the longest ago are evicted.
+    #[serde(default = "Parameters::default_max_suspended_blocks_bytes")]
+    pub max_suspended_blocks_bytes: usize,
+
+    /// The maximum number of suspended blocks per authority. Beyond it, the blocks of the authority
+    /// suspended the longest ago are evicted, so that an authority producing blocks without a valid
+    /// causal history cannot take the room of the others.
+    #[serde(default = "Parameters::default_max_suspended_blocks_per_authority")]
+    pub max_suspended_blocks_per_authority: usize,
+
+    /// Whether suspended blocks are persisted to storage, so that they are recovered after a
+    /// restart instead of being fetched again from peers.
+    #[serde(default)]
+    pub persist_suspended_blocks: bool,
+
+    /// Interval between commit SLO reports. Each report summarizes commit throughput, commit
+    /// latency percentiles and per-authority contribution over the trailing `slo_report_window`.
+    #[serde(default = "Parameters::default_slo_report_interval")]
+    pub slo_report_interval: Duration,
+
+    /// Trailing window of commits covered by each SLO report.
+    #[serde(default = "Parameters::default_slo_report_window")]
+    pub slo_report_window: Duration,
+
+    /// If set, the latest SLO report is written to this path as JSON, in addition to being
+    /// reported in metrics.
+    #[serde(default)]
+    pub slo_report_path: Option<PathBuf>,
+
+    /// Whether to verify the integrity of the consensus store on startup, before recovering from
+    /// it: the checksums of blocks and commits, and the references between them. An authority
+    /// with a corrupted store halts with a report of the corruption, unless
+    /// `repair_store_on_startup` is set.
+    #[serde(default)]
+    pub verify_store_on_startup: bool,
+
+    /// Whether to repair a store found to be corrupted by the startup verification, by truncating
+    /// it to its last consistent commit. Commits after it are committed again from the blocks
+    /// that remain in the store, or are fetched from peers. Commits already processed by the
+    /// authority are never truncated: an authority whose store cannot be repaired otherwise
+    /// halts.
+    #[serde(default)]
+    pub repair_store_on_startup: bool,
+
+    /// Interval between probes of the highest rounds received by peers from each authority, used
+    /// to estimate how far behind blocks propagate to a quorum.
+    #[serde(default = "Parameters::default_round_prober_interval")]
+    pub round_prober_interval: Duration,
+
+    /// Timeout of each request to a peer for its highest received rounds.
+    #[serde(default = "Parameters::default_round_prober_request_timeout")]
+    pub round_prober_request_timeout: Duration,
+
+    /// The number of rounds by which the own proposed blocks can be ahead of the highest round
+    /// received by a quorum, before the authority stops proposing new blocks until its blocks catch
+    /// up. This avoids producing blocks that cannot be included by peers anyway.
+    #[serde(default = "Parameters::default_propagation_delay_stop_proposal_threshold")]
+    pub propagation_delay_stop_proposal_threshold: u32,
+
+    /// Duration without a quorum of blocks for the next round, after which the authority is
+    /// considered stalled. The stall is reported in metrics and logs, with the authorities whose
+    /// blocks are missing to reach a quorum.
+    #[serde(default = "Parameters::default_stall_detection_timeout")]
+    pub stall_detection_timeout: Duration,
+
+    /// The number of rounds of missing blocks (i.e. missing blocks per authority of the committee)
+    /// beyond which the authority is considered to be catching up, e.g. after a restart, and the
+    /// synchronizer paces its periodic fetches to avoid overwhelming its own CPU and its peers.
+    #[serde(default = "Parameters::default_synchronizer_catch_up_threshold_rounds")]
+    pub synchronizer_catch_up_threshold_rounds: u32,
+
+    /// While catching up, the number of rounds of missing blocks fetched by each periodic run of
+    /// the synchronizer, starting from the lowest rounds.
+    #[serde(default = "Parameters::default_synchronizer_catch_up_fetch_rounds")]
+    pub synchronizer_catch_up_fetch_rounds: u32,
+
+    /// While catching up, the number of rounds of fetched blocks sent to core at a time, so that
+    /// their acceptance and commit is interleaved with the processing of live blocks.
+    #[serde(default = "Parameters::default_synchronizer_catch_up_core_batch_rounds")]
+    pub synchronizer_catch_up_core_batch_rounds: u32,
+
     /// The database path.
     /// Required.
     pub db_path: Option<PathBuf>,
 
+    /// The network used to communicate with peers. All authorities of a committee must use the
+    /// same type of network.
+    #[serde(default)]
+    pub network_type: NetworkType,
+
     /// Anemo network settings.
     #[serde(default = "AnemoParameters::default")]
     pub anemo: AnemoParameters,
+
+    /// QUIC network settings.
+    #[serde(default = "QuicParameters::default")]
+    pub quic: QuicParameters,
 }
 
 impl Parameters {
@@ -62,6 +163,58 @@ impl Parameters {
         Duration::from_millis(500)
     }
 
+    pub fn default_event_log_capacity() -> u64 {
+        100_000
+    }
+
+    pub fn default_max_suspended_blocks() -> usize {
+        100_000
+    }
+
+    pub fn default_max_suspended_blocks_bytes() -> usize {
+        1 << 30
+    }
+
+    pub fn default_max_suspended_blocks_per_authority() -> usize {
+        10_000
+    }
+
+    pub fn default_slo_report_interval() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn default_slo_report_window() -> Duration {
+        Duration::from_secs(600)
+    }
+
+    pub fn default_round_prober_interval() -> Duration {
+        Duration::from_secs(5)
+    }
+
+    pub fn default_round_prober_request_timeout() -> Duration {
+        Duration::from_secs(2)
+    }
+
+    pub fn default_propagation_delay_stop_proposal_threshold() -> u32 {
+        5
+    }
+
+    pub fn default_stall_detection_timeout() -> Duration {
+        Duration::from_secs(10)
+    }
+
+    pub fn default_synchronizer_catch_up_threshold_rounds() -> u32 {
+        100
+    }
+
+    pub fn default_synchronizer_catch_up_fetch_rounds() -> u32 {
+        50
+    }
+
+    pub fn default_synchronizer_catch_up_core_batch_rounds() -> u32 {
+        10
+    }
+
     pub fn db_path_str_unsafe(&self) -> String {
         self.db_path
             .clone()
@@ -80,12 +233,46 @@ impl Default for Parameters {
             leader_timeout: Parameters::default_leader_timeout(),
             min_round_delay: Parameters::default_min_round_delay(),
             max_forward_time_drift: Parameters::default_max_forward_time_drift(),
+            event_log_capacity: Parameters::default_event_log_capacity(),
+            max_suspended_blocks: Parameters::default_max_suspended_blocks(),
+            max_suspended_blocks_bytes: Parameters::default_max_suspended_blocks_bytes(),
+            max_suspended_blocks_per_authority:
+                Parameters::default_max_suspended_blocks_per_authority(),
+            persist_suspended_blocks: false,
+            slo_report_interval: Parameters::default_slo_report_interval(),
+            slo_report_window: Parameters::default_slo_report_window(),
+            slo_report_path: None,
+            verify_store_on_startup: false,
+            repair_store_on_startup: false,
+            round_prober_interval: Parameters::default_round_prober_interval(),
+            round_prober_request_timeout: Parameters::default_round_prober_request_timeout(),
+            propagation_delay_stop_proposal_threshold:
+                Parameters::default_propagation_delay_stop_proposal_threshold(),
+            stall_detection_timeout: Parameters::default_stall_detection_timeout(),
+            synchronizer_catch_up_threshold_rounds:
+                Parameters::default_synchronizer_catch_up_threshold_rounds(),
+            synchronizer_catch_up_fetch_rounds:
+                Parameters::default_synchronizer_catch_up_fetch_rounds(),
+            synchronizer_catch_up_core_batch_rounds:
+                Parameters::default_synchronizer_catch_up_core_batch_rounds(),
             db_path: None,
+            network_type: NetworkType::default(),
             anemo: AnemoParameters::default(),
+            quic: QuicParameters::default(),
         }
     }
 }
 
+/// Type of network used by a consensus authority.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkType {
+    #[default]
+    Anemo,
+    Tonic,
+    Quic,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AnemoParameters {
     /// Size in bytes above which network messages are considered excessively large. Excessively
@@ -113,3 +300,62 @@ impl AnemoParameters {
         8 << 20
     }
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct QuicParameters {
+    /// Interval between keep-alive packets sent on idle connections, which prevents them from
+    /// timing out.
+    ///
+    /// If unspecified, this will default to 5 seconds.
+    #[serde(default = "QuicParameters::default_keep_alive_interval")]
+    keep_alive_interval: Duration,
+
+    /// Duration of inactivity after which a connection is closed. Should be larger than
+    /// `keep_alive_interval`, so that connections to live peers are not closed.
+    ///
+    /// If unspecified, this will default to 30 seconds.
+    #[serde(default = "QuicParameters::default_idle_timeout")]
+    idle_timeout: Duration,
+
+    /// Size in bytes above which messages are rejected.
+    ///
+    /// If unspecified, this will default to 64 MiB.
+    #[serde(default = "QuicParameters::default_max_message_size")]
+    max_message_size: usize,
+}
+
+impl Default for QuicParameters {
+    fn default() -> Self {
+        Self {
+            keep_alive_interval: QuicParameters::default_keep_alive_interval(),
+            idle_timeout: QuicParameters::default_idle_timeout(),
+            max_message_size: QuicParameters::default_max_message_size(),
+        }
+    }
+}
+
+impl QuicParameters {
+    pub fn keep_alive_interval(&self) -> Duration {
+        self.keep_alive_interval
+    }
+
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size
+    }
+
+    fn default_keep_alive_interval() -> Duration {
+        Duration::from_secs(5)
+    }
+
+    fn default_idle_timeout() -> Duration {
+        Duration::from_secs(30)
+    }
+
+    fn default_max_message_size() -> usize {
+        64 << 20
+    }
+}
@@ -44,6 +44,24 @@ fn build_tonic_services(out_dir: &Path) {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            tonic_build::manual::Method::builder()
+                .name("get_latest_rounds")
+                .route_name("GetLatestRounds")
+                .input_type("crate::network::GetLatestRoundsRequest")
+                .output_type("crate::network::GetLatestRoundsResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            tonic_build::manual::Method::builder()
+                .name("report_rejected_blocks")
+                .route_name("ReportRejectedBlocks")
+                .input_type("crate::network::ReportRejectedBlocksRequest")
+                .output_type("crate::network::ReportRejectedBlocksResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
         .build();
 
     tonic_build::manual::Builder::new()
@@ -79,6 +97,24 @@ fn build_anemo_services(out_dir: &Path) {
                 .codec_path(codec_path)
                 .build(),
         )
+        .method(
+            anemo_build::manual::Method::builder()
+                .name("get_latest_rounds")
+                .route_name("GetLatestRounds")
+                .request_type("crate::network::GetLatestRoundsRequest")
+                .response_type("crate::network::GetLatestRoundsResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            anemo_build::manual::Method::builder()
+                .name("report_rejected_blocks")
+                .route_name("ReportRejectedBlocks")
+                .request_type("crate::network::ReportRejectedBlocksRequest")
+                .response_type("crate::network::ReportRejectedBlocksResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
         .build();
 
     anemo_build::manual::Builder::new()
@@ -9,16 +9,21 @@ use std::{
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use consensus_config::{AuthorityIndex, Committee, NetworkKeyPair, Parameters, ProtocolKeyPair};
+use consensus_config::{
+    AuthorityIndex, Committee, NetworkKeyPair, NetworkType, Parameters, ProtocolKeyPair,
+};
 use parking_lot::RwLock;
 use prometheus::Registry;
 use sui_protocol_config::ProtocolConfig;
+use tap::tap::TapFallible;
 use tokio::time::sleep;
-use tracing::{info, warn};
+use tracing::{debug, error, info, warn};
 
+#[cfg(test)]
+use crate::network::fault_injection::FaultInjector;
 use crate::{
-    block::{timestamp_utc_ms, BlockAPI, BlockRef, SignedBlock, VerifiedBlock},
-    block_manager::BlockManager,
+    block::{timestamp_utc_ms, BlockAPI, BlockRef, Round, SignedBlock, VerifiedBlock},
+    block_manager::{BlockManager, RejectionReason},
     block_verifier::{BlockVerifier, SignedBlockVerifier},
     broadcaster::Broadcaster,
     commit_observer::CommitObserver,
@@ -27,12 +32,18 @@ use crate::{
     core_thread::{ChannelCoreThreadDispatcher, CoreThreadDispatcher, CoreThreadHandle},
     dag_state::DagState,
     error::{ConsensusError, ConsensusResult},
+    event_log::ConsensusEvent,
     leader_timeout::{LeaderTimeoutTask, LeaderTimeoutTaskHandle},
     metrics::initialise_metrics,
     network::{
-        anemo_network::AnemoManager, tonic_network::TonicManager, NetworkManager, NetworkService,
+        anemo_network::AnemoManager, quic_network::QuicManager, tonic_network::TonicManager,
+        NetworkManager, NetworkService, MAX_REJECTIONS_PER_REPORT,
     },
-    storage::rocksdb_store::RocksDBStore,
+    rejection_reporter::{RejectionReporter, RejectionReporterHandle},
+    round_prober::{RoundProber, RoundProberHandle},
+    slo_report::{SloReportTask, SloReportTaskHandle},
+    stall_detector::{StallDetector, StallDetectorHandle, StallReason},
+    storage::{integrity::verify_store_on_startup, rocksdb_store::RocksDBStore},
     synchronizer::{Synchronizer, SynchronizerHandle},
     transaction::{TransactionClient, TransactionConsumer, TransactionVerifier},
     CommitConsumer,
@@ -44,18 +55,12 @@ use crate::{
 pub enum ConsensusAuthority {
     WithAnemo(AuthorityNode<AnemoManager>),
     WithTonic(AuthorityNode<TonicManager>),
-}
-
-// Type of network used by the authority node.
-#[derive(Clone, Copy)]
-pub enum NetworkType {
-    Anemo,
-    Tonic,
+    WithQuic(AuthorityNode<QuicManager>),
 }
 
 impl ConsensusAuthority {
+    /// Starts the authority, with the type of network set in `parameters`.
     pub async fn start(
-        network_type: NetworkType,
         own_index: AuthorityIndex,
         committee: Committee,
         parameters: Parameters,
@@ -66,7 +71,7 @@ impl ConsensusAuthority {
         commit_consumer: CommitConsumer,
         registry: Registry,
     ) -> Self {
-        match network_type {
+        match parameters.network_type {
             NetworkType::Anemo => {
                 let authority = AuthorityNode::start(
                     own_index,
@@ -97,6 +102,21 @@ impl ConsensusAuthority {
                 .await;
                 Self::WithTonic(authority)
             }
+            NetworkType::Quic => {
+                let authority = AuthorityNode::start(
+                    own_index,
+                    committee,
+                    parameters,
+                    protocol_config,
+                    protocol_keypair,
+                    network_keypair,
+                    transaction_verifier,
+                    commit_consumer,
+                    registry,
+                )
+                .await;
+                Self::WithQuic(authority)
+            }
         }
     }
 
@@ -104,6 +124,7 @@ impl ConsensusAuthority {
         match self {
             Self::WithAnemo(authority) => authority.stop().await,
             Self::WithTonic(authority) => authority.stop().await,
+            Self::WithQuic(authority) => authority.stop().await,
         }
     }
 
@@ -111,6 +132,17 @@ impl ConsensusAuthority {
         match self {
             Self::WithAnemo(authority) => authority.transaction_client(),
             Self::WithTonic(authority) => authority.transaction_client(),
+            Self::WithQuic(authority) => authority.transaction_client(),
+        }
+    }
+
+    /// Returns why the authority cannot advance rounds, if it has been stalled for longer than
+    /// `Parameters::stall_detection_timeout`, or `None` if it is making progress.
+    pub fn stall_reason(&self) -> Option<StallReason> {
+        match self {
+            Self::WithAnemo(authority) => authority.stall_reason(),
+            Self::WithTonic(authority) => authority.stall_reason(),
+            Self::WithQuic(authority) => authority.stall_reason(),
         }
     }
 
@@ -119,6 +151,17 @@ impl ConsensusAuthority {
         match self {
             Self::WithAnemo(authority) => &authority.context,
             Self::WithTonic(authority) => &authority.context,
+            Self::WithQuic(authority) => &authority.context,
+        }
+    }
+
+    /// Returns the fault injector applied to the outgoing requests of the authority.
+    #[cfg(test)]
+    fn fault_injector(&self) -> Arc<FaultInjector> {
+        match self {
+            Self::WithAnemo(authority) => authority.network_manager.fault_injector(),
+            Self::WithTonic(authority) => authority.network_manager.fault_injector(),
+            Self::WithQuic(authority) => authority.network_manager.fault_injector(),
         }
     }
 }
@@ -132,6 +175,10 @@ where
     transaction_client: Arc<TransactionClient>,
     synchronizer: Arc<SynchronizerHandle>,
     leader_timeout_handle: LeaderTimeoutTaskHandle,
+    slo_report_handle: SloReportTaskHandle,
+    round_prober_handle: RoundProberHandle,
+    stall_detector_handle: StallDetectorHandle,
+    rejection_reporter_handle: RejectionReporterHandle,
     core_thread_handle: CoreThreadHandle,
     broadcaster: Broadcaster,
     network_manager: N,
@@ -179,8 +226,17 @@ where
         // REQUIRED: Broadcaster must be created before Core, to start listen on block broadcasts.
         let broadcaster =
             Broadcaster::new(context.clone(), network_client.clone(), &signals_receivers);
+        // REQUIRED: RejectionReporter must be created before Core, to report the blocks rejected
+        // during recovery.
+        let rejection_reporter_handle =
+            RejectionReporter::start(context.clone(), network_client.clone(), &signals_receivers);
 
         let store = Arc::new(RocksDBStore::new(&context.parameters.db_path_str_unsafe()));
+        verify_store_on_startup(
+            &context,
+            &store,
+            commit_consumer.last_processed_commit_index,
+        );
         let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
 
         let block_verifier = Arc::new(SignedBlockVerifier::new(
@@ -193,6 +249,8 @@ where
 
         let commit_observer =
             CommitObserver::new(context.clone(), commit_consumer, dag_state.clone(), store);
+        let slo_report_handle =
+            SloReportTask::start(context.clone(), commit_observer.slo_tracker());
 
         let core = Core::new(
             context.clone(),
@@ -210,6 +268,15 @@ where
         let leader_timeout_handle =
             LeaderTimeoutTask::start(core_dispatcher.clone(), &signals_receivers, context.clone());
 
+        let round_prober_handle = RoundProber::start(
+            context.clone(),
+            network_client.clone(),
+            core_dispatcher.clone(),
+            dag_state.clone(),
+        );
+
+        let stall_detector_handle = StallDetector::start(context.clone(), dag_state.clone());
+
         let synchronizer = Synchronizer::start(
             network_client,
             context.clone(),
@@ -234,6 +301,10 @@ where
             transaction_client: Arc::new(tx_client),
             synchronizer,
             leader_timeout_handle,
+            slo_report_handle,
+            round_prober_handle,
+            stall_detector_handle,
+            rejection_reporter_handle,
             core_thread_handle,
             broadcaster,
             network_manager,
@@ -248,8 +319,12 @@ where
 
         self.network_manager.stop().await;
         self.broadcaster.stop();
+        self.round_prober_handle.stop().await;
+        self.stall_detector_handle.stop().await;
+        self.rejection_reporter_handle.stop().await;
         self.core_thread_handle.stop().await;
         self.leader_timeout_handle.stop().await;
+        self.slo_report_handle.stop().await;
         self.synchronizer.stop().await;
 
         self.context
@@ -262,6 +337,10 @@ where
     pub(crate) fn transaction_client(&self) -> Arc<TransactionClient> {
         self.transaction_client.clone()
     }
+
+    pub(crate) fn stall_reason(&self) -> Option<StallReason> {
+        self.stall_detector_handle.stall_reason()
+    }
 }
 
 /// Authority's network interface.
@@ -273,9 +352,8 @@ pub(crate) struct AuthorityService<C: CoreThreadDispatcher> {
     dag_state: Arc<RwLock<DagState>>,
 }
 
-#[async_trait]
-impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
-    async fn handle_send_block(
+impl<C: CoreThreadDispatcher> AuthorityService<C> {
+    async fn send_block(
         &self,
         peer: AuthorityIndex,
         serialized_block: Bytes,
@@ -292,6 +370,7 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
                 .invalid_blocks
                 .with_label_values(&[&peer.to_string(), "send_block"])
                 .inc();
+            self.dag_state.write().record_invalid_block(peer);
             let e = ConsensusError::UnexpectedAuthority(signed_block.author(), peer);
             info!("Block with wrong authority from {}: {}", peer, e);
             return Err(e);
@@ -305,6 +384,7 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
                 .invalid_blocks
                 .with_label_values(&[&peer.to_string(), "send_block"])
                 .inc();
+            self.dag_state.write().record_invalid_block(peer);
             info!("Invalid block from {}: {}", peer, e);
             return Err(e);
         }
@@ -317,10 +397,17 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
                 .saturating_sub(timestamp_utc_ms()),
         );
         if forward_time_drift > self.context.parameters.max_forward_time_drift {
-            return Err(ConsensusError::BlockTooFarInFuture {
+            let e = ConsensusError::BlockTooFarInFuture {
                 block_timestamp: verified_block.timestamp_ms(),
                 forward_time_drift,
-            });
+            };
+            self.dag_state
+                .write()
+                .record_event(ConsensusEvent::BlockRejected {
+                    block: verified_block.reference(),
+                    reason: e.to_string(),
+                });
+            return Err(e);
         }
 
         // Wait until the block's timestamp is current.
@@ -347,6 +434,7 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
                 .fetch_blocks(missing_ancestors, peer)
                 .await
             {
+                self.context.metrics.node_metrics.record_error(&err, None);
                 warn!("Errored while trying to fetch missing ancestors via synchronizer: {err}");
             }
         }
@@ -354,7 +442,7 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
         Ok(())
     }
 
-    async fn handle_fetch_blocks(
+    async fn fetch_blocks(
         &self,
         peer: AuthorityIndex,
         block_refs: Vec<BlockRef>,
@@ -390,11 +478,158 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
 
         Ok(result)
     }
+
+    async fn report_rejected_blocks(
+        &self,
+        peer: AuthorityIndex,
+        rejections: Vec<(Bytes, RejectionReason)>,
+    ) -> ConsensusResult<()> {
+        if rejections.len() > MAX_REJECTIONS_PER_REPORT {
+            return Err(ConsensusError::TooManyRejectedBlocksReported(peer));
+        }
+
+        for (serialized_block, reason) in rejections {
+            let signed_block: SignedBlock =
+                bcs::from_bytes(&serialized_block).map_err(ConsensusError::MalformedBlock)?;
+
+            // Only blocks signed by their authors prove who produced the rejected blocks.
+            if let Err(e) = self.block_verifier.verify(&signed_block) {
+                self.context
+                    .metrics
+                    .node_metrics
+                    .invalid_blocks
+                    .with_label_values(&[&peer.to_string(), "report_rejected_blocks"])
+                    .inc();
+                self.dag_state.write().record_invalid_block(peer);
+                info!("Invalid rejected block reported by {}: {}", peer, e);
+                return Err(e);
+            }
+            let block = VerifiedBlock::new_verified(signed_block, serialized_block);
+
+            if block.author() == self.context.own_index {
+                error!(
+                    "Own block {} has been rejected by {}: {}",
+                    block.reference(),
+                    peer,
+                    reason
+                );
+                continue;
+            }
+
+            // Repeated reports of the same invalid block by a peer are ignored.
+            if self
+                .dag_state
+                .read()
+                .is_reported_invalid_block(peer, &block.reference())
+            {
+                debug!(
+                    "Invalid block {} has already been reported by {}",
+                    block.reference(),
+                    peer
+                );
+                continue;
+            }
+
+            // The rejection can only be checked once all the ancestors of the block are accepted.
+            let ancestors = self.dag_state.read().get_blocks(block.ancestors());
+            let Some(ancestors) = ancestors.into_iter().collect::<Option<Vec<_>>>() else {
+                debug!(
+                    "Cannot check block {} rejected by {} without its ancestors: {}",
+                    block.reference(),
+                    peer,
+                    reason
+                );
+                continue;
+            };
+            match self.block_verifier.check_ancestors(&block, &ancestors) {
+                Err(e) => {
+                    // The block is only counted against its author on its first report.
+                    if self
+                        .dag_state
+                        .write()
+                        .record_reported_invalid_block(peer, block.reference())
+                    {
+                        self.context
+                            .metrics
+                            .node_metrics
+                            .invalid_blocks
+                            .with_label_values(&[
+                                &block.author().to_string(),
+                                "report_rejected_blocks",
+                            ])
+                            .inc();
+                    }
+                    info!(
+                        "Block {} rejected by {} is invalid: {}",
+                        block.reference(),
+                        peer,
+                        e
+                    );
+                }
+                Ok(()) => {
+                    warn!(
+                        "Block {} rejected by {} is valid, but reported as: {}",
+                        block.reference(),
+                        peer,
+                        reason
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
+    async fn handle_send_block(&self, peer: AuthorityIndex, block: Bytes) -> ConsensusResult<()> {
+        self.send_block(peer, block).await.tap_err(|e| {
+            self.context
+                .metrics
+                .node_metrics
+                .record_error(e, Some(peer))
+        })
+    }
+
+    async fn handle_fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        self.fetch_blocks(peer, block_refs).await.tap_err(|e| {
+            self.context
+                .metrics
+                .node_metrics
+                .record_error(e, Some(peer))
+        })
+    }
+
+    async fn handle_get_latest_rounds(&self, _peer: AuthorityIndex) -> ConsensusResult<Vec<Round>> {
+        Ok(self.dag_state.read().last_accepted_rounds())
+    }
+
+    async fn handle_report_rejected_blocks(
+        &self,
+        peer: AuthorityIndex,
+        rejections: Vec<(Bytes, RejectionReason)>,
+    ) -> ConsensusResult<()> {
+        self.report_rejected_blocks(peer, rejections)
+            .await
+            .tap_err(|e| {
+                self.context
+                    .metrics
+                    .node_metrics
+                    .record_error(e, Some(peer))
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::BTreeSet, sync::Arc};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        sync::Arc,
+    };
 
     use async_trait::async_trait;
     use consensus_config::{local_committee_and_keys, Parameters};
@@ -403,16 +638,21 @@ mod tests {
     use rstest::rstest;
     use sui_protocol_config::ProtocolConfig;
     use tempfile::TempDir;
-    use tokio::{sync::mpsc::unbounded_channel, time::sleep};
+    use tokio::{
+        sync::mpsc::{unbounded_channel, UnboundedReceiver},
+        time::sleep,
+    };
 
     use super::*;
     use crate::{
         authority_node::AuthorityService,
-        block::{timestamp_utc_ms, BlockRef, Round, TestBlock, VerifiedBlock},
+        block::{timestamp_utc_ms, BlockDigest, BlockRef, Round, TestBlock, VerifiedBlock},
+        block_manager::BlockRejection,
         block_verifier::NoopBlockVerifier,
+        commit::CommittedSubDag,
         context::Context,
         core_thread::{CoreError, CoreThreadDispatcher},
-        network::NetworkClient,
+        network::{fault_injection::DelayDistribution, NetworkClient},
         storage::mem_store::MemStore,
         transaction::NoopTransactionVerifier,
     };
@@ -448,7 +688,11 @@ mod tests {
             unimplemented!()
         }
 
-        async fn get_missing_blocks(&self) -> Result<BTreeSet<BlockRef>, CoreError> {
+        async fn get_missing_blocks(&self) -> Result<BTreeMap<BlockRef, usize>, CoreError> {
+            unimplemented!()
+        }
+
+        async fn set_propagation_delay(&self, _delay: Round) -> Result<(), CoreError> {
             unimplemented!()
         }
     }
@@ -475,12 +719,30 @@ mod tests {
         ) -> ConsensusResult<Vec<Bytes>> {
             unimplemented!("Unimplemented")
         }
+
+        async fn get_latest_rounds(
+            &self,
+            _peer: AuthorityIndex,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<Round>> {
+            unimplemented!("Unimplemented")
+        }
+
+        async fn report_rejected_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _rejections: &[BlockRejection],
+            _timeout: Duration,
+        ) -> ConsensusResult<()> {
+            unimplemented!("Unimplemented")
+        }
     }
 
     #[rstest]
     #[tokio::test]
     async fn test_authority_start_and_stop(
-        #[values(NetworkType::Anemo, NetworkType::Tonic)] network_type: NetworkType,
+        #[values(NetworkType::Anemo, NetworkType::Tonic, NetworkType::Quic)]
+        network_type: NetworkType,
     ) {
         let (committee, keypairs) = local_committee_and_keys(0, vec![1]);
         let registry = Registry::new();
@@ -488,6 +750,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let parameters = Parameters {
             db_path: Some(temp_dir.into_path()),
+            network_type,
             ..Default::default()
         };
         let txn_verifier = NoopTransactionVerifier {};
@@ -500,7 +763,6 @@ mod tests {
         let commit_consumer = CommitConsumer::new(sender, 0, 0);
 
         let authority = ConsensusAuthority::start(
-            network_type,
             own_index,
             committee,
             parameters,
@@ -570,11 +832,124 @@ mod tests {
         assert_eq!(blocks[0], input_block);
     }
 
+    struct RejectingBlockVerifier;
+
+    impl BlockVerifier for RejectingBlockVerifier {
+        fn verify(&self, _block: &SignedBlock) -> ConsensusResult<()> {
+            Ok(())
+        }
+
+        fn check_ancestors(
+            &self,
+            block: &VerifiedBlock,
+            _ancestors: &[VerifiedBlock],
+        ) -> ConsensusResult<()> {
+            Err(ConsensusError::InvalidBlockTimestamp {
+                max_timestamp_ms: u64::MAX,
+                block_timestamp_ms: block.timestamp_ms(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_report_rejected_blocks() {
+        let (context, _keys) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let block_verifier = Arc::new(RejectingBlockVerifier);
+        let core_dispatcher = Arc::new(FakeCoreThreadDispatcher::new());
+        let network_client = Arc::new(FakeNetworkClient::default());
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store)));
+        let synchronizer = Synchronizer::start(
+            network_client,
+            context.clone(),
+            core_dispatcher.clone(),
+            block_verifier.clone(),
+        );
+        let authority_service = AuthorityService {
+            context: context.clone(),
+            block_verifier,
+            core_dispatcher,
+            synchronizer,
+            dag_state: dag_state.clone(),
+        };
+
+        // The rejection of the block of authority 1 is confirmed, as its ancestors are available.
+        // The block of authority 2 cannot be checked without its ancestor, and the own block is
+        // not counted against the own authority.
+        let missing_ancestor =
+            BlockRef::new(1, AuthorityIndex::new_for_test(3), BlockDigest::default());
+        let rejections: Vec<_> = [
+            TestBlock::new(1, 1).build(),
+            TestBlock::new(2, 2)
+                .set_ancestors(vec![missing_ancestor])
+                .build(),
+            TestBlock::new(1, 0).build(),
+        ]
+        .into_iter()
+        .map(|block| {
+            let block = VerifiedBlock::new_for_test(block);
+            let reason = RejectionReason::InvalidAncestors("invalid".to_string());
+            (block.serialized().clone(), reason)
+        })
+        .collect();
+        authority_service
+            .handle_report_rejected_blocks(AuthorityIndex::new_for_test(3), rejections.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            dag_state.read().invalid_blocks_by_authority(),
+            &[0, 1, 0, 0]
+        );
+
+        // The same invalid block is only counted once, whether it is reported again by the same
+        // peer or by another one.
+        authority_service
+            .handle_report_rejected_blocks(AuthorityIndex::new_for_test(3), rejections.clone())
+            .await
+            .unwrap();
+        authority_service
+            .handle_report_rejected_blocks(AuthorityIndex::new_for_test(2), rejections.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            dag_state.read().invalid_blocks_by_authority(),
+            &[0, 1, 0, 0]
+        );
+
+        // Reports with too many rejections are refused without being checked.
+        let too_many_rejections = (1..=MAX_REJECTIONS_PER_REPORT as u32 + 1)
+            .map(|round| {
+                let block = VerifiedBlock::new_for_test(TestBlock::new(round, 2).build());
+                let reason = RejectionReason::InvalidAncestors("invalid".to_string());
+                (block.serialized().clone(), reason)
+            })
+            .collect();
+        let result = authority_service
+            .handle_report_rejected_blocks(AuthorityIndex::new_for_test(3), too_many_rejections)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ConsensusError::TooManyRejectedBlocksReported(peer))
+                if peer == AuthorityIndex::new_for_test(3)
+        ));
+        assert_eq!(
+            dag_state.read().invalid_blocks_by_authority(),
+            &[0, 1, 0, 0]
+        );
+    }
+
     // TODO: build AuthorityFixture.
-    #[rstest]
-    #[tokio::test(flavor = "current_thread", start_paused = true)]
-    async fn test_authority_committee(
-        #[values(NetworkType::Anemo, NetworkType::Tonic)] network_type: NetworkType,
+    /// Starts an authority for each member of a committee of 4, and returns them with the
+    /// receivers of their commits.
+    async fn start_committee(
+        network_type: NetworkType,
+    ) -> (
+        Vec<ConsensusAuthority>,
+        Vec<UnboundedReceiver<CommittedSubDag>>,
     ) {
         let (committee, keypairs) = local_committee_and_keys(0, vec![1, 1, 1, 1]);
         let mut output_receivers = vec![];
@@ -585,6 +960,7 @@ mod tests {
             let temp_dir = TempDir::new().unwrap();
             let parameters = Parameters {
                 db_path: Some(temp_dir.into_path()),
+                network_type,
                 ..Default::default()
             };
             let txn_verifier = NoopTransactionVerifier {};
@@ -597,7 +973,6 @@ mod tests {
             output_receivers.push(receiver);
 
             let authority = ConsensusAuthority::start(
-                network_type,
                 index,
                 committee.clone(),
                 parameters,
@@ -611,6 +986,41 @@ mod tests {
             .await;
             authorities.push(authority);
         }
+        (authorities, output_receivers)
+    }
+
+    /// Waits until all of `transactions` are committed, failing if no commit arrives within
+    /// `timeout`.
+    async fn expect_committed(
+        receiver: &mut UnboundedReceiver<CommittedSubDag>,
+        transactions: &BTreeSet<Vec<u8>>,
+        timeout: Duration,
+    ) {
+        let mut expected_transactions = transactions.clone();
+        while !expected_transactions.is_empty() {
+            let committed_subdag = tokio::time::timeout(timeout, receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            for b in committed_subdag.blocks {
+                for txn in b.transactions().iter().map(|t| t.data().to_vec()) {
+                    assert!(
+                        expected_transactions.remove(&txn),
+                        "Transaction not submitted or already seen: {:?}",
+                        txn
+                    );
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_authority_committee(
+        #[values(NetworkType::Anemo, NetworkType::Tonic, NetworkType::Quic)]
+        network_type: NetworkType,
+    ) {
+        let (authorities, output_receivers) = start_committee(network_type).await;
 
         const NUM_TRANSACTIONS: u8 = 15;
         let mut submitted_transactions = BTreeSet::<Vec<u8>>::new();
@@ -625,28 +1035,75 @@ mod tests {
         }
 
         for mut receiver in output_receivers {
-            let mut expected_transactions = submitted_transactions.clone();
-            loop {
-                let committed_subdag =
-                    tokio::time::timeout(Duration::from_secs(1), receiver.recv())
-                        .await
-                        .unwrap()
-                        .unwrap();
-                for b in committed_subdag.blocks {
-                    for txn in b.transactions().iter().map(|t| t.data().to_vec()) {
-                        assert!(
-                            expected_transactions.remove(&txn),
-                            "Transaction not submitted or already seen: {:?}",
-                            txn
-                        );
-                    }
-                }
-                if expected_transactions.is_empty() {
-                    break;
-                }
-            }
+            expect_committed(
+                &mut receiver,
+                &submitted_transactions,
+                Duration::from_secs(1),
+            )
+            .await;
+        }
+
+        for authority in authorities {
+            authority.stop().await;
+        }
+    }
+
+    #[rstest]
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_authority_committee_with_network_faults(
+        #[values(NetworkType::Anemo, NetworkType::Tonic, NetworkType::Quic)]
+        network_type: NetworkType,
+    ) {
+        let (authorities, mut output_receivers) = start_committee(network_type).await;
+
+        // Authority 3 is cut off from the others, which still form a quorum over a lossy and
+        // slow network.
+        let partitions = vec![
+            BTreeSet::from([
+                AuthorityIndex::new_for_test(0),
+                AuthorityIndex::new_for_test(1),
+                AuthorityIndex::new_for_test(2),
+            ]),
+            BTreeSet::from([AuthorityIndex::new_for_test(3)]),
+        ];
+        for authority in &authorities {
+            let fault_injector = authority.fault_injector();
+            fault_injector.set_drop_rate(0.2);
+            fault_injector.set_delay(DelayDistribution::Uniform {
+                min: Duration::from_millis(10),
+                max: Duration::from_millis(100),
+            });
+            fault_injector.partition(partitions.clone());
+        }
+
+        const NUM_TRANSACTIONS: u8 = 15;
+        let mut submitted_transactions = BTreeSet::<Vec<u8>>::new();
+        for i in 0..NUM_TRANSACTIONS {
+            let txn = vec![i; 16];
+            submitted_transactions.insert(txn.clone());
+            authorities[i as usize % 3]
+                .transaction_client()
+                .submit(txn)
+                .await
+                .unwrap();
         }
 
+        // Commits progress among the connected authorities despite the faults.
+        for receiver in &mut output_receivers[..3] {
+            expect_committed(receiver, &submitted_transactions, Duration::from_secs(10)).await;
+        }
+
+        // Once the network heals, the partitioned authority catches up.
+        for authority in &authorities {
+            authority.fault_injector().reset();
+        }
+        expect_committed(
+            &mut output_receivers[3],
+            &submitted_transactions,
+            Duration::from_secs(10),
+        )
+        .await;
+
         for authority in authorities {
             authority.stop().await;
         }
@@ -3,11 +3,13 @@
 
 use std::{
     collections::{BTreeMap, BTreeSet},
-    iter,
+    fmt, iter,
     sync::Arc,
 };
 
+use consensus_config::AuthorityIndex;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use tracing::warn;
 
 use crate::{
@@ -15,27 +17,65 @@ use crate::{
     block_verifier::BlockVerifier,
     context::Context,
     dag_state::DagState,
+    event_log::ConsensusEvent,
 };
 
 struct SuspendedBlock {
     block: VerifiedBlock,
     missing_ancestors: BTreeSet<BlockRef>,
+    /// The order in which the block was suspended, to evict the oldest suspended blocks first.
+    sequence: u64,
 }
 
 impl SuspendedBlock {
-    fn new(block: VerifiedBlock, missing_ancestors: BTreeSet<BlockRef>) -> Self {
+    fn new(block: VerifiedBlock, missing_ancestors: BTreeSet<BlockRef>, sequence: u64) -> Self {
         Self {
             block,
             missing_ancestors,
+            sequence,
         }
     }
 }
 
+/// The reason why the block manager rejected a block that passed verification.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum RejectionReason {
+    /// The block is inconsistent with its ancestors, e.g. its timestamp is lower than theirs.
+    InvalidAncestors(String),
+    /// The block includes an ancestor that has been rejected.
+    RejectedAncestor(BlockRef),
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::InvalidAncestors(e) => write!(f, "{e}"),
+            RejectionReason::RejectedAncestor(ancestor) => {
+                write!(f, "ancestor {ancestor} is rejected")
+            }
+        }
+    }
+}
+
+/// A block rejected by the block manager, to be reported to peers. As the block is signed by its
+/// author, peers can verify who produced it, and the reason of the rejection if they have its
+/// ancestors.
+#[derive(Clone, Debug)]
+pub(crate) struct BlockRejection {
+    pub block: VerifiedBlock,
+    pub reason: RejectionReason,
+}
+
 /// Block manager suspends incoming blocks until they are connected to the existing graph,
 /// returning newly connected blocks.
-/// TODO: As it is possible to have Byzantine validators who produce Blocks without valid causal
-/// history we need to make sure that BlockManager takes care of that and avoid OOM (Out Of Memory)
-/// situations.
+///
+/// As Byzantine validators can produce blocks without valid causal history, which would stay
+/// suspended forever, the suspended blocks are bounded in number and size, overall and per
+/// authority, by evicting the blocks suspended the longest ago. An evicted block that other
+/// suspended blocks are waiting for is reported as missing again, to be fetched once there is room.
+///
+/// If `persist_suspended_blocks` is set, suspended blocks are also persisted to storage, and
+/// recovered after a restart so that they do not need to be fetched again from peers.
 pub(crate) struct BlockManager {
     context: Arc<Context>,
     dag_state: Arc<RwLock<DagState>>,
@@ -52,6 +92,17 @@ pub(crate) struct BlockManager {
     /// Keeps all the blocks that we actually miss and haven't fetched them yet. That set will basically contain all the
     /// keys from the `missing_ancestors` minus any keys that exist in `suspended_blocks`.
     missing_blocks: BTreeSet<BlockRef>,
+    /// The suspended blocks of each authority, by the order in which they were suspended.
+    suspended_blocks_by_authority: Vec<BTreeMap<u64, BlockRef>>,
+    /// The total size in bytes of the suspended blocks.
+    suspended_blocks_bytes: usize,
+    /// The order of the next suspended block.
+    next_suspended_sequence: u64,
+    /// Changes to the suspended blocks that are yet to be persisted: the blocks that have been
+    /// suspended, or `None` for the blocks that are no longer suspended.
+    suspended_blocks_to_write: BTreeMap<BlockRef, Option<VerifiedBlock>>,
+    /// The blocks rejected since they were last taken, to be reported to peers.
+    rejected_blocks: Vec<BlockRejection>,
 }
 
 impl BlockManager {
@@ -61,13 +112,34 @@ impl BlockManager {
         block_verifier: Arc<dyn BlockVerifier>,
     ) -> Self {
         Self {
-            context,
             dag_state,
             block_verifier,
             suspended_blocks: BTreeMap::new(),
             missing_ancestors: BTreeMap::new(),
             missing_blocks: BTreeSet::new(),
+            suspended_blocks_by_authority: vec![BTreeMap::new(); context.committee.size()],
+            suspended_blocks_bytes: 0,
+            next_suspended_sequence: 0,
+            suspended_blocks_to_write: BTreeMap::new(),
+            rejected_blocks: vec![],
+            context,
+        }
+    }
+
+    /// Reads the blocks that were suspended before a restart, if suspended blocks are persisted.
+    /// They are to be accepted again via `try_accept_blocks`, which suspends again the ones that
+    /// are still missing ancestors. All of them are removed from storage otherwise, so that blocks
+    /// accepted or evicted in the meantime do not linger.
+    pub(crate) fn recover_suspended_blocks(&mut self) -> Vec<VerifiedBlock> {
+        if !self.context.parameters.persist_suspended_blocks {
+            return vec![];
+        }
+        let blocks = self.dag_state.read().read_suspended_blocks();
+        for block in &blocks {
+            self.suspended_blocks_to_write
+                .insert(block.reference(), None);
         }
+        blocks
     }
 
     /// Tries to accept the provided blocks assuming that all their causal history exists. The method
@@ -89,7 +161,8 @@ impl BlockManager {
 
                 // Try to verify the block with ancestor blocks.
                 let mut blocks_to_accept: BTreeMap<BlockRef, VerifiedBlock> = BTreeMap::new();
-                let mut blocks_to_reject: BTreeMap<BlockRef, VerifiedBlock> = BTreeMap::new();
+                let mut blocks_to_reject: BTreeMap<BlockRef, (VerifiedBlock, RejectionReason)> =
+                    BTreeMap::new();
                 {
                     'block: for b in iter::once(block).chain(unsuspended_blocks) {
                         let ancestors = self.dag_state.read().get_blocks(b.ancestors());
@@ -112,31 +185,44 @@ impl BlockManager {
                             }
                             // If an ancestor is already rejected, reject this block as well.
                             if blocks_to_reject.contains_key(included) {
-                                blocks_to_reject.insert(b.reference(), b);
+                                let reason = RejectionReason::RejectedAncestor(*included);
+                                blocks_to_reject.insert(b.reference(), (b, reason));
                                 continue 'block;
                             }
                             panic!("Unsuspended block {:?} has a missing ancestor! Ancestor not found in DagState: {:?}", b, included);
                         }
                         if let Err(e) = self.block_verifier.check_ancestors(&b, &ancestor_blocks) {
                             warn!("Block {:?} failed to verify ancestors: {}", b, e);
-                            blocks_to_reject.insert(b.reference(), b);
+                            self.context
+                                .metrics
+                                .node_metrics
+                                .record_error(&e, Some(b.author()));
+                            let reason = RejectionReason::InvalidAncestors(e.to_string());
+                            blocks_to_reject.insert(b.reference(), (b, reason));
                         } else {
                             blocks_to_accept.insert(b.reference(), b);
                         }
                     }
                 }
-                for (block_ref, block) in blocks_to_reject {
+                for (block_ref, (block, reason)) in blocks_to_reject {
                     self.context
                         .metrics
                         .node_metrics
                         .invalid_blocks
                         .with_label_values(&[&block_ref.author.to_string(), "accept_block"])
                         .inc();
-                    warn!("Invalid block {:?} is rejected", block);
+                    warn!("Invalid block {:?} is rejected: {}", block, reason);
+                    {
+                        let mut dag_state = self.dag_state.write();
+                        dag_state.record_event(ConsensusEvent::BlockRejected {
+                            block: block_ref,
+                            reason: reason.to_string(),
+                        });
+                        dag_state.record_invalid_block(block_ref.author);
+                    }
+                    self.rejected_blocks.push(BlockRejection { block, reason });
                 }
 
-                // TODO: report blocks_to_reject to peers.
-
                 // Insert the accepted blocks into DAG state so future blocks including them as
                 // ancestors do not get suspended.
                 let blocks_to_accept: Vec<_> = blocks_to_accept.into_values().collect();
@@ -148,6 +234,8 @@ impl BlockManager {
             }
         }
 
+        self.write_suspended_blocks();
+
         // Newly missed blocks
         // TODO: make sure that the computation here is bounded either in the byzantine or node fall
         // back scenario.
@@ -167,6 +255,11 @@ impl BlockManager {
         (accepted_blocks, missing_blocks_after)
     }
 
+    /// Takes the blocks rejected since the last call, to be reported to peers.
+    pub(crate) fn take_rejected_blocks(&mut self) -> Vec<BlockRejection> {
+        std::mem::take(&mut self.rejected_blocks)
+    }
+
     /// Tries to accept the provided block. To accept a block its ancestors must have been already successfully accepted. If
     /// block is accepted then Some result is returned. None is returned when either the block is suspended or the block
     /// has been already accepted before.
@@ -204,6 +297,7 @@ impl BlockManager {
                 }
             }
         }
+        drop(dag_state);
 
         // Remove the block ref from the `missing_blocks` - if exists - since we now have received the block. The block
         // might still get suspended, but we won't report it as missing in order to not re-fetch.
@@ -222,8 +316,13 @@ impl BlockManager {
                 .suspended_blocks
                 .with_label_values(&[hostname])
                 .inc();
-            self.suspended_blocks
-                .insert(block_ref, SuspendedBlock::new(block, missing_ancestors));
+            self.dag_state
+                .write()
+                .record_event(ConsensusEvent::BlockSuspended {
+                    block: block_ref,
+                    missing_ancestors: missing_ancestors.iter().cloned().collect(),
+                });
+            self.suspend_block(block, missing_ancestors);
             return None;
         }
 
@@ -295,17 +394,164 @@ impl BlockManager {
 
         if block.missing_ancestors.is_empty() {
             // we have no missing dependency, so we unsuspend the block and return it
-            return self.suspended_blocks.remove(block_ref);
+            return self.remove_suspended_block(block_ref);
         }
         None
     }
 
+    /// Suspends the block until its `missing_ancestors` are accepted, evicting suspended blocks if
+    /// that exceeds the limits.
+    fn suspend_block(&mut self, block: VerifiedBlock, missing_ancestors: BTreeSet<BlockRef>) {
+        let block_ref = block.reference();
+        let sequence = self.next_suspended_sequence;
+        self.next_suspended_sequence += 1;
+        self.suspended_blocks_by_authority[block_ref.author].insert(sequence, block_ref);
+        self.suspended_blocks_bytes += block.serialized().len();
+        if self.context.parameters.persist_suspended_blocks {
+            self.suspended_blocks_to_write
+                .insert(block_ref, Some(block.clone()));
+        }
+        self.suspended_blocks.insert(
+            block_ref,
+            SuspendedBlock::new(block, missing_ancestors, sequence),
+        );
+        self.evict_suspended_blocks(block_ref.author);
+        self.report_suspended_blocks_bytes();
+    }
+
+    fn remove_suspended_block(&mut self, block_ref: &BlockRef) -> Option<SuspendedBlock> {
+        let block = self.suspended_blocks.remove(block_ref)?;
+        self.suspended_blocks_by_authority[block_ref.author].remove(&block.sequence);
+        self.suspended_blocks_bytes -= block.block.serialized().len();
+        if self.context.parameters.persist_suspended_blocks {
+            self.suspended_blocks_to_write.insert(*block_ref, None);
+        }
+        self.report_suspended_blocks_bytes();
+        Some(block)
+    }
+
+    /// Persists the changes to the suspended blocks since the last write.
+    fn write_suspended_blocks(&mut self) {
+        let mut suspended_blocks = vec![];
+        let mut unsuspended_blocks = vec![];
+        for (block_ref, block) in std::mem::take(&mut self.suspended_blocks_to_write) {
+            match block {
+                Some(block) => suspended_blocks.push(block),
+                None => unsuspended_blocks.push(block_ref),
+            }
+        }
+        self.dag_state
+            .read()
+            .write_suspended_blocks(suspended_blocks, unsuspended_blocks);
+    }
+
+    /// Evicts the oldest suspended blocks of `author` while it exceeds its quota, and then the oldest
+    /// suspended blocks overall while the limits on all suspended blocks are exceeded.
+    fn evict_suspended_blocks(&mut self, author: AuthorityIndex) {
+        let parameters = &self.context.parameters;
+        let max_per_authority = parameters.max_suspended_blocks_per_authority;
+        let max_blocks = parameters.max_suspended_blocks;
+        let max_bytes = parameters.max_suspended_blocks_bytes;
+
+        while self.suspended_blocks_by_authority[author].len() > max_per_authority {
+            let (_, &block_ref) = self.suspended_blocks_by_authority[author]
+                .first_key_value()
+                .expect("Authority should have suspended blocks");
+            self.evict_suspended_block(block_ref, "authority_quota");
+        }
+
+        loop {
+            let reason = if self.suspended_blocks.len() > max_blocks {
+                "max_blocks"
+            } else if self.suspended_blocks_bytes > max_bytes {
+                "max_bytes"
+            } else {
+                break;
+            };
+            let Some((_, &block_ref)) = self
+                .suspended_blocks_by_authority
+                .iter()
+                .filter_map(|blocks| blocks.first_key_value())
+                .min_by_key(|(sequence, _)| **sequence)
+            else {
+                break;
+            };
+            self.evict_suspended_block(block_ref, reason);
+        }
+    }
+
+    /// Evicts a suspended block, which no longer waits for its missing ancestors.
+    fn evict_suspended_block(&mut self, block_ref: BlockRef, reason: &str) {
+        let Some(block) = self.remove_suspended_block(&block_ref) else {
+            return;
+        };
+
+        // Ancestors that no other suspended block is waiting for are no longer missing.
+        for ancestor in &block.missing_ancestors {
+            if let Some(dependents) = self.missing_ancestors.get_mut(ancestor) {
+                dependents.remove(&block_ref);
+                if dependents.is_empty() {
+                    self.missing_ancestors.remove(ancestor);
+                    self.missing_blocks.remove(ancestor);
+                }
+            }
+        }
+
+        // If other suspended blocks are waiting for the evicted block, it is missing again.
+        if self.missing_ancestors.contains_key(&block_ref) {
+            self.missing_blocks.insert(block_ref);
+        }
+
+        let hostname = self
+            .context
+            .committee
+            .authority(block_ref.author)
+            .hostname
+            .as_str();
+        self.context
+            .metrics
+            .node_metrics
+            .evicted_suspended_blocks
+            .with_label_values(&[hostname, reason])
+            .inc();
+        self.dag_state
+            .write()
+            .record_event(ConsensusEvent::BlockEvicted {
+                block: block_ref,
+                reason: reason.to_string(),
+            });
+    }
+
+    fn report_suspended_blocks_bytes(&self) {
+        self.context
+            .metrics
+            .node_metrics
+            .suspended_blocks_bytes
+            .set(self.suspended_blocks_bytes as i64);
+    }
+
     /// Returns all the blocks that are currently missing and needed in order to accept suspended
     /// blocks.
     pub(crate) fn missing_blocks(&self) -> BTreeSet<BlockRef> {
         self.missing_blocks.clone()
     }
 
+    /// Returns all the blocks that are currently missing, along with the number of suspended blocks
+    /// that directly reference each of them as an ancestor, i.e. that are waiting for it to be
+    /// fetched.
+    pub(crate) fn missing_blocks_with_dependents(&self) -> BTreeMap<BlockRef, usize> {
+        self.missing_blocks
+            .iter()
+            .map(|block_ref| {
+                let dependents = self
+                    .missing_ancestors
+                    .get(block_ref)
+                    .map_or(0, |dependents| dependents.len());
+                (*block_ref, dependents)
+            })
+            .collect()
+    }
+
     /// Returns all the suspended blocks whose causal history we miss hence we can't accept them yet.
     #[cfg(test)]
     pub(crate) fn suspended_blocks(&self) -> Vec<BlockRef> {
@@ -315,8 +561,12 @@ impl BlockManager {
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::BTreeSet, sync::Arc};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        sync::Arc,
+    };
 
+    use consensus_config::Parameters;
     use parking_lot::RwLock;
     use rand::{prelude::StdRng, seq::SliceRandom, SeedableRng};
 
@@ -327,7 +577,7 @@ mod tests {
         context::Context,
         dag_state::DagState,
         error::{ConsensusError, ConsensusResult},
-        storage::mem_store::MemStore,
+        storage::{mem_store::MemStore, Store},
     };
 
     #[test]
@@ -365,6 +615,15 @@ mod tests {
         // ancestors of the first element suffices.
         assert_eq!(block_manager.missing_blocks(), missing_block_refs);
 
+        // AND each of the missing blocks is waited for by all the round 2 blocks
+        assert_eq!(
+            block_manager.missing_blocks_with_dependents(),
+            missing_block_refs
+                .iter()
+                .map(|block_ref| (*block_ref, round_2_blocks.len()))
+                .collect::<BTreeMap<_, _>>()
+        );
+
         // AND suspended blocks should return the round_2_blocks
         assert_eq!(
             block_manager.suspended_blocks(),
@@ -375,6 +634,150 @@ mod tests {
         );
     }
 
+    #[test]
+    fn evict_suspended_blocks_beyond_limits() {
+        // GIVEN a block manager allowing 2 suspended blocks per authority
+        let (context, _key_pairs) = Context::new_for_test(4);
+        let context = Arc::new(context.with_parameters(Parameters {
+            max_suspended_blocks_per_authority: 2,
+            ..Default::default()
+        }));
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let mut block_manager =
+            BlockManager::new(context.clone(), dag_state, Arc::new(NoopBlockVerifier));
+
+        // WHEN accepting the blocks of rounds 2 to 4, which are all suspended
+        let all_blocks = dag(context.clone(), 4);
+        let blocks_of_round = |round: Round| {
+            all_blocks
+                .iter()
+                .filter(|block| block.round() == round)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let refs_of_round = |round: Round| {
+            blocks_of_round(round)
+                .iter()
+                .map(|block| block.reference())
+                .collect::<BTreeSet<_>>()
+        };
+        let blocks = all_blocks
+            .iter()
+            .filter(|block| block.round() >= 2)
+            .cloned()
+            .collect::<Vec<_>>();
+        let (accepted_blocks, _missing) = block_manager.try_accept_blocks(blocks);
+        assert!(accepted_blocks.is_empty());
+
+        // THEN the oldest suspended block of each authority, of round 2, is evicted
+        let suspended_blocks = block_manager.suspended_blocks();
+        assert_eq!(
+            suspended_blocks.into_iter().collect::<BTreeSet<_>>(),
+            refs_of_round(3).union(&refs_of_round(4)).cloned().collect()
+        );
+        for (_, authority) in context.committee.authorities() {
+            assert_eq!(
+                context
+                    .metrics
+                    .node_metrics
+                    .evicted_suspended_blocks
+                    .with_label_values(&[authority.hostname.as_str(), "authority_quota"])
+                    .get(),
+                1
+            );
+        }
+
+        // AND the evicted blocks are missing again, as the round 3 blocks are waiting for them,
+        // unlike the round 1 blocks that only they were waiting for
+        assert_eq!(block_manager.missing_blocks(), refs_of_round(2));
+
+        // WHEN the suspended blocks exceed the overall limit
+        let context = Arc::new(context.as_ref().clone().with_parameters(Parameters {
+            max_suspended_blocks: 4,
+            ..Default::default()
+        }));
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let mut block_manager =
+            BlockManager::new(context.clone(), dag_state, Arc::new(NoopBlockVerifier));
+        let mut blocks = blocks_of_round(2);
+        blocks.extend(blocks_of_round(3));
+        block_manager.try_accept_blocks(blocks);
+
+        // THEN the oldest suspended blocks, of round 2, are evicted
+        assert_eq!(
+            block_manager
+                .suspended_blocks()
+                .into_iter()
+                .collect::<BTreeSet<_>>(),
+            refs_of_round(3)
+        );
+        assert_eq!(block_manager.missing_blocks(), refs_of_round(2));
+    }
+
+    #[test]
+    fn recover_persisted_suspended_blocks() {
+        // GIVEN a block manager persisting suspended blocks
+        let (context, _key_pairs) = Context::new_for_test(4);
+        let context = Arc::new(context.with_parameters(Parameters {
+            persist_suspended_blocks: true,
+            ..Default::default()
+        }));
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let mut block_manager =
+            BlockManager::new(context.clone(), dag_state, Arc::new(NoopBlockVerifier));
+
+        // AND the blocks of rounds 3 and 4 suspended, as the blocks of round 2 are missing
+        let all_blocks = dag(context.clone(), 4);
+        let (suspended, rest): (Vec<_>, Vec<_>) =
+            all_blocks.into_iter().partition(|block| block.round() >= 3);
+        block_manager.try_accept_blocks(suspended.clone());
+        let suspended_refs = suspended
+            .iter()
+            .map(|block| block.reference())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(
+            store
+                .read_suspended_blocks()
+                .unwrap()
+                .iter()
+                .map(|block| block.reference())
+                .collect::<BTreeSet<_>>(),
+            suspended_refs
+        );
+
+        // WHEN restarting with the same store
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let mut block_manager =
+            BlockManager::new(context.clone(), dag_state, Arc::new(NoopBlockVerifier));
+        let recovered = block_manager.recover_suspended_blocks();
+        let (accepted_blocks, missing) = block_manager.try_accept_blocks(recovered);
+
+        // THEN the suspended blocks are recovered, and only the blocks of round 2 are missing
+        assert!(accepted_blocks.is_empty());
+        assert_eq!(
+            block_manager
+                .suspended_blocks()
+                .into_iter()
+                .collect::<BTreeSet<_>>(),
+            suspended_refs
+        );
+        let round_2_refs = rest
+            .iter()
+            .filter(|block| block.round() == 2)
+            .map(|block| block.reference())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(missing, round_2_refs);
+
+        // AND once the missing blocks are accepted, no suspended block is left in storage
+        let (accepted_blocks, _missing) = block_manager.try_accept_blocks(rest);
+        assert_eq!(accepted_blocks.len(), 16);
+        assert!(block_manager.suspended_blocks().is_empty());
+        assert!(store.read_suspended_blocks().unwrap().is_empty());
+    }
+
     #[test]
     fn try_accept_block_returns_missing_blocks_once() {
         let (context, _key_pairs) = Context::new_for_test(4);
@@ -597,5 +1000,24 @@ mod tests {
 
         // Other blocks should be rejected and there should be no remaining suspended block.
         assert!(block_manager.suspended_blocks().is_empty());
+
+        // The rejected blocks are taken once, with the reason of their rejection.
+        let rejected_blocks = block_manager.take_rejected_blocks();
+        assert_eq!(rejected_blocks.len(), 12);
+        for rejection in &rejected_blocks {
+            match rejection.reason {
+                RejectionReason::InvalidAncestors(_) => assert_eq!(rejection.block.round(), 3),
+                RejectionReason::RejectedAncestor(ancestor) => {
+                    assert_eq!(ancestor.round + 1, rejection.block.round())
+                }
+            }
+        }
+        assert!(block_manager.take_rejected_blocks().is_empty());
+
+        // The authors of the rejected blocks are counted as having produced invalid blocks.
+        assert_eq!(
+            block_manager.dag_state.read().invalid_blocks_by_authority(),
+            &[3, 3, 3, 3]
+        );
     }
 }
@@ -157,7 +157,8 @@ impl Broadcaster {
                             rtt_estimate = rtt_estimate.mul_f64(TIMEOUT_RTT_INCREMENT_FACTOR);
                             requests.push(send_block(network_client.clone(), peer, rtt_estimate, block));
                         },
-                        Ok(Err(_)) => {
+                        Ok(Err(e)) => {
+                            context.metrics.node_metrics.record_error(&e, Some(peer));
                             requests.push(send_block(network_client.clone(), peer, rtt_estimate, block));
                         },
                     };
@@ -196,7 +197,8 @@ mod test {
 
     use super::*;
     use crate::{
-        block::{BlockRef, TestBlock},
+        block::{BlockRef, Round, TestBlock},
+        block_manager::BlockRejection,
         core::CoreSignals,
     };
 
@@ -241,6 +243,23 @@ mod test {
         ) -> ConsensusResult<Vec<Bytes>> {
             unimplemented!("Unimplemented")
         }
+
+        async fn get_latest_rounds(
+            &self,
+            _peer: AuthorityIndex,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<Round>> {
+            unimplemented!("Unimplemented")
+        }
+
+        async fn report_rejected_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _rejections: &[BlockRejection],
+            _timeout: Duration,
+        ) -> ConsensusResult<()> {
+            unimplemented!("Unimplemented")
+        }
     }
 
     #[tokio::test(flavor = "current_thread", start_paused = true)]
@@ -51,10 +51,11 @@ pub(crate) type WaveNumber = u32;
 #[enum_dispatch(CommitAPI)]
 pub(crate) enum Commit {
     V1(CommitV1),
+    V2(CommitV2),
 }
 
 impl Commit {
-    /// Create a new commit.
+    /// Create a new commit, timestamped by its leader block.
     pub(crate) fn new(
         index: CommitIndex,
         previous_digest: CommitDigest,
@@ -69,6 +70,24 @@ impl Commit {
         })
     }
 
+    /// Create a new commit with its own timestamp. Only produced when median based commit
+    /// timestamps are enabled.
+    pub(crate) fn new_v2(
+        index: CommitIndex,
+        previous_digest: CommitDigest,
+        timestamp_ms: BlockTimestampMs,
+        leader: BlockRef,
+        blocks: Vec<BlockRef>,
+    ) -> Self {
+        Commit::V2(CommitV2 {
+            index,
+            previous_digest,
+            timestamp_ms,
+            leader,
+            blocks,
+        })
+    }
+
     pub(crate) fn serialize(&self) -> Result<Bytes, bcs::Error> {
         let bytes = bcs::to_bytes(self)?;
         Ok(bytes.into())
@@ -81,6 +100,8 @@ pub(crate) trait CommitAPI {
     fn round(&self) -> Round;
     fn index(&self) -> CommitIndex;
     fn previous_digest(&self) -> CommitDigest;
+    /// Timestamp of the commit, if it is not the timestamp of its leader block.
+    fn timestamp_ms(&self) -> Option<BlockTimestampMs>;
     fn leader(&self) -> BlockRef;
     fn blocks(&self) -> &[BlockRef];
 }
@@ -114,6 +135,53 @@ impl CommitAPI for CommitV1 {
         self.previous_digest
     }
 
+    fn timestamp_ms(&self) -> Option<BlockTimestampMs> {
+        None
+    }
+
+    fn leader(&self) -> BlockRef {
+        self.leader
+    }
+
+    fn blocks(&self) -> &[BlockRef] {
+        &self.blocks
+    }
+}
+
+/// Specifies one consensus commit, along with its timestamp.
+/// Produced instead of CommitV1 when median based commit timestamps are enabled.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub(crate) struct CommitV2 {
+    /// Index of the commit.
+    index: CommitIndex,
+    /// Digest of the previous commit.
+    previous_digest: CommitDigest,
+    /// Timestamp of the commit. With median based commit timestamps, it depends on the timestamp
+    /// of the previous commit, so it cannot be derived from the committed blocks alone.
+    timestamp_ms: BlockTimestampMs,
+    /// A reference to the commit leader.
+    leader: BlockRef,
+    /// Refs to committed blocks, in the commit order.
+    blocks: Vec<BlockRef>,
+}
+
+impl CommitAPI for CommitV2 {
+    fn round(&self) -> Round {
+        self.leader.round
+    }
+
+    fn index(&self) -> CommitIndex {
+        self.index
+    }
+
+    fn previous_digest(&self) -> CommitDigest {
+        self.previous_digest
+    }
+
+    fn timestamp_ms(&self) -> Option<BlockTimestampMs> {
+        Some(self.timestamp_ms)
+    }
+
     fn leader(&self) -> BlockRef {
         self.leader
     }
@@ -151,10 +219,11 @@ impl TrustedCommit {
     pub(crate) fn new_for_test(
         index: CommitIndex,
         previous_digest: CommitDigest,
+        timestamp_ms: BlockTimestampMs,
         leader: BlockRef,
         blocks: Vec<BlockRef>,
     ) -> Self {
-        let commit = Commit::new(index, previous_digest, leader, blocks);
+        let commit = Commit::new_v2(index, previous_digest, timestamp_ms, leader, blocks);
         let serialized = commit.serialize().unwrap();
         Self::new_trusted(commit, serialized)
     }
@@ -249,12 +318,17 @@ pub struct CommittedSubDag {
     pub leader: BlockRef,
     /// All the committed blocks that are part of this sub-dag
     pub blocks: Vec<VerifiedBlock>,
-    /// The timestamp of the commit, obtained from the timestamp of the leader block.
+    /// The timestamp of the commit, obtained from the timestamp of the leader block, or from the
+    /// timestamps of the blocks the leader is built on if median based commit timestamps are
+    /// enabled, in which case it is never lower than the timestamp of the previous commit.
     pub timestamp_ms: BlockTimestampMs,
     /// Index of the commit.
     /// First commit after genesis has a index of 1, then every next commit has a
     /// index incremented by 1.
     pub commit_index: CommitIndex,
+    /// Randomness derived from the commit, identical on every authority. It lets consumers of
+    /// the committed transactions draw consensus-derived randomness without extra round trips.
+    pub randomness_seed: CommitRandomnessSeed,
 }
 
 impl CommittedSubDag {
@@ -263,24 +337,68 @@ impl CommittedSubDag {
         leader: BlockRef,
         blocks: Vec<VerifiedBlock>,
         timestamp_ms: u64,
-        commit_index: CommitIndex,
+        commit_ref: CommitRef,
     ) -> Self {
         Self {
             leader,
             blocks,
             timestamp_ms,
-            commit_index,
+            commit_index: commit_ref.index,
+            randomness_seed: CommitRandomnessSeed::derive(commit_ref, leader.round),
         }
     }
 
     /// Sort the blocks of the sub-dag by round number then authority index. Any
     /// deterministic & stable algorithm works.
     pub fn sort(&mut self) {
-        self.blocks.sort_by(|a, b| {
-            a.round()
-                .cmp(&b.round())
-                .then_with(|| a.author().cmp(&b.author()))
-        });
+        sort_sub_dag_blocks(&mut self.blocks);
+    }
+}
+
+/// Sorts the blocks of a sub-dag by round number then authority index, which is the order they
+/// are committed in.
+pub(crate) fn sort_sub_dag_blocks(blocks: &mut [VerifiedBlock]) {
+    blocks.sort_by(|a, b| {
+        a.round()
+            .cmp(&b.round())
+            .then_with(|| a.author().cmp(&b.author()))
+    });
+}
+
+/// Seed of the randomness of a commit, derived from the commit's digest, index and leader round.
+///
+/// The seed is deterministic, so every authority derives the same seed for a commit, and it
+/// cannot be predicted before the commit is formed. However, it can be biased by the authorities
+/// whose blocks are committed (e.g. the leader, by choosing the blocks it is built on), so it must
+/// not be used where unbiasable randomness is required.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CommitRandomnessSeed([u8; consensus_config::DIGEST_LENGTH]);
+
+impl CommitRandomnessSeed {
+    /// Separates seeds from other hashes of the same data.
+    const DOMAIN: &'static [u8] = b"consensus_commit_randomness_seed";
+
+    pub(crate) fn derive(commit_ref: CommitRef, leader_round: Round) -> Self {
+        let mut hasher = DefaultHashFunction::new();
+        hasher.update(Self::DOMAIN);
+        hasher.update(commit_ref.digest.0);
+        hasher.update(commit_ref.index.to_le_bytes());
+        hasher.update(leader_round.to_le_bytes());
+        Self(hasher.finalize().into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8; consensus_config::DIGEST_LENGTH] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for CommitRandomnessSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}",
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, self.0)
+        )
     }
 }
 
@@ -334,8 +452,10 @@ pub fn load_committed_subdag_from_store(
         .collect::<Vec<_>>();
     let leader_block_idx = leader_block_idx.expect("Leader block must be in the sub-dag");
     let leader_block_ref = blocks[leader_block_idx].reference();
-    let timestamp_ms = blocks[leader_block_idx].timestamp_ms();
-    CommittedSubDag::new(leader_block_ref, blocks, timestamp_ms, commit.index())
+    let timestamp_ms = commit
+        .timestamp_ms()
+        .unwrap_or_else(|| blocks[leader_block_idx].timestamp_ms());
+    CommittedSubDag::new(leader_block_ref, blocks, timestamp_ms, commit.reference())
 }
 
 pub struct CommitConsumer {
@@ -504,17 +624,91 @@ mod tests {
         let commit = TrustedCommit::new_for_test(
             commit_index,
             CommitDigest::MIN,
+            leader_block.timestamp_ms(),
             leader_ref,
             blocks.clone(),
         );
+        let commit_ref = commit.reference();
 
         let subdag = load_committed_subdag_from_store(store.as_ref(), commit);
         assert_eq!(subdag.leader, leader_ref);
         assert_eq!(subdag.timestamp_ms, leader_block.timestamp_ms());
+
+        // A V1 commit is timestamped by its leader block.
+        let commit = Commit::new(commit_index, CommitDigest::MIN, leader_ref, blocks.clone());
+        let serialized = commit.serialize().unwrap();
+        let v1_subdag = load_committed_subdag_from_store(
+            store.as_ref(),
+            TrustedCommit::new_trusted(commit, serialized),
+        );
+        assert_eq!(v1_subdag.timestamp_ms, leader_block.timestamp_ms());
+        assert_eq!(v1_subdag.blocks, subdag.blocks);
         assert_eq!(
             subdag.blocks.len(),
             (num_authorities * wave_length) as usize + 1
         );
         assert_eq!(subdag.commit_index, commit_index);
+        assert_eq!(
+            subdag.randomness_seed,
+            CommitRandomnessSeed::derive(commit_ref, leader_ref.round)
+        );
+    }
+
+    #[test]
+    fn test_commit_randomness_seed() {
+        let leader = BlockRef::new(3, AuthorityIndex::new_for_test(0), Default::default());
+        let commit = TrustedCommit::new_for_test(1, CommitDigest::MIN, 0, leader, vec![leader]);
+        let seed = CommitRandomnessSeed::derive(commit.reference(), leader.round);
+
+        // The seed is deterministic.
+        assert_eq!(
+            seed,
+            CommitRandomnessSeed::derive(commit.reference(), leader.round)
+        );
+
+        // Any change to the commit changes the seed.
+        let next_commit = TrustedCommit::new_for_test(2, commit.digest(), 0, leader, vec![leader]);
+        assert_ne!(
+            seed,
+            CommitRandomnessSeed::derive(next_commit.reference(), leader.round)
+        );
+        assert_ne!(
+            seed,
+            CommitRandomnessSeed::derive(commit.reference(), leader.round + 1)
+        );
+    }
+
+    #[test]
+    fn test_commit_versions() {
+        let leader = BlockRef::new(3, AuthorityIndex::new_for_test(0), Default::default());
+
+        // V1 commits keep the encoding they had before commits carried a timestamp, so their
+        // digests do not change.
+        let v1 = Commit::new(1, CommitDigest::MIN, leader, vec![leader]);
+        let serialized = v1.serialize().unwrap();
+        assert_eq!(
+            serialized.as_ref(),
+            bcs::to_bytes(&(
+                0u8,
+                1 as CommitIndex,
+                CommitDigest::MIN,
+                leader,
+                vec![leader]
+            ))
+            .unwrap()
+        );
+        let decoded: Commit = bcs::from_bytes(&serialized).unwrap();
+        assert_eq!(decoded, v1);
+        assert_eq!(decoded.timestamp_ms(), None);
+
+        let v2 = Commit::new_v2(1, CommitDigest::MIN, 1000, leader, vec![leader]);
+        let serialized = v2.serialize().unwrap();
+        let decoded: Commit = bcs::from_bytes(&serialized).unwrap();
+        assert_eq!(decoded, v2);
+        assert_eq!(decoded.timestamp_ms(), Some(1000));
+        assert_ne!(
+            TrustedCommit::new_trusted(v1.clone(), v1.serialize().unwrap()).digest(),
+            TrustedCommit::new_trusted(v2, serialized).digest()
+        );
     }
 }
@@ -15,6 +15,7 @@ use crate::{
     context::Context,
     dag_state::DagState,
     linearizer::Linearizer,
+    slo_report::CommitSloTracker,
     storage::Store,
 };
 
@@ -37,6 +38,8 @@ pub(crate) struct CommitObserver {
     sender: UnboundedSender<CommittedSubDag>,
     /// Persistent storage for blocks, commits and other consensus data.
     store: Arc<dyn Store>,
+    /// Keeps recent commits to produce commit SLO reports from.
+    slo_tracker: Arc<CommitSloTracker>,
 }
 
 impl CommitObserver {
@@ -50,8 +53,9 @@ impl CommitObserver {
         store: Arc<dyn Store>,
     ) -> Self {
         let mut observer = Self {
+            slo_tracker: Arc::new(CommitSloTracker::new(context.clone())),
+            commit_interpreter: Linearizer::new(context.clone(), dag_state.clone()),
             context,
-            commit_interpreter: Linearizer::new(dag_state.clone()),
             sender: commit_consumer.sender,
             store,
         };
@@ -84,10 +88,15 @@ impl CommitObserver {
         }
 
         self.report_metrics(&sent_sub_dags);
+        self.slo_tracker.record(&sent_sub_dags);
         tracing::trace!("Committed & sent {sent_sub_dags:#?}");
         Ok(sent_sub_dags)
     }
 
+    pub(crate) fn slo_tracker(&self) -> Arc<CommitSloTracker> {
+        self.slo_tracker.clone()
+    }
+
     fn recover_and_send_commits(&mut self, last_processed_commit_index: CommitIndex) {
         // TODO: remove this check, to allow consensus to regenerate commits?
         let last_commit = self
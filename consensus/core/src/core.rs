@@ -20,11 +20,12 @@ use crate::{
         timestamp_utc_ms, Block, BlockAPI, BlockRef, BlockTimestampMs, BlockV1, Round, SignedBlock,
         Slot, VerifiedBlock, GENESIS_ROUND,
     },
-    block_manager::BlockManager,
+    block_manager::{BlockManager, BlockRejection},
     commit_observer::CommitObserver,
     context::Context,
     dag_state::DagState,
     error::{ConsensusError, ConsensusResult},
+    event_log::ConsensusEvent,
     threshold_clock::ThresholdClock,
     transaction::TransactionConsumer,
     universal_committer::{
@@ -71,6 +72,10 @@ pub(crate) struct Core {
     block_signer: ProtocolKeyPair,
     /// Keeping track of state of the DAG, including blocks, commits and last committed rounds.
     dag_state: Arc<RwLock<DagState>>,
+    /// The number of rounds by which the own last proposed block is ahead of the highest own round
+    /// received by a quorum, as estimated by the round prober. Block proposals are held off while
+    /// it exceeds `Parameters::propagation_delay_stop_proposal_threshold`.
+    propagation_delay: Round,
 }
 
 impl Core {
@@ -121,6 +126,7 @@ impl Core {
             signals,
             block_signer,
             dag_state,
+            propagation_delay: 0,
         }
         .recover()
     }
@@ -136,6 +142,14 @@ impl Core {
         // Recover the last available quorum to correctly advance the threshold clock.
         let last_quorum = self.dag_state.read().last_quorum();
         self.add_accepted_blocks(last_quorum);
+        // Recover the blocks that were suspended before the restart. Their missing ancestors are
+        // fetched by the synchronizer.
+        let suspended_blocks = self.block_manager.recover_suspended_blocks();
+        if !suspended_blocks.is_empty() {
+            let (accepted_blocks, _missing_blocks) =
+                self.block_manager.try_accept_blocks(suspended_blocks);
+            self.add_accepted_blocks(accepted_blocks);
+        }
         // Try to commit and propose, since they may not have run after the last storage write.
         self.try_commit().unwrap();
         if self.try_propose(true).unwrap().is_none() {
@@ -168,6 +182,11 @@ impl Core {
         // Try to accept them via the block manager
         let (accepted_blocks, missing_blocks) = self.block_manager.try_accept_blocks(blocks);
 
+        // Report the rejected blocks to peers.
+        for rejection in self.block_manager.take_rejected_blocks() {
+            self.signals.block_rejected(rejection);
+        }
+
         if !accepted_blocks.is_empty() {
             // Now add accepted blocks to the threshold clock and pending ancestors list.
             self.add_accepted_blocks(accepted_blocks);
@@ -208,6 +227,9 @@ impl Core {
     ) -> ConsensusResult<Option<VerifiedBlock>> {
         if self.last_proposed_round() < round {
             self.context.metrics.node_metrics.leader_timeout_total.inc();
+            self.dag_state
+                .write()
+                .record_event(ConsensusEvent::LeaderTimeout { round });
             return self.try_propose(true);
         }
         Ok(None)
@@ -243,6 +265,26 @@ impl Core {
             return None;
         }
 
+        // Do not propose while the own blocks are not received by a quorum, even on leader timeout,
+        // as peers could not include the new block anyway.
+        let threshold = self
+            .context
+            .parameters
+            .propagation_delay_stop_proposal_threshold;
+        if self.propagation_delay > threshold {
+            debug!(
+                "Skipping proposal for round {clock_round}, own propagation delay is {} rounds",
+                self.propagation_delay
+            );
+            self.context
+                .metrics
+                .node_metrics
+                .core_skipped_proposals
+                .with_label_values(&["propagation_delay"])
+                .inc();
+            return None;
+        }
+
         let now = timestamp_utc_ms();
 
         // Create a new block either because we want to "forcefully" propose a block due to a leader timeout,
@@ -367,8 +409,21 @@ impl Core {
         self.commit_observer.handle_commit(committed_leaders)
     }
 
-    pub(crate) fn get_missing_blocks(&self) -> BTreeSet<BlockRef> {
-        self.block_manager.missing_blocks()
+    /// Returns the missing blocks, along with the number of suspended blocks waiting for each.
+    pub(crate) fn get_missing_blocks(&self) -> BTreeMap<BlockRef, usize> {
+        self.block_manager.missing_blocks_with_dependents()
+    }
+
+    /// Sets the propagation delay of the own blocks, as estimated by the round prober.
+    pub(crate) fn set_propagation_delay(&mut self, delay: Round) {
+        let threshold = self
+            .context
+            .parameters
+            .propagation_delay_stop_proposal_threshold;
+        if delay > threshold && self.propagation_delay <= threshold {
+            warn!("Own blocks are {delay} rounds ahead of a quorum, holding off block proposals");
+        }
+        self.propagation_delay = delay;
     }
 
     /// Retrieves the next ancestors to propose to form a block at `clock_round` round. Also, the `block_timestamp` is provided
@@ -497,6 +552,7 @@ impl Core {
 /// Senders of signals from Core, for outputs and events (ex new block produced).
 pub(crate) struct CoreSignals {
     tx_block_broadcast: broadcast::Sender<VerifiedBlock>,
+    tx_rejected_blocks: broadcast::Sender<BlockRejection>,
     new_round_sender: watch::Sender<Round>,
     context: Arc<Context>,
 }
@@ -508,16 +564,20 @@ impl CoreSignals {
     pub fn new(context: Arc<Context>) -> (Self, CoreSignalsReceivers) {
         let (tx_block_broadcast, _rx_block_broadcast) =
             broadcast::channel::<VerifiedBlock>(Self::BROADCAST_BACKLOG_CAPACITY);
+        let (tx_rejected_blocks, _rx_rejected_blocks) =
+            broadcast::channel::<BlockRejection>(Self::BROADCAST_BACKLOG_CAPACITY);
         let (new_round_sender, new_round_receiver) = watch::channel(0);
 
         let me = Self {
             tx_block_broadcast: tx_block_broadcast.clone(),
+            tx_rejected_blocks: tx_rejected_blocks.clone(),
             new_round_sender,
             context,
         };
 
         let receivers = CoreSignalsReceivers {
             tx_block_broadcast,
+            tx_rejected_blocks,
             new_round_receiver,
         };
 
@@ -540,6 +600,14 @@ impl CoreSignals {
         Ok(())
     }
 
+    /// Sends a signal that a block has been rejected, to report it to peers. Rejections are only
+    /// reported on a best effort basis, so it is fine if there is no subscriber to the signal.
+    pub fn block_rejected(&self, rejection: BlockRejection) {
+        if let Err(err) = self.tx_rejected_blocks.send(rejection) {
+            debug!("Couldn't report the rejected block to any receiver: {err}");
+        }
+    }
+
     /// Sends a signal that threshold clock has advanced to new round. The `round_number` is the round at which the
     /// threshold clock has advanced to.
     pub fn new_round(&mut self, round_number: Round) {
@@ -551,6 +619,7 @@ impl CoreSignals {
 /// Intentially un-clonable. Comonents should only subscribe to channels they need.
 pub(crate) struct CoreSignalsReceivers {
     tx_block_broadcast: broadcast::Sender<VerifiedBlock>,
+    tx_rejected_blocks: broadcast::Sender<BlockRejection>,
     new_round_receiver: watch::Receiver<Round>,
 }
 
@@ -559,6 +628,10 @@ impl CoreSignalsReceivers {
         self.tx_block_broadcast.subscribe()
     }
 
+    pub(crate) fn rejected_blocks_receiver(&self) -> broadcast::Receiver<BlockRejection> {
+        self.tx_rejected_blocks.subscribe()
+    }
+
     pub(crate) fn new_round_receiver(&self) -> watch::Receiver<Round> {
         self.new_round_receiver.clone()
     }
@@ -582,7 +655,7 @@ mod test {
         block_verifier::NoopBlockVerifier,
         commit::CommitAPI as _,
         storage::{mem_store::MemStore, Store, WriteBatch},
-        transaction::TransactionClient,
+        transaction::{TransactionClient, TransactionPriority},
         CommitConsumer, CommitIndex,
     };
 
@@ -852,7 +925,7 @@ mod test {
             total += transaction.len();
             index += 1;
             let _w = transaction_client
-                .submit_no_wait(transaction)
+                .submit_no_wait(transaction, TransactionPriority::User)
                 .await
                 .unwrap();
 
@@ -1049,6 +1122,36 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_core_propagation_delay_holds_off_proposals() {
+        telemetry_subscribers::init_for_testing();
+
+        // Create the cores for all authorities. Each of them proposes a block for round 1 on
+        // recovery.
+        let mut all_cores = create_cores(vec![1, 1, 1, 1]);
+        let round_1_blocks = all_cores
+            .iter()
+            .map(|(core, _, _, _, _)| core.last_proposed_block.clone())
+            .collect::<Vec<_>>();
+
+        // While the own blocks are too far ahead of a quorum, no block is proposed, even on
+        // leader timeout.
+        let (core, _, _, _, _) = &mut all_cores[0];
+        let threshold = core
+            .context
+            .parameters
+            .propagation_delay_stop_proposal_threshold;
+        core.set_propagation_delay(threshold + 1);
+        core.add_blocks(round_1_blocks).unwrap();
+        assert!(core.force_new_block(2).unwrap().is_none());
+        assert_eq!(core.last_proposed_round(), 1);
+
+        // Once the own blocks have propagated, proposals resume.
+        core.set_propagation_delay(threshold);
+        assert!(core.force_new_block(2).unwrap().is_some());
+        assert_eq!(core.last_proposed_round(), 2);
+    }
+
     #[tokio::test]
     async fn test_core_signals() {
         telemetry_subscribers::init_for_testing();
@@ -1,7 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::BTreeSet, fmt::Debug, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use mysten_metrics::{metered_channel, monitored_scope, spawn_logged_monitored_task};
@@ -24,8 +28,12 @@ enum CoreThreadCommand {
     AddBlocks(Vec<VerifiedBlock>, oneshot::Sender<BTreeSet<BlockRef>>),
     /// Called when a leader timeout occurs and a block should be produced
     ForceNewBlock(Round, oneshot::Sender<()>),
-    /// Request missing blocks that need to be synced.
-    GetMissing(oneshot::Sender<BTreeSet<BlockRef>>),
+    /// Request missing blocks that need to be synced, along with the number of suspended blocks
+    /// waiting for each.
+    GetMissing(oneshot::Sender<BTreeMap<BlockRef, usize>>),
+    /// Set the number of rounds by which the own blocks are ahead of the round received by a
+    /// quorum, as estimated by the round prober.
+    SetPropagationDelay(Round, oneshot::Sender<()>),
 }
 
 #[derive(Error, Debug)]
@@ -43,7 +51,11 @@ pub trait CoreThreadDispatcher: Sync + Send + 'static {
 
     async fn force_new_block(&self, round: Round) -> Result<(), CoreError>;
 
-    async fn get_missing_blocks(&self) -> Result<BTreeSet<BlockRef>, CoreError>;
+    /// Returns the missing blocks, along with the number of suspended blocks waiting for each.
+    async fn get_missing_blocks(&self) -> Result<BTreeMap<BlockRef, usize>, CoreError>;
+
+    /// Sets the propagation delay of the own blocks, which holds off block proposals when too high.
+    async fn set_propagation_delay(&self, delay: Round) -> Result<(), CoreError>;
 }
 
 pub(crate) struct CoreThreadHandle {
@@ -84,6 +96,10 @@ impl CoreThread {
                 CoreThreadCommand::GetMissing(sender) => {
                     sender.send(self.core.get_missing_blocks()).ok();
                 }
+                CoreThreadCommand::SetPropagationDelay(delay, sender) => {
+                    self.core.set_propagation_delay(delay);
+                    sender.send(()).ok();
+                }
             }
         }
 
@@ -166,11 +182,18 @@ impl CoreThreadDispatcher for ChannelCoreThreadDispatcher {
         receiver.await.map_err(Shutdown)
     }
 
-    async fn get_missing_blocks(&self) -> Result<BTreeSet<BlockRef>, CoreError> {
+    async fn get_missing_blocks(&self) -> Result<BTreeMap<BlockRef, usize>, CoreError> {
         let (sender, receiver) = oneshot::channel();
         self.send(CoreThreadCommand::GetMissing(sender)).await;
         receiver.await.map_err(Shutdown)
     }
+
+    async fn set_propagation_delay(&self, delay: Round) -> Result<(), CoreError> {
+        let (sender, receiver) = oneshot::channel();
+        self.send(CoreThreadCommand::SetPropagationDelay(delay, sender))
+            .await;
+        receiver.await.map_err(Shutdown)
+    }
 }
 
 #[cfg(test)]
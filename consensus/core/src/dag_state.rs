@@ -4,7 +4,10 @@
 use std::{
     cmp::max,
     collections::{BTreeMap, BTreeSet, VecDeque},
-    ops::Bound::{Excluded, Included, Unbounded},
+    ops::{
+        Bound::{Excluded, Included, Unbounded},
+        Range,
+    },
     panic,
     sync::Arc,
 };
@@ -15,9 +18,13 @@ use tracing::error;
 use crate::block::GENESIS_ROUND;
 use crate::stake_aggregator::{QuorumThreshold, StakeAggregator};
 use crate::{
-    block::{genesis_blocks, BlockAPI, BlockDigest, BlockRef, Round, Slot, VerifiedBlock},
+    block::{
+        genesis_blocks, timestamp_utc_ms, BlockAPI, BlockDigest, BlockRef, BlockTimestampMs, Round,
+        Slot, VerifiedBlock,
+    },
     commit::{CommitAPI as _, CommitDigest, CommitIndex, CommitRef, TrustedCommit},
     context::Context,
+    event_log::{ConsensusEvent, ConsensusEventRecord},
     storage::{Store, WriteBatch},
 };
 
@@ -61,6 +68,21 @@ pub(crate) struct DagState {
     // Data to be flushed to storage.
     blocks_to_write: Vec<VerifiedBlock>,
     commits_to_write: Vec<TrustedCommit>,
+    events_to_write: Vec<ConsensusEventRecord>,
+
+    // Sequence number of the next event recorded in the event log.
+    next_event_sequence: u64,
+    // Sequence number of the oldest event persisted in the event log, that has not been evicted.
+    first_event_sequence: u64,
+
+    // Number of invalid blocks attributed to each authority, either produced or sent by it.
+    // Does not persist across restarts.
+    invalid_blocks: Vec<u64>,
+
+    // Authorities that reported the rejection of each block found invalid, so that the block is
+    // counted once against its author however many times it is reported. Evicted along with the
+    // cached blocks.
+    reported_invalid_blocks: BTreeMap<BlockRef, BTreeSet<AuthorityIndex>>,
 
     // Persistent storage for blocks, commits and other consensus data.
     store: Arc<dyn Store>,
@@ -94,6 +116,16 @@ impl DagState {
             }
         };
 
+        let next_event_sequence = store
+            .read_last_event()
+            .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e))
+            .map_or(0, |event| event.sequence + 1);
+        let first_event_sequence = store
+            .scan_events(0, 1)
+            .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e))
+            .first()
+            .map_or(next_event_sequence, |event| event.sequence);
+
         let mut state = Self {
             context,
             genesis,
@@ -106,6 +138,11 @@ impl DagState {
             commits_to_vote: VecDeque::new(),
             blocks_to_write: vec![],
             commits_to_write: vec![],
+            events_to_write: vec![],
+            next_event_sequence,
+            first_event_sequence,
+            invalid_blocks: vec![0; num_authorities],
+            reported_invalid_blocks: BTreeMap::new(),
             store,
             cached_rounds,
         };
@@ -149,6 +186,7 @@ impl DagState {
         }
         self.update_block_metadata(&block);
         self.blocks_to_write.push(block);
+        self.record_event(ConsensusEvent::BlockAccepted { block: block_ref });
         self.context.metrics.node_metrics.accepted_blocks.inc();
     }
 
@@ -456,6 +494,15 @@ impl DagState {
         self.highest_accepted_round
     }
 
+    /// Returns the round of the last accepted block of each authority, indexed by authority, or
+    /// the genesis round for authorities without accepted blocks.
+    pub(crate) fn last_accepted_rounds(&self) -> Vec<Round> {
+        self.recent_refs
+            .iter()
+            .map(|refs| refs.last().map_or(GENESIS_ROUND, |r| r.round))
+            .collect()
+    }
+
     // Buffers a new commit in memory and updates last committed rounds.
     // REQUIRED: must not skip over any commit index.
     pub(crate) fn add_commit(&mut self, commit: TrustedCommit) {
@@ -500,10 +547,82 @@ impl DagState {
             );
         }
 
+        self.record_event(ConsensusEvent::CommitAdvanced {
+            index: commit.index(),
+            leader: commit.leader(),
+        });
         self.commits_to_vote.push_back(commit.reference());
         self.commits_to_write.push(commit);
     }
 
+    /// Buffers an event to be appended to the event log on the next flush.
+    pub(crate) fn record_event(&mut self, event: ConsensusEvent) {
+        self.events_to_write.push(ConsensusEventRecord {
+            sequence: self.next_event_sequence,
+            timestamp_ms: timestamp_utc_ms(),
+            event,
+        });
+        self.next_event_sequence += 1;
+    }
+
+    /// Counts an invalid block against the authority that produced or sent it.
+    pub(crate) fn record_invalid_block(&mut self, authority: AuthorityIndex) {
+        self.invalid_blocks[authority] += 1;
+    }
+
+    /// Records that `reporter` reported the rejection of `block`, which has been found invalid.
+    /// The block is counted against its author the first time it is reported, and returns
+    /// whether it was. Blocks at or below the evicted round of their author are not counted, as
+    /// their reports are no longer tracked.
+    pub(crate) fn record_reported_invalid_block(
+        &mut self,
+        reporter: AuthorityIndex,
+        block: BlockRef,
+    ) -> bool {
+        if block.round <= self.authority_evict_round(block.author) {
+            return false;
+        }
+        let reporters = self.reported_invalid_blocks.entry(block).or_default();
+        let first_report = reporters.is_empty();
+        reporters.insert(reporter);
+        if first_report {
+            self.invalid_blocks[block.author] += 1;
+        }
+        first_report
+    }
+
+    /// Returns true if `reporter` has already reported the rejection of `block`, and the block
+    /// has been found invalid.
+    pub(crate) fn is_reported_invalid_block(
+        &self,
+        reporter: AuthorityIndex,
+        block: &BlockRef,
+    ) -> bool {
+        self.reported_invalid_blocks
+            .get(block)
+            .is_some_and(|reporters| reporters.contains(&reporter))
+    }
+
+    /// Returns the number of invalid blocks attributed to each authority since startup, indexed
+    /// by authority, e.g. to score peers.
+    pub(crate) fn invalid_blocks_by_authority(&self) -> &[u64] {
+        &self.invalid_blocks
+    }
+
+    /// Returns the sequence numbers of the events to evict from the event log, to keep it within
+    /// its capacity. Events are evicted once a tenth of the capacity has accumulated beyond it,
+    /// rather than on every flush.
+    fn events_to_evict(&mut self) -> Range<u64> {
+        let capacity = self.context.parameters.event_log_capacity;
+        let evict_before = self.next_event_sequence.saturating_sub(capacity);
+        if evict_before.saturating_sub(self.first_event_sequence) < (capacity / 10).max(1) {
+            return 0..0;
+        }
+        let evict_events = self.first_event_sequence..evict_before;
+        self.first_event_sequence = evict_before;
+        evict_events
+    }
+
     pub(crate) fn take_commit_votes(&mut self, limit: usize) -> Vec<CommitRef> {
         let mut votes = Vec::new();
         while !self.commits_to_vote.is_empty() && votes.len() < limit {
@@ -528,6 +647,15 @@ impl DagState {
         }
     }
 
+    /// Timestamp of the last commit. Only V2 commits carry a timestamp, and they are the only ones
+    /// produced when median based commit timestamps are enabled, the one case this is needed.
+    pub(crate) fn last_commit_timestamp_ms(&self) -> BlockTimestampMs {
+        self.last_commit
+            .as_ref()
+            .and_then(|commit| commit.timestamp_ms())
+            .unwrap_or(0)
+    }
+
     /// Leader slot of the last commit.
     pub(crate) fn last_commit_leader(&self) -> Slot {
         match &self.last_commit {
@@ -560,15 +688,19 @@ impl DagState {
         // Flush buffered data to storage.
         let blocks = std::mem::take(&mut self.blocks_to_write);
         let commits = std::mem::take(&mut self.commits_to_write);
-        if blocks.is_empty() && commits.is_empty() {
+        let events = std::mem::take(&mut self.events_to_write);
+        if blocks.is_empty() && commits.is_empty() && events.is_empty() {
             return;
         }
+        let evict_events = self.events_to_evict();
         self.store
             .write(WriteBatch::new(
                 blocks,
                 commits,
                 // TODO: limit to write at most once per commit round with multi-leader.
                 self.last_committed_rounds.clone(),
+                events,
+                evict_events,
             ))
             .unwrap_or_else(|e| panic!("Failed to write to storage: {:?}", e));
         self.context
@@ -592,6 +724,34 @@ impl DagState {
                 }
             }
         }
+        let last_committed_rounds = &self.last_committed_rounds;
+        let cached_rounds = self.cached_rounds;
+        self.reported_invalid_blocks.retain(|block_ref, _| {
+            block_ref.round
+                > Self::evict_round(last_committed_rounds[block_ref.author], cached_rounds)
+        });
+    }
+
+    /// Persists changes to the blocks suspended by the block manager. Unlike accepted blocks, they
+    /// are written right away, as they are not part of the DAG state that flush persists.
+    pub(crate) fn write_suspended_blocks(
+        &self,
+        suspended_blocks: Vec<VerifiedBlock>,
+        unsuspended_blocks: Vec<BlockRef>,
+    ) {
+        if suspended_blocks.is_empty() && unsuspended_blocks.is_empty() {
+            return;
+        }
+        self.store
+            .write(WriteBatch::default().suspended_blocks(suspended_blocks, unsuspended_blocks))
+            .unwrap_or_else(|e| panic!("Failed to write suspended blocks to storage: {:?}", e));
+    }
+
+    /// Reads the blocks persisted as suspended by the block manager before a restart.
+    pub(crate) fn read_suspended_blocks(&self) -> Vec<VerifiedBlock> {
+        self.store
+            .read_suspended_blocks()
+            .unwrap_or_else(|e| panic!("Failed to read suspended blocks from storage: {:?}", e))
     }
 
     /// Detects and returns the blocks of the round that forms the last quorum. The method will return
@@ -1069,6 +1229,7 @@ mod test {
         dag_state.add_commit(TrustedCommit::new_for_test(
             1 as CommitIndex,
             CommitDigest::MIN,
+            0,
             blocks.last().unwrap().reference(),
             blocks
                 .into_iter()
@@ -1162,6 +1323,7 @@ mod test {
             commits.push(TrustedCommit::new_for_test(
                 round as CommitIndex,
                 CommitDigest::MIN,
+                0,
                 blocks.last().unwrap().reference(),
                 vec![],
             ));
@@ -1239,6 +1401,120 @@ mod test {
         assert_eq!(dag_state.last_commit_index(), 5);
     }
 
+    #[test]
+    fn test_event_log_flush_and_recovery() {
+        let (mut context, _) = Context::new_for_test(4);
+        context.parameters.event_log_capacity = 5;
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+
+        // Accepting blocks and adding a commit are recorded.
+        let blocks: Vec<_> = (0..4)
+            .map(|author| VerifiedBlock::new_for_test(TestBlock::new(1, author).build()))
+            .collect();
+        dag_state.accept_blocks(blocks.clone());
+        dag_state.add_commit(TrustedCommit::new_for_test(
+            1,
+            CommitDigest::MIN,
+            0,
+            blocks[0].reference(),
+            vec![],
+        ));
+        dag_state.record_event(ConsensusEvent::LeaderTimeout { round: 2 });
+
+        // Events are only persisted on flush.
+        assert!(store.read_last_event().unwrap().is_none());
+        dag_state.flush();
+
+        // Only the last 5 of the 6 recorded events are kept.
+        let events = store.scan_events(0, 10).unwrap();
+        let sequences: Vec<_> = events.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            events[3].event,
+            ConsensusEvent::CommitAdvanced {
+                index: 1,
+                leader: blocks[0].reference(),
+            }
+        );
+        assert_eq!(events[4].event, ConsensusEvent::LeaderTimeout { round: 2 });
+
+        // After recovery, the sequence numbers of new events continue from the log.
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+        dag_state.record_event(ConsensusEvent::LeaderTimeout { round: 3 });
+        dag_state.flush();
+        let last_event = store.read_last_event().unwrap().unwrap();
+        assert_eq!(last_event.sequence, 6);
+        assert_eq!(store.scan_events(0, 10).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_record_reported_invalid_block() {
+        let (mut context, _) = Context::new_for_test(4);
+        context.parameters.dag_state_cached_rounds = 5;
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+
+        // The block is counted against its author on its first report only.
+        let block = BlockRef::new(3, AuthorityIndex::new_for_test(1), BlockDigest::default());
+        let reporter = AuthorityIndex::new_for_test(2);
+        assert!(dag_state.record_reported_invalid_block(reporter, block));
+        assert!(!dag_state.record_reported_invalid_block(reporter, block));
+        assert!(!dag_state.record_reported_invalid_block(AuthorityIndex::new_for_test(3), block));
+        assert!(dag_state.is_reported_invalid_block(reporter, &block));
+        assert!(!dag_state.is_reported_invalid_block(AuthorityIndex::new_for_test(0), &block));
+        assert_eq!(dag_state.invalid_blocks_by_authority(), &[0, 1, 0, 0]);
+
+        // Once the block's round is evicted, its reports are dropped and no longer counted.
+        let leader = BlockRef::new(10, AuthorityIndex::new_for_test(1), BlockDigest::default());
+        dag_state.add_commit(TrustedCommit::new_for_test(
+            1,
+            CommitDigest::MIN,
+            0,
+            leader,
+            vec![leader],
+        ));
+        dag_state.flush();
+        assert!(!dag_state.is_reported_invalid_block(reporter, &block));
+        assert!(!dag_state.record_reported_invalid_block(reporter, block));
+        assert_eq!(dag_state.invalid_blocks_by_authority(), &[0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_event_log_evicts_in_batches() {
+        let (mut context, _) = Context::new_for_test(4);
+        context.parameters.event_log_capacity = 20;
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+
+        // One event beyond the capacity is not evicted yet.
+        for round in 0..21 {
+            dag_state.record_event(ConsensusEvent::LeaderTimeout { round });
+        }
+        dag_state.flush();
+        assert_eq!(store.scan_events(0, 100).unwrap().len(), 21);
+
+        // Events are evicted once a tenth of the capacity has accumulated beyond it.
+        dag_state.record_event(ConsensusEvent::LeaderTimeout { round: 21 });
+        dag_state.flush();
+        let events = store.scan_events(0, 100).unwrap();
+        assert_eq!(events.len(), 20);
+        assert_eq!(events[0].sequence, 2);
+
+        // The oldest event is recovered after a restart.
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+        for round in 22..24 {
+            dag_state.record_event(ConsensusEvent::LeaderTimeout { round });
+        }
+        dag_state.flush();
+        let events = store.scan_events(0, 100).unwrap();
+        assert_eq!(events.len(), 20);
+        assert_eq!(events[0].sequence, 4);
+    }
+
     #[test]
     fn test_get_cached_last_block_per_authority() {
         // GIVEN
@@ -1266,6 +1542,7 @@ mod test {
         dag_state.add_commit(TrustedCommit::new_for_test(
             1 as CommitIndex,
             CommitDigest::MIN,
+            0,
             all_blocks.last().unwrap().reference(),
             all_blocks
                 .into_iter()
@@ -1328,6 +1605,7 @@ mod test {
         dag_state.add_commit(TrustedCommit::new_for_test(
             1 as CommitIndex,
             CommitDigest::MIN,
+            0,
             all_blocks.last().unwrap().reference(),
             all_blocks
                 .into_iter()
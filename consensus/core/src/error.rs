@@ -19,6 +19,9 @@ pub enum ConsensusError {
     #[error("Error deserializing commit: {0}")]
     MalformedCommit(bcs::Error),
 
+    #[error("Error deserializing block rejection: {0}")]
+    MalformedRejection(bcs::Error),
+
     #[error("Error serializing: {0}")]
     SerializationFailure(bcs::Error),
 
@@ -46,6 +49,9 @@ pub enum ConsensusError {
     #[error("Too many blocks have been requested from authority {0}")]
     TooManyFetchBlocksRequested(AuthorityIndex),
 
+    #[error("Too many rejected blocks have been reported by authority {0}")]
+    TooManyRejectedBlocksReported(AuthorityIndex),
+
     #[error("Invalid authority index: {index} > {max}")]
     InvalidAuthorityIndex { index: AuthorityIndex, max: usize },
 
@@ -107,10 +113,66 @@ pub enum ConsensusError {
     #[error("Network error: {0:?}")]
     NetworkError(String),
 
+    #[error("Block encryption failure: {0}")]
+    BlockEncryptionFailure(String),
+
     #[error("Consensus has shut down!")]
     Shutdown,
 }
 
+impl ConsensusError {
+    /// Returns a short and stable name of the error variant, to label error metrics with.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ConsensusError::MalformedBlock(..) => "malformed_block",
+            ConsensusError::MalformedCommit(..) => "malformed_commit",
+            ConsensusError::MalformedRejection(..) => "malformed_rejection",
+            ConsensusError::SerializationFailure(..) => "serialization_failure",
+            ConsensusError::UnexpectedAuthority(..) => "unexpected_authority",
+            ConsensusError::WrongEpoch { .. } => "wrong_epoch",
+            ConsensusError::UnexpectedGenesisBlock => "unexpected_genesis_block",
+            ConsensusError::UnexpectedGenesisBlockRequested => "unexpected_genesis_block_requested",
+            ConsensusError::UnexpectedFetchedBlock { .. } => "unexpected_fetched_block",
+            ConsensusError::TooManyFetchedBlocksReturned(..) => "too_many_fetched_blocks_returned",
+            ConsensusError::TooManyFetchBlocksRequested(..) => "too_many_fetch_blocks_requested",
+            ConsensusError::TooManyRejectedBlocksReported(..) => {
+                "too_many_rejected_blocks_reported"
+            }
+            ConsensusError::InvalidAuthorityIndex { .. } => "invalid_authority_index",
+            ConsensusError::MalformedSignature(..) => "malformed_signature",
+            ConsensusError::SignatureVerificationFailure(..) => "signature_verification_failure",
+            ConsensusError::SynchronizerSaturated(..) => "synchronizer_saturated",
+            ConsensusError::InvalidAncestorPosition { .. } => "invalid_ancestor_position",
+            ConsensusError::InvalidAncestorRound { .. } => "invalid_ancestor_round",
+            ConsensusError::InvalidGenesisAncestor(..) => "invalid_genesis_ancestor",
+            ConsensusError::TooManyAncestors(..) => "too_many_ancestors",
+            ConsensusError::DuplicatedAncestorsAuthority(..) => "duplicated_ancestors_authority",
+            ConsensusError::InsufficientParentStakes { .. } => "insufficient_parent_stakes",
+            ConsensusError::InvalidTransaction(..) => "invalid_transaction",
+            ConsensusError::InvalidBlockTimestamp { .. } => "invalid_block_timestamp",
+            ConsensusError::BlockTooFarInFuture { .. } => "block_too_far_in_future",
+            ConsensusError::RocksDBFailure(..) => "rocksdb_failure",
+            ConsensusError::UnknownNetworkPeer(..) => "unknown_network_peer",
+            ConsensusError::PeerDisconnected(..) => "peer_disconnected",
+            ConsensusError::NetworkError(..) => "network_error",
+            ConsensusError::BlockEncryptionFailure(..) => "block_encryption_failure",
+            ConsensusError::Shutdown => "shutdown",
+        }
+    }
+
+    /// Returns true if the error is caused by the authority itself rather than by a peer, e.g.
+    /// when it is shutting down while handling a request from the peer.
+    pub(crate) fn is_local(&self) -> bool {
+        matches!(
+            self,
+            ConsensusError::SerializationFailure(..)
+                | ConsensusError::SynchronizerSaturated(..)
+                | ConsensusError::RocksDBFailure(..)
+                | ConsensusError::Shutdown
+        )
+    }
+}
+
 pub type ConsensusResult<T> = Result<T, ConsensusError>;
 
 #[macro_export]
@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    block::{BlockRef, BlockTimestampMs, Round},
+    commit::CommitIndex,
+    storage::{rocksdb_store::RocksDBStore, Store},
+};
+
+/// A state transition of the consensus authority, recorded in the event log. The event log is
+/// persisted alongside blocks and commits, and is bounded to the last
+/// `Parameters::event_log_capacity` events, so that the timeline leading to an incident can be
+/// reconstructed from the store after the fact.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsensusEvent {
+    /// The block has been accepted into the DAG, with all of its ancestors.
+    BlockAccepted { block: BlockRef },
+    /// The block has been suspended until its missing ancestors are accepted.
+    BlockSuspended {
+        block: BlockRef,
+        missing_ancestors: Vec<BlockRef>,
+    },
+    /// The block passed signature verification but has been rejected, e.g. because it is
+    /// inconsistent with its ancestors or too far in the future. Blocks failing signature
+    /// verification are not recorded, so that peers cannot flood the log.
+    BlockRejected { block: BlockRef, reason: String },
+    /// A new commit has been sequenced.
+    CommitAdvanced {
+        index: CommitIndex,
+        leader: BlockRef,
+    },
+    /// The leader timeout fired, forcing a proposal for the round.
+    LeaderTimeout { round: Round },
+    /// The suspended block has been evicted to bound the memory of suspended blocks.
+    BlockEvicted { block: BlockRef, reason: String },
+}
+
+impl fmt::Display for ConsensusEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsensusEvent::BlockAccepted { block } => write!(f, "BlockAccepted {block}"),
+            ConsensusEvent::BlockSuspended {
+                block,
+                missing_ancestors,
+            } => {
+                write!(f, "BlockSuspended {block} missing [")?;
+                for (i, ancestor) in missing_ancestors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{ancestor}")?;
+                }
+                write!(f, "]")
+            }
+            ConsensusEvent::BlockRejected { block, reason } => {
+                write!(f, "BlockRejected {block}: {reason}")
+            }
+            ConsensusEvent::CommitAdvanced { index, leader } => {
+                write!(f, "CommitAdvanced index {index} leader {leader}")
+            }
+            ConsensusEvent::LeaderTimeout { round } => write!(f, "LeaderTimeout round {round}"),
+            ConsensusEvent::BlockEvicted { block, reason } => {
+                write!(f, "BlockEvicted {block}: {reason}")
+            }
+        }
+    }
+}
+
+/// An event of the log, with its position in the log and the time it was recorded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsensusEventRecord {
+    pub sequence: u64,
+    pub timestamp_ms: BlockTimestampMs,
+    pub event: ConsensusEvent,
+}
+
+impl fmt::Display for ConsensusEventRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} @{}ms {}",
+            self.sequence, self.timestamp_ms, self.event
+        )
+    }
+}
+
+/// Reads up to `limit` events of the log persisted in the consensus store at `db_path`, starting
+/// from `start_sequence`. The store must not be opened by a running authority.
+pub fn read_event_log(
+    db_path: &str,
+    start_sequence: u64,
+    limit: usize,
+) -> Result<Vec<ConsensusEventRecord>, anyhow::Error> {
+    let store = RocksDBStore::new(db_path);
+    Ok(store.scan_events(start_sequence, limit)?)
+}
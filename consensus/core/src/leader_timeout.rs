@@ -96,7 +96,7 @@ impl<D: CoreThreadDispatcher> LeaderTimeoutTask<D> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeSet;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -140,7 +140,11 @@ mod tests {
             Ok(())
         }
 
-        async fn get_missing_blocks(&self) -> Result<BTreeSet<BlockRef>, CoreError> {
+        async fn get_missing_blocks(&self) -> Result<BTreeMap<BlockRef, usize>, CoreError> {
+            todo!()
+        }
+
+        async fn set_propagation_delay(&self, _delay: Round) -> Result<(), CoreError> {
             todo!()
         }
     }
@@ -14,12 +14,17 @@ mod core;
 mod core_thread;
 mod dag_state;
 mod error;
+mod event_log;
 mod leader_schedule;
 mod leader_timeout;
 mod linearizer;
 mod metrics;
 mod network;
+mod rejection_reporter;
+mod round_prober;
+mod slo_report;
 mod stake_aggregator;
+mod stall_detector;
 mod storage;
 mod synchronizer;
 #[cfg(test)]
@@ -28,7 +33,12 @@ mod threshold_clock;
 mod transaction;
 mod universal_committer;
 
-pub use authority_node::{ConsensusAuthority, NetworkType};
-pub use block::{BlockAPI, Round};
-pub use commit::{CommitConsumer, CommitIndex, CommittedSubDag};
-pub use transaction::{TransactionClient, TransactionVerifier, ValidationError};
+pub use authority_node::ConsensusAuthority;
+pub use block::{BlockAPI, BlockRef, Round};
+pub use commit::{CommitConsumer, CommitIndex, CommitRandomnessSeed, CommittedSubDag};
+pub use event_log::{read_event_log, ConsensusEvent, ConsensusEventRecord};
+pub use stall_detector::StallReason;
+pub use storage::integrity::{verify_store, InconsistentCommit, StoreIntegrityReport};
+pub use transaction::{
+    TransactionClient, TransactionPriority, TransactionVerifier, ValidationError,
+};
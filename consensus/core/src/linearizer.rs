@@ -6,35 +6,91 @@ use std::{collections::HashSet, sync::Arc};
 use parking_lot::RwLock;
 
 use crate::{
-    block::{BlockAPI, Round, VerifiedBlock},
-    commit::{Commit, CommitIndex, CommittedSubDag, TrustedCommit},
+    block::{BlockAPI, BlockTimestampMs, Round, VerifiedBlock, GENESIS_ROUND},
+    commit::{sort_sub_dag_blocks, Commit, CommittedSubDag, TrustedCommit},
+    context::Context,
     dag_state::DagState,
 };
 
 /// Expand a committed sequence of leader into a sequence of sub-dags.
 #[derive(Clone)]
 pub(crate) struct Linearizer {
+    context: Arc<Context>,
     /// In memory block store representing the dag state
     dag_state: Arc<RwLock<DagState>>,
 }
 
 impl Linearizer {
-    pub(crate) fn new(dag_state: Arc<RwLock<DagState>>) -> Self {
-        Self { dag_state }
+    pub(crate) fn new(context: Arc<Context>, dag_state: Arc<RwLock<DagState>>) -> Self {
+        Self { context, dag_state }
     }
 
-    /// Collect the sub-dag from a specific leader excluding any duplicates or
+    /// Computes the timestamp of the commit of `leader_block`, which is the leader's timestamp
+    /// unless median based commit timestamps are enabled. It is then the stake-weighted median of
+    /// the timestamps of the blocks of the previous round the leader is built on, so that a
+    /// Byzantine leader cannot pick the timestamp of its commit on its own, and no lower than the
+    /// timestamp of the previous commit. Unlike the blocks voting for the leader, the blocks it is
+    /// built on are fixed by the leader, so every authority derives the same timestamp.
+    fn commit_timestamp_ms(
+        &self,
+        leader_block: &VerifiedBlock,
+        last_commit_timestamp_ms: BlockTimestampMs,
+    ) -> BlockTimestampMs {
+        if !self
+            .context
+            .protocol_config
+            .consensus_median_based_commit_timestamp()
+        {
+            return leader_block.timestamp_ms();
+        }
+
+        // Genesis blocks have no meaningful timestamp, so leaders of the first round fall back to
+        // their own timestamp.
+        let parents = leader_block
+            .ancestors()
+            .iter()
+            .filter(|ancestor| {
+                ancestor.round > GENESIS_ROUND && ancestor.round + 1 == leader_block.round()
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        let mut timestamps = self
+            .dag_state
+            .read()
+            .get_blocks(&parents)
+            .into_iter()
+            .map(|parent| {
+                let parent = parent.expect("We should have all ancestors of a committed leader.");
+                let stake = self.context.committee.stake(parent.author());
+                (parent.timestamp_ms(), stake)
+            })
+            .collect::<Vec<_>>();
+        timestamps.sort();
+
+        let total_stake: u64 = timestamps.iter().map(|(_, stake)| stake).sum();
+        let mut cumulative_stake = 0;
+        let median = timestamps
+            .into_iter()
+            .find(|(_, stake)| {
+                cumulative_stake += stake;
+                cumulative_stake * 2 > total_stake
+            })
+            .map_or(leader_block.timestamp_ms(), |(timestamp_ms, _)| {
+                timestamp_ms
+            });
+        median.max(last_commit_timestamp_ms)
+    }
+
+    /// Collect the blocks of the sub-dag from a specific leader excluding any duplicates or
     /// blocks that have already been committed (within previous sub-dags).
     fn collect_sub_dag(
         &mut self,
         leader_block: VerifiedBlock,
-        last_commit_index: CommitIndex,
         last_committed_rounds: Vec<Round>,
-    ) -> CommittedSubDag {
+    ) -> Vec<VerifiedBlock> {
         let mut to_commit = Vec::new();
         let mut committed = HashSet::new();
 
-        let timestamp_ms = leader_block.timestamp_ms();
         let leader_block_ref = leader_block.reference();
         let mut buffer = vec![leader_block];
         assert!(committed.insert(leader_block_ref));
@@ -68,12 +124,7 @@ impl Linearizer {
                 assert!(committed.insert(ancestor.reference()));
             }
         }
-        CommittedSubDag::new(
-            leader_block_ref,
-            to_commit,
-            timestamp_ms,
-            last_commit_index + 1,
-        )
+        to_commit
     }
 
     // This function should be called whenever a new commit is observed. This will
@@ -89,40 +140,64 @@ impl Linearizer {
             let dag_state = self.dag_state.read();
             let last_commit_index = dag_state.last_commit_index();
             let last_commit_digest = dag_state.last_commit_digest();
+            let last_commit_timestamp_ms = dag_state.last_commit_timestamp_ms();
             let mut last_committed_rounds = dag_state.last_committed_rounds();
             drop(dag_state);
 
+            let timestamp_ms = self.commit_timestamp_ms(&leader_block, last_commit_timestamp_ms);
+            let leader_block_ref = leader_block.reference();
+
             // Collect the sub-dag generated using each of these leaders.
-            let mut sub_dag = self.collect_sub_dag(
-                leader_block,
-                last_commit_index,
-                last_committed_rounds.clone(),
-            );
+            let mut blocks = self.collect_sub_dag(leader_block, last_committed_rounds.clone());
 
             // [Optional] sort the sub-dag using a deterministic algorithm.
-            sub_dag.sort();
-
-            // Buffer commit in dag state for persistence later.
-            let commit = Commit::new(
-                sub_dag.commit_index,
-                last_commit_digest,
-                sub_dag.leader,
-                sub_dag
-                    .blocks
-                    .iter()
-                    .map(|block| {
-                        let block_ref = block.reference();
-                        last_committed_rounds[block_ref.author.value()] = block_ref.round;
-                        block_ref
-                    })
-                    .collect(),
-            );
+            sort_sub_dag_blocks(&mut blocks);
+
+            let block_refs = blocks
+                .iter()
+                .map(|block| {
+                    let block_ref = block.reference();
+                    last_committed_rounds[block_ref.author.value()] = block_ref.round;
+                    block_ref
+                })
+                .collect();
+
+            // Buffer commit in dag state for persistence later. Commits only carry their own
+            // timestamp when it can differ from the leader's, to keep the encoding of V1 commits.
+            let commit = if self
+                .context
+                .protocol_config
+                .consensus_median_based_commit_timestamp()
+            {
+                Commit::new_v2(
+                    last_commit_index + 1,
+                    last_commit_digest,
+                    timestamp_ms,
+                    leader_block_ref,
+                    block_refs,
+                )
+            } else {
+                Commit::new(
+                    last_commit_index + 1,
+                    last_commit_digest,
+                    leader_block_ref,
+                    block_refs,
+                )
+            };
             let serialized = commit
                 .serialize()
                 .unwrap_or_else(|e| panic!("Failed to serialize commit: {}", e));
             let commit = TrustedCommit::new_trusted(commit, serialized);
             self.dag_state.write().add_commit(commit.clone());
-            committed_sub_dags.push(sub_dag);
+
+            // The commit digest is only known once the commit is formed, so the sub-dag is created
+            // last, to derive its randomness seed from it.
+            committed_sub_dags.push(CommittedSubDag::new(
+                leader_block_ref,
+                blocks,
+                timestamp_ms,
+                commit.reference(),
+            ));
         }
         // Committed blocks must be persisted to storage before sending them to Sui and executing
         // their transactions.
@@ -140,10 +215,11 @@ impl Linearizer {
 mod tests {
     use super::*;
     use crate::{
-        commit::{CommitAPI as _, CommitDigest, DEFAULT_WAVE_LENGTH},
+        block::TestBlock,
+        commit::{CommitAPI as _, CommitDigest, CommitIndex, DEFAULT_WAVE_LENGTH},
         context::Context,
         leader_schedule::LeaderSchedule,
-        storage::mem_store::MemStore,
+        storage::{mem_store::MemStore, Store as _},
         test_dag::{build_dag, get_all_leader_blocks},
     };
 
@@ -152,11 +228,9 @@ mod tests {
         telemetry_subscribers::init_for_testing();
         let num_authorities = 4;
         let context = Arc::new(Context::new_for_test(num_authorities).0);
-        let dag_state = Arc::new(RwLock::new(DagState::new(
-            context.clone(),
-            Arc::new(MemStore::new()),
-        )));
-        let mut linearizer = Linearizer::new(dag_state.clone());
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let mut linearizer = Linearizer::new(context.clone(), dag_state.clone());
         let leader_schedule = LeaderSchedule::new(context.clone());
 
         // Populate fully connected test blocks for round 0 ~ 10, authorities 0 ~ 3.
@@ -196,6 +270,10 @@ mod tests {
             }
             assert_eq!(subdag.commit_index, idx as CommitIndex + 1);
         }
+
+        // Without median based commit timestamps, commits are timestamped by their leader.
+        let last_commit = store.read_last_commit().unwrap().unwrap();
+        assert!(matches!(*last_commit, Commit::V1(_)));
     }
 
     #[test]
@@ -208,7 +286,7 @@ mod tests {
             Arc::new(MemStore::new()),
         )));
         let leader_schedule = LeaderSchedule::new(context.clone());
-        let mut linearizer = Linearizer::new(dag_state.clone());
+        let mut linearizer = Linearizer::new(context.clone(), dag_state.clone());
         let wave_length = DEFAULT_WAVE_LENGTH;
 
         let mut blocks = vec![];
@@ -253,6 +331,7 @@ mod tests {
         let first_commit_data = TrustedCommit::new_for_test(
             last_commit_index,
             CommitDigest::MIN,
+            first_leader.timestamp_ms(),
             first_leader.reference(),
             blocks.clone(),
         );
@@ -304,6 +383,7 @@ mod tests {
         let expected_second_commit = TrustedCommit::new_for_test(
             last_commit_index,
             CommitDigest::MIN,
+            second_leader.timestamp_ms(),
             second_leader.reference(),
             blocks.clone(),
         );
@@ -332,4 +412,64 @@ mod tests {
             assert!(block.round() <= expected_second_commit.leader().round);
         }
     }
+
+    #[test]
+    fn test_median_based_commit_timestamp() {
+        telemetry_subscribers::init_for_testing();
+        let (mut context, _) = Context::new_for_test(4);
+        context
+            .protocol_config
+            .set_consensus_median_based_commit_timestamp_for_testing(true);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let mut linearizer = Linearizer::new(context.clone(), dag_state.clone());
+
+        // Blocks of round 2 have timestamps 2002, 2003, 2004 and 2005.
+        let parents = build_dag(context.clone(), dag_state.clone(), None, 2);
+
+        // A leader with a timestamp far in the future does not determine the commit timestamp,
+        // which is the median of the timestamps of its parents.
+        let leader = VerifiedBlock::new_for_test(
+            TestBlock::new(3, 0)
+                .set_timestamp_ms(1_000_000)
+                .set_ancestors(parents)
+                .build(),
+        );
+        dag_state.write().accept_block(leader.clone());
+        let commits = linearizer.handle_commit(vec![leader.clone()]);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].timestamp_ms, 2004);
+        assert_eq!(dag_state.read().last_commit_timestamp_ms(), 2004);
+        assert!(matches!(
+            *store.read_last_commit().unwrap().unwrap(),
+            Commit::V2(_)
+        ));
+
+        // The timestamp of a commit is never lower than the timestamp of the previous commit.
+        let parents = context
+            .committee
+            .authorities()
+            .map(|(index, _)| {
+                let block = VerifiedBlock::new_for_test(
+                    TestBlock::new(4, index.value() as u32)
+                        .set_timestamp_ms(10)
+                        .set_ancestors(vec![leader.reference()])
+                        .build(),
+                );
+                dag_state.write().accept_block(block.clone());
+                block.reference()
+            })
+            .collect::<Vec<_>>();
+        let leader = VerifiedBlock::new_for_test(
+            TestBlock::new(5, 1)
+                .set_timestamp_ms(10)
+                .set_ancestors(parents)
+                .build(),
+        );
+        dag_state.write().accept_block(leader.clone());
+        let commits = linearizer.handle_commit(vec![leader]);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].timestamp_ms, 2004);
+    }
 }
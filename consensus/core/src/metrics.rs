@@ -3,12 +3,18 @@
 
 use std::sync::Arc;
 
-use crate::network::metrics::{NetworkRouteMetrics, QuinnConnectionMetrics};
+use consensus_config::AuthorityIndex;
+
+use crate::{
+    error::ConsensusError,
+    network::metrics::{NetworkRouteMetrics, QuinnConnectionMetrics},
+};
 use prometheus::{
-    register_histogram_vec_with_registry, register_histogram_with_registry,
-    register_int_counter_vec_with_registry, register_int_counter_with_registry,
-    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Histogram,
-    HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Registry,
+    register_gauge_vec_with_registry, register_histogram_vec_with_registry,
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, GaugeVec, Histogram, HistogramVec, IntCounter, IntCounterVec,
+    IntGauge, IntGaugeVec, Registry,
 };
 
 // starts from 1μs, 50μs, 100μs...
@@ -79,12 +85,15 @@ pub(crate) struct NodeMetrics {
     pub block_timestamp_drift_wait_ms: IntCounterVec,
     pub blocks_per_commit_count: Histogram,
     pub broadcaster_rtt_estimate_ms: IntGaugeVec,
+    pub consensus_errors: IntCounterVec,
     pub core_lock_dequeued: IntCounter,
     pub core_lock_enqueued: IntCounter,
+    pub core_skipped_proposals: IntCounterVec,
     pub highest_accepted_round: IntGauge,
     pub accepted_blocks: IntCounter,
     pub dag_state_store_read_count: IntCounterVec,
     pub dag_state_store_write_count: IntCounter,
+    pub evicted_suspended_blocks: IntCounterVec,
     pub fetch_blocks_scheduler_inflight: IntGauge,
     pub fetched_blocks: IntCounterVec,
     pub invalid_blocks: IntCounterVec,
@@ -95,9 +104,23 @@ pub(crate) struct NodeMetrics {
     pub leader_timeout_total: IntCounter,
     pub missing_blocks_total: IntGauge,
     pub quorum_receive_latency: Histogram,
+    pub round_prober_own_propagation_delay: IntGauge,
+    pub round_prober_propagation_delay: IntGaugeVec,
+    pub round_prober_quorum_round: IntGaugeVec,
+    pub round_prober_request_errors: IntCounterVec,
     pub scope_processing_time: HistogramVec,
+    pub slo_authority_block_share: GaugeVec,
+    pub slo_commit_latency_ms: IntGaugeVec,
+    pub slo_commit_throughput: GaugeVec,
+    pub stall_detected: IntGauge,
+    pub stall_missing_authorities: IntGaugeVec,
+    pub stall_missing_stake: IntGauge,
     pub sub_dags_per_commit_count: Histogram,
     pub suspended_blocks: IntCounterVec,
+    pub suspended_blocks_bytes: IntGauge,
+    pub synchronizer_catching_up: IntGauge,
+    pub synchronizer_deduplicated_fetches: IntCounter,
+    pub synchronizer_lowest_missing_round: IntGauge,
     pub threshold_clock_round: IntGauge,
     pub unsuspended_blocks: IntCounterVec,
     pub uptime: Histogram,
@@ -140,6 +163,12 @@ impl NodeMetrics {
                 &["peer"],
                 registry,
             ).unwrap(),
+            consensus_errors: register_int_counter_vec_with_registry!(
+                "consensus_errors",
+                "Number of errors by error type, and by the peer that caused the error or `local` for errors not caused by a peer",
+                &["error", "peer"],
+                registry,
+            ).unwrap(),
             core_lock_dequeued: register_int_counter_with_registry!(
                 "core_lock_dequeued",
                 "Number of dequeued core requests",
@@ -150,6 +179,12 @@ impl NodeMetrics {
                 "Number of enqueued core requests",
                 registry,
             ).unwrap(),
+            core_skipped_proposals: register_int_counter_vec_with_registry!(
+                "core_skipped_proposals",
+                "Number of block proposals skipped by Core, by reason",
+                &["reason"],
+                registry,
+            ).unwrap(),
             highest_accepted_round: register_int_gauge_with_registry!(
                 "highest_accepted_round",
                 "The highest round where a block has been accepted. Resets on restart.",
@@ -171,6 +206,12 @@ impl NodeMetrics {
                 "Number of times DagState needs to write to store",
                 registry,
             ).unwrap(),
+            evicted_suspended_blocks: register_int_counter_vec_with_registry!(
+                "evicted_suspended_blocks",
+                "The number of suspended blocks evicted to bound the memory of the block manager, by the limit that was exceeded",
+                &["authority", "reason"],
+                registry,
+            ).unwrap(),
             fetch_blocks_scheduler_inflight: register_int_gauge_with_registry!(
                 "fetch_blocks_scheduler_inflight",
                 "Designates whether the synchronizer scheduler task to fetch blocks is currently running",
@@ -226,6 +267,29 @@ impl NodeMetrics {
                 "The time it took to receive a new round quorum of blocks",
                 registry
             ).unwrap(),
+            round_prober_own_propagation_delay: register_int_gauge_with_registry!(
+                "round_prober_own_propagation_delay",
+                "The number of rounds by which the own last proposed block is ahead of the highest own round received by a quorum",
+                registry,
+            ).unwrap(),
+            round_prober_propagation_delay: register_int_gauge_vec_with_registry!(
+                "round_prober_propagation_delay",
+                "The number of rounds by which the highest round of an authority received by any peer is ahead of the highest round received by a quorum",
+                &["authority"],
+                registry,
+            ).unwrap(),
+            round_prober_quorum_round: register_int_gauge_vec_with_registry!(
+                "round_prober_quorum_round",
+                "The highest round of an authority received by a quorum, as of the last probe",
+                &["authority"],
+                registry,
+            ).unwrap(),
+            round_prober_request_errors: register_int_counter_vec_with_registry!(
+                "round_prober_request_errors",
+                "Number of failed requests to peers for their highest received rounds",
+                &["peer"],
+                registry,
+            ).unwrap(),
             scope_processing_time: register_histogram_vec_with_registry!(
                 "scope_processing_time",
                 "The processing time of a specific code scope",
@@ -233,6 +297,40 @@ impl NodeMetrics {
                 FINE_GRAINED_LATENCY_SEC_BUCKETS.to_vec(),
                 registry
             ).unwrap(),
+            slo_authority_block_share: register_gauge_vec_with_registry!(
+                "slo_authority_block_share",
+                "Share of the committed blocks authored by each authority, over the trailing SLO report window",
+                &["authority"],
+                registry,
+            ).unwrap(),
+            slo_commit_latency_ms: register_int_gauge_vec_with_registry!(
+                "slo_commit_latency_ms",
+                "Percentiles of the latency in ms between leader creation and commit, over the trailing SLO report window",
+                &["quantile"],
+                registry,
+            ).unwrap(),
+            slo_commit_throughput: register_gauge_vec_with_registry!(
+                "slo_commit_throughput",
+                "Committed commits, blocks or transactions per second, over the trailing SLO report window",
+                &["unit"],
+                registry,
+            ).unwrap(),
+            stall_detected: register_int_gauge_with_registry!(
+                "stall_detected",
+                "Whether the authority is stalled, without a quorum of blocks for the next round for longer than the stall detection timeout",
+                registry,
+            ).unwrap(),
+            stall_missing_authorities: register_int_gauge_vec_with_registry!(
+                "stall_missing_authorities",
+                "Whether the block of an authority for the round a stalled authority waits on is missing",
+                &["authority"],
+                registry,
+            ).unwrap(),
+            stall_missing_stake: register_int_gauge_with_registry!(
+                "stall_missing_stake",
+                "The total stake of the authorities whose blocks are missing for the round a stalled authority waits on",
+                registry,
+            ).unwrap(),
             sub_dags_per_commit_count: register_histogram_with_registry!(
                 "sub_dags_per_commit_count",
                 "The number of subdags per commit.",
@@ -244,6 +342,26 @@ impl NodeMetrics {
                 &["authority"],
                 registry,
             ).unwrap(),
+            suspended_blocks_bytes: register_int_gauge_with_registry!(
+                "suspended_blocks_bytes",
+                "The total size in bytes of the blocks currently suspended",
+                registry,
+            ).unwrap(),
+            synchronizer_catching_up: register_int_gauge_with_registry!(
+                "synchronizer_catching_up",
+                "Designates whether the synchronizer is pacing fetches because the node is catching up with many missing blocks",
+                registry,
+            ).unwrap(),
+            synchronizer_deduplicated_fetches: register_int_counter_with_registry!(
+                "synchronizer_deduplicated_fetches",
+                "Number of missing blocks not requested by the synchronizer, as they were already being fetched",
+                registry,
+            ).unwrap(),
+            synchronizer_lowest_missing_round: register_int_gauge_with_registry!(
+                "synchronizer_lowest_missing_round",
+                "The lowest round of the missing blocks seen by the synchronizer. Its rate of increase is the catch-up progress rate.",
+                registry,
+            ).unwrap(),
             threshold_clock_round: register_int_gauge_with_registry!(
                 "threshold_clock_round",
                 "The current threshold clock round. We only advance to a new round when a quorum of parents have been synced.",
@@ -265,11 +383,29 @@ impl NodeMetrics {
     }
 }
 
+impl NodeMetrics {
+    /// Counts an occurrence of `error`, caused by `peer` if any. Errors of the authority itself
+    /// are counted as `local`, even when they happen while handling a peer.
+    pub(crate) fn record_error(&self, error: &ConsensusError, peer: Option<AuthorityIndex>) {
+        let peer = match peer {
+            Some(peer) if !error.is_local() => peer.to_string(),
+            _ => "local".to_string(),
+        };
+        self.consensus_errors
+            .with_label_values(&[error.name(), &peer])
+            .inc();
+    }
+}
+
 pub(crate) struct ChannelMetrics {
     /// occupancy of the channel from TransactionClient to TransactionConsumer
     pub tx_transactions_submit: IntGauge,
     /// total received on channel from TransactionClient to TransactionConsumer
     pub tx_transactions_submit_total: IntCounter,
+    /// occupancy of the system transactions channel from TransactionClient to TransactionConsumer
+    pub tx_system_transactions_submit: IntGauge,
+    /// total received on the system transactions channel from TransactionClient to TransactionConsumer
+    pub tx_system_transactions_submit_total: IntCounter,
     /// occupancy of the CoreThread commands channel
     pub core_thread: IntGauge,
     /// total received on the CoreThread commands channel
@@ -289,6 +425,16 @@ impl ChannelMetrics {
                 "total received on channel from the `TransactionClient` to the `TransactionConsumer`",
                 registry
             ).unwrap(),
+            tx_system_transactions_submit: register_int_gauge_with_registry!(
+                "tx_system_transactions_submit",
+                "occupancy of the system transactions channel from the `TransactionClient` to the `TransactionConsumer`",
+                registry
+            ).unwrap(),
+            tx_system_transactions_submit_total: register_int_counter_with_registry!(
+                "tx_system_transactions_submit_total",
+                "total received on the system transactions channel from the `TransactionClient` to the `TransactionConsumer`",
+                registry
+            ).unwrap(),
             core_thread: register_int_gauge_with_registry!(
                 "core_thread",
                 "occupancy of the `CoreThread` commands channel",
@@ -325,3 +471,35 @@ impl NetworkMetrics {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use consensus_config::AuthorityIndex;
+
+    use super::test_metrics;
+    use crate::error::ConsensusError;
+
+    #[test]
+    fn test_record_error() {
+        let metrics = test_metrics();
+        let node_metrics = &metrics.node_metrics;
+        let peer = AuthorityIndex::new_for_test(1);
+
+        // Errors caused by the peer are counted against it.
+        node_metrics.record_error(&ConsensusError::UnexpectedGenesisBlock, Some(peer));
+        // Errors of the authority itself are local, even while handling the peer.
+        node_metrics.record_error(&ConsensusError::Shutdown, Some(peer));
+        node_metrics.record_error(&ConsensusError::SynchronizerSaturated(peer), None);
+
+        let count = |error: &str, peer: &str| {
+            node_metrics
+                .consensus_errors
+                .with_label_values(&[error, peer])
+                .get()
+        };
+        assert_eq!(count("unexpected_genesis_block", "1"), 1);
+        assert_eq!(count("shutdown", "1"), 0);
+        assert_eq!(count("shutdown", "local"), 1);
+        assert_eq!(count("synchronizer_saturated", "local"), 1);
+    }
+}
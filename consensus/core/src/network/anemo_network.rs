@@ -24,19 +24,24 @@ use prometheus::HistogramTimer;
 use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, error, warn};
 
+#[cfg(test)]
+use super::fault_injection::FaultInjector;
 use super::{
     anemo_gen::{
         consensus_rpc_client::ConsensusRpcClient,
         consensus_rpc_server::{ConsensusRpc, ConsensusRpcServer},
     },
+    block_encryption::BlockEncryption,
     connection_monitor::{AnemoConnectionMonitor, ConnectionMonitorHandle},
     epoch_filter::{AllowedEpoch, EPOCH_HEADER_KEY},
     metrics::NetworkRouteMetrics,
-    FetchBlocksRequest, FetchBlocksResponse, NetworkClient, NetworkManager, NetworkService,
-    SendBlockRequest, SendBlockResponse,
+    FetchBlocksRequest, FetchBlocksResponse, GetLatestRoundsRequest, GetLatestRoundsResponse,
+    NetworkClient, NetworkManager, NetworkService, ReportRejectedBlocksRequest,
+    ReportRejectedBlocksResponse, SendBlockRequest, SendBlockResponse,
 };
 use crate::{
-    block::{BlockRef, VerifiedBlock},
+    block::{BlockRef, Round, VerifiedBlock},
+    block_manager::BlockRejection,
     context::Context,
     error::{ConsensusError, ConsensusResult},
 };
@@ -45,6 +50,9 @@ use crate::{
 pub(crate) struct AnemoClient {
     context: Arc<Context>,
     network: Arc<ArcSwapOption<anemo::Network>>,
+    block_encryption: Arc<BlockEncryption>,
+    #[cfg(test)]
+    fault_injector: Arc<FaultInjector>,
 }
 
 impl AnemoClient {
@@ -52,8 +60,11 @@ impl AnemoClient {
 
     pub(crate) fn new(context: Arc<Context>) -> Self {
         Self {
+            #[cfg(test)]
+            fault_injector: Arc::new(FaultInjector::new(context.own_index.value() as u64)),
             context,
             network: Arc::new(ArcSwapOption::default()),
+            block_encryption: Arc::new(BlockEncryption::new()),
         }
     }
 
@@ -128,9 +139,13 @@ impl NetworkClient for AnemoClient {
         block: &VerifiedBlock,
         timeout: Duration,
     ) -> ConsensusResult<()> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
         let mut client = self.get_client(peer, timeout).await?;
         let request = SendBlockRequest {
-            block: block.serialized().clone(),
+            block: self.block_encryption.seal(peer, block.serialized())?,
         };
         client
             .send_block(anemo::Request::new(request).with_timeout(timeout))
@@ -145,6 +160,10 @@ impl NetworkClient for AnemoClient {
         block_refs: Vec<BlockRef>,
         timeout: Duration,
     ) -> ConsensusResult<Vec<Bytes>> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
         let mut client = self.get_client(peer, timeout).await?;
         let request = FetchBlocksRequest {
             block_refs: block_refs
@@ -162,7 +181,53 @@ impl NetworkClient for AnemoClient {
             .fetch_blocks(anemo::Request::new(request).with_timeout(timeout))
             .await
             .map_err(|e| ConsensusError::NetworkError(format!("fetch_blocks failed: {e:?}")))?;
-        Ok(response.into_body().blocks)
+        response
+            .into_body()
+            .blocks
+            .into_iter()
+            .map(|block| self.block_encryption.open(peer, block))
+            .collect()
+    }
+
+    async fn get_latest_rounds(
+        &self,
+        peer: AuthorityIndex,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<Round>> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
+        let mut client = self.get_client(peer, timeout).await?;
+        let request = GetLatestRoundsRequest {};
+        let response = client
+            .get_latest_rounds(anemo::Request::new(request).with_timeout(timeout))
+            .await
+            .map_err(|e| {
+                ConsensusError::NetworkError(format!("get_latest_rounds failed: {e:?}"))
+            })?;
+        Ok(response.into_body().highest_received)
+    }
+
+    async fn report_rejected_blocks(
+        &self,
+        peer: AuthorityIndex,
+        rejections: &[BlockRejection],
+        timeout: Duration,
+    ) -> ConsensusResult<()> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
+        let mut client = self.get_client(peer, timeout).await?;
+        let request = ReportRejectedBlocksRequest::new(peer, rejections, &self.block_encryption)?;
+        client
+            .report_rejected_blocks(anemo::Request::new(request).with_timeout(timeout))
+            .await
+            .map_err(|e| {
+                ConsensusError::NetworkError(format!("report_rejected_blocks failed: {e:?}"))
+            })?;
+        Ok(())
     }
 }
 
@@ -170,10 +235,11 @@ impl NetworkClient for AnemoClient {
 struct AnemoServiceProxy<S: NetworkService> {
     peer_map: BTreeMap<PeerId, AuthorityIndex>,
     service: Arc<S>,
+    block_encryption: Arc<BlockEncryption>,
 }
 
 impl<S: NetworkService> AnemoServiceProxy<S> {
-    fn new(context: Arc<Context>, service: Arc<S>) -> Self {
+    fn new(context: Arc<Context>, service: Arc<S>, block_encryption: Arc<BlockEncryption>) -> Self {
         let peer_map = context
             .committee
             .authorities()
@@ -182,7 +248,11 @@ impl<S: NetworkService> AnemoServiceProxy<S> {
                 (peer_id, index)
             })
             .collect();
-        Self { peer_map, service }
+        Self {
+            peer_map,
+            service,
+            block_encryption,
+        }
     }
 }
 
@@ -204,7 +274,15 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                 "peer not found",
             )
         })?;
-        let block = request.into_body().block;
+        let block = self
+            .block_encryption
+            .open(*index, request.into_body().block)
+            .map_err(|e| {
+                anemo::rpc::Status::new_with_message(
+                    anemo::types::response::StatusCode::BadRequest,
+                    format!("{e}"),
+                )
+            })?;
         self.service
             .handle_send_block(*index, block)
             .await
@@ -255,8 +333,84 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                     format!("{e}"),
                 )
             })?;
+        let blocks = blocks
+            .iter()
+            .map(|block| self.block_encryption.seal(*index, block))
+            .collect::<ConsensusResult<_>>()
+            .map_err(|e| {
+                anemo::rpc::Status::new_with_message(
+                    anemo::types::response::StatusCode::InternalServerError,
+                    format!("{e}"),
+                )
+            })?;
         Ok(Response::new(FetchBlocksResponse { blocks }))
     }
+
+    async fn get_latest_rounds(
+        &self,
+        request: anemo::Request<GetLatestRoundsRequest>,
+    ) -> Result<anemo::Response<GetLatestRoundsResponse>, anemo::rpc::Status> {
+        let Some(peer_id) = request.peer_id() else {
+            return Err(anemo::rpc::Status::new_with_message(
+                anemo::types::response::StatusCode::BadRequest,
+                "peer_id not found",
+            ));
+        };
+        let index = self.peer_map.get(peer_id).ok_or_else(|| {
+            anemo::rpc::Status::new_with_message(
+                anemo::types::response::StatusCode::BadRequest,
+                "peer not found",
+            )
+        })?;
+        let highest_received = self
+            .service
+            .handle_get_latest_rounds(*index)
+            .await
+            .map_err(|e| {
+                anemo::rpc::Status::new_with_message(
+                    anemo::types::response::StatusCode::InternalServerError,
+                    format!("{e}"),
+                )
+            })?;
+        Ok(Response::new(GetLatestRoundsResponse { highest_received }))
+    }
+
+    async fn report_rejected_blocks(
+        &self,
+        request: anemo::Request<ReportRejectedBlocksRequest>,
+    ) -> Result<anemo::Response<ReportRejectedBlocksResponse>, anemo::rpc::Status> {
+        let Some(peer_id) = request.peer_id() else {
+            return Err(anemo::rpc::Status::new_with_message(
+                anemo::types::response::StatusCode::BadRequest,
+                "peer_id not found",
+            ));
+        };
+        let index = self.peer_map.get(peer_id).ok_or_else(|| {
+            anemo::rpc::Status::new_with_message(
+                anemo::types::response::StatusCode::BadRequest,
+                "peer not found",
+            )
+        })?;
+        let rejections = request
+            .into_body()
+            .open(*index, &self.block_encryption)
+            .map_err(|e| {
+                anemo::rpc::Status::new_with_message(
+                    anemo::types::response::StatusCode::BadRequest,
+                    format!("{e}"),
+                )
+            })?;
+        self.service
+            .handle_report_rejected_blocks(*index, rejections)
+            .await
+            .map_err(|e| {
+                anemo::rpc::Status::new_with_message(
+                    anemo::types::response::StatusCode::BadRequest,
+                    format!("{e}"),
+                )
+            })?;
+        Ok(Response::new(ReportRejectedBlocksResponse {}))
+    }
 }
 
 /// Manages the lifecycle of Anemo network. Typical usage during initialization:
@@ -294,6 +448,11 @@ impl<S: NetworkService> NetworkManager<S> for AnemoManager {
         self.client.clone()
     }
 
+    #[cfg(test)]
+    fn fault_injector(&self) -> Arc<FaultInjector> {
+        self.client.fault_injector.clone()
+    }
+
     async fn install_service(&mut self, network_keypair: NetworkKeyPair, service: Arc<S>) {
         self.context
             .metrics
@@ -302,7 +461,14 @@ impl<S: NetworkService> NetworkManager<S> for AnemoManager {
             .with_label_values(&["anemo"])
             .set(1);
 
-        let server = ConsensusRpcServer::new(AnemoServiceProxy::new(self.context.clone(), service));
+        self.client
+            .block_encryption
+            .install(&self.context, &network_keypair);
+        let server = ConsensusRpcServer::new(AnemoServiceProxy::new(
+            self.context.clone(),
+            service,
+            self.client.block_encryption.clone(),
+        ));
         let authority = self.context.committee.authority(self.context.own_index);
         // Bind to localhost in unit tests since only local networking is needed.
         // Bind to the unspecified address to allow the actual address to be assigned,
@@ -611,7 +777,8 @@ mod test {
     use tokio::time::sleep;
 
     use crate::{
-        block::{BlockRef, TestBlock, VerifiedBlock},
+        block::{BlockRef, Round, TestBlock, VerifiedBlock},
+        block_manager::RejectionReason,
         context::Context,
         error::ConsensusResult,
         network::{anemo_network::AnemoManager, NetworkClient, NetworkManager, NetworkService},
@@ -650,6 +817,21 @@ mod test {
             self.lock().handle_fetch_blocks.push((peer, block_refs));
             Ok(vec![])
         }
+
+        async fn handle_get_latest_rounds(
+            &self,
+            _peer: AuthorityIndex,
+        ) -> ConsensusResult<Vec<Round>> {
+            Ok(vec![0; 4])
+        }
+
+        async fn handle_report_rejected_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _rejections: Vec<(Bytes, RejectionReason)>,
+        ) -> ConsensusResult<()> {
+            unimplemented!("Unimplemented")
+        }
     }
 
     #[tokio::test(flavor = "current_thread", start_paused = true)]
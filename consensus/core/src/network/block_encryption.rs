@@ -0,0 +1,205 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use aes_gcm::{
+    aead::{Aead as _, KeyInit as _},
+    Aes256Gcm, Nonce,
+};
+use arc_swap::ArcSwapOption;
+use bytes::{BufMut as _, Bytes, BytesMut};
+use consensus_config::{AuthorityIndex, DefaultHashFunction, NetworkKeyPair};
+use fastcrypto::hash::HashFunction as _;
+use rand::{rngs::OsRng, RngCore as _};
+use tracing::warn;
+
+use crate::{
+    context::Context,
+    error::{ConsensusError, ConsensusResult},
+};
+
+const NONCE_LENGTH: usize = 12;
+const KEY_DERIVATION_DOMAIN: &[u8] = b"consensus-block-encryption";
+
+/// Encrypts serialized blocks exchanged with peers, when enabled by the protocol config.
+///
+/// Each pair of authorities shares a key derived from the Diffie-Hellman secret of their network
+/// keys and the epoch, so a block sent to a peer can only be read by that peer, and keys rotate
+/// with the committee. Encryption is applied on top of the channel security of the network
+/// implementation. A sealed block is the random nonce followed by the AES-256-GCM ciphertext.
+///
+/// The cipher is shared by the network client and service of an authority. It is installed
+/// together with the network service, which is when the network key pair is available. Until
+/// then, and when encryption is disabled, blocks pass through unchanged.
+#[derive(Default)]
+pub(crate) struct BlockEncryption {
+    ciphers: ArcSwapOption<Vec<Option<Aes256Gcm>>>,
+}
+
+impl BlockEncryption {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derives the keys shared with every peer of the committee, if block encryption is enabled
+    /// for the epoch of `context`.
+    pub(crate) fn install(&self, context: &Context, network_keypair: &NetworkKeyPair) {
+        if !context.protocol_config.consensus_block_encryption() {
+            return;
+        }
+        let ciphers = context
+            .committee
+            .authorities()
+            .map(|(index, authority)| {
+                if index == context.own_index {
+                    return None;
+                }
+                let Some(shared_secret) = network_keypair.shared_secret(&authority.network_key)
+                else {
+                    warn!("Cannot derive block encryption key for authority {index}");
+                    return None;
+                };
+                let (a, b) = if context.own_index < index {
+                    (context.own_index, index)
+                } else {
+                    (index, context.own_index)
+                };
+                let mut hasher = DefaultHashFunction::new();
+                hasher.update(KEY_DERIVATION_DOMAIN);
+                hasher.update(shared_secret);
+                hasher.update(context.committee.epoch().to_le_bytes());
+                hasher.update((a.value() as u64).to_le_bytes());
+                hasher.update((b.value() as u64).to_le_bytes());
+                Aes256Gcm::new_from_slice(&hasher.finalize().digest).ok()
+            })
+            .collect();
+        self.ciphers.store(Some(Arc::new(ciphers)));
+    }
+
+    /// Encrypts a serialized block to be sent to `peer`.
+    pub(crate) fn seal(&self, peer: AuthorityIndex, block: &Bytes) -> ConsensusResult<Bytes> {
+        let Some(ciphers) = self.ciphers.load_full() else {
+            return Ok(block.clone());
+        };
+        let cipher = Self::cipher(&ciphers, peer)?;
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), block.as_ref())
+            .map_err(|_| {
+                ConsensusError::BlockEncryptionFailure(format!("cannot encrypt block for {peer}"))
+            })?;
+        let mut sealed = BytesMut::with_capacity(NONCE_LENGTH + ciphertext.len());
+        sealed.put_slice(&nonce);
+        sealed.put_slice(&ciphertext);
+        Ok(sealed.freeze())
+    }
+
+    /// Decrypts a serialized block received from `peer`.
+    pub(crate) fn open(&self, peer: AuthorityIndex, sealed: Bytes) -> ConsensusResult<Bytes> {
+        let Some(ciphers) = self.ciphers.load_full() else {
+            return Ok(sealed);
+        };
+        let cipher = Self::cipher(&ciphers, peer)?;
+        if sealed.len() < NONCE_LENGTH {
+            return Err(ConsensusError::BlockEncryptionFailure(format!(
+                "encrypted block from {peer} is too short"
+            )));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+        let block = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                ConsensusError::BlockEncryptionFailure(format!("cannot decrypt block from {peer}"))
+            })?;
+        Ok(block.into())
+    }
+
+    fn cipher(ciphers: &[Option<Aes256Gcm>], peer: AuthorityIndex) -> ConsensusResult<&Aes256Gcm> {
+        ciphers
+            .get(peer.value())
+            .and_then(Option::as_ref)
+            .ok_or_else(|| {
+                ConsensusError::BlockEncryptionFailure(format!("no encryption key for {peer}"))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bytes::Bytes;
+    use consensus_config::{AuthorityIndex, NetworkKeyPair, ProtocolKeyPair};
+
+    use super::BlockEncryption;
+    use crate::context::Context;
+
+    fn install(
+        context: &Context,
+        keypairs: &[(NetworkKeyPair, ProtocolKeyPair)],
+        index: u32,
+    ) -> BlockEncryption {
+        let context = context
+            .clone()
+            .with_authority_index(AuthorityIndex::new_for_test(index));
+        let encryption = BlockEncryption::new();
+        encryption.install(&context, &keypairs[index as usize].0);
+        encryption
+    }
+
+    #[test]
+    fn test_sealed_block_is_only_readable_by_peer() {
+        let (mut context, keypairs) = Context::new_for_test(3);
+        context
+            .protocol_config
+            .set_consensus_block_encryption_for_testing(true);
+        let encryption_0 = install(&context, &keypairs, 0);
+        let encryption_1 = install(&context, &keypairs, 1);
+        let encryption_2 = install(&context, &keypairs, 2);
+        let (index_0, index_1, index_2) = (
+            AuthorityIndex::new_for_test(0),
+            AuthorityIndex::new_for_test(1),
+            AuthorityIndex::new_for_test(2),
+        );
+
+        let block = Bytes::from_static(b"serialized block with transactions");
+        let sealed = encryption_0.seal(index_1, &block).unwrap();
+        assert_ne!(sealed, block);
+        assert!(!sealed
+            .windows(block.len())
+            .any(|window| window == block.as_ref()));
+
+        // Only the intended peer can decrypt the block, and only as coming from the sender.
+        assert_eq!(encryption_1.open(index_0, sealed.clone()).unwrap(), block);
+        assert!(encryption_2.open(index_0, sealed.clone()).is_err());
+        assert!(encryption_1.open(index_2, sealed.clone()).is_err());
+
+        // Tampered blocks are rejected.
+        let mut tampered = sealed.to_vec();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(encryption_1.open(index_0, tampered.into()).is_err());
+        assert!(encryption_1
+            .open(index_0, Bytes::from_static(b"short"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_disabled_encryption_passes_blocks_through() {
+        let (context, keypairs) = Context::new_for_test(2);
+        assert!(!context.protocol_config.consensus_block_encryption());
+        let encryption_0 = install(&context, &keypairs, 0);
+        let encryption_1 = install(&context, &keypairs, 1);
+
+        let block = Bytes::from_static(b"serialized block");
+        let sealed = encryption_0
+            .seal(AuthorityIndex::new_for_test(1), &block)
+            .unwrap();
+        assert_eq!(sealed, block);
+        assert_eq!(
+            encryption_1
+                .open(AuthorityIndex::new_for_test(0), sealed)
+                .unwrap(),
+            block
+        );
+    }
+}
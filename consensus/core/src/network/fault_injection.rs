@@ -0,0 +1,228 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeSet, time::Duration};
+
+use consensus_config::AuthorityIndex;
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, Rng as _, SeedableRng as _};
+
+use crate::error::{ConsensusError, ConsensusResult};
+
+/// Distribution of the delay injected before each outgoing request.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) enum DelayDistribution {
+    #[default]
+    None,
+    Fixed(Duration),
+    /// Uniformly distributed in `[min, max]`.
+    Uniform {
+        min: Duration,
+        max: Duration,
+    },
+}
+
+#[derive(Clone, Debug, Default)]
+struct FaultConfig {
+    // Probability in [0, 1] that a request is dropped.
+    drop_rate: f64,
+    delay: DelayDistribution,
+    // Authorities in different sets cannot reach each other. Authorities not in any set are
+    // unaffected.
+    partitions: Vec<BTreeSet<AuthorityIndex>>,
+}
+
+impl FaultConfig {
+    fn partitioned(&self, a: AuthorityIndex, b: AuthorityIndex) -> bool {
+        let find = |index| self.partitions.iter().position(|set| set.contains(&index));
+        match (find(a), find(b)) {
+            (Some(pa), Some(pb)) => pa != pb,
+            _ => false,
+        }
+    }
+}
+
+/// Injects faults into the outgoing requests of a `NetworkClient`. Only available in tests,
+/// so network pathologies can be reproduced deterministically: the random number generator
+/// is seeded, and faults are applied in the client before any request reaches the network.
+pub(crate) struct FaultInjector {
+    inner: Mutex<FaultInjectorInner>,
+}
+
+struct FaultInjectorInner {
+    config: FaultConfig,
+    rng: StdRng,
+}
+
+impl FaultInjector {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            inner: Mutex::new(FaultInjectorInner {
+                config: FaultConfig::default(),
+                rng: StdRng::seed_from_u64(seed),
+            }),
+        }
+    }
+
+    /// Reseeds the random number generator used to sample drops and delays.
+    pub(crate) fn reseed(&self, seed: u64) {
+        self.inner.lock().rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Sets the probability in [0, 1] that an outgoing request is dropped.
+    pub(crate) fn set_drop_rate(&self, drop_rate: f64) {
+        assert!(
+            (0.0..=1.0).contains(&drop_rate),
+            "Invalid drop rate: {drop_rate}"
+        );
+        self.inner.lock().config.drop_rate = drop_rate;
+    }
+
+    /// Sets the distribution of the delay injected before each outgoing request.
+    pub(crate) fn set_delay(&self, delay: DelayDistribution) {
+        if let DelayDistribution::Uniform { min, max } = &delay {
+            assert!(min <= max, "Invalid delay range: {min:?} > {max:?}");
+        }
+        self.inner.lock().config.delay = delay;
+    }
+
+    /// Partitions the network so that authorities in different sets cannot reach each other.
+    /// Replaces any existing partitions.
+    pub(crate) fn partition(&self, partitions: Vec<BTreeSet<AuthorityIndex>>) {
+        self.inner.lock().config.partitions = partitions;
+    }
+
+    /// Removes all partitions.
+    pub(crate) fn heal(&self) {
+        self.inner.lock().config.partitions.clear();
+    }
+
+    /// Removes all injected faults.
+    pub(crate) fn reset(&self) {
+        self.inner.lock().config = FaultConfig::default();
+    }
+
+    /// Applies the configured faults to a request from `own_index` to `peer`, sleeping for the
+    /// sampled delay. Returns an error if the request should not be sent.
+    pub(crate) async fn apply(
+        &self,
+        own_index: AuthorityIndex,
+        peer: AuthorityIndex,
+        timeout: Duration,
+    ) -> ConsensusResult<()> {
+        let (dropped, delay) = {
+            let mut inner = self.inner.lock();
+            if inner.config.partitioned(own_index, peer) {
+                return Err(ConsensusError::NetworkError(format!(
+                    "Injected fault: {own_index} is partitioned from {peer}"
+                )));
+            }
+            let drop_rate = inner.config.drop_rate;
+            let dropped = drop_rate > 0.0 && inner.rng.gen_bool(drop_rate);
+            let delay = match inner.config.delay.clone() {
+                DelayDistribution::None => Duration::ZERO,
+                DelayDistribution::Fixed(delay) => delay,
+                DelayDistribution::Uniform { min, max } => inner.rng.gen_range(min..=max),
+            };
+            (dropped, delay)
+        };
+
+        if dropped {
+            return Err(ConsensusError::NetworkError(format!(
+                "Injected fault: request from {own_index} to {peer} dropped"
+            )));
+        }
+        if delay >= timeout {
+            tokio::time::sleep(timeout).await;
+            return Err(ConsensusError::NetworkError(format!(
+                "Injected fault: request from {own_index} to {peer} timed out after {timeout:?}"
+            )));
+        }
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeSet, time::Duration};
+
+    use consensus_config::AuthorityIndex;
+    use tokio::time::Instant;
+
+    use super::{DelayDistribution, FaultInjector};
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn index(i: u32) -> AuthorityIndex {
+        AuthorityIndex::new_for_test(i)
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_partition() {
+        let injector = FaultInjector::new(0);
+        injector.partition(vec![
+            BTreeSet::from([index(0), index(1)]),
+            BTreeSet::from([index(2)]),
+        ]);
+
+        assert!(injector.apply(index(0), index(1), TIMEOUT).await.is_ok());
+        assert!(injector.apply(index(0), index(2), TIMEOUT).await.is_err());
+        assert!(injector.apply(index(2), index(1), TIMEOUT).await.is_err());
+        // Authorities outside of any partition are unaffected.
+        assert!(injector.apply(index(3), index(2), TIMEOUT).await.is_ok());
+        assert!(injector.apply(index(0), index(3), TIMEOUT).await.is_ok());
+
+        injector.heal();
+        assert!(injector.apply(index(0), index(2), TIMEOUT).await.is_ok());
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_drop_rate_is_deterministic() {
+        let sample = |seed| async move {
+            let injector = FaultInjector::new(0);
+            injector.reseed(seed);
+            injector.set_drop_rate(0.5);
+            let mut results = vec![];
+            for _ in 0..100 {
+                results.push(injector.apply(index(0), index(1), TIMEOUT).await.is_ok());
+            }
+            results
+        };
+
+        let results = sample(42).await;
+        assert_eq!(results, sample(42).await);
+        let delivered = results.iter().filter(|ok| **ok).count();
+        assert!(delivered > 0 && delivered < 100);
+
+        let injector = FaultInjector::new(42);
+        injector.set_drop_rate(1.0);
+        assert!(injector.apply(index(0), index(1), TIMEOUT).await.is_err());
+        injector.reset();
+        assert!(injector.apply(index(0), index(1), TIMEOUT).await.is_ok());
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_delay() {
+        let injector = FaultInjector::new(0);
+
+        injector.set_delay(DelayDistribution::Fixed(Duration::from_secs(1)));
+        let start = Instant::now();
+        assert!(injector.apply(index(0), index(1), TIMEOUT).await.is_ok());
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+
+        let (min, max) = (Duration::from_secs(2), Duration::from_secs(3));
+        injector.set_delay(DelayDistribution::Uniform { min, max });
+        let start = Instant::now();
+        assert!(injector.apply(index(0), index(1), TIMEOUT).await.is_ok());
+        assert!(start.elapsed() >= min && start.elapsed() <= max);
+
+        // Delays exceeding the request timeout fail the request once the timeout elapses.
+        injector.set_delay(DelayDistribution::Fixed(Duration::from_secs(20)));
+        let start = Instant::now();
+        assert!(injector.apply(index(0), index(1), TIMEOUT).await.is_err());
+        assert_eq!(start.elapsed(), TIMEOUT);
+    }
+}
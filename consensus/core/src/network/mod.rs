@@ -9,9 +9,10 @@ use consensus_config::{AuthorityIndex, NetworkKeyPair};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    block::{BlockRef, VerifiedBlock},
+    block::{BlockRef, Round, VerifiedBlock},
+    block_manager::{BlockRejection, RejectionReason},
     context::Context,
-    error::ConsensusResult,
+    error::{ConsensusError, ConsensusResult},
 };
 
 // Anemo generated stubs for RPCs.
@@ -24,11 +25,18 @@ mod tonic_gen {
 }
 
 pub(crate) mod anemo_network;
+pub(crate) mod block_encryption;
 pub(crate) mod connection_monitor;
 pub(crate) mod epoch_filter;
+#[cfg(test)]
+pub(crate) mod fault_injection;
 pub(crate) mod metrics;
+pub(crate) mod quic_network;
 pub(crate) mod tonic_network;
 
+/// The maximum number of rejections reported in one request.
+pub(crate) const MAX_REJECTIONS_PER_REPORT: usize = 100;
+
 /// Network client for communicating with peers.
 ///
 /// NOTE: the timeout parameters help saving resources at client and potentially server.
@@ -51,6 +59,21 @@ pub(crate) trait NetworkClient: Send + Sync + 'static {
         block_refs: Vec<BlockRef>,
         timeout: Duration,
     ) -> ConsensusResult<Vec<Bytes>>;
+
+    /// Gets the highest round received by a peer from each authority, indexed by authority.
+    async fn get_latest_rounds(
+        &self,
+        peer: AuthorityIndex,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<Round>>;
+
+    /// Reports blocks rejected by this authority to a peer, with the reasons of the rejections.
+    async fn report_rejected_blocks(
+        &self,
+        peer: AuthorityIndex,
+        rejections: &[BlockRejection],
+        timeout: Duration,
+    ) -> ConsensusResult<()>;
 }
 
 /// Network service for handling requests from peers.
@@ -64,6 +87,12 @@ pub(crate) trait NetworkService: Send + Sync + 'static {
         peer: AuthorityIndex,
         block_refs: Vec<BlockRef>,
     ) -> ConsensusResult<Vec<Bytes>>;
+    async fn handle_get_latest_rounds(&self, peer: AuthorityIndex) -> ConsensusResult<Vec<Round>>;
+    async fn handle_report_rejected_blocks(
+        &self,
+        peer: AuthorityIndex,
+        rejections: Vec<(Bytes, RejectionReason)>,
+    ) -> ConsensusResult<()>;
 }
 
 /// An `AuthorityNode` holds a `NetworkManager` until shutdown.
@@ -80,6 +109,10 @@ where
     /// Returns the network client.
     fn client(&self) -> Arc<Self::Client>;
 
+    /// Returns the fault injector applied to outgoing requests of the network client.
+    #[cfg(test)]
+    fn fault_injector(&self) -> Arc<fault_injection::FaultInjector>;
+
     /// Installs network service.
     async fn install_service(&mut self, network_keypair: NetworkKeyPair, service: Arc<S>);
 
@@ -110,3 +143,58 @@ pub(crate) struct FetchBlocksResponse {
     #[prost(bytes = "bytes", repeated, tag = "1")]
     blocks: Vec<Bytes>,
 }
+
+#[derive(Clone, Serialize, Deserialize, prost::Message)]
+pub(crate) struct GetLatestRoundsRequest {}
+
+#[derive(Clone, Serialize, Deserialize, prost::Message)]
+pub(crate) struct GetLatestRoundsResponse {
+    // Highest received round per authority.
+    #[prost(uint32, repeated, tag = "1")]
+    highest_received: Vec<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize, prost::Message)]
+pub(crate) struct ReportRejectedBlocksRequest {
+    // Serialized pairs of a sealed SignedBlock and the RejectionReason of the block.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    rejections: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Serialize, Deserialize, prost::Message)]
+pub(crate) struct ReportRejectedBlocksResponse {}
+
+impl ReportRejectedBlocksRequest {
+    /// Serializes the rejections to report to `peer`, sealing the rejected blocks for it.
+    fn new(
+        peer: AuthorityIndex,
+        rejections: &[BlockRejection],
+        block_encryption: &block_encryption::BlockEncryption,
+    ) -> ConsensusResult<Self> {
+        let rejections = rejections
+            .iter()
+            .map(|rejection| {
+                let block = block_encryption.seal(peer, rejection.block.serialized())?;
+                bcs::to_bytes(&(block, &rejection.reason))
+                    .map_err(ConsensusError::SerializationFailure)
+            })
+            .collect::<ConsensusResult<_>>()?;
+        Ok(Self { rejections })
+    }
+
+    /// Deserializes the rejections reported by `peer`, opening the rejected blocks.
+    fn open(
+        self,
+        peer: AuthorityIndex,
+        block_encryption: &block_encryption::BlockEncryption,
+    ) -> ConsensusResult<Vec<(Bytes, RejectionReason)>> {
+        self.rejections
+            .into_iter()
+            .map(|serialized| {
+                let (block, reason): (Bytes, RejectionReason) =
+                    bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedRejection)?;
+                Ok((block_encryption.open(peer, block)?, reason))
+            })
+            .collect()
+    }
+}
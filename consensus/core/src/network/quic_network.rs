@@ -0,0 +1,863 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use arc_swap::ArcSwapOption;
+use async_trait::async_trait;
+use bytes::Bytes;
+use cfg_if::cfg_if;
+use consensus_config::{AuthorityIndex, NetworkKeyPair, NetworkPublicKey};
+use fastcrypto::{ed25519::Ed25519PrivateKey, traits::ToFromBytes as _};
+use parking_lot::RwLock;
+use quinn::{
+    ClientConfig, Connecting, Connection, Endpoint, IdleTimeout, RecvStream, SendStream,
+    ServerConfig, TransportConfig,
+};
+use serde::{Deserialize, Serialize};
+use sui_tls::{
+    public_key_from_certificate, CertVerifier, HashSetAllow, SelfSignedCertificate,
+    SUI_VALIDATOR_SERVER_NAME,
+};
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+
+#[cfg(test)]
+use super::fault_injection::FaultInjector;
+use super::{
+    block_encryption::BlockEncryption,
+    tonic_network::{to_host_port_str, to_socket_addr},
+    FetchBlocksRequest, FetchBlocksResponse, GetLatestRoundsRequest, GetLatestRoundsResponse,
+    NetworkClient, NetworkManager, NetworkService, ReportRejectedBlocksRequest,
+    ReportRejectedBlocksResponse, SendBlockRequest, SendBlockResponse,
+};
+use crate::{
+    block::{BlockRef, Round, VerifiedBlock},
+    block_manager::BlockRejection,
+    context::Context,
+    error::{ConsensusError, ConsensusResult},
+};
+
+/// Application protocol negotiated by QUIC endpoints.
+const ALPN_PROTOCOL: &[u8] = b"sui-consensus";
+
+/// A request to a peer. Each request is sent on its own bidirectional stream, which the peer
+/// answers with a `QuicResponse` on the same stream. The sender of a request is the authority
+/// whose network key the connection is authenticated with.
+#[derive(Serialize, Deserialize)]
+enum QuicRequest {
+    SendBlock(SendBlockRequest),
+    FetchBlocks(FetchBlocksRequest),
+    GetLatestRounds(GetLatestRoundsRequest),
+    ReportRejectedBlocks(ReportRejectedBlocksRequest),
+}
+
+#[derive(Serialize, Deserialize)]
+enum QuicResponse {
+    SendBlock(SendBlockResponse),
+    FetchBlocks(FetchBlocksResponse),
+    GetLatestRounds(GetLatestRoundsResponse),
+    ReportRejectedBlocks(ReportRejectedBlocksResponse),
+    Error(String),
+}
+
+/// Implements QUIC RPC client for Consensus.
+pub(crate) struct QuicClient {
+    context: Arc<Context>,
+    // The endpoint of the server, which outgoing connections are also made from. It is set once
+    // the service is installed.
+    endpoint: ArcSwapOption<ClientEndpoint>,
+    // Size is limited by known authorities in the committee.
+    connections: RwLock<BTreeMap<AuthorityIndex, Connection>>,
+    block_encryption: Arc<BlockEncryption>,
+    #[cfg(test)]
+    fault_injector: Arc<FaultInjector>,
+}
+
+/// The endpoint outgoing connections are made from, with the configuration to connect to each
+/// peer of the committee.
+struct ClientEndpoint {
+    endpoint: Endpoint,
+    configs: BTreeMap<AuthorityIndex, ClientConfig>,
+}
+
+impl QuicClient {
+    const GET_ENDPOINT_INTERVAL: Duration = Duration::from_millis(10);
+
+    pub(crate) fn new(context: Arc<Context>) -> Self {
+        Self {
+            #[cfg(test)]
+            fault_injector: Arc::new(FaultInjector::new(context.own_index.value() as u64)),
+            context,
+            endpoint: ArcSwapOption::default(),
+            connections: RwLock::new(BTreeMap::new()),
+            block_encryption: Arc::new(BlockEncryption::new()),
+        }
+    }
+
+    fn set_endpoint(&self, endpoint: ClientEndpoint) {
+        self.endpoint.store(Some(Arc::new(endpoint)));
+    }
+
+    async fn get_connection(
+        &self,
+        peer: AuthorityIndex,
+        timeout: Duration,
+    ) -> ConsensusResult<Connection> {
+        {
+            let connections = self.connections.read();
+            if let Some(connection) = connections.get(&peer) {
+                if connection.close_reason().is_none() {
+                    return Ok(connection.clone());
+                }
+            }
+        }
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let endpoint = loop {
+            if let Some(endpoint) = self.endpoint.load_full() {
+                break endpoint;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(ConsensusError::NetworkError(
+                    "QUIC endpoint has not been started".to_string(),
+                ));
+            }
+            tokio::time::sleep(Self::GET_ENDPOINT_INTERVAL).await;
+        };
+
+        let authority = self.context.committee.authority(peer);
+        let address = to_host_port_str(&authority.address).map_err(|e| {
+            ConsensusError::NetworkError(format!("Cannot convert address to host:port: {e:?}"))
+        })?;
+        let socket_address = tokio::net::lookup_host(&address)
+            .await
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| ConsensusError::NetworkError(format!("Cannot resolve {address}")))?;
+
+        let connection = loop {
+            let connecting = endpoint
+                .endpoint
+                .connect_with(
+                    endpoint.configs[&peer].clone(),
+                    socket_address,
+                    SUI_VALIDATOR_SERVER_NAME,
+                )
+                .map_err(|e| {
+                    ConsensusError::NetworkError(format!("Cannot connect to {address}: {e:?}"))
+                })?;
+            match tokio::time::timeout_at(deadline, connecting).await {
+                Ok(Ok(connection)) => break connection,
+                Ok(Err(e)) => warn!("Failed to connect to {address}: {e:?}"),
+                Err(_) => {}
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(ConsensusError::NetworkError(format!(
+                    "Timed out connecting to {address}"
+                )));
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        };
+
+        let mut connections = self.connections.write();
+        // There should not be many concurrent attempts at connecting to the same peer.
+        match connections.get(&peer) {
+            Some(existing) if existing.close_reason().is_none() => Ok(existing.clone()),
+            _ => {
+                connections.insert(peer, connection.clone());
+                Ok(connection)
+            }
+        }
+    }
+
+    async fn request(
+        &self,
+        peer: AuthorityIndex,
+        request: QuicRequest,
+        timeout: Duration,
+    ) -> ConsensusResult<QuicResponse> {
+        let connection = self.get_connection(peer, timeout).await?;
+        let request = bcs::to_bytes(&request).map_err(ConsensusError::SerializationFailure)?;
+        let max_message_size = self.context.parameters.quic.max_message_size();
+
+        let response = tokio::time::timeout(timeout, async {
+            let (mut send, mut recv) = connection.open_bi().await.map_err(|e| e.to_string())?;
+            write_message(&mut send, &request).await?;
+            recv.read_to_end(max_message_size)
+                .await
+                .map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|_| ConsensusError::NetworkError("Request timed out".to_string()))?
+        .map_err(|e| ConsensusError::NetworkError(format!("Request failed: {e}")))?;
+
+        match bcs::from_bytes(&response) {
+            Ok(QuicResponse::Error(e)) => Err(ConsensusError::NetworkError(format!(
+                "Peer failed to handle request: {e}"
+            ))),
+            Ok(response) => Ok(response),
+            Err(e) => Err(ConsensusError::NetworkError(format!(
+                "Malformed response: {e:?}"
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl NetworkClient for QuicClient {
+    async fn send_block(
+        &self,
+        peer: AuthorityIndex,
+        block: &VerifiedBlock,
+        timeout: Duration,
+    ) -> ConsensusResult<()> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
+        let request = QuicRequest::SendBlock(SendBlockRequest {
+            block: self.block_encryption.seal(peer, block.serialized())?,
+        });
+        match self.request(peer, request, timeout).await? {
+            QuicResponse::SendBlock(_) => Ok(()),
+            _ => Err(ConsensusError::NetworkError(
+                "Unexpected response to send_block".to_string(),
+            )),
+        }
+    }
+
+    async fn fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
+        let request = QuicRequest::FetchBlocks(FetchBlocksRequest {
+            block_refs: block_refs
+                .iter()
+                .filter_map(|r| match bcs::to_bytes(r) {
+                    Ok(serialized) => Some(serialized),
+                    Err(e) => {
+                        debug!("Failed to serialize block ref {:?}: {e:?}", r);
+                        None
+                    }
+                })
+                .collect(),
+        });
+        match self.request(peer, request, timeout).await? {
+            QuicResponse::FetchBlocks(response) => response
+                .blocks
+                .into_iter()
+                .map(|block| self.block_encryption.open(peer, block))
+                .collect(),
+            _ => Err(ConsensusError::NetworkError(
+                "Unexpected response to fetch_blocks".to_string(),
+            )),
+        }
+    }
+
+    async fn get_latest_rounds(
+        &self,
+        peer: AuthorityIndex,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<Round>> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
+        let request = QuicRequest::GetLatestRounds(GetLatestRoundsRequest {});
+        match self.request(peer, request, timeout).await? {
+            QuicResponse::GetLatestRounds(response) => Ok(response.highest_received),
+            _ => Err(ConsensusError::NetworkError(
+                "Unexpected response to get_latest_rounds".to_string(),
+            )),
+        }
+    }
+
+    async fn report_rejected_blocks(
+        &self,
+        peer: AuthorityIndex,
+        rejections: &[BlockRejection],
+        timeout: Duration,
+    ) -> ConsensusResult<()> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
+        let request = QuicRequest::ReportRejectedBlocks(ReportRejectedBlocksRequest::new(
+            peer,
+            rejections,
+            &self.block_encryption,
+        )?);
+        match self.request(peer, request, timeout).await? {
+            QuicResponse::ReportRejectedBlocks(_) => Ok(()),
+            _ => Err(ConsensusError::NetworkError(
+                "Unexpected response to report_rejected_blocks".to_string(),
+            )),
+        }
+    }
+}
+
+/// Proxies QUIC requests to NetworkService with actual handler implementation.
+struct QuicServiceProxy<S: NetworkService> {
+    context: Arc<Context>,
+    service: Arc<S>,
+    block_encryption: Arc<BlockEncryption>,
+}
+
+impl<S: NetworkService> QuicServiceProxy<S> {
+    fn new(context: Arc<Context>, service: Arc<S>, block_encryption: Arc<BlockEncryption>) -> Self {
+        Self {
+            context,
+            service,
+            block_encryption,
+        }
+    }
+
+    async fn handle_connection(self: Arc<Self>, connecting: Connecting) {
+        let connection = match connecting.await {
+            Ok(connection) => connection,
+            Err(e) => {
+                debug!("Failed to accept connection: {e:?}");
+                return;
+            }
+        };
+        let Some(peer) = self.peer_index(&connection) else {
+            debug!(
+                "Connection from {} is not authenticated by a committee member",
+                connection.remote_address()
+            );
+            connection.close(0u32.into(), b"unknown peer");
+            return;
+        };
+        loop {
+            match connection.accept_bi().await {
+                Ok((send, recv)) => {
+                    let proxy = self.clone();
+                    tokio::spawn(async move { proxy.handle_stream(peer, send, recv).await });
+                }
+                Err(e) => {
+                    debug!(
+                        "Connection from {} is closed: {e:?}",
+                        connection.remote_address()
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Returns the authority whose network key authenticated the connection. Clients must present
+    /// a certificate of a committee member's network key, which the TLS handshake verifies.
+    fn peer_index(&self, connection: &Connection) -> Option<AuthorityIndex> {
+        let certificates = connection
+            .peer_identity()?
+            .downcast::<Vec<rustls::Certificate>>()
+            .ok()?;
+        let public_key =
+            NetworkPublicKey::new(public_key_from_certificate(certificates.first()?).ok()?);
+        self.context
+            .committee
+            .authorities()
+            .find(|(_, authority)| authority.network_key == public_key)
+            .map(|(index, _)| index)
+    }
+
+    async fn handle_stream(
+        &self,
+        peer_index: AuthorityIndex,
+        mut send: SendStream,
+        mut recv: RecvStream,
+    ) {
+        let max_message_size = self.context.parameters.quic.max_message_size();
+        let request = match recv.read_to_end(max_message_size).await {
+            Ok(request) => request,
+            Err(e) => {
+                debug!("Failed to read request: {e:?}");
+                return;
+            }
+        };
+        let response = self
+            .handle_request(peer_index, &request)
+            .await
+            .unwrap_or_else(|e| QuicResponse::Error(format!("{e:?}")));
+        let response = match bcs::to_bytes(&response) {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("Failed to serialize response: {e:?}");
+                return;
+            }
+        };
+        if let Err(e) = write_message(&mut send, &response).await {
+            debug!("Failed to write response: {e}");
+        }
+    }
+
+    async fn handle_request(
+        &self,
+        peer_index: AuthorityIndex,
+        request: &[u8],
+    ) -> ConsensusResult<QuicResponse> {
+        let request: QuicRequest = bcs::from_bytes(request)
+            .map_err(|e| ConsensusError::NetworkError(format!("Malformed request: {e:?}")))?;
+
+        match request {
+            QuicRequest::SendBlock(request) => {
+                let block = self.block_encryption.open(peer_index, request.block)?;
+                self.service.handle_send_block(peer_index, block).await?;
+                Ok(QuicResponse::SendBlock(SendBlockResponse {}))
+            }
+            QuicRequest::FetchBlocks(request) => {
+                let block_refs = request
+                    .block_refs
+                    .into_iter()
+                    .filter_map(|serialized| match bcs::from_bytes(&serialized) {
+                        Ok(r) => Some(r),
+                        Err(e) => {
+                            debug!("Failed to deserialize block ref {:?}: {e:?}", serialized);
+                            None
+                        }
+                    })
+                    .collect();
+                let blocks = self
+                    .service
+                    .handle_fetch_blocks(peer_index, block_refs)
+                    .await?;
+                let blocks = blocks
+                    .iter()
+                    .map(|block| self.block_encryption.seal(peer_index, block))
+                    .collect::<ConsensusResult<_>>()?;
+                Ok(QuicResponse::FetchBlocks(FetchBlocksResponse { blocks }))
+            }
+            QuicRequest::GetLatestRounds(_) => {
+                let highest_received = self.service.handle_get_latest_rounds(peer_index).await?;
+                Ok(QuicResponse::GetLatestRounds(GetLatestRoundsResponse {
+                    highest_received,
+                }))
+            }
+            QuicRequest::ReportRejectedBlocks(request) => {
+                let rejections = request.open(peer_index, &self.block_encryption)?;
+                self.service
+                    .handle_report_rejected_blocks(peer_index, rejections)
+                    .await?;
+                Ok(QuicResponse::ReportRejectedBlocks(
+                    ReportRejectedBlocksResponse {},
+                ))
+            }
+        }
+    }
+}
+
+/// Manages the lifecycle of QUIC network client and service. Typical usage during initialization:
+/// 1. Create a new `QuicManager`.
+/// 2. Take `QuicClient` from `QuicManager::client()`.
+/// 3. Create consensus components.
+/// 4. Create `QuicService` for consensus service handler.
+/// 5. Install `QuicService` to `QuicManager` with `QuicManager::install_service()`.
+pub(crate) struct QuicManager {
+    context: Arc<Context>,
+    client: Arc<QuicClient>,
+    endpoint: Option<Endpoint>,
+    server: JoinSet<()>,
+}
+
+impl QuicManager {
+    pub(crate) fn new(context: Arc<Context>) -> Self {
+        Self {
+            context: context.clone(),
+            client: Arc::new(QuicClient::new(context)),
+            endpoint: None,
+            server: JoinSet::new(),
+        }
+    }
+}
+
+impl<S: NetworkService> NetworkManager<S> for QuicManager {
+    type Client = QuicClient;
+
+    fn new(context: Arc<Context>) -> Self {
+        QuicManager::new(context)
+    }
+
+    fn client(&self) -> Arc<Self::Client> {
+        self.client.clone()
+    }
+
+    #[cfg(test)]
+    fn fault_injector(&self) -> Arc<FaultInjector> {
+        self.client.fault_injector.clone()
+    }
+
+    async fn install_service(&mut self, network_keypair: NetworkKeyPair, service: Arc<S>) {
+        self.context
+            .metrics
+            .network_metrics
+            .network_type
+            .with_label_values(&["quic"])
+            .set(1);
+
+        let authority = self.context.committee.authority(self.context.own_index);
+        // Bind to localhost in unit tests since only local networking is needed.
+        // Bind to the unspecified address to allow the actual address to be assigned,
+        // in simtest and production.
+        cfg_if!(
+            if #[cfg(test)] {
+                let own_address = authority.address.with_localhost_ip();
+            } else {
+                let own_address = authority.address.with_zero_ip();
+            }
+        );
+        let own_address = to_socket_addr(&own_address).unwrap();
+        self.client
+            .block_encryption
+            .install(&self.context, &network_keypair);
+        let private_key = Ed25519PrivateKey::from_bytes(&network_keypair.private_key_bytes())
+            .expect("Invalid network private key");
+        let certificate = SelfSignedCertificate::new(private_key, SUI_VALIDATOR_SERVER_NAME);
+        let service = Arc::new(QuicServiceProxy::new(
+            self.context.clone(),
+            service,
+            self.client.block_encryption.clone(),
+        ));
+
+        let endpoint = Endpoint::server(server_config(&self.context, &certificate), own_address)
+            .expect("Failed to bind QUIC endpoint");
+        let configs = self
+            .context
+            .committee
+            .authorities()
+            .map(|(index, authority)| {
+                (
+                    index,
+                    client_config(&self.context, &certificate, &authority.network_key),
+                )
+            })
+            .collect();
+        self.client.set_endpoint(ClientEndpoint {
+            endpoint: endpoint.clone(),
+            configs,
+        });
+
+        let server_endpoint = endpoint.clone();
+        self.server.spawn(async move {
+            while let Some(connecting) = server_endpoint.accept().await {
+                tokio::spawn(service.clone().handle_connection(connecting));
+            }
+            info!("QuicNetwork server stopped");
+        });
+        self.endpoint = Some(endpoint);
+
+        info!("QuicNetwork server started at: {own_address}");
+    }
+
+    async fn stop(&mut self) {
+        if let Some(endpoint) = self.endpoint.take() {
+            endpoint.close(0u32.into(), b"shutdown");
+        }
+        // Release the client's handle of the endpoint, so that its socket is closed.
+        self.client.endpoint.store(None);
+        self.client.connections.write().clear();
+        self.server.join_next().await;
+
+        self.context
+            .metrics
+            .network_metrics
+            .network_type
+            .with_label_values(&["quic"])
+            .set(0);
+    }
+}
+
+async fn write_message(send: &mut SendStream, message: &[u8]) -> Result<(), String> {
+    send.write_all(message).await.map_err(|e| e.to_string())?;
+    send.finish().await.map_err(|e| e.to_string())
+}
+
+fn transport_config(context: &Context) -> Arc<TransportConfig> {
+    let parameters = &context.parameters.quic;
+    let mut transport = TransportConfig::default();
+    transport
+        .keep_alive_interval(Some(parameters.keep_alive_interval()))
+        .max_idle_timeout(Some(
+            IdleTimeout::try_from(parameters.idle_timeout()).expect("Invalid QUIC idle timeout"),
+        ));
+    Arc::new(transport)
+}
+
+/// The server only accepts clients presenting a certificate of the network key of a committee
+/// member, so that requests can be attributed to the authenticated peer.
+fn server_config(context: &Context, certificate: &SelfSignedCertificate) -> ServerConfig {
+    let mut allowlist = HashSetAllow::new();
+    allowlist.inner_mut().write().unwrap().extend(
+        context
+            .committee
+            .authorities()
+            .map(|(_, authority)| authority.network_key.clone().into_inner()),
+    );
+    let mut crypto = rustls::ServerConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .expect("QUIC requires TLS 1.3")
+        .with_client_cert_verifier(Arc::new(CertVerifier::new(allowlist)))
+        .with_single_cert(
+            vec![certificate.rustls_certificate()],
+            certificate.rustls_private_key(),
+        )
+        .expect("Invalid QUIC certificate");
+    crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+
+    let mut config = ServerConfig::with_crypto(Arc::new(crypto));
+    config.transport_config(transport_config(context));
+    config
+}
+
+/// Connections to a peer only accept the certificate of the peer's network key, and present the
+/// certificate of the own network key.
+fn client_config(
+    context: &Context,
+    certificate: &SelfSignedCertificate,
+    peer_key: &NetworkPublicKey,
+) -> ClientConfig {
+    let mut allowlist = HashSetAllow::new();
+    allowlist
+        .inner_mut()
+        .write()
+        .unwrap()
+        .insert(peer_key.clone().into_inner());
+    let mut crypto = rustls::ClientConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .expect("QUIC requires TLS 1.3")
+        .with_custom_certificate_verifier(Arc::new(CertVerifier::new(allowlist)))
+        .with_client_auth_cert(
+            vec![certificate.rustls_certificate()],
+            certificate.rustls_private_key(),
+        )
+        .expect("Invalid QUIC certificate");
+    crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+
+    let mut config = ClientConfig::new(Arc::new(crypto));
+    config.transport_config(transport_config(context));
+    config
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Arc, time::Duration};
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use consensus_config::{AuthorityIndex, NetworkKeyPair};
+    use parking_lot::Mutex;
+    use rand::{rngs::StdRng, SeedableRng as _};
+
+    use crate::{
+        block::{BlockRef, Round, TestBlock, VerifiedBlock},
+        block_manager::RejectionReason,
+        context::Context,
+        error::ConsensusResult,
+        network::{quic_network::QuicManager, NetworkClient, NetworkManager, NetworkService},
+    };
+
+    struct TestService {
+        handle_send_block: Vec<(AuthorityIndex, Bytes)>,
+        handle_fetch_blocks: Vec<(AuthorityIndex, Vec<BlockRef>)>,
+        blocks: Vec<Bytes>,
+    }
+
+    impl TestService {
+        pub(crate) fn new(blocks: Vec<Bytes>) -> Self {
+            Self {
+                handle_send_block: Vec::new(),
+                handle_fetch_blocks: Vec::new(),
+                blocks,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl NetworkService for Mutex<TestService> {
+        async fn handle_send_block(
+            &self,
+            peer: AuthorityIndex,
+            block: Bytes,
+        ) -> ConsensusResult<()> {
+            self.lock().handle_send_block.push((peer, block));
+            Ok(())
+        }
+
+        async fn handle_fetch_blocks(
+            &self,
+            peer: AuthorityIndex,
+            block_refs: Vec<BlockRef>,
+        ) -> ConsensusResult<Vec<Bytes>> {
+            let mut service = self.lock();
+            service.handle_fetch_blocks.push((peer, block_refs));
+            Ok(service.blocks.clone())
+        }
+
+        async fn handle_get_latest_rounds(
+            &self,
+            _peer: AuthorityIndex,
+        ) -> ConsensusResult<Vec<Round>> {
+            Ok(vec![0; 4])
+        }
+
+        async fn handle_report_rejected_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _rejections: Vec<(Bytes, RejectionReason)>,
+        ) -> ConsensusResult<()> {
+            unimplemented!("Unimplemented")
+        }
+    }
+
+    #[tokio::test]
+    async fn quic_basics() {
+        let (context, keys) = Context::new_for_test(4);
+
+        let context_0 = Arc::new(
+            context
+                .clone()
+                .with_authority_index(context.committee.to_authority_index(0).unwrap()),
+        );
+        let mut manager_0 = QuicManager::new(context_0.clone());
+        let client_0 = <QuicManager as NetworkManager<Mutex<TestService>>>::client(&manager_0);
+        let service_0 = Arc::new(Mutex::new(TestService::new(vec![])));
+        manager_0
+            .install_service(keys[0].0.clone(), service_0.clone())
+            .await;
+
+        let test_block_1 = VerifiedBlock::new_for_test(TestBlock::new(9, 1).build());
+        let context_1 = Arc::new(
+            context
+                .clone()
+                .with_authority_index(context.committee.to_authority_index(1).unwrap()),
+        );
+        let mut manager_1 = QuicManager::new(context_1.clone());
+        let client_1 = <QuicManager as NetworkManager<Mutex<TestService>>>::client(&manager_1);
+        let service_1 = Arc::new(Mutex::new(TestService::new(vec![test_block_1
+            .serialized()
+            .clone()])));
+        manager_1
+            .install_service(keys[1].0.clone(), service_1.clone())
+            .await;
+
+        // Test that servers can receive client RPCs.
+        let test_block_0 = VerifiedBlock::new_for_test(TestBlock::new(9, 0).build());
+        client_0
+            .send_block(
+                context.committee.to_authority_index(1).unwrap(),
+                &test_block_0,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        client_1
+            .send_block(
+                context.committee.to_authority_index(0).unwrap(),
+                &test_block_1,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(service_0.lock().handle_send_block.len(), 1);
+        assert_eq!(service_0.lock().handle_send_block[0].0.value(), 1);
+        assert_eq!(
+            service_0.lock().handle_send_block[0].1,
+            test_block_1.serialized(),
+        );
+        assert_eq!(service_1.lock().handle_send_block.len(), 1);
+        assert_eq!(service_1.lock().handle_send_block[0].0.value(), 0);
+        assert_eq!(
+            service_1.lock().handle_send_block[0].1,
+            test_block_0.serialized(),
+        );
+
+        // Test that blocks can be fetched from peers.
+        let blocks = client_0
+            .fetch_blocks(
+                context.committee.to_authority_index(1).unwrap(),
+                vec![test_block_1.reference()],
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(blocks, vec![test_block_1.serialized().clone()]);
+        assert_eq!(
+            service_1.lock().handle_fetch_blocks,
+            vec![(
+                context.committee.to_authority_index(0).unwrap(),
+                vec![test_block_1.reference()]
+            )]
+        );
+
+        <QuicManager as NetworkManager<Mutex<TestService>>>::stop(&mut manager_0).await;
+        <QuicManager as NetworkManager<Mutex<TestService>>>::stop(&mut manager_1).await;
+    }
+
+    #[tokio::test]
+    async fn quic_authenticates_peers() {
+        let (context, keys) = Context::new_for_test(4);
+
+        let context_0 = Arc::new(
+            context
+                .clone()
+                .with_authority_index(context.committee.to_authority_index(0).unwrap()),
+        );
+        let mut manager_0 = QuicManager::new(context_0.clone());
+        let client_0 = <QuicManager as NetworkManager<Mutex<TestService>>>::client(&manager_0);
+        let service_0 = Arc::new(Mutex::new(TestService::new(vec![])));
+        manager_0
+            .install_service(keys[0].0.clone(), service_0.clone())
+            .await;
+
+        // Authority 1 runs with a network key which is not the one of the committee.
+        let context_1 = Arc::new(
+            context
+                .clone()
+                .with_authority_index(context.committee.to_authority_index(1).unwrap()),
+        );
+        let mut manager_1 = QuicManager::new(context_1.clone());
+        let client_1 = <QuicManager as NetworkManager<Mutex<TestService>>>::client(&manager_1);
+        let service_1 = Arc::new(Mutex::new(TestService::new(vec![])));
+        let unknown_key = NetworkKeyPair::generate(&mut StdRng::from_seed([9; 32]));
+        manager_1
+            .install_service(unknown_key, service_1.clone())
+            .await;
+
+        // Its requests are rejected, and it is not trusted as a server either.
+        let test_block_1 = VerifiedBlock::new_for_test(TestBlock::new(9, 1).build());
+        client_1
+            .send_block(
+                context.committee.to_authority_index(0).unwrap(),
+                &test_block_1,
+                Duration::from_secs(2),
+            )
+            .await
+            .unwrap_err();
+        assert!(service_0.lock().handle_send_block.is_empty());
+
+        let test_block_0 = VerifiedBlock::new_for_test(TestBlock::new(9, 0).build());
+        client_0
+            .send_block(
+                context.committee.to_authority_index(1).unwrap(),
+                &test_block_0,
+                Duration::from_secs(2),
+            )
+            .await
+            .unwrap_err();
+        assert!(service_1.lock().handle_send_block.is_empty());
+
+        <QuicManager as NetworkManager<Mutex<TestService>>>::stop(&mut manager_0).await;
+        <QuicManager as NetworkManager<Mutex<TestService>>>::stop(&mut manager_1).await;
+    }
+}
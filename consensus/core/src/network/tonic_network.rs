@@ -24,16 +24,21 @@ use tonic::{
 };
 use tracing::{debug, info, warn};
 
+#[cfg(test)]
+use super::fault_injection::FaultInjector;
 use super::{
+    block_encryption::BlockEncryption,
     tonic_gen::{
         consensus_service_client::ConsensusServiceClient,
         consensus_service_server::ConsensusService,
     },
-    FetchBlocksRequest, FetchBlocksResponse, NetworkClient, NetworkManager, NetworkService,
-    SendBlockRequest, SendBlockResponse,
+    FetchBlocksRequest, FetchBlocksResponse, GetLatestRoundsRequest, GetLatestRoundsResponse,
+    NetworkClient, NetworkManager, NetworkService, ReportRejectedBlocksRequest,
+    ReportRejectedBlocksResponse, SendBlockRequest, SendBlockResponse,
 };
 use crate::{
-    block::{BlockRef, VerifiedBlock},
+    block::{BlockRef, Round, VerifiedBlock},
+    block_manager::BlockRejection,
     context::Context,
     error::{ConsensusError, ConsensusResult},
     network::tonic_gen::consensus_service_server::ConsensusServiceServer,
@@ -45,13 +50,19 @@ const AUTHORITY_INDEX_METADATA_KEY: &str = "authority-index";
 pub(crate) struct TonicClient {
     context: Arc<Context>,
     channel_pool: Arc<ChannelPool>,
+    block_encryption: Arc<BlockEncryption>,
+    #[cfg(test)]
+    fault_injector: Arc<FaultInjector>,
 }
 
 impl TonicClient {
     pub(crate) fn new(context: Arc<Context>) -> Self {
         Self {
+            #[cfg(test)]
+            fault_injector: Arc::new(FaultInjector::new(context.own_index.value() as u64)),
             context: context.clone(),
             channel_pool: Arc::new(ChannelPool::new(context)),
+            block_encryption: Arc::new(BlockEncryption::new()),
         }
     }
 
@@ -73,9 +84,13 @@ impl NetworkClient for TonicClient {
         block: &VerifiedBlock,
         timeout: Duration,
     ) -> ConsensusResult<()> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
         let mut client = self.get_client(peer, timeout).await?;
         let mut request = Request::new(SendBlockRequest {
-            block: block.serialized().clone(),
+            block: self.block_encryption.seal(peer, block.serialized())?,
         });
         request.set_timeout(timeout);
         // TODO: remove below after adding authentication.
@@ -96,6 +111,10 @@ impl NetworkClient for TonicClient {
         block_refs: Vec<BlockRef>,
         timeout: Duration,
     ) -> ConsensusResult<Vec<Bytes>> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
         let mut client = self.get_client(peer, timeout).await?;
         let mut request = Request::new(FetchBlocksRequest {
             block_refs: block_refs
@@ -119,7 +138,63 @@ impl NetworkClient for TonicClient {
             .fetch_blocks(request)
             .await
             .map_err(|e| ConsensusError::NetworkError(format!("fetch_blocks failed: {e:?}")))?;
-        Ok(response.into_inner().blocks)
+        response
+            .into_inner()
+            .blocks
+            .into_iter()
+            .map(|block| self.block_encryption.open(peer, block))
+            .collect()
+    }
+
+    async fn get_latest_rounds(
+        &self,
+        peer: AuthorityIndex,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<Round>> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
+        let mut client = self.get_client(peer, timeout).await?;
+        let mut request = Request::new(GetLatestRoundsRequest {});
+        request.set_timeout(timeout);
+        // TODO: remove below after adding authentication.
+        request.metadata_mut().insert(
+            AUTHORITY_INDEX_METADATA_KEY,
+            self.context.own_index.value().to_string().parse().unwrap(),
+        );
+        let response = client.get_latest_rounds(request).await.map_err(|e| {
+            ConsensusError::NetworkError(format!("get_latest_rounds failed: {e:?}"))
+        })?;
+        Ok(response.into_inner().highest_received)
+    }
+
+    async fn report_rejected_blocks(
+        &self,
+        peer: AuthorityIndex,
+        rejections: &[BlockRejection],
+        timeout: Duration,
+    ) -> ConsensusResult<()> {
+        #[cfg(test)]
+        self.fault_injector
+            .apply(self.context.own_index, peer, timeout)
+            .await?;
+        let mut client = self.get_client(peer, timeout).await?;
+        let mut request = Request::new(ReportRejectedBlocksRequest::new(
+            peer,
+            rejections,
+            &self.block_encryption,
+        )?);
+        request.set_timeout(timeout);
+        // TODO: remove below after adding authentication.
+        request.metadata_mut().insert(
+            AUTHORITY_INDEX_METADATA_KEY,
+            self.context.own_index.value().to_string().parse().unwrap(),
+        );
+        client.report_rejected_blocks(request).await.map_err(|e| {
+            ConsensusError::NetworkError(format!("report_rejected_blocks failed: {e:?}"))
+        })?;
+        Ok(())
     }
 }
 
@@ -191,11 +266,16 @@ impl ChannelPool {
 struct TonicServiceProxy<S: NetworkService> {
     context: Arc<Context>,
     service: Arc<S>,
+    block_encryption: Arc<BlockEncryption>,
 }
 
 impl<S: NetworkService> TonicServiceProxy<S> {
-    fn new(context: Arc<Context>, service: Arc<S>) -> Self {
-        Self { context, service }
+    fn new(context: Arc<Context>, service: Arc<S>, block_encryption: Arc<BlockEncryption>) -> Self {
+        Self {
+            context,
+            service,
+            block_encryption,
+        }
     }
 }
 
@@ -215,7 +295,10 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
         else {
             return Err(tonic::Status::invalid_argument("Invalid authority index"));
         };
-        let block = request.into_inner().block;
+        let block = self
+            .block_encryption
+            .open(peer_index, request.into_inner().block)
+            .map_err(|e| tonic::Status::invalid_argument(format!("{e:?}")))?;
         self.service
             .handle_send_block(peer_index, block)
             .await
@@ -254,8 +337,60 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
             .handle_fetch_blocks(peer_index, block_refs)
             .await
             .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+        let blocks = blocks
+            .iter()
+            .map(|block| self.block_encryption.seal(peer_index, block))
+            .collect::<ConsensusResult<_>>()
+            .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
         Ok(Response::new(FetchBlocksResponse { blocks }))
     }
+
+    async fn get_latest_rounds(
+        &self,
+        request: Request<GetLatestRoundsRequest>,
+    ) -> Result<Response<GetLatestRoundsResponse>, tonic::Status> {
+        // TODO: switch to using authenticated peer identity.
+        let Some(peer_index) = request
+            .metadata()
+            .get(AUTHORITY_INDEX_METADATA_KEY)
+            .and_then(|s| s.to_str().ok())
+            .and_then(|s| s.parse().ok())
+            .and_then(|index| self.context.committee.to_authority_index(index))
+        else {
+            return Err(tonic::Status::invalid_argument("Invalid authority index"));
+        };
+        let highest_received = self
+            .service
+            .handle_get_latest_rounds(peer_index)
+            .await
+            .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+        Ok(Response::new(GetLatestRoundsResponse { highest_received }))
+    }
+
+    async fn report_rejected_blocks(
+        &self,
+        request: Request<ReportRejectedBlocksRequest>,
+    ) -> Result<Response<ReportRejectedBlocksResponse>, tonic::Status> {
+        // TODO: switch to using authenticated peer identity.
+        let Some(peer_index) = request
+            .metadata()
+            .get(AUTHORITY_INDEX_METADATA_KEY)
+            .and_then(|s| s.to_str().ok())
+            .and_then(|s| s.parse().ok())
+            .and_then(|index| self.context.committee.to_authority_index(index))
+        else {
+            return Err(tonic::Status::invalid_argument("Invalid authority index"));
+        };
+        let rejections = request
+            .into_inner()
+            .open(peer_index, &self.block_encryption)
+            .map_err(|e| tonic::Status::invalid_argument(format!("{e:?}")))?;
+        self.service
+            .handle_report_rejected_blocks(peer_index, rejections)
+            .await
+            .map_err(|e| tonic::Status::invalid_argument(format!("{e:?}")))?;
+        Ok(Response::new(ReportRejectedBlocksResponse {}))
+    }
 }
 
 /// Manages the lifecycle of Tonic network client and service. Typical usage during initialization:
@@ -293,7 +428,12 @@ impl<S: NetworkService> NetworkManager<S> for TonicManager {
         self.client.clone()
     }
 
-    async fn install_service(&mut self, _network_keypair: NetworkKeyPair, service: Arc<S>) {
+    #[cfg(test)]
+    fn fault_injector(&self) -> Arc<FaultInjector> {
+        self.client.fault_injector.clone()
+    }
+
+    async fn install_service(&mut self, network_keypair: NetworkKeyPair, service: Arc<S>) {
         self.context
             .metrics
             .network_metrics
@@ -315,7 +455,14 @@ impl<S: NetworkService> NetworkManager<S> for TonicManager {
         let own_address = to_socket_addr(&own_address).unwrap();
         let (tx, rx) = oneshot::channel::<()>();
         self.shutdown = Some(tx);
-        let service = TonicServiceProxy::new(self.context.clone(), service);
+        self.client
+            .block_encryption
+            .install(&self.context, &network_keypair);
+        let service = TonicServiceProxy::new(
+            self.context.clone(),
+            service,
+            self.client.block_encryption.clone(),
+        );
 
         let server = Server::builder()
             .initial_connection_window_size(64 << 20)
@@ -360,7 +507,7 @@ impl<S: NetworkService> NetworkManager<S> for TonicManager {
 
 /// Attempts to convert a multiaddr of the form `/[ip4,ip6,dns]/{}/udp/{port}` into
 /// a host:port string.
-fn to_host_port_str(addr: &Multiaddr) -> Result<String, &'static str> {
+pub(crate) fn to_host_port_str(addr: &Multiaddr) -> Result<String, &'static str> {
     let mut iter = addr.iter();
 
     match (iter.next(), iter.next()) {
@@ -383,7 +530,7 @@ fn to_host_port_str(addr: &Multiaddr) -> Result<String, &'static str> {
 
 /// Attempts to convert a multiaddr of the form `/[ip4,ip6]/{}/[udp,tcp]/{port}` into
 /// a SocketAddr value.
-fn to_socket_addr(addr: &Multiaddr) -> Result<SocketAddr, &'static str> {
+pub(crate) fn to_socket_addr(addr: &Multiaddr) -> Result<SocketAddr, &'static str> {
     let mut iter = addr.iter();
 
     match (iter.next(), iter.next()) {
@@ -407,7 +554,7 @@ fn to_socket_addr(addr: &Multiaddr) -> Result<SocketAddr, &'static str> {
 // TODO: after supporting peer authentication, using rtest to share the test case with anemo_network.rs
 #[cfg(test)]
 mod test {
-    use std::{sync::Arc, time::Duration};
+    use std::{collections::BTreeSet, sync::Arc, time::Duration};
 
     use async_trait::async_trait;
     use bytes::Bytes;
@@ -415,7 +562,8 @@ mod test {
     use parking_lot::Mutex;
 
     use crate::{
-        block::{BlockRef, TestBlock, VerifiedBlock},
+        block::{BlockRef, Round, TestBlock, VerifiedBlock},
+        block_manager::RejectionReason,
         context::Context,
         error::ConsensusResult,
         network::{tonic_network::TonicManager, NetworkClient, NetworkManager, NetworkService},
@@ -454,6 +602,21 @@ mod test {
             self.lock().handle_fetch_blocks.push((peer, block_refs));
             Ok(vec![])
         }
+
+        async fn handle_get_latest_rounds(
+            &self,
+            _peer: AuthorityIndex,
+        ) -> ConsensusResult<Vec<Round>> {
+            Ok(vec![0; 4])
+        }
+
+        async fn handle_report_rejected_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _rejections: Vec<(Bytes, RejectionReason)>,
+        ) -> ConsensusResult<()> {
+            unimplemented!("Unimplemented")
+        }
     }
 
     #[tokio::test]
@@ -518,5 +681,103 @@ mod test {
             service_1.lock().handle_send_block[0].1,
             test_block_0.serialized(),
         );
+
+        // Test that injected faults prevent requests from reaching the server.
+        let fault_injector =
+            <TonicManager as NetworkManager<Mutex<TestService>>>::fault_injector(&manager_0);
+        fault_injector.partition(vec![
+            BTreeSet::from([context.committee.to_authority_index(0).unwrap()]),
+            BTreeSet::from([context.committee.to_authority_index(1).unwrap()]),
+        ]);
+        assert!(client_0
+            .send_block(
+                context.committee.to_authority_index(1).unwrap(),
+                &test_block_0,
+                Duration::from_secs(5),
+            )
+            .await
+            .is_err());
+        assert_eq!(service_1.lock().handle_send_block.len(), 1);
+
+        fault_injector.heal();
+        client_0
+            .send_block(
+                context.committee.to_authority_index(1).unwrap(),
+                &test_block_0,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(service_1.lock().handle_send_block.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn tonic_block_encryption() {
+        let (mut context, keys) = Context::new_for_test(4);
+        context
+            .protocol_config
+            .set_consensus_block_encryption_for_testing(true);
+
+        let context_0 = Arc::new(
+            context
+                .clone()
+                .with_authority_index(context.committee.to_authority_index(0).unwrap()),
+        );
+        let mut manager_0 = TonicManager::new(context_0.clone());
+        let client_0 = <TonicManager as NetworkManager<Mutex<TestService>>>::client(&manager_0);
+        let service_0 = Arc::new(Mutex::new(TestService::new()));
+        manager_0
+            .install_service(keys[0].0.clone(), service_0.clone())
+            .await;
+
+        let context_1 = Arc::new(
+            context
+                .clone()
+                .with_authority_index(context.committee.to_authority_index(1).unwrap()),
+        );
+        let mut manager_1 = TonicManager::new(context_1.clone());
+        let service_1 = Arc::new(Mutex::new(TestService::new()));
+        manager_1
+            .install_service(keys[1].0.clone(), service_1.clone())
+            .await;
+
+        // The block is encrypted on the wire and decrypted before reaching the service.
+        let test_block_0 = VerifiedBlock::new_for_test(TestBlock::new(9, 0).build());
+        client_0
+            .send_block(
+                context.committee.to_authority_index(1).unwrap(),
+                &test_block_0,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(service_1.lock().handle_send_block.len(), 1);
+        assert_eq!(
+            service_1.lock().handle_send_block[0].1,
+            test_block_0.serialized(),
+        );
+
+        // A block sent by an authority not holding the shared key is rejected, even though the
+        // channel itself accepts the request.
+        let context_2 = Arc::new(
+            context
+                .clone()
+                .with_authority_index(context.committee.to_authority_index(2).unwrap()),
+        );
+        let mut manager_2 = TonicManager::new(context_2.clone());
+        let client_2 = <TonicManager as NetworkManager<Mutex<TestService>>>::client(&manager_2);
+        manager_2
+            .install_service(keys[3].0.clone(), Arc::new(Mutex::new(TestService::new())))
+            .await;
+        let test_block_2 = VerifiedBlock::new_for_test(TestBlock::new(9, 2).build());
+        assert!(client_2
+            .send_block(
+                context.committee.to_authority_index(1).unwrap(),
+                &test_block_2,
+                Duration::from_secs(5),
+            )
+            .await
+            .is_err());
+        assert_eq!(service_1.lock().handle_send_block.len(), 1);
     }
 }
@@ -0,0 +1,231 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::Arc, time::Duration};
+
+use futures::{stream::FuturesUnordered, StreamExt as _};
+use tokio::{
+    sync::{
+        broadcast::{self, error::RecvError},
+        oneshot::{Receiver, Sender},
+    },
+    task::JoinHandle,
+};
+use tracing::{debug, warn};
+
+use crate::{
+    block_manager::BlockRejection,
+    context::Context,
+    core::CoreSignalsReceivers,
+    network::{NetworkClient, MAX_REJECTIONS_PER_REPORT},
+};
+
+// TODO: move to Parameters.
+const REPORT_REJECTED_BLOCKS_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub(crate) struct RejectionReporterHandle {
+    handle: JoinHandle<()>,
+    stop: Sender<()>,
+}
+
+impl RejectionReporterHandle {
+    pub async fn stop(self) {
+        self.stop.send(()).ok();
+        self.handle.await.ok();
+    }
+}
+
+/// Reports the blocks rejected by the block manager to all peers. The peer that sent an invalid
+/// block learns why it was rejected, and as the rejected blocks are signed by their authors, the
+/// other peers can attribute them to their authors, after checking the rejections themselves.
+///
+/// Reports are best effort: rejections are not retried, and rejections are dropped if they are
+/// produced faster than they can be reported.
+pub(crate) struct RejectionReporter<C: NetworkClient> {
+    context: Arc<Context>,
+    network_client: Arc<C>,
+    rejected_blocks: broadcast::Receiver<BlockRejection>,
+    stop: Receiver<()>,
+}
+
+impl<C: NetworkClient> RejectionReporter<C> {
+    /// Starts reporting rejected blocks. Must be called before Core is created, to receive the
+    /// rejections during recovery.
+    pub fn start(
+        context: Arc<Context>,
+        network_client: Arc<C>,
+        signals_receivers: &CoreSignalsReceivers,
+    ) -> RejectionReporterHandle {
+        let (stop_sender, stop) = tokio::sync::oneshot::channel();
+        let mut me = Self {
+            context,
+            network_client,
+            rejected_blocks: signals_receivers.rejected_blocks_receiver(),
+            stop,
+        };
+        let handle = tokio::spawn(async move { me.run().await });
+
+        RejectionReporterHandle {
+            handle,
+            stop: stop_sender,
+        }
+    }
+
+    async fn run(&mut self) {
+        loop {
+            tokio::select! {
+                result = self.rejected_blocks.recv() => {
+                    let rejection = match result {
+                        Ok(rejection) => rejection,
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!("Skipped reporting {skipped} rejected blocks");
+                            continue;
+                        }
+                        Err(RecvError::Closed) => {
+                            debug!("Rejected blocks channel has been closed, now shutting down");
+                            return;
+                        }
+                    };
+                    // Batch the rejections that are already available.
+                    let mut rejections = vec![rejection];
+                    while rejections.len() < MAX_REJECTIONS_PER_REPORT {
+                        match self.rejected_blocks.try_recv() {
+                            Ok(rejection) => rejections.push(rejection),
+                            Err(_) => break,
+                        }
+                    }
+                    self.report(&rejections).await;
+                }
+                _ = &mut self.stop => {
+                    debug!("Stop signal has been received, now shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Reports the rejections to all peers concurrently.
+    async fn report(&self, rejections: &[BlockRejection]) {
+        let mut requests = FuturesUnordered::new();
+        for (peer, _) in self.context.committee.authorities() {
+            if peer == self.context.own_index {
+                continue;
+            }
+            let network_client = self.network_client.clone();
+            requests.push(async move {
+                let result = network_client
+                    .report_rejected_blocks(peer, rejections, REPORT_REJECTED_BLOCKS_TIMEOUT)
+                    .await;
+                (peer, result)
+            });
+        }
+        while let Some((peer, result)) = requests.next().await {
+            if let Err(e) = result {
+                self.context
+                    .metrics
+                    .node_metrics
+                    .record_error(&e, Some(peer));
+                debug!("Failed to report rejected blocks to peer {peer}: {e:?}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use consensus_config::AuthorityIndex;
+    use parking_lot::Mutex;
+    use tokio::time::sleep;
+
+    use super::*;
+    use crate::{
+        block::{BlockRef, Round, TestBlock, VerifiedBlock},
+        block_manager::RejectionReason,
+        core::CoreSignals,
+        error::ConsensusResult,
+    };
+
+    #[derive(Default)]
+    struct FakeNetworkClient {
+        reports: Mutex<BTreeMap<AuthorityIndex, Vec<BlockRef>>>,
+    }
+
+    #[async_trait]
+    impl NetworkClient for FakeNetworkClient {
+        async fn send_block(
+            &self,
+            _peer: AuthorityIndex,
+            _block: &VerifiedBlock,
+            _timeout: Duration,
+        ) -> ConsensusResult<()> {
+            unimplemented!("Unimplemented")
+        }
+
+        async fn fetch_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _block_refs: Vec<BlockRef>,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<Bytes>> {
+            unimplemented!("Unimplemented")
+        }
+
+        async fn get_latest_rounds(
+            &self,
+            _peer: AuthorityIndex,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<Round>> {
+            unimplemented!("Unimplemented")
+        }
+
+        async fn report_rejected_blocks(
+            &self,
+            peer: AuthorityIndex,
+            rejections: &[BlockRejection],
+            _timeout: Duration,
+        ) -> ConsensusResult<()> {
+            self.reports
+                .lock()
+                .entry(peer)
+                .or_default()
+                .extend(rejections.iter().map(|r| r.block.reference()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_report_rejected_blocks() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let (core_signals, signals_receivers) = CoreSignals::new(context.clone());
+        let network_client = Arc::new(FakeNetworkClient::default());
+        let handle =
+            RejectionReporter::start(context.clone(), network_client.clone(), &signals_receivers);
+
+        let blocks = (1..=3)
+            .map(|round| VerifiedBlock::new_for_test(TestBlock::new(round, 1).build()))
+            .collect::<Vec<_>>();
+        for block in &blocks {
+            core_signals.block_rejected(BlockRejection {
+                block: block.clone(),
+                reason: RejectionReason::InvalidAncestors("invalid".to_string()),
+            });
+        }
+        sleep(Duration::from_secs(1)).await;
+
+        // All the rejections are reported to all the peers, but not to the own authority.
+        let expected = blocks.iter().map(|b| b.reference()).collect::<Vec<_>>();
+        let reports = network_client.reports.lock().clone();
+        assert_eq!(reports.len(), 3);
+        assert!(!reports.contains_key(&context.own_index));
+        for refs in reports.values() {
+            assert_eq!(refs, &expected);
+        }
+
+        handle.stop().await;
+    }
+}
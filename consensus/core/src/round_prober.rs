@@ -0,0 +1,398 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use consensus_config::{Committee, Stake};
+use futures::{stream::FuturesUnordered, StreamExt as _};
+use parking_lot::RwLock;
+use tokio::{
+    sync::oneshot::{Receiver, Sender},
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+use tracing::{debug, warn};
+
+use crate::{
+    block::{Round, GENESIS_ROUND},
+    context::Context,
+    core_thread::CoreThreadDispatcher,
+    dag_state::DagState,
+    network::NetworkClient,
+};
+
+pub(crate) struct RoundProberHandle {
+    handle: JoinHandle<()>,
+    stop: Sender<()>,
+}
+
+impl RoundProberHandle {
+    pub async fn stop(self) {
+        self.stop.send(()).ok();
+        self.handle.await.ok();
+    }
+}
+
+/// Periodically probes peers for the highest round they have received from each authority, to
+/// estimate how far behind blocks propagate to a quorum. The propagation delay of each authority
+/// is reported in metrics, and the propagation delay of the own blocks is fed to Core, which
+/// holds off block proposals while the own blocks are not received by a quorum.
+pub(crate) struct RoundProber<C: NetworkClient, D: CoreThreadDispatcher> {
+    context: Arc<Context>,
+    network_client: Arc<C>,
+    core_dispatcher: Arc<D>,
+    dag_state: Arc<RwLock<DagState>>,
+    stop: Receiver<()>,
+}
+
+impl<C: NetworkClient, D: CoreThreadDispatcher> RoundProber<C, D> {
+    pub fn start(
+        context: Arc<Context>,
+        network_client: Arc<C>,
+        core_dispatcher: Arc<D>,
+        dag_state: Arc<RwLock<DagState>>,
+    ) -> RoundProberHandle {
+        let (stop_sender, stop) = tokio::sync::oneshot::channel();
+        let mut me = Self {
+            context,
+            network_client,
+            core_dispatcher,
+            dag_state,
+            stop,
+        };
+        let handle = tokio::spawn(async move { me.run().await });
+
+        RoundProberHandle {
+            handle,
+            stop: stop_sender,
+        }
+    }
+
+    async fn run(&mut self) {
+        let mut probe_interval = interval(self.context.parameters.round_prober_interval);
+        probe_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = probe_interval.tick() => {
+                    let propagation_delay = self.probe().await;
+                    if let Err(err) = self.core_dispatcher.set_propagation_delay(propagation_delay).await {
+                        warn!("Error received while calling dispatcher, probably dispatcher is shutting down, will now exit: {err:?}");
+                        return;
+                    }
+                }
+                _ = &mut self.stop => {
+                    debug!("Stop signal has been received, now shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Probes the peers for their highest received rounds and reports the quorum round and
+    /// propagation delay of each authority in metrics. Returns the propagation delay of the own
+    /// blocks, or 0 if too few peers responded to estimate it.
+    async fn probe(&self) -> Round {
+        let committee = &self.context.committee;
+        let own_index = self.context.own_index;
+        let timeout = self.context.parameters.round_prober_request_timeout;
+
+        let mut requests = FuturesUnordered::new();
+        for (peer, _) in committee.authorities() {
+            if peer == own_index {
+                continue;
+            }
+            let network_client = self.network_client.clone();
+            requests
+                .push(async move { (peer, network_client.get_latest_rounds(peer, timeout).await) });
+        }
+
+        let mut highest_received = vec![None; committee.size()];
+        highest_received[own_index] = Some(self.dag_state.read().last_accepted_rounds());
+        while let Some((peer, result)) = requests.next().await {
+            match result {
+                Ok(rounds) if rounds.len() == committee.size() => {
+                    highest_received[peer] = Some(rounds);
+                    continue;
+                }
+                Ok(rounds) => warn!(
+                    "Peer {peer} returned {} latest rounds, expected {}",
+                    rounds.len(),
+                    committee.size()
+                ),
+                Err(e) => {
+                    self.context
+                        .metrics
+                        .node_metrics
+                        .record_error(&e, Some(peer));
+                    debug!("Failed to get latest rounds from peer {peer}: {e:?}");
+                }
+            }
+            let peer_hostname = &committee.authority(peer).hostname;
+            self.context
+                .metrics
+                .node_metrics
+                .round_prober_request_errors
+                .with_label_values(&[peer_hostname])
+                .inc();
+        }
+
+        let Some(quorum_rounds) = compute_quorum_rounds(committee, &highest_received) else {
+            warn!("Too few peers responded to the round prober to estimate propagation delays");
+            return 0;
+        };
+
+        let metrics = &self.context.metrics.node_metrics;
+        for (authority, info) in committee.authorities() {
+            let highest_round = highest_received
+                .iter()
+                .flatten()
+                .map(|rounds| rounds[authority])
+                .max()
+                .unwrap_or(GENESIS_ROUND);
+            metrics
+                .round_prober_quorum_round
+                .with_label_values(&[&info.hostname])
+                .set(quorum_rounds[authority] as i64);
+            metrics
+                .round_prober_propagation_delay
+                .with_label_values(&[&info.hostname])
+                .set(highest_round.saturating_sub(quorum_rounds[authority]) as i64);
+        }
+
+        // The own highest received round is the round of the last proposed block.
+        let own_round = highest_received[own_index]
+            .as_ref()
+            .map_or(GENESIS_ROUND, |rounds| rounds[own_index]);
+        let propagation_delay = own_round.saturating_sub(quorum_rounds[own_index]);
+        metrics
+            .round_prober_own_propagation_delay
+            .set(propagation_delay as i64);
+        propagation_delay
+    }
+}
+
+/// Computes, for each authority, the highest round received from it by a quorum, given the highest
+/// rounds received by each peer that responded. Returns `None` if the peers that responded do not
+/// form a quorum.
+fn compute_quorum_rounds(
+    committee: &Committee,
+    highest_received: &[Option<Vec<Round>>],
+) -> Option<Vec<Round>> {
+    let responded_stake: Stake = committee
+        .authorities()
+        .filter(|(peer, _)| highest_received[peer.value()].is_some())
+        .map(|(peer, _)| committee.stake(peer))
+        .sum();
+    if !committee.reached_quorum(responded_stake) {
+        return None;
+    }
+
+    let quorum_rounds = committee
+        .authorities()
+        .map(|(authority, _)| {
+            let mut rounds = committee
+                .authorities()
+                .filter_map(|(peer, _)| {
+                    let rounds = highest_received[peer.value()].as_ref()?;
+                    Some((rounds[authority], committee.stake(peer)))
+                })
+                .collect::<Vec<_>>();
+            rounds.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+            // The highest round such that peers with a quorum of stake have received it.
+            let mut stake = 0;
+            for (round, peer_stake) in rounds {
+                stake += peer_stake;
+                if committee.reached_quorum(stake) {
+                    return round;
+                }
+            }
+            GENESIS_ROUND
+        })
+        .collect();
+    Some(quorum_rounds)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use consensus_config::AuthorityIndex;
+
+    use super::*;
+    use crate::{
+        block::{BlockRef, VerifiedBlock},
+        block_manager::BlockRejection,
+        core_thread::CoreError,
+        error::{ConsensusError, ConsensusResult},
+        storage::mem_store::MemStore,
+    };
+
+    struct FakeCoreThreadDispatcher {}
+
+    #[async_trait]
+    impl CoreThreadDispatcher for FakeCoreThreadDispatcher {
+        async fn add_blocks(
+            &self,
+            _blocks: Vec<VerifiedBlock>,
+        ) -> Result<BTreeSet<BlockRef>, CoreError> {
+            unimplemented!()
+        }
+
+        async fn force_new_block(&self, _round: Round) -> Result<(), CoreError> {
+            unimplemented!()
+        }
+
+        async fn get_missing_blocks(&self) -> Result<BTreeMap<BlockRef, usize>, CoreError> {
+            unimplemented!()
+        }
+
+        async fn set_propagation_delay(&self, _delay: Round) -> Result<(), CoreError> {
+            unimplemented!()
+        }
+    }
+
+    struct FakeNetworkClient {
+        latest_rounds: Vec<Option<Vec<Round>>>,
+    }
+
+    #[async_trait]
+    impl NetworkClient for FakeNetworkClient {
+        async fn send_block(
+            &self,
+            _peer: AuthorityIndex,
+            _block: &VerifiedBlock,
+            _timeout: Duration,
+        ) -> ConsensusResult<()> {
+            unimplemented!("Unimplemented")
+        }
+
+        async fn fetch_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _block_refs: Vec<BlockRef>,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<Bytes>> {
+            unimplemented!("Unimplemented")
+        }
+
+        async fn get_latest_rounds(
+            &self,
+            peer: AuthorityIndex,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<Round>> {
+            self.latest_rounds[peer]
+                .clone()
+                .ok_or_else(|| ConsensusError::NetworkError("Unreachable".to_string()))
+        }
+
+        async fn report_rejected_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _rejections: &[BlockRejection],
+            _timeout: Duration,
+        ) -> ConsensusResult<()> {
+            unimplemented!("Unimplemented")
+        }
+    }
+
+    #[test]
+    fn test_compute_quorum_rounds() {
+        let (context, _) = Context::new_for_test(4);
+        let committee = &context.committee;
+
+        let highest_received = vec![
+            Some(vec![10, 10, 10, 10]),
+            Some(vec![8, 10, 9, 1]),
+            Some(vec![7, 9, 10, 2]),
+            None,
+        ];
+        assert_eq!(
+            compute_quorum_rounds(committee, &highest_received),
+            Some(vec![7, 9, 9, 1])
+        );
+
+        let highest_received = vec![
+            Some(vec![10, 10, 10, 10]),
+            Some(vec![8, 10, 9, 1]),
+            None,
+            None,
+        ];
+        assert_eq!(compute_quorum_rounds(committee, &highest_received), None);
+    }
+
+    #[tokio::test]
+    async fn test_probe() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let dag_state = Arc::new(RwLock::new(DagState::new(
+            context.clone(),
+            Arc::new(MemStore::new()),
+        )));
+
+        // The own authority has not accepted any block yet, so it has received round 0 from all
+        // authorities. The blocks of authority 3 are not received by enough peers.
+        let network_client = Arc::new(FakeNetworkClient {
+            latest_rounds: vec![
+                None,
+                Some(vec![5, 8, 7, 0]),
+                Some(vec![3, 7, 8, 6]),
+                Some(vec![4, 6, 6, 9]),
+            ],
+        });
+        let (stop_sender, stop) = tokio::sync::oneshot::channel();
+        let prober = RoundProber {
+            context: context.clone(),
+            network_client,
+            core_dispatcher: Arc::new(FakeCoreThreadDispatcher {}),
+            dag_state,
+            stop,
+        };
+
+        // The own blocks have not been proposed yet, so they cannot be delayed.
+        assert_eq!(prober.probe().await, 0);
+        drop(stop_sender);
+
+        let metrics = &context.metrics.node_metrics;
+        let quorum_round = |authority: usize| {
+            let hostname = &context
+                .committee
+                .authorities()
+                .nth(authority)
+                .unwrap()
+                .1
+                .hostname;
+            metrics
+                .round_prober_quorum_round
+                .with_label_values(&[hostname])
+                .get()
+        };
+        let propagation_delay = |authority: usize| {
+            let hostname = &context
+                .committee
+                .authorities()
+                .nth(authority)
+                .unwrap()
+                .1
+                .hostname;
+            metrics
+                .round_prober_propagation_delay
+                .with_label_values(&[hostname])
+                .get()
+        };
+        assert_eq!(
+            (0..4).map(quorum_round).collect::<Vec<_>>(),
+            vec![3, 6, 6, 0]
+        );
+        assert_eq!(
+            (0..4).map(propagation_delay).collect::<Vec<_>>(),
+            vec![2, 2, 2, 9]
+        );
+    }
+}
@@ -0,0 +1,398 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::VecDeque, path::Path, sync::Arc};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::{
+    sync::oneshot::{Receiver, Sender},
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+use tracing::{debug, warn};
+
+use crate::{
+    block::{timestamp_utc_ms, BlockAPI, BlockTimestampMs},
+    commit::CommittedSubDag,
+    context::Context,
+};
+
+/// A commit observed by the tracker, reduced to what is needed to produce reports.
+struct CommitSample {
+    observed_at_ms: BlockTimestampMs,
+    /// Time between the leader block creation and the commit being observed.
+    latency_ms: u64,
+    leader_author: usize,
+    block_authors: Vec<usize>,
+    num_transactions: usize,
+}
+
+/// Keeps the commits of the trailing `Parameters::slo_report_window`, to compute commit SLO
+/// reports from. Commits are recorded by CommitObserver, and reports are produced periodically
+/// by SloReportTask.
+pub(crate) struct CommitSloTracker {
+    context: Arc<Context>,
+    started_at_ms: BlockTimestampMs,
+    samples: Mutex<VecDeque<CommitSample>>,
+}
+
+impl CommitSloTracker {
+    pub(crate) fn new(context: Arc<Context>) -> Self {
+        Self {
+            context,
+            started_at_ms: timestamp_utc_ms(),
+            samples: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn record(&self, committed: &[CommittedSubDag]) {
+        self.record_at(timestamp_utc_ms(), committed);
+    }
+
+    fn record_at(&self, now_ms: BlockTimestampMs, committed: &[CommittedSubDag]) {
+        let mut samples = self.samples.lock();
+        for sub_dag in committed {
+            samples.push_back(CommitSample {
+                observed_at_ms: now_ms,
+                latency_ms: now_ms.saturating_sub(sub_dag.timestamp_ms),
+                leader_author: sub_dag.leader.author.value(),
+                block_authors: sub_dag
+                    .blocks
+                    .iter()
+                    .map(|block| block.author().value())
+                    .collect(),
+                num_transactions: sub_dag
+                    .blocks
+                    .iter()
+                    .map(|block| block.transactions().len())
+                    .sum(),
+            });
+        }
+        self.evict(&mut samples, now_ms);
+    }
+
+    pub(crate) fn report(&self) -> SloReport {
+        self.report_at(timestamp_utc_ms())
+    }
+
+    fn report_at(&self, now_ms: BlockTimestampMs) -> SloReport {
+        let mut samples = self.samples.lock();
+        self.evict(&mut samples, now_ms);
+
+        // Until the tracker has run for a full window, rates are computed over the time elapsed.
+        let window_ms = self.context.parameters.slo_report_window.as_millis() as u64;
+        let elapsed_ms = now_ms.saturating_sub(self.started_at_ms).min(window_ms);
+        let per_sec = |count: usize| {
+            if elapsed_ms == 0 {
+                0.0
+            } else {
+                count as f64 * 1000.0 / elapsed_ms as f64
+            }
+        };
+
+        let committee_size = self.context.committee.size();
+        let mut leaders_committed = vec![0; committee_size];
+        let mut blocks_committed = vec![0; committee_size];
+        let mut num_blocks = 0;
+        let mut num_transactions = 0;
+        let mut latencies = Vec::with_capacity(samples.len());
+        for sample in samples.iter() {
+            leaders_committed[sample.leader_author] += 1;
+            for author in &sample.block_authors {
+                blocks_committed[*author] += 1;
+            }
+            num_blocks += sample.block_authors.len();
+            num_transactions += sample.num_transactions;
+            latencies.push(sample.latency_ms);
+        }
+        latencies.sort_unstable();
+
+        let authorities = self
+            .context
+            .committee
+            .authorities()
+            .map(|(index, authority)| AuthorityContribution {
+                authority: index.value() as u32,
+                hostname: authority.hostname.clone(),
+                leaders_committed: leaders_committed[index],
+                blocks_committed: blocks_committed[index],
+                block_share: if num_blocks == 0 {
+                    0.0
+                } else {
+                    blocks_committed[index] as f64 / num_blocks as f64
+                },
+            })
+            .collect();
+
+        SloReport {
+            generated_at_ms: now_ms,
+            window_ms: elapsed_ms,
+            commits: samples.len() as u64,
+            commits_per_sec: per_sec(samples.len()),
+            blocks_per_sec: per_sec(num_blocks),
+            transactions_per_sec: per_sec(num_transactions),
+            commit_latency_ms: LatencyPercentiles {
+                p50: percentile(&latencies, 50),
+                p95: percentile(&latencies, 95),
+                p99: percentile(&latencies, 99),
+            },
+            authorities,
+        }
+    }
+
+    fn evict(&self, samples: &mut VecDeque<CommitSample>, now_ms: BlockTimestampMs) {
+        let window_ms = self.context.parameters.slo_report_window.as_millis() as u64;
+        while let Some(sample) = samples.front() {
+            if sample.observed_at_ms + window_ms >= now_ms {
+                break;
+            }
+            samples.pop_front();
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted values, or 0 when there are no values.
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Summary of the commits of the trailing window, suitable to be attached to incident reviews and
+/// release comparisons as evidence of commit throughput and latency.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct SloReport {
+    pub generated_at_ms: BlockTimestampMs,
+    /// Duration covered by the report. Shorter than the configured window until the authority
+    /// has run for a full window.
+    pub window_ms: u64,
+    pub commits: u64,
+    pub commits_per_sec: f64,
+    pub blocks_per_sec: f64,
+    pub transactions_per_sec: f64,
+    pub commit_latency_ms: LatencyPercentiles,
+    pub authorities: Vec<AuthorityContribution>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct LatencyPercentiles {
+    pub p50: u64,
+    pub p95: u64,
+    pub p99: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct AuthorityContribution {
+    pub authority: u32,
+    pub hostname: String,
+    pub leaders_committed: u64,
+    pub blocks_committed: u64,
+    /// Share of the committed blocks of the window authored by this authority.
+    pub block_share: f64,
+}
+
+pub(crate) struct SloReportTaskHandle {
+    handle: JoinHandle<()>,
+    stop: Sender<()>,
+}
+
+impl SloReportTaskHandle {
+    pub async fn stop(self) {
+        self.stop.send(()).ok();
+        self.handle.await.ok();
+    }
+}
+
+/// Periodically produces a SloReport from the CommitSloTracker, reports it in metrics and writes
+/// it to `Parameters::slo_report_path` if set.
+pub(crate) struct SloReportTask {
+    context: Arc<Context>,
+    tracker: Arc<CommitSloTracker>,
+    stop: Receiver<()>,
+}
+
+impl SloReportTask {
+    pub fn start(context: Arc<Context>, tracker: Arc<CommitSloTracker>) -> SloReportTaskHandle {
+        let (stop_sender, stop) = tokio::sync::oneshot::channel();
+        let mut me = Self {
+            context,
+            tracker,
+            stop,
+        };
+        let handle = tokio::spawn(async move { me.run().await });
+
+        SloReportTaskHandle {
+            handle,
+            stop: stop_sender,
+        }
+    }
+
+    async fn run(&mut self) {
+        let mut report_interval = interval(self.context.parameters.slo_report_interval);
+        report_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick completes immediately, and there is nothing to report yet.
+        report_interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = report_interval.tick() => {
+                    let report = self.tracker.report();
+                    self.report_metrics(&report);
+                    if let Some(path) = &self.context.parameters.slo_report_path {
+                        if let Err(e) = write_report(path, &report).await {
+                            warn!("Failed to write commit SLO report to {}: {e:?}", path.display());
+                        }
+                    }
+                }
+                _ = &mut self.stop => {
+                    debug!("Stop signal has been received, now shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    fn report_metrics(&self, report: &SloReport) {
+        let metrics = &self.context.metrics.node_metrics;
+        metrics
+            .slo_commit_throughput
+            .with_label_values(&["commits"])
+            .set(report.commits_per_sec);
+        metrics
+            .slo_commit_throughput
+            .with_label_values(&["blocks"])
+            .set(report.blocks_per_sec);
+        metrics
+            .slo_commit_throughput
+            .with_label_values(&["transactions"])
+            .set(report.transactions_per_sec);
+        for (quantile, latency_ms) in [
+            ("p50", report.commit_latency_ms.p50),
+            ("p95", report.commit_latency_ms.p95),
+            ("p99", report.commit_latency_ms.p99),
+        ] {
+            metrics
+                .slo_commit_latency_ms
+                .with_label_values(&[quantile])
+                .set(latency_ms as i64);
+        }
+        for authority in &report.authorities {
+            metrics
+                .slo_authority_block_share
+                .with_label_values(&[&authority.hostname])
+                .set(authority.block_share);
+        }
+    }
+}
+
+/// Writes the report as JSON, replacing the previous report atomically so readers never observe a
+/// partially written file.
+async fn write_report(path: &Path, report: &SloReport) -> anyhow::Result<()> {
+    let contents = serde_json::to_vec_pretty(report)?;
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, contents).await?;
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use consensus_config::{AuthorityIndex, Parameters};
+
+    use super::*;
+    use crate::{
+        block::{BlockRef, TestBlock, VerifiedBlock},
+        commit::{CommitDigest, CommitRef},
+    };
+
+    fn sub_dag(
+        commit_index: u32,
+        leader_author: u32,
+        authors: &[u32],
+        timestamp_ms: BlockTimestampMs,
+    ) -> CommittedSubDag {
+        let blocks: Vec<_> = authors
+            .iter()
+            .map(|author| {
+                VerifiedBlock::new_for_test(
+                    TestBlock::new(commit_index, *author)
+                        .set_timestamp_ms(timestamp_ms)
+                        .build(),
+                )
+            })
+            .collect();
+        let leader = BlockRef {
+            author: AuthorityIndex::new_for_test(leader_author),
+            ..blocks.last().unwrap().reference()
+        };
+        let commit_ref = CommitRef {
+            index: commit_index,
+            digest: CommitDigest::MIN,
+        };
+        CommittedSubDag::new(leader, blocks, timestamp_ms, commit_ref)
+    }
+
+    #[test]
+    fn test_report_over_window() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context.with_parameters(Parameters {
+            slo_report_window: Duration::from_secs(10),
+            ..Default::default()
+        }));
+        let tracker = CommitSloTracker::new(context);
+        let start = tracker.started_at_ms;
+
+        // Evicted from the window by the time of the report.
+        tracker.record_at(start + 1_000, &[sub_dag(1, 0, &[0, 1, 2, 3], start)]);
+        // 100 commits with latencies 1..=100ms, each with a block from authorities 1 and 2.
+        for i in 1..=100u64 {
+            let observed_at = start + 5_000 + i;
+            tracker.record_at(
+                observed_at,
+                &[sub_dag(i as u32 + 1, 1, &[1, 2], observed_at - i)],
+            );
+        }
+
+        let report = tracker.report_at(start + 12_000);
+        assert_eq!(report.window_ms, 10_000);
+        assert_eq!(report.commits, 100);
+        assert_eq!(report.commits_per_sec, 10.0);
+        assert_eq!(report.blocks_per_sec, 20.0);
+        assert_eq!(
+            report.commit_latency_ms,
+            LatencyPercentiles {
+                p50: 50,
+                p95: 95,
+                p99: 99,
+            }
+        );
+        assert_eq!(report.authorities.len(), 4);
+        assert_eq!(report.authorities[0].leaders_committed, 0);
+        assert_eq!(report.authorities[0].blocks_committed, 0);
+        assert_eq!(report.authorities[1].leaders_committed, 100);
+        assert_eq!(report.authorities[1].blocks_committed, 100);
+        assert_eq!(report.authorities[1].block_share, 0.5);
+        assert_eq!(report.authorities[2].leaders_committed, 0);
+        assert_eq!(report.authorities[2].block_share, 0.5);
+    }
+
+    #[test]
+    fn test_report_without_commits() {
+        let (context, _) = Context::new_for_test(4);
+        let tracker = CommitSloTracker::new(Arc::new(context));
+
+        let report = tracker.report_at(tracker.started_at_ms);
+        assert_eq!(report.commits, 0);
+        assert_eq!(report.commits_per_sec, 0.0);
+        assert_eq!(report.commit_latency_ms.p99, 0);
+        assert!(report
+            .authorities
+            .iter()
+            .all(|authority| authority.block_share == 0.0));
+    }
+}
@@ -0,0 +1,297 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use consensus_config::{Committee, Stake};
+use parking_lot::RwLock;
+use tokio::{
+    sync::oneshot::{Receiver, Sender},
+    task::JoinHandle,
+    time::{interval, Instant, MissedTickBehavior},
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    block::{Round, GENESIS_ROUND},
+    context::Context,
+    dag_state::DagState,
+};
+
+/// Interval between checks of whether the authority is stalled.
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Why a stalled authority cannot advance to the next round: the authorities whose blocks are
+/// missing to form a quorum at the round it waits on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StallReason {
+    /// The round the authority waits for a quorum of blocks of.
+    pub round: Round,
+    /// The hostnames of the authorities without an accepted block at or above the round.
+    pub missing_authorities: Vec<String>,
+    /// The total stake of the missing authorities.
+    pub missing_stake: Stake,
+    /// How long the authority has been waiting on the round.
+    pub stalled_for: Duration,
+}
+
+impl fmt::Display for StallReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot reach quorum: missing authorities {} with total stake {} (waiting on round {} \
+            for {}s)",
+            self.missing_authorities.join(","),
+            self.missing_stake,
+            self.round,
+            self.stalled_for.as_secs(),
+        )
+    }
+}
+
+pub(crate) struct StallDetectorHandle {
+    handle: JoinHandle<()>,
+    stop: Sender<()>,
+    status: Arc<RwLock<Option<StallReason>>>,
+}
+
+impl StallDetectorHandle {
+    /// Returns why the authority is stalled, or `None` if it is making progress.
+    pub fn stall_reason(&self) -> Option<StallReason> {
+        self.status.read().clone()
+    }
+
+    pub async fn stop(self) {
+        self.stop.send(()).ok();
+        self.handle.await.ok();
+    }
+}
+
+/// Periodically checks whether the authority advances rounds, from the rounds of the blocks it
+/// accepted. When it has waited on a round for longer than `Parameters::stall_detection_timeout`,
+/// which happens when no quorum of authorities is reachable, the authority is stalled. The
+/// authorities whose blocks are missing to reach a quorum are reported in metrics and logs, and
+/// through `ConsensusAuthority::stall_reason()`, to speed up triage during partitions.
+pub(crate) struct StallDetector {
+    context: Arc<Context>,
+    dag_state: Arc<RwLock<DagState>>,
+    status: Arc<RwLock<Option<StallReason>>>,
+    /// The round waited on, and since when it is waited on.
+    waiting: Option<(Round, Instant)>,
+    stop: Receiver<()>,
+}
+
+impl StallDetector {
+    pub fn start(context: Arc<Context>, dag_state: Arc<RwLock<DagState>>) -> StallDetectorHandle {
+        let (stop_sender, stop) = tokio::sync::oneshot::channel();
+        let status = Arc::new(RwLock::new(None));
+        let mut me = Self {
+            context,
+            dag_state,
+            status: status.clone(),
+            waiting: None,
+            stop,
+        };
+        let handle = tokio::spawn(async move { me.run().await });
+
+        StallDetectorHandle {
+            handle,
+            stop: stop_sender,
+            status,
+        }
+    }
+
+    async fn run(&mut self) {
+        let mut check_interval = interval(STALL_CHECK_INTERVAL);
+        check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = check_interval.tick() => {
+                    self.check(Instant::now());
+                }
+                _ = &mut self.stop => {
+                    debug!("Stop signal has been received, now shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    fn check(&mut self, now: Instant) {
+        let committee = &self.context.committee;
+        let last_accepted_rounds = self.dag_state.read().last_accepted_rounds();
+        let round = waiting_round(committee, &last_accepted_rounds);
+        let since = match self.waiting {
+            Some((waiting_round, since)) if waiting_round == round => since,
+            _ => {
+                self.waiting = Some((round, now));
+                now
+            }
+        };
+        let stalled_for = now.duration_since(since);
+        let reason = (stalled_for >= self.context.parameters.stall_detection_timeout)
+            .then(|| explain_stall(committee, &last_accepted_rounds, round, stalled_for));
+        self.report(reason);
+    }
+
+    fn report(&self, reason: Option<StallReason>) {
+        let committee = &self.context.committee;
+        let metrics = &self.context.metrics.node_metrics;
+        metrics.stall_detected.set(reason.is_some() as i64);
+        metrics
+            .stall_missing_stake
+            .set(reason.as_ref().map_or(0, |reason| reason.missing_stake) as i64);
+        for (_, authority) in committee.authorities() {
+            let missing = reason
+                .as_ref()
+                .is_some_and(|reason| reason.missing_authorities.contains(&authority.hostname));
+            metrics
+                .stall_missing_authorities
+                .with_label_values(&[&authority.hostname])
+                .set(missing as i64);
+        }
+
+        let mut status = self.status.write();
+        match (&*status, &reason) {
+            // Only log when the stall starts, or when the missing authorities change.
+            (Some(previous), Some(reason))
+                if previous.round == reason.round
+                    && previous.missing_authorities == reason.missing_authorities => {}
+            (_, Some(reason)) => warn!("Consensus is stalled: {reason}"),
+            (Some(previous), None) => info!(
+                "Consensus is no longer stalled, after waiting on round {} for {}s",
+                previous.round,
+                previous.stalled_for.as_secs()
+            ),
+            (None, None) => {}
+        }
+        *status = reason;
+    }
+}
+
+/// Returns the round the authority waits for a quorum of blocks of, given the round of the last
+/// accepted block of each authority: the highest accepted round, or the round after it once the
+/// blocks of a quorum are accepted at it. This mirrors the round of the ThresholdClock.
+fn waiting_round(committee: &Committee, last_accepted_rounds: &[Round]) -> Round {
+    let highest_round = last_accepted_rounds
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(GENESIS_ROUND);
+    let stake: Stake = committee
+        .authorities()
+        .filter(|(authority, _)| last_accepted_rounds[authority.value()] >= highest_round)
+        .map(|(authority, _)| committee.stake(authority))
+        .sum();
+    if committee.reached_quorum(stake) {
+        highest_round + 1
+    } else {
+        highest_round
+    }
+}
+
+/// Explains why the authority cannot form a quorum at `round`, from the authorities whose last
+/// accepted block is below it.
+fn explain_stall(
+    committee: &Committee,
+    last_accepted_rounds: &[Round],
+    round: Round,
+    stalled_for: Duration,
+) -> StallReason {
+    let missing = committee
+        .authorities()
+        .filter(|(authority, _)| last_accepted_rounds[authority.value()] < round)
+        .collect::<Vec<_>>();
+    StallReason {
+        round,
+        missing_authorities: missing
+            .iter()
+            .map(|(_, authority)| authority.hostname.clone())
+            .collect(),
+        missing_stake: missing.iter().map(|(_, authority)| authority.stake).sum(),
+        stalled_for,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::storage::mem_store::MemStore;
+
+    #[test]
+    fn test_waiting_round() {
+        let (context, _) = Context::new_for_test(4);
+        let committee = &context.committee;
+
+        // A quorum of blocks is accepted at round 5, so the authority waits on round 6.
+        assert_eq!(waiting_round(committee, &[5, 5, 5, 3]), 6);
+        // Only one block is accepted at round 5, so the authority waits on the other blocks of it.
+        assert_eq!(waiting_round(committee, &[5, 4, 4, 4]), 5);
+        assert_eq!(waiting_round(committee, &[0, 0, 0, 0]), 1);
+    }
+
+    #[test]
+    fn test_explain_stall() {
+        let (context, _) = Context::new_for_test(4);
+        let committee = &context.committee;
+
+        let reason = explain_stall(committee, &[6, 6, 5, 2], 6, Duration::from_secs(12));
+        assert_eq!(
+            reason,
+            StallReason {
+                round: 6,
+                missing_authorities: vec!["test_host_2".to_string(), "test_host_3".to_string()],
+                missing_stake: 2,
+                stalled_for: Duration::from_secs(12),
+            }
+        );
+        assert_eq!(
+            reason.to_string(),
+            "cannot reach quorum: missing authorities test_host_2,test_host_3 with total stake 2 \
+            (waiting on round 6 for 12s)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let dag_state = Arc::new(RwLock::new(DagState::new(
+            context.clone(),
+            Arc::new(MemStore::new()),
+        )));
+        let (_stop_sender, stop) = tokio::sync::oneshot::channel();
+        let mut detector = StallDetector {
+            context: context.clone(),
+            dag_state,
+            status: Arc::new(RwLock::new(None)),
+            waiting: None,
+            stop,
+        };
+
+        // No block is accepted after genesis, so the authority waits on round 1 from the start.
+        let start = Instant::now();
+        detector.check(start);
+        assert_eq!(*detector.status.read(), None);
+
+        let timeout = context.parameters.stall_detection_timeout;
+        detector.check(start + timeout);
+        let reason = detector.status.read().clone().unwrap();
+        assert_eq!(reason.round, 1);
+        assert_eq!(reason.missing_authorities.len(), 4);
+        assert_eq!(reason.missing_stake, 4);
+        assert_eq!(reason.stalled_for, timeout);
+
+        let metrics = &context.metrics.node_metrics;
+        assert_eq!(metrics.stall_detected.get(), 1);
+        assert_eq!(metrics.stall_missing_stake.get(), 4);
+        assert_eq!(
+            metrics
+                .stall_missing_authorities
+                .with_label_values(&["test_host_0"])
+                .get(),
+            1
+        );
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use super::rocksdb_store::RocksDBStore;
+use crate::{block::BlockRef, commit::CommitIndex, context::Context};
+
+/// Result of verifying the integrity of a consensus store.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct StoreIntegrityReport {
+    pub blocks_checked: u64,
+    pub commits_checked: u64,
+    /// Blocks that cannot be deserialized, or do not match the reference they are stored by.
+    pub corrupted_blocks: Vec<BlockRef>,
+    /// Blocks that are missing from the index of blocks by author.
+    pub unindexed_blocks: Vec<BlockRef>,
+    /// Entries of the index of blocks by author, for blocks that are not in the store.
+    pub dangling_index_entries: Vec<BlockRef>,
+    /// Index of the last commit in the store, or 0 if there is none.
+    pub last_commit: CommitIndex,
+    /// The first commit that is corrupted, does not extend the previous commit, or references a
+    /// block that is missing or corrupted. All commits after it are inconsistent as well.
+    pub inconsistent_commit: Option<InconsistentCommit>,
+    /// Index of the last consistent commit that the store can recover from, which a repair
+    /// truncates the commits to.
+    pub last_consistent_commit: CommitIndex,
+    /// Set if the store could not be read through, e.g. due to a RocksDB checksum mismatch, in
+    /// which case the report is incomplete and the store cannot be repaired.
+    pub read_error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InconsistentCommit {
+    pub index: CommitIndex,
+    pub reason: String,
+}
+
+impl StoreIntegrityReport {
+    pub fn is_consistent(&self) -> bool {
+        self.read_error.is_none()
+            && self.corrupted_blocks.is_empty()
+            && self.unindexed_blocks.is_empty()
+            && self.dangling_index_entries.is_empty()
+            && self.last_consistent_commit == self.last_commit
+    }
+
+    pub fn is_repairable(&self) -> bool {
+        self.read_error.is_none()
+    }
+
+    /// Whether repairing the store would truncate commits that the authority already processed,
+    /// which it cannot recover from: the commits it recovers on restart must extend the last one
+    /// it processed.
+    pub fn truncates_processed_commits(&self, last_processed_commit: CommitIndex) -> bool {
+        self.last_consistent_commit < self.last_commit
+            && self.last_consistent_commit < last_processed_commit
+    }
+}
+
+impl fmt::Display for StoreIntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Checked {} blocks and {} commits: {}",
+            self.blocks_checked,
+            self.commits_checked,
+            if self.is_consistent() {
+                "the store is consistent"
+            } else {
+                "the store is corrupted"
+            }
+        )?;
+        if let Some(e) = &self.read_error {
+            writeln!(f, "Failed to read the store, the report is incomplete: {e}")?;
+        }
+        for (description, blocks) in [
+            ("Corrupted blocks", &self.corrupted_blocks),
+            (
+                "Blocks missing from the author index",
+                &self.unindexed_blocks,
+            ),
+            (
+                "Author index entries of missing blocks",
+                &self.dangling_index_entries,
+            ),
+        ] {
+            if !blocks.is_empty() {
+                writeln!(f, "{description} ({}):", blocks.len())?;
+                for block in blocks {
+                    writeln!(f, "  {block}")?;
+                }
+            }
+        }
+        if let Some(commit) = &self.inconsistent_commit {
+            writeln!(
+                f,
+                "Commit {} is inconsistent: {}",
+                commit.index, commit.reason
+            )?;
+        }
+        if self.last_consistent_commit != self.last_commit {
+            writeln!(
+                f,
+                "Commits {}..={} would be truncated, down to the last consistent commit {}",
+                self.last_consistent_commit + 1,
+                self.last_commit,
+                self.last_consistent_commit
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Verifies the integrity of the consensus store at `db_path`, and repairs it if `repair` is set
+/// and it is corrupted, by removing corrupted blocks, fixing the index of blocks by author, and
+/// truncating commits to the last consistent one. The store must not be opened by a running
+/// authority.
+///
+/// `repair` is the index of the last commit processed by the authority, which commits are never
+/// truncated below. A store that can only be repaired by truncating processed commits is left
+/// as is.
+///
+/// A repair removes corrupted blocks, including the authority's own: an authority that lost its
+/// own blocks could propose equivocating blocks at their rounds, so it must not be restarted
+/// before they are fetched back from peers, or the epoch ends.
+pub fn verify_store(
+    db_path: &str,
+    repair: Option<CommitIndex>,
+) -> Result<StoreIntegrityReport, anyhow::Error> {
+    let store = RocksDBStore::new(db_path);
+    let report = store.verify_integrity();
+    if let Some(last_processed_commit) = repair {
+        if !report.is_consistent() {
+            if !report.is_repairable() {
+                anyhow::bail!("Cannot repair the store, it could not be read through:\n{report}");
+            }
+            if report.truncates_processed_commits(last_processed_commit) {
+                anyhow::bail!(
+                    "Cannot repair the store, it would truncate commits below the last processed \
+                     commit {last_processed_commit}:\n{report}"
+                );
+            }
+            store.repair(&report)?;
+        }
+    }
+    Ok(report)
+}
+
+/// Verifies the integrity of the authority's store on startup, if enabled by its parameters.
+/// Panics if the store is corrupted, unless it is to be repaired and can be repaired safely,
+/// without truncating commits up to `last_processed_commit`.
+pub(crate) fn verify_store_on_startup(
+    context: &Context,
+    store: &RocksDBStore,
+    last_processed_commit: CommitIndex,
+) {
+    if !context.parameters.verify_store_on_startup {
+        return;
+    }
+    let report = store.verify_integrity();
+    if report.is_consistent() {
+        info!("Verified the consensus store. {report}");
+        return;
+    }
+    error!("The consensus store is corrupted. {report}");
+    assert!(
+        context.parameters.repair_store_on_startup,
+        "The consensus store is corrupted, set repair_store_on_startup to repair it. {report}"
+    );
+    assert!(
+        report.is_repairable(),
+        "The consensus store is corrupted and cannot be repaired. {report}"
+    );
+    // Repairing would remove the authority's own corrupted blocks, which it would then be at
+    // risk of equivocating.
+    assert!(
+        report
+            .corrupted_blocks
+            .iter()
+            .all(|block| block.author != context.own_index),
+        "The consensus store cannot be repaired safely, some of the authority's own blocks are \
+         corrupted. {report}"
+    );
+    // The commits that the authority already processed cannot be committed again, so recovering
+    // from a store truncated below them would fork its output.
+    assert!(
+        !report.truncates_processed_commits(last_processed_commit),
+        "The consensus store cannot be repaired safely, it would truncate commits down to {}, \
+         below the last processed commit {last_processed_commit}. {report}",
+        report.last_consistent_commit
+    );
+    store
+        .repair(&report)
+        .unwrap_or_else(|e| panic!("Failed to repair the consensus store: {e}"));
+    warn!(
+        "Repaired the consensus store, truncating commits to {}",
+        report.last_consistent_commit
+    );
+}
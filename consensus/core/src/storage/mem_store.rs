@@ -18,6 +18,7 @@ use crate::{
     block::{BlockAPI as _, BlockDigest, BlockRef, Round, VerifiedBlock},
     commit::{CommitDigest, CommitIndex},
     error::ConsensusResult,
+    event_log::ConsensusEventRecord,
 };
 
 /// In-memory storage for testing.
@@ -31,6 +32,8 @@ struct Inner {
     commits: BTreeMap<(CommitIndex, CommitDigest), TrustedCommit>,
     commit_votes: BTreeSet<(CommitIndex, CommitDigest, BlockRef)>,
     commit_info: BTreeMap<(CommitIndex, CommitDigest), CommitInfo>,
+    events: BTreeMap<u64, ConsensusEventRecord>,
+    suspended_blocks: BTreeMap<BlockRef, VerifiedBlock>,
 }
 
 impl MemStore {
@@ -43,6 +46,8 @@ impl MemStore {
                 commits: BTreeMap::new(),
                 commit_votes: BTreeSet::new(),
                 commit_info: BTreeMap::new(),
+                events: BTreeMap::new(),
+                suspended_blocks: BTreeMap::new(),
             }),
         }
     }
@@ -82,6 +87,18 @@ impl Store for MemStore {
                 .commit_info
                 .insert((last_commit.index(), last_commit.digest()), commit_info);
         }
+        for event in write_batch.events {
+            inner.events.insert(event.sequence, event);
+        }
+        inner
+            .events
+            .retain(|sequence, _| !write_batch.evict_events.contains(sequence));
+        for block_ref in write_batch.unsuspended_blocks {
+            inner.suspended_blocks.remove(&block_ref);
+        }
+        for block in write_batch.suspended_blocks {
+            inner.suspended_blocks.insert(block.reference(), block);
+        }
         Ok(())
     }
 
@@ -196,4 +213,31 @@ impl Store for MemStore {
         let inner = self.inner.read();
         Ok(inner.commit_info.last_key_value().map(|(_k, v)| v.clone()))
     }
+
+    fn scan_events(
+        &self,
+        start_sequence: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<ConsensusEventRecord>> {
+        let inner = self.inner.read();
+        Ok(inner
+            .events
+            .range(start_sequence..)
+            .take(limit)
+            .map(|(_, event)| event.clone())
+            .collect())
+    }
+
+    fn read_last_event(&self) -> ConsensusResult<Option<ConsensusEventRecord>> {
+        let inner = self.inner.read();
+        Ok(inner
+            .events
+            .last_key_value()
+            .map(|(_, event)| event.clone()))
+    }
+
+    fn read_suspended_blocks(&self) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let inner = self.inner.read();
+        Ok(inner.suspended_blocks.values().cloned().collect())
+    }
 }
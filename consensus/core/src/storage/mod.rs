@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod integrity;
 pub(crate) mod mem_store;
 pub(crate) mod rocksdb_store;
 
@@ -17,6 +18,7 @@ use crate::{
     block::{BlockRef, Round, VerifiedBlock},
     commit::{CommitIndex, TrustedCommit},
     error::ConsensusResult,
+    event_log::ConsensusEventRecord,
 };
 
 /// A common interface for consensus storage.
@@ -58,6 +60,19 @@ pub(crate) trait Store: Send + Sync {
 
     /// Reads the last commit info, including last committed round per authority.
     fn read_last_commit_info(&self) -> ConsensusResult<Option<CommitInfo>>;
+
+    /// Reads up to `limit` events of the event log, from `start_sequence` (inclusive).
+    fn scan_events(
+        &self,
+        start_sequence: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<ConsensusEventRecord>>;
+
+    /// Reads the last event of the event log.
+    fn read_last_event(&self) -> ConsensusResult<Option<ConsensusEventRecord>>;
+
+    /// Reads the blocks persisted as suspended by the block manager.
+    fn read_suspended_blocks(&self) -> ConsensusResult<Vec<VerifiedBlock>>;
 }
 
 /// Represents data to be written to the store together atomically.
@@ -66,6 +81,14 @@ pub(crate) struct WriteBatch {
     pub(crate) blocks: Vec<VerifiedBlock>,
     pub(crate) commits: Vec<TrustedCommit>,
     pub(crate) last_committed_rounds: Vec<Round>,
+    pub(crate) events: Vec<ConsensusEventRecord>,
+    /// Sequence numbers of the events to evict from the event log.
+    pub(crate) evict_events: Range<u64>,
+    /// Blocks suspended by the block manager, persisted to be recovered after a restart.
+    pub(crate) suspended_blocks: Vec<VerifiedBlock>,
+    /// Blocks that are no longer suspended, to remove from the persisted suspended blocks. They
+    /// are removed before `suspended_blocks` are written.
+    pub(crate) unsuspended_blocks: Vec<BlockRef>,
 }
 
 impl WriteBatch {
@@ -73,14 +96,30 @@ impl WriteBatch {
         blocks: Vec<VerifiedBlock>,
         commits: Vec<TrustedCommit>,
         last_committed_rounds: Vec<Round>,
+        events: Vec<ConsensusEventRecord>,
+        evict_events: Range<u64>,
     ) -> Self {
         WriteBatch {
             blocks,
             commits,
             last_committed_rounds,
+            events,
+            evict_events,
+            suspended_blocks: vec![],
+            unsuspended_blocks: vec![],
         }
     }
 
+    pub(crate) fn suspended_blocks(
+        mut self,
+        suspended_blocks: Vec<VerifiedBlock>,
+        unsuspended_blocks: Vec<BlockRef>,
+    ) -> Self {
+        self.suspended_blocks = suspended_blocks;
+        self.unsuspended_blocks = unsuspended_blocks;
+        self
+    }
+
     // Test setters.
 
     #[cfg(test)]
@@ -94,6 +133,17 @@ impl WriteBatch {
         self.commits = commits;
         self
     }
+
+    #[cfg(test)]
+    pub(crate) fn events(
+        mut self,
+        events: Vec<ConsensusEventRecord>,
+        evict_events: Range<u64>,
+    ) -> Self {
+        self.events = events;
+        self.evict_events = evict_events;
+        self
+    }
 }
 
 /// Per-commit properties that can be derived and do not need to be part of the Commit struct.
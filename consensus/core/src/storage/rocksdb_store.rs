@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 use std::ops::Range;
 use std::{
     ops::Bound::{Excluded, Included},
@@ -17,13 +17,15 @@ use typed_store::{
     Map as _,
 };
 
+use super::integrity::{InconsistentCommit, StoreIntegrityReport};
 use super::{CommitInfo, Store, WriteBatch};
 use crate::block::Slot;
-use crate::commit::{CommitAPI as _, CommitDigest, TrustedCommit};
+use crate::commit::{Commit, CommitAPI as _, CommitDigest, TrustedCommit};
 use crate::{
     block::{BlockAPI as _, BlockDigest, BlockRef, Round, SignedBlock, VerifiedBlock},
     commit::CommitIndex,
     error::{ConsensusError, ConsensusResult},
+    event_log::ConsensusEventRecord,
 };
 
 /// Persistent storage with RocksDB.
@@ -39,6 +41,10 @@ pub(crate) struct RocksDBStore {
     commit_votes: DBMap<(CommitIndex, CommitDigest, BlockRef), ()>,
     /// Stores the latest values of a few properties.
     commit_info: DBMap<(CommitIndex, CommitDigest), CommitInfo>,
+    /// Bounded log of consensus events, by sequence number.
+    events: DBMap<u64, ConsensusEventRecord>,
+    /// Stores the SignedBlock of blocks suspended by the block manager, by refs.
+    suspended_blocks: DBMap<(Round, AuthorityIndex, BlockDigest), Bytes>,
 }
 
 impl RocksDBStore {
@@ -47,6 +53,8 @@ impl RocksDBStore {
     const COMMITS_CF: &'static str = "commits";
     const COMMIT_VOTES_CF: &'static str = "commit_votes";
     const COMMIT_INFO_CF: &'static str = "commit_info";
+    const EVENTS_CF: &'static str = "events";
+    const SUSPENDED_BLOCKS_CF: &'static str = "suspended_blocks";
 
     /// Creates a new instance of RocksDB storage.
     pub(crate) fn new(path: &str) -> Self {
@@ -70,6 +78,8 @@ impl RocksDBStore {
             (Self::COMMITS_CF, cf_options.clone()),
             (Self::COMMIT_VOTES_CF, cf_options.clone()),
             (Self::COMMIT_INFO_CF, cf_options.clone()),
+            (Self::EVENTS_CF, cf_options.clone()),
+            (Self::SUSPENDED_BLOCKS_CF, cf_options.clone()),
         ];
         let rocksdb = open_cf_opts(
             path,
@@ -79,12 +89,22 @@ impl RocksDBStore {
         )
         .expect("Cannot open database");
 
-        let (blocks, digests_by_authorities, commits, commit_votes, commit_info) = reopen!(&rocksdb,
+        let (
+            blocks,
+            digests_by_authorities,
+            commits,
+            commit_votes,
+            commit_info,
+            events,
+            suspended_blocks,
+        ) = reopen!(&rocksdb,
             Self::BLOCKS_CF;<(Round, AuthorityIndex, BlockDigest), bytes::Bytes>,
             Self::DIGESTS_BY_AUTHORITIES_CF;<(AuthorityIndex, Round, BlockDigest), ()>,
             Self::COMMITS_CF;<(CommitIndex, CommitDigest), Bytes>,
             Self::COMMIT_VOTES_CF;<(CommitIndex, CommitDigest, BlockRef), ()>,
-            Self::COMMIT_INFO_CF;<(CommitIndex, CommitDigest), CommitInfo>
+            Self::COMMIT_INFO_CF;<(CommitIndex, CommitDigest), CommitInfo>,
+            Self::EVENTS_CF;<u64, ConsensusEventRecord>,
+            Self::SUSPENDED_BLOCKS_CF;<(Round, AuthorityIndex, BlockDigest), Bytes>
         );
 
         Self {
@@ -93,7 +113,168 @@ impl RocksDBStore {
             commits,
             commit_votes,
             commit_info,
+            events,
+            suspended_blocks,
+        }
+    }
+
+    /// Verifies that blocks and commits match the digests they are stored by, that blocks are
+    /// indexed by author, and that commits form a chain of consecutive commits which only
+    /// reference blocks that are in the store.
+    pub(crate) fn verify_integrity(&self) -> StoreIntegrityReport {
+        let mut report = StoreIntegrityReport::default();
+        if let Err(e) = self
+            .verify_blocks(&mut report)
+            .and_then(|_| self.verify_commits(&mut report))
+        {
+            report.read_error = Some(e.to_string());
+        }
+        report
+    }
+
+    fn verify_blocks(&self, report: &mut StoreIntegrityReport) -> ConsensusResult<()> {
+        for result in self.blocks.safe_iter() {
+            let ((round, author, digest), serialized) = result?;
+            let block_ref = BlockRef::new(round, author, digest);
+            report.blocks_checked += 1;
+            let is_intact = bcs::from_bytes::<SignedBlock>(&serialized).is_ok_and(|signed_block| {
+                VerifiedBlock::new_verified(signed_block, serialized).reference() == block_ref
+            });
+            if !is_intact {
+                report.corrupted_blocks.push(block_ref);
+            } else if !self
+                .digests_by_authorities
+                .contains_key(&(author, round, digest))?
+            {
+                report.unindexed_blocks.push(block_ref);
+            }
+        }
+        for result in self.digests_by_authorities.safe_iter() {
+            let ((author, round, digest), _) = result?;
+            if !self.blocks.contains_key(&(round, author, digest))? {
+                report
+                    .dangling_index_entries
+                    .push(BlockRef::new(round, author, digest));
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_commits(&self, report: &mut StoreIntegrityReport) -> ConsensusResult<()> {
+        let corrupted_blocks: BTreeSet<_> = report.corrupted_blocks.iter().copied().collect();
+        let mut previous = (0, CommitDigest::MIN);
+        for result in self.commits.safe_iter() {
+            let ((index, digest), serialized) = result?;
+            report.commits_checked += 1;
+            report.last_commit = index;
+            if report.inconsistent_commit.is_some() {
+                continue;
+            }
+            if let Some(reason) =
+                self.commit_inconsistency(index, digest, serialized, previous, &corrupted_blocks)?
+            {
+                report.inconsistent_commit = Some(InconsistentCommit { index, reason });
+                continue;
+            }
+            previous = (index, digest);
+            // Recovery needs the commit info of the last commit, which is only written for the
+            // last commit of each batch.
+            if self.commit_info.contains_key(&(index, digest))? {
+                report.last_consistent_commit = index;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns why a commit is inconsistent, if it is, given the previous consistent commit.
+    fn commit_inconsistency(
+        &self,
+        index: CommitIndex,
+        digest: CommitDigest,
+        serialized: Bytes,
+        (previous_index, previous_digest): (CommitIndex, CommitDigest),
+        corrupted_blocks: &BTreeSet<BlockRef>,
+    ) -> ConsensusResult<Option<String>> {
+        let Ok(commit) = bcs::from_bytes::<Commit>(&serialized) else {
+            return Ok(Some("the commit cannot be deserialized".to_string()));
+        };
+        let commit = TrustedCommit::new_trusted(commit, serialized);
+        if commit.digest() != digest || commit.index() != index {
+            return Ok(Some(format!(
+                "the commit does not match the digest {digest} it is stored by"
+            )));
         }
+        if index != previous_index + 1 || commit.previous_digest() != previous_digest {
+            return Ok(Some(format!(
+                "the commit does not extend the previous consistent commit {previous_index}"
+            )));
+        }
+        let blocks = commit.blocks();
+        for (block_ref, exists) in blocks.iter().zip(self.contains_blocks(blocks)?) {
+            if !exists || corrupted_blocks.contains(block_ref) {
+                return Ok(Some(format!(
+                    "the committed block {block_ref} is missing or corrupted"
+                )));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Repairs the inconsistencies found by `verify_integrity`: removes corrupted blocks, fixes
+    /// the index of blocks by author, and truncates commits to the last consistent one.
+    pub(crate) fn repair(&self, report: &StoreIntegrityReport) -> ConsensusResult<()> {
+        let mut batch = self.blocks.batch();
+        batch
+            .delete_batch(
+                &self.blocks,
+                report
+                    .corrupted_blocks
+                    .iter()
+                    .map(|r| (r.round, r.author, r.digest)),
+            )
+            .map_err(ConsensusError::RocksDBFailure)?;
+        batch
+            .delete_batch(
+                &self.digests_by_authorities,
+                report
+                    .corrupted_blocks
+                    .iter()
+                    .chain(&report.dangling_index_entries)
+                    .map(|r| (r.author, r.round, r.digest)),
+            )
+            .map_err(ConsensusError::RocksDBFailure)?;
+        batch
+            .insert_batch(
+                &self.digests_by_authorities,
+                report
+                    .unindexed_blocks
+                    .iter()
+                    .map(|r| ((r.author, r.round, r.digest), ())),
+            )
+            .map_err(ConsensusError::RocksDBFailure)?;
+
+        let truncated = (
+            Included((report.last_consistent_commit + 1, CommitDigest::MIN)),
+            Included((CommitIndex::MAX, CommitDigest::MAX)),
+        );
+        let mut truncated_commits = vec![];
+        for result in self.commits.safe_range_iter(truncated) {
+            let (key, _) = result?;
+            truncated_commits.push(key);
+        }
+        let mut truncated_commit_info = vec![];
+        for result in self.commit_info.safe_range_iter(truncated) {
+            let (key, _) = result?;
+            truncated_commit_info.push(key);
+        }
+        batch
+            .delete_batch(&self.commits, truncated_commits)
+            .map_err(ConsensusError::RocksDBFailure)?;
+        batch
+            .delete_batch(&self.commit_info, truncated_commit_info)
+            .map_err(ConsensusError::RocksDBFailure)?;
+        batch.write()?;
+        Ok(())
     }
 }
 
@@ -145,6 +326,40 @@ impl Store for RocksDBStore {
                 )
                 .map_err(ConsensusError::RocksDBFailure)?;
         }
+        for event in write_batch.events {
+            batch
+                .insert_batch(&self.events, [(event.sequence, event)])
+                .map_err(ConsensusError::RocksDBFailure)?;
+        }
+        // A range delete, so that the cost of an eviction does not depend on the number of
+        // events evicted before it.
+        let evict_events = write_batch.evict_events;
+        if !evict_events.is_empty() {
+            batch
+                .schedule_delete_range(&self.events, &evict_events.start, &evict_events.end)
+                .map_err(ConsensusError::RocksDBFailure)?;
+        }
+        batch
+            .delete_batch(
+                &self.suspended_blocks,
+                write_batch
+                    .unsuspended_blocks
+                    .iter()
+                    .map(|r| (r.round, r.author, r.digest)),
+            )
+            .map_err(ConsensusError::RocksDBFailure)?;
+        batch
+            .insert_batch(
+                &self.suspended_blocks,
+                write_batch.suspended_blocks.iter().map(|block| {
+                    let block_ref = block.reference();
+                    (
+                        (block_ref.round, block_ref.author, block_ref.digest),
+                        block.serialized(),
+                    )
+                }),
+            )
+            .map_err(ConsensusError::RocksDBFailure)?;
         batch.write()?;
         Ok(())
     }
@@ -287,4 +502,41 @@ impl Store for RocksDBStore {
         let (_, commit_info) = result.map_err(ConsensusError::RocksDBFailure)?;
         Ok(Some(commit_info))
     }
+
+    fn scan_events(
+        &self,
+        start_sequence: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<ConsensusEventRecord>> {
+        let mut events = vec![];
+        for result in self
+            .events
+            .safe_range_iter((Included(start_sequence), Included(u64::MAX)))
+            .take(limit)
+        {
+            let (_, event) = result?;
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    fn read_last_event(&self) -> ConsensusResult<Option<ConsensusEventRecord>> {
+        let Some(result) = self.events.safe_iter().skip_to_last().next() else {
+            return Ok(None);
+        };
+        let (_, event) = result?;
+        Ok(Some(event))
+    }
+
+    fn read_suspended_blocks(&self) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let mut blocks = vec![];
+        for result in self.suspended_blocks.safe_iter() {
+            let (_, serialized) = result?;
+            let signed_block: SignedBlock =
+                bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
+            // Only verified blocks are suspended.
+            blocks.push(VerifiedBlock::new_verified(signed_block, serialized));
+        }
+        Ok(blocks)
+    }
 }
@@ -1,14 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use consensus_config::AuthorityIndex;
+use consensus_config::{AuthorityIndex, Parameters};
 use rstest::rstest;
 use tempfile::TempDir;
 
-use super::{mem_store::MemStore, rocksdb_store::RocksDBStore, Store, WriteBatch};
+use super::{
+    integrity::{verify_store, verify_store_on_startup},
+    mem_store::MemStore,
+    rocksdb_store::RocksDBStore,
+    Store, WriteBatch,
+};
 use crate::{
     block::{BlockAPI, BlockDigest, BlockRef, Slot, TestBlock, VerifiedBlock},
     commit::{CommitDigest, TrustedCommit},
+    context::Context,
+    event_log::{ConsensusEvent, ConsensusEventRecord},
 };
 
 /// Test fixture for store tests. Wraps around various store implementations.
@@ -218,24 +225,28 @@ async fn read_and_scan_commits(
         TrustedCommit::new_for_test(
             1,
             CommitDigest::MIN,
+            0,
             BlockRef::new(1, AuthorityIndex::new_for_test(0), BlockDigest::default()),
             vec![],
         ),
         TrustedCommit::new_for_test(
             2,
             CommitDigest::MIN,
+            0,
             BlockRef::new(2, AuthorityIndex::new_for_test(0), BlockDigest::default()),
             vec![],
         ),
         TrustedCommit::new_for_test(
             3,
             CommitDigest::MIN,
+            0,
             BlockRef::new(3, AuthorityIndex::new_for_test(0), BlockDigest::default()),
             vec![],
         ),
         TrustedCommit::new_for_test(
             4,
             CommitDigest::MIN,
+            0,
             BlockRef::new(4, AuthorityIndex::new_for_test(0), BlockDigest::default()),
             vec![],
         ),
@@ -293,3 +304,219 @@ async fn read_and_scan_commits(
         assert_eq!(scanned_commits, written_commits,);
     }
 }
+
+#[rstest]
+#[tokio::test]
+async fn append_and_evict_events(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    {
+        let last_event = store
+            .read_last_event()
+            .expect("Read last event should not fail");
+        assert!(last_event.is_none(), "{:?}", last_event);
+    }
+
+    let events: Vec<_> = (0..6)
+        .map(|sequence| ConsensusEventRecord {
+            sequence,
+            timestamp_ms: 1_000 + sequence,
+            event: ConsensusEvent::LeaderTimeout {
+                round: sequence as u32,
+            },
+        })
+        .collect();
+    store
+        .write(WriteBatch::default().events(events[..4].to_vec(), 0..0))
+        .unwrap();
+
+    {
+        let scanned_events = store
+            .scan_events(1, 2)
+            .expect("Scan events should not fail");
+        assert_eq!(scanned_events, events[1..3].to_vec());
+    }
+
+    // Writing more events evicts the ones in the given range of sequence numbers.
+    store
+        .write(WriteBatch::default().events(events[4..].to_vec(), 0..3))
+        .unwrap();
+
+    {
+        let scanned_events = store
+            .scan_events(0, 10)
+            .expect("Scan events should not fail");
+        assert_eq!(scanned_events, events[3..].to_vec());
+
+        let last_event = store
+            .read_last_event()
+            .expect("Read last event should not fail");
+        assert_eq!(last_event.as_ref(), events.last());
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn write_and_remove_suspended_blocks(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    let suspended_blocks: Vec<VerifiedBlock> = vec![
+        VerifiedBlock::new_for_test(TestBlock::new(2, 0).build()),
+        VerifiedBlock::new_for_test(TestBlock::new(2, 1).build()),
+        VerifiedBlock::new_for_test(TestBlock::new(3, 0).build()),
+    ];
+    store
+        .write(WriteBatch::default().suspended_blocks(suspended_blocks.clone(), vec![]))
+        .unwrap();
+
+    {
+        let read_blocks = store
+            .read_suspended_blocks()
+            .expect("Read suspended blocks should not fail");
+        assert_eq!(read_blocks, suspended_blocks);
+    }
+
+    // Blocks are removed before others are suspended, so a block can be suspended again.
+    let block = VerifiedBlock::new_for_test(TestBlock::new(4, 2).build());
+    store
+        .write(WriteBatch::default().suspended_blocks(
+            vec![block.clone(), suspended_blocks[2].clone()],
+            vec![
+                suspended_blocks[0].reference(),
+                suspended_blocks[2].reference(),
+            ],
+        ))
+        .unwrap();
+
+    {
+        let read_blocks = store
+            .read_suspended_blocks()
+            .expect("Read suspended blocks should not fail");
+        assert_eq!(
+            read_blocks,
+            vec![
+                suspended_blocks[1].clone(),
+                suspended_blocks[2].clone(),
+                block
+            ]
+        );
+    }
+}
+
+/// Writes blocks for rounds 1 to 3 and commits 1 to 4, of which commits 1 and 2 are consistent,
+/// commit 3 is written without commit info, and commit 4 references a block that is not in the
+/// store.
+fn write_inconsistent_commits(store: &RocksDBStore) -> (Vec<VerifiedBlock>, Vec<TrustedCommit>) {
+    let blocks: Vec<VerifiedBlock> = (1..=3)
+        .map(|round| VerifiedBlock::new_for_test(TestBlock::new(round, 0).build()))
+        .collect();
+    let missing_block = VerifiedBlock::new_for_test(TestBlock::new(4, 0).build());
+    let mut commits: Vec<TrustedCommit> = vec![];
+    for (i, block) in blocks.iter().chain([&missing_block]).enumerate() {
+        let previous_digest = commits
+            .last()
+            .map_or(CommitDigest::MIN, |commit| commit.digest());
+        commits.push(TrustedCommit::new_for_test(
+            i as u32 + 1,
+            previous_digest,
+            0,
+            block.reference(),
+            vec![block.reference()],
+        ));
+    }
+
+    store
+        .write(
+            WriteBatch::default()
+                .blocks(blocks.clone())
+                .commits(commits[..2].to_vec()),
+        )
+        .unwrap();
+    store
+        .write(WriteBatch::default().commits(commits[2..].to_vec()))
+        .unwrap();
+    (blocks, commits)
+}
+
+#[tokio::test]
+async fn verify_and_repair_rocksdb_store() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = RocksDBStore::new(temp_dir.path().to_str().unwrap());
+    let (blocks, commits) = write_inconsistent_commits(&store);
+
+    let report = store.verify_integrity();
+    assert!(!report.is_consistent(), "{report}");
+    assert_eq!(report.blocks_checked, 3);
+    assert_eq!(report.commits_checked, 4);
+    assert!(report.corrupted_blocks.is_empty());
+    assert_eq!(report.last_commit, 4);
+    assert_eq!(report.inconsistent_commit.as_ref().unwrap().index, 4);
+    assert_eq!(report.last_consistent_commit, 2);
+
+    store.repair(&report).unwrap();
+
+    let report = store.verify_integrity();
+    assert!(report.is_consistent(), "{report}");
+    assert_eq!(report.last_commit, 2);
+    assert_eq!(
+        store.read_last_commit().unwrap().as_ref(),
+        Some(&commits[1])
+    );
+    assert!(store.read_last_commit_info().unwrap().is_some());
+    // Blocks are kept, to commit them again.
+    assert_eq!(
+        store
+            .scan_blocks_by_author(AuthorityIndex::new_for_test(0), 0)
+            .unwrap(),
+        blocks
+    );
+}
+
+#[tokio::test]
+async fn repair_never_truncates_processed_commits() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+    let store = RocksDBStore::new(path);
+    let (_, commits) = write_inconsistent_commits(&store);
+    drop(store);
+
+    // Commit 3 was processed, so the store cannot be truncated to commit 2.
+    let error = verify_store(path, Some(3)).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("below the last processed commit 3"),
+        "{error}"
+    );
+    let report = verify_store(path, None).unwrap();
+    assert_eq!(report.last_commit, 4);
+    assert_eq!(report.last_consistent_commit, 2);
+
+    let report = verify_store(path, Some(2)).unwrap();
+    assert!(!report.is_consistent(), "{report}");
+    let store = RocksDBStore::new(path);
+    assert_eq!(
+        store.read_last_commit().unwrap().as_ref(),
+        Some(&commits[1])
+    );
+}
+
+#[tokio::test]
+#[should_panic(expected = "below the last processed commit 3")]
+async fn startup_repair_never_truncates_processed_commits() {
+    let temp_dir = TempDir::new().unwrap();
+    let (context, _) = Context::new_for_test(4);
+    let context = context.with_parameters(Parameters {
+        verify_store_on_startup: true,
+        repair_store_on_startup: true,
+        ..Default::default()
+    });
+    let store = RocksDBStore::new(temp_dir.path().to_str().unwrap());
+    write_inconsistent_commits(&store);
+
+    verify_store_on_startup(&context, &store, 3);
+}
@@ -8,6 +8,7 @@ use mysten_metrics::{monitored_future, monitored_scope};
 use parking_lot::Mutex;
 #[cfg(not(test))]
 use rand::{rngs::ThreadRng, seq::SliceRandom};
+use std::cmp::Reverse;
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::time::Duration;
@@ -36,6 +37,49 @@ const FETCH_FROM_PEERS_TIMEOUT: Duration = Duration::from_millis(4_000);
 
 const MAX_FETCH_BLOCKS_PER_REQUEST: usize = 200;
 
+/// The max number of peers to fetch missing blocks from in a single periodic run.
+const MAX_PEERS: usize = 3;
+
+/// The missing blocks that are being fetched, so that they are not requested again, from the same
+/// or another peer, until their request completes.
+#[derive(Clone, Default)]
+struct InflightBlocks(Arc<Mutex<BTreeSet<BlockRef>>>);
+
+impl InflightBlocks {
+    /// Marks the `block_refs` that are not already being fetched as in flight, until the returned
+    /// guard is dropped.
+    fn mark(&self, block_refs: impl IntoIterator<Item = BlockRef>) -> InflightBlocksGuard {
+        let mut inflight = self.0.lock();
+        let block_refs = block_refs
+            .into_iter()
+            .filter(|block_ref| inflight.insert(*block_ref))
+            .collect();
+        InflightBlocksGuard {
+            inflight: self.clone(),
+            block_refs,
+        }
+    }
+
+    fn contains(&self, block_ref: &BlockRef) -> bool {
+        self.0.lock().contains(block_ref)
+    }
+}
+
+/// The blocks requested by a fetch request, which stay in flight until it is dropped.
+struct InflightBlocksGuard {
+    inflight: InflightBlocks,
+    block_refs: BTreeSet<BlockRef>,
+}
+
+impl Drop for InflightBlocksGuard {
+    fn drop(&mut self) {
+        let mut inflight = self.inflight.0.lock();
+        for block_ref in &self.block_refs {
+            inflight.remove(block_ref);
+        }
+    }
+}
+
 enum Command {
     FetchBlocks {
         missing_block_refs: BTreeSet<BlockRef>,
@@ -78,11 +122,14 @@ impl SynchronizerHandle {
 pub(crate) struct Synchronizer<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> {
     context: Arc<Context>,
     commands_receiver: Receiver<Command>,
-    fetch_block_senders: BTreeMap<AuthorityIndex, Sender<BTreeSet<BlockRef>>>,
+    fetch_block_senders: BTreeMap<AuthorityIndex, Sender<InflightBlocksGuard>>,
     core_dispatcher: Arc<D>,
     fetch_blocks_scheduler_task: JoinSet<()>,
     network_client: Arc<C>,
     block_verifier: Arc<V>,
+    /// Whether the node is catching up, as of the last periodic run.
+    catching_up: bool,
+    inflight_blocks: InflightBlocks,
 }
 
 impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C, V, D> {
@@ -123,6 +170,8 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
                 fetch_blocks_scheduler_task: JoinSet::new(),
                 network_client,
                 block_verifier,
+                catching_up: false,
+                inflight_blocks: InflightBlocks::default(),
             };
             s.run().await;
         });
@@ -153,9 +202,18 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
                                 continue;
                             }
 
+                            // Blocks that are already being fetched are not requested again.
+                            let requested = missing_block_refs.len();
+                            let blocks_guard = self.inflight_blocks.mark(missing_block_refs);
+                            self.context.metrics.node_metrics.synchronizer_deduplicated_fetches.inc_by((requested - blocks_guard.block_refs.len()) as u64);
+                            if blocks_guard.block_refs.is_empty() {
+                                result.send(Ok(())).ok();
+                                continue;
+                            }
+
                             // We don't block if the corresponding peer task is saturated - but we rather drop the request. That's ok as the periodic
                             // synchronization task will handle any still missing blocks in next run.
-                            let r = self.fetch_block_senders.get(&peer_index).expect("Fatal error, sender should be present").try_send(missing_block_refs).map_err(|err| {
+                            let r = self.fetch_block_senders.get(&peer_index).expect("Fatal error, sender should be present").try_send(blocks_guard).map_err(|err| {
                                 match err {
                                     TrySendError::Full(_) => ConsensusError::SynchronizerSaturated(peer_index),
                                     TrySendError::Closed(_) => ConsensusError::Shutdown
@@ -201,7 +259,7 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
         block_verifier: Arc<V>,
         context: Arc<Context>,
         core_dispatcher: Arc<D>,
-        mut receiver: Receiver<BTreeSet<BlockRef>>,
+        mut receiver: Receiver<InflightBlocksGuard>,
     ) {
         const MAX_RETRIES: u32 = 5;
 
@@ -209,10 +267,13 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
 
         loop {
             tokio::select! {
-                Some(block_refs) = receiver.recv(), if requests.len() < FETCH_BLOCKS_CONCURRENCY => {
-                    requests.push(Self::fetch_blocks_request(network_client.clone(), peer_index, block_refs, FETCH_REQUEST_TIMEOUT, 1))
+                Some(blocks_guard) = receiver.recv(), if requests.len() < FETCH_BLOCKS_CONCURRENCY => {
+                    requests.push(Self::fetch_blocks_request(network_client.clone(), peer_index, blocks_guard, FETCH_REQUEST_TIMEOUT, 1))
                 },
-                Some((response, block_refs, retries, _peer)) = requests.next() => {
+                Some((response, blocks_guard, retries, _peer)) = requests.next() => {
+                    if let Ok(Err(e)) = &response {
+                        context.metrics.node_metrics.record_error(e, Some(peer_index));
+                    }
                     match response {
                         Ok(Ok(blocks)) => {
                             context
@@ -222,16 +283,17 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
 
                             if let Err(err) = Self::process_fetched_blocks(blocks,
                                 peer_index,
-                                block_refs,
+                                blocks_guard.block_refs.clone(),
                                 core_dispatcher.clone(),
                                 block_verifier.clone(),
                                 context.clone()).await {
+                                context.metrics.node_metrics.record_error(&err, Some(peer_index));
                                 warn!("Error while processing fetched blocks from peer {peer_index}: {err}");
                             }
                         },
                         Ok(Err(_)) | Err(Elapsed {..}) => {
                             if retries <= MAX_RETRIES {
-                                requests.push(Self::fetch_blocks_request(network_client.clone(), peer_index, block_refs, FETCH_REQUEST_TIMEOUT, retries))
+                                requests.push(Self::fetch_blocks_request(network_client.clone(), peer_index, blocks_guard, FETCH_REQUEST_TIMEOUT, retries))
                             } else {
                                 warn!("Max retries {retries} reached while trying to fetch blocks from peer {peer_index}.");
                             }
@@ -306,12 +368,12 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
     async fn fetch_blocks_request(
         network_client: Arc<C>,
         peer: AuthorityIndex,
-        block_refs: BTreeSet<BlockRef>,
+        blocks_guard: InflightBlocksGuard,
         request_timeout: Duration,
         mut retries: u32,
     ) -> (
         Result<ConsensusResult<Vec<Bytes>>, Elapsed>,
-        BTreeSet<BlockRef>,
+        InflightBlocksGuard,
         u32,
         AuthorityIndex,
     ) {
@@ -320,7 +382,7 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
             request_timeout,
             network_client.fetch_blocks(
                 peer,
-                block_refs.clone().into_iter().collect::<Vec<_>>(),
+                blocks_guard.block_refs.iter().cloned().collect::<Vec<_>>(),
                 request_timeout,
             ),
         )
@@ -332,7 +394,7 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
             sleep_until(start + request_timeout).await;
             retries += 1;
         }
-        (resp, block_refs, retries, peer)
+        (resp, blocks_guard, retries, peer)
     }
 
     async fn start_fetch_missing_blocks_task(&mut self) -> ConsensusResult<()> {
@@ -342,11 +404,41 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
             .await
             .map_err(|_err| ConsensusError::Shutdown)?;
 
+        self.update_catch_up_state(&missing_blocks);
+
         // No reason to kick off the scheduler if there are no missing blocks to fetch
         if missing_blocks.is_empty() {
             return Ok(());
         }
 
+        // While catching up, only a limited number of the lowest round blocks are fetched per run.
+        let catching_up = self.catching_up;
+        let max_blocks = if catching_up {
+            Self::catch_up_blocks(
+                &self.context,
+                self.context.parameters.synchronizer_catch_up_fetch_rounds,
+            )
+            .min(MAX_PEERS * MAX_FETCH_BLOCKS_PER_REQUEST)
+        } else {
+            MAX_PEERS * MAX_FETCH_BLOCKS_PER_REQUEST
+        };
+        let core_batch_size = Self::catch_up_blocks(
+            &self.context,
+            self.context
+                .parameters
+                .synchronizer_catch_up_core_batch_rounds,
+        );
+        let batches = Self::schedule_missing_blocks(
+            &self.context,
+            &self.inflight_blocks,
+            &missing_blocks,
+            max_blocks,
+            catching_up,
+        );
+        if batches.is_empty() {
+            return Ok(());
+        }
+
         let context = self.context.clone();
         let network_client = self.network_client.clone();
         let block_verifier = self.block_verifier.clone();
@@ -358,10 +450,9 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
 
                 context.metrics.node_metrics.fetch_blocks_scheduler_inflight.inc();
 
-                let total_requested = missing_blocks.len();
+                let total_requested: usize = batches.iter().map(|batch| batch.block_refs.len()).sum();
 
-                // Fetch blocks from peers
-                let results = Self::fetch_blocks_from_authorities(context.clone(), network_client, missing_blocks).await;
+                let results = Self::fetch_blocks_from_authorities(context.clone(), network_client, batches).await;
 
                 if results.is_empty() {
                     warn!("No results returned while requesting missing blocks");
@@ -370,12 +461,24 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
 
                 // Now process the returned results
                 let mut total_fetched = 0;
-                for (requested_block_refs, fetched_blocks, peer) in results {
+                let fetch_type = if catching_up { "catch_up" } else { "periodic" };
+                for (blocks_guard, fetched_blocks, peer) in results {
                     total_fetched += fetched_blocks.len();
-                    context.metrics.node_metrics.fetched_blocks.with_label_values(&[&peer.to_string(), "periodic"]).inc_by(fetched_blocks.len() as u64);
-
-                    if let Err(err) = Self::process_fetched_blocks(fetched_blocks, peer, requested_block_refs, core_dispatcher.clone(), block_verifier.clone(), context.clone()).await {
-                        warn!("Error occurred while processing fetched blocks from peer {peer}: {err}");
+                    context.metrics.node_metrics.fetched_blocks.with_label_values(&[&peer.to_string(), fetch_type]).inc_by(fetched_blocks.len() as u64);
+
+                    // While catching up, send the fetched blocks to core in smaller batches so their processing is
+                    // interleaved with the processing of live blocks, instead of stalling core.
+                    let batches = if catching_up {
+                        fetched_blocks.chunks(core_batch_size).map(|batch| batch.to_vec()).collect()
+                    } else {
+                        vec![fetched_blocks]
+                    };
+                    for batch in batches {
+                        if let Err(err) = Self::process_fetched_blocks(batch, peer, blocks_guard.block_refs.clone(), core_dispatcher.clone(), block_verifier.clone(), context.clone()).await {
+                            context.metrics.node_metrics.record_error(&err, Some(peer));
+                            warn!("Error occurred while processing fetched blocks from peer {peer}: {err}");
+                            break;
+                        }
                     }
                 }
 
@@ -386,22 +489,98 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
         Ok(())
     }
 
-    /// Fetches the `missing_blocks` from available peers. The method will attempt to split the load amongst multiple (random) peers.
-    /// The method returns a vector with the fetched blocks from each peer that successfully responded. Each element of the vector
-    /// is a tuple which contains the requested missing block refs, the returned blocks and the peer authority index.
-    async fn fetch_blocks_from_authorities(
-        context: Arc<Context>,
-        network_client: Arc<C>,
-        missing_blocks: BTreeSet<BlockRef>,
-    ) -> Vec<(BTreeSet<BlockRef>, Vec<Bytes>, AuthorityIndex)> {
-        const MAX_PEERS: usize = 3;
+    /// Updates whether the node is catching up, based on the number of `missing_blocks`, and reports
+    /// the catch-up progress.
+    fn update_catch_up_state(&mut self, missing_blocks: &BTreeMap<BlockRef, usize>) {
+        let threshold = Self::catch_up_blocks(
+            &self.context,
+            self.context
+                .parameters
+                .synchronizer_catch_up_threshold_rounds,
+        );
+        let catching_up = missing_blocks.len() > threshold;
+        if catching_up != self.catching_up {
+            if catching_up {
+                info!(
+                    "{} blocks are missing, pacing the synchronizer while catching up",
+                    missing_blocks.len()
+                );
+            } else {
+                info!(
+                    "Synchronizer caught up, {} blocks are missing",
+                    missing_blocks.len()
+                );
+            }
+            self.catching_up = catching_up;
+        }
+
+        let node_metrics = &self.context.metrics.node_metrics;
+        node_metrics
+            .synchronizer_catching_up
+            .set(catching_up as i64);
+        if let Some((lowest, _)) = missing_blocks.first_key_value() {
+            node_metrics
+                .synchronizer_lowest_missing_round
+                .set(lowest.round as i64);
+        }
+    }
 
-        // Attempt to fetch only up to a max of blocks
-        let missing_blocks = missing_blocks
+    /// The number of blocks in `rounds` rounds of the committee, and at least one.
+    fn catch_up_blocks(context: &Context, rounds: u32) -> usize {
+        (rounds as usize * context.committee.size()).max(1)
+    }
+
+    /// Selects up to `max_blocks` of the `missing_blocks` that are not already being fetched, and
+    /// batches them into requests of up to `MAX_FETCH_BLOCKS_PER_REQUEST` blocks, which are marked as
+    /// in flight until they are dropped.
+    ///
+    /// The blocks that the most suspended blocks are waiting for are selected first, as fetching them
+    /// unblocks the most of the DAG, and then the blocks of the lowest rounds. While catching up, the
+    /// blocks of the lowest rounds are selected first instead, as they are the ones that can be
+    /// accepted first. Each batch covers consecutive rounds of as few authorities as possible, which
+    /// peers can read from their stores as ranges.
+    fn schedule_missing_blocks(
+        context: &Context,
+        inflight_blocks: &InflightBlocks,
+        missing_blocks: &BTreeMap<BlockRef, usize>,
+        max_blocks: usize,
+        catching_up: bool,
+    ) -> Vec<InflightBlocksGuard> {
+        let mut candidates = missing_blocks
+            .iter()
+            .filter(|(block_ref, _)| !inflight_blocks.contains(block_ref))
+            .collect::<Vec<_>>();
+        context
+            .metrics
+            .node_metrics
+            .synchronizer_deduplicated_fetches
+            .inc_by((missing_blocks.len() - candidates.len()) as u64);
+
+        // The sort is stable, so blocks waited for by as many suspended blocks stay ordered by round.
+        if !catching_up {
+            candidates.sort_by_key(|(_, dependents)| Reverse(**dependents));
+        }
+        let mut selected = candidates
             .into_iter()
-            .take(MAX_PEERS * MAX_FETCH_BLOCKS_PER_REQUEST)
+            .take(max_blocks)
+            .map(|(block_ref, _)| *block_ref)
             .collect::<Vec<_>>();
 
+        selected.sort_by_key(|block_ref| (block_ref.author, block_ref.round));
+        selected
+            .chunks(MAX_FETCH_BLOCKS_PER_REQUEST)
+            .map(|batch| inflight_blocks.mark(batch.iter().cloned()))
+            .collect()
+    }
+
+    /// Fetches the `batches` of missing blocks from available peers. The method will attempt to split the load amongst multiple (random) peers.
+    /// The method returns a vector with the fetched blocks from each peer that successfully responded. Each element of the vector
+    /// is a tuple which contains the requested missing blocks, the returned blocks and the peer authority index.
+    async fn fetch_blocks_from_authorities(
+        context: Arc<Context>,
+        network_client: Arc<C>,
+        batches: Vec<InflightBlocksGuard>,
+    ) -> Vec<(InflightBlocksGuard, Vec<Bytes>, AuthorityIndex)> {
         #[allow(unused_mut)]
         let mut peers = context
             .committee
@@ -422,16 +601,15 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
         let mut request_futures = FuturesUnordered::new();
 
         // Send the initial requests
-        for blocks in missing_blocks.chunks(MAX_FETCH_BLOCKS_PER_REQUEST) {
+        for blocks_guard in batches {
             let peer = peers
                 .next()
                 .expect("Possible misconfiguration as a peer should be found");
-            let block_refs = blocks.iter().cloned().collect::<BTreeSet<_>>();
 
             request_futures.push(Self::fetch_blocks_request(
                 network_client.clone(),
                 peer,
-                block_refs,
+                blocks_guard,
                 FETCH_REQUEST_TIMEOUT,
                 1,
             ));
@@ -444,10 +622,13 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
 
         loop {
             tokio::select! {
-                Some((response, requested_block_refs, _retries, peer_index)) = request_futures.next() =>
+                Some((response, blocks_guard, _retries, peer_index)) = request_futures.next() => {
+                    if let Ok(Err(e)) = &response {
+                        context.metrics.node_metrics.record_error(e, Some(peer_index));
+                    }
                     match response {
                         Ok(Ok(fetched_blocks)) => {
-                            results.push((requested_block_refs, fetched_blocks, peer_index));
+                            results.push((blocks_guard, fetched_blocks, peer_index));
 
                             // no more pending requests are left, just break the loop
                             if request_futures.is_empty() {
@@ -460,7 +641,7 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
                                 request_futures.push(Self::fetch_blocks_request(
                                     network_client.clone(),
                                     next_peer,
-                                    requested_block_refs,
+                                    blocks_guard,
                                     FETCH_REQUEST_TIMEOUT,
                                     1,
                                 ));
@@ -468,7 +649,8 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
                                 debug!("No more peers left to fetch blocks");
                             }
                         }
-                    },
+                    }
+                },
                 _ = &mut fetcher_timeout => {
                     debug!("Timed out while fetching all the blocks");
                     break;
@@ -482,16 +664,20 @@ impl<C: NetworkClient, V: BlockVerifier, D: CoreThreadDispatcher> Synchronizer<C
 
 #[cfg(test)]
 mod tests {
-    use crate::block::{BlockRef, Round, TestBlock, VerifiedBlock};
+    use crate::block::{BlockDigest, BlockRef, Round, TestBlock, VerifiedBlock};
+    use crate::block_manager::BlockRejection;
     use crate::block_verifier::NoopBlockVerifier;
     use crate::context::Context;
     use crate::core_thread::{CoreError, CoreThreadDispatcher};
     use crate::error::{ConsensusError, ConsensusResult};
     use crate::network::NetworkClient;
-    use crate::synchronizer::{Synchronizer, FETCH_BLOCKS_CONCURRENCY, FETCH_REQUEST_TIMEOUT};
+    use crate::synchronizer::{
+        InflightBlocks, Synchronizer, FETCH_BLOCKS_CONCURRENCY, FETCH_REQUEST_TIMEOUT,
+        MAX_FETCH_BLOCKS_PER_REQUEST,
+    };
     use async_trait::async_trait;
     use bytes::Bytes;
-    use consensus_config::AuthorityIndex;
+    use consensus_config::{AuthorityIndex, Parameters};
     use std::collections::{BTreeMap, BTreeSet};
     use std::sync::Arc;
     use std::time::Duration;
@@ -501,7 +687,7 @@ mod tests {
     #[derive(Default)]
     struct MockCoreThreadDispatcher {
         add_blocks: tokio::sync::Mutex<Vec<VerifiedBlock>>,
-        missing_blocks: tokio::sync::Mutex<BTreeSet<BlockRef>>,
+        missing_blocks: tokio::sync::Mutex<BTreeMap<BlockRef, usize>>,
     }
 
     impl MockCoreThreadDispatcher {
@@ -512,7 +698,7 @@ mod tests {
 
         async fn stub_missing_blocks(&self, block_refs: BTreeSet<BlockRef>) {
             let mut lock = self.missing_blocks.lock().await;
-            lock.extend(block_refs);
+            lock.extend(block_refs.into_iter().map(|block_ref| (block_ref, 1)));
         }
     }
 
@@ -531,12 +717,16 @@ mod tests {
             todo!()
         }
 
-        async fn get_missing_blocks(&self) -> Result<BTreeSet<BlockRef>, CoreError> {
+        async fn get_missing_blocks(&self) -> Result<BTreeMap<BlockRef, usize>, CoreError> {
             let mut lock = self.missing_blocks.lock().await;
             let result = lock.clone();
             lock.clear();
             Ok(result)
         }
+
+        async fn set_propagation_delay(&self, _delay: Round) -> Result<(), CoreError> {
+            todo!()
+        }
     }
 
     type FetchRequestKey = (Vec<BlockRef>, AuthorityIndex);
@@ -599,6 +789,23 @@ mod tests {
 
             Ok(serialised)
         }
+
+        async fn get_latest_rounds(
+            &self,
+            _peer: AuthorityIndex,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<Round>> {
+            todo!()
+        }
+
+        async fn report_rejected_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _rejections: &[BlockRejection],
+            _timeout: Duration,
+        ) -> ConsensusResult<()> {
+            todo!()
+        }
     }
 
     #[tokio::test]
@@ -758,4 +965,234 @@ mod tests {
             .unwrap()
             .is_empty());
     }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn synchronizer_paces_fetches_while_catching_up() {
+        // GIVEN a committee of 4, which catches up beyond 40 missing blocks, fetching 20 blocks per run
+        let (context, _) = Context::new_for_test(4);
+        let parameters = Parameters {
+            synchronizer_catch_up_threshold_rounds: 10,
+            synchronizer_catch_up_fetch_rounds: 5,
+            synchronizer_catch_up_core_batch_rounds: 2,
+            ..context.parameters.clone()
+        };
+        let context = Arc::new(context.with_parameters(parameters));
+        let block_verifier = Arc::new(NoopBlockVerifier {});
+        let core_dispatcher = Arc::new(MockCoreThreadDispatcher::default());
+        let network_client = Arc::new(MockNetworkClient::default());
+
+        // Create more missing blocks than the catch up threshold
+        let missing_blocks = (0..=40)
+            .map(|round| VerifiedBlock::new_for_test(TestBlock::new(round as Round, 0).build()))
+            .collect::<Vec<_>>();
+        core_dispatcher
+            .stub_missing_blocks(missing_blocks.iter().map(|b| b.reference()).collect())
+            .await;
+
+        // AND stub a single request for the lowest round blocks. Any other request would fail the mock.
+        let expected_blocks = missing_blocks[..20].to_vec();
+        let peer = AuthorityIndex::new_for_test(1);
+        network_client
+            .stub_fetch_blocks(expected_blocks.clone(), peer, None)
+            .await;
+
+        // WHEN start the synchronizer and wait for the periodic task to run
+        let handle = Synchronizer::start(
+            network_client.clone(),
+            context.clone(),
+            core_dispatcher.clone(),
+            block_verifier,
+        );
+
+        // The first periodic run starts after 500ms.
+        sleep(Duration::from_millis(600)).await;
+
+        // THEN only the lowest round blocks should have been fetched and added to core
+        let added_blocks = core_dispatcher.get_add_blocks().await;
+        assert_eq!(added_blocks, expected_blocks);
+        assert_eq!(
+            context
+                .metrics
+                .node_metrics
+                .fetched_blocks
+                .with_label_values(&[&peer.to_string(), "catch_up"])
+                .get(),
+            20
+        );
+        assert_eq!(
+            context.metrics.node_metrics.synchronizer_catching_up.get(),
+            1
+        );
+
+        // AND the missing ancestors of live blocks should still be fetched while catching up
+        let live_block = VerifiedBlock::new_for_test(TestBlock::new(1_000, 1).build());
+        network_client
+            .stub_fetch_blocks(vec![live_block.clone()], peer, None)
+            .await;
+        assert!(handle
+            .fetch_blocks(BTreeSet::from([live_block.reference()]), peer)
+            .await
+            .is_ok());
+        sleep(Duration::from_millis(100)).await;
+        let added_blocks = core_dispatcher.get_add_blocks().await;
+        assert_eq!(added_blocks.last(), Some(&live_block));
+
+        // AND the node should have caught up on the next run, as no more blocks are missing
+        sleep(Duration::from_millis(500)).await;
+        assert_eq!(
+            context.metrics.node_metrics.synchronizer_catching_up.get(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn deduplicate_inflight_fetch_blocks() {
+        // GIVEN
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let block_verifier = Arc::new(NoopBlockVerifier {});
+        let core_dispatcher = Arc::new(MockCoreThreadDispatcher::default());
+        let network_client = Arc::new(MockNetworkClient::default());
+
+        let handle = Synchronizer::start(
+            network_client.clone(),
+            context.clone(),
+            core_dispatcher.clone(),
+            block_verifier,
+        );
+
+        // AND stub a single slow fetch_blocks request from peer 1. Any other request would fail the mock.
+        let expected_blocks = (0..10)
+            .map(|round| VerifiedBlock::new_for_test(TestBlock::new(round, 0).build()))
+            .collect::<Vec<_>>();
+        let missing_blocks = expected_blocks
+            .iter()
+            .map(|block| block.reference())
+            .collect::<BTreeSet<_>>();
+        let peer = AuthorityIndex::new_for_test(1);
+        network_client
+            .stub_fetch_blocks(
+                expected_blocks.clone(),
+                peer,
+                Some(Duration::from_millis(500)),
+            )
+            .await;
+
+        // WHEN requesting the same missing blocks twice, from two peers
+        assert!(handle
+            .fetch_blocks(missing_blocks.clone(), peer)
+            .await
+            .is_ok());
+        assert!(handle
+            .fetch_blocks(missing_blocks.clone(), AuthorityIndex::new_for_test(2))
+            .await
+            .is_ok());
+
+        // THEN the second request is skipped, as the blocks are already being fetched
+        assert_eq!(
+            context
+                .metrics
+                .node_metrics
+                .synchronizer_deduplicated_fetches
+                .get(),
+            missing_blocks.len() as u64
+        );
+
+        // AND the blocks are added to core once
+        sleep(Duration::from_millis(1_000)).await;
+        let added_blocks = core_dispatcher.get_add_blocks().await;
+        assert_eq!(added_blocks, expected_blocks);
+    }
+
+    #[test]
+    fn schedule_missing_blocks_by_priority() {
+        type TestSynchronizer =
+            Synchronizer<MockNetworkClient, NoopBlockVerifier, MockCoreThreadDispatcher>;
+        let (context, _) = Context::new_for_test(4);
+        let inflight_blocks = InflightBlocks::default();
+        let block_ref = |round, author| {
+            BlockRef::new(
+                round,
+                AuthorityIndex::new_for_test(author),
+                BlockDigest::MIN,
+            )
+        };
+
+        // The block of round 5 is waited for by the most suspended blocks.
+        let missing_blocks = BTreeMap::from([
+            (block_ref(1, 1), 1),
+            (block_ref(1, 2), 1),
+            (block_ref(5, 1), 3),
+        ]);
+
+        // WHEN not catching up, the blocks waited for by the most suspended blocks are selected first
+        let batches = TestSynchronizer::schedule_missing_blocks(
+            &context,
+            &inflight_blocks,
+            &missing_blocks,
+            2,
+            false,
+        );
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0].block_refs,
+            BTreeSet::from([block_ref(1, 1), block_ref(5, 1)])
+        );
+
+        // AND the blocks in flight are not scheduled again until their request completes
+        let remaining = TestSynchronizer::schedule_missing_blocks(
+            &context,
+            &inflight_blocks,
+            &missing_blocks,
+            2,
+            false,
+        );
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].block_refs, BTreeSet::from([block_ref(1, 2)]));
+        assert_eq!(
+            context
+                .metrics
+                .node_metrics
+                .synchronizer_deduplicated_fetches
+                .get(),
+            2
+        );
+        drop(batches);
+        drop(remaining);
+
+        // WHEN catching up, the blocks of the lowest rounds are selected first
+        let batches = TestSynchronizer::schedule_missing_blocks(
+            &context,
+            &inflight_blocks,
+            &missing_blocks,
+            2,
+            true,
+        );
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0].block_refs,
+            BTreeSet::from([block_ref(1, 1), block_ref(1, 2)])
+        );
+        drop(batches);
+
+        // WHEN more blocks are missing than fit in a request, the batches are grouped by authority
+        let missing_blocks = (1..=MAX_FETCH_BLOCKS_PER_REQUEST as Round)
+            .flat_map(|round| [(block_ref(round, 1), 0), (block_ref(round, 2), 0)])
+            .collect::<BTreeMap<_, _>>();
+        let batches = TestSynchronizer::schedule_missing_blocks(
+            &context,
+            &inflight_blocks,
+            &missing_blocks,
+            2 * MAX_FETCH_BLOCKS_PER_REQUEST,
+            false,
+        );
+        assert_eq!(batches.len(), 2);
+        for (batch, author) in batches.iter().zip([1, 2]) {
+            assert_eq!(batch.block_refs.len(), MAX_FETCH_BLOCKS_PER_REQUEST);
+            assert!(batch
+                .block_refs
+                .iter()
+                .all(|block_ref| block_ref.author == AuthorityIndex::new_for_test(author)));
+        }
+    }
 }
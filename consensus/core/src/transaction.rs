@@ -14,7 +14,8 @@ use tracing::error;
 use crate::block::Transaction;
 use crate::context::Context;
 
-/// The maximum number of transactions pending to the queue to be pulled for block proposal
+/// The maximum number of transactions pending to the queue of each lane to be pulled for block
+/// proposal
 const MAX_PENDING_TRANSACTIONS: usize = 2_000;
 
 const MAX_CONSUMED_TRANSACTIONS_PER_REQUEST: u64 = 5_000;
@@ -34,11 +35,28 @@ impl TransactionGuard {
     }
 }
 
+/// The lane a transaction is submitted to. Each lane has its own queue, and the transactions of the
+/// system lane are pulled for block proposals before those of the user lane, so that system
+/// transactions keep getting sequenced when user transactions saturate consensus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionPriority {
+    /// Transactions that validators need sequenced to make progress, e.g. checkpoint signatures.
+    System,
+    /// Transactions submitted on behalf of users.
+    User,
+}
+
+/// The receiving ends of the lanes that the TransactionClient submits transactions to.
+pub(crate) struct TransactionReceiver {
+    system: metered_channel::Receiver<TransactionGuard>,
+    user: metered_channel::Receiver<TransactionGuard>,
+}
+
 /// The TransactionConsumer is responsible for fetching the next transactions to be included for the block proposals.
-/// The transactions are submitted to a channel which is shared between the TransactionConsumer and the TransactionClient
-/// and are pulled every time the `next` method is called.
+/// The transactions are submitted to channels (one per lane) which are shared between the TransactionConsumer and the
+/// TransactionClient and are pulled every time the `next` method is called.
 pub(crate) struct TransactionConsumer {
-    tx_receiver: metered_channel::Receiver<TransactionGuard>,
+    tx_receiver: TransactionReceiver,
     max_consumed_bytes_per_request: u64,
     max_consumed_transactions_per_request: u64,
     pending_transaction: Option<TransactionGuard>,
@@ -46,7 +64,7 @@ pub(crate) struct TransactionConsumer {
 
 impl TransactionConsumer {
     pub(crate) fn new(
-        tx_receiver: metered_channel::Receiver<TransactionGuard>,
+        tx_receiver: TransactionReceiver,
         context: Arc<Context>,
         max_consumed_transactions_per_request: Option<u64>,
     ) -> Self {
@@ -61,8 +79,9 @@ impl TransactionConsumer {
         }
     }
 
-    // Attempts to fetch the next transactions that have been submitted for sequence. Also a `max_consumed_bytes_per_request` parameter
-    // is given in order to ensure up to `max_consumed_bytes_per_request` bytes of transactions are retrieved.
+    // Attempts to fetch the next transactions that have been submitted for sequence, draining the system lane before the user lane.
+    // Also a `max_consumed_bytes_per_request` parameter is given in order to ensure up to `max_consumed_bytes_per_request` bytes
+    // of transactions are retrieved.
     pub(crate) fn next(&mut self) -> Vec<TransactionGuard> {
         let mut transactions = Vec::new();
         let mut total_size: usize = 0;
@@ -73,19 +92,22 @@ impl TransactionConsumer {
             transactions.push(t);
         }
 
-        while let Ok(t) = self.tx_receiver.try_recv() {
-            total_size += t.transaction.data().len();
+        let TransactionReceiver { system, user } = &mut self.tx_receiver;
+        'lanes: for receiver in [system, user] {
+            while let Ok(t) = receiver.try_recv() {
+                total_size += t.transaction.data().len();
 
-            // If we went over the max size with this transaction, just cache it for the next pull.
-            if total_size as u64 > self.max_consumed_bytes_per_request {
-                self.pending_transaction = Some(t);
-                break;
-            }
+                // If we went over the max size with this transaction, just cache it for the next pull.
+                if total_size as u64 > self.max_consumed_bytes_per_request {
+                    self.pending_transaction = Some(t);
+                    break 'lanes;
+                }
 
-            transactions.push(t);
+                transactions.push(t);
 
-            if transactions.len() as u64 >= self.max_consumed_transactions_per_request {
-                break;
+                if transactions.len() as u64 >= self.max_consumed_transactions_per_request {
+                    break 'lanes;
+                }
             }
         }
         transactions
@@ -94,7 +116,8 @@ impl TransactionConsumer {
 
 #[derive(Clone)]
 pub struct TransactionClient {
-    sender: metered_channel::Sender<TransactionGuard>,
+    system_sender: metered_channel::Sender<TransactionGuard>,
+    user_sender: metered_channel::Sender<TransactionGuard>,
     max_transaction_size: u64,
 }
 
@@ -108,10 +131,19 @@ pub enum ClientError {
 }
 
 impl TransactionClient {
-    pub(crate) fn new(
-        context: Arc<Context>,
-    ) -> (Self, metered_channel::Receiver<TransactionGuard>) {
-        let (sender, receiver) = channel_with_total(
+    pub(crate) fn new(context: Arc<Context>) -> (Self, TransactionReceiver) {
+        let (system_sender, system_receiver) = channel_with_total(
+            MAX_PENDING_TRANSACTIONS,
+            &context
+                .metrics
+                .channel_metrics
+                .tx_system_transactions_submit,
+            &context
+                .metrics
+                .channel_metrics
+                .tx_system_transactions_submit_total,
+        );
+        let (user_sender, user_receiver) = channel_with_total(
             MAX_PENDING_TRANSACTIONS,
             &context.metrics.channel_metrics.tx_transactions_submit,
             &context.metrics.channel_metrics.tx_transactions_submit_total,
@@ -119,19 +151,34 @@ impl TransactionClient {
 
         (
             Self {
-                sender,
+                system_sender,
+                user_sender,
                 max_transaction_size: context
                     .protocol_config
                     .consensus_max_transaction_size_bytes(),
             },
-            receiver,
+            TransactionReceiver {
+                system: system_receiver,
+                user: user_receiver,
+            },
         )
     }
 
-    /// Submits a transaction to be sequenced. The method returns when the transaction has been successfully
+    /// Submits a user transaction to be sequenced. The method returns when the transaction has been successfully
     /// included to the next proposed block.
     pub async fn submit(&self, transaction: Vec<u8>) -> Result<(), ClientError> {
-        let included_in_block = self.submit_no_wait(transaction).await?;
+        self.submit_with_priority(transaction, TransactionPriority::User)
+            .await
+    }
+
+    /// Submits a transaction to be sequenced through the lane of `priority`. The method returns when the transaction
+    /// has been successfully included to the next proposed block.
+    pub async fn submit_with_priority(
+        &self,
+        transaction: Vec<u8>,
+        priority: TransactionPriority,
+    ) -> Result<(), ClientError> {
+        let included_in_block = self.submit_no_wait(transaction, priority).await?;
         included_in_block
             .await
             .tap_err(|e| error!("Transaction acknowledge failed with {:?}", e))
@@ -146,6 +193,7 @@ impl TransactionClient {
     pub(crate) async fn submit_no_wait(
         &self,
         transaction: Vec<u8>,
+        priority: TransactionPriority,
     ) -> Result<oneshot::Receiver<()>, ClientError> {
         let (included_in_block_ack_send, included_in_block_ack_receive) = oneshot::channel();
         if transaction.len() as u64 > self.max_transaction_size {
@@ -159,13 +207,32 @@ impl TransactionClient {
             transaction: Transaction::new(transaction),
             included_in_block_ack: included_in_block_ack_send,
         };
-        self.sender
+        self.sender(priority)
             .send(t)
             .await
             .tap_err(|e| error!("Submit transaction failed with {:?}", e))
             .map_err(|e| ClientError::ConsensusShuttingDown(e.to_string()))?;
         Ok(included_in_block_ack_receive)
     }
+
+    /// Returns the number of transactions queued in the lane of `priority` and not yet pulled for a block proposal.
+    /// Once it reaches `max_queue_depth`, submissions to the lane wait for queued transactions to be pulled, so callers
+    /// can use it to shed or defer load before that happens.
+    pub fn queue_depth(&self, priority: TransactionPriority) -> usize {
+        MAX_PENDING_TRANSACTIONS.saturating_sub(self.sender(priority).capacity())
+    }
+
+    /// Returns the number of transactions each lane can queue before submissions to it wait.
+    pub fn max_queue_depth(&self) -> usize {
+        MAX_PENDING_TRANSACTIONS
+    }
+
+    fn sender(&self, priority: TransactionPriority) -> &metered_channel::Sender<TransactionGuard> {
+        match priority {
+            TransactionPriority::System => &self.system_sender,
+            TransactionPriority::User => &self.user_sender,
+        }
+    }
 }
 
 /// `TransactionVerifier` implementation is supplied by Sui to validate transactions in a block,
@@ -201,7 +268,9 @@ impl TransactionVerifier for NoopTransactionVerifier {
 #[cfg(test)]
 mod tests {
     use crate::context::Context;
-    use crate::transaction::{TransactionClient, TransactionConsumer, TransactionGuard};
+    use crate::transaction::{
+        TransactionClient, TransactionConsumer, TransactionGuard, TransactionPriority,
+    };
     use futures::stream::FuturesUnordered;
     use futures::StreamExt;
     use std::sync::Arc;
@@ -227,7 +296,7 @@ mod tests {
             let transaction =
                 bcs::to_bytes(&format!("transaction {i}")).expect("Serialization should not fail.");
             let w = client
-                .submit_no_wait(transaction)
+                .submit_no_wait(transaction, TransactionPriority::User)
                 .await
                 .expect("Shouldn't submit successfully transaction");
             included_in_block_waiters.push(w);
@@ -280,7 +349,7 @@ mod tests {
             let transaction =
                 bcs::to_bytes(&format!("transaction {i}")).expect("Serialization should not fail.");
             let _w = client
-                .submit_no_wait(transaction)
+                .submit_no_wait(transaction, TransactionPriority::User)
                 .await
                 .expect("Shouldn't submit successfully transaction");
         }
@@ -336,4 +405,44 @@ mod tests {
             assert_eq!(format!("transaction {i}").to_string(), t);
         }
     }
+
+    #[tokio::test]
+    async fn system_lane_is_consumed_first() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let (client, tx_receiver) = TransactionClient::new(context.clone());
+        let mut consumer = TransactionConsumer::new(tx_receiver, context.clone(), Some(3));
+
+        // submit user transactions first, then system transactions
+        for (i, priority) in [
+            TransactionPriority::User,
+            TransactionPriority::User,
+            TransactionPriority::System,
+            TransactionPriority::System,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let transaction =
+                bcs::to_bytes(&format!("transaction {i}")).expect("Serialization should not fail.");
+            let _w = client
+                .submit_no_wait(transaction, priority)
+                .await
+                .expect("Shouldn't submit successfully transaction");
+        }
+        assert_eq!(client.queue_depth(TransactionPriority::System), 2);
+        assert_eq!(client.queue_depth(TransactionPriority::User), 2);
+
+        // the system transactions are pulled before the user transactions submitted earlier
+        let transactions = consumer
+            .next()
+            .into_iter()
+            .map(|t| bcs::from_bytes::<String>(t.transaction.data()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transactions,
+            vec!["transaction 2", "transaction 3", "transaction 0"]
+        );
+        assert_eq!(client.queue_depth(TransactionPriority::System), 0);
+        assert_eq!(client.queue_depth(TransactionPriority::User), 1);
+    }
 }
@@ -51,6 +51,10 @@ pub trait ConsensusManagerTrait {
     async fn is_running(&self) -> bool;
 
     fn get_storage_base_path(&self) -> PathBuf;
+
+    /// Returns why consensus cannot make progress, if it is stalled, or `None` if it is making
+    /// progress or does not detect stalls.
+    fn stall_reason(&self) -> Option<String>;
 }
 
 impl ConsensusManager {
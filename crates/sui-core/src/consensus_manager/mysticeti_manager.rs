@@ -4,8 +4,8 @@ use std::{path::PathBuf, sync::Arc};
 
 use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
-use consensus_config::{Committee, NetworkKeyPair, Parameters, ProtocolKeyPair};
-use consensus_core::{CommitConsumer, CommitIndex, ConsensusAuthority, NetworkType, Round};
+use consensus_config::{Committee, NetworkKeyPair, NetworkType, Parameters, ProtocolKeyPair};
+use consensus_core::{CommitConsumer, CommitIndex, ConsensusAuthority, Round};
 use fastcrypto::ed25519;
 use mysten_metrics::{RegistryID, RegistryService};
 use narwhal_executor::ExecutionState;
@@ -90,13 +90,11 @@ impl ConsensusManagerTrait for MysticetiManager {
         let epoch = epoch_store.epoch();
         let protocol_config = epoch_store.protocol_config();
         let network_type = match std::env::var("CONSENSUS_NETWORK") {
-            Ok(type_str) => {
-                if type_str.to_lowercase() == "tonic" {
-                    NetworkType::Tonic
-                } else {
-                    NetworkType::Anemo
-                }
-            }
+            Ok(type_str) => match type_str.to_lowercase().as_str() {
+                "tonic" => NetworkType::Tonic,
+                "quic" => NetworkType::Quic,
+                _ => NetworkType::Anemo,
+            },
             Err(_) => NetworkType::Anemo,
         };
 
@@ -114,6 +112,7 @@ impl ConsensusManagerTrait for MysticetiManager {
         // TODO(mysticeti): Fill in the other fields
         let parameters = Parameters {
             db_path: Some(self.get_store_path(epoch)),
+            network_type,
             ..Default::default()
         };
 
@@ -141,7 +140,6 @@ impl ConsensusManagerTrait for MysticetiManager {
         // TODO(mysticeti): Investigate if we need to return potential errors from
         // AuthorityNode and add retries here?
         let authority = ConsensusAuthority::start(
-            network_type,
             own_index,
             committee.clone(),
             parameters.clone(),
@@ -203,4 +201,10 @@ impl ConsensusManagerTrait for MysticetiManager {
     fn get_storage_base_path(&self) -> PathBuf {
         self.storage_base_path.clone()
     }
+
+    fn stall_reason(&self) -> Option<String> {
+        let authority = self.authority.load();
+        let (authority, _) = authority.as_deref()?;
+        authority.stall_reason().map(|reason| reason.to_string())
+    }
 }
@@ -226,4 +226,9 @@ impl ConsensusManagerTrait for NarwhalManager {
     fn get_storage_base_path(&self) -> PathBuf {
         self.storage_base_path.clone()
     }
+
+    // Stalls are only detected by Mysticeti.
+    fn stall_reason(&self) -> Option<String> {
+        None
+    }
 }
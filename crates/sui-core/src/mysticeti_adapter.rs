@@ -4,7 +4,7 @@
 use std::{sync::Arc, time::Duration};
 
 use arc_swap::{ArcSwapOption, Guard};
-use consensus_core::TransactionClient;
+use consensus_core::{TransactionClient, TransactionPriority};
 use sui_types::{
     error::{SuiError, SuiResult},
     messages_consensus::ConsensusTransaction,
@@ -82,10 +82,17 @@ impl SubmitToConsensus for LazyMysticetiClient {
         // Mysticeti shuts down, so there should be no correctness issue.
         let client = self.get().await;
         let tx_bytes = bcs::to_bytes(&transaction).expect("Serialization should not fail.");
+        // Transactions other than user certificates are needed by validators to make progress
+        // (e.g. checkpoint signatures), so they are sequenced ahead of user load.
+        let priority = if transaction.is_user_certificate() {
+            TransactionPriority::User
+        } else {
+            TransactionPriority::System
+        };
         client
             .as_ref()
             .expect("Client should always be returned")
-            .submit(tx_bytes)
+            .submit_with_priority(tx_bytes, priority)
             .await
             .tap_err(|r| {
                 // Will be logged by caller as well.
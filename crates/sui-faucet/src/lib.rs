@@ -6,6 +6,7 @@ mod faucet;
 mod metrics;
 mod requests;
 mod responses;
+mod server;
 
 pub mod metrics_layer;
 pub use metrics_layer::*;
@@ -14,3 +15,4 @@ pub use errors::FaucetError;
 pub use faucet::*;
 pub use requests::*;
 pub use responses::*;
+pub use server::*;
@@ -136,6 +136,10 @@ impl Builder {
         &self.validators
     }
 
+    pub fn signatures(&self) -> &BTreeMap<AuthorityPublicKeyBytes, AuthoritySignInfo> {
+        &self.signatures
+    }
+
     pub fn add_validator_signature(mut self, keypair: &AuthorityKeyPair) -> Self {
         let UnsignedGenesis { checkpoint, .. } = self.build_unsigned_genesis_checkpoint();
 
@@ -5,3 +5,6 @@ use axum::http::HeaderName;
 
 pub static VERSION_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-version");
 pub static LIMITS_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-show-usage");
+pub static WAIT_FOR_TRANSACTION_HEADER: HeaderName =
+    HeaderName::from_static("x-sui-rpc-wait-for-transaction");
+pub static API_KEY_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-api-key");
@@ -31,6 +31,9 @@ pub enum Command {
         /// DB URL for data fetching
         #[clap(short, long)]
         db_url: Option<String>,
+        /// URL of a read replica of the DB to load balance data fetching across (can be repeated)
+        #[clap(long = "db-replica-url")]
+        db_replica_urls: Vec<String>,
         /// Port to bind the server to
         #[clap(short, long)]
         port: Option<u16>,
@@ -51,5 +54,14 @@ pub enum Command {
         /// RPC url to the Node for tx execution
         #[clap(long)]
         node_rpc_url: Option<String>,
+
+        /// Serve the schema with deterministic generated data instead of reading from the
+        /// database, for developing against the API without an indexer.
+        #[clap(long)]
+        mock: bool,
+
+        /// Seed for the data generated in mock mode. Responses are stable for a given seed.
+        #[clap(long, default_value_t = 0, requires = "mock")]
+        mock_seed: u64,
     },
 }
@@ -4,9 +4,15 @@
 use crate::functional_group::FunctionalGroup;
 use crate::types::big_int::BigInt;
 use async_graphql::*;
+use chrono::NaiveDate;
 use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeSet, fmt::Display, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::PathBuf,
+    time::Duration,
+};
 use sui_json_rpc::name_service::NameServiceConfig;
 
 // TODO: calculate proper cost limits
@@ -14,6 +20,7 @@ use sui_json_rpc::name_service::NameServiceConfig;
 /// These values are set to support TS SDK shim layer queries for json-rpc compatibility.
 const MAX_QUERY_NODES: u32 = 300;
 const MAX_QUERY_PAYLOAD_SIZE: u32 = 5_000;
+const MAX_RESPONSE_SIZE: u64 = 10 * 1024 * 1024;
 
 const MAX_QUERY_DEPTH: u32 = 20;
 const MAX_OUTPUT_NODES: u64 = 100_000; // Maximum number of output nodes allowed in the response
@@ -28,6 +35,10 @@ const MAX_TYPE_NODES: u32 = 256;
 const MAX_MOVE_VALUE_DEPTH: u32 = 128;
 
 pub(crate) const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 40_000;
+const DEFAULT_MAX_WAIT_FOR_TRANSACTION_MS: u64 = 10_000;
+const DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION: u32 = 10;
+const DEFAULT_MAX_SUBSCRIPTION_LAG_CHECKPOINTS: u64 = 1_000;
+const DEFAULT_MAX_BATCH_SIZE: u32 = 10;
 
 const DEFAULT_IDE_TITLE: &str = "Sui GraphQL IDE";
 
@@ -43,6 +54,7 @@ pub(crate) const DEFAULT_SERVER_DB_POOL_SIZE: u32 = 3;
 pub(crate) const DEFAULT_SERVER_PROM_HOST: &str = "0.0.0.0";
 pub(crate) const DEFAULT_SERVER_PROM_PORT: u16 = 9184;
 pub(crate) const DEFAULT_WATERMARK_UPDATE_MS: u64 = 500;
+pub(crate) const DEFAULT_USAGE_REPORT_INTERVAL_MS: u64 = 24 * 60 * 60 * 1000;
 
 /// The combination of all configurations for the GraphQL service.
 #[derive(Serialize, Clone, Deserialize, Debug, Default)]
@@ -69,6 +81,10 @@ pub struct ConnectionConfig {
     /// Host to bind the server to
     pub(crate) host: String,
     pub(crate) db_url: String,
+    /// URLs of read replicas of the database at `db_url`. Queries are load balanced across the
+    /// database and its replicas.
+    #[serde(default)]
+    pub(crate) db_replica_urls: Vec<String>,
     pub(crate) db_pool_size: u32,
     pub(crate) prom_url: String,
     pub(crate) prom_port: u16,
@@ -86,6 +102,11 @@ pub struct ServiceConfig {
     #[serde(default)]
     pub(crate) disabled_features: BTreeSet<FunctionalGroup>,
 
+    /// Remove mutations from the schema, so the service only serves reads, even if it is
+    /// connected to a fullnode.
+    #[serde(default)]
+    pub(crate) disable_mutations: bool,
+
     #[serde(default)]
     pub(crate) experiments: Experiments,
 
@@ -97,6 +118,33 @@ pub struct ServiceConfig {
 
     #[serde(default)]
     pub(crate) zklogin: ZkLoginConfig,
+
+    /// API keys that are allowed to make admin queries (such as reading usage reports), when
+    /// passed in a request's `x-sui-rpc-api-key` header.
+    #[serde(default)]
+    pub(crate) admin_api_keys: BTreeSet<String>,
+
+    #[serde(default)]
+    pub(crate) persisted_queries: PersistedQueriesConfig,
+
+    #[serde(default)]
+    pub(crate) rate_limits: RateLimitsConfig,
+
+    #[serde(default)]
+    pub(crate) response_cache: ResponseCacheConfig,
+
+    #[serde(default)]
+    pub(crate) compression: CompressionConfig,
+
+    #[serde(default)]
+    pub(crate) health: HealthConfig,
+
+    /// Fields that have been renamed in the schema, whose old names continue to be served.
+    #[serde(default)]
+    pub(crate) field_aliases: Vec<FieldAlias>,
+
+    #[serde(default)]
+    pub(crate) pricing: PricingConfig,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Copy)]
@@ -110,6 +158,9 @@ pub struct Limits {
     pub max_output_nodes: u64,
     #[serde(default)]
     pub max_query_payload_size: u32,
+    /// Maximum size (in bytes) of a response, before it is compressed.
+    #[serde(default)]
+    pub max_response_size: u64,
     #[serde(default)]
     pub max_db_query_cost: u64,
     #[serde(default)]
@@ -126,6 +177,14 @@ pub struct Limits {
     pub max_type_nodes: u32,
     #[serde(default)]
     pub max_move_value_depth: u32,
+    #[serde(default)]
+    pub max_wait_for_transaction_ms: u64,
+    #[serde(default)]
+    pub max_subscriptions_per_connection: u32,
+    #[serde(default)]
+    pub max_subscription_lag_checkpoints: u64,
+    #[serde(default)]
+    pub max_batch_size: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Copy)]
@@ -133,6 +192,159 @@ pub struct Limits {
 pub struct BackgroundTasksConfig {
     #[serde(default)]
     pub watermark_update_ms: u64,
+    /// How often usage by each API key is aggregated and written out as a usage report.
+    #[serde(default)]
+    pub usage_report_interval_ms: u64,
+}
+
+/// Configures persisted query mode, in which the service only executes queries that have been
+/// registered ahead of time, identified by the hex-encoded SHA-256 hash of their text. The mode is
+/// enabled if at least one source of registered queries is configured.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct PersistedQueriesConfig {
+    /// Path to a file listing the hashes of registered queries, one per line. Blank lines and
+    /// lines starting with `#` are ignored.
+    #[serde(default)]
+    pub(crate) file: Option<PathBuf>,
+    /// Whether to load the hashes of registered queries from the `persisted_queries` table.
+    #[serde(default)]
+    pub(crate) from_db: bool,
+}
+
+/// Quotas on the requests made with each API key (named in a request's `x-sui-rpc-api-key`
+/// header), over and above the limits that apply to every request. Requests without an API key
+/// are only subject to those limits.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimitsConfig {
+    /// The quota for API keys that do not have their own quota.
+    #[serde(default)]
+    pub(crate) default: Quota,
+    /// Quotas for specific API keys. Metrics are only reported per API key for these keys.
+    #[serde(default)]
+    pub(crate) keys: BTreeMap<String, Quota>,
+    /// Where the usage of each API key is tracked.
+    #[serde(default)]
+    pub(crate) backend: StoreBackend,
+}
+
+/// The requests an API key can make, and their cost, each minute.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Quota {
+    /// Maximum number of requests that can be made per minute, unlimited if not set.
+    #[serde(default)]
+    pub(crate) requests_per_minute: Option<u64>,
+    /// Maximum number of output nodes that requests can cost per minute, unlimited if not set.
+    /// The cost of a request is estimated by the query limits checker.
+    #[serde(default)]
+    pub(crate) output_nodes_per_minute: Option<u64>,
+}
+
+/// Weights of a model pricing requests by their cost, to experiment with usage-based pricing. The
+/// price of every request is computed and reported (in metrics, usage reports, and the `usage`
+/// extension of responses), but it is not enforced. A request's price is the sum of its costs,
+/// each multiplied by its weight, so the price of every request is zero by default.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct PricingConfig {
+    /// The price of a request, regardless of its query. Each request of a batch is priced.
+    #[serde(default)]
+    pub(crate) per_request: u64,
+    /// The price of each node (field name) in a request's query.
+    #[serde(default)]
+    pub(crate) per_input_node: u64,
+    /// The price of each output node, as estimated by the query limits checker.
+    #[serde(default)]
+    pub(crate) per_output_node: u64,
+    /// The price of each level of nesting of the deepest operation in a request's query.
+    #[serde(default)]
+    pub(crate) per_depth: u64,
+    /// The price of each byte of a request's query payload.
+    #[serde(default)]
+    pub(crate) per_payload_byte: u64,
+}
+
+/// Configures a cache of the responses to queries, so that identical queries served at the same
+/// checkpoint are only executed once. The cache is enabled if it has a capacity.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ResponseCacheConfig {
+    /// The max number of responses to cache in memory. Responses cached in Redis are bounded by its
+    /// eviction policy instead.
+    #[serde(default)]
+    pub(crate) capacity: usize,
+    /// How long a response can be served from the cache for, in milliseconds. Responses are always
+    /// evicted when the checkpoint watermark advances, so if this is not set, they are served
+    /// until then.
+    #[serde(default)]
+    pub(crate) ttl_ms: Option<u64>,
+    /// Where the responses are cached.
+    #[serde(default)]
+    pub(crate) backend: StoreBackend,
+}
+
+/// Where the state shared by requests (API key usage, cached responses) is stored: either in the
+/// memory of each instance of the service (`"memory"`), or in a Redis instance, given by its URL
+/// (`"redis://..."`), so that instances behind a load balancer share their state.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(try_from = "String", into = "String")]
+pub enum StoreBackend {
+    #[default]
+    Memory,
+    Redis(String),
+}
+
+/// Configures compression of responses, for clients that accept it (through their
+/// `Accept-Encoding` header). Compression is enabled if at least one encoding is configured.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct CompressionConfig {
+    /// The encodings the service can compress responses with. If a client accepts more than one
+    /// of them, the one it prefers is used.
+    #[serde(default)]
+    pub(crate) encodings: BTreeSet<Encoding>,
+    /// Responses smaller than this (in bytes) are sent uncompressed.
+    #[serde(default)]
+    pub(crate) min_response_size: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum Encoding {
+    Gzip,
+    Br,
+}
+
+/// Configures the checks made by the service's `/health` endpoint.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct HealthConfig {
+    /// How far behind the network (in milliseconds, by wall-clock time) the checkpoint watermark
+    /// can fall before the service reports itself as unhealthy. If this is not set, the lag is
+    /// reported, but does not affect the service's health.
+    #[serde(default)]
+    pub(crate) max_checkpoint_lag_ms: Option<u64>,
+}
+
+/// A field that has been renamed in the schema, whose old name is served as an alias of its new
+/// name (with a deprecation warning in the response's extensions) until it expires, so that
+/// clients have time to migrate to the new name.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct FieldAlias {
+    /// The type (object or interface) that the field belongs to.
+    #[serde(rename = "type")]
+    pub(crate) type_: String,
+    /// The field's name before it was renamed.
+    pub(crate) old_name: String,
+    /// The field's name in the schema.
+    pub(crate) new_name: String,
+    /// The last day (UTC) that the old name is served on. If this is not set, it is served
+    /// indefinitely.
+    #[serde(default)]
+    pub(crate) until: Option<NaiveDate>,
 }
 
 /// The Version of the service. `year.month` represents the major release.
@@ -252,6 +464,8 @@ impl ServiceConfig {
     }
 
     /// The maximum number of nodes (field names) the service will accept in a single query.
+    ///
+    /// The operations of a query, and the queries of a batch of requests, share this limit.
     pub async fn max_query_nodes(&self) -> u32 {
         self.limits.max_query_nodes
     }
@@ -266,6 +480,8 @@ impl ServiceConfig {
     /// with a connection of first: 10 and has a field to a connection with last: 20, the count
     /// at the second level would be 200 nodes. This is then summed to the count of 10 nodes
     /// at the first level, for a total of 210 nodes.
+    ///
+    /// The operations of a query, and the queries of a batch of requests, share this limit.
     pub async fn max_output_nodes(&self) -> u64 {
         self.limits.max_output_nodes
     }
@@ -296,6 +512,12 @@ impl ServiceConfig {
         self.limits.max_query_payload_size
     }
 
+    /// Maximum size in bytes of a response, before it is compressed. Responses that are larger
+    /// are replaced by an error, regardless of the encoding they would be sent with.
+    async fn max_response_size(&self) -> u64 {
+        self.limits.max_response_size
+    }
+
     /// Maximum nesting allowed in type arguments in Move Types resolved by this service.
     async fn max_type_argument_depth(&self) -> u32 {
         self.limits.max_type_argument_depth
@@ -317,6 +539,30 @@ impl ServiceConfig {
     async fn max_move_value_depth(&self) -> u32 {
         self.limits.max_move_value_depth
     }
+
+    /// Maximum time in milliseconds that a request will wait for the transaction named in its
+    /// `x-sui-rpc-wait-for-transaction` header to be indexed, before it is served.
+    async fn max_wait_for_transaction_ms(&self) -> u64 {
+        self.limits.max_wait_for_transaction_ms
+    }
+
+    /// Maximum number of subscriptions that can be active at the same time on a single WebSocket
+    /// connection.
+    async fn max_subscriptions_per_connection(&self) -> u32 {
+        self.limits.max_subscriptions_per_connection
+    }
+
+    /// Maximum number of checkpoints that a subscription can fall behind the latest checkpoint
+    /// before it is ended, because its subscriber is not keeping up with the data it streams.
+    async fn max_subscription_lag_checkpoints(&self) -> u64 {
+        self.limits.max_subscription_lag_checkpoints
+    }
+
+    /// Maximum number of requests in a batch. The queries of a batch share the limit on the size
+    /// of a query payload, and its requests share the limit on the time spent serving a request.
+    async fn max_batch_size(&self) -> u32 {
+        self.limits.max_batch_size
+    }
 }
 
 impl TxExecFullNodeConfig {
@@ -339,12 +585,21 @@ impl ConnectionConfig {
             port: port.unwrap_or(default.port),
             host: host.unwrap_or(default.host),
             db_url: db_url.unwrap_or(default.db_url),
+            db_replica_urls: default.db_replica_urls,
             db_pool_size: db_pool_size.unwrap_or(default.db_pool_size),
             prom_url: prom_url.unwrap_or(default.prom_url),
             prom_port: prom_port.unwrap_or(default.prom_port),
         }
     }
 
+    /// Load balance queries across the database at `db_url` and these read replicas of it.
+    pub fn with_db_replica_urls(self, db_replica_urls: Vec<String>) -> Self {
+        Self {
+            db_replica_urls,
+            ..self
+        }
+    }
+
     pub fn ci_integration_test_cfg() -> Self {
         Self {
             db_url: DEFAULT_SERVER_DB_URL.to_string(),
@@ -373,6 +628,10 @@ impl ConnectionConfig {
         self.db_url.clone()
     }
 
+    pub fn db_replica_urls(&self) -> &[String] {
+        &self.db_replica_urls
+    }
+
     pub fn db_pool_size(&self) -> u32 {
         self.db_pool_size
     }
@@ -418,10 +677,103 @@ impl Ide {
     }
 }
 
+impl PersistedQueriesConfig {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.file.is_some() || self.from_db
+    }
+}
+
+impl RateLimitsConfig {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.default.is_limited() || !self.keys.is_empty()
+    }
+
+    /// The quota that applies to requests made with `api_key`.
+    pub(crate) fn quota(&self, api_key: &str) -> Quota {
+        self.keys.get(api_key).copied().unwrap_or(self.default)
+    }
+}
+
+impl Quota {
+    pub(crate) fn is_limited(&self) -> bool {
+        self.requests_per_minute.is_some() || self.output_nodes_per_minute.is_some()
+    }
+}
+
+impl PricingConfig {
+    /// The price of a request with the given costs. Prices saturate instead of overflowing.
+    pub(crate) fn price(
+        &self,
+        input_nodes: u64,
+        output_nodes: u64,
+        depth: u64,
+        payload_bytes: u64,
+    ) -> u64 {
+        [
+            (1, self.per_request),
+            (input_nodes, self.per_input_node),
+            (output_nodes, self.per_output_node),
+            (depth, self.per_depth),
+            (payload_bytes, self.per_payload_byte),
+        ]
+        .into_iter()
+        .fold(0u64, |price, (cost, weight)| {
+            price.saturating_add(cost.saturating_mul(weight))
+        })
+    }
+}
+
+impl ResponseCacheConfig {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+}
+
+impl TryFrom<String> for StoreBackend {
+    type Error = String;
+
+    fn try_from(backend: String) -> Result<Self, Self::Error> {
+        if backend == "memory" {
+            return Ok(StoreBackend::Memory);
+        }
+
+        // The URL is only parsed here, the service connects to Redis once it needs to.
+        match redis::Client::open(backend.as_str()) {
+            Ok(_) => Ok(StoreBackend::Redis(backend)),
+            Err(e) => Err(format!(
+                "unknown backend \"{backend}\", expected \"memory\" or a Redis URL: {e}"
+            )),
+        }
+    }
+}
+
+impl From<StoreBackend> for String {
+    fn from(backend: StoreBackend) -> Self {
+        match backend {
+            StoreBackend::Memory => "memory".to_string(),
+            StoreBackend::Redis(url) => url,
+        }
+    }
+}
+
+impl CompressionConfig {
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.encodings.is_empty()
+    }
+}
+
+impl FieldAlias {
+    /// Whether the old name is still served on `today`.
+    pub(crate) fn is_active(&self, today: NaiveDate) -> bool {
+        self.until.map_or(true, |until| today <= until)
+    }
+}
+
 impl BackgroundTasksConfig {
     pub fn test_defaults() -> Self {
         Self {
-            watermark_update_ms: 100, // Set to 100ms for testing
+            watermark_update_ms: 100,       // Set to 100ms for testing
+            usage_report_interval_ms: 1000, // Set to 1s for testing
         }
     }
 }
@@ -440,6 +792,7 @@ impl Default for ConnectionConfig {
             port: DEFAULT_SERVER_CONNECTION_PORT,
             host: DEFAULT_SERVER_CONNECTION_HOST.to_string(),
             db_url: DEFAULT_SERVER_DB_URL.to_string(),
+            db_replica_urls: vec![],
             db_pool_size: DEFAULT_SERVER_DB_POOL_SIZE,
             prom_url: DEFAULT_SERVER_PROM_HOST.to_string(),
             prom_port: DEFAULT_SERVER_PROM_PORT,
@@ -454,6 +807,7 @@ impl Default for Limits {
             max_query_nodes: MAX_QUERY_NODES,
             max_output_nodes: MAX_OUTPUT_NODES,
             max_query_payload_size: MAX_QUERY_PAYLOAD_SIZE,
+            max_response_size: MAX_RESPONSE_SIZE,
             max_db_query_cost: MAX_DB_QUERY_COST,
             default_page_size: DEFAULT_PAGE_SIZE,
             max_page_size: MAX_PAGE_SIZE,
@@ -462,6 +816,10 @@ impl Default for Limits {
             max_type_argument_width: MAX_TYPE_ARGUMENT_WIDTH,
             max_type_nodes: MAX_TYPE_NODES,
             max_move_value_depth: MAX_MOVE_VALUE_DEPTH,
+            max_wait_for_transaction_ms: DEFAULT_MAX_WAIT_FOR_TRANSACTION_MS,
+            max_subscriptions_per_connection: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+            max_subscription_lag_checkpoints: DEFAULT_MAX_SUBSCRIPTION_LAG_CHECKPOINTS,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
         }
     }
 }
@@ -485,6 +843,7 @@ impl Default for BackgroundTasksConfig {
     fn default() -> Self {
         Self {
             watermark_update_ms: DEFAULT_WATERMARK_UPDATE_MS,
+            usage_report_interval_ms: DEFAULT_USAGE_REPORT_INTERVAL_MS,
         }
     }
 }
@@ -508,6 +867,7 @@ mod tests {
                 max-query-nodes = 300
                 max-output-nodes = 200000
                 max-query-payload-size = 2000
+                max-response-size = 1048576
                 max-db-query-cost = 50
                 default-page-size = 20
                 max-page-size = 50
@@ -516,6 +876,10 @@ mod tests {
                 max-type-argument-width = 64
                 max-type-nodes = 128
                 max-move-value-depth = 256
+                max-wait-for-transaction-ms = 5000
+                max-subscriptions-per-connection = 4
+                max-subscription-lag-checkpoints = 500
+                max-batch-size = 5
             "#,
         )
         .unwrap();
@@ -526,6 +890,7 @@ mod tests {
                 max_query_nodes: 300,
                 max_output_nodes: 200000,
                 max_query_payload_size: 2000,
+                max_response_size: 1_048_576,
                 max_db_query_cost: 50,
                 default_page_size: 20,
                 max_page_size: 50,
@@ -534,6 +899,10 @@ mod tests {
                 max_type_argument_width: 64,
                 max_type_nodes: 128,
                 max_move_value_depth: 256,
+                max_wait_for_transaction_ms: 5_000,
+                max_subscriptions_per_connection: 4,
+                max_subscription_lag_checkpoints: 500,
+                max_batch_size: 5,
             },
             ..Default::default()
         };
@@ -561,6 +930,18 @@ mod tests {
         assert_eq!(actual, expect)
     }
 
+    #[test]
+    fn test_read_disable_mutations_in_service_config() {
+        let actual = ServiceConfig::read("disable-mutations = true").unwrap();
+
+        let expect = ServiceConfig {
+            disable_mutations: true,
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
     #[test]
     fn test_read_experiments_in_service_config() {
         let actual = ServiceConfig::read(
@@ -578,6 +959,274 @@ mod tests {
         assert_eq!(actual, expect)
     }
 
+    #[test]
+    fn test_read_admin_api_keys_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" admin-api-keys = ["operator", "billing"]
+
+                [background-tasks]
+                watermark-update-ms = 1000
+                usage-report-interval-ms = 3600000
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            admin_api_keys: BTreeSet::from(["billing".to_string(), "operator".to_string()]),
+            background_tasks: BackgroundTasksConfig {
+                watermark_update_ms: 1_000,
+                usage_report_interval_ms: 3_600_000,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_persisted_queries_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [persisted-queries]
+                file = "persisted-queries.txt"
+                from-db = true
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            persisted_queries: PersistedQueriesConfig {
+                file: Some(PathBuf::from("persisted-queries.txt")),
+                from_db: true,
+            },
+            ..Default::default()
+        };
+
+        assert!(actual.persisted_queries.is_enabled());
+        assert!(!ServiceConfig::default().persisted_queries.is_enabled());
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_rate_limits_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [rate-limits]
+                backend = "memory"
+
+                [rate-limits.default]
+                requests-per-minute = 600
+                output-nodes-per-minute = 1000000
+
+                [rate-limits.keys.partner]
+                requests-per-minute = 6000
+            "#,
+        )
+        .unwrap();
+
+        let default = Quota {
+            requests_per_minute: Some(600),
+            output_nodes_per_minute: Some(1_000_000),
+        };
+        let partner = Quota {
+            requests_per_minute: Some(6000),
+            output_nodes_per_minute: None,
+        };
+        let expect = ServiceConfig {
+            rate_limits: RateLimitsConfig {
+                default,
+                keys: BTreeMap::from([("partner".to_string(), partner)]),
+                backend: StoreBackend::Memory,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+        assert!(actual.rate_limits.is_enabled());
+        assert_eq!(actual.rate_limits.quota("partner"), partner);
+        assert_eq!(actual.rate_limits.quota("other"), default);
+        assert!(!ServiceConfig::default().rate_limits.is_enabled());
+    }
+
+    #[test]
+    fn test_read_response_cache_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [response-cache]
+                capacity = 10000
+                ttl-ms = 5000
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            response_cache: ResponseCacheConfig {
+                capacity: 10_000,
+                ttl_ms: Some(5_000),
+                backend: StoreBackend::Memory,
+            },
+            ..Default::default()
+        };
+
+        assert!(actual.response_cache.is_enabled());
+        assert!(!ServiceConfig::default().response_cache.is_enabled());
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_redis_store_backend() {
+        let actual = ServiceConfig::read(
+            r#" [rate-limits]
+                backend = "redis://127.0.0.1:6379"
+
+                [response-cache]
+                capacity = 10000
+                backend = "redis://127.0.0.1:6379/1"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual.rate_limits.backend,
+            StoreBackend::Redis("redis://127.0.0.1:6379".to_string())
+        );
+        assert_eq!(
+            actual.response_cache.backend,
+            StoreBackend::Redis("redis://127.0.0.1:6379/1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reject_unknown_store_backend() {
+        for section in ["rate-limits", "response-cache"] {
+            let err = ServiceConfig::read(&format!(
+                r#" [{section}]
+                    backend = "memcached"
+                "#
+            ))
+            .unwrap_err();
+            assert!(
+                err.to_string().contains("unknown backend \"memcached\""),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_compression_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [compression]
+                encodings = ["br", "gzip"]
+                min-response-size = 1024
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            compression: CompressionConfig {
+                encodings: BTreeSet::from([Encoding::Gzip, Encoding::Br]),
+                min_response_size: 1024,
+            },
+            ..Default::default()
+        };
+
+        assert!(actual.compression.is_enabled());
+        assert!(!ServiceConfig::default().compression.is_enabled());
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_health_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [health]
+                max-checkpoint-lag-ms = 300000
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            health: HealthConfig {
+                max_checkpoint_lag_ms: Some(300_000),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_field_aliases_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [[field-aliases]]
+                type = "Checkpoint"
+                old-name = "seq"
+                new-name = "sequenceNumber"
+                until = "2024-06-30"
+
+                [[field-aliases]]
+                type = "Query"
+                old-name = "chainId"
+                new-name = "chainIdentifier"
+            "#,
+        )
+        .unwrap();
+
+        let until = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let expect = ServiceConfig {
+            field_aliases: vec![
+                FieldAlias {
+                    type_: "Checkpoint".to_string(),
+                    old_name: "seq".to_string(),
+                    new_name: "sequenceNumber".to_string(),
+                    until: Some(until),
+                },
+                FieldAlias {
+                    type_: "Query".to_string(),
+                    old_name: "chainId".to_string(),
+                    new_name: "chainIdentifier".to_string(),
+                    until: None,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+
+        // Aliases are served up to and including their last day.
+        assert!(actual.field_aliases[0].is_active(until));
+        assert!(!actual.field_aliases[0].is_active(until.succ_opt().unwrap()));
+        assert!(actual.field_aliases[1].is_active(until.succ_opt().unwrap()));
+    }
+
+    #[test]
+    fn test_read_pricing_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [pricing]
+                per-request = 1000
+                per-input-node = 10
+                per-output-node = 1
+                per-payload-byte = 2
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            pricing: PricingConfig {
+                per_request: 1000,
+                per_input_node: 10,
+                per_output_node: 1,
+                per_depth: 0,
+                per_payload_byte: 2,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+        assert_eq!(
+            actual.pricing.price(20, 300, 5, 400),
+            1000 + 200 + 300 + 800
+        );
+        assert_eq!(ServiceConfig::default().pricing.price(20, 300, 5, 400), 0);
+        assert_eq!(actual.pricing.price(0, u64::MAX, 0, 0), u64::MAX);
+    }
+
     #[test]
     fn test_read_everything_in_service_config() {
         let actual = ServiceConfig::read(
@@ -588,6 +1237,7 @@ mod tests {
                 max-query-nodes = 320
                 max-output-nodes = 200000
                 max-query-payload-size = 200
+                max-response-size = 65536
                 max-db-query-cost = 20
                 default-page-size = 10
                 max-page-size = 20
@@ -596,6 +1246,10 @@ mod tests {
                 max-type-argument-width = 64
                 max-type-nodes = 128
                 max-move-value-depth = 256
+                max-wait-for-transaction-ms = 5000
+                max-subscriptions-per-connection = 4
+                max-subscription-lag-checkpoints = 500
+                max-batch-size = 5
 
                 [experiments]
                 test-flag = true
@@ -609,6 +1263,7 @@ mod tests {
                 max_query_nodes: 320,
                 max_output_nodes: 200000,
                 max_query_payload_size: 200,
+                max_response_size: 65_536,
                 max_db_query_cost: 20,
                 default_page_size: 10,
                 max_page_size: 20,
@@ -617,6 +1272,10 @@ mod tests {
                 max_type_argument_width: 64,
                 max_type_nodes: 128,
                 max_move_value_depth: 256,
+                max_wait_for_transaction_ms: 5_000,
+                max_subscriptions_per_connection: 4,
+                max_subscription_lag_checkpoints: 500,
+                max_batch_size: 5,
             },
             disabled_features: BTreeSet::from([FunctionalGroup::Analytics]),
             experiments: Experiments { test_flag: true },
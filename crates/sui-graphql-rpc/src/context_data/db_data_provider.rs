@@ -8,7 +8,10 @@ use crate::{
 };
 use std::{collections::BTreeMap, time::Duration};
 use sui_indexer::db::PgConnectionPoolConfig;
-use sui_indexer::{apis::GovernanceReadApi, indexer_reader::IndexerReader};
+use sui_indexer::{
+    apis::GovernanceReadApi,
+    indexer_reader::{IndexerReader, ReadConsistency},
+};
 use sui_json_rpc_types::Stake as RpcStakedSui;
 use sui_types::{
     base_types::SuiAddress as NativeSuiAddress,
@@ -36,6 +39,8 @@ impl PgManager {
         )
     }
 
+    /// The reader's point reads are bounded by the last fully committed checkpoint, consistent
+    /// with the checkpoint-bounded queries GraphQL makes directly against the database.
     pub(crate) fn reader_with_config(
         db_url: impl Into<String>,
         pool_size: u32,
@@ -44,7 +49,11 @@ impl PgManager {
         let mut config = PgConnectionPoolConfig::default();
         config.set_pool_size(pool_size);
         config.set_statement_timeout(Duration::from_millis(timeout_ms));
+        // Give up waiting for a connection within the same bound, so that requests can fail over
+        // to another replica if this database is unavailable.
+        config.set_connection_timeout(Duration::from_millis(timeout_ms));
         IndexerReader::new_with_config(db_url, config)
+            .map(|reader| reader.with_consistency(ReadConsistency::Checkpoint))
             .map_err(|e| Error::Internal(format!("Failed to create reader: {e}")))
     }
 }
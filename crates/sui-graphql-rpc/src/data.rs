@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod pg;
+pub(crate) mod replicas;
 
 use async_trait::async_trait;
 use diesel::{
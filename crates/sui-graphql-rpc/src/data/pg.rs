@@ -3,7 +3,7 @@
 
 use std::time::Instant;
 
-use super::QueryExecutor;
+use super::{replicas::Replicas, QueryExecutor};
 use crate::{config::Limits, error::Error, metrics::Metrics};
 use async_trait::async_trait;
 use diesel::{
@@ -12,13 +12,11 @@ use diesel::{
     query_dsl::LoadQuery,
     QueryResult, RunQueryDsl,
 };
-use sui_indexer::indexer_reader::IndexerReader;
-
 use tracing::error;
 
 #[derive(Clone)]
 pub(crate) struct PgExecutor {
-    pub inner: IndexerReader,
+    pub replicas: Replicas,
     pub limits: Limits,
     pub metrics: Metrics,
 }
@@ -29,13 +27,41 @@ pub(crate) struct PgConnection<'c> {
 }
 
 impl PgExecutor {
-    pub(crate) fn new(inner: IndexerReader, limits: Limits, metrics: Metrics) -> Self {
+    pub(crate) fn new(replicas: Replicas, limits: Limits, metrics: Metrics) -> Self {
         Self {
-            inner,
+            replicas,
             limits,
             metrics,
         }
     }
+
+    /// Execute `txn` against each of the databases that queries are served from, for queries
+    /// whose results must hold regardless of which database a later query is served from (e.g.
+    /// the latest checkpoint that has been indexed). Databases that cannot be queried are
+    /// skipped, and an error is only returned if none of them can be.
+    pub(crate) async fn execute_on_each<T, U, E>(&self, txn: T) -> Result<Vec<U>, Error>
+    where
+        T: Fn(&mut PgConnection<'_>) -> Result<U, E>,
+        E: From<diesel::result::Error> + std::error::Error,
+        T: Send + 'static,
+        U: Send + 'static,
+        E: Send + 'static,
+    {
+        let max_cost = self.limits.max_db_query_cost;
+        let instant = Instant::now();
+        let result = self
+            .replicas
+            .run_query_on_each_async(self.metrics.clone(), move |conn| {
+                txn(&mut PgConnection { max_cost, conn })
+            })
+            .await;
+        self.metrics
+            .observe_db_data(instant.elapsed(), result.is_ok());
+        if let Err(e) = &result {
+            error!("DB query error: {e:?}");
+        }
+        result.map_err(|e| Error::Internal(e.to_string()))
+    }
 }
 
 #[async_trait]
@@ -55,8 +81,10 @@ impl QueryExecutor for PgExecutor {
         let max_cost = self.limits.max_db_query_cost;
         let instant = Instant::now();
         let result = self
-            .inner
-            .run_query_async(move |conn| txn(&mut PgConnection { max_cost, conn }))
+            .replicas
+            .run_query_async(false, self.metrics.clone(), move |conn| {
+                txn(&mut PgConnection { max_cost, conn })
+            })
             .await;
         self.metrics
             .observe_db_data(instant.elapsed(), result.is_ok());
@@ -77,8 +105,10 @@ impl QueryExecutor for PgExecutor {
         let max_cost = self.limits.max_db_query_cost;
         let instant = Instant::now();
         let result = self
-            .inner
-            .run_query_repeatable_async(move |conn| txn(&mut PgConnection { max_cost, conn }))
+            .replicas
+            .run_query_async(true, self.metrics.clone(), move |conn| {
+                txn(&mut PgConnection { max_cost, conn })
+            })
             .await;
         self.metrics
             .observe_db_data(instant.elapsed(), result.is_ok());
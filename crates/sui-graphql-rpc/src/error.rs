@@ -14,6 +14,9 @@ pub(crate) mod code {
     pub const BAD_USER_INPUT: &str = "BAD_USER_INPUT";
     pub const INTERNAL_SERVER_ERROR: &str = "INTERNAL_SERVER_ERROR";
     pub const REQUEST_TIMEOUT: &str = "REQUEST_TIMEOUT";
+    pub const RESPONSE_TOO_LARGE: &str = "RESPONSE_TOO_LARGE";
+    pub const SERVICE_READ_ONLY: &str = "SERVICE_READ_ONLY";
+    pub const TOO_MANY_REQUESTS: &str = "TOO_MANY_REQUESTS";
     pub const UNKNOWN: &str = "UNKNOWN";
 }
 
@@ -76,6 +79,9 @@ pub enum Error {
     Client(String),
     #[error("Internal error occurred while processing request: {0}")]
     Internal(String),
+    // The request needs to write to the network, but the service only serves reads
+    #[error("{0}")]
+    ReadOnly(String),
 }
 
 impl ErrorExtensions for Error {
@@ -91,6 +97,9 @@ impl ErrorExtensions for Error {
             Error::Internal(_) => {
                 e.set("code", code::INTERNAL_SERVER_ERROR);
             }
+            Error::ReadOnly(_) => {
+                e.set("code", code::SERVICE_READ_ONLY);
+            }
         })
     }
 }
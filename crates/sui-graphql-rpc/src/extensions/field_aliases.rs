@@ -0,0 +1,201 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery, NextRequest},
+    parser::types::{
+        DocumentOperations, ExecutableDocument, OperationDefinition, OperationType, Selection,
+        SelectionSet,
+    },
+    registry::{MetaType, Registry},
+    value, Name, Positioned, Response, ServerResult, Value, Variables,
+};
+use chrono::{NaiveDate, Utc};
+use tokio::sync::Mutex;
+
+use crate::config::FieldAlias;
+
+/// Serves the old names of fields that have been renamed in the schema, by rewriting them to their
+/// new names in the query (keeping the old name as the key in the response), and reports each
+/// alias that was used as a deprecation in the response's extensions.
+///
+/// An alias is only applied to fields selected on the exact type it is configured for, and only
+/// while its old name is not itself a field of that type.
+#[derive(Debug, Default)]
+pub(crate) struct FieldAliases {
+    /// Aliases, keyed by `Type.oldName`.
+    aliases: Arc<BTreeMap<String, FieldAlias>>,
+    /// Keys of the aliases used by the current request.
+    used: Mutex<BTreeSet<String>>,
+}
+
+impl FieldAliases {
+    pub(crate) fn new(aliases: impl IntoIterator<Item = FieldAlias>) -> Self {
+        Self {
+            aliases: Arc::new(
+                aliases
+                    .into_iter()
+                    .map(|alias| (format!("{}.{}", alias.type_, alias.old_name), alias))
+                    .collect(),
+            ),
+            used: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// Rewrite aliased fields in `selection_set`, which is selected on `parent`. Fragment spreads
+    /// are not followed, because fragment definitions are rewritten separately.
+    fn rewrite(
+        &self,
+        registry: &Registry,
+        parent: &MetaType,
+        selection_set: &mut Positioned<SelectionSet>,
+        today: NaiveDate,
+        used: &mut BTreeSet<String>,
+    ) {
+        for selection in &mut selection_set.node.items {
+            match &mut selection.node {
+                Selection::Field(field) => {
+                    let field = &mut field.node;
+                    let key = format!("{}.{}", parent.name(), field.name.node);
+
+                    if let Some(alias) = self.aliases.get(&key) {
+                        if alias.is_active(today) && parent.field_by_name(&alias.old_name).is_none()
+                        {
+                            // Keep serving the field's value under its old name, unless the query
+                            // gave it an alias of its own.
+                            if field.alias.is_none() {
+                                field.alias = Some(field.name.clone());
+                            }
+
+                            field.name.node = Name::new(&alias.new_name);
+                            used.insert(key);
+                        }
+                    }
+
+                    let Some(ty) = parent
+                        .field_by_name(&field.name.node)
+                        .and_then(|f| registry.concrete_type_by_name(&f.ty))
+                    else {
+                        continue;
+                    };
+
+                    self.rewrite(registry, ty, &mut field.selection_set, today, used);
+                }
+
+                Selection::InlineFragment(fragment) => {
+                    let fragment = &mut fragment.node;
+                    let ty = match &fragment.type_condition {
+                        None => Some(parent),
+                        Some(cond) => registry.types.get(cond.node.on.node.as_str()),
+                    };
+
+                    if let Some(ty) = ty {
+                        self.rewrite(registry, ty, &mut fragment.selection_set, today, used);
+                    }
+                }
+
+                Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+
+    fn rewrite_operation(
+        &self,
+        registry: &Registry,
+        operation: &mut Positioned<OperationDefinition>,
+        today: NaiveDate,
+        used: &mut BTreeSet<String>,
+    ) {
+        let operation = &mut operation.node;
+        let root = match operation.ty {
+            OperationType::Query => Some(&registry.query_type),
+            OperationType::Mutation => registry.mutation_type.as_ref(),
+            OperationType::Subscription => registry.subscription_type.as_ref(),
+        };
+
+        if let Some(root) = root.and_then(|name| registry.types.get(name)) {
+            self.rewrite(registry, root, &mut operation.selection_set, today, used);
+        }
+    }
+}
+
+impl ExtensionFactory for FieldAliases {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(FieldAliases {
+            aliases: self.aliases.clone(),
+            used: Mutex::new(BTreeSet::new()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for FieldAliases {
+    /// Reports the aliases that the request used as deprecations.
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let resp = next.run(ctx).await;
+        let used = std::mem::take(&mut *self.used.lock().await);
+        if used.is_empty() {
+            return resp;
+        }
+
+        let deprecations: Vec<Value> = used
+            .iter()
+            .filter_map(|key| self.aliases.get(key))
+            .map(|alias| {
+                let field = format!("{}.{}", alias.type_, alias.old_name);
+                let until = alias.until.map(|until| until.to_string());
+                value!({
+                    "field": field,
+                    "replacement": alias.new_name,
+                    "until": until,
+                })
+            })
+            .collect();
+
+        resp.extension("deprecations", Value::List(deprecations))
+    }
+
+    /// Rewrites the old names of renamed fields to their new names, before the query is
+    /// validated against the schema.
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let mut doc = next.run(ctx, query, variables).await?;
+        let registry = &ctx.schema_env.registry;
+        let today = Utc::now().date_naive();
+        let mut used = BTreeSet::new();
+
+        match &mut doc.operations {
+            DocumentOperations::Single(operation) => {
+                self.rewrite_operation(registry, operation, today, &mut used);
+            }
+            DocumentOperations::Multiple(operations) => {
+                for operation in operations.values_mut() {
+                    self.rewrite_operation(registry, operation, today, &mut used);
+                }
+            }
+        }
+
+        for fragment in doc.fragments.values_mut() {
+            let fragment = &mut fragment.node;
+            if let Some(ty) = registry
+                .types
+                .get(fragment.type_condition.node.on.node.as_str())
+            {
+                self.rewrite(registry, ty, &mut fragment.selection_set, today, &mut used);
+            }
+        }
+
+        self.used.lock().await.extend(used);
+        Ok(doc)
+    }
+}
@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod feature_gate;
+pub(crate) mod field_aliases;
 pub(crate) mod logger;
+pub(crate) mod persisted_queries;
 pub mod query_limits_checker;
+pub(crate) mod rate_limiter;
 pub(crate) mod timeout;
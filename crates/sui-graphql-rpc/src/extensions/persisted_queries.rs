@@ -0,0 +1,180 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeSet, net::SocketAddr, sync::Arc};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery},
+    parser::types::ExecutableDocument,
+    ServerResult, Variables,
+};
+use diesel::QueryDsl;
+use fastcrypto::hash::{HashFunction, Sha256};
+use sui_indexer::schema::persisted_queries;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    config::PersistedQueriesConfig,
+    data::{Db, DbConnection, QueryExecutor},
+    error::{code, graphql_error, Error},
+    metrics::Metrics,
+};
+
+/// Only allows queries that have been registered ahead of time (persisted queries) to be executed.
+/// Queries are identified by the hex-encoded SHA-256 hash of their text, so a registered query
+/// must be sent exactly as it was registered.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PersistedQueries {
+    hashes: Arc<BTreeSet<String>>,
+}
+
+impl PersistedQueries {
+    pub(crate) fn new(hashes: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            hashes: Arc::new(hashes.into_iter().collect()),
+        }
+    }
+
+    /// Load the hashes of the persisted queries from the sources enabled in `config`: a file
+    /// listing them, and/or the `persisted_queries` table.
+    pub(crate) async fn load(config: &PersistedQueriesConfig, db: &Db) -> Result<Self, Error> {
+        let mut hashes = BTreeSet::new();
+
+        if let Some(path) = &config.file {
+            let contents = std::fs::read_to_string(path).map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to read persisted queries from {}: {e}",
+                    path.display()
+                ))
+            })?;
+            hashes.extend(parse_hashes(&contents)?);
+        }
+
+        if config.from_db {
+            use persisted_queries::dsl;
+            let stored: Vec<String> = db
+                .execute(move |conn| {
+                    conn.results(move || dsl::persisted_queries.select(dsl::query_hash))
+                })
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to fetch persisted queries: {e}")))?;
+            hashes.extend(stored.into_iter().map(|h| h.to_lowercase()));
+        }
+
+        info!("Loaded {} persisted queries", hashes.len());
+        Ok(Self::new(hashes))
+    }
+
+    /// The hash that identifies `query` as a persisted query.
+    pub(crate) fn hash(query: &str) -> String {
+        hex::encode(Sha256::digest(query.as_bytes()).digest)
+    }
+}
+
+/// Parse a list of persisted query hashes, one per line, ignoring blank lines and lines starting
+/// with `#`.
+fn parse_hashes(contents: &str) -> Result<Vec<String>, Error> {
+    let mut hashes = vec![];
+    for (ix, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.len() != 64 || !line.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::Internal(format!(
+                "Invalid persisted query hash on line {}: {line}",
+                ix + 1
+            )));
+        }
+
+        hashes.push(line.to_lowercase());
+    }
+
+    Ok(hashes)
+}
+
+impl ExtensionFactory for PersistedQueries {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for PersistedQueries {
+    /// Rejects the query before it is parsed, if it has not been registered.
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let hash = Self::hash(query);
+        if !self.hashes.contains(&hash) {
+            let query_id: &Uuid = ctx.data_unchecked();
+            let session_id: &SocketAddr = ctx.data_unchecked();
+            let metrics: &Metrics = ctx.data_unchecked();
+            metrics.request_metrics.persisted_query_rejections.inc();
+            info!(
+                query_id = %query_id,
+                session_id = %session_id,
+                error_code = code::BAD_REQUEST,
+                "Query {hash} is not a persisted query",
+            );
+
+            return Err(graphql_error(
+                code::BAD_REQUEST,
+                format!(
+                    "This service only executes persisted queries, and query {hash} has not been \
+                     registered"
+                ),
+            ));
+        }
+
+        next.run(ctx, query, variables).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_query() {
+        assert_eq!(
+            PersistedQueries::hash("{ chainIdentifier }"),
+            "a2da14596e504f3f879d825b36cbf8d45f353b0224c2602323a3914e133e3aea",
+        );
+        assert_ne!(
+            PersistedQueries::hash("{ chainIdentifier }"),
+            PersistedQueries::hash("{chainIdentifier}"),
+        );
+    }
+
+    #[test]
+    fn test_parse_hashes() {
+        let a = PersistedQueries::hash("{ chainIdentifier }");
+        let b = PersistedQueries::hash("{ epoch { epochId } }");
+        let contents = format!(
+            "# Queries used by the explorer\n{a}\n\n  {}  \n",
+            b.to_uppercase()
+        );
+
+        assert_eq!(parse_hashes(&contents).unwrap(), vec![a, b]);
+    }
+
+    #[test]
+    fn test_parse_invalid_hashes() {
+        let err = parse_hashes("\nnot-a-hash\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Internal error occurred while processing request: \
+             Invalid persisted query hash on line 2: not-a-hash"
+        );
+
+        // Too short to be a SHA-256 hash.
+        assert!(parse_hashes("abcdef").is_err());
+    }
+}
@@ -4,6 +4,7 @@
 use crate::config::{Limits, ServiceConfig};
 use crate::error::{code, graphql_error, graphql_error_at_pos};
 use crate::metrics::Metrics;
+use crate::usage::RequestCost;
 use async_graphql::extensions::NextParseQuery;
 use async_graphql::extensions::NextRequest;
 use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory};
@@ -28,6 +29,15 @@ use uuid::Uuid;
 /// Only display usage information if this header was in the request.
 pub(crate) struct ShowUsage;
 
+/// Shared by the requests of a batch, so that the operations of all of them draw from a single
+/// budget of input and output nodes, as if they were the operations of a single request. Each
+/// request of the batch is given its own copy, identifying its position in the batch.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BatchBudget {
+    index: usize,
+    used: Arc<std::sync::Mutex<ComponentCost>>,
+}
+
 #[derive(Clone, Debug, Default)]
 struct ValidationRes {
     input_nodes: u32,
@@ -36,6 +46,7 @@ struct ValidationRes {
     num_variables: u32,
     num_fragments: u32,
     query_payload: u32,
+    price: u64,
 }
 
 #[derive(Debug, Default)]
@@ -70,7 +81,7 @@ impl ExtensionFactory for QueryLimitsChecker {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 struct ComponentCost {
     pub input_nodes: u32,
     pub output_nodes: u64,
@@ -89,6 +100,16 @@ impl std::ops::Add for ComponentCost {
     }
 }
 
+impl BatchBudget {
+    /// The copy of the budget given to the request at position `index` in the batch.
+    pub(crate) fn for_request(&self, index: usize) -> Self {
+        Self {
+            index,
+            used: self.used.clone(),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl Extension for QueryLimitsChecker {
     async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
@@ -104,6 +125,7 @@ impl Extension for QueryLimitsChecker {
                     "variables": validation_result.num_variables,
                     "fragments": validation_result.num_fragments,
                     "queryPayload": validation_result.query_payload,
+                    "price": validation_result.price,
                 }),
             )
         } else {
@@ -154,15 +176,22 @@ impl Extension for QueryLimitsChecker {
 
         // TODO: Limit the complexity of fragments early on
 
+        // The operations of a request in a batch start from the nodes used up by the requests
+        // before it.
+        let batch = ctx.data_opt::<BatchBudget>();
+        let initial_costs =
+            batch.map_or_else(ComponentCost::default, |batch| *batch.used.lock().unwrap());
         let mut running_costs = ComponentCost {
             depth: 0,
-            input_nodes: 0,
-            output_nodes: 0,
+            ..initial_costs
         };
         let mut max_depth_seen = 0;
 
+        // Limits shared by several operations name the operation that exceeded them.
+        let attribute = batch.is_some() || doc.operations.iter().count() > 1;
+
         // An operation is a query, mutation or subscription consisting of a set of selections
-        for (count, (_name, oper)) in doc.operations.iter().enumerate() {
+        for (count, (name, oper)) in doc.operations.iter().enumerate() {
             let sel_set = &oper.node.selection_set;
 
             // If the query is pure introspection, we don't need to check the limits.
@@ -178,6 +207,7 @@ impl Extension for QueryLimitsChecker {
                 }
             }
 
+            let operation = attribute.then(|| describe_operation(name, batch));
             running_costs.depth = 0;
             self.analyze_selection_set(
                 &cfg.limits,
@@ -185,29 +215,52 @@ impl Extension for QueryLimitsChecker {
                 sel_set,
                 &mut running_costs,
                 variables,
+                operation.as_deref(),
                 ctx,
             )?;
             max_depth_seen = max_depth_seen.max(running_costs.depth);
         }
+
+        if let Some(batch) = batch {
+            *batch.used.lock().unwrap() = running_costs;
+        }
+        // Usage, price and metrics are reported for this request alone.
+        let input_nodes = running_costs.input_nodes - initial_costs.input_nodes;
+        let output_nodes = running_costs.output_nodes - initial_costs.output_nodes;
+        let price = cfg.pricing.price(
+            input_nodes as u64,
+            output_nodes,
+            max_depth_seen as u64,
+            query.len() as u64,
+        );
+
+        // The requests of a batch share their `RequestCost`, which is charged for the nodes used
+        // up by the whole batch so far, and for the price of each of its requests.
+        if let Some(cost) = ctx.data_opt::<RequestCost>() {
+            cost.record(running_costs.input_nodes as u64, running_costs.output_nodes);
+            cost.charge(price);
+        }
+
         if ctx.data_opt::<ShowUsage>().is_some() {
             *self.validation_result.lock().await = Some(ValidationRes {
-                input_nodes: running_costs.input_nodes,
-                output_nodes: running_costs.output_nodes,
+                input_nodes,
+                output_nodes,
                 depth: running_costs.depth,
                 query_payload: query.len() as u32,
                 num_variables: variables.len() as u32,
                 num_fragments: doc.fragments.len() as u32,
+                price,
             });
         }
         metrics.query_validation_latency(instant.elapsed());
         metrics
             .request_metrics
             .input_nodes
-            .observe(running_costs.input_nodes as f64);
+            .observe(input_nodes as f64);
         metrics
             .request_metrics
             .output_nodes
-            .observe(running_costs.output_nodes as f64);
+            .observe(output_nodes as f64);
         metrics
             .request_metrics
             .query_depth
@@ -216,12 +269,15 @@ impl Extension for QueryLimitsChecker {
             .request_metrics
             .query_payload_size
             .observe(query.len() as f64);
+        metrics.request_metrics.request_price.observe(price as f64);
         Ok(doc)
     }
 }
 
 impl QueryLimitsChecker {
     /// Parse the selected fields in one operation and check if it conforms to configured limits.
+    /// Errors name the `operation` if it is set.
+    #[allow(clippy::too_many_arguments)]
     fn analyze_selection_set(
         &self,
         limits: &Limits,
@@ -229,6 +285,7 @@ impl QueryLimitsChecker {
         sel_set: &Positioned<SelectionSet>,
         cost: &mut ComponentCost,
         variables: &Variables,
+        operation: Option<&str>,
         ctx: &ExtensionContext<'_>,
     ) -> ServerResult<()> {
         // Use BFS to analyze the query and count the number of nodes and the depth of the query
@@ -246,7 +303,7 @@ impl QueryLimitsChecker {
                 parent_node_count: 1,
             });
             cost.input_nodes += 1;
-            check_limits(limits, cost, Some(selection.pos), ctx)?;
+            check_limits(limits, cost, Some(selection.pos), operation, ctx)?;
         }
 
         // Track the number of nodes at first level if any
@@ -255,7 +312,7 @@ impl QueryLimitsChecker {
         while !que.is_empty() {
             // Signifies the start of a new level
             cost.depth += 1;
-            check_limits(limits, cost, None, ctx)?;
+            check_limits(limits, cost, None, operation, ctx)?;
             while level_len > 0 {
                 // Ok to unwrap since we checked for empty queue
                 // and level_len > 0
@@ -282,7 +339,7 @@ impl QueryLimitsChecker {
                                 parent_node_count: current_count,
                             });
                             cost.input_nodes += 1;
-                            check_limits(limits, cost, Some(field_sel.pos), ctx)?;
+                            check_limits(limits, cost, Some(field_sel.pos), operation, ctx)?;
                         }
                     }
 
@@ -309,7 +366,7 @@ impl QueryLimitsChecker {
                                 parent_node_count,
                             });
                             cost.input_nodes += 1;
-                            check_limits(limits, cost, Some(selection.pos), ctx)?;
+                            check_limits(limits, cost, Some(selection.pos), operation, ctx)?;
                         }
                     }
 
@@ -321,7 +378,7 @@ impl QueryLimitsChecker {
                                 parent_node_count,
                             });
                             cost.input_nodes += 1;
-                            check_limits(limits, cost, Some(selection.pos), ctx)?;
+                            check_limits(limits, cost, Some(selection.pos), operation, ctx)?;
                         }
                     }
                 }
@@ -334,26 +391,47 @@ impl QueryLimitsChecker {
     }
 }
 
+/// Describes an operation in errors, by its name and, if it is part of a batch, the position of its
+/// request in the batch.
+fn describe_operation(name: Option<&Name>, batch: Option<&BatchBudget>) -> String {
+    let operation = match name {
+        Some(name) => format!("operation `{name}`"),
+        None => "anonymous operation".to_string(),
+    };
+    match batch {
+        Some(batch) => format!(
+            "{operation} of the request at index {} in the batch",
+            batch.index
+        ),
+        None => operation,
+    }
+}
+
 fn check_limits(
     limits: &Limits,
     cost: &ComponentCost,
     pos: Option<Pos>,
+    operation: Option<&str>,
     ctx: &ExtensionContext<'_>,
 ) -> ServerResult<()> {
     let query_id: &Uuid = ctx.data_unchecked();
     let session_id: &SocketAddr = ctx.data_unchecked();
     let error_code = code::BAD_USER_INPUT;
+    // Names the operation that exceeded a limit shared with other operations.
+    let attribution = operation
+        .map(|operation| format!(", exceeded by {operation}"))
+        .unwrap_or_default();
     if cost.input_nodes > limits.max_query_nodes {
         info!(
             query_id = %query_id,
             session_id = %session_id,
             error_code,
-            "Query has too many nodes: {}", cost.input_nodes
+            "Query has too many nodes: {}{attribution}", cost.input_nodes
         );
         return Err(graphql_error_at_pos(
             error_code,
             format!(
-                "Query has too many nodes {}. The maximum allowed is {}",
+                "Query has too many nodes {}. The maximum allowed is {}{attribution}",
                 cost.input_nodes, limits.max_query_nodes
             ),
             pos.unwrap_or_default(),
@@ -365,12 +443,12 @@ fn check_limits(
             query_id = %query_id,
             session_id = %session_id,
             error_code,
-            "Query has too many levels of nesting: {}", cost.depth
+            "Query has too many levels of nesting: {}{attribution}", cost.depth
         );
         return Err(graphql_error_at_pos(
             error_code,
             format!(
-                "Query has too many levels of nesting {}. The maximum allowed is {}",
+                "Query has too many levels of nesting {}. The maximum allowed is {}{attribution}",
                 cost.depth, limits.max_query_depth
             ),
             pos.unwrap_or_default(),
@@ -382,13 +460,13 @@ fn check_limits(
             query_id = %query_id,
             session_id = %session_id,
             error_code,
-            "Query will result in too many output nodes: {}",
+            "Query will result in too many output nodes: {}{attribution}",
             cost.output_nodes
         );
         return Err(graphql_error_at_pos(
             error_code,
                 format!(
-                "Query will result in too many output nodes. The maximum allowed is {}, estimated {}",
+                "Query will result in too many output nodes. The maximum allowed is {}, estimated {}{attribution}",
                 limits.max_output_nodes, cost.output_nodes
             ),
             pos.unwrap_or_default(),
@@ -0,0 +1,483 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery},
+    parser::types::ExecutableDocument,
+    ServerResult, Variables,
+};
+use once_cell::sync::Lazy;
+use redis::Script;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    config::{RateLimitsConfig, StoreBackend},
+    error::{code, graphql_error},
+    metrics::Metrics,
+    redis_store::RedisStore,
+    usage::{ApiKey, RequestCost},
+};
+
+/// The number of quota buckets (up to one per quota of each API key) to keep in memory, before
+/// forgetting the buckets that have not been used recently.
+const MAX_TRACKED_BUCKETS: usize = 20_000;
+
+/// The period over which quotas are replenished.
+const QUOTA_PERIOD: Duration = Duration::from_secs(60);
+
+/// Label used in metrics for API keys that do not have their own quota configured, to bound the
+/// number of metrics reported.
+const OTHER_API_KEYS: &str = "other";
+
+/// Takes `ARGV[2]` tokens from the bucket at `KEYS[1]`, which holds up to `ARGV[1]` tokens and is
+/// refilled with that many tokens every `ARGV[3]` milliseconds, returning whether it held that
+/// many. Buckets are refilled according to the clock of the Redis instance, so that all instances
+/// of the service agree on it.
+static TAKE_TOKENS: Lazy<Script> = Lazy::new(|| {
+    Script::new(
+        r#"
+        local capacity = tonumber(ARGV[1])
+        local amount = tonumber(ARGV[2])
+        local period = tonumber(ARGV[3])
+        local time = redis.call('TIME')
+        local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+        local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'refilled_at')
+        local tokens = tonumber(bucket[1]) or capacity
+        local refilled_at = tonumber(bucket[2]) or now
+        local elapsed = math.max(0, now - refilled_at)
+        tokens = math.min(capacity, tokens + capacity * elapsed / period)
+
+        local taken = tokens >= amount
+        if taken then
+            tokens = tokens - amount
+        end
+
+        redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'refilled_at', now)
+        -- A bucket that is not used for a whole period is full again, so it can be forgotten.
+        redis.call('PEXPIRE', KEYS[1], period)
+        if taken then
+            return 1
+        end
+        return 0
+        "#,
+    )
+});
+
+/// Enforces quotas on the requests made with each API key, using token buckets that are
+/// replenished continuously over the quota period. Buckets are kept in the memory of each instance
+/// of the service, or in Redis, to share quotas between instances, depending on the configured
+/// backend. Requests are let through if their usage cannot be tracked because Redis is not
+/// available.
+///
+/// The request quota is checked before the query is parsed. The output node quota is checked once
+/// the query limits checker has estimated the cost of the query, so this extension must be
+/// registered before the `QueryLimitsChecker`.
+#[derive(Clone)]
+pub(crate) struct RateLimiter {
+    config: Arc<RateLimitsConfig>,
+    store: Arc<dyn QuotaStore>,
+}
+
+/// One of the quotas of an API key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum QuotaKind {
+    Requests,
+    OutputNodes,
+}
+
+/// Where the token buckets of API keys are kept.
+#[async_trait::async_trait]
+trait QuotaStore: Send + Sync {
+    /// Take `amount` tokens from `api_key`'s bucket for its `kind` quota, which holds up to
+    /// `capacity` tokens, returning whether the bucket held that many.
+    async fn take(
+        &self,
+        api_key: &str,
+        kind: QuotaKind,
+        capacity: u64,
+        amount: u64,
+        now: Instant,
+    ) -> anyhow::Result<bool>;
+}
+
+/// Keeps buckets in the memory of this instance of the service.
+#[derive(Default)]
+struct MemoryQuotaStore {
+    buckets: Mutex<HashMap<(String, QuotaKind), TokenBucket>>,
+}
+
+/// Keeps buckets in Redis, where they are shared by all instances of the service. `now` is
+/// ignored, as buckets are refilled according to the clock of the Redis instance.
+struct RedisQuotaStore(RedisStore);
+
+/// A bucket that holds up to `capacity` tokens, refilled at a rate of `capacity` tokens per quota
+/// period.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: RateLimitsConfig) -> Self {
+        let store: Arc<dyn QuotaStore> = match &config.backend {
+            StoreBackend::Memory => Arc::new(MemoryQuotaStore::default()),
+            StoreBackend::Redis(url) => Arc::new(RedisQuotaStore(
+                RedisStore::new(url, "rate-limits")
+                    .expect("Redis URL is validated when the config is read"),
+            )),
+        };
+
+        Self {
+            config: Arc::new(config),
+            store,
+        }
+    }
+
+    /// Take a request from `api_key`'s request quota, returning whether it was available.
+    async fn take_request(&self, api_key: &str, now: Instant) -> Result<(), QuotaKind> {
+        let capacity = self.config.quota(api_key).requests_per_minute;
+        self.take(api_key, QuotaKind::Requests, capacity, 1, now)
+            .await
+    }
+
+    /// Take `output_nodes` from `api_key`'s output node quota, returning whether they were
+    /// available.
+    async fn take_output_nodes(
+        &self,
+        api_key: &str,
+        output_nodes: u64,
+        now: Instant,
+    ) -> Result<(), QuotaKind> {
+        let capacity = self.config.quota(api_key).output_nodes_per_minute;
+        self.take(api_key, QuotaKind::OutputNodes, capacity, output_nodes, now)
+            .await
+    }
+
+    /// Take `amount` from `api_key`'s `kind` quota, if it is limited to `capacity`.
+    async fn take(
+        &self,
+        api_key: &str,
+        kind: QuotaKind,
+        capacity: Option<u64>,
+        amount: u64,
+        now: Instant,
+    ) -> Result<(), QuotaKind> {
+        let Some(capacity) = capacity else {
+            return Ok(());
+        };
+
+        match self.store.take(api_key, kind, capacity, amount, now).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(kind),
+            Err(e) => {
+                warn!(
+                    "Failed to track the {} quota of an API key: {e}",
+                    kind.name()
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// The label to report metrics for `api_key` under.
+    fn metrics_label<'k>(&self, api_key: &'k str) -> &'k str {
+        if self.config.keys.contains_key(api_key) {
+            api_key
+        } else {
+            OTHER_API_KEYS
+        }
+    }
+}
+
+impl QuotaKind {
+    fn name(&self) -> &'static str {
+        match self {
+            QuotaKind::Requests => "requests",
+            QuotaKind::OutputNodes => "output_nodes",
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl QuotaStore for MemoryQuotaStore {
+    async fn take(
+        &self,
+        api_key: &str,
+        kind: QuotaKind,
+        capacity: u64,
+        amount: u64,
+        now: Instant,
+    ) -> anyhow::Result<bool> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let key = (api_key.to_string(), kind);
+
+        // Buckets that have been completely refilled behave the same as new buckets, so they can
+        // be forgotten to make room for other keys.
+        if buckets.len() >= MAX_TRACKED_BUCKETS && !buckets.contains_key(&key) {
+            buckets.retain(|_, b| !b.is_full(now));
+        }
+
+        let bucket = buckets
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new(capacity, now));
+        Ok(bucket.take(amount as f64, now))
+    }
+}
+
+#[async_trait::async_trait]
+impl QuotaStore for RedisQuotaStore {
+    async fn take(
+        &self,
+        api_key: &str,
+        kind: QuotaKind,
+        capacity: u64,
+        amount: u64,
+        _now: Instant,
+    ) -> anyhow::Result<bool> {
+        let mut connection = self.0.connection().await?;
+        let taken = TAKE_TOKENS
+            .key(self.0.key(&format!("{}:{api_key}", kind.name())))
+            .arg(capacity)
+            .arg(amount)
+            .arg(QUOTA_PERIOD.as_millis() as u64)
+            .invoke_async(&mut connection)
+            .await?;
+        Ok(taken)
+    }
+}
+
+impl TokenBucket {
+    fn new(capacity: u64, now: Instant) -> Self {
+        Self {
+            capacity: capacity as f64,
+            tokens: capacity as f64,
+            refilled_at: now,
+        }
+    }
+
+    /// Take `amount` tokens from the bucket, if it holds that many.
+    fn take(&mut self, amount: f64, now: Instant) -> bool {
+        self.refill(now);
+        if self.tokens < amount {
+            return false;
+        }
+
+        self.tokens -= amount;
+        true
+    }
+
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.capacity
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        let refill = self.capacity * elapsed.as_secs_f64() / QUOTA_PERIOD.as_secs_f64();
+        self.tokens = (self.tokens + refill).min(self.capacity);
+        self.refilled_at = now;
+    }
+}
+
+impl ExtensionFactory for RateLimiter {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for RateLimiter {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let Some(ApiKey(api_key)) = ctx.data_opt::<ApiKey>() else {
+            return next.run(ctx, query, variables).await;
+        };
+
+        let result = match self.take_request(api_key, Instant::now()).await {
+            Ok(()) => Ok(next.run(ctx, query, variables).await?),
+            Err(exceeded) => Err(exceeded),
+        };
+
+        let result = match result {
+            Ok(doc) => {
+                let output_nodes = ctx
+                    .data_opt::<RequestCost>()
+                    .map_or(0, |cost| cost.usage().output_nodes);
+                self.take_output_nodes(api_key, output_nodes, Instant::now())
+                    .await
+                    .map(|()| doc)
+            }
+            Err(exceeded) => Err(exceeded),
+        };
+
+        let metrics: &Metrics = ctx.data_unchecked();
+        let label = self.metrics_label(api_key);
+        let outcome = result.as_ref().map_or_else(|e| e.name(), |_| "allowed");
+        metrics
+            .request_metrics
+            .rate_limited_requests
+            .with_label_values(&[label, outcome])
+            .inc();
+
+        result.map_err(|exceeded| {
+            let query_id: &Uuid = ctx.data_unchecked();
+            let session_id: &SocketAddr = ctx.data_unchecked();
+            info!(
+                query_id = %query_id,
+                session_id = %session_id,
+                error_code = code::TOO_MANY_REQUESTS,
+                "API key exceeded its {} quota",
+                exceeded.name(),
+            );
+
+            let quota = match exceeded {
+                QuotaKind::Requests => "requests",
+                QuotaKind::OutputNodes => "output nodes",
+            };
+            graphql_error(
+                code::TOO_MANY_REQUESTS,
+                format!("API key has exceeded its quota of {quota} per minute"),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::config::Quota;
+
+    use super::*;
+
+    fn limiter(requests: Option<u64>, output_nodes: Option<u64>) -> RateLimiter {
+        RateLimiter::new(RateLimitsConfig {
+            default: Quota {
+                requests_per_minute: requests,
+                output_nodes_per_minute: output_nodes,
+            },
+            keys: BTreeMap::from([(
+                "partner".to_string(),
+                Quota {
+                    requests_per_minute: Some(10),
+                    output_nodes_per_minute: None,
+                },
+            )]),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_request_quota_per_key() {
+        let limiter = limiter(Some(2), None);
+        let now = Instant::now();
+
+        assert_eq!(limiter.take_request("a", now).await, Ok(()));
+        assert_eq!(limiter.take_request("a", now).await, Ok(()));
+        assert_eq!(
+            limiter.take_request("a", now).await,
+            Err(QuotaKind::Requests)
+        );
+
+        // Other keys have their own quota.
+        assert_eq!(limiter.take_request("b", now).await, Ok(()));
+        for _ in 0..10 {
+            assert_eq!(limiter.take_request("partner", now).await, Ok(()));
+        }
+        assert_eq!(
+            limiter.take_request("partner", now).await,
+            Err(QuotaKind::Requests)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_quota_is_replenished() {
+        let limiter = limiter(Some(2), None);
+        let now = Instant::now();
+
+        assert_eq!(limiter.take_request("a", now).await, Ok(()));
+        assert_eq!(limiter.take_request("a", now).await, Ok(()));
+        assert_eq!(
+            limiter.take_request("a", now).await,
+            Err(QuotaKind::Requests)
+        );
+
+        // Half the quota is replenished after half the period.
+        let later = now + QUOTA_PERIOD / 2;
+        assert_eq!(limiter.take_request("a", later).await, Ok(()));
+        assert_eq!(
+            limiter.take_request("a", later).await,
+            Err(QuotaKind::Requests)
+        );
+
+        // The quota does not accumulate beyond its capacity.
+        let much_later = now + QUOTA_PERIOD * 10;
+        assert_eq!(limiter.take_request("a", much_later).await, Ok(()));
+        assert_eq!(limiter.take_request("a", much_later).await, Ok(()));
+        assert_eq!(
+            limiter.take_request("a", much_later).await,
+            Err(QuotaKind::Requests)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_output_node_quota() {
+        let limiter = limiter(None, Some(100));
+        let now = Instant::now();
+
+        assert_eq!(limiter.take_output_nodes("a", 60, now).await, Ok(()));
+        assert_eq!(
+            limiter.take_output_nodes("a", 60, now).await,
+            Err(QuotaKind::OutputNodes)
+        );
+        assert_eq!(limiter.take_output_nodes("a", 40, now).await, Ok(()));
+
+        // The partner's quota does not limit output nodes.
+        assert_eq!(
+            limiter.take_output_nodes("partner", 1_000, now).await,
+            Ok(())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_redis_does_not_limit_requests() {
+        let limiter = RateLimiter::new(RateLimitsConfig {
+            default: Quota {
+                requests_per_minute: Some(1),
+                output_nodes_per_minute: Some(1),
+            },
+            // Nothing listens on this port.
+            backend: StoreBackend::Redis("redis://127.0.0.1:1".to_string()),
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        for _ in 0..2 {
+            assert_eq!(limiter.take_request("a", now).await, Ok(()));
+            assert_eq!(limiter.take_output_nodes("a", 10, now).await, Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_metrics_label() {
+        let limiter = limiter(Some(1), None);
+        assert_eq!(limiter.metrics_label("partner"), "partner");
+        assert_eq!(limiter.metrics_label("a"), OTHER_API_KEYS);
+    }
+}
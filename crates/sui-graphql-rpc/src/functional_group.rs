@@ -97,7 +97,8 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
             (("Query", "protocolConfig"), G::SystemState),
             (("Query", "resolveSuinsAddress"), G::NameService),
             (("Subscription", "events"), G::Subscriptions),
-            (("Subscription", "transactions"), G::Subscriptions),
+            (("Subscription", "newCheckpoints"), G::Subscriptions),
+            (("Subscription", "transactionEffects"), G::Subscriptions),
             (("SystemStateSummary", "safeMode"), G::SystemState),
             (("SystemStateSummary", "storageFund"), G::SystemState),
             (("SystemStateSummary", "systemParameters"), G::SystemState),
@@ -121,6 +122,7 @@ mod tests {
     use async_graphql::registry::Registry;
     use async_graphql::OutputType;
 
+    use crate::subscription::Subscription;
     use crate::types::query::Query;
 
     use super::*;
@@ -132,14 +134,13 @@ mod tests {
     fn test_groups_match_schema() {
         let mut registry = Registry::default();
         Query::create_type_info(&mut registry);
+        Subscription::create_type_info(&mut registry);
 
         let unimplemented = BTreeSet::from_iter([
             ("Checkpoint", "addressMetrics"),
             ("Epoch", "protocolConfig"),
             ("Query", "moveCallMetrics"),
             ("Query", "networkMetrics"),
-            ("Subscription", "events"),
-            ("Subscription", "transactions"),
         ]);
 
         for (type_, field) in &unimplemented {
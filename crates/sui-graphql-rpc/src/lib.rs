@@ -14,6 +14,9 @@ pub(crate) mod functional_group;
 mod metrics;
 mod mutation;
 pub(crate) mod raw_query;
+mod redis_store;
 pub mod server;
+mod subscription;
 pub mod test_infra;
 mod types;
+mod usage;
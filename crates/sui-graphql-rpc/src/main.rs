@@ -11,6 +11,7 @@ use sui_graphql_rpc::config::{
 };
 use sui_graphql_rpc::server::builder::export_schema;
 use sui_graphql_rpc::server::graphiql_server::start_graphiql_server;
+use sui_graphql_rpc::server::mock::start_mock_server;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
 
@@ -86,14 +87,18 @@ async fn main() {
         Command::StartServer {
             ide_title,
             db_url,
+            db_replica_urls,
             port,
             host,
             config,
             node_rpc_url,
             prom_host,
             prom_port,
+            mock,
+            mock_seed,
         } => {
-            let connection = ConnectionConfig::new(port, host, db_url, None, prom_host, prom_port);
+            let connection = ConnectionConfig::new(port, host, db_url, None, prom_host, prom_port)
+                .with_db_replica_urls(db_replica_urls);
             let service_config = service_config(config);
             let _guard = telemetry_subscribers::TelemetryConfig::new()
                 .with_env()
@@ -112,9 +117,15 @@ async fn main() {
 
             let cancellation_token_clone = cancellation_token.clone();
             let graphql_service_handle = tracker.spawn(async move {
-                start_graphiql_server(&server_config, &VERSION, cancellation_token_clone)
-                    .await
-                    .unwrap();
+                if mock {
+                    start_mock_server(&server_config, mock_seed, cancellation_token_clone)
+                        .await
+                        .unwrap();
+                } else {
+                    start_graphiql_server(&server_config, &VERSION, cancellation_token_clone)
+                        .await
+                        .unwrap();
+                }
             });
 
             // Wait for shutdown signal
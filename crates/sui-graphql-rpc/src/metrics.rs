@@ -7,8 +7,8 @@ use async_graphql::{PathSegment, ServerError};
 use prometheus::{
     register_gauge_with_registry, register_histogram_vec_with_registry,
     register_histogram_with_registry, register_int_counter_vec_with_registry,
-    register_int_counter_with_registry, Gauge, Histogram, HistogramVec, IntCounter, IntCounterVec,
-    Registry,
+    register_int_counter_with_registry, register_int_gauge_with_registry, Gauge, Histogram,
+    HistogramVec, IntCounter, IntCounterVec, IntGauge, Registry,
 };
 
 use crate::error::code;
@@ -35,6 +35,22 @@ const QUERY_PAYLOAD_SIZE_BUCKETS: &[f64] = &[
     10., 20., 50., 100., 200., 400., 800., 1200., 1600., 2400., 3200., 4800., 6400., 9600., 12800.,
     25600., 51200., 102400.,
 ];
+const RESPONSE_SIZE_BUCKETS: &[f64] = &[
+    256., 1024., 4096., 16384., 65536., 262144., 1048576., 2097152., 4194304., 8388608., 16777216.,
+    33554432., 67108864.,
+];
+const REQUEST_PRICE_BUCKETS: &[f64] = &[
+    1.,
+    10.,
+    100.,
+    1_000.,
+    10_000.,
+    100_000.,
+    1_000_000.,
+    10_000_000.,
+    100_000_000.,
+    1_000_000_000.,
+];
 const DB_QUERY_COST_BUCKETS: &[f64] = &[
     1., 2., 4., 8., 12., 16., 24., 32., 48., 64., 96., 128., 256., 512., 1024.,
 ];
@@ -43,6 +59,7 @@ const DB_QUERY_COST_BUCKETS: &[f64] = &[
 pub(crate) struct Metrics {
     pub db_metrics: Arc<DBMetrics>,
     pub request_metrics: Arc<RequestMetrics>,
+    pub subscription_metrics: Arc<SubscriptionMetrics>,
 }
 
 #[derive(Clone)]
@@ -51,6 +68,10 @@ pub(crate) struct DBMetrics {
     pub db_fetches: IntCounterVec,
     /// The fetch latency grouped by result (success or error)
     pub db_fetch_latency: HistogramVec,
+    /// The number of fetches from each DB replica, grouped by result (success or error)
+    pub db_replica_fetches: IntCounterVec,
+    /// The number of times each DB replica was taken out of rotation
+    pub db_replica_ejections: IntCounterVec,
     // TODO make this work, blocked by pg.rs (unclear if to use log function or smth else)
     pub _db_query_cost: Histogram,
     // TODO determine if we want this metric, and implement it
@@ -69,6 +90,8 @@ pub(crate) struct RequestMetrics {
     pub query_payload_too_large_size: Histogram,
     /// The size (in bytes) of the payload
     pub query_payload_size: Histogram,
+    /// The price of the request, according to the pricing config
+    pub request_price: Histogram,
     /// The time it takes to validate the query
     pub query_validation_latency: Histogram,
     /// The time it takes for the GraphQL service to execute the request
@@ -81,16 +104,48 @@ pub(crate) struct RequestMetrics {
     pub num_queries_top_level: IntCounterVec,
     /// Total inflight requests
     pub inflight_requests: Gauge,
+    /// Number of queries rejected because they are not registered as persisted queries
+    pub persisted_query_rejections: IntCounter,
+    /// Number of requests made with an API key, by API key and whether they were allowed or
+    /// which quota they exceeded
+    pub rate_limited_requests: IntCounterVec,
+    /// Number of queries served from the response cache
+    pub response_cache_hits: IntCounter,
+    /// Number of cacheable queries that were not found in the response cache
+    pub response_cache_misses: IntCounter,
+    /// The size (in bytes) of the response, before it is compressed
+    pub response_size: Histogram,
+    /// The size (in bytes) of the response that is higher than the maximum
+    pub response_too_large_size: Histogram,
+    /// The size (in bytes) of the response sent to the client, by content encoding
+    pub response_encoded_size: HistogramVec,
+}
+
+#[derive(Clone)]
+pub(crate) struct SubscriptionMetrics {
+    /// Number of subscriptions started, by subscription field
+    pub subscriptions_started: IntCounterVec,
+    /// Number of subscriptions rejected because their connection had too many subscriptions
+    pub subscriptions_rejected: IntCounter,
+    /// Number of subscriptions ended because their subscriber fell too far behind, by subscription
+    /// field
+    pub subscriptions_lagged: IntCounterVec,
+    /// Number of subscriptions currently streaming data
+    pub active_subscriptions: IntGauge,
+    /// Number of entries streamed to subscribers, by subscription field
+    pub subscription_items: IntCounterVec,
 }
 
 impl Metrics {
     pub(crate) fn new(registry: &Registry) -> Self {
         let db_metrics = DBMetrics::new(registry);
         let request_metrics = RequestMetrics::new(registry);
+        let subscription_metrics = SubscriptionMetrics::new(registry);
 
         Self {
             db_metrics: Arc::new(db_metrics),
             request_metrics: Arc::new(request_metrics),
+            subscription_metrics: Arc::new(subscription_metrics),
         }
     }
 
@@ -104,6 +159,23 @@ impl Metrics {
             .observe(time.as_secs_f64());
     }
 
+    /// Updates the metrics for a fetch from a specific DB replica. Failing to connect to a replica
+    /// counts as an error.
+    pub(crate) fn observe_db_replica(&self, replica: &str, succeeded: bool) {
+        let label = if succeeded { "success" } else { "error" };
+        self.db_metrics
+            .db_replica_fetches
+            .with_label_values(&[replica, label])
+            .inc();
+    }
+
+    pub(crate) fn inc_db_replica_ejections(&self, replica: &str) {
+        self.db_metrics
+            .db_replica_ejections
+            .with_label_values(&[replica])
+            .inc();
+    }
+
     /// The total time needed for handling the query
     pub(crate) fn query_latency(&self, time: Duration) {
         self.request_metrics
@@ -162,6 +234,20 @@ impl DBMetrics {
                 registry,
             )
             .unwrap(),
+            db_replica_fetches: register_int_counter_vec_with_registry!(
+                "db_replica_fetches",
+                "The number of fetches from each DB replica, grouped by result (success or error)",
+                &["replica", "type"],
+                registry
+            )
+            .unwrap(),
+            db_replica_ejections: register_int_counter_vec_with_registry!(
+                "db_replica_ejections",
+                "The number of times each DB replica was taken out of rotation",
+                &["replica"],
+                registry
+            )
+            .unwrap(),
             _db_query_cost: register_histogram_with_registry!(
                 "db_query_cost",
                 "Cost of a DB query",
@@ -218,6 +304,13 @@ impl RequestMetrics {
                 registry,
             )
             .unwrap(),
+            request_price: register_histogram_with_registry!(
+                "request_price",
+                "Price of the request, according to the pricing config",
+                REQUEST_PRICE_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
             query_validation_latency: register_histogram_with_registry!(
                 "query_validation_latency",
                 "The time to validate the query",
@@ -258,6 +351,94 @@ impl RequestMetrics {
                 registry
             )
             .unwrap(),
+            persisted_query_rejections: register_int_counter_with_registry!(
+                "persisted_query_rejections",
+                "Number of queries rejected for not being registered as persisted queries",
+                registry
+            )
+            .unwrap(),
+            rate_limited_requests: register_int_counter_vec_with_registry!(
+                "rate_limited_requests",
+                "Number of requests made with each API key, by whether they were allowed or which \
+                 quota they exceeded",
+                &["api_key", "outcome"],
+                registry
+            )
+            .unwrap(),
+            response_cache_hits: register_int_counter_with_registry!(
+                "response_cache_hits",
+                "Number of queries served from the response cache",
+                registry
+            )
+            .unwrap(),
+            response_cache_misses: register_int_counter_with_registry!(
+                "response_cache_misses",
+                "Number of cacheable queries that were not found in the response cache",
+                registry
+            )
+            .unwrap(),
+            response_size: register_histogram_with_registry!(
+                "response_size",
+                "Size of the response (bytes), before it is compressed",
+                RESPONSE_SIZE_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            response_too_large_size: register_histogram_with_registry!(
+                "response_too_large_size",
+                "Response size (bytes), that was rejected due to being larger than maximum",
+                RESPONSE_SIZE_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            response_encoded_size: register_histogram_vec_with_registry!(
+                "response_encoded_size",
+                "Size of the response (bytes) sent to the client, by content encoding",
+                &["encoding"],
+                RESPONSE_SIZE_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl SubscriptionMetrics {
+    pub(crate) fn new(registry: &Registry) -> Self {
+        Self {
+            subscriptions_started: register_int_counter_vec_with_registry!(
+                "subscriptions_started",
+                "Number of subscriptions started for each subscription field",
+                &["field"],
+                registry,
+            )
+            .unwrap(),
+            subscriptions_rejected: register_int_counter_with_registry!(
+                "subscriptions_rejected",
+                "Number of subscriptions rejected for exceeding the per-connection limit",
+                registry,
+            )
+            .unwrap(),
+            subscriptions_lagged: register_int_counter_vec_with_registry!(
+                "subscriptions_lagged",
+                "Number of subscriptions ended for falling too far behind the latest checkpoint",
+                &["field"],
+                registry,
+            )
+            .unwrap(),
+            active_subscriptions: register_int_gauge_with_registry!(
+                "active_subscriptions",
+                "Number of subscriptions that are streaming data at a moment in time",
+                registry,
+            )
+            .unwrap(),
+            subscription_items: register_int_counter_vec_with_registry!(
+                "subscription_items",
+                "Number of entries streamed for each subscription field",
+                &["field"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::types::service_status::{fullnode_client, mutations_enabled};
 use crate::types::transaction_block_effects::TransactionBlockEffectsKind;
 use crate::{
     error::Error, types::execution_result::ExecutionResult,
@@ -10,7 +11,6 @@ use async_graphql::*;
 use fastcrypto::encoding::Encoding;
 use fastcrypto::{encoding::Base64, traits::ToFromBytes};
 use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
-use sui_sdk::SuiClient;
 use sui_types::effects::TransactionEffects as NativeTransactionEffects;
 use sui_types::event::Event as NativeEvent;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
@@ -33,22 +33,28 @@ impl Mutation {
     /// There may be a delay between transaction finality and when GraphQL requests (including the
     /// request that issued the transaction) reflect its effects. As a result, queries that depend
     /// on indexing the state of the chain (e.g. contents of output objects, address-level balance
-    /// information at the time of the transaction), must wait for indexing to catch up by polling
-    /// for the transaction digest using `Query.transactionBlock`.
+    /// information at the time of the transaction), must wait for indexing to catch up, either by
+    /// polling for the transaction digest using `Query.transactionBlock`, or by passing the digest
+    /// in the `x-sui-rpc-wait-for-transaction` header of follow-up requests. Such requests are only
+    /// served once their view of the chain includes the transaction, waiting for at most
+    /// `ServiceConfig.maxWaitForTransactionMs`.
+    ///
+    /// Fails with a `SERVICE_READ_ONLY` error if the service is not connected to a fullnode to
+    /// execute the transaction through.
+    #[graphql(visible = "mutations_enabled")]
     async fn execute_transaction_block(
         &self,
         ctx: &Context<'_>,
         tx_bytes: String,
         signatures: Vec<String>,
     ) -> Result<ExecutionResult> {
-        let sui_sdk_client: &Option<SuiClient> = ctx
-            .data()
-            .map_err(|_| Error::Internal("Unable to fetch Sui SDK client".to_string()))
-            .extend()?;
-        let sui_sdk_client = sui_sdk_client
-            .as_ref()
-            .ok_or_else(|| Error::Internal("Sui SDK client not initialized".to_string()))
-            .extend()?;
+        if !mutations_enabled(ctx) {
+            return Err(Error::ReadOnly(
+                "This service is read-only, mutations are disabled".to_string(),
+            )
+            .extend());
+        }
+        let sui_sdk_client = fullnode_client(ctx).extend()?;
         let tx_data = bcs::from_bytes(
             &Base64::decode(&tx_bytes)
                 .map_err(|e| {
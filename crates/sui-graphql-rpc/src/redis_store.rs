@@ -0,0 +1,70 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::Arc, time::Duration};
+
+use redis::{aio::ConnectionManager, Client, RedisResult};
+use tokio::sync::OnceCell;
+
+/// Prefix of the keys the service stores in Redis, so that the Redis instance can be shared with
+/// other services.
+const KEY_PREFIX: &str = "sui-graphql-rpc";
+
+/// How long to wait to connect to Redis, or for it to respond to a command, before giving up, so
+/// that an unavailable Redis instance does not hold up requests.
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How many times to retry connecting to Redis, before giving up.
+const CONNECTION_RETRIES: usize = 1;
+
+/// A Redis instance holding state that is shared by the instances of the service (API key usage,
+/// cached responses), under keys in its own `namespace`. The connection is made the first time it
+/// is needed, and re-established whenever it is lost.
+#[derive(Clone)]
+pub(crate) struct RedisStore {
+    client: Client,
+    namespace: &'static str,
+    connection: Arc<OnceCell<ConnectionManager>>,
+}
+
+impl RedisStore {
+    pub(crate) fn new(url: &str, namespace: &'static str) -> RedisResult<Self> {
+        Ok(Self {
+            client: Client::open(url)?,
+            namespace,
+            connection: Arc::new(OnceCell::new()),
+        })
+    }
+
+    /// The Redis key that `key` is stored under.
+    pub(crate) fn key(&self, key: &str) -> String {
+        format!("{KEY_PREFIX}:{}:{key}", self.namespace)
+    }
+
+    pub(crate) async fn connection(&self) -> RedisResult<ConnectionManager> {
+        self.connection
+            .get_or_try_init(|| {
+                ConnectionManager::new_with_backoff_and_timeouts(
+                    self.client.clone(),
+                    /* exponent_base */ 2,
+                    /* factor */ 100,
+                    CONNECTION_RETRIES,
+                    TIMEOUT,
+                    TIMEOUT,
+                )
+            })
+            .await
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_namespaced() {
+        let store = RedisStore::new("redis://127.0.0.1:6379", "rate-limits").unwrap();
+        assert_eq!(store.key("a"), "sui-graphql-rpc:rate-limits:a");
+    }
+}
@@ -2,26 +2,39 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::{
-    ConnectionConfig, ServiceConfig, Version, MAX_CONCURRENT_REQUESTS,
+    ConnectionConfig, Limits, ServiceConfig, Version, MAX_CONCURRENT_REQUESTS,
     RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
 };
 use crate::consistency::CheckpointViewedAt;
 use crate::context_data::package_cache::DbPackageStore;
-use crate::data::Db;
+use crate::data::{replicas::Replicas, Db, QueryExecutor};
 use crate::metrics::Metrics;
 use crate::mutation::Mutation;
+use crate::server::compression::{
+    check_response_size_middleware, compression_layer, record_encoded_size_middleware,
+    ResponseSizeLimit,
+};
+use crate::server::health::{HealthReport, Heartbeat, TaskHeartbeats};
+use crate::server::response_cache::ResponseCache;
+use crate::subscription::{ConnectionSubscriptions, Subscription};
 use crate::types::checkpoint::Checkpoint;
+use crate::types::digest::Digest;
 use crate::types::move_object::IMoveObject;
 use crate::types::object::IObject;
 use crate::types::owner::IOwner;
+use crate::types::transaction_block::TransactionBlock;
+use crate::usage::{now_ms, ApiKey, RequestCost, Usage, UsageReporter, UsageTracker};
 use crate::{
     config::ServerConfig,
     context_data::db_data_provider::PgManager,
-    error::Error,
+    error::{code, graphql_error, Error},
     extensions::{
         feature_gate::FeatureGate,
+        field_aliases::FieldAliases,
         logger::Logger,
-        query_limits_checker::{QueryLimitsChecker, ShowUsage},
+        persisted_queries::PersistedQueries,
+        query_limits_checker::{BatchBudget, QueryLimitsChecker, ShowUsage},
+        rate_limiter::RateLimiter,
         timeout::Timeout,
     },
     server::version::{check_version_middleware, set_version_middleware},
@@ -30,16 +43,20 @@ use crate::{
 use async_graphql::dataloader::DataLoader;
 use async_graphql::extensions::ApolloTracing;
 use async_graphql::extensions::Tracing;
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
 use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
-use async_graphql::{EmptySubscription, ServerError};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql::{BatchRequest, BatchResponse, Data, Response, ServerError};
+use async_graphql_axum::{GraphQLBatchRequest, GraphQLProtocol, GraphQLResponse, GraphQLWebSocket};
+use axum::extract::ws::WebSocketUpgrade;
 use axum::extract::FromRef;
 use axum::extract::{connect_info::IntoMakeServiceWithConnectInfo, ConnectInfo, State};
-use axum::http::{HeaderMap, StatusCode};
+use axum::http::HeaderMap;
 use axum::middleware::{self};
 use axum::response::IntoResponse;
-use axum::routing::{post, MethodRouter, Route};
+use axum::routing::{get, post, MethodRouter, Route};
+use axum::Json;
 use axum::{headers::Header, Router};
+use diesel::OptionalExtension;
 use http::{HeaderValue, Method, Request};
 use hyper::server::conn::AddrIncoming as HyperAddrIncoming;
 use hyper::Body;
@@ -47,11 +64,15 @@ use hyper::Server as HyperServer;
 use mysten_metrics::spawn_monitored_task;
 use mysten_network::callback::{CallbackLayer, MakeCallbackHandler, ResponseHandler};
 use std::convert::Infallible;
-use std::net::TcpStream;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
 use std::sync::Arc;
+use std::time::Duration;
 use std::{any::Any, net::SocketAddr, time::Instant};
-use sui_graphql_rpc_headers::{LIMITS_HEADER, VERSION_HEADER};
+use sui_graphql_rpc_headers::{
+    API_KEY_HEADER, LIMITS_HEADER, VERSION_HEADER, WAIT_FOR_TRANSACTION_HEADER,
+};
+use sui_indexer::db::new_pg_connection_pool;
 use sui_package_resolver::{PackageStoreWithLruCache, Resolver};
 use sui_sdk::SuiClientBuilder;
 use tokio::join;
@@ -62,12 +83,19 @@ use tower_http::cors::{AllowOrigin, CorsLayer};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+/// How often a request waiting for a transaction checks whether it has been indexed.
+const WAIT_FOR_TRANSACTION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the health check waits for the DB to respond before reporting it as unhealthy.
+const HEALTH_CHECK_DB_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub(crate) struct Server {
     pub server: HyperServer<HyperAddrIncoming, IntoMakeServiceWithConnectInfo<Router, SocketAddr>>,
     /// The following fields are internally used for background tasks
     checkpoint_watermark: CheckpointWatermark,
     state: AppState,
     db_reader: Db,
+    usage_reporter: Option<UsageReporter>,
 }
 
 impl Server {
@@ -82,6 +110,10 @@ impl Server {
         let watermark_task = {
             let metrics = self.state.metrics.clone();
             let sleep_ms = self.state.service.background_tasks.watermark_update_ms;
+            let heartbeat = self
+                .state
+                .heartbeats
+                .register("watermark_update", Duration::from_millis(sleep_ms));
             let cancellation_token = self.state.cancellation_token.clone();
             info!("Starting watermark update task");
             spawn_monitored_task!(async move {
@@ -90,12 +122,26 @@ impl Server {
                     self.checkpoint_watermark,
                     metrics,
                     tokio::time::Duration::from_millis(sleep_ms),
+                    heartbeat,
                     cancellation_token,
                 )
                 .await;
             })
         };
 
+        // A handle that spawns a background task to periodically write out the usage of the
+        // service by each API key, if usage is being tracked.
+        let usage_task = self.usage_reporter.map(|reporter| {
+            let interval =
+                Duration::from_millis(self.state.service.background_tasks.usage_report_interval_ms);
+            let heartbeat = self.state.heartbeats.register("usage_report", interval);
+            let cancellation_token = self.state.cancellation_token.clone();
+            info!("Starting usage report task");
+            spawn_monitored_task!(async move {
+                reporter.run(interval, heartbeat, cancellation_token).await;
+            })
+        });
+
         let server_task = {
             info!("Starting graphql service");
             let cancellation_token = self.state.cancellation_token.clone();
@@ -110,9 +156,14 @@ impl Server {
             })
         };
 
-        // Wait for both tasks to complete. This ensures that the service doesn't fully shut down
-        // until both the background task and the server have completed their shutdown processes.
-        let _ = join!(watermark_task, server_task);
+        // Wait for all tasks to complete. This ensures that the service doesn't fully shut down
+        // until the background tasks and the server have completed their shutdown processes.
+        let usage_task = async move {
+            if let Some(usage_task) = usage_task {
+                let _ = usage_task.await;
+            }
+        };
+        let _ = join!(watermark_task, usage_task, server_task);
 
         Ok(())
     }
@@ -120,9 +171,10 @@ impl Server {
 
 pub(crate) struct ServerBuilder {
     state: AppState,
-    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, Subscription>,
     router: Option<Router>,
     db_reader: Option<Db>,
+    usage_reporter: Option<UsageReporter>,
 }
 
 #[derive(Clone)]
@@ -131,6 +183,7 @@ pub(crate) struct AppState {
     service: ServiceConfig,
     metrics: Metrics,
     cancellation_token: CancellationToken,
+    heartbeats: TaskHeartbeats,
     pub version: Version,
 }
 
@@ -152,6 +205,7 @@ impl AppState {
             service,
             metrics,
             cancellation_token,
+            heartbeats: TaskHeartbeats::default(),
             version,
         }
     }
@@ -176,6 +230,7 @@ impl ServerBuilder {
             schema: schema_builder(),
             router: None,
             db_reader: None,
+            usage_reporter: None,
         }
     }
 
@@ -196,7 +251,7 @@ impl ServerBuilder {
         self
     }
 
-    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
+    fn build_schema(self) -> Schema<Query, Mutation, Subscription> {
         self.schema.finish()
     }
 
@@ -206,15 +261,17 @@ impl ServerBuilder {
         self,
     ) -> (
         String,
-        Schema<Query, Mutation, EmptySubscription>,
+        Schema<Query, Mutation, Subscription>,
         Db,
         Router,
+        Option<UsageReporter>,
     ) {
         let address = self.address();
         let ServerBuilder {
             schema,
             db_reader,
             router,
+            usage_reporter,
             ..
         } = self;
         (
@@ -222,6 +279,7 @@ impl ServerBuilder {
             schema.finish(),
             db_reader.expect("DB reader not initialized"),
             router.expect("Router not initialized"),
+            usage_reporter,
         )
     }
 
@@ -230,8 +288,15 @@ impl ServerBuilder {
             let router: Router = Router::new()
                 .route("/", post(graphql_handler))
                 .route("/graphql", post(graphql_handler))
-                .route("/health", axum::routing::get(health_checks))
+                .route("/health", axum::routing::get(health_check))
                 .with_state(self.state.clone())
+                .route_layer(middleware::from_fn_with_state(
+                    ResponseSizeLimit {
+                        max_response_size: self.state.service.limits.max_response_size,
+                        metrics: self.state.metrics.clone(),
+                    },
+                    check_response_size_middleware,
+                ))
                 .route_layer(middleware::from_fn_with_state(
                     self.state.version,
                     set_version_middleware,
@@ -240,6 +305,11 @@ impl ServerBuilder {
                     self.state.version,
                     check_version_middleware,
                 ))
+                .route_layer(compression_layer(&self.state.service.compression))
+                .route_layer(middleware::from_fn_with_state(
+                    self.state.metrics.clone(),
+                    record_encoded_size_middleware,
+                ))
                 .route_layer(CallbackLayer::new(MetricsMakeCallbackHandler {
                     metrics: self.state.metrics.clone(),
                 }));
@@ -266,7 +336,7 @@ impl ServerBuilder {
         self
     }
 
-    fn cors() -> Result<CorsLayer, Error> {
+    pub(crate) fn cors() -> Result<CorsLayer, Error> {
         let acl = match std::env::var("ACCESS_CONTROL_ALLOW_ORIGIN") {
             Ok(value) => {
                 let allow_hosts = value
@@ -293,6 +363,8 @@ impl ServerBuilder {
                 hyper::header::CONTENT_TYPE,
                 VERSION_HEADER.clone(),
                 LIMITS_HEADER.clone(),
+                WAIT_FOR_TRANSACTION_HEADER.clone(),
+                API_KEY_HEADER.clone(),
             ]);
         Ok(cors)
     }
@@ -300,14 +372,32 @@ impl ServerBuilder {
     /// Consumes the `ServerBuilder` to create a `Server` that can be run.
     pub fn build(self) -> Result<Server, Error> {
         let state = self.state.clone();
-        let (address, schema, db_reader, router) = self.build_components();
 
-        // Initialize the checkpoint watermark for the background task to update.
+        // Initialize the checkpoint watermark for the background task to update. Subscriptions
+        // follow it to find out when there is new data to stream.
         let checkpoint_watermark = CheckpointWatermark(Arc::new(AtomicU64::new(0)));
 
+        let (address, schema, db_reader, router, usage_reporter) = self
+            .context_data(checkpoint_watermark.clone())
+            .build_components();
+
+        // Usage is only tracked if there is somewhere to report it to.
+        let usage_tracker = usage_reporter.as_ref().map(|r| r.tracker.clone());
+
+        let response_cache = state.service.response_cache.is_enabled().then(|| {
+            ResponseCache::new(
+                &state.service.response_cache,
+                state.metrics.request_metrics.clone(),
+            )
+        });
+
         let app = router
+            .route("/subscriptions", get(subscription_handler))
             .layer(axum::extract::Extension(schema))
+            .layer(axum::extract::Extension(usage_tracker))
+            .layer(axum::extract::Extension(response_cache))
             .layer(axum::extract::Extension(checkpoint_watermark.clone()))
+            .layer(axum::extract::Extension(db_reader.clone()))
             .layer(Self::cors()?);
 
         Ok(Server {
@@ -320,6 +410,7 @@ impl ServerBuilder {
             checkpoint_watermark,
             state,
             db_reader,
+            usage_reporter,
         })
     }
 
@@ -377,13 +468,43 @@ impl ServerBuilder {
         )
         .map_err(|e| Error::Internal(format!("Failed to create pg connection pool: {}", e)))?;
 
+        // Queries are load balanced across the primary DB and its read replicas, while other
+        // readers only use the primary.
+        let mut replicas = vec![(config.connection.db_url.clone(), reader.clone())];
+        for db_replica_url in &config.connection.db_replica_urls {
+            let replica = PgManager::reader_with_config(
+                db_replica_url.clone(),
+                config.connection.db_pool_size,
+                config.service.limits.request_timeout_ms,
+            )
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to create pg connection pool for replica: {e}"
+                ))
+            })?;
+            replicas.push((db_replica_url.clone(), replica));
+        }
+
         // DB
-        let db = Db::new(reader.clone(), config.service.limits, metrics.clone());
+        let db = Db::new(
+            Replicas::new(replicas),
+            config.service.limits,
+            metrics.clone(),
+        );
         let pg_conn_pool = PgManager::new(reader.clone());
         let package_store = DbPackageStore(reader.clone());
         let package_cache = PackageStoreWithLruCache::new(package_store);
         builder.db_reader = Some(db.clone());
 
+        // Usage reports are written through their own connection, because connections for serving
+        // requests are read-only.
+        let usage_pool = new_pg_connection_pool(&config.connection.db_url, Some(1))
+            .map_err(|e| Error::Internal(format!("Failed to create pg connection pool: {}", e)))?;
+        builder.usage_reporter = Some(UsageReporter {
+            tracker: UsageTracker::new(),
+            pool: usage_pool,
+        });
+
         // SDK for talking to fullnode. Used for executing transactions only
         // TODO: fail fast if no url, once we enable mutations fully
         let sui_sdk_client = if let Some(url) = &config.tx_exec_full_node.node_rpc_url {
@@ -396,7 +517,15 @@ impl ServerBuilder {
                     .map_err(|e| Error::Internal(format!("Failed to create SuiClient: {}", e)))?,
             )
         } else {
-            warn!("No fullnode url found in config. `dryRunTransactionBlock` and `executeTransactionBlock` will not work");
+            warn!("No fullnode url found in config, serving in read-only mode. `dryRunTransactionBlock` and `executeTransactionBlock` will fail with SERVICE_READ_ONLY");
+            None
+        };
+
+        // Only the persisted queries are executable, if the service is configured to allow them
+        // exclusively.
+        let persisted_queries = if config.service.persisted_queries.is_enabled() {
+            Some(PersistedQueries::load(&config.service.persisted_queries, &db).await?)
+        } else {
             None
         };
 
@@ -415,12 +544,24 @@ impl ServerBuilder {
             .context_data(metrics.clone())
             .context_data(config.clone());
 
+        // Rejecting queries that are not persisted comes first, so that no work is done for them.
+        if let Some(persisted_queries) = persisted_queries {
+            builder = builder.extension(persisted_queries);
+        }
         if config.internal_features.feature_gate {
             builder = builder.extension(FeatureGate);
         }
+        // Serves the old names of renamed fields, by rewriting them before queries are validated.
+        if !config.service.field_aliases.is_empty() {
+            builder = builder.extension(FieldAliases::new(config.service.field_aliases.clone()));
+        }
         if config.internal_features.logger {
             builder = builder.extension(Logger::default());
         }
+        // Quotas wrap the query limits checker, to charge requests for the cost it estimates.
+        if config.service.rate_limits.is_enabled() {
+            builder = builder.extension(RateLimiter::new(config.service.rate_limits.clone()));
+        }
         if config.internal_features.query_limits_checker {
             builder = builder.extension(QueryLimitsChecker::default());
         }
@@ -441,8 +582,8 @@ impl ServerBuilder {
     }
 }
 
-fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    async_graphql::Schema::build(Query, Mutation, EmptySubscription)
+fn schema_builder() -> SchemaBuilder<Query, Mutation, Subscription> {
+    async_graphql::Schema::build(Query, Mutation, Subscription)
         .register_output_type::<IMoveObject>()
         .register_output_type::<IObject>()
         .register_output_type::<IOwner>()
@@ -455,36 +596,265 @@ pub fn export_schema() -> String {
 
 /// Entry point for graphql requests. Each request is stamped with a unique ID, a `ShowUsage` flag
 /// if set in the request headers, and the high checkpoint watermark as set by the background task.
+/// If the request names a transaction in its `x-sui-rpc-wait-for-transaction` header, it is only
+/// served once the watermark includes that transaction, or fails if that takes longer than the
+/// configured limit. If the request names an API key in its `x-sui-rpc-api-key` header, its usage
+/// is attributed to that key in usage reports. If the response cache is enabled, queries are served
+/// from it when an identical query has already been served at the same watermark.
+///
+/// A batch of requests is served as one request: its requests are executed in order, at the same
+/// watermark, and their operations share a single budget of query limits. The size of the batch
+/// is limited, its queries share the limit on the query payload size, and its requests share the
+/// request timeout.
+#[allow(clippy::too_many_arguments)]
 async fn graphql_handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     schema: axum::Extension<SuiGraphQLSchema>,
     watermark: axum::Extension<CheckpointWatermark>,
+    db: axum::Extension<Db>,
+    usage: axum::Extension<Option<UsageTracker>>,
+    cache: axum::Extension<Option<ResponseCache>>,
     headers: HeaderMap,
-    req: GraphQLRequest,
+    req: GraphQLBatchRequest,
 ) -> (axum::http::Extensions, GraphQLResponse) {
-    let mut req = req.into_inner();
-    req.data.insert(Uuid::new_v4());
-    if headers.contains_key(ShowUsage::name()) {
-        req.data.insert(ShowUsage)
+    let instant = Instant::now();
+    let (is_batch, mut requests) = match req.into_inner() {
+        BatchRequest::Single(req) => (false, vec![req]),
+        BatchRequest::Batch(requests) => (true, requests),
+    };
+    let show_usage = headers.contains_key(ShowUsage::name());
+    let api_key = headers
+        .get(&API_KEY_HEADER)
+        .and_then(|key| key.to_str().ok())
+        .map(|key| ApiKey(key.to_string()));
+    let cost = RequestCost::default();
+    let budget = BatchBudget::default();
+    for (index, req) in requests.iter_mut().enumerate() {
+        req.data.insert(Uuid::new_v4());
+        if show_usage {
+            req.data.insert(ShowUsage)
+        }
+        // Capture the IP address of the client
+        // Note: if a load balancer is used it must be configured to forward the client IP address
+        req.data.insert(addr);
+
+        if let Some(api_key) = &api_key {
+            req.data.insert(api_key.clone());
+            req.data.insert(cost.clone());
+        }
+        if is_batch {
+            req.data.insert(budget.for_request(index));
+        }
     }
-    // Capture the IP address of the client
-    // Note: if a load balancer is used it must be configured to forward the client IP address
-    req.data.insert(addr);
+    let query_payload_bytes = requests.iter().map(|req| req.query.len() as u64).sum();
 
-    let checkpoint_viewed_at = watermark.0 .0.load(Relaxed);
+    // Attribute the request's usage to its API key, if it has one and usage is being tracked.
+    let record_usage = |failed: bool| {
+        let (Some(api_key), Some(tracker)) = (&api_key, &usage.0) else {
+            return;
+        };
 
-    // This wrapping is done to delineate the watermark from potentially other u64 types.
-    req.data.insert(CheckpointViewedAt(checkpoint_viewed_at));
+        tracker.record(
+            api_key,
+            Usage {
+                requests: 1,
+                errors: failed as u64,
+                query_payload_bytes,
+                latency_ms: instant.elapsed().as_millis() as u64,
+                ..cost.usage()
+            },
+        );
+    };
 
-    let result = schema.execute(req).await;
+    let mut extensions = axum::http::Extensions::new();
+    if is_batch {
+        if let Err(error) = check_batch(&requests, &db.limits) {
+            extensions.insert(GraphqlErrors(Arc::new(vec![error.clone()])));
+            record_usage(true);
+            return (extensions, Response::from_errors(vec![error]).into());
+        }
+    }
+
+    let checkpoint_viewed_at = match headers.get(&WAIT_FOR_TRANSACTION_HEADER) {
+        None => watermark.0 .0.load(Relaxed),
+        Some(digest) => match wait_for_transaction(&db, &watermark, digest).await {
+            Ok(checkpoint_viewed_at) => checkpoint_viewed_at,
+            Err((code, e)) => {
+                let error = graphql_error(code, e.to_string());
+                extensions.insert(GraphqlErrors(Arc::new(vec![error.clone()])));
+                record_usage(true);
+                return (extensions, Response::from_errors(vec![error]).into());
+            }
+        },
+    };
+
+    // Cached responses are served without executing their query, so they are not subject to rate
+    // limits, and cost nothing beyond the request itself. Batches are not cached.
+    let cache_key = match (&cache.0, requests.as_slice()) {
+        (Some(_), [req]) if !is_batch => ResponseCache::key(req, api_key.as_ref(), show_usage),
+        _ => None,
+    };
+    if let (Some(cache), Some(key)) = (&cache.0, &cache_key) {
+        if let Some(response) = cache.get(key, checkpoint_viewed_at, Instant::now()).await {
+            record_usage(false);
+            return (extensions, response.into());
+        }
+    }
+
+    // Each request is bounded by the request timeout on its own, and the requests of a batch also
+    // share it, so that a batch takes no longer to serve than a single request.
+    let request_timeout = Duration::from_millis(db.limits.request_timeout_ms);
+    let deadline = tokio::time::Instant::now() + request_timeout;
+    let mut responses = Vec::with_capacity(requests.len());
+    for mut req in requests {
+        // This wrapping is done to delineate the watermark from potentially other u64 types.
+        req.data.insert(CheckpointViewedAt(checkpoint_viewed_at));
+        let response = if is_batch {
+            tokio::time::timeout_at(deadline, schema.execute(req))
+                .await
+                .unwrap_or_else(|_| {
+                    Response::from_errors(vec![graphql_error(
+                        code::REQUEST_TIMEOUT,
+                        format!("Batch timed out. Limit: {}s", request_timeout.as_secs_f32()),
+                    )])
+                })
+        } else {
+            schema.execute(req).await
+        };
+        responses.push(response);
+    }
+    let failed = responses.iter().any(|response| response.is_err());
+    record_usage(failed);
+
+    if let (Some(cache), Some(key), [response]) = (&cache.0, cache_key, responses.as_slice()) {
+        cache
+            .insert(key, checkpoint_viewed_at, response, Instant::now())
+            .await;
+    }
 
     // If there are errors, insert them as an extention so that the Metrics callback handler can
     // pull it out later.
-    let mut extensions = axum::http::Extensions::new();
-    if result.is_err() {
-        extensions.insert(GraphqlErrors(std::sync::Arc::new(result.errors.clone())));
+    if failed {
+        let errors = responses
+            .iter()
+            .flat_map(|response| response.errors.clone())
+            .collect();
+        extensions.insert(GraphqlErrors(std::sync::Arc::new(errors)));
     };
-    (extensions, result.into())
+
+    let response = if is_batch {
+        BatchResponse::Batch(responses)
+    } else {
+        BatchResponse::Single(responses.pop().expect("A single request has a response"))
+    };
+    (extensions, response.into())
+}
+
+/// Rejects a batch with more requests than the limit, or whose queries are larger in total than a
+/// single query is allowed to be.
+fn check_batch(requests: &[async_graphql::Request], limits: &Limits) -> Result<(), ServerError> {
+    if requests.len() > limits.max_batch_size as usize {
+        return Err(graphql_error(
+            code::BAD_USER_INPUT,
+            format!(
+                "Batch is too large: {} requests. The maximum allowed is {}",
+                requests.len(),
+                limits.max_batch_size
+            ),
+        ));
+    }
+
+    let payload_size: usize = requests.iter().map(|req| req.query.len()).sum();
+    if payload_size > limits.max_query_payload_size as usize {
+        return Err(graphql_error(
+            code::BAD_USER_INPUT,
+            format!(
+                "Query payload is too large. The maximum allowed is {} bytes, shared by the \
+                 queries of a batch",
+                limits.max_query_payload_size
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Entry point for graphql subscriptions, which are served over WebSocket connections. Each
+/// connection is stamped with a unique ID and the client's address, like requests, and keeps track
+/// of its subscriptions, to limit how many it can make.
+async fn subscription_handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    schema: axum::Extension<SuiGraphQLSchema>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> impl IntoResponse {
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |socket| {
+            let mut data = Data::default();
+            data.insert(Uuid::new_v4());
+            data.insert(addr);
+            data.insert(ConnectionSubscriptions::default());
+            GraphQLWebSocket::new(socket, schema.0, protocol)
+                .with_data(data)
+                .serve()
+        })
+}
+
+/// Waits until the checkpoint watermark includes the transaction with the given `digest`, polling
+/// the database for the checkpoint it was included in. Returns the watermark to serve the request
+/// at, or an error (with its GraphQL error code) if the digest is malformed, or the transaction is
+/// not included within the `max_wait_for_transaction_ms` limit.
+async fn wait_for_transaction(
+    db: &Db,
+    watermark: &CheckpointWatermark,
+    digest: &HeaderValue,
+) -> Result<u64, (&'static str, Error)> {
+    let digest = digest
+        .to_str()
+        .ok()
+        .and_then(|d| Digest::from_str(d).ok())
+        .ok_or_else(|| {
+            (
+                code::BAD_REQUEST,
+                Error::Client(format!(
+                    "Invalid transaction digest in '{}' header",
+                    WAIT_FOR_TRANSACTION_HEADER.as_str(),
+                )),
+            )
+        })?;
+
+    let max_wait_ms = db.limits.max_wait_for_transaction_ms;
+    let wait = async {
+        let mut tx_checkpoint = None;
+        loop {
+            if tx_checkpoint.is_none() {
+                tx_checkpoint =
+                    match TransactionBlock::query_checkpoint_sequence_number(db, digest).await {
+                        Ok(checkpoint) => checkpoint,
+                        Err(e) => return Err((code::INTERNAL_SERVER_ERROR, e)),
+                    };
+            }
+
+            let checkpoint_viewed_at = watermark.0.load(Relaxed);
+            if tx_checkpoint.is_some_and(|checkpoint| checkpoint <= checkpoint_viewed_at) {
+                return Ok(checkpoint_viewed_at);
+            }
+
+            tokio::time::sleep(WAIT_FOR_TRANSACTION_POLL_INTERVAL).await;
+        }
+    };
+
+    tokio::time::timeout(Duration::from_millis(max_wait_ms), wait)
+        .await
+        .map_err(|_| {
+            (
+                code::REQUEST_TIMEOUT,
+                Error::Client(format!(
+                    "Transaction {digest} was not indexed within {max_wait_ms}ms"
+                )),
+            )
+        })?
 }
 
 #[derive(Clone)]
@@ -534,29 +904,39 @@ impl Drop for MetricsCallbackHandler {
 }
 
 #[derive(Debug, Clone)]
-struct GraphqlErrors(std::sync::Arc<Vec<async_graphql::ServerError>>);
-
-/// Connect via a TCPStream to the DB to check if it is alive
-async fn health_checks(State(connection): State<ConnectionConfig>) -> StatusCode {
-    let Ok(url) = reqwest::Url::parse(connection.db_url.as_str()) else {
-        return StatusCode::INTERNAL_SERVER_ERROR;
-    };
-
-    let Some(host) = url.host_str() else {
-        return StatusCode::INTERNAL_SERVER_ERROR;
-    };
-
-    let tcp_url = if let Some(port) = url.port() {
-        format!("{host}:{port}")
-    } else {
-        host.to_string()
-    };
-
-    if TcpStream::connect(tcp_url).is_err() {
-        StatusCode::INTERNAL_SERVER_ERROR
-    } else {
-        StatusCode::OK
-    }
+pub(crate) struct GraphqlErrors(pub std::sync::Arc<Vec<async_graphql::ServerError>>);
+
+/// Checks whether the service can serve requests: that the DB responds to a query (fetching the
+/// timestamp of the checkpoint watermark), that the watermark is keeping up with the network, and
+/// that background tasks are still making progress. Responds with a JSON report on the health of
+/// each component, with a 503 status if any of them is unhealthy.
+async fn health_check(
+    State(state): State<AppState>,
+    axum::extract::Extension(db): axum::extract::Extension<Db>,
+    axum::extract::Extension(watermark): axum::extract::Extension<CheckpointWatermark>,
+) -> impl IntoResponse {
+    let checkpoint = watermark.0.load(Relaxed);
+
+    let start = Instant::now();
+    let timestamp_ms = tokio::time::timeout(
+        HEALTH_CHECK_DB_TIMEOUT,
+        db.execute(move |conn| Checkpoint::query_timestamp(conn, checkpoint).optional()),
+    )
+    .await
+    .map_err(|_| "Timed out querying the DB".to_string())
+    .and_then(|result| result.map_err(|e| e.to_string()));
+    let db_latency = start.elapsed();
+
+    let report = HealthReport::new(
+        &state.service.health,
+        checkpoint,
+        timestamp_ms,
+        db_latency,
+        &state.heartbeats,
+        now_ms(),
+    );
+
+    (report.status_code(), Json(report))
 }
 
 // One server per proc, so this is okay
@@ -565,12 +945,14 @@ async fn get_or_init_server_start_time() -> &'static Instant {
     ONCE.get_or_init(|| async move { Instant::now() }).await
 }
 
-/// Starts an infinite loop that periodically updates the `checkpoint_viewed_at` high watermark.
+/// Starts an infinite loop that periodically updates the `checkpoint_viewed_at` high watermark,
+/// beating `heartbeat` after each successful update.
 pub(crate) async fn update_watermark(
     db: &Db,
     checkpoint_viewed_at: CheckpointWatermark,
     metrics: Metrics,
     sleep_ms: tokio::time::Duration,
+    heartbeat: Heartbeat,
     cancellation_token: CancellationToken,
 ) {
     loop {
@@ -592,6 +974,7 @@ pub(crate) async fn update_watermark(
 
                 if let Some(checkpoint) = new_checkpoint_viewed_at {
                     checkpoint_viewed_at.0.store(checkpoint, Relaxed);
+                    heartbeat.beat();
                 }
             }
         }
@@ -601,15 +984,21 @@ pub(crate) async fn update_watermark(
 pub mod tests {
     use super::*;
     use crate::{
-        config::{ConnectionConfig, Limits, ServiceConfig, Version},
+        config::{
+            ConnectionConfig, FieldAlias, Limits, Quota, RateLimitsConfig, ServiceConfig, Version,
+        },
         context_data::db_data_provider::PgManager,
-        extensions::query_limits_checker::QueryLimitsChecker,
+        extensions::field_aliases::FieldAliases,
+        extensions::persisted_queries::PersistedQueries,
+        extensions::query_limits_checker::{BatchBudget, QueryLimitsChecker},
+        extensions::rate_limiter::RateLimiter,
         extensions::timeout::Timeout,
     };
     use async_graphql::{
         extensions::{Extension, ExtensionContext, NextExecute},
-        Response,
+        value, Request, Response, Value,
     };
+    use chrono::{NaiveDate, Utc};
     use std::sync::Arc;
     use std::time::Duration;
     use uuid::Uuid;
@@ -625,10 +1014,15 @@ pub mod tests {
         let service_config = service_config.unwrap_or_default();
 
         let db_url: String = connection_config.db_url.clone();
-        let reader = PgManager::reader(db_url).expect("Failed to create pg connection pool");
+        let reader =
+            PgManager::reader(db_url.clone()).expect("Failed to create pg connection pool");
         let version = Version::for_testing();
         let metrics = metrics();
-        let db = Db::new(reader.clone(), service_config.limits, metrics.clone());
+        let db = Db::new(
+            Replicas::new([(db_url, reader.clone())]),
+            service_config.limits,
+            metrics.clone(),
+        );
         let pg_conn_pool = PgManager::new(reader);
         let cancellation_token = CancellationToken::new();
         let watermark = CheckpointViewedAt(1);
@@ -724,6 +1118,215 @@ pub mod tests {
         assert_eq!(errs, vec![exp]);
     }
 
+    pub async fn test_persisted_queries_impl() {
+        let persisted = "{ chainIdentifier }";
+        let schema = prep_schema(None, None)
+            .extension(PersistedQueries::new([PersistedQueries::hash(persisted)]))
+            .build_schema();
+
+        schema
+            .execute(persisted)
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+
+        // The same query, written differently, is not persisted.
+        let ad_hoc = "{chainIdentifier}";
+        let errs: Vec<_> = schema
+            .execute(ad_hoc)
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+
+        assert_eq!(
+            errs,
+            vec![format!(
+                "This service only executes persisted queries, and query {} has not been \
+                 registered",
+                PersistedQueries::hash(ad_hoc),
+            )]
+        );
+    }
+
+    pub async fn test_field_aliases_impl() {
+        let alias = |ty: &str, old: &str, new: &str, until: Option<NaiveDate>| FieldAlias {
+            type_: ty.to_string(),
+            old_name: old.to_string(),
+            new_name: new.to_string(),
+            until,
+        };
+
+        let yesterday = Utc::now().date_naive().pred_opt().unwrap();
+        let schema = prep_schema(None, None)
+            .extension(FieldAliases::new([
+                alias("Query", "chainId", "chainIdentifier", None),
+                alias("Checkpoint", "seq", "sequenceNumber", None),
+                alias("Query", "network", "chainIdentifier", Some(yesterday)),
+                // Fields that still exist are not aliased.
+                alias("Query", "protocolConfig", "chainIdentifier", None),
+            ]))
+            .build_schema();
+
+        // Old names are served under their old name, unless they are given an alias, in operations
+        // and fragments alike.
+        let resp = schema
+            .execute(
+                r#"
+                    query {
+                        chainId
+                        id: chainId
+                        checkpoint { ...Seq }
+                    }
+
+                    fragment Seq on Checkpoint { seq }
+                "#,
+            )
+            .await;
+
+        assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+        let Value::Object(data) = &resp.data else {
+            panic!("Expected an object, got {:?}", resp.data);
+        };
+        assert!(data.contains_key("chainId"));
+        assert!(data.contains_key("id"));
+        assert!(!data.contains_key("chainIdentifier"));
+
+        assert_eq!(
+            resp.extensions.get("deprecations"),
+            Some(&value!([
+                {
+                    "field": "Checkpoint.seq",
+                    "replacement": "sequenceNumber",
+                    "until": null,
+                },
+                {
+                    "field": "Query.chainId",
+                    "replacement": "chainIdentifier",
+                    "until": null,
+                },
+            ]))
+        );
+
+        // Queries that only use current names are not told about deprecations.
+        let resp = schema.execute("{ chainIdentifier }").await;
+        assert!(resp.errors.is_empty(), "{:?}", resp.errors);
+        assert!(!resp.extensions.contains_key("deprecations"));
+
+        // Expired aliases are no longer served.
+        let resp = schema.execute("{ network }").await;
+        assert_eq!(resp.errors.len(), 1);
+        assert!(!resp.extensions.contains_key("deprecations"));
+    }
+
+    pub async fn test_rate_limits_impl() {
+        let schema = prep_schema(None, None)
+            .extension(RateLimiter::new(RateLimitsConfig {
+                default: Quota {
+                    requests_per_minute: Some(2),
+                    output_nodes_per_minute: None,
+                },
+                ..Default::default()
+            }))
+            .build_schema();
+
+        let query = "{ chainIdentifier }";
+        let with_key = |key: &str| Request::new(query).data(ApiKey(key.to_string()));
+
+        for _ in 0..2 {
+            schema
+                .execute(with_key("a"))
+                .await
+                .into_result()
+                .expect("Should complete successfully");
+        }
+
+        let errs: Vec<_> = schema
+            .execute(with_key("a"))
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            errs,
+            vec!["API key has exceeded its quota of requests per minute".to_string()]
+        );
+
+        // Other keys have their own quota, and requests without a key are not limited.
+        schema
+            .execute(with_key("b"))
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+        schema
+            .execute(query)
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+    }
+
+    pub async fn test_read_only_mode_impl() {
+        async fn exec(service_config: ServiceConfig, query: &str) -> Response {
+            prep_schema(None, Some(service_config))
+                .build_schema()
+                .execute(query)
+                .await
+        }
+
+        let status = "{ serviceStatus { mode connectedToFullnode mutationsEnabled } }";
+        let mutation = r#"mutation {
+            executeTransactionBlock(txBytes: "", signatures: []) { errors }
+        }"#;
+
+        // Without a fullnode to execute transactions through, the service is read-only, but
+        // mutations are still part of the schema.
+        let response = exec(ServiceConfig::default(), status).await;
+        assert_eq!(
+            response.data,
+            value!({
+                "serviceStatus": {
+                    "mode": "READ_ONLY",
+                    "connectedToFullnode": false,
+                    "mutationsEnabled": true,
+                }
+            })
+        );
+
+        let errs = exec(ServiceConfig::default(), mutation).await.errors;
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            errs[0].message,
+            "This service is read-only, because it is not connected to a fullnode to run \
+             transactions through"
+        );
+        assert_eq!(
+            errs[0].extensions.as_ref().and_then(|e| e.get("code")),
+            Some(&value!("SERVICE_READ_ONLY"))
+        );
+
+        // Mutations can be removed from the schema entirely.
+        let disabled = ServiceConfig {
+            disable_mutations: true,
+            ..Default::default()
+        };
+        let response = exec(disabled.clone(), status).await;
+        assert_eq!(
+            response.data,
+            value!({
+                "serviceStatus": {
+                    "mode": "READ_ONLY",
+                    "connectedToFullnode": false,
+                    "mutationsEnabled": false,
+                }
+            })
+        );
+        assert!(exec(disabled, mutation).await.is_err());
+    }
+
     pub async fn test_query_depth_limit_impl() {
         async fn exec_query_depth_limit(depth: u32, query: &str) -> Response {
             let service_config = ServiceConfig {
@@ -840,6 +1443,107 @@ pub mod tests {
         );
     }
 
+    pub async fn test_query_batch_budget_impl() {
+        let service_config = ServiceConfig {
+            limits: Limits {
+                max_query_nodes: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = prep_schema(None, Some(service_config))
+            .extension(QueryLimitsChecker::default())
+            .build_schema();
+
+        // Each request is within the limit on its own...
+        let queries = [
+            "{ chainIdentifier }",
+            "{ chainIdentifier protocolConfig { protocolVersion } }",
+        ];
+        for query in queries {
+            schema
+                .execute(query)
+                .await
+                .into_result()
+                .expect("Should complete successfully");
+        }
+
+        // ...but not as part of a batch, which has a single budget shared by its requests.
+        let budget = BatchBudget::default();
+        let mut responses = vec![];
+        for (index, query) in queries.into_iter().enumerate() {
+            let request = Request::new(query).data(budget.for_request(index));
+            responses.push(schema.execute(request).await);
+        }
+        assert!(responses[0].is_ok());
+        let err: Vec<_> = responses[1]
+            .errors
+            .iter()
+            .map(|e| e.message.clone())
+            .collect();
+        assert_eq!(
+            err,
+            vec![
+                "Query has too many nodes 4. The maximum allowed is 3, exceeded by anonymous \
+                 operation of the request at index 1 in the batch"
+                    .to_string()
+            ]
+        );
+
+        // The operations of a multi-operation document share a budget as well, and the operation
+        // that exceeds it is named.
+        let err: Vec<_> = schema
+            .execute("query A { chainIdentifier } query B { chainIdentifier } query C { chainIdentifier protocolConfig { protocolVersion } }")
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(err.len(), 1);
+        assert!(
+            err[0].starts_with(
+                "Query has too many nodes 4. The maximum allowed is 3, exceeded by operation"
+            ),
+            "{}",
+            err[0]
+        );
+    }
+
+    pub fn test_batch_limits_impl() {
+        let limits = Limits {
+            max_batch_size: 2,
+            max_query_payload_size: 40,
+            ..Default::default()
+        };
+        let error = |query: &str, size: usize| {
+            let requests: Vec<_> = (0..size).map(|_| Request::new(query)).collect();
+            check_batch(&requests, &limits).map_err(|e| e.message)
+        };
+
+        // Each query is within the payload limit, and so is the batch of two of them...
+        let query = "{ chainIdentifier }";
+        assert_eq!(error(query, 2), Ok(()));
+
+        // ...but a batch can have no more than two requests...
+        assert_eq!(
+            error(query, 3),
+            Err("Batch is too large: 3 requests. The maximum allowed is 2".to_string())
+        );
+
+        // ...whose queries share the payload limit.
+        let query = "{ epoch { epochId } }";
+        assert_eq!(error(query, 1), Ok(()));
+        assert_eq!(
+            error(query, 2),
+            Err(
+                "Query payload is too large. The maximum allowed is 40 bytes, shared by the \
+                 queries of a batch"
+                    .to_string()
+            )
+        );
+    }
+
     pub async fn test_query_default_page_limit_impl() {
         let service_config = ServiceConfig {
             limits: Limits {
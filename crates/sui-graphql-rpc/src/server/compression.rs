@@ -0,0 +1,275 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_graphql::Response as GraphQLServerResponse;
+use async_graphql_axum::GraphQLResponse;
+use axum::{
+    body::{boxed, Full},
+    extract::State,
+    http::{header::CONTENT_ENCODING, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tower_http::compression::{predicate::SizeAbove, CompressionLayer};
+use tracing::info;
+
+use crate::{
+    config::{CompressionConfig, Encoding},
+    error::{code, graphql_error, graphql_error_response},
+    metrics::Metrics,
+    server::builder::GraphqlErrors,
+};
+
+/// The maximum size of a response, and the metrics to report response sizes to.
+#[derive(Clone)]
+pub(crate) struct ResponseSizeLimit {
+    pub max_response_size: u64,
+    pub metrics: Metrics,
+}
+
+/// Compresses responses with the configured encoding that the client prefers, out of those it
+/// accepts. Responses are sent uncompressed if compression is not enabled, or the client does
+/// not accept any of the configured encodings.
+pub(crate) fn compression_layer(config: &CompressionConfig) -> CompressionLayer<SizeAbove> {
+    CompressionLayer::new()
+        .gzip(config.encodings.contains(&Encoding::Gzip))
+        .br(config.encodings.contains(&Encoding::Br))
+        .deflate(false)
+        .zstd(false)
+        .compress_when(SizeAbove::new(config.min_response_size))
+}
+
+/// Middleware to limit the size of responses. The limit applies to the response before it is
+/// compressed, so that whether a query's response fits does not depend on the encodings that the
+/// client accepts. Responses that are too large are replaced by an error.
+pub(crate) async fn check_response_size_middleware<B>(
+    State(limit): State<ResponseSizeLimit>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let (parts, body) = next.run(request).await.into_parts();
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                graphql_error_response(
+                    code::INTERNAL_SERVER_ERROR,
+                    format!("Failed to read response: {e}"),
+                ),
+            )
+                .into_response();
+        }
+    };
+
+    let size = bytes.len() as u64;
+    let request_metrics = &limit.metrics.request_metrics;
+    request_metrics.response_size.observe(size as f64);
+
+    if size > limit.max_response_size {
+        request_metrics.response_too_large_size.observe(size as f64);
+        info!(
+            error_code = code::RESPONSE_TOO_LARGE,
+            "Response is too large: {size}"
+        );
+
+        let error = graphql_error(
+            code::RESPONSE_TOO_LARGE,
+            format!(
+                "Response is too large. The maximum allowed is {} bytes",
+                limit.max_response_size,
+            ),
+        );
+
+        let mut response =
+            GraphQLResponse::from(GraphQLServerResponse::from_errors(vec![error.clone()]))
+                .into_response();
+        response
+            .extensions_mut()
+            .insert(GraphqlErrors(Arc::new(vec![error])));
+        return response;
+    }
+
+    Response::from_parts(parts, boxed(Full::from(bytes)))
+}
+
+/// Middleware to report the size of responses as they are sent to the client, after they have
+/// been compressed, by their content encoding.
+pub(crate) async fn record_encoded_size_middleware<B>(
+    State(metrics): State<Metrics>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let (parts, body) = next.run(request).await.into_parts();
+    let Ok(bytes) = hyper::body::to_bytes(body).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let encoding = parts
+        .headers
+        .get(CONTENT_ENCODING)
+        .and_then(|e| e.to_str().ok())
+        .unwrap_or("identity");
+
+    metrics
+        .request_metrics
+        .response_encoded_size
+        .with_label_values(&[encoding])
+        .observe(bytes.len() as f64);
+
+    Response::from_parts(parts, boxed(Full::from(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::header::ACCEPT_ENCODING, middleware, routing::get, Router};
+    use prometheus::Registry;
+    use std::collections::BTreeSet;
+    use tower::ServiceExt;
+
+    /// A response that compresses well, so that its compressed size is well below its size.
+    const BODY_SIZE: usize = 2_000;
+
+    fn service(max_response_size: u64, encodings: &[Encoding], metrics: Metrics) -> Router {
+        let compression = CompressionConfig {
+            encodings: BTreeSet::from_iter(encodings.iter().copied()),
+            min_response_size: 0,
+        };
+
+        Router::new()
+            .route("/", get(|| async { "a".repeat(BODY_SIZE) }))
+            .layer(middleware::from_fn_with_state(
+                ResponseSizeLimit {
+                    max_response_size,
+                    metrics: metrics.clone(),
+                },
+                check_response_size_middleware,
+            ))
+            .layer(compression_layer(&compression))
+            .layer(middleware::from_fn_with_state(
+                metrics,
+                record_encoded_size_middleware,
+            ))
+    }
+
+    fn request(accept_encoding: Option<&'static str>) -> Request<Body> {
+        let mut request = Request::builder().uri("/");
+        if let Some(encoding) = accept_encoding {
+            request = request.header(ACCEPT_ENCODING, encoding);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    fn encoding(response: &Response) -> Option<&str> {
+        response
+            .headers()
+            .get(CONTENT_ENCODING)
+            .and_then(|e| e.to_str().ok())
+    }
+
+    async fn body_size(response: Response) -> usize {
+        hyper::body::to_bytes(response.into_body())
+            .await
+            .unwrap()
+            .len()
+    }
+
+    #[tokio::test]
+    async fn test_uncompressed() {
+        let metrics = Metrics::new(&Registry::new());
+        let response = service(10_000, &[Encoding::Gzip], metrics.clone())
+            .oneshot(request(None))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(encoding(&response), None);
+        assert_eq!(body_size(response).await, BODY_SIZE);
+
+        let request_metrics = &metrics.request_metrics;
+        assert_eq!(request_metrics.response_size.get_sample_count(), 1);
+        assert_eq!(
+            request_metrics
+                .response_encoded_size
+                .with_label_values(&["identity"])
+                .get_sample_sum(),
+            BODY_SIZE as f64,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_negotiated_encoding() {
+        let metrics = Metrics::new(&Registry::new());
+        let service = service(10_000, &[Encoding::Gzip, Encoding::Br], metrics.clone());
+
+        let response = service
+            .clone()
+            .oneshot(request(Some("gzip")))
+            .await
+            .unwrap();
+        assert_eq!(encoding(&response), Some("gzip"));
+        assert!(body_size(response).await < BODY_SIZE);
+
+        let response = service.clone().oneshot(request(Some("br"))).await.unwrap();
+        assert_eq!(encoding(&response), Some("br"));
+        assert!(body_size(response).await < BODY_SIZE);
+
+        // Encodings that are not configured are not used, even if the client accepts them.
+        let response = service.oneshot(request(Some("deflate"))).await.unwrap();
+        assert_eq!(encoding(&response), None);
+        assert_eq!(body_size(response).await, BODY_SIZE);
+
+        let encoded_size = &metrics.request_metrics.response_encoded_size;
+        for label in ["gzip", "br", "identity"] {
+            assert_eq!(
+                encoded_size.with_label_values(&[label]).get_sample_count(),
+                1
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compression_disabled() {
+        let metrics = Metrics::new(&Registry::new());
+        let response = service(10_000, &[], metrics)
+            .oneshot(request(Some("gzip, br")))
+            .await
+            .unwrap();
+
+        assert_eq!(encoding(&response), None);
+        assert_eq!(body_size(response).await, BODY_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_response_too_large() {
+        let metrics = Metrics::new(&Registry::new());
+        let service = service(1_000, &[Encoding::Gzip], metrics.clone());
+
+        // The limit applies to the uncompressed response, even if it would fit once compressed.
+        for accept_encoding in [None, Some("gzip")] {
+            let response = service
+                .clone()
+                .oneshot(request(accept_encoding))
+                .await
+                .unwrap();
+
+            let errors = response.extensions().get::<GraphqlErrors>().unwrap();
+            assert_eq!(errors.0.len(), 1);
+            assert_eq!(
+                errors.0[0].message,
+                "Response is too large. The maximum allowed is 1000 bytes"
+            );
+        }
+
+        assert_eq!(
+            metrics
+                .request_metrics
+                .response_too_large_size
+                .get_sample_count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,301 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use axum::http::StatusCode;
+use serde::Serialize;
+
+use crate::{config::HealthConfig, usage::now_ms};
+
+/// A background task is considered to have stalled if it misses this many consecutive heartbeats.
+const MAX_MISSED_HEARTBEATS: u32 = 3;
+
+/// Extra time a background task is given to beat, on top of its missed heartbeats, to absorb
+/// slow iterations (e.g. a slow DB query) without flapping.
+const HEARTBEAT_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Tracks when each of the service's background tasks last made progress, so that health checks
+/// can detect tasks that have stalled or stopped.
+#[derive(Clone, Default)]
+pub(crate) struct TaskHeartbeats(Arc<Mutex<BTreeMap<&'static str, Heartbeat>>>);
+
+/// Records the progress of a single background task.
+#[derive(Clone, Debug)]
+pub(crate) struct Heartbeat {
+    /// How often the task is expected to beat.
+    interval: Duration,
+    /// When the task last beat, in milliseconds since the Unix epoch.
+    last_beat_ms: Arc<AtomicU64>,
+}
+
+/// Whether a component of the service (or the service as a whole) can serve requests.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub(crate) enum Status {
+    Ok,
+    Unhealthy,
+}
+
+/// The response to a health check, reporting the health of each of the service's components. The
+/// service is only healthy if all its components are.
+#[derive(Serialize, Debug)]
+pub(crate) struct HealthReport {
+    pub status: Status,
+    pub db: DbHealth,
+    pub watermark: WatermarkHealth,
+    pub background_tasks: BTreeMap<&'static str, TaskHealth>,
+}
+
+/// Whether the DB responded to a query, and how long it took to.
+#[derive(Serialize, Debug)]
+pub(crate) struct DbHealth {
+    pub status: Status,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The checkpoint that the service is serving requests at, and how far it is behind the network.
+#[derive(Serialize, Debug)]
+pub(crate) struct WatermarkHealth {
+    pub status: Status,
+    pub checkpoint: u64,
+    /// When the watermark checkpoint was created, if it could be found in the DB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_ms: Option<u64>,
+    /// How long ago the watermark checkpoint was created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lag_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_lag_ms: Option<u64>,
+}
+
+/// When a background task last made progress.
+#[derive(Serialize, Debug)]
+pub(crate) struct TaskHealth {
+    pub status: Status,
+    pub last_heartbeat_ms: u64,
+    pub since_last_heartbeat_ms: u64,
+}
+
+impl TaskHeartbeats {
+    /// Start tracking the background task called `name`, which is expected to beat at least every
+    /// `interval`. The task is treated as having just beat, so that it is not reported as stalled
+    /// before it has had the chance to.
+    pub(crate) fn register(&self, name: &'static str, interval: Duration) -> Heartbeat {
+        let heartbeat = Heartbeat {
+            interval,
+            last_beat_ms: Arc::new(AtomicU64::new(now_ms())),
+        };
+
+        self.0.lock().unwrap().insert(name, heartbeat.clone());
+        heartbeat
+    }
+
+    fn report(&self, now_ms: u64) -> BTreeMap<&'static str, TaskHealth> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, heartbeat)| (*name, heartbeat.health(now_ms)))
+            .collect()
+    }
+}
+
+impl Heartbeat {
+    /// Record that the task has made progress.
+    pub(crate) fn beat(&self) {
+        self.last_beat_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    fn health(&self, now_ms: u64) -> TaskHealth {
+        let last_heartbeat_ms = self.last_beat_ms.load(Ordering::Relaxed);
+        let since_last_heartbeat_ms = now_ms.saturating_sub(last_heartbeat_ms);
+        let stalled_after = self.interval * MAX_MISSED_HEARTBEATS + HEARTBEAT_GRACE_PERIOD;
+
+        TaskHealth {
+            status: Status::from_ok(
+                u128::from(since_last_heartbeat_ms) <= stalled_after.as_millis(),
+            ),
+            last_heartbeat_ms,
+            since_last_heartbeat_ms,
+        }
+    }
+}
+
+impl Status {
+    fn from_ok(ok: bool) -> Self {
+        if ok {
+            Status::Ok
+        } else {
+            Status::Unhealthy
+        }
+    }
+}
+
+impl HealthReport {
+    /// Assemble a report from the outcome of querying the DB for the timestamp of the `checkpoint`
+    /// that the service is serving requests at (`None` if the checkpoint was not found), and how
+    /// long that query took.
+    pub(crate) fn new(
+        config: &HealthConfig,
+        checkpoint: u64,
+        timestamp_ms: Result<Option<u64>, String>,
+        db_latency: Duration,
+        heartbeats: &TaskHeartbeats,
+        now_ms: u64,
+    ) -> Self {
+        let (timestamp_ms, error) = match timestamp_ms {
+            Ok(timestamp_ms) => (timestamp_ms, None),
+            Err(e) => (None, Some(e)),
+        };
+
+        let db = DbHealth {
+            status: Status::from_ok(error.is_none()),
+            latency_ms: db_latency.as_millis() as u64,
+            error,
+        };
+
+        let lag_ms = timestamp_ms.map(|ts| now_ms.saturating_sub(ts));
+        let max_lag_ms = config.max_checkpoint_lag_ms;
+        let watermark = WatermarkHealth {
+            status: Status::from_ok(match (lag_ms, max_lag_ms) {
+                (None, _) => false,
+                (Some(lag), Some(max_lag)) => lag <= max_lag,
+                (Some(_), None) => true,
+            }),
+            checkpoint,
+            timestamp_ms,
+            lag_ms,
+            max_lag_ms,
+        };
+
+        let background_tasks = heartbeats.report(now_ms);
+
+        let healthy = db.status == Status::Ok
+            && watermark.status == Status::Ok
+            && background_tasks.values().all(|t| t.status == Status::Ok);
+
+        Self {
+            status: Status::from_ok(healthy),
+            db,
+            watermark,
+            background_tasks,
+        }
+    }
+
+    /// The status code to respond to the health check with. Unhealthy services respond with a 503,
+    /// so that load balancers stop routing requests to them.
+    pub(crate) fn status_code(&self) -> StatusCode {
+        match self.status {
+            Status::Ok => StatusCode::OK,
+            Status::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health_report(
+        max_checkpoint_lag_ms: Option<u64>,
+        timestamp_ms: Result<Option<u64>, String>,
+        heartbeats: &TaskHeartbeats,
+        now_ms: u64,
+    ) -> HealthReport {
+        HealthReport::new(
+            &HealthConfig {
+                max_checkpoint_lag_ms,
+            },
+            10,
+            timestamp_ms,
+            Duration::from_millis(5),
+            heartbeats,
+            now_ms,
+        )
+    }
+
+    #[test]
+    fn test_healthy() {
+        let heartbeats = TaskHeartbeats::default();
+        heartbeats.register("watermark", Duration::from_millis(500));
+
+        let now = now_ms();
+        let report = health_report(Some(1_000), Ok(Some(now - 100)), &heartbeats, now);
+        assert_eq!(report.status, Status::Ok);
+        assert_eq!(report.status_code(), StatusCode::OK);
+        assert_eq!(report.watermark.lag_ms, Some(100));
+        assert_eq!(report.background_tasks["watermark"].status, Status::Ok);
+    }
+
+    #[test]
+    fn test_db_error() {
+        let heartbeats = TaskHeartbeats::default();
+        let report = health_report(None, Err("Boom".to_string()), &heartbeats, now_ms());
+
+        assert_eq!(report.status, Status::Unhealthy);
+        assert_eq!(report.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(report.db.status, Status::Unhealthy);
+        assert_eq!(report.db.error.as_deref(), Some("Boom"));
+
+        // The watermark's lag cannot be verified without the DB.
+        assert_eq!(report.watermark.status, Status::Unhealthy);
+        assert_eq!(report.watermark.lag_ms, None);
+    }
+
+    #[test]
+    fn test_watermark_lag() {
+        let heartbeats = TaskHeartbeats::default();
+        let now = now_ms();
+
+        // Lag is only reported on, unless a maximum is configured.
+        let report = health_report(None, Ok(Some(now - 60_000)), &heartbeats, now);
+        assert_eq!(report.status, Status::Ok);
+        assert_eq!(report.watermark.lag_ms, Some(60_000));
+
+        let report = health_report(Some(30_000), Ok(Some(now - 60_000)), &heartbeats, now);
+        assert_eq!(report.status, Status::Unhealthy);
+        assert_eq!(report.db.status, Status::Ok);
+        assert_eq!(report.watermark.status, Status::Unhealthy);
+
+        // The watermark checkpoint is not in the DB.
+        let report = health_report(None, Ok(None), &heartbeats, now);
+        assert_eq!(report.status, Status::Unhealthy);
+        assert_eq!(report.watermark.status, Status::Unhealthy);
+    }
+
+    #[test]
+    fn test_stalled_background_task() {
+        let heartbeats = TaskHeartbeats::default();
+        let watermark = heartbeats.register("watermark", Duration::from_millis(500));
+        heartbeats.register("usage", Duration::from_secs(60 * 60));
+
+        let stalled_after =
+            500 * MAX_MISSED_HEARTBEATS as u64 + HEARTBEAT_GRACE_PERIOD.as_millis() as u64;
+
+        let now = now_ms();
+        let later = now + stalled_after + 1_000;
+        let report = health_report(None, Ok(Some(later)), &heartbeats, later);
+        assert_eq!(report.status, Status::Unhealthy);
+        assert_eq!(
+            report.background_tasks["watermark"].status,
+            Status::Unhealthy
+        );
+        assert_eq!(report.background_tasks["usage"].status, Status::Ok);
+
+        // Beating again brings the task back to health.
+        watermark.beat();
+        let now = now_ms();
+        let report = health_report(None, Ok(Some(now)), &heartbeats, now);
+        assert_eq!(report.status, Status::Ok);
+    }
+}
@@ -0,0 +1,577 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use async_graphql::dynamic::{
+    Enum, EnumItem, Field, FieldFuture, FieldValue, InputObject, InputValue, Interface,
+    InterfaceField, Object, ResolverContext, Scalar, Schema, TypeRef, Union,
+};
+use async_graphql::parser::types::{
+    BaseType, FieldDefinition, InputValueDefinition, Type, TypeKind, TypeSystemDefinition,
+};
+use async_graphql::{Name, Value};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use chrono::prelude::{TimeZone, Utc as ChronoUtc};
+use fastcrypto::encoding::{Base58, Base64, Encoding, Hex};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::config::{Limits, ServerConfig};
+use crate::error::Error;
+use crate::server::builder::{export_schema, ServerBuilder};
+use crate::server::graphiql_server::graphiql;
+
+/// Timestamps of mock data fall within the year following this one (2024-01-01T00:00:00Z).
+const MOCK_EPOCH_MS: u64 = 1_704_067_200_000;
+const YEAR_MS: u64 = 365 * 24 * 60 * 60 * 1000;
+
+/// Lists that are not connections hold between one and this many elements.
+const MAX_MOCK_LIST_LEN: u64 = 3;
+
+/// The kind of a named type in the schema, as far as generating values for it is concerned.
+enum Kind {
+    Scalar,
+    Enum(Vec<String>),
+    Object,
+    /// An interface or union, with the object types that can stand in for it.
+    Abstract(Vec<String>),
+    Input,
+}
+
+/// The mock data attached to an object: the seed its fields are derived from and, for
+/// connections and their page info, the page being served.
+struct Mock {
+    seed: u64,
+    page: Option<Page>,
+}
+
+#[derive(Clone, Copy)]
+struct Page {
+    len: u64,
+    has_next: bool,
+}
+
+/// Generates deterministic data for fields of the schema. Every value is derived from the seed of
+/// its parent object, and the name and arguments of its field, so the same query always gets the
+/// same response for the same seed, and repeated references to the same field agree.
+struct Mocker {
+    seed: u64,
+    default_page_size: u64,
+    max_page_size: u64,
+    kinds: HashMap<String, Kind>,
+}
+
+/// Copies the description of a type or field of the real schema to its mock, if it has one.
+trait Describe: Sized {
+    fn description_opt(self, description: Option<String>) -> Self;
+}
+
+macro_rules! impl_describe {
+    ($($ty:ty),*) => {
+        $(
+            impl Describe for $ty {
+                fn description_opt(self, description: Option<String>) -> Self {
+                    match description {
+                        Some(description) => self.description(description),
+                        None => self,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_describe!(
+    Scalar,
+    Enum,
+    EnumItem,
+    Object,
+    Field,
+    Interface,
+    InterfaceField,
+    Union,
+    InputObject,
+    InputValue
+);
+
+/// Builds a schema with the same types, fields and arguments as the real service, whose resolvers
+/// serve data generated from `seed` instead of reading from the database.
+pub fn mock_schema(seed: u64, limits: &Limits) -> Result<Schema, Error> {
+    let sdl = export_schema();
+    let document = async_graphql::parser::parse_schema(&sdl)
+        .map_err(|e| Error::Internal(format!("Failed to parse the schema: {e}")))?;
+
+    let mut query = "Query".to_string();
+    let mut mutation = None;
+    let mut types = vec![];
+    for definition in document.definitions {
+        match definition {
+            TypeSystemDefinition::Schema(schema) => {
+                let schema = schema.node;
+                if let Some(name) = schema.query {
+                    query = name.node.to_string();
+                }
+                mutation = schema.mutation.map(|name| name.node.to_string());
+            }
+            TypeSystemDefinition::Type(ty) => types.push(ty.node),
+            // The real service does not define custom directives.
+            TypeSystemDefinition::Directive(_) => {}
+        }
+    }
+
+    let mut kinds = HashMap::new();
+    for ty in &types {
+        let name = ty.name.node.to_string();
+        let kind = match &ty.kind {
+            TypeKind::Scalar => Kind::Scalar,
+            TypeKind::Enum(e) => Kind::Enum(
+                e.values
+                    .iter()
+                    .map(|v| v.node.value.node.to_string())
+                    .collect(),
+            ),
+            TypeKind::Object(_) => Kind::Object,
+            TypeKind::Interface(_) => Kind::Abstract(
+                types
+                    .iter()
+                    .filter_map(|other| match &other.kind {
+                        TypeKind::Object(o) if o.implements.iter().any(|i| i.node == name) => {
+                            Some(other.name.node.to_string())
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            TypeKind::Union(u) => {
+                Kind::Abstract(u.members.iter().map(|m| m.node.to_string()).collect())
+            }
+            TypeKind::InputObject(_) => Kind::Input,
+        };
+        kinds.insert(name, kind);
+    }
+
+    let mocker = Arc::new(Mocker {
+        seed,
+        default_page_size: limits.default_page_size,
+        max_page_size: limits.max_page_size,
+        kinds,
+    });
+
+    let mut builder = Schema::build(&query, mutation.as_deref(), None)
+        .limit_depth(limits.max_query_depth as usize);
+    for ty in types {
+        let name = ty.name.node.to_string();
+        let description = ty.description.map(|d| d.node);
+        match ty.kind {
+            TypeKind::Scalar => {
+                // Built-in scalars are always part of the schema.
+                if !matches!(name.as_str(), "Int" | "Float" | "String" | "Boolean" | "ID") {
+                    builder = builder.register(Scalar::new(name).description_opt(description));
+                }
+            }
+            TypeKind::Enum(e) => {
+                let mut mock = Enum::new(name).description_opt(description);
+                for value in e.values {
+                    let value = value.node;
+                    mock = mock.item(
+                        EnumItem::new(value.value.node.to_string())
+                            .description_opt(value.description.map(|d| d.node)),
+                    );
+                }
+                builder = builder.register(mock);
+            }
+            TypeKind::Object(o) => {
+                let mut mock = Object::new(&name).description_opt(description);
+                for interface in o.implements {
+                    mock = mock.implement(interface.node.to_string());
+                }
+                for field in o.fields {
+                    mock = mock.field(mock_field(&mocker, &name, field.node));
+                }
+                builder = builder.register(mock);
+            }
+            TypeKind::Interface(i) => {
+                let mut mock = Interface::new(name).description_opt(description);
+                for interface in i.implements {
+                    mock = mock.implement(interface.node.to_string());
+                }
+                for field in i.fields {
+                    let field = field.node;
+                    let mut mock_field =
+                        InterfaceField::new(field.name.node.to_string(), type_ref(&field.ty.node))
+                            .description_opt(field.description.map(|d| d.node));
+                    for argument in field.arguments {
+                        mock_field = mock_field.argument(input_value(argument.node));
+                    }
+                    mock = mock.field(mock_field);
+                }
+                builder = builder.register(mock);
+            }
+            TypeKind::Union(u) => {
+                let mut mock = Union::new(name).description_opt(description);
+                for member in u.members {
+                    mock = mock.possible_type(member.node.to_string());
+                }
+                builder = builder.register(mock);
+            }
+            TypeKind::InputObject(i) => {
+                let mut mock = InputObject::new(name).description_opt(description);
+                for field in i.fields {
+                    mock = mock.field(input_value(field.node));
+                }
+                builder = builder.register(mock);
+            }
+        }
+    }
+
+    builder
+        .finish()
+        .map_err(|e| Error::Internal(format!("Failed to build the mock schema: {e}")))
+}
+
+/// Starts a server for the mock schema, with the same routes as the real service. It does not
+/// connect to a database or a fullnode, so it can be used to develop against the API without any
+/// infrastructure.
+pub async fn start_mock_server(
+    server_config: &ServerConfig,
+    seed: u64,
+    cancellation_token: CancellationToken,
+) -> Result<(), Error> {
+    let schema = mock_schema(seed, &server_config.service.limits)?;
+    let address = server_config.connection.server_address();
+
+    let app = Router::new()
+        .route("/", get(graphiql).post(mock_graphql_handler))
+        .route("/graphql", get(graphiql).post(mock_graphql_handler))
+        .route("/health", get(|| async { StatusCode::OK }))
+        .layer(axum::extract::Extension(schema))
+        .layer(axum::extract::Extension(Some(
+            server_config.ide.ide_title.clone(),
+        )))
+        .layer(ServerBuilder::cors()?);
+
+    info!("Serving mock data with seed {seed}");
+    info!("Launch GraphiQL IDE at: http://{}", address);
+
+    let address: SocketAddr = address
+        .parse()
+        .map_err(|_| Error::Internal(format!("Failed to parse address {}", address)))?;
+    axum::Server::bind(&address)
+        .serve(app.into_make_service())
+        .with_graceful_shutdown(async move { cancellation_token.cancelled().await })
+        .await
+        .map_err(|e| Error::Internal(format!("Server run failed: {}", e)))
+}
+
+async fn mock_graphql_handler(
+    schema: axum::Extension<Schema>,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute(req.into_inner()).await.into()
+}
+
+fn mock_field(mocker: &Arc<Mocker>, parent: &str, field: FieldDefinition) -> Field {
+    let name = field.name.node.to_string();
+    let ty = field.ty.node;
+    let mocker = mocker.clone();
+    let parent = parent.to_string();
+    let mut mock = Field::new(name.clone(), type_ref(&ty), move |ctx| {
+        let mocker = mocker.clone();
+        let parent = parent.clone();
+        let name = name.clone();
+        let ty = ty.clone();
+        FieldFuture::new(async move { mocker.resolve(&ctx, &parent, &name, &ty).map(Some) })
+    })
+    .description_opt(field.description.map(|d| d.node));
+    for argument in field.arguments {
+        mock = mock.argument(input_value(argument.node));
+    }
+    mock
+}
+
+fn input_value(value: InputValueDefinition) -> InputValue {
+    let mut mock = InputValue::new(value.name.node.to_string(), type_ref(&value.ty.node))
+        .description_opt(value.description.map(|d| d.node));
+    if let Some(default) = value.default_value {
+        mock = mock.default_value(default.node);
+    }
+    mock
+}
+
+fn type_ref(ty: &Type) -> TypeRef {
+    let base = match &ty.base {
+        BaseType::Named(name) => TypeRef::Named(name.to_string().into()),
+        BaseType::List(inner) => TypeRef::List(Box::new(type_ref(inner))),
+    };
+    if ty.nullable {
+        base
+    } else {
+        TypeRef::NonNull(Box::new(base))
+    }
+}
+
+impl Mocker {
+    fn resolve<'a>(
+        &self,
+        ctx: &ResolverContext<'a>,
+        parent: &str,
+        field: &str,
+        ty: &Type,
+    ) -> async_graphql::Result<FieldValue<'a>> {
+        let (seed, page) = match ctx.parent_value.try_downcast_ref::<Mock>() {
+            Ok(mock) => (mock.seed, mock.page),
+            // Fields of the root types.
+            Err(_) => (self.seed, None),
+        };
+
+        // The edges, nodes and page info of a connection describe the same page of items.
+        if let Some(page) = page {
+            match (field, &ty.base) {
+                ("edges", BaseType::List(item)) => {
+                    return self.list(page.len, |i| self.value(item, field, item_seed(seed, i)));
+                }
+                ("nodes", BaseType::List(item)) => {
+                    return self.list(page.len, |i| {
+                        self.value(item, field, child_seed(item_seed(seed, i), "node"))
+                    });
+                }
+                ("pageInfo", _) => {
+                    return Ok(FieldValue::owned_any(Mock {
+                        seed,
+                        page: Some(page),
+                    }));
+                }
+                _ => {}
+            }
+
+            if parent == "PageInfo" {
+                let last = page.len.checked_sub(1);
+                return Ok(FieldValue::value(match field {
+                    "hasPreviousPage" => Value::from(false),
+                    "hasNextPage" => Value::from(page.has_next),
+                    "startCursor" if page.len > 0 => cursor(item_seed(seed, 0)),
+                    "endCursor" => last.map_or(Value::Null, |i| cursor(item_seed(seed, i))),
+                    _ => Value::Null,
+                }));
+            }
+        }
+
+        if parent.ends_with("Edge") && field == "cursor" {
+            return Ok(FieldValue::value(cursor(seed)));
+        }
+
+        let seed = child_seed(seed, &field_key(ctx, field));
+        if named_type(ty).ends_with("Connection") {
+            let requested = ["first", "last"]
+                .iter()
+                .find_map(|arg| ctx.args.get(arg).and_then(|v| v.u64().ok()))
+                .unwrap_or(self.default_page_size);
+            let len = requested.min(self.max_page_size);
+            return Ok(FieldValue::owned_any(Mock {
+                seed,
+                page: Some(Page {
+                    len,
+                    // Every mock connection has another page, unless it was asked for none.
+                    has_next: len > 0,
+                }),
+            }));
+        }
+
+        self.value(ty, field, seed)
+    }
+
+    fn value<'a>(
+        &self,
+        ty: &Type,
+        field: &str,
+        seed: u64,
+    ) -> async_graphql::Result<FieldValue<'a>> {
+        match &ty.base {
+            BaseType::List(item) => self.list(1 + seed % MAX_MOCK_LIST_LEN, |i| {
+                self.value(item, field, item_seed(seed, i))
+            }),
+            BaseType::Named(name) => {
+                let mock = || FieldValue::owned_any(Mock { seed, page: None });
+                Ok(match self.kinds.get(name.as_str()) {
+                    Some(Kind::Object) => mock(),
+                    Some(Kind::Abstract(types)) if !types.is_empty() => {
+                        let ty = &types[(seed % types.len() as u64) as usize];
+                        mock().with_type(ty.clone())
+                    }
+                    Some(Kind::Enum(items)) if !items.is_empty() => {
+                        let item = &items[(seed % items.len() as u64) as usize];
+                        FieldValue::value(Value::Enum(Name::new(item)))
+                    }
+                    Some(Kind::Scalar) | None => FieldValue::value(scalar(name, field, seed)),
+                    Some(Kind::Abstract(_) | Kind::Enum(_) | Kind::Input) => {
+                        return Err(format!("Cannot generate a value of type {name}").into())
+                    }
+                })
+            }
+        }
+    }
+
+    fn list<'a>(
+        &self,
+        len: u64,
+        item: impl Fn(u64) -> async_graphql::Result<FieldValue<'a>>,
+    ) -> async_graphql::Result<FieldValue<'a>> {
+        Ok(FieldValue::list(
+            (0..len).map(item).collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
+
+/// Generates a value for a scalar, based on its type and, for strings, the name of its field.
+fn scalar(ty: &str, field: &str, seed: u64) -> Value {
+    let small = seed % 1000;
+    match ty {
+        "Int" => Value::from(small as i32),
+        "Float" => Value::from((seed % 100_000) as f64 / 100.0),
+        "Boolean" => Value::from(seed % 2 == 0),
+        "ID" | "SuiAddress" => Value::from(format!("0x{}", Hex::encode(bytes(seed, 32)))),
+        "UInt53" => Value::from(seed % 1_000_000_000),
+        "BigInt" => Value::from((seed % 1_000_000_000_000).to_string()),
+        "Base64" => Value::from(Base64::encode(bytes(seed, 32))),
+        "DateTime" => {
+            let ms = (MOCK_EPOCH_MS + seed % YEAR_MS) as i64;
+            match ChronoUtc.timestamp_millis_opt(ms).single() {
+                Some(date_time) => Value::from(format!("{:?}", date_time)),
+                None => Value::Null,
+            }
+        }
+        "JSON" => Value::from_json(serde_json::json!({ "value": small })).unwrap_or(Value::Null),
+        "MoveData" => Value::from_json(serde_json::json!({ "Number": small.to_string() }))
+            .unwrap_or(Value::Null),
+        "MoveTypeLayout" | "MoveTypeSignature" => Value::from("u64"),
+        "OpenMoveTypeSignature" => {
+            Value::from_json(serde_json::json!({ "body": "u64" })).unwrap_or(Value::Null)
+        }
+        "String" => {
+            let field = field.to_lowercase();
+            if field.contains("digest") {
+                Value::from(Base58::encode(bytes(seed, 32)))
+            } else if field == "repr" || field.ends_with("type") {
+                Value::from("0x2::coin::Coin<0x2::sui::SUI>")
+            } else if field.contains("url") {
+                Value::from(format!("https://example.com/mock/{small}"))
+            } else {
+                Value::from(format!("{field}_{small}"))
+            }
+        }
+        _ => Value::from(format!("{ty}_{small}")),
+    }
+}
+
+/// The name of the type a field returns, under any lists and non-null wrappers.
+fn named_type(ty: &Type) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name.as_str(),
+        BaseType::List(inner) => named_type(inner),
+    }
+}
+
+/// Identifies a field and its arguments, so that e.g. different pages of a connection get
+/// different items.
+fn field_key(ctx: &ResolverContext, field: &str) -> String {
+    let mut key = field.to_string();
+    for (name, value) in ctx.args.iter() {
+        key.push_str(&format!(" {name}: {}", value.as_value()));
+    }
+    key
+}
+
+fn cursor(seed: u64) -> Value {
+    Value::from(Base64::encode(seed.to_be_bytes()))
+}
+
+fn item_seed(seed: u64, index: u64) -> u64 {
+    child_seed(seed, &format!("[{index}]"))
+}
+
+/// Derives the seed of a child value with FNV-1a, so that it is stable across runs and platforms.
+fn child_seed(seed: u64, key: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in seed.to_le_bytes().iter().chain(key.as_bytes()) {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// Expands a seed into `len` bytes with SplitMix64.
+fn bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    let mut bytes = Vec::with_capacity(len);
+    while bytes.len() < len {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        bytes.extend_from_slice(&z.to_le_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value as Json;
+
+    async fn execute(seed: u64, query: &str) -> Json {
+        let schema = mock_schema(seed, &Limits::default()).unwrap();
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mock_connection_shape() {
+        let query = r#"{
+            address(address: "0x1") {
+                coins(first: 2) {
+                    edges { cursor node { address coinBalance } }
+                    nodes { address coinBalance }
+                    pageInfo { hasNextPage startCursor endCursor }
+                }
+            }
+        }"#;
+        let data = execute(0, query).await;
+        let coins = &data["address"]["coins"];
+
+        let edges = coins["edges"].as_array().unwrap();
+        let nodes = coins["nodes"].as_array().unwrap();
+        assert_eq!(edges.len(), 2);
+        let edge_nodes: Vec<_> = edges.iter().map(|e| e["node"].clone()).collect();
+        assert_eq!(&edge_nodes, nodes);
+        assert_eq!(coins["pageInfo"]["startCursor"], edges[0]["cursor"]);
+        assert_eq!(coins["pageInfo"]["endCursor"], edges[1]["cursor"]);
+        assert_eq!(coins["pageInfo"]["hasNextPage"], Json::Bool(true));
+    }
+
+    #[tokio::test]
+    async fn test_mock_data_is_deterministic() {
+        let query = r#"{
+            chainIdentifier
+            checkpoint { digest sequenceNumber timestamp }
+            epoch { referenceGasPrice validatorSet { activeValidators(first: 3) { nodes { name } } } }
+        }"#;
+        let data = execute(7, query).await;
+        assert_eq!(data, execute(7, query).await);
+        assert_ne!(data, execute(8, query).await);
+
+        // Different pages of a connection hold different items.
+        let page = |after: &str| {
+            format!(r#"{{ checkpoints(first: 1, after: "{after}") {{ nodes {{ digest }} }} }}"#)
+        };
+        assert_ne!(execute(7, &page("a")).await, execute(7, &page("b")).await);
+    }
+}
@@ -4,4 +4,8 @@
 pub mod graphiql_server;
 
 pub mod builder;
+pub(crate) mod compression;
+pub(crate) mod health;
+pub mod mock;
+pub(crate) mod response_cache;
 pub mod version;
@@ -0,0 +1,488 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_graphql::{
+    parser::{parse_query, types::OperationType},
+    Request, Response, Value,
+};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use lru::LruCache;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::{
+    config::{ResponseCacheConfig, StoreBackend},
+    metrics::RequestMetrics,
+    redis_store::RedisStore,
+    usage::ApiKey,
+};
+
+/// How long responses cached in Redis are kept for if the cache is not configured with a TTL.
+/// Responses are cached per checkpoint, so they stop being served once the watermark advances
+/// regardless, and this only bounds how long they take up space in Redis.
+const DEFAULT_REDIS_TTL: Duration = Duration::from_secs(60);
+
+/// Caches the responses to queries, so that identical queries served at the same checkpoint (as
+/// dashboards tend to issue every few seconds) are only executed once. Responses are cached in the
+/// memory of each instance of the service, or in Redis, to share them between instances, depending
+/// on the configured backend. Requests are served as if their response was not cached while Redis
+/// cannot be reached.
+#[derive(Clone)]
+pub(crate) struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    metrics: Arc<RequestMetrics>,
+}
+
+/// Identifies requests that are guaranteed to get the same response when served at the same
+/// checkpoint.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize)]
+pub(crate) struct CacheKey {
+    query: String,
+    operation_name: Option<String>,
+    variables: String,
+    api_key: Option<String>,
+    show_usage: bool,
+}
+
+/// A successful response, as it is cached.
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    data: Value,
+    extensions: BTreeMap<String, Value>,
+}
+
+/// Where cached responses are kept.
+#[async_trait::async_trait]
+trait CacheStore: Send + Sync {
+    /// The response cached under `key` for `checkpoint_viewed_at`, if there is one that has not
+    /// expired.
+    async fn get(
+        &self,
+        key: &CacheKey,
+        checkpoint_viewed_at: u64,
+        now: Instant,
+    ) -> anyhow::Result<Option<CachedResponse>>;
+
+    /// Cache `response` under `key`, as the response served at `checkpoint_viewed_at`.
+    async fn insert(
+        &self,
+        key: CacheKey,
+        checkpoint_viewed_at: u64,
+        response: CachedResponse,
+        now: Instant,
+    ) -> anyhow::Result<()>;
+}
+
+/// Caches responses in an LRU cache in the memory of this instance of the service. It only holds
+/// responses for the latest checkpoint it has seen: it is cleared as soon as a request is served
+/// at a later checkpoint.
+struct MemoryCacheStore {
+    inner: Mutex<Inner>,
+    ttl: Option<Duration>,
+}
+
+struct Inner {
+    checkpoint_viewed_at: u64,
+    entries: LruCache<CacheKey, Entry>,
+}
+
+struct Entry {
+    cached_at: Instant,
+    response: CachedResponse,
+}
+
+/// Caches responses in Redis, where they are shared by all instances of the service. Responses
+/// are cached under their checkpoint, so that they stop being served once the watermark advances,
+/// and expire after the configured TTL. The number of responses cached is bounded by the eviction
+/// policy of the Redis instance, rather than by the capacity of the cache.
+struct RedisCacheStore {
+    redis: RedisStore,
+    ttl: Duration,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: &ResponseCacheConfig, metrics: Arc<RequestMetrics>) -> Self {
+        let ttl = config.ttl_ms.map(Duration::from_millis);
+        let store: Arc<dyn CacheStore> = match &config.backend {
+            StoreBackend::Memory => {
+                let capacity = NonZeroUsize::new(config.capacity).unwrap_or(NonZeroUsize::MIN);
+                Arc::new(MemoryCacheStore {
+                    inner: Mutex::new(Inner {
+                        checkpoint_viewed_at: 0,
+                        entries: LruCache::new(capacity),
+                    }),
+                    ttl,
+                })
+            }
+            StoreBackend::Redis(url) => Arc::new(RedisCacheStore {
+                redis: RedisStore::new(url, "response-cache")
+                    .expect("Redis URL is validated when the config is read"),
+                ttl: ttl.unwrap_or(DEFAULT_REDIS_TTL),
+            }),
+        };
+
+        Self { store, metrics }
+    }
+
+    /// The key to cache the response to `request` under, or `None` if its response should not be
+    /// cached: because it is not a well-formed query (mutations and subscriptions are not cached,
+    /// and malformed requests are left for the schema to reject).
+    ///
+    /// Requests made with different API keys are cached separately, because some fields are only
+    /// accessible to certain keys, and so are requests that ask to be shown their usage.
+    pub(crate) fn key(
+        request: &Request,
+        api_key: Option<&ApiKey>,
+        show_usage: bool,
+    ) -> Option<CacheKey> {
+        let doc = parse_query(&request.query).ok()?;
+        if !doc
+            .operations
+            .iter()
+            .all(|(_, op)| op.node.ty == OperationType::Query)
+        {
+            return None;
+        }
+
+        Some(CacheKey {
+            query: request.query.clone(),
+            operation_name: request.operation_name.clone(),
+            variables: serde_json::to_string(&request.variables).ok()?,
+            api_key: api_key.map(|ApiKey(key)| key.clone()),
+            show_usage,
+        })
+    }
+
+    /// The cached response for `key`, if there is one for `checkpoint_viewed_at` that has not
+    /// expired.
+    pub(crate) async fn get(
+        &self,
+        key: &CacheKey,
+        checkpoint_viewed_at: u64,
+        now: Instant,
+    ) -> Option<Response> {
+        let hit = match self.store.get(key, checkpoint_viewed_at, now).await {
+            Ok(hit) => hit,
+            Err(e) => {
+                warn!("Failed to read cached response: {e}");
+                None
+            }
+        };
+
+        if hit.is_some() {
+            self.metrics.response_cache_hits.inc();
+        } else {
+            self.metrics.response_cache_misses.inc();
+        }
+
+        hit.map(|cached| {
+            let mut response = Response::new(cached.data);
+            response.extensions = cached.extensions;
+            response
+        })
+    }
+
+    /// Cache `response` under `key`, as the response served at `checkpoint_viewed_at`. Only
+    /// successful responses are cached, so that transient errors are not repeated.
+    pub(crate) async fn insert(
+        &self,
+        key: CacheKey,
+        checkpoint_viewed_at: u64,
+        response: &Response,
+        now: Instant,
+    ) {
+        if response.is_err() {
+            return;
+        }
+
+        let cached = CachedResponse {
+            data: response.data.clone(),
+            extensions: response.extensions.clone(),
+        };
+        if let Err(e) = self
+            .store
+            .insert(key, checkpoint_viewed_at, cached, now)
+            .await
+        {
+            warn!("Failed to cache response: {e}");
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheStore for MemoryCacheStore {
+    async fn get(
+        &self,
+        key: &CacheKey,
+        checkpoint_viewed_at: u64,
+        now: Instant,
+    ) -> anyhow::Result<Option<CachedResponse>> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.advance(checkpoint_viewed_at);
+
+        let expired = inner
+            .entries
+            .peek(key)
+            .map(|entry| self.is_expired(entry, now));
+        Ok(match expired {
+            _ if inner.checkpoint_viewed_at != checkpoint_viewed_at => None,
+            Some(true) => {
+                inner.entries.pop(key);
+                None
+            }
+            Some(false) => inner.entries.get(key).map(|entry| entry.response.clone()),
+            None => None,
+        })
+    }
+
+    async fn insert(
+        &self,
+        key: CacheKey,
+        checkpoint_viewed_at: u64,
+        response: CachedResponse,
+        now: Instant,
+    ) -> anyhow::Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.advance(checkpoint_viewed_at);
+
+        // The response is for a checkpoint the cache has already moved on from.
+        if inner.checkpoint_viewed_at != checkpoint_viewed_at {
+            return Ok(());
+        }
+
+        inner.entries.put(
+            key,
+            Entry {
+                cached_at: now,
+                response,
+            },
+        );
+        Ok(())
+    }
+}
+
+impl MemoryCacheStore {
+    fn is_expired(&self, entry: &Entry, now: Instant) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now.saturating_duration_since(entry.cached_at) >= ttl)
+    }
+}
+
+impl Inner {
+    /// Invalidate all cached responses if the watermark has advanced past the checkpoint they
+    /// were served at.
+    fn advance(&mut self, checkpoint_viewed_at: u64) {
+        if checkpoint_viewed_at > self.checkpoint_viewed_at {
+            self.checkpoint_viewed_at = checkpoint_viewed_at;
+            self.entries.clear();
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheStore for RedisCacheStore {
+    async fn get(
+        &self,
+        key: &CacheKey,
+        checkpoint_viewed_at: u64,
+        _now: Instant,
+    ) -> anyhow::Result<Option<CachedResponse>> {
+        let mut connection = self.redis.connection().await?;
+        let cached: Option<String> = connection
+            .get(self.redis_key(key, checkpoint_viewed_at)?)
+            .await?;
+        Ok(cached.map(|c| serde_json::from_str(&c)).transpose()?)
+    }
+
+    async fn insert(
+        &self,
+        key: CacheKey,
+        checkpoint_viewed_at: u64,
+        response: CachedResponse,
+        _now: Instant,
+    ) -> anyhow::Result<()> {
+        let mut connection = self.redis.connection().await?;
+        connection
+            .pset_ex::<_, _, ()>(
+                self.redis_key(&key, checkpoint_viewed_at)?,
+                serde_json::to_string(&response)?,
+                self.ttl.as_millis() as u64,
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+impl RedisCacheStore {
+    /// The Redis key of the response to `key` at `checkpoint_viewed_at`. Queries can be large, so
+    /// the key holds a digest of the query (and the rest of the cache key) instead.
+    fn redis_key(&self, key: &CacheKey, checkpoint_viewed_at: u64) -> anyhow::Result<String> {
+        let digest = Blake2b256::digest(serde_json::to_vec(key)?).digest;
+        Ok(self
+            .redis
+            .key(&format!("{checkpoint_viewed_at}:{}", hex::encode(digest))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{value, ServerError, Variables};
+    use prometheus::Registry;
+
+    use crate::metrics::Metrics;
+
+    use super::*;
+
+    fn cache(capacity: usize, ttl_ms: Option<u64>) -> ResponseCache {
+        let metrics = Metrics::new(&Registry::new());
+        ResponseCache::new(
+            &ResponseCacheConfig {
+                capacity,
+                ttl_ms,
+                ..Default::default()
+            },
+            metrics.request_metrics,
+        )
+    }
+
+    fn response(n: i32) -> Response {
+        Response::new(value!({ "n": n }))
+    }
+
+    fn key(query: &str) -> CacheKey {
+        ResponseCache::key(&Request::new(query), None, false).unwrap()
+    }
+
+    #[test]
+    fn test_only_queries_are_cached() {
+        assert!(ResponseCache::key(&Request::new("{ chainIdentifier }"), None, false).is_some());
+        assert!(ResponseCache::key(
+            &Request::new(
+                "mutation { executeTransactionBlock(txBytes: \"\", signatures: []) { errors } }"
+            ),
+            None,
+            false,
+        )
+        .is_none());
+        assert!(ResponseCache::key(&Request::new("{ chainIdentifier"), None, false).is_none());
+    }
+
+    #[test]
+    fn test_key_distinguishes_requests() {
+        let query = "query ($n: Int) { epoch(id: $n) { epochId } }";
+        let request =
+            |n: i32| Request::new(query).variables(Variables::from_value(value!({ "n": n })));
+        let api_key = ApiKey("a".to_string());
+
+        let base = ResponseCache::key(&request(1), None, false);
+        assert_eq!(base, ResponseCache::key(&request(1), None, false));
+        assert_ne!(base, ResponseCache::key(&request(2), None, false));
+        assert_ne!(base, ResponseCache::key(&request(1), Some(&api_key), false));
+        assert_ne!(base, ResponseCache::key(&request(1), None, true));
+    }
+
+    #[tokio::test]
+    async fn test_cached_per_checkpoint() {
+        let cache = cache(10, None);
+        let now = Instant::now();
+
+        assert!(cache.get(&key("{ a }"), 1, now).await.is_none());
+        cache.insert(key("{ a }"), 1, &response(1), now).await;
+        assert_eq!(
+            cache.get(&key("{ a }"), 1, now).await.unwrap().data,
+            response(1).data
+        );
+
+        // Advancing the watermark invalidates cached responses...
+        assert!(cache.get(&key("{ a }"), 2, now).await.is_none());
+        cache.insert(key("{ a }"), 2, &response(2), now).await;
+
+        // ...and responses for earlier checkpoints are neither served nor cached.
+        assert!(cache.get(&key("{ a }"), 1, now).await.is_none());
+        cache.insert(key("{ b }"), 1, &response(1), now).await;
+        assert!(cache.get(&key("{ b }"), 2, now).await.is_none());
+
+        assert_eq!(
+            cache.get(&key("{ a }"), 2, now).await.unwrap().data,
+            response(2).data
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let cache = cache(10, None);
+        let now = Instant::now();
+
+        let error = Response::from_errors(vec![ServerError::new("Boom", None)]);
+        cache.insert(key("{ a }"), 1, &error, now).await;
+        assert!(cache.get(&key("{ a }"), 1, now).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cached_responses_expire() {
+        let cache = cache(10, Some(1_000));
+        let now = Instant::now();
+
+        cache.insert(key("{ a }"), 1, &response(1), now).await;
+        assert!(cache
+            .get(&key("{ a }"), 1, now + Duration::from_millis(999))
+            .await
+            .is_some());
+        assert!(cache
+            .get(&key("{ a }"), 1, now + Duration::from_millis(1_000))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_capacity_is_bounded() {
+        let cache = cache(1, None);
+        let now = Instant::now();
+
+        cache.insert(key("{ a }"), 1, &response(1), now).await;
+        cache.insert(key("{ b }"), 1, &response(2), now).await;
+        assert!(cache.get(&key("{ a }"), 1, now).await.is_none());
+        assert!(cache.get(&key("{ b }"), 1, now).await.is_some());
+    }
+
+    #[test]
+    fn test_redis_key_is_per_checkpoint() {
+        let store = RedisCacheStore {
+            redis: RedisStore::new("redis://127.0.0.1:6379", "response-cache").unwrap(),
+            ttl: DEFAULT_REDIS_TTL,
+        };
+
+        let a1 = store.redis_key(&key("{ a }"), 1).unwrap();
+        assert!(a1.starts_with("sui-graphql-rpc:response-cache:1:"));
+        assert_eq!(a1, store.redis_key(&key("{ a }"), 1).unwrap());
+        assert_ne!(a1, store.redis_key(&key("{ a }"), 2).unwrap());
+        assert_ne!(a1, store.redis_key(&key("{ b }"), 1).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_redis_is_a_miss() {
+        let metrics = Metrics::new(&Registry::new());
+        let cache = ResponseCache::new(
+            &ResponseCacheConfig {
+                capacity: 10,
+                // Nothing listens on this port.
+                backend: StoreBackend::Redis("redis://127.0.0.1:1".to_string()),
+                ..Default::default()
+            },
+            metrics.request_metrics.clone(),
+        );
+        let now = Instant::now();
+
+        cache.insert(key("{ a }"), 1, &response(1), now).await;
+        assert!(cache.get(&key("{ a }"), 1, now).await.is_none());
+        assert_eq!(metrics.request_metrics.response_cache_misses.get(), 1);
+    }
+}
@@ -0,0 +1,550 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_graphql::connection::{Connection, CursorType};
+use async_graphql::*;
+use futures::{future, stream, Stream, StreamExt};
+
+use crate::config::ServiceConfig;
+use crate::data::Db;
+use crate::error::Error;
+use crate::functional_group::FunctionalGroup;
+use crate::metrics::Metrics;
+use crate::server::builder::CheckpointWatermark;
+use crate::types::checkpoint::{self, Checkpoint, CheckpointCursor};
+use crate::types::cursor::{JsonCursor, Page};
+use crate::types::event::{self, Event, EventFilter, EventKey};
+use crate::types::sui_address::SuiAddress;
+use crate::types::transaction_block::{
+    self, TransactionBlock, TransactionBlockCursor, TransactionBlockFilter, TransactionBlockInner,
+};
+use crate::types::transaction_block_effects::TransactionBlockEffects;
+
+/// Number of recently streamed transactions remembered by `transactionEffects`, to avoid streaming
+/// a transaction that was both sent and received by the address twice.
+const RECENT_TRANSACTIONS: usize = 1000;
+
+pub struct Subscription;
+
+/// Subscriptions stream data to clients over a WebSocket connection to the `/subscriptions`
+/// endpoint, as it is indexed. Each subscription starts after the latest data available when it is
+/// made, and is served at the same checkpoints as queries: whenever the service's view of the
+/// chain advances, the data added up to the new checkpoint is streamed in order.
+#[Subscription]
+impl Subscription {
+    /// Checkpoints, as they are indexed.
+    async fn new_checkpoints(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<impl Stream<Item = Result<Checkpoint>>> {
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let slot = SubscriptionSlot::acquire(ctx, "newCheckpoints")?;
+        tail(ctx, slot, move |page, checkpoint_viewed_at| {
+            let db = db.clone();
+            async move { Checkpoint::paginate(&db, page, None, Some(checkpoint_viewed_at)).await }
+        })
+        .await
+    }
+
+    /// Events matching `filter`, as they are indexed.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>>> {
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let filter = filter.unwrap_or_default();
+        let slot = SubscriptionSlot::acquire(ctx, "events")?;
+        tail(ctx, slot, move |page, checkpoint_viewed_at| {
+            let (db, filter) = (db.clone(), filter.clone());
+            async move { Event::paginate(&db, page, filter, Some(checkpoint_viewed_at)).await }
+        })
+        .await
+    }
+
+    /// The effects of transactions signed or received by `address`, as they are indexed.
+    async fn transaction_effects(
+        &self,
+        ctx: &Context<'_>,
+        address: SuiAddress,
+    ) -> Result<impl Stream<Item = Result<TransactionBlockEffects>>> {
+        // Both streams count as a single subscription.
+        let slot = SubscriptionSlot::acquire(ctx, "transactionEffects")?;
+        let sent = transactions(
+            ctx,
+            slot.clone(),
+            TransactionBlockFilter {
+                sign_address: Some(address),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let received = transactions(
+            ctx,
+            slot,
+            TransactionBlockFilter {
+                recv_address: Some(address),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        let mut recent = RecentTransactions::default();
+        Ok(stream::select(sent, received).filter_map(move |tx| {
+            let tx = match tx {
+                Ok(tx) if !recent.insert(&tx) => None,
+                Ok(tx) => Some(TransactionBlockEffects::try_from(tx).extend()),
+                Err(e) => Some(Err(e)),
+            };
+            future::ready(tx)
+        }))
+    }
+}
+
+async fn transactions(
+    ctx: &Context<'_>,
+    slot: Arc<SubscriptionSlot>,
+    filter: TransactionBlockFilter,
+) -> Result<impl Stream<Item = Result<TransactionBlock>>> {
+    let db: Db = ctx.data_unchecked::<Db>().clone();
+    tail(ctx, slot, move |page, checkpoint_viewed_at| {
+        let (db, filter) = (db.clone(), filter.clone());
+        async move { TransactionBlock::paginate(&db, page, filter, Some(checkpoint_viewed_at)).await }
+    })
+    .await
+}
+
+/// The subscriptions active on a WebSocket connection. A fresh instance is added to the data of
+/// each connection, to limit the number of subscriptions that can be made on it.
+#[derive(Clone, Default)]
+pub(crate) struct ConnectionSubscriptions(Arc<AtomicUsize>);
+
+/// Holds one of the subscriptions allowed on a connection, and keeps the subscription metrics up to
+/// date, for as long as the subscription's streams are alive.
+struct SubscriptionSlot {
+    connection: Option<ConnectionSubscriptions>,
+    metrics: Metrics,
+    field: &'static str,
+}
+
+/// Cursors that can be moved to a later checkpoint, to page through the entries of a connection
+/// that were added since the entry they point to.
+trait Revisit {
+    fn revisit_at(self, checkpoint_viewed_at: u64) -> Self;
+}
+
+/// The state of a subscription between two pages of entries.
+struct Tail<C, F> {
+    config: ServiceConfig,
+    watermark: CheckpointWatermark,
+    /// The checkpoint that entries are being streamed at.
+    checkpoint_viewed_at: u64,
+    /// Whether all the entries up to `checkpoint_viewed_at` have been streamed.
+    caught_up: bool,
+    /// The last checkpoint that all entries were streamed at, to measure how far behind the
+    /// subscription is.
+    caught_up_at: u64,
+    /// The cursor of the last entry streamed, or `None` if no entry has been streamed yet, and
+    /// none existed when subscribing.
+    after: Option<C>,
+    paginate: F,
+    slot: Arc<SubscriptionSlot>,
+}
+
+/// Streams the entries of a connection, as they are added. `paginate` fetches a page of the
+/// connection at a given checkpoint.
+async fn tail<C, T, F, Fut>(
+    ctx: &Context<'_>,
+    slot: Arc<SubscriptionSlot>,
+    paginate: F,
+) -> Result<impl Stream<Item = Result<T>>>
+where
+    C: CursorType + Revisit + Clone + Send + Sync + 'static,
+    T: Send + 'static,
+    F: Fn(Page<C>, u64) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Connection<String, T>, Error>> + Send,
+{
+    let config: &ServiceConfig = ctx.data_unchecked();
+    let watermark: &CheckpointWatermark = ctx
+        .data()
+        .map_err(|_| Error::Internal("Unable to fetch checkpoint watermark".to_string()))
+        .extend()?;
+
+    // Start after the latest entry at the time of subscribing.
+    let checkpoint_viewed_at = watermark.0.load(Ordering::Relaxed);
+    let latest = paginate(
+        Page::from_params(config, None, None, Some(1), None)?,
+        checkpoint_viewed_at,
+    )
+    .await
+    .extend()?;
+    let after = match latest.edges.last() {
+        Some(edge) => Some(decode_cursor(&edge.cursor)?),
+        None => None,
+    };
+
+    let tail = Tail {
+        config: config.clone(),
+        watermark: watermark.clone(),
+        checkpoint_viewed_at,
+        caught_up: true,
+        caught_up_at: checkpoint_viewed_at,
+        after,
+        paginate,
+        slot,
+    };
+
+    // Once the stream fails, it ends.
+    let pages = stream::unfold(Some(tail), |tail| async move {
+        let mut tail = tail?;
+        match tail.next_entries().await {
+            Ok(entries) => Some((entries.into_iter().map(Ok).collect(), Some(tail))),
+            Err(e) => Some((vec![Err(e)], None)),
+        }
+    });
+    Ok(pages.flat_map(stream::iter))
+}
+
+impl<C, T, F, Fut> Tail<C, F>
+where
+    C: CursorType + Revisit + Clone + Send + Sync + 'static,
+    F: Fn(Page<C>, u64) -> Fut,
+    Fut: Future<Output = Result<Connection<String, T>, Error>>,
+{
+    /// Returns the next page of entries. Once all the entries up to the checkpoint being viewed
+    /// have been streamed, waits for the checkpoint watermark to advance, and pages through the
+    /// entries added up to the new watermark. Fails if the subscription has fallen too far behind
+    /// the watermark, because its subscriber does not keep up with the entries streamed.
+    async fn next_entries(&mut self) -> Result<Vec<T>> {
+        if self.caught_up {
+            let poll_interval =
+                Duration::from_millis(self.config.background_tasks.watermark_update_ms.max(1));
+            self.checkpoint_viewed_at = loop {
+                let watermark = self.watermark.0.load(Ordering::Relaxed);
+                if watermark > self.checkpoint_viewed_at {
+                    break watermark;
+                }
+                tokio::time::sleep(poll_interval).await;
+            };
+        }
+
+        let lag = self
+            .watermark
+            .0
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.caught_up_at);
+        let max_lag = self.config.limits.max_subscription_lag_checkpoints;
+        if lag > max_lag {
+            self.slot.lagged();
+            return Err(Error::Client(format!(
+                "Subscription fell {lag} checkpoints behind the latest checkpoint, more than the \
+                 maximum of {max_lag}. Subscribe again to stream from the latest checkpoint."
+            )))
+            .extend();
+        }
+
+        let after = self
+            .after
+            .clone()
+            .map(|cursor| cursor.revisit_at(self.checkpoint_viewed_at));
+        let page = Page::from_params(
+            &self.config,
+            Some(self.config.limits.max_page_size),
+            after,
+            None,
+            None,
+        )?;
+
+        let connection = (self.paginate)(page, self.checkpoint_viewed_at)
+            .await
+            .extend()?;
+        let mut entries = Vec::with_capacity(connection.edges.len());
+        for edge in connection.edges {
+            self.after = Some(decode_cursor(&edge.cursor)?);
+            entries.push(edge.node);
+        }
+
+        self.caught_up = !connection.has_next_page;
+        if self.caught_up {
+            self.caught_up_at = self.checkpoint_viewed_at;
+        }
+        self.slot.streamed(entries.len());
+        Ok(entries)
+    }
+}
+
+fn decode_cursor<C: CursorType>(cursor: &str) -> Result<C> {
+    C::decode_cursor(cursor)
+        .map_err(|e| Error::Internal(format!("Failed to decode cursor: {e}")))
+        .extend()
+}
+
+impl SubscriptionSlot {
+    fn acquire(ctx: &Context<'_>, field: &'static str) -> Result<Arc<Self>> {
+        let config: &ServiceConfig = ctx.data_unchecked();
+        let metrics: &Metrics = ctx.data_unchecked();
+        let connection = ctx.data_opt::<ConnectionSubscriptions>().cloned();
+        Self::reserve(config, metrics, connection, field)
+    }
+
+    /// Reserves one of the subscriptions allowed on `connection`, if there is one left.
+    fn reserve(
+        config: &ServiceConfig,
+        metrics: &Metrics,
+        connection: Option<ConnectionSubscriptions>,
+        field: &'static str,
+    ) -> Result<Arc<Self>> {
+        let group = FunctionalGroup::Subscriptions;
+        if config.disabled_features.contains(&group) {
+            return Err(Error::Client(format!(
+                "Cannot subscribe to \"{field}\". Feature {} is disabled.",
+                group.name(),
+            )))
+            .extend();
+        }
+
+        if let Some(ConnectionSubscriptions(active)) = &connection {
+            let max = config.limits.max_subscriptions_per_connection as usize;
+            let reserved = active.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |active| {
+                (active < max).then_some(active + 1)
+            });
+            if reserved.is_err() {
+                metrics.subscription_metrics.subscriptions_rejected.inc();
+                return Err(Error::Client(format!(
+                    "Too many subscriptions on this connection. The maximum allowed is {max}."
+                )))
+                .extend();
+            }
+        }
+
+        metrics
+            .subscription_metrics
+            .subscriptions_started
+            .with_label_values(&[field])
+            .inc();
+        metrics.subscription_metrics.active_subscriptions.inc();
+        Ok(Arc::new(Self {
+            connection,
+            metrics: metrics.clone(),
+            field,
+        }))
+    }
+
+    fn streamed(&self, entries: usize) {
+        self.metrics
+            .subscription_metrics
+            .subscription_items
+            .with_label_values(&[self.field])
+            .inc_by(entries as u64);
+    }
+
+    fn lagged(&self) {
+        self.metrics
+            .subscription_metrics
+            .subscriptions_lagged
+            .with_label_values(&[self.field])
+            .inc();
+    }
+}
+
+impl Drop for SubscriptionSlot {
+    fn drop(&mut self) {
+        if let Some(ConnectionSubscriptions(active)) = &self.connection {
+            active.fetch_sub(1, Ordering::SeqCst);
+        }
+        self.metrics.subscription_metrics.active_subscriptions.dec();
+    }
+}
+
+impl Revisit for checkpoint::Cursor {
+    fn revisit_at(self, checkpoint_viewed_at: u64) -> Self {
+        JsonCursor::new(CheckpointCursor {
+            checkpoint_viewed_at,
+            ..(*self).clone()
+        })
+    }
+}
+
+impl Revisit for event::Cursor {
+    fn revisit_at(self, checkpoint_viewed_at: u64) -> Self {
+        JsonCursor::new(EventKey {
+            checkpoint_viewed_at,
+            ..(*self).clone()
+        })
+    }
+}
+
+impl Revisit for transaction_block::Cursor {
+    fn revisit_at(self, checkpoint_viewed_at: u64) -> Self {
+        JsonCursor::new(TransactionBlockCursor {
+            checkpoint_viewed_at,
+            ..(*self).clone()
+        })
+    }
+}
+
+/// Bounded set of the sequence numbers of recently streamed transactions.
+#[derive(Default)]
+struct RecentTransactions {
+    sequence_numbers: HashSet<i64>,
+    order: VecDeque<i64>,
+}
+
+impl RecentTransactions {
+    /// Returns whether `tx` was not streamed recently.
+    fn insert(&mut self, tx: &TransactionBlock) -> bool {
+        let TransactionBlockInner::Stored { stored_tx, .. } = &tx.inner else {
+            return true;
+        };
+
+        let sequence_number = stored_tx.tx_sequence_number;
+        if !self.sequence_numbers.insert(sequence_number) {
+            return false;
+        }
+
+        self.order.push_back(sequence_number);
+        if self.order.len() > RECENT_TRANSACTIONS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.sequence_numbers.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicU64;
+
+    use async_graphql::connection::Edge;
+    use prometheus::Registry;
+
+    use super::*;
+
+    fn config(max_page_size: u64, max_subscription_lag_checkpoints: u64) -> ServiceConfig {
+        let mut config = ServiceConfig::test_defaults();
+        config.limits.max_page_size = max_page_size;
+        config.limits.max_subscription_lag_checkpoints = max_subscription_lag_checkpoints;
+        config.limits.max_subscriptions_per_connection = 1;
+        config
+    }
+
+    type Fut = future::Ready<Result<Connection<String, u64>, Error>>;
+
+    /// Pages through a connection holding one entry per checkpoint, its sequence number.
+    fn paginate(page: Page<checkpoint::Cursor>, checkpoint_viewed_at: u64) -> Fut {
+        let first = page.after().map_or(0, |c| c.sequence_number + 1);
+        let last = checkpoint_viewed_at.min(first + page.limit() as u64 - 1);
+        let mut connection = Connection::new(first > 0, last < checkpoint_viewed_at);
+        for sequence_number in first..=last {
+            let cursor = JsonCursor::new(CheckpointCursor {
+                checkpoint_viewed_at,
+                sequence_number,
+            });
+            connection
+                .edges
+                .push(Edge::new(cursor.encode_cursor(), sequence_number));
+        }
+        future::ready(Ok(connection))
+    }
+
+    /// A tail of checkpoints, subscribed to at checkpoint 0.
+    fn checkpoints(
+        config: ServiceConfig,
+        watermark: &CheckpointWatermark,
+    ) -> Tail<checkpoint::Cursor, fn(Page<checkpoint::Cursor>, u64) -> Fut> {
+        let slot = SubscriptionSlot::reserve(
+            &config,
+            &Metrics::new(&Registry::new()),
+            None,
+            "newCheckpoints",
+        )
+        .unwrap();
+        Tail {
+            config,
+            watermark: watermark.clone(),
+            checkpoint_viewed_at: 0,
+            caught_up: true,
+            caught_up_at: 0,
+            after: Some(JsonCursor::new(CheckpointCursor {
+                checkpoint_viewed_at: 0,
+                sequence_number: 0,
+            })),
+            paginate: paginate as fn(_, _) -> _,
+            slot,
+        }
+    }
+
+    fn watermark(checkpoint: u64) -> CheckpointWatermark {
+        CheckpointWatermark(Arc::new(AtomicU64::new(checkpoint)))
+    }
+
+    #[tokio::test]
+    async fn test_stream_entries_one_page_at_a_time() {
+        let watermark = watermark(0);
+        let mut tail = checkpoints(config(2, 100), &watermark);
+
+        watermark.0.store(5, Ordering::Relaxed);
+        assert_eq!(tail.next_entries().await.unwrap(), vec![1, 2]);
+        assert_eq!(tail.next_entries().await.unwrap(), vec![3, 4]);
+        assert_eq!(tail.next_entries().await.unwrap(), vec![5]);
+
+        // Waits for the watermark to advance again.
+        let next = tokio::time::timeout(Duration::from_millis(500), tail.next_entries());
+        assert!(next.await.is_err());
+
+        watermark.0.store(6, Ordering::Relaxed);
+        assert_eq!(tail.next_entries().await.unwrap(), vec![6]);
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_lagging_subscriber() {
+        let watermark = watermark(0);
+        let mut tail = checkpoints(config(2, 5), &watermark);
+
+        watermark.0.store(4, Ordering::Relaxed);
+        assert_eq!(tail.next_entries().await.unwrap(), vec![1, 2]);
+        assert_eq!(tail.next_entries().await.unwrap(), vec![3, 4]);
+
+        // The subscriber has caught up with checkpoint 4, and falls behind again.
+        watermark.0.store(8, Ordering::Relaxed);
+        assert_eq!(tail.next_entries().await.unwrap(), vec![5, 6]);
+        watermark.0.store(10, Ordering::Relaxed);
+        let err = tail.next_entries().await.unwrap_err();
+        assert!(
+            err.message.contains("fell 6 checkpoints behind"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_subscription_slots_per_connection() {
+        let config = config(2, 5);
+        let metrics = Metrics::new(&Registry::new());
+        let connection = ConnectionSubscriptions::default();
+        let reserve = || {
+            SubscriptionSlot::reserve(
+                &config,
+                &metrics,
+                Some(connection.clone()),
+                "transactionEffects",
+            )
+        };
+
+        // A slot shared by the streams of a subscription is released with the last of them.
+        let slot = reserve().unwrap();
+        let shared = slot.clone();
+        assert!(reserve().is_err());
+        drop(slot);
+        assert!(reserve().is_err());
+        drop(shared);
+        assert!(reserve().is_ok());
+    }
+}
@@ -4,7 +4,7 @@
 use super::{
     balance::{self, Balance},
     coin::Coin,
-    cursor::Page,
+    cursor::{Page, SortOrder},
     move_object::MoveObject,
     object::{self, ObjectFilter},
     owner::OwnerImpl,
@@ -145,9 +145,11 @@ impl Address {
         before: Option<transaction_block::Cursor>,
         relation: Option<AddressTransactionBlockRelationship>,
         filter: Option<TransactionBlockFilter>,
+        order: Option<SortOrder>,
     ) -> Result<Connection<String, TransactionBlock>> {
         use AddressTransactionBlockRelationship as R;
-        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        let page =
+            Page::from_params(ctx.data_unchecked(), first, after, last, before)?.with_order(order);
 
         let Some(filter) = filter.unwrap_or_default().intersect(match relation {
             // Relationship defaults to "signer" if none is supplied.
@@ -5,7 +5,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use super::{
     base64::Base64,
-    cursor::{self, Page, Paginated, Target},
+    cursor::{self, Page, Paginated, SortOrder, Target},
     date_time::DateTime,
     digest::Digest,
     epoch::Epoch,
@@ -156,8 +156,10 @@ impl Checkpoint {
         last: Option<u64>,
         before: Option<transaction_block::Cursor>,
         filter: Option<TransactionBlockFilter>,
+        order: Option<SortOrder>,
     ) -> Result<Connection<String, TransactionBlock>> {
-        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        let page =
+            Page::from_params(ctx.data_unchecked(), first, after, last, before)?.with_order(order);
 
         let Some(filter) = filter
             .unwrap_or_default()
@@ -296,10 +298,15 @@ impl Checkpoint {
         Ok(stored as u64)
     }
 
+    /// The latest checkpoint that every database queries are served from has indexed, so that
+    /// queries bounded by it see the same data, whichever replica they are served from.
     pub(crate) async fn query_latest_checkpoint_sequence_number(db: &Db) -> Result<u64, Error> {
-        db.execute(move |conn| Checkpoint::latest_checkpoint_sequence_number(conn))
+        db.execute_on_each(move |conn| Checkpoint::latest_checkpoint_sequence_number(conn))
             .await
-            .map_err(|e| Error::Internal(format!("Failed to fetch checkpoint: {e}")))
+            .map_err(|e| Error::Internal(format!("Failed to fetch checkpoint: {e}")))?
+            .into_iter()
+            .min()
+            .ok_or_else(|| Error::Internal("Failed to fetch checkpoint".to_string()))
     }
 
     /// Queries the database for the upper bound of the available range supported by the graphql
@@ -46,6 +46,22 @@ pub(crate) struct Page<C> {
     /// this field states whether the entries up to limit are taken fron the `Front` or `Back` of
     /// that range.
     end: End,
+
+    /// The order in which the data-set is traversed. `after`, `before`, `Front` and `Back` are all
+    /// interpreted relative to this order.
+    order: SortOrder,
+}
+
+/// The order in which a paginated connection is traversed. A cursor always points at the same
+/// entry, regardless of the order, so `first`/`after` pages forward and `last`/`before` pages
+/// backward through the connection in the requested order.
+#[derive(Enum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) enum SortOrder {
+    /// From the earliest entry (e.g. lowest checkpoint or sequence number) to the latest.
+    #[default]
+    Ascending,
+    /// From the latest entry (e.g. highest checkpoint or sequence number) to the earliest.
+    Descending,
 }
 
 /// Whether the page is extracted from the beginning or the end of the range bounded by the cursors.
@@ -142,6 +158,7 @@ impl<C> Page<C> {
                 before,
                 limit: limit.unwrap_or(limits.default_page_size),
                 end: End::Front,
+                order: SortOrder::Ascending,
             },
 
             (None, after, Some(limit), before) => Page {
@@ -149,6 +166,7 @@ impl<C> Page<C> {
                 before,
                 limit,
                 end: End::Back,
+                order: SortOrder::Ascending,
             },
         };
 
@@ -159,6 +177,14 @@ impl<C> Page<C> {
         Ok(page)
     }
 
+    /// Traverse the data-set in the given `order` (defaults to ascending).
+    pub(crate) fn with_order(self, order: Option<SortOrder>) -> Self {
+        Page {
+            order: order.unwrap_or_default(),
+            ..self
+        }
+    }
+
     pub(crate) fn after(&self) -> Option<&C> {
         self.after.as_ref()
     }
@@ -277,7 +303,8 @@ impl<C: CursorType + Eq + Clone + Send + Sync + 'static> Page<C> {
         ST: Send + 'static,
         GB: Send + 'static,
     {
-        let page = self.clone();
+        let ascending = self.ascending();
+        let page = ascending.clone();
         let query = move || {
             let mut query = query();
             if let Some(after) = page.after() {
@@ -298,17 +325,19 @@ impl<C: CursorType + Eq + Clone + Send + Sync + 'static> Page<C> {
             vec![]
         } else {
             let mut results = conn.results(query)?;
-            if !self.is_from_front() {
+            if !ascending.is_from_front() {
                 results.reverse();
             }
             results
         };
 
-        Ok(self.paginate_results(
+        let (prev, next, results) = ascending.paginate_results(
             results.first().map(|f| f.cursor(checkpoint_viewed_at)),
             results.last().map(|l| l.cursor(checkpoint_viewed_at)),
             results,
-        ))
+        );
+
+        Ok(self.reorder(prev, next, results))
     }
 
     /// This function is similar to `paginate_query`, but is specifically designed for handling
@@ -327,8 +356,9 @@ impl<C: CursorType + Eq + Clone + Send + Sync + 'static> Page<C> {
     where
         T: Send + RawPaginated<C> + FromSqlRow<Untyped, DieselBackend> + 'static,
     {
-        let new_query = move || {
-            let query = self.apply::<T>(query.clone());
+        let ascending = self.ascending();
+        let new_query = || {
+            let query = ascending.apply::<T>(query.clone());
             query.into_boxed()
         };
 
@@ -337,17 +367,56 @@ impl<C: CursorType + Eq + Clone + Send + Sync + 'static> Page<C> {
             vec![]
         } else {
             let mut results: Vec<T> = conn.results(new_query)?;
-            if !self.is_from_front() {
+            if !ascending.is_from_front() {
                 results.reverse();
             }
             results
         };
 
-        Ok(self.paginate_results(
+        let (prev, next, results) = ascending.paginate_results(
             results.first().map(|f| f.cursor(checkpoint_viewed_at)),
             results.last().map(|l| l.cursor(checkpoint_viewed_at)),
             results,
-        ))
+        );
+
+        Ok(self.reorder(prev, next, results))
+    }
+
+    /// The page containing the same entries as this one, when the data-set is traversed in
+    /// ascending order: the bounds are swapped and the page is drawn from the opposite end.
+    pub(crate) fn ascending(&self) -> Self {
+        match self.order {
+            SortOrder::Ascending => self.clone(),
+            SortOrder::Descending => Page {
+                after: self.before.clone(),
+                before: self.after.clone(),
+                limit: self.limit,
+                end: match self.end {
+                    End::Front => End::Back,
+                    End::Back => End::Front,
+                },
+                order: SortOrder::Ascending,
+            },
+        }
+    }
+
+    /// Convert the results of paginating `self.ascending()` into the results of paginating this
+    /// page, by reversing them (and the sides that further pages were detected on) if this page
+    /// is descending.
+    fn reorder<T>(
+        &self,
+        prev: bool,
+        next: bool,
+        results: vec::IntoIter<T>,
+    ) -> (bool, bool, vec::IntoIter<T>) {
+        match self.order {
+            SortOrder::Ascending => (prev, next, results),
+            SortOrder::Descending => {
+                let mut results: Vec<T> = results.collect();
+                results.reverse();
+                (next, prev, results.into_iter())
+            }
+        }
     }
 
     /// Given the results of a database query, determine whether the result set has a previous and
@@ -361,7 +430,7 @@ impl<C: CursorType + Eq + Clone + Send + Sync + 'static> Page<C> {
         f_cursor: Option<C>,
         l_cursor: Option<C>,
         results: Vec<T>,
-    ) -> (bool, bool, impl Iterator<Item = T>)
+    ) -> (bool, bool, vec::IntoIter<T>)
     where
         T: Send + 'static,
     {
@@ -608,6 +677,7 @@ mod tests {
                 before: None,
                 limit: 20,
                 end: Front,
+                order: Ascending,
             }"#]];
         expect.assert_eq(&format!("{page:#?}"));
     }
@@ -626,6 +696,7 @@ mod tests {
                 before: None,
                 limit: 20,
                 end: Front,
+                order: Ascending,
             }"#]];
         expect.assert_eq(&format!("{page:#?}"));
     }
@@ -644,6 +715,7 @@ mod tests {
                 before: None,
                 limit: 10,
                 end: Front,
+                order: Ascending,
             }"#]];
         expect.assert_eq(&format!("{page:#?}"));
     }
@@ -662,6 +734,7 @@ mod tests {
                 ),
                 limit: 20,
                 end: Front,
+                order: Ascending,
             }"#]];
         expect.assert_eq(&format!("{page:#?}"));
     }
@@ -680,6 +753,7 @@ mod tests {
                 ),
                 limit: 10,
                 end: Back,
+                order: Ascending,
             }"#]];
         expect.assert_eq(&format!("{page:#?}"));
     }
@@ -706,6 +780,7 @@ mod tests {
                 ),
                 limit: 10,
                 end: Front,
+                order: Ascending,
             }"#]];
         expect.assert_eq(&format!("{page:#?}"));
     }
@@ -732,6 +807,7 @@ mod tests {
                 ),
                 limit: 10,
                 end: Back,
+                order: Ascending,
             }"#]];
         expect.assert_eq(&format!("{page:#?}"));
     }
@@ -758,6 +834,7 @@ mod tests {
                 ),
                 limit: 20,
                 end: Front,
+                order: Ascending,
             }"#]];
         expect.assert_eq(&format!("{page:#?}"));
     }
@@ -806,4 +883,38 @@ mod tests {
             }"#]];
         expect.assert_eq(&format!("{err:#?}"));
     }
+
+    #[test]
+    fn test_descending_page() {
+        let config = ServiceConfig::default();
+        let page: Page<JsonCursor<u64>> =
+            Page::from_params(&config, Some(10), Some(JsonCursor::new(42)), None, None)
+                .unwrap()
+                .with_order(Some(SortOrder::Descending));
+
+        let expect = expect![[r#"
+            Page {
+                after: None,
+                before: Some(
+                    42,
+                ),
+                limit: 10,
+                end: Back,
+                order: Ascending,
+            }"#]];
+        expect.assert_eq(&format!("{:#?}", page.ascending()));
+    }
+
+    #[test]
+    fn test_descending_results() {
+        let config = ServiceConfig::default();
+        let page: Page<JsonCursor<u64>> = Page::from_params(&config, None, None, None, None)
+            .unwrap()
+            .with_order(Some(SortOrder::Descending));
+
+        let (prev, next, results) = page.reorder(true, false, vec![1, 2, 3].into_iter());
+        assert!(!prev);
+        assert!(next);
+        assert_eq!(results.collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
 }
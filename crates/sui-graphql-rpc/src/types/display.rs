@@ -5,7 +5,10 @@ use async_graphql::*;
 
 use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
 use move_core_types::annotated_value::{MoveStruct, MoveValue};
-use sui_indexer::{models::display::StoredDisplay, schema::display};
+use sui_indexer::{
+    models::{display::StoredDisplay, objects::ResolvedDisplay},
+    schema::display,
+};
 use sui_types::TypeTag;
 
 use crate::{
@@ -80,6 +83,28 @@ impl Display {
 
         Ok(rendered)
     }
+
+    /// The fields of this `Display` as rendered when the object was indexed, if they were rendered
+    /// with this version of the `Display`. Renderings that failed for some fields are not used, as
+    /// they do not attribute their errors to fields.
+    pub(crate) fn resolved(&self, resolved: &ResolvedDisplay) -> Option<Vec<DisplayEntry>> {
+        let fields = resolved.fields_at(self.stored.version as u16)?;
+        if fields.error.is_some() {
+            return None;
+        }
+
+        let mut data = fields.data?;
+        let event = self.stored.to_display_update_event().ok()?;
+        event
+            .fields
+            .contents
+            .into_iter()
+            .map(|entry| {
+                let value = data.remove(&entry.key)?;
+                Some(DisplayEntry::create_value(entry.key, value))
+            })
+            .collect()
+    }
 }
 
 impl DisplayEntry {
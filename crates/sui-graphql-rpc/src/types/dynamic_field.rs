@@ -4,6 +4,7 @@
 use async_graphql::connection::{Connection, CursorType, Edge};
 use async_graphql::*;
 use move_core_types::annotated_value::{self as A, MoveStruct};
+use move_core_types::language_storage::StructTag;
 use sui_indexer::models::objects::StoredHistoryObject;
 use sui_indexer::types::OwnerType;
 use sui_package_resolver::Resolver;
@@ -58,6 +59,18 @@ impl DynamicField {
     /// The string type, data, and serialized value of the DynamicField's 'name' field.
     /// This field is used to uniquely identify a child of the parent object.
     async fn name(&self, ctx: &Context<'_>) -> Result<Option<MoveValue>> {
+        // Live dynamic fields may have had their name resolved when they were indexed, which saves
+        // resolving the layout of their type.
+        if let ObjectKind::Live(_, stored) = &self.super_.super_.kind {
+            if let Some(bcs) = &stored.df_bcs_name {
+                let struct_tag = StructTag::from(self.super_.native.type_().clone());
+                let type_tag = DynamicFieldInfo::try_extract_field_name(&struct_tag, &self.df_kind)
+                    .map_err(|e| Error::Internal(e.to_string()))
+                    .extend()?;
+                return Ok(Some(MoveValue::new(type_tag, Base64::from(bcs))));
+            }
+        }
+
         let resolver: &Resolver<PackageCache> = ctx
             .data()
             .map_err(|_| Error::Internal("Unable to fetch Package Cache.".to_string()))
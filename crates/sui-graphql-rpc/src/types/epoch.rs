@@ -9,7 +9,7 @@ use crate::error::Error;
 
 use super::big_int::BigInt;
 use super::checkpoint::{self, Checkpoint, CheckpointId};
-use super::cursor::Page;
+use super::cursor::{Page, SortOrder};
 use super::date_time::DateTime;
 use super::protocol_config::ProtocolConfigs;
 use super::system_state_summary::SystemStateSummary;
@@ -235,8 +235,10 @@ impl Epoch {
         last: Option<u64>,
         before: Option<transaction_block::Cursor>,
         filter: Option<TransactionBlockFilter>,
+        order: Option<SortOrder>,
     ) -> Result<Connection<String, TransactionBlock>> {
-        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        let page =
+            Page::from_params(ctx.data_unchecked(), first, after, last, before)?.with_order(order);
 
         #[allow(clippy::unnecessary_lazy_evaluations)] // rust-lang/rust-clippy#9422
         let Some(filter) = filter
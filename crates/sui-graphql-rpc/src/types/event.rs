@@ -45,14 +45,14 @@ pub(crate) struct Event {
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub(crate) struct EventKey {
     /// Transaction Sequence Number
-    tx: u64,
+    pub tx: u64,
 
     /// Event Sequence Number
-    e: u64,
+    pub e: u64,
 
     /// The checkpoint sequence number this was viewed at.
     #[serde(rename = "c")]
-    checkpoint_viewed_at: u64,
+    pub checkpoint_viewed_at: u64,
 }
 
 pub(crate) type Cursor = cursor::JsonCursor<EventKey>;
@@ -13,5 +13,8 @@ pub(crate) struct ExecutionResult {
     /// The effects of the executed transaction. Since the transaction was just executed
     /// and not indexed yet, fields including `balance_changes`, `timestamp` and `checkpoint`
     /// are not available.
+    ///
+    /// Pass the transaction's digest in the `x-sui-rpc-wait-for-transaction` header of
+    /// subsequent requests to ensure that they observe its effects.
     pub effects: TransactionBlockEffects,
 }
@@ -38,6 +38,7 @@ pub(crate) mod owner;
 pub(crate) mod protocol_config;
 pub(crate) mod query;
 pub(crate) mod safe_mode;
+pub(crate) mod service_status;
 pub(crate) mod stake;
 pub(crate) mod stake_subsidy;
 pub(crate) mod storage_fund;
@@ -52,6 +53,7 @@ pub(crate) mod transaction_block_kind;
 pub(crate) mod transaction_metadata;
 pub(crate) mod type_filter;
 pub(crate) mod unchanged_shared_object;
+pub(crate) mod usage_report;
 pub(crate) mod validator;
 pub(crate) mod validator_credentials;
 pub(crate) mod validator_set;
@@ -111,7 +111,9 @@ pub(crate) struct ObjectFilter {
     /// type name.
     ///
     /// Generic types can be queried by either the generic type name, e.g. `0x2::coin::Coin`, or by
-    /// the full type name, such as `0x2::coin::Coin<0x2::sui::SUI>`.
+    /// the full type name, such as `0x2::coin::Coin<0x2::sui::SUI>`, or by a type name in which
+    /// some type parameters are wildcards, such as `0x2::coin::Coin<*>` or
+    /// `0x2::dynamic_field::Field<0x2::object::ID, *>`.
     pub type_: Option<TypeFilter>,
 
     /// Filter for live objects by their current owners.
@@ -650,10 +652,7 @@ impl ObjectImpl<'_> {
             .ok_or_else(|| Error::Internal("Failed to convert object into MoveObject".to_string()))
             .extend()?;
 
-        let (struct_tag, move_struct) = deserialize_move_struct(move_object, ctx.data_unchecked())
-            .await
-            .extend()?;
-
+        let struct_tag = StructTag::from(move_object.type_().clone());
         let Some(display) = Display::query(ctx.data_unchecked(), struct_tag.into())
             .await
             .extend()?
@@ -661,6 +660,17 @@ impl ObjectImpl<'_> {
             return Ok(None);
         };
 
+        // Live objects may have been rendered when they were indexed, which saves resolving the
+        // layout of their type.
+        if let ObjectKind::Live(_, stored) = &self.0.kind {
+            if let Some(rendered) = stored.resolved_display().and_then(|r| display.resolved(&r)) {
+                return Ok(Some(rendered));
+            }
+        }
+
+        let (_, move_struct) = deserialize_move_struct(move_object, ctx.data_unchecked())
+            .await
+            .extend()?;
         Ok(Some(display.render(&move_struct).extend()?))
     }
 }
@@ -745,6 +755,7 @@ impl Object {
         // paginated queries are consistent with the previous query that created the cursor.
         let cursor_viewed_at = page.validate_cursor_consistency()?;
         let checkpoint_viewed_at: Option<u64> = cursor_viewed_at.or(checkpoint_viewed_at);
+        let type_filter = filter.type_.clone();
 
         let response = db
             .execute_repeatable(move |conn| {
@@ -776,6 +787,19 @@ impl Object {
             let cursor = stored.cursor(checkpoint_viewed_at).encode_cursor();
             let object =
                 Object::try_from_stored_history_object(stored, Some(checkpoint_viewed_at))?;
+
+            // Type patterns can over-match in the query, in which case the page is left with
+            // fewer objects than requested.
+            if let Some(type_filter) = &type_filter {
+                let tag = object
+                    .native_impl()
+                    .and_then(|native| native.struct_tag())
+                    .map(|tag| TypeTag::Struct(Box::new(tag)));
+                if tag.map_or(true, |tag| type_filter.rejects(&tag)) {
+                    continue;
+                }
+            }
+
             conn.edges.push(Edge::new(cursor, downcast(object)?));
         }
 
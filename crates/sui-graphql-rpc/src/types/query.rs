@@ -8,7 +8,6 @@ use fastcrypto::encoding::{Base64, Encoding};
 use move_core_types::account_address::AccountAddress;
 use serde::de::DeserializeOwned;
 use sui_json_rpc_types::DevInspectArgs;
-use sui_sdk::SuiClient;
 use sui_types::transaction::{TransactionData, TransactionKind};
 use sui_types::{gas_coin::GAS, transaction::TransactionDataAPI, TypeTag};
 
@@ -20,7 +19,7 @@ use super::{
     checkpoint::{self, Checkpoint, CheckpointId},
     coin::Coin,
     coin_metadata::CoinMetadata,
-    cursor::Page,
+    cursor::{Page, SortOrder},
     digest::Digest,
     dry_run_result::DryRunResult,
     epoch::Epoch,
@@ -29,11 +28,13 @@ use super::{
     object::{self, Object, ObjectFilter, ObjectLookupKey},
     owner::Owner,
     protocol_config::ProtocolConfigs,
+    service_status::{fullnode_client, ServiceStatus},
     sui_address::SuiAddress,
     suins_registration::Domain,
     transaction_block::{self, TransactionBlock, TransactionBlockFilter},
     transaction_metadata::TransactionMetadata,
     type_filter::ExactTypeFilter,
+    usage_report::{self, UsageReport},
 };
 use crate::consistency::{consistent_range, CheckpointViewedAt};
 use crate::data::QueryExecutor;
@@ -41,10 +42,12 @@ use crate::types::base64::Base64 as GraphQLBase64;
 use crate::types::zklogin_verify_signature::verify_zklogin_signature;
 use crate::types::zklogin_verify_signature::ZkLoginIntentScope;
 use crate::types::zklogin_verify_signature::ZkLoginVerifyResult;
-use crate::{config::ServiceConfig, data::Db, error::Error, mutation::Mutation};
+use crate::{
+    config::ServiceConfig, data::Db, error::Error, mutation::Mutation, subscription::Subscription,
+};
 
 pub(crate) struct Query;
-pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
+pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, Subscription>;
 
 #[Object]
 impl Query {
@@ -84,6 +87,12 @@ impl Query {
             .extend()
     }
 
+    /// Which of the service's features are currently available, e.g. whether it can execute
+    /// transactions, or only serves reads.
+    async fn service_status(&self, ctx: &Context<'_>) -> ServiceStatus {
+        ServiceStatus::from_ctx(ctx)
+    }
+
     /// Simulate running a transaction to inspect its effects without
     /// committing to them on-chain.
     ///
@@ -101,6 +110,9 @@ impl Query {
     ///     checks that prevent access to objects that are owned by
     ///     addresses other than the sender, and calling non-public,
     ///     non-entry functions, and some other checks.  Defaults to false.
+    ///
+    /// Fails with a `SERVICE_READ_ONLY` error if the service is not
+    /// connected to a fullnode to run the transaction through.
     async fn dry_run_transaction_block(
         &self,
         ctx: &Context<'_>,
@@ -110,14 +122,7 @@ impl Query {
     ) -> Result<DryRunResult> {
         let skip_checks = skip_checks.unwrap_or(false);
 
-        let sui_sdk_client: &Option<SuiClient> = ctx
-            .data()
-            .map_err(|_| Error::Internal("Unable to fetch Sui SDK client".to_string()))
-            .extend()?;
-        let sui_sdk_client = sui_sdk_client
-            .as_ref()
-            .ok_or_else(|| Error::Internal("Sui SDK client not initialized".to_string()))
-            .extend()?;
+        let sui_sdk_client = fullnode_client(ctx).extend()?;
 
         let (sender_address, tx_kind, gas_price, gas_sponsor, gas_budget, gas_objects) =
             if let Some(TransactionMetadata {
@@ -341,10 +346,12 @@ impl Query {
         last: Option<u64>,
         before: Option<transaction_block::Cursor>,
         filter: Option<TransactionBlockFilter>,
+        order: Option<SortOrder>,
     ) -> Result<Connection<String, TransactionBlock>> {
         let CheckpointViewedAt(checkpoint_viewed_at) = *ctx.data()?;
 
-        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        let page =
+            Page::from_params(ctx.data_unchecked(), first, after, last, before)?.with_order(order);
         TransactionBlock::paginate(
             ctx.data_unchecked(),
             page,
@@ -364,10 +371,12 @@ impl Query {
         last: Option<u64>,
         before: Option<event::Cursor>,
         filter: Option<EventFilter>,
+        order: Option<SortOrder>,
     ) -> Result<Connection<String, Event>> {
         let CheckpointViewedAt(checkpoint_viewed_at) = *ctx.data()?;
 
-        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        let page =
+            Page::from_params(ctx.data_unchecked(), first, after, last, before)?.with_order(order);
         Event::paginate(
             ctx.data_unchecked(),
             page,
@@ -443,6 +452,28 @@ impl Query {
             .extend()
     }
 
+    /// Reports of the usage of this service by each API key, aggregated over reporting periods,
+    /// from the earliest period to the latest. Only available to requests made with an admin API
+    /// key in their `x-sui-rpc-api-key` header.
+    ///
+    /// `apiKey` limits the reports to those of the given API key.
+    async fn usage_reports(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<usage_report::Cursor>,
+        last: Option<u64>,
+        before: Option<usage_report::Cursor>,
+        api_key: Option<String>,
+    ) -> Result<Connection<String, UsageReport>> {
+        UsageReport::check_admin(ctx).extend()?;
+
+        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        UsageReport::paginate(ctx.data_unchecked(), page, api_key)
+            .await
+            .extend()
+    }
+
     /// Verify a zkLogin signature based on the provided transaction or personal message
     /// based on current epoch, chain id, and latest JWKs fetched on-chain. If the
     /// signature is valid, the function returns a `ZkLoginVerifyResult` with success as
@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use sui_sdk::SuiClient;
+
+use crate::{config::ServiceConfig, error::Error};
+
+/// Whether the service can execute transactions, in addition to serving reads.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ServiceMode {
+    /// The service serves reads, and executes and dry-runs transactions through a fullnode.
+    Full,
+    /// The service only serves reads, because it is not connected to a fullnode, or it has been
+    /// configured with mutations disabled.
+    ReadOnly,
+}
+
+/// The current status of the service, indicating which of its features are available.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ServiceStatus {
+    pub connected_to_fullnode: bool,
+    pub mutations_enabled: bool,
+}
+
+#[Object]
+impl ServiceStatus {
+    /// Whether the service can execute transactions (`FULL`), or only serves reads (`READ_ONLY`).
+    async fn mode(&self) -> ServiceMode {
+        if self.connected_to_fullnode && self.mutations_enabled {
+            ServiceMode::Full
+        } else {
+            ServiceMode::ReadOnly
+        }
+    }
+
+    /// Whether the service is connected to a fullnode, to execute and dry-run transactions with.
+    async fn connected_to_fullnode(&self) -> bool {
+        self.connected_to_fullnode
+    }
+
+    /// Whether mutations are part of the service's schema.
+    async fn mutations_enabled(&self) -> bool {
+        self.mutations_enabled
+    }
+}
+
+impl ServiceStatus {
+    pub(crate) fn from_ctx(ctx: &Context<'_>) -> Self {
+        Self {
+            connected_to_fullnode: matches!(ctx.data_opt::<Option<SuiClient>>(), Some(Some(_))),
+            mutations_enabled: mutations_enabled(ctx),
+        }
+    }
+}
+
+/// Whether mutations are part of the schema, which they are unless disabled in the service's
+/// configuration. Used to control the visibility of mutation fields.
+pub(crate) fn mutations_enabled(ctx: &Context<'_>) -> bool {
+    ctx.data_opt::<ServiceConfig>()
+        .map_or(true, |config| !config.disable_mutations)
+}
+
+/// The client for the fullnode that the service executes and dry-runs transactions through, or a
+/// read-only error if the service is not connected to one.
+pub(crate) fn fullnode_client<'ctx>(ctx: &'ctx Context<'_>) -> Result<&'ctx SuiClient, Error> {
+    match ctx.data_opt::<Option<SuiClient>>() {
+        Some(Some(client)) => Ok(client),
+        _ => Err(Error::ReadOnly(
+            "This service is read-only, because it is not connected to a fullnode to run \
+             transactions through"
+                .to_string(),
+        )),
+    }
+}
@@ -10,9 +10,13 @@ use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
 use fastcrypto::encoding::{Base58, Encoding};
 use serde::{Deserialize, Serialize};
 use sui_indexer::{
-    models::transactions::StoredTransaction,
+    models::{
+        transactions::StoredTransaction,
+        tx_address_buckets::{tx_address_bucket, TX_ADDRESS_BUCKET_SIZE},
+    },
     schema::{
-        transactions, tx_calls, tx_changed_objects, tx_input_objects, tx_recipients, tx_senders,
+        checkpoints, transactions, tx_address_buckets, tx_calls, tx_changed_objects,
+        tx_input_objects, tx_recipients, tx_senders,
     },
 };
 use sui_types::{
@@ -28,7 +32,7 @@ use sui_types::{
 
 use crate::{
     consistency::Checkpointed,
-    data::{self, Db, DbConnection, QueryExecutor},
+    data::{self, Conn, Db, DbConnection, QueryExecutor},
     error::Error,
     types::intersect,
 };
@@ -109,6 +113,10 @@ pub(crate) struct TransactionBlockFilter {
     pub transaction_ids: Option<Vec<Digest>>,
 }
 
+/// The max number of buckets of an address's transactions to scan, to bound the range of
+/// transactions to scan for a page of them.
+const MAX_TX_ADDRESS_BUCKETS_SCANNED: i64 = 100;
+
 pub(crate) type Cursor = cursor::JsonCursor<TransactionBlockCursor>;
 type Query<ST, GB> = data::Query<ST, transactions::table, GB>;
 
@@ -275,6 +283,29 @@ impl TransactionBlock {
         }))
     }
 
+    /// Look up the sequence number of the checkpoint that the transaction with the given `digest`
+    /// was included in. Returns `None` if the transaction has not been indexed yet.
+    pub(crate) async fn query_checkpoint_sequence_number(
+        db: &Db,
+        digest: Digest,
+    ) -> Result<Option<u64>, Error> {
+        use transactions::dsl;
+
+        let checkpoint: Option<i64> = db
+            .execute(move |conn| {
+                conn.result(move || {
+                    dsl::transactions
+                        .select(dsl::checkpoint_sequence_number)
+                        .filter(dsl::transaction_digest.eq(digest.to_vec()))
+                })
+                .optional()
+            })
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to fetch transaction checkpoint: {e}")))?;
+
+        Ok(checkpoint.map(|c| c as u64))
+    }
+
     /// Look up multiple `TransactionBlock`s by their digests. Returns a map from those digests to
     /// their resulting transaction blocks, for the blocks that could be found. We return a map
     /// because the order of results from the DB is not otherwise guaranteed to match the order that
@@ -341,12 +372,23 @@ impl TransactionBlock {
                     None => Checkpoint::latest_checkpoint_sequence_number(conn),
                 }?;
 
+                let (lo, hi) =
+                    tx_address_bucket_bounds(conn, &page, &filter, checkpoint_viewed_at)?;
+
                 let result = page.paginate_query::<StoredTransaction, _, _, _>(
                     conn,
                     checkpoint_viewed_at,
                     move || {
                         let mut query = tx::dsl::transactions.into_boxed();
 
+                        if let Some(lo) = lo {
+                            query = query.filter(tx::dsl::tx_sequence_number.ge(lo));
+                        }
+
+                        if let Some(hi) = hi {
+                            query = query.filter(tx::dsl::tx_sequence_number.lt(hi));
+                        }
+
                         if let Some(f) = &filter.function {
                             let sub_query = tx_calls::dsl::tx_calls
                                 .select(tx_calls::dsl::tx_sequence_number)
@@ -382,16 +424,34 @@ impl TransactionBlock {
                         );
 
                         if let Some(a) = &filter.sign_address {
-                            let sub_query = tx_senders::dsl::tx_senders
+                            let mut sub_query = tx_senders::dsl::tx_senders
                                 .select(tx_senders::dsl::tx_sequence_number)
-                                .filter(tx_senders::dsl::sender.eq(a.into_vec()));
+                                .filter(tx_senders::dsl::sender.eq(a.into_vec()))
+                                .into_boxed();
+                            if let Some(lo) = lo {
+                                sub_query =
+                                    sub_query.filter(tx_senders::dsl::tx_sequence_number.ge(lo));
+                            }
+                            if let Some(hi) = hi {
+                                sub_query =
+                                    sub_query.filter(tx_senders::dsl::tx_sequence_number.lt(hi));
+                            }
                             query = query.filter(tx::dsl::tx_sequence_number.eq_any(sub_query));
                         }
 
                         if let Some(a) = &filter.recv_address {
-                            let sub_query = tx_recipients::dsl::tx_recipients
+                            let mut sub_query = tx_recipients::dsl::tx_recipients
                                 .select(tx_recipients::dsl::tx_sequence_number)
-                                .filter(tx_recipients::dsl::recipient.eq(a.into_vec()));
+                                .filter(tx_recipients::dsl::recipient.eq(a.into_vec()))
+                                .into_boxed();
+                            if let Some(lo) = lo {
+                                sub_query =
+                                    sub_query.filter(tx_recipients::dsl::tx_sequence_number.ge(lo));
+                            }
+                            if let Some(hi) = hi {
+                                sub_query =
+                                    sub_query.filter(tx_recipients::dsl::tx_sequence_number.lt(hi));
+                            }
                             query = query.filter(tx::dsl::tx_sequence_number.eq_any(sub_query));
                         }
 
@@ -442,6 +502,123 @@ impl TransactionBlock {
     }
 }
 
+/// Bounds `[lo, hi)` on the sequence numbers of the transactions that could be in `page`, for
+/// filters that only select transactions by their sender or recipient, when that address is hot
+/// (its transactions are counted in `tx_address_buckets`). Without these bounds, the cost of
+/// fetching a page of an address's transactions grows with the number of transactions it has.
+///
+/// Buckets are scanned from the page's cursor (or the end of the range the page is drawn from),
+/// until they hold enough transactions to fill the page (and detect the pages either side of it).
+/// The bucket containing the cursor is not counted, as its transactions may be on either side of
+/// it, and bucket counts never exceed the transactions they hold, so the bounds never exclude
+/// transactions that belong on the page. If not enough transactions are found, the page is not
+/// bounded.
+fn tx_address_bucket_bounds(
+    conn: &mut Conn<'_>,
+    page: &Page<Cursor>,
+    filter: &TransactionBlockFilter,
+    checkpoint_viewed_at: u64,
+) -> Result<(Option<i64>, Option<i64>), diesel::result::Error> {
+    use tx_address_buckets::dsl as b;
+
+    // Other filters exclude transactions that are counted in buckets.
+    let (address, sent) = match filter {
+        TransactionBlockFilter {
+            function: None,
+            kind: None,
+            after_checkpoint: None,
+            at_checkpoint: None,
+            before_checkpoint: None,
+            sign_address: Some(address),
+            recv_address: None,
+            input_object: None,
+            changed_object: None,
+            transaction_ids: None,
+        } => (address.into_vec(), true),
+        TransactionBlockFilter {
+            function: None,
+            kind: None,
+            after_checkpoint: None,
+            at_checkpoint: None,
+            before_checkpoint: None,
+            sign_address: None,
+            recv_address: Some(address),
+            input_object: None,
+            changed_object: None,
+            transaction_ids: None,
+        } => (address.into_vec(), false),
+        _ => return Ok((None, None)),
+    };
+
+    let page = page.ascending();
+    let needed = page.limit() as i64 + 2;
+    let size = TX_ADDRESS_BUCKET_SIZE as i64;
+
+    if page.is_from_front() {
+        let after = page
+            .after()
+            .map(|c| tx_address_bucket(c.tx_sequence_number) as i64);
+
+        let buckets: Vec<(i64, i64, i64)> = conn.results(move || {
+            let mut query = b::tx_address_buckets
+                .select((b::bucket, b::sent, b::received))
+                .filter(b::address.eq(address.clone()))
+                .into_boxed();
+            if let Some(after) = after {
+                query = query.filter(b::bucket.gt(after));
+            }
+            query
+                .order_by(b::bucket.asc())
+                .limit(MAX_TX_ADDRESS_BUCKETS_SCANNED)
+        })?;
+
+        let hi = covering_bucket(buckets, sent, needed).map(|b| (b + 1) * size);
+        Ok((None, hi))
+    } else {
+        // Without a cursor, the page is drawn from the latest transaction visible at
+        // `checkpoint_viewed_at`. Later buckets may count transactions that are not visible.
+        let before = match page.before() {
+            Some(c) => c.tx_sequence_number,
+            None => {
+                use checkpoints::dsl as c;
+                let network_total_transactions: i64 = conn.first(move || {
+                    c::checkpoints
+                        .select(c::network_total_transactions)
+                        .filter(c::sequence_number.eq(checkpoint_viewed_at as i64))
+                })?;
+                (network_total_transactions as u64).saturating_sub(1)
+            }
+        };
+        let before = tx_address_bucket(before) as i64;
+
+        let buckets: Vec<(i64, i64, i64)> = conn.results(move || {
+            b::tx_address_buckets
+                .select((b::bucket, b::sent, b::received))
+                .filter(b::address.eq(address.clone()))
+                .filter(b::bucket.lt(before))
+                .order_by(b::bucket.desc())
+                .limit(MAX_TX_ADDRESS_BUCKETS_SCANNED)
+        })?;
+
+        let lo = covering_bucket(buckets, sent, needed).map(|b| b * size);
+        Ok((lo, None))
+    }
+}
+
+/// The first of `buckets` (`(bucket, sent, received)` tuples, in the order they are scanned) at
+/// which the running total of transactions sent (if `sent` is true) or received reaches `needed`,
+/// if it does.
+fn covering_bucket(buckets: Vec<(i64, i64, i64)>, sent: bool, needed: i64) -> Option<i64> {
+    let mut total = 0;
+    for (bucket, s, r) in buckets {
+        total += if sent { s } else { r };
+        if total >= needed {
+            return Some(bucket);
+        }
+    }
+    None
+}
+
 impl TransactionBlockFilter {
     /// Try to create a filter whose results are the intersection of transaction blocks in `self`'s
     /// results and transaction blocks in `other`'s results. This may not be possible if the
@@ -569,3 +746,23 @@ impl TryFrom<TransactionBlockEffects> for TransactionBlock {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_covering_bucket() {
+        let buckets = vec![(3, 1, 10), (5, 2, 0), (6, 4, 1)];
+
+        // Sent transactions reach 3 at bucket 5, and 7 at bucket 6.
+        assert_eq!(covering_bucket(buckets.clone(), true, 3), Some(5));
+        assert_eq!(covering_bucket(buckets.clone(), true, 7), Some(6));
+        assert_eq!(covering_bucket(buckets.clone(), true, 8), None);
+
+        // Received transactions are counted separately.
+        assert_eq!(covering_bucket(buckets.clone(), false, 10), Some(3));
+        assert_eq!(covering_bucket(buckets, false, 12), None);
+        assert_eq!(covering_bucket(vec![], true, 1), None);
+    }
+}
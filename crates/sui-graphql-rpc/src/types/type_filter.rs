@@ -39,6 +39,31 @@ pub(crate) enum TypeFilter {
     ///
     /// would match both 0x2::coin::Coin and 0x2::coin::Coin<0x2::sui::SUI>.
     ByType(TypeTag),
+
+    /// Filter the type by a pattern in which some type parameters are wildcards, e.g.
+    ///
+    ///  0x2::coin::Coin<*>
+    ///
+    /// would match 0x2::coin::Coin<0x2::sui::SUI>, but not 0x2::coin::Coin.
+    ByPattern(TypePattern),
+}
+
+/// A type in which some type parameters are replaced by wildcards (`*`), matching any type in
+/// their place.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum TypePattern {
+    /// Matches any type.
+    Any,
+
+    /// Exact match on the type.
+    Type(TypeTag),
+
+    /// Matches vectors whose element type matches the pattern.
+    Vector(Box<TypePattern>),
+
+    /// Matches instantiations of the generic struct (whose tag has no type parameters of its own)
+    /// whose type parameters match the patterns.
+    Struct(StructTag, Vec<TypePattern>),
 }
 
 /// GraphQL scalar containing a filter on fully-qualified names.
@@ -105,6 +130,8 @@ impl TypeFilter {
                 let exact = tag.to_canonical_string(/* with_prefix */ true);
                 query.filter(field.eq(exact))
             }
+
+            TypeFilter::ByPattern(pattern) => query.filter(field.like(pattern.like_pattern())),
         }
     }
 
@@ -140,11 +167,23 @@ impl TypeFilter {
                 let statement = field.to_string() + " = {}";
                 query = filter!(query, statement, exact_pattern);
             }
+
+            TypeFilter::ByPattern(pattern) => {
+                let statement = field.to_string() + " LIKE {}";
+                query = filter!(query, statement, pattern.like_pattern());
+            }
         }
 
         query
     }
 
+    /// Whether `tag`, the type of a result of a query this filter was applied to, does not match
+    /// the filter after all. Only type patterns can over-match in queries, so their results need
+    /// to be checked with this.
+    pub(crate) fn rejects(&self, tag: &TypeTag) -> bool {
+        matches!(self, TypeFilter::ByPattern(pattern) if !pattern.matches(tag))
+    }
+
     /// Try to create a filter whose results are the intersection of the results of the input
     /// filters (`self` and `other`). This may not be possible if the resulting filter is
     /// inconsistent (e.g. a filter that requires the module member's package to be at two different
@@ -152,6 +191,7 @@ impl TypeFilter {
     pub(crate) fn intersect(self, other: Self) -> Option<Self> {
         use ModuleFilter as M;
         use TypeFilter as T;
+        use TypePattern as P;
         use TypeTag as TT;
 
         match (&self, &other) {
@@ -193,6 +233,48 @@ impl TypeFilter {
 
             // Intersecting a module-level filter with a primitive type, which will never work.
             (T::ByType(_), T::ByModule(_)) | (T::ByModule(_), T::ByType(_)) => None,
+
+            (T::ByPattern(p), T::ByPattern(q)) => p.intersect(q).map(T::ByPattern),
+
+            // A type filter without type parameters also matches all generic instantiations of the
+            // type, which includes all instantiations matching a pattern on the same type.
+            (T::ByPattern(P::Struct(s, _)), T::ByType(TT::Struct(t)))
+                if t.type_params.is_empty() =>
+            {
+                ((&s.address, &s.module, &s.name) == (&t.address, &t.module, &t.name))
+                    .then_some(self)
+            }
+
+            (T::ByType(TT::Struct(s)), T::ByPattern(P::Struct(t, _)))
+                if s.type_params.is_empty() =>
+            {
+                ((&s.address, &s.module, &s.name) == (&t.address, &t.module, &t.name))
+                    .then_some(other)
+            }
+
+            (T::ByPattern(p), T::ByType(t)) => p.matches(t).then_some(other),
+            (T::ByType(t), T::ByPattern(p)) => p.matches(t).then_some(self),
+
+            (T::ByPattern(P::Struct(s, _)), T::ByModule(M::ByPackage(q))) => {
+                (SuiAddress::from(s.address) == *q).then_some(self)
+            }
+
+            (T::ByPattern(P::Struct(s, _)), T::ByModule(M::ByModule(q, n))) => {
+                ((SuiAddress::from(s.address), s.module.as_str()) == (*q, n.as_str()))
+                    .then_some(self)
+            }
+
+            (T::ByModule(M::ByPackage(p)), T::ByPattern(P::Struct(t, _))) => {
+                (SuiAddress::from(t.address) == *p).then_some(other)
+            }
+
+            (T::ByModule(M::ByModule(p, m)), T::ByPattern(P::Struct(t, _))) => {
+                ((SuiAddress::from(t.address), t.module.as_str()) == (*p, m.as_str()))
+                    .then_some(other)
+            }
+
+            // Intersecting a module-level filter with a pattern on a vector type.
+            (T::ByPattern(_), T::ByModule(_)) | (T::ByModule(_), T::ByPattern(_)) => None,
         }
     }
 }
@@ -298,6 +380,63 @@ impl ModuleFilter {
     }
 }
 
+impl TypePattern {
+    /// The `LIKE` pattern for the canonical representations of the types matching this pattern,
+    /// with wildcards compiled to `%`, and `%` and `_` in type names escaped. Patterns always
+    /// start with the name of the outermost type, so lookups can use an index on the type column.
+    /// A wildcard followed by other type parameters can over-match in the rare case where the
+    /// types in its place are themselves instantiated with types that resemble the rest of the
+    /// pattern, so results must be checked with `TypeFilter::rejects`.
+    fn like_pattern(&self) -> String {
+        match self {
+            TypePattern::Any => "%".to_string(),
+            TypePattern::Type(tag) => escape_like(&tag.to_canonical_string(/* with_prefix */ true)),
+            TypePattern::Vector(p) => format!("vector<{}>", p.like_pattern()),
+            TypePattern::Struct(tag, params) => {
+                let params: Vec<_> = params.iter().map(|p| p.like_pattern()).collect();
+                let name = escape_like(&tag.to_canonical_string(/* with_prefix */ true));
+                format!("{name}<{}>", params.join(","))
+            }
+        }
+    }
+
+    /// Whether `tag` matches this pattern.
+    fn matches(&self, tag: &TypeTag) -> bool {
+        match (self, tag) {
+            (TypePattern::Any, _) => true,
+            (TypePattern::Type(t), _) => t == tag,
+            (TypePattern::Vector(p), TypeTag::Vector(t)) => p.matches(t),
+            (TypePattern::Struct(s, ps), TypeTag::Struct(t)) => {
+                (&s.address, &s.module, &s.name) == (&t.address, &t.module, &t.name)
+                    && ps.len() == t.type_params.len()
+                    && ps.iter().zip(&t.type_params).all(|(p, t)| p.matches(t))
+            }
+            (TypePattern::Vector(_) | TypePattern::Struct(_, _), _) => false,
+        }
+    }
+
+    /// The pattern matching exactly the types that match both `self` and `other`, or `None` if no
+    /// type matches both.
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        use TypePattern as P;
+
+        match (self, other) {
+            (P::Any, p) | (p, P::Any) => Some(p.clone()),
+            (P::Type(t), p) | (p, P::Type(t)) => p.matches(t).then(|| P::Type(t.clone())),
+            (P::Vector(p), P::Vector(q)) => Some(P::Vector(Box::new(p.intersect(q)?))),
+            (P::Struct(s, ps), P::Struct(t, qs)) => {
+                if s != t || ps.len() != qs.len() {
+                    return None;
+                }
+
+                let params = ps.iter().zip(qs).map(|(p, q)| p.intersect(q));
+                Some(P::Struct(s.clone(), params.collect::<Option<_>>()?))
+            }
+            (P::Vector(_), P::Struct(_, _)) | (P::Struct(_, _), P::Vector(_)) => None,
+        }
+    }
+}
+
 impl_string_input!(ExactTypeFilter);
 impl_string_input!(TypeFilter);
 impl_string_input!(FqNameFilter);
@@ -324,14 +463,56 @@ impl FromStr for TypeFilter {
             Ok(TypeFilter::ByType(tag))
         } else if let Ok(filter) = ModuleFilter::from_str(s) {
             Ok(TypeFilter::ByModule(filter))
+        } else if let Ok(p @ (TypePattern::Vector(_) | TypePattern::Struct(_, _))) =
+            TypePattern::from_str(s)
+        {
+            Ok(TypeFilter::ByPattern(p))
         } else {
             Err(Error::InvalidFormat(
-                "package[::module[::type[<type_params>]]] or primitive type",
+                "package[::module[::type[<type_params>]]] or primitive type, where type params can \
+                 be wildcards (*)",
             ))
         }
     }
 }
 
+impl FromStr for TypePattern {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidFormat("type, where type params can be wildcards (*)");
+
+        let s = s.trim();
+        if s == "*" {
+            return Ok(TypePattern::Any);
+        } else if !s.contains('*') {
+            return parse_sui_type_tag(s)
+                .map(TypePattern::Type)
+                .map_err(|_| invalid());
+        }
+
+        let (name, params) = s
+            .strip_suffix('>')
+            .and_then(|s| s.split_once('<'))
+            .ok_or_else(invalid)?;
+
+        let params = split_type_params(params)
+            .ok_or_else(invalid)?
+            .into_iter()
+            .map(TypePattern::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if name.trim() == "vector" {
+            let [param] = <[_; 1]>::try_from(params).map_err(|_| invalid())?;
+            return Ok(TypePattern::Vector(Box::new(param)));
+        }
+
+        match parse_sui_type_tag(name) {
+            Ok(TypeTag::Struct(tag)) => Ok(TypePattern::Struct(*tag, params)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
 impl FromStr for FqNameFilter {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Error> {
@@ -390,6 +571,29 @@ impl fmt::Display for TypeFilter {
             TypeFilter::ByType(t) => {
                 write!(f, "{}", t.to_canonical_display(/* with_prefix */ true))
             }
+            TypeFilter::ByPattern(p) => write!(f, "{p}"),
+        }
+    }
+}
+
+impl fmt::Display for TypePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypePattern::Any => write!(f, "*"),
+            TypePattern::Type(t) => {
+                write!(f, "{}", t.to_canonical_display(/* with_prefix */ true))
+            }
+            TypePattern::Vector(p) => write!(f, "vector<{p}>"),
+            TypePattern::Struct(tag, params) => {
+                write!(f, "{}<", tag.to_canonical_display(/* with_prefix */ true))?;
+                for (i, p) in params.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{p}")?;
+                }
+                write!(f, ">")
+            }
         }
     }
 }
@@ -412,6 +616,38 @@ impl From<StructTag> for TypeFilter {
     }
 }
 
+/// Splits the type parameters of a type (the part between its outermost angle brackets) at the
+/// commas that are not nested inside other type parameters, or returns `None` if the brackets are
+/// unbalanced.
+fn split_type_params(params: &str) -> Option<Vec<&str>> {
+    let mut split = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in params.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                split.push(&params[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    (depth == 0).then(|| {
+        split.push(&params[start..]);
+        split
+    })
+}
+
+/// Escapes the characters that would otherwise be interpreted as wildcards in a `LIKE` pattern.
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -477,6 +713,65 @@ mod tests {
         expect.assert_eq(&filters.join("\n"))
     }
 
+    #[test]
+    fn test_valid_type_pattern_filters() {
+        let inputs = [
+            "0x2::coin::Coin<*>",
+            "0x2::dynamic_field::Field<0x2::object::ID, *>",
+            "0x2::dynamic_field::Field<*, 0x2::coin::Coin<*>>",
+            "vector<*>",
+            "vector<0x2::coin::Coin<*>>",
+        ]
+        .into_iter();
+
+        let filters: Vec<_> = inputs
+            .map(|i| {
+                let TypeFilter::ByPattern(p) = TypeFilter::from_str(i).unwrap() else {
+                    panic!("Expected a pattern for {i}");
+                };
+                format!("{p} {}", p.like_pattern())
+            })
+            .collect();
+
+        let expect = expect![[r#"
+            0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<*> 0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<%>
+            0x0000000000000000000000000000000000000000000000000000000000000002::dynamic_field::Field<0x0000000000000000000000000000000000000000000000000000000000000002::object::ID,*> 0x0000000000000000000000000000000000000000000000000000000000000002::dynamic\_field::Field<0x0000000000000000000000000000000000000000000000000000000000000002::object::ID,%>
+            0x0000000000000000000000000000000000000000000000000000000000000002::dynamic_field::Field<*,0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<*>> 0x0000000000000000000000000000000000000000000000000000000000000002::dynamic\_field::Field<%,0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<%>>
+            vector<*> vector<%>
+            vector<0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<*>> vector<0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<%>>"#]];
+        expect.assert_eq(&filters.join("\n"))
+    }
+
+    #[test]
+    fn test_type_pattern_matches() {
+        let coin = TypePattern::from_str("0x2::coin::Coin<*>").unwrap();
+        let field = TypePattern::from_str("0x2::dynamic_field::Field<*, u64>").unwrap();
+
+        let tag = |s: &str| parse_sui_type_tag(s).unwrap();
+        assert!(coin.matches(&tag("0x2::coin::Coin<0x2::sui::SUI>")));
+        assert!(coin.matches(&tag("0x2::coin::Coin<vector<u8>>")));
+        assert!(!coin.matches(&tag("0x2::coin::Coin")));
+        assert!(!coin.matches(&tag("0x2::balance::Balance<0x2::sui::SUI>")));
+
+        assert!(field.matches(&tag("0x2::dynamic_field::Field<0x2::object::ID, u64>")));
+        assert!(!field.matches(&tag("0x2::dynamic_field::Field<u64, 0x2::object::ID>")));
+    }
+
+    #[test]
+    fn test_type_filter_rejects() {
+        let coin = TypeFilter::from_str("0x2::coin::Coin").unwrap();
+        let field = TypeFilter::from_str("0x2::dynamic_field::Field<*, *>").unwrap();
+
+        let tag = |s: &str| parse_sui_type_tag(s).unwrap();
+        assert!(!coin.rejects(&tag("0x2::coin::Coin<0x2::sui::SUI>")));
+        assert!(!field.rejects(&tag("0x2::dynamic_field::Field<u8, u64>")));
+
+        // Matches the `LIKE` pattern for `field`, but only has one type parameter.
+        assert!(field.rejects(&tag(
+            "0x2::dynamic_field::Field<0x2::table::Table<u8, u64>>"
+        )));
+    }
+
     #[test]
     fn test_valid_function_filters() {
         let inputs = [
@@ -533,6 +828,14 @@ mod tests {
             "0x2::trailing::",
             "0x3::mismatched::bra<0x4::ke::ts",
             "vector",
+            "*",
+            "0x2::*",
+            "0x2::coin::*",
+            "0x2::coin::Coin<*",
+            "0x2::coin::Coin<*>>",
+            "0x2::dynamic_field::Field<*,, u64>",
+            "vector<*, *>",
+            "u64<*>",
         ] {
             assert!(TypeFilter::from_str(invalid_type_filter).is_err());
         }
@@ -616,4 +919,67 @@ mod tests {
         assert_eq!(coin_typ.clone().intersect(std_utf8.clone()), None);
         assert_eq!(coin_sui.clone().intersect(std_utf8.clone()), None);
     }
+
+    #[test]
+    fn test_type_pattern_intersection() {
+        let sui = TypeFilter::from_str("0x2").unwrap();
+        let coin_mod = TypeFilter::from_str("0x2::coin").unwrap();
+        let coin_typ = TypeFilter::from_str("0x2::coin::Coin").unwrap();
+        let coin_any = TypeFilter::from_str("0x2::coin::Coin<*>").unwrap();
+        let coin_sui = TypeFilter::from_str("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+        let std_utf8 = TypeFilter::from_str("0x1::string::String").unwrap();
+
+        let field_any_u64 = TypeFilter::from_str("0x2::dynamic_field::Field<*, u64>").unwrap();
+        let field_id_any =
+            TypeFilter::from_str("0x2::dynamic_field::Field<0x2::object::ID, *>").unwrap();
+        let field_id_u64 =
+            TypeFilter::from_str("0x2::dynamic_field::Field<0x2::object::ID, u64>").unwrap();
+        let vec_any = TypeFilter::from_str("vector<*>").unwrap();
+
+        assert_eq!(
+            coin_any.clone().intersect(coin_any.clone()),
+            Some(coin_any.clone())
+        );
+
+        assert_eq!(
+            sui.clone().intersect(coin_any.clone()),
+            Some(coin_any.clone())
+        );
+
+        assert_eq!(
+            coin_any.clone().intersect(coin_mod.clone()),
+            Some(coin_any.clone())
+        );
+
+        assert_eq!(
+            coin_typ.clone().intersect(coin_any.clone()),
+            Some(coin_any.clone())
+        );
+
+        assert_eq!(
+            coin_any.clone().intersect(coin_sui.clone()),
+            Some(coin_sui.clone())
+        );
+
+        assert_eq!(
+            field_any_u64.clone().intersect(field_id_any.clone()),
+            Some(TypeFilter::ByPattern(TypePattern::Struct(
+                StructTag::from_str("0x2::dynamic_field::Field").unwrap(),
+                vec![
+                    TypePattern::from_str("0x2::object::ID").unwrap(),
+                    TypePattern::from_str("u64").unwrap(),
+                ],
+            )))
+        );
+
+        assert_eq!(
+            field_id_u64.clone().intersect(field_any_u64.clone()),
+            Some(field_id_u64.clone())
+        );
+
+        assert_eq!(coin_any.clone().intersect(std_utf8.clone()), None);
+        assert_eq!(coin_any.clone().intersect(vec_any.clone()), None);
+        assert_eq!(coin_any.clone().intersect(field_any_u64.clone()), None);
+        assert_eq!(vec_any.clone().intersect(coin_mod.clone()), None);
+    }
 }
@@ -0,0 +1,204 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::{
+    connection::{Connection, CursorType, Edge},
+    *,
+};
+use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use serde::{Deserialize, Serialize};
+use sui_indexer::{models::usage_reports::StoredUsageReport, schema::usage_reports};
+
+use crate::{
+    config::ServiceConfig,
+    data::{self, Db, QueryExecutor},
+    error::Error,
+    usage::ApiKey,
+};
+
+use super::{
+    cursor::{self, Page, Paginated, Target},
+    date_time::DateTime,
+};
+
+/// Usage of the service by a single API key over a reporting period.
+#[derive(Clone)]
+pub(crate) struct UsageReport {
+    pub stored: StoredUsageReport,
+}
+
+pub(crate) type Cursor = cursor::JsonCursor<UsageReportCursor>;
+type Query<ST, GB> = data::Query<ST, usage_reports::table, GB>;
+
+/// The cursor returned for each `UsageReport` in a connection's page of results. Usage reports are
+/// not tied to checkpoints, so unlike most cursors, this one does not pin a checkpoint to view
+/// data at.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct UsageReportCursor {
+    #[serde(rename = "s")]
+    pub period_start_ms: u64,
+    #[serde(rename = "k")]
+    pub api_key: String,
+}
+
+/// Usage of the GraphQL service by a single API key, aggregated over a reporting period. Costs
+/// are summed over all the requests made with the key during that period.
+#[Object]
+impl UsageReport {
+    /// The API key whose usage is reported.
+    async fn api_key(&self) -> &str {
+        &self.stored.api_key
+    }
+
+    /// The start of the reporting period.
+    async fn period_start(&self) -> Result<DateTime> {
+        DateTime::from_ms(self.stored.period_start_ms).extend()
+    }
+
+    /// The end of the reporting period.
+    async fn period_end(&self) -> Result<DateTime> {
+        DateTime::from_ms(self.stored.period_end_ms).extend()
+    }
+
+    /// The number of requests made with the API key.
+    async fn requests(&self) -> u64 {
+        self.stored.requests as u64
+    }
+
+    /// The number of requests made with the API key that failed.
+    async fn errors(&self) -> u64 {
+        self.stored.errors as u64
+    }
+
+    /// The fraction of requests made with the API key that failed.
+    async fn error_rate(&self) -> f64 {
+        if self.stored.requests == 0 {
+            0.0
+        } else {
+            self.stored.errors as f64 / self.stored.requests as f64
+        }
+    }
+
+    /// The total number of nodes (field names) in the queries of these requests.
+    async fn input_nodes(&self) -> u64 {
+        self.stored.input_nodes as u64
+    }
+
+    /// The total number of output nodes of these requests, as estimated by the service when
+    /// checking them against the `maxOutputNodes` limit.
+    async fn output_nodes(&self) -> u64 {
+        self.stored.output_nodes as u64
+    }
+
+    /// The total size of the query payloads of these requests, in bytes.
+    async fn query_payload_bytes(&self) -> u64 {
+        self.stored.query_payload_bytes as u64
+    }
+
+    /// The total time in milliseconds spent serving these requests.
+    async fn latency_ms(&self) -> u64 {
+        self.stored.latency_ms as u64
+    }
+
+    /// The total price of these requests, according to the pricing model configured for the
+    /// service at the time they were made.
+    async fn price(&self) -> u64 {
+        self.stored.price as u64
+    }
+}
+
+impl UsageReport {
+    /// Check that the request was made with one of the admin API keys configured for the service,
+    /// which are the only keys allowed to read usage reports.
+    pub(crate) fn check_admin(ctx: &Context<'_>) -> Result<(), Error> {
+        let config: &ServiceConfig = ctx.data_unchecked();
+        match ctx.data_opt::<ApiKey>() {
+            Some(ApiKey(key)) if config.admin_api_keys.contains(key) => Ok(()),
+            _ => Err(Error::Client(
+                "Usage reports can only be read with an admin API key".to_string(),
+            )),
+        }
+    }
+
+    /// Query the database for a `page` of usage reports, optionally only for the reports of the
+    /// given `api_key`.
+    pub(crate) async fn paginate(
+        db: &Db,
+        page: Page<Cursor>,
+        api_key: Option<String>,
+    ) -> Result<Connection<String, UsageReport>, Error> {
+        use usage_reports::dsl;
+
+        let (prev, next, results) = db
+            .execute(move |conn| {
+                page.paginate_query::<StoredUsageReport, _, _, _>(
+                    conn,
+                    // Usage reports are not tied to checkpoints, so there is no checkpoint to
+                    // view them at.
+                    0,
+                    move || {
+                        let mut query = dsl::usage_reports.into_boxed();
+                        if let Some(api_key) = &api_key {
+                            query = query.filter(dsl::api_key.eq(api_key.clone()));
+                        }
+                        query
+                    },
+                )
+            })
+            .await?;
+
+        let mut conn = Connection::new(prev, next);
+        for stored in results {
+            let cursor = stored.cursor(0).encode_cursor();
+            conn.edges.push(Edge::new(cursor, UsageReport { stored }));
+        }
+
+        Ok(conn)
+    }
+}
+
+impl Paginated<Cursor> for StoredUsageReport {
+    type Source = usage_reports::table;
+
+    fn filter_ge<ST, GB>(cursor: &Cursor, query: Query<ST, GB>) -> Query<ST, GB> {
+        use usage_reports::dsl::{api_key as key, period_start_ms as start};
+        let cursor_start = cursor.period_start_ms as i64;
+        query.filter(
+            start
+                .gt(cursor_start)
+                .or(start.eq(cursor_start).and(key.ge(cursor.api_key.clone()))),
+        )
+    }
+
+    fn filter_le<ST, GB>(cursor: &Cursor, query: Query<ST, GB>) -> Query<ST, GB> {
+        use usage_reports::dsl::{api_key as key, period_start_ms as start};
+        let cursor_start = cursor.period_start_ms as i64;
+        query.filter(
+            start
+                .lt(cursor_start)
+                .or(start.eq(cursor_start).and(key.le(cursor.api_key.clone()))),
+        )
+    }
+
+    fn order<ST, GB>(asc: bool, query: Query<ST, GB>) -> Query<ST, GB> {
+        use usage_reports::dsl;
+        if asc {
+            query
+                .order_by(dsl::period_start_ms.asc())
+                .then_order_by(dsl::api_key.asc())
+        } else {
+            query
+                .order_by(dsl::period_start_ms.desc())
+                .then_order_by(dsl::api_key.desc())
+        }
+    }
+}
+
+impl Target<Cursor> for StoredUsageReport {
+    fn cursor(&self, _checkpoint_viewed_at: u64) -> Cursor {
+        Cursor::new(UsageReportCursor {
+            period_start_ms: self.period_start_ms as u64,
+            api_key: self.api_key.clone(),
+        })
+    }
+}
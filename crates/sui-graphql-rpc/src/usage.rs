@@ -0,0 +1,335 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use diesel::dsl::sql;
+use diesel::sql_types::BigInt;
+use diesel::{upsert::excluded, ExpressionMethods, RunQueryDsl};
+use sui_indexer::db::{get_pg_pool_connection, PgConnectionPool};
+use sui_indexer::models::usage_reports::StoredUsageReport;
+use sui_indexer::schema::usage_reports;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::server::health::Heartbeat;
+
+/// The maximum number of distinct API keys whose usage is tracked in a single reporting period.
+/// Requests made with other keys once this limit is reached are not reported, to bound the memory
+/// used by clients sending arbitrary keys.
+const MAX_TRACKED_API_KEYS: usize = 10_000;
+
+/// The API key a request was made with, as named in its `x-sui-rpc-api-key` header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ApiKey(pub String);
+
+/// The cost of serving a single request, as measured by the query limits checker while it
+/// validates the request's query.
+#[derive(Clone, Default)]
+pub(crate) struct RequestCost(Arc<Mutex<Usage>>);
+
+/// Usage of the service accumulated over some number of requests.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Usage {
+    pub requests: u64,
+    pub errors: u64,
+    pub input_nodes: u64,
+    pub output_nodes: u64,
+    pub query_payload_bytes: u64,
+    pub latency_ms: u64,
+    /// The price of the requests, according to the service's pricing config.
+    pub price: u64,
+}
+
+/// Usage of the service by each API key, accumulated in memory since the start of the current
+/// reporting period, until it is written out by the usage reporting task.
+#[derive(Clone)]
+pub(crate) struct UsageTracker(Arc<Mutex<Period>>);
+
+/// Writes the usage tracked by its `tracker` out to the database as usage reports.
+pub(crate) struct UsageReporter {
+    pub tracker: UsageTracker,
+    pub pool: PgConnectionPool,
+}
+
+struct Period {
+    start_ms: u64,
+    usage: BTreeMap<String, Usage>,
+}
+
+impl RequestCost {
+    /// Record the number of input and output nodes in the request's query.
+    pub(crate) fn record(&self, input_nodes: u64, output_nodes: u64) {
+        let mut cost = self.0.lock().unwrap();
+        cost.input_nodes = input_nodes;
+        cost.output_nodes = output_nodes;
+    }
+
+    /// Add the price of one of the requests sharing this cost.
+    pub(crate) fn charge(&self, price: u64) {
+        let mut cost = self.0.lock().unwrap();
+        cost.price = cost.price.saturating_add(price);
+    }
+
+    /// The cost recorded for this request, if any (requests that fail before their query is
+    /// validated have no cost).
+    pub(crate) fn usage(&self) -> Usage {
+        *self.0.lock().unwrap()
+    }
+}
+
+impl Usage {
+    fn add(&mut self, other: &Usage) {
+        self.requests += other.requests;
+        self.errors += other.errors;
+        self.input_nodes += other.input_nodes;
+        self.output_nodes += other.output_nodes;
+        self.query_payload_bytes += other.query_payload_bytes;
+        self.latency_ms += other.latency_ms;
+        self.price = self.price.saturating_add(other.price);
+    }
+
+    /// The usage as a report to store. Values beyond the range of the report's columns are stored
+    /// as their maximum, like the saturated price of requests whose price overflowed.
+    fn report(&self, api_key: String, start_ms: u64, end_ms: u64) -> StoredUsageReport {
+        let clamp = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+        StoredUsageReport {
+            api_key,
+            period_start_ms: clamp(start_ms),
+            period_end_ms: clamp(end_ms),
+            requests: clamp(self.requests),
+            errors: clamp(self.errors),
+            input_nodes: clamp(self.input_nodes),
+            output_nodes: clamp(self.output_nodes),
+            query_payload_bytes: clamp(self.query_payload_bytes),
+            latency_ms: clamp(self.latency_ms),
+            price: clamp(self.price),
+        }
+    }
+}
+
+impl From<&StoredUsageReport> for Usage {
+    fn from(report: &StoredUsageReport) -> Self {
+        Usage {
+            requests: report.requests as u64,
+            errors: report.errors as u64,
+            input_nodes: report.input_nodes as u64,
+            output_nodes: report.output_nodes as u64,
+            query_payload_bytes: report.query_payload_bytes as u64,
+            latency_ms: report.latency_ms as u64,
+            price: report.price as u64,
+        }
+    }
+}
+
+impl UsageTracker {
+    /// Start tracking usage, in a reporting period that starts now.
+    pub(crate) fn new() -> Self {
+        Self::new_at(now_ms())
+    }
+
+    fn new_at(start_ms: u64) -> Self {
+        UsageTracker(Arc::new(Mutex::new(Period {
+            start_ms,
+            usage: BTreeMap::new(),
+        })))
+    }
+
+    /// Add `usage` to the usage of `api_key` in the current reporting period.
+    pub(crate) fn record(&self, api_key: &ApiKey, usage: Usage) {
+        let mut period = self.0.lock().unwrap();
+        if let Some(total) = period.usage.get_mut(&api_key.0) {
+            total.add(&usage);
+        } else if period.usage.len() < MAX_TRACKED_API_KEYS {
+            period.usage.insert(api_key.0.clone(), usage);
+        } else {
+            warn!("Too many API keys to track usage for, not reporting usage of {api_key:?}");
+        }
+    }
+
+    /// End the current reporting period at `end_ms`, returning a report of each API key's usage
+    /// during it, and start a new period.
+    pub(crate) fn take(&self, end_ms: u64) -> Vec<StoredUsageReport> {
+        let mut period = self.0.lock().unwrap();
+        let start_ms = std::mem::replace(&mut period.start_ms, end_ms);
+        std::mem::take(&mut period.usage)
+            .into_iter()
+            .map(|(api_key, usage)| usage.report(api_key, start_ms, end_ms))
+            .collect()
+    }
+
+    /// Add usage from `reports` that could not be written out back into the current reporting
+    /// period, extending it to start from the earliest of those reports, so that the usage is
+    /// included in the next report instead of being lost.
+    pub(crate) fn restore(&self, reports: Vec<StoredUsageReport>) {
+        let mut period = self.0.lock().unwrap();
+        for report in reports {
+            period.start_ms = period.start_ms.min(report.period_start_ms as u64);
+            period
+                .usage
+                .entry(report.api_key.clone())
+                .or_default()
+                .add(&Usage::from(&report));
+        }
+    }
+}
+
+impl Default for UsageTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UsageReporter {
+    /// Background task that writes out the tracked usage as usage reports, every `interval`. Any
+    /// remaining usage is written out when a cancellation signal is received. The task beats
+    /// `heartbeat` every time it writes out reports.
+    pub(crate) async fn run(
+        self,
+        interval: Duration,
+        heartbeat: Heartbeat,
+        cancellation_token: CancellationToken,
+    ) {
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Shutdown signal received, writing final usage reports");
+                    write_reports(&self.tracker, &self.pool).await;
+                    return;
+                },
+                _ = tokio::time::sleep(interval) => {
+                    write_reports(&self.tracker, &self.pool).await;
+                    heartbeat.beat();
+                }
+            }
+        }
+    }
+}
+
+/// Write out the usage accumulated in `tracker` since the last report. If the reports cannot be
+/// written, their usage is returned to the tracker to be retried with the next report.
+async fn write_reports(tracker: &UsageTracker, pool: &PgConnectionPool) {
+    let reports = tracker.take(now_ms());
+    if reports.is_empty() {
+        return;
+    }
+
+    let pool = pool.clone();
+    let rows = reports.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        use usage_reports::dsl;
+        let mut conn = get_pg_pool_connection(&pool).map_err(|e| e.to_string())?;
+        diesel::insert_into(usage_reports::table)
+            .values(&rows)
+            .on_conflict((dsl::api_key, dsl::period_start_ms))
+            .do_update()
+            .set((
+                dsl::period_end_ms.eq(excluded(dsl::period_end_ms)),
+                dsl::requests.eq(dsl::requests + excluded(dsl::requests)),
+                dsl::errors.eq(dsl::errors + excluded(dsl::errors)),
+                dsl::input_nodes.eq(dsl::input_nodes + excluded(dsl::input_nodes)),
+                dsl::output_nodes.eq(dsl::output_nodes + excluded(dsl::output_nodes)),
+                dsl::query_payload_bytes
+                    .eq(dsl::query_payload_bytes + excluded(dsl::query_payload_bytes)),
+                dsl::latency_ms.eq(dsl::latency_ms + excluded(dsl::latency_ms)),
+                // Prices saturate, as they do in memory, instead of overflowing the column.
+                dsl::price.eq(sql::<BigInt>(
+                    "CASE WHEN usage_reports.price > 9223372036854775807 - excluded.price \
+                     THEN 9223372036854775807 \
+                     ELSE usage_reports.price + excluded.price END",
+                )),
+            ))
+            .execute(&mut conn)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+
+    match result {
+        Ok(_) => info!("Wrote usage reports for {} API keys", reports.len()),
+        Err(e) => {
+            error!("Failed to write usage reports, retrying with the next report: {e}");
+            tracker.restore(reports);
+        }
+    }
+}
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(requests: u64, errors: u64, input_nodes: u64) -> Usage {
+        Usage {
+            requests,
+            errors,
+            input_nodes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_usage_is_reported_per_key() {
+        let tracker = UsageTracker::new_at(100);
+        let a = ApiKey("a".to_string());
+        let b = ApiKey("b".to_string());
+
+        tracker.record(&a, usage(1, 0, 10));
+        tracker.record(&b, usage(1, 1, 5));
+        tracker.record(&a, usage(1, 1, 20));
+
+        let reports = tracker.take(200);
+        assert_eq!(
+            reports,
+            vec![
+                usage(2, 1, 30).report("a".to_string(), 100, 200),
+                usage(1, 1, 5).report("b".to_string(), 100, 200),
+            ]
+        );
+
+        // The next period starts where the last one ended, with no usage.
+        tracker.record(&b, usage(1, 0, 1));
+        let reports = tracker.take(300);
+        assert_eq!(
+            reports,
+            vec![usage(1, 0, 1).report("b".to_string(), 200, 300)]
+        );
+    }
+
+    #[test]
+    fn test_restored_usage_is_reported_again() {
+        let tracker = UsageTracker::new_at(100);
+        let a = ApiKey("a".to_string());
+
+        tracker.record(&a, usage(1, 0, 10));
+        let failed = tracker.take(200);
+
+        tracker.record(&a, usage(2, 1, 5));
+        tracker.restore(failed);
+
+        let reports = tracker.take(300);
+        assert_eq!(
+            reports,
+            vec![usage(3, 1, 15).report("a".to_string(), 100, 300)]
+        );
+    }
+
+    #[test]
+    fn test_saturated_price_is_clamped() {
+        let saturated = Usage {
+            requests: 1,
+            price: u64::MAX,
+            ..Default::default()
+        };
+        let report = saturated.report("a".to_string(), 100, 200);
+        assert_eq!(report.price, i64::MAX);
+        assert_eq!(Usage::from(&report).price, i64::MAX as u64);
+    }
+}
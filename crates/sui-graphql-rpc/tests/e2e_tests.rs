@@ -814,6 +814,30 @@ mod tests {
         test_timeout_impl().await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_persisted_queries() {
+        test_persisted_queries_impl().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_field_aliases() {
+        test_field_aliases_impl().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_rate_limits() {
+        test_rate_limits_impl().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_read_only_mode() {
+        test_read_only_mode_impl().await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_query_depth_limit() {
@@ -826,6 +850,17 @@ mod tests {
         test_query_node_limit_impl().await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_query_batch_budget() {
+        test_query_batch_budget_impl().await;
+    }
+
+    #[test]
+    fn test_batch_limits() {
+        test_batch_limits_impl();
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_query_default_page_limit() {
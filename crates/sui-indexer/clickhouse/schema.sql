@@ -0,0 +1,313 @@
+-- Tables of the ClickHouse indexer store, created when the store starts, if they do not exist.
+-- They mirror the Postgres tables of the same names, with bytes stored as `String`s. Rows are only
+-- ever inserted: tables are `ReplacingMergeTree`s ordered by the primary key of the Postgres
+-- table, so that rows written again (e.g. when checkpoints are committed again after a failed
+-- batch) replace the rows written before, once parts are merged, and reads that must not see
+-- both use `FINAL`.
+
+CREATE TABLE IF NOT EXISTS checkpoints
+(
+    sequence_number             Int64,
+    checkpoint_digest           String,
+    epoch                       Int64,
+    network_total_transactions  Int64,
+    previous_checkpoint_digest  Nullable(String),
+    end_of_epoch                Bool,
+    tx_digests                  Array(Nullable(String)),
+    timestamp_ms                Int64,
+    total_gas_cost              Int64,
+    computation_cost            Int64,
+    storage_cost                Int64,
+    storage_rebate              Int64,
+    non_refundable_storage_fee  Int64,
+    checkpoint_commitments      String,
+    validator_signature         String,
+    end_of_epoch_data           Nullable(String),
+    checkpoint_summary          Nullable(String),
+    checkpoint_contents         Nullable(String)
+)
+ENGINE = ReplacingMergeTree
+ORDER BY sequence_number;
+
+CREATE TABLE IF NOT EXISTS transactions
+(
+    tx_sequence_number          Int64,
+    transaction_digest          String,
+    raw_transaction             String,
+    raw_effects                 String,
+    checkpoint_sequence_number  Int64,
+    timestamp_ms                Int64,
+    object_changes              Array(Nullable(String)),
+    balance_changes             Array(Nullable(String)),
+    events                      Array(Nullable(String)),
+    transaction_kind            Int16,
+    success_command_count       Int16
+)
+ENGINE = ReplacingMergeTree
+ORDER BY tx_sequence_number;
+
+CREATE TABLE IF NOT EXISTS events
+(
+    tx_sequence_number          Int64,
+    event_sequence_number       Int64,
+    transaction_digest          String,
+    checkpoint_sequence_number  Int64,
+    senders                     Array(Nullable(String)),
+    package                     String,
+    module                      String,
+    event_type                  String,
+    timestamp_ms                Int64,
+    bcs                         String
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (tx_sequence_number, event_sequence_number);
+
+CREATE TABLE IF NOT EXISTS tx_senders
+(
+    tx_sequence_number          Int64,
+    sender                      String,
+    gas_used                    Nullable(Int64)
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (sender, tx_sequence_number);
+
+CREATE TABLE IF NOT EXISTS tx_recipients
+(
+    tx_sequence_number          Int64,
+    recipient                   String
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (recipient, tx_sequence_number);
+
+CREATE TABLE IF NOT EXISTS tx_input_objects
+(
+    tx_sequence_number          Int64,
+    object_id                   String
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (object_id, tx_sequence_number);
+
+CREATE TABLE IF NOT EXISTS tx_changed_objects
+(
+    tx_sequence_number          Int64,
+    object_id                   String
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (object_id, tx_sequence_number);
+
+CREATE TABLE IF NOT EXISTS tx_calls
+(
+    tx_sequence_number          Int64,
+    package                     String,
+    module                      String,
+    func                        String,
+    gas_used                    Nullable(Int64)
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (package, tx_sequence_number);
+
+CREATE TABLE IF NOT EXISTS tx_shared_objects
+(
+    tx_sequence_number          Int64,
+    checkpoint_sequence_number  Int64,
+    object_id                   String,
+    mutable                     Bool,
+    success                     Bool
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (object_id, tx_sequence_number);
+
+-- Live objects. Each object keeps its latest version, and deleted objects are replaced by rows
+-- with `is_deleted` set, so rows are read with `FINAL` and `is_deleted = 0`.
+CREATE TABLE IF NOT EXISTS objects
+(
+    object_id                   String,
+    object_version              Int64,
+    object_digest               String,
+    checkpoint_sequence_number  Int64,
+    owner_type                  Int16,
+    owner_id                    Nullable(String),
+    object_type                 Nullable(String),
+    serialized_object           String,
+    coin_type                   Nullable(String),
+    coin_balance                Nullable(Int64),
+    df_kind                     Nullable(Int16),
+    df_name                     Nullable(String),
+    df_object_type              Nullable(String),
+    df_object_id                Nullable(String),
+    df_bcs_name                 Nullable(String),
+    display_version             Nullable(Int16),
+    display_fields              Nullable(String),
+    is_deleted                  UInt8 DEFAULT 0
+)
+ENGINE = ReplacingMergeTree(object_version, is_deleted)
+ORDER BY object_id;
+
+CREATE TABLE IF NOT EXISTS objects_history
+(
+    object_id                   String,
+    object_version              Int64,
+    object_status               Int16,
+    object_digest               Nullable(String),
+    checkpoint_sequence_number  Int64,
+    owner_type                  Nullable(Int16),
+    owner_id                    Nullable(String),
+    object_type                 Nullable(String),
+    serialized_object           Nullable(String),
+    coin_type                   Nullable(String),
+    coin_balance                Nullable(Int64),
+    df_kind                     Nullable(Int16),
+    df_name                     Nullable(String),
+    df_object_type              Nullable(String),
+    df_object_id                Nullable(String)
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (object_id, object_version, checkpoint_sequence_number);
+
+CREATE TABLE IF NOT EXISTS objects_snapshot
+(
+    object_id                   String,
+    object_version              Int64,
+    object_status               Int16,
+    object_digest               Nullable(String),
+    checkpoint_sequence_number  Int64,
+    owner_type                  Nullable(Int16),
+    owner_id                    Nullable(String),
+    object_type                 Nullable(String),
+    serialized_object           Nullable(String),
+    coin_type                   Nullable(String),
+    coin_balance                Nullable(Int64),
+    df_kind                     Nullable(Int16),
+    df_name                     Nullable(String),
+    df_object_type              Nullable(String),
+    df_object_id                Nullable(String)
+)
+ENGINE = ReplacingMergeTree(object_version)
+ORDER BY object_id;
+
+CREATE TABLE IF NOT EXISTS packages
+(
+    package_id                  String,
+    move_package                String
+)
+ENGINE = ReplacingMergeTree
+ORDER BY package_id;
+
+CREATE TABLE IF NOT EXISTS display
+(
+    object_type                 String,
+    id                          String,
+    version                     Int16,
+    bcs                         String
+)
+ENGINE = ReplacingMergeTree(version)
+ORDER BY object_type;
+
+CREATE TABLE IF NOT EXISTS epochs
+(
+    epoch                           Int64,
+    first_checkpoint_id             Int64,
+    epoch_start_timestamp           Int64,
+    reference_gas_price             Int64,
+    protocol_version                Int64,
+    total_stake                     Int64,
+    storage_fund_balance            Int64,
+    system_state                    String,
+    epoch_total_transactions        Nullable(Int64),
+    last_checkpoint_id              Nullable(Int64),
+    epoch_end_timestamp             Nullable(Int64),
+    storage_fund_reinvestment       Nullable(Int64),
+    storage_charge                  Nullable(Int64),
+    storage_rebate                  Nullable(Int64),
+    stake_subsidy_amount            Nullable(Int64),
+    total_gas_fees                  Nullable(Int64),
+    total_stake_rewards_distributed Nullable(Int64),
+    leftover_storage_fund_inflow    Nullable(Int64),
+    epoch_commitments               Nullable(String)
+)
+ENGINE = ReplacingMergeTree
+ORDER BY epoch;
+
+CREATE TABLE IF NOT EXISTS quarantined_checkpoints
+(
+    checkpoint_sequence_number  Int64,
+    epoch                       Int64,
+    checkpoint_digest           String,
+    error                       String,
+    attempts                    Int64,
+    context                     String,
+    quarantined_at_ms           Int64
+)
+ENGINE = ReplacingMergeTree
+ORDER BY checkpoint_sequence_number;
+
+CREATE TABLE IF NOT EXISTS epoch_consistency_reports
+(
+    epoch                       Int64,
+    discrepancies               Int64,
+    report                      String,
+    checked_at_ms               Int64
+)
+ENGINE = ReplacingMergeTree
+ORDER BY epoch;
+
+CREATE TABLE IF NOT EXISTS move_call_metrics
+(
+    day                         Int64,
+    package                     String,
+    module                      String,
+    func                        String,
+    call_count                  Int64,
+    distinct_senders            Int64,
+    total_gas_used              Int64
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (day, package, module, func);
+
+CREATE TABLE IF NOT EXISTS shared_object_metrics
+(
+    day                         Int64,
+    object_id                   String,
+    tx_count                    Int64,
+    mutating_tx_count           Int64,
+    failed_tx_count             Int64,
+    distinct_senders            Int64,
+    max_checkpoint_mutations    Int64
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (day, object_id);
+
+CREATE TABLE IF NOT EXISTS rollup_watermarks
+(
+    name                        String,
+    checkpoint_sequence_number  Int64
+)
+ENGINE = ReplacingMergeTree
+ORDER BY name;
+
+CREATE TABLE IF NOT EXISTS active_address_metrics
+(
+    day                         Int64,
+    active_addresses            Int64
+)
+ENGINE = ReplacingMergeTree
+ORDER BY day;
+
+CREATE TABLE IF NOT EXISTS package_tx_metrics
+(
+    day                         Int64,
+    package                     String,
+    tx_count                    Int64
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (day, package);
+
+CREATE TABLE IF NOT EXISTS sender_gas_metrics
+(
+    day                         Int64,
+    sender                      String,
+    tx_count                    Int64,
+    total_gas_used              Int64
+)
+ENGINE = ReplacingMergeTree
+ORDER BY (day, sender);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE checkpoints DROP COLUMN IF EXISTS checkpoint_contents;
+ALTER TABLE checkpoints DROP COLUMN IF EXISTS checkpoint_summary;
@@ -0,0 +1,4 @@
+-- bcs serialized CheckpointSummary and CheckpointContents bytes, used to serve transaction
+-- inclusion proofs. NULL for checkpoints indexed before these columns were added.
+ALTER TABLE checkpoints ADD COLUMN checkpoint_summary bytea;
+ALTER TABLE checkpoints ADD COLUMN checkpoint_contents bytea;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS move_call_metrics;
+DROP INDEX IF EXISTS checkpoints_timestamp_ms;
+ALTER TABLE tx_calls DROP COLUMN IF EXISTS gas_used;
@@ -0,0 +1,21 @@
+-- Net gas used by the transaction making the call. NULL for calls indexed before this column
+-- was added.
+ALTER TABLE tx_calls ADD COLUMN gas_used BIGINT;
+
+CREATE INDEX checkpoints_timestamp_ms ON checkpoints (timestamp_ms);
+
+-- Daily aggregates of `tx_calls`, computed once a day has completed.
+CREATE TABLE move_call_metrics (
+    -- days since the unix epoch, in UTC
+    day                         BIGINT       NOT NULL,
+    package                     BYTEA        NOT NULL,
+    module                      TEXT         NOT NULL,
+    func                        TEXT         NOT NULL,
+    call_count                  BIGINT       NOT NULL,
+    distinct_senders            BIGINT       NOT NULL,
+    -- sum of the net gas used by the transactions making the calls
+    total_gas_used              BIGINT       NOT NULL,
+    PRIMARY KEY(day, package, module, func)
+);
+CREATE INDEX move_call_metrics_func ON move_call_metrics (package, module, func, day);
+CREATE INDEX move_call_metrics_call_count ON move_call_metrics (day, call_count);
@@ -0,0 +1,13 @@
+-- Checkpoints that repeatedly failed to commit and were skipped by the indexer. Data of these
+-- checkpoints may be missing or partially written in other tables.
+CREATE TABLE quarantined_checkpoints (
+    checkpoint_sequence_number  BIGINT       PRIMARY KEY,
+    epoch                       BIGINT       NOT NULL,
+    checkpoint_digest           BYTEA        NOT NULL,
+    -- error of the last failed attempt to commit the checkpoint
+    error                       TEXT         NOT NULL,
+    attempts                    BIGINT       NOT NULL,
+    -- JSON description of the checkpoint data that failed to commit
+    context                     TEXT         NOT NULL,
+    quarantined_at_ms           BIGINT       NOT NULL
+);
@@ -0,0 +1,17 @@
+-- Usage of the GraphQL service by each API key, aggregated over reporting periods. Written by
+-- the GraphQL service's usage reporting background task.
+CREATE TABLE usage_reports (
+    api_key                     TEXT         NOT NULL,
+    -- bounds of the reporting period, as unix timestamps in milliseconds
+    period_start_ms             BIGINT       NOT NULL,
+    period_end_ms               BIGINT       NOT NULL,
+    requests                    BIGINT       NOT NULL,
+    errors                      BIGINT       NOT NULL,
+    -- sums of the costs of the requests made during the period
+    input_nodes                 BIGINT       NOT NULL,
+    output_nodes                BIGINT       NOT NULL,
+    query_payload_bytes         BIGINT       NOT NULL,
+    latency_ms                  BIGINT       NOT NULL,
+    PRIMARY KEY (api_key, period_start_ms)
+);
+CREATE INDEX usage_reports_period_start_ms ON usage_reports (period_start_ms, api_key);
@@ -0,0 +1,8 @@
+-- Queries registered ahead of time to be served by the GraphQL service, when it only allows
+-- persisted queries to be executed.
+CREATE TABLE persisted_queries (
+    -- hex-encoded SHA-256 hash of the query text
+    query_hash                  TEXT         PRIMARY KEY,
+    -- the query text, for reference
+    query                       TEXT
+);
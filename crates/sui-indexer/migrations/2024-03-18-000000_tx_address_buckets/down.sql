@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS tx_address_buckets;
+DROP TABLE IF EXISTS hot_addresses;
@@ -0,0 +1,25 @@
+-- Addresses with so many transactions that paginating through them using only `tx_senders` and
+-- `tx_recipients` degrades. Their transactions are additionally counted in `tx_address_buckets`.
+-- Addresses are registered by inserting them into this table: the buckets of transactions indexed
+-- from then on are counted as they are committed, and a background task backfills the buckets of
+-- transactions indexed before.
+CREATE TABLE hot_addresses (
+    address                     BYTEA        PRIMARY KEY,
+    -- the lowest bucket backfilled so far, NULL if the backfill has not started
+    backfilled_from_bucket      BIGINT,
+    -- whether the backfill has reached the address's earliest transaction
+    backfilled                  BOOLEAN      NOT NULL DEFAULT FALSE
+);
+
+-- The number of transactions each hot address sent and received, in buckets of consecutive
+-- transaction sequence numbers (bucket N holds sequence numbers in
+-- [N * bucket size, (N + 1) * bucket size)). Counts can lag behind `tx_senders` and
+-- `tx_recipients`, but never exceed them, so readers can use them to bound the range of
+-- transactions to scan for a page of an address's transactions.
+CREATE TABLE tx_address_buckets (
+    address                     BYTEA        NOT NULL,
+    bucket                      BIGINT       NOT NULL,
+    sent                        BIGINT       NOT NULL,
+    received                    BIGINT       NOT NULL,
+    PRIMARY KEY(address, bucket)
+);
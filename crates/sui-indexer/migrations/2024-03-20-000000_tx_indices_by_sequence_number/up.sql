@@ -0,0 +1,4 @@
+-- Index the remaining transaction index tables by transaction sequence number, so that the rows
+-- of pruned transactions can be found without scanning the whole table.
+CREATE INDEX IF NOT EXISTS tx_input_objects_tx_sequence_number_index ON tx_input_objects (tx_sequence_number ASC);
+CREATE INDEX IF NOT EXISTS tx_changed_objects_tx_sequence_number_index ON tx_changed_objects (tx_sequence_number ASC);
@@ -0,0 +1,11 @@
+-- Results of cross-checking the indexed data of an epoch against the system state of the
+-- fullnode, while the epoch is in progress. Discrepancies point at ingestion bugs that corrupted
+-- the epoch's data.
+CREATE TABLE epoch_consistency_reports (
+    epoch                       BIGINT       PRIMARY KEY,
+    -- number of discrepancies found, 0 if the indexed data is consistent with the fullnode
+    discrepancies               BIGINT       NOT NULL,
+    -- JSON list of the discrepancies found, with the indexed and fullnode value of each
+    report                      TEXT         NOT NULL,
+    checked_at_ms               BIGINT       NOT NULL
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS objects_snapshot_type_pattern;
+DROP INDEX IF EXISTS objects_type_pattern;
@@ -0,0 +1,4 @@
+-- Index object types for prefix matching, so that filters on type patterns (e.g. all instances of
+-- a generic type, regardless of its type parameters) can be served by index lookups.
+CREATE INDEX IF NOT EXISTS objects_type_pattern ON objects (object_type text_pattern_ops);
+CREATE INDEX IF NOT EXISTS objects_snapshot_type_pattern ON objects_snapshot (object_type text_pattern_ops, object_id);
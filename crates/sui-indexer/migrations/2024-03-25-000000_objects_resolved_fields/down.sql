@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE objects DROP COLUMN IF EXISTS display_fields;
+ALTER TABLE objects DROP COLUMN IF EXISTS display_version;
+ALTER TABLE objects DROP COLUMN IF EXISTS df_bcs_name;
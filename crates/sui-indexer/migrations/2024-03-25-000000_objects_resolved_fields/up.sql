@@ -0,0 +1,8 @@
+-- Fields of objects resolved at ingestion (or by the backfill), so that reads do not need to
+-- resolve the layouts of their types. Both are NULL until resolved.
+-- The BCS bytes of the name of dynamic fields.
+ALTER TABLE objects ADD COLUMN IF NOT EXISTS df_bcs_name BYTEA;
+-- The Display of the object rendered as JSON, and the version of the Display it was rendered
+-- with, which is stale once the Display is updated.
+ALTER TABLE objects ADD COLUMN IF NOT EXISTS display_version SMALLINT;
+ALTER TABLE objects ADD COLUMN IF NOT EXISTS display_fields TEXT;
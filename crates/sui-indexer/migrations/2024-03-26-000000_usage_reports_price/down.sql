@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE usage_reports DROP COLUMN IF EXISTS price;
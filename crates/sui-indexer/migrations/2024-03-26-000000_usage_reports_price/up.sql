@@ -0,0 +1,3 @@
+-- sum of the prices of the requests made during the period, according to the GraphQL service's
+-- pricing config
+ALTER TABLE usage_reports ADD COLUMN IF NOT EXISTS price BIGINT NOT NULL DEFAULT 0;
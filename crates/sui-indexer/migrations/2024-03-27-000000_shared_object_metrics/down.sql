@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS shared_object_metrics;
+DROP TABLE IF EXISTS tx_shared_objects;
@@ -0,0 +1,29 @@
+-- The shared objects taken as input by each transaction, according to its effects.
+CREATE TABLE tx_shared_objects (
+    tx_sequence_number          BIGINT       NOT NULL,
+    checkpoint_sequence_number  BIGINT       NOT NULL,
+    object_id                   BYTEA        NOT NULL,
+    -- whether the transaction took the object mutably, and was sequenced against the other
+    -- transactions taking it
+    mutable                     BOOLEAN      NOT NULL,
+    -- whether the transaction executed successfully
+    success                     BOOLEAN      NOT NULL,
+    PRIMARY KEY(object_id, tx_sequence_number)
+);
+CREATE INDEX tx_shared_objects_tx_sequence_number_index ON tx_shared_objects (tx_sequence_number ASC);
+
+-- Daily aggregates of `tx_shared_objects`, computed once a day has completed.
+CREATE TABLE shared_object_metrics (
+    -- days since the unix epoch, in UTC
+    day                         BIGINT       NOT NULL,
+    object_id                   BYTEA        NOT NULL,
+    tx_count                    BIGINT       NOT NULL,
+    mutating_tx_count           BIGINT       NOT NULL,
+    failed_tx_count             BIGINT       NOT NULL,
+    distinct_senders            BIGINT       NOT NULL,
+    -- the most transactions mutating the object in a single checkpoint
+    max_checkpoint_mutations    BIGINT       NOT NULL,
+    PRIMARY KEY(day, object_id)
+);
+CREATE INDEX shared_object_metrics_object_id ON shared_object_metrics (object_id, day);
+CREATE INDEX shared_object_metrics_mutating_tx_count ON shared_object_metrics (day, mutating_tx_count);
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS sender_gas_metrics;
+DROP TABLE IF EXISTS package_tx_metrics;
+DROP TABLE IF EXISTS active_address_metrics;
+DROP TABLE IF EXISTS rollup_watermarks;
+ALTER TABLE tx_senders DROP COLUMN IF EXISTS gas_used;
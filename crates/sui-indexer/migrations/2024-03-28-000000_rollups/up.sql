@@ -0,0 +1,39 @@
+-- Net gas used by the transaction. NULL for transactions indexed before this column was added.
+ALTER TABLE tx_senders ADD COLUMN gas_used BIGINT;
+
+-- The last checkpoint whose transactions are counted in the rollup tables below. The rollups are
+-- refreshed incrementally, adding the transactions of the checkpoints after it.
+CREATE TABLE rollup_watermarks (
+    name                        TEXT         PRIMARY KEY,
+    checkpoint_sequence_number  BIGINT       NOT NULL
+);
+
+-- Daily rollups of `tx_senders` and `tx_calls`. The rows of the latest day are updated as its
+-- checkpoints are indexed.
+CREATE TABLE active_address_metrics (
+    -- days since the unix epoch, in UTC
+    day                         BIGINT       PRIMARY KEY,
+    -- number of distinct addresses sending transactions
+    active_addresses            BIGINT       NOT NULL
+);
+
+CREATE TABLE package_tx_metrics (
+    day                         BIGINT       NOT NULL,
+    package                     BYTEA        NOT NULL,
+    -- number of transactions calling functions of the package
+    tx_count                    BIGINT       NOT NULL,
+    PRIMARY KEY(day, package)
+);
+CREATE INDEX package_tx_metrics_package ON package_tx_metrics (package, day);
+CREATE INDEX package_tx_metrics_tx_count ON package_tx_metrics (day, tx_count);
+
+CREATE TABLE sender_gas_metrics (
+    day                         BIGINT       NOT NULL,
+    sender                      BYTEA        NOT NULL,
+    tx_count                    BIGINT       NOT NULL,
+    -- sum of the net gas used by the transactions of the sender
+    total_gas_used              BIGINT       NOT NULL,
+    PRIMARY KEY(day, sender)
+);
+CREATE INDEX sender_gas_metrics_sender ON sender_gas_metrics (sender, day);
+CREATE INDEX sender_gas_metrics_total_gas_used ON sender_gas_metrics (day, total_gas_used);
@@ -1,14 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::errors::IndexerError;
 use crate::indexer_reader::IndexerReader;
 use jsonrpsee::{core::RpcResult, RpcModule};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_api::{validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS};
 use sui_json_rpc_types::{
-    CheckpointedObjectID, EpochInfo, EpochPage, Page, QueryObjectsPage, SuiObjectResponseQuery,
+    ActiveAddressMetrics, CheckpointedObjectID, EpochInfo, EpochPage, MoveFunctionMetrics,
+    PackageTxMetrics, Page, QueryObjectsPage, SenderGasMetrics, SharedObjectMetrics,
+    SuiObjectResponseQuery, TransactionInclusionProof,
 };
 use sui_open_rpc::Module;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
 use sui_types::sui_serde::BigInt;
 
 pub(crate) struct ExtendedApi {
@@ -78,6 +83,132 @@ impl ExtendedApiServer for ExtendedApi {
             .await?;
         Ok(latest_checkpoint.network_total_transactions.into())
     }
+
+    async fn get_transaction_inclusion_proof(
+        &self,
+        digest: TransactionDigest,
+    ) -> RpcResult<TransactionInclusionProof> {
+        self.inner
+            .spawn_blocking(move |this| this.get_transaction_inclusion_proof(digest))
+            .await?
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Transaction {digest} not found in any indexed checkpoint"
+                ))
+                .into()
+            })
+    }
+
+    async fn get_move_function_metrics(
+        &self,
+        package: ObjectID,
+        module: Option<String>,
+        function: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<MoveFunctionMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| {
+                this.get_move_function_metrics(package, module, function, limit)
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_hot_move_functions(
+        &self,
+        day: Option<BigInt<u64>>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<MoveFunctionMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| this.get_hot_move_functions(day.map(|d| *d), limit))
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_shared_object_metrics(
+        &self,
+        object_id: ObjectID,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<SharedObjectMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| this.get_shared_object_metrics(object_id, limit))
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_hot_shared_objects(
+        &self,
+        day: Option<BigInt<u64>>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<SharedObjectMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| this.get_hot_shared_objects(day.map(|d| *d), limit))
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_active_address_metrics(
+        &self,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ActiveAddressMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| this.get_active_address_metrics(limit))
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_package_tx_metrics(
+        &self,
+        package: ObjectID,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<PackageTxMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| this.get_package_tx_metrics(package, limit))
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_top_packages(
+        &self,
+        day: Option<BigInt<u64>>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<PackageTxMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| this.get_top_packages(day.map(|d| *d), limit))
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_sender_gas_metrics(
+        &self,
+        sender: SuiAddress,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<SenderGasMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| this.get_sender_gas_metrics(sender, limit))
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_top_gas_senders(
+        &self,
+        day: Option<BigInt<u64>>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<SenderGasMetrics>> {
+        let limit = validate_limit(limit, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS)?;
+        self.inner
+            .spawn_blocking(move |this| this.get_top_gas_senders(day.map(|d| *d), limit))
+            .await
+            .map_err(Into::into)
+    }
 }
 
 impl SuiRpcModule for ExtendedApi {
@@ -57,16 +57,19 @@ impl IndexerApi {
             .spawn_blocking(move |this| {
                 objects
                     .into_iter()
-                    .map(|object| object.try_into_object_read(&this))
-                    .collect::<Result<Vec<_>, _>>()
+                    .map(|object| {
+                        let resolved_display = object.resolved_display();
+                        Ok((object.try_into_object_read(&this)?, resolved_display))
+                    })
+                    .collect::<Result<Vec<_>, IndexerError>>()
             })
             .await?;
         let has_next_page = objects.len() > limit;
         objects.truncate(limit);
 
-        let next_cursor = objects.last().map(|o_read| o_read.object_id());
+        let next_cursor = objects.last().map(|(o_read, _)| o_read.object_id());
         let mut parallel_tasks = vec![];
-        for o in objects {
+        for (o, resolved_display) in objects {
             let inner_clone = self.inner.clone();
             let options = options.clone();
             parallel_tasks.push(tokio::task::spawn(async move {
@@ -76,7 +79,10 @@ impl IndexerApi {
                     )),
                     ObjectRead::Exists(object_ref, o, layout) => {
                         if options.show_display {
-                            match inner_clone.get_display_fields(&o, &layout).await {
+                            match inner_clone
+                                .get_display_fields(&o, &layout, resolved_display)
+                                .await
+                            {
                                 Ok(rendered_fields) => Ok(SuiObjectResponse::new_with_data(
                                     (object_ref, o, layout, options, Some(rendered_fields))
                                         .try_into()?,
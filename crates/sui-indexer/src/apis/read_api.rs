@@ -81,7 +81,7 @@ impl ReadApiServer for ReadApi {
             ObjectRead::Exists(object_ref, o, layout) => {
                 let mut display_fields = None;
                 if options.show_display {
-                    match self.inner.get_display_fields(&o, &layout).await {
+                    match self.inner.get_display_fields(&o, &layout, None).await {
                         Ok(rendered_fields) => display_fields = Some(rendered_fields),
                         Err(e) => {
                             return Ok(SuiObjectResponse::new(
@@ -129,6 +129,128 @@ pub enum IndexerError {
 
     #[error(transparent)]
     NameServiceError(#[from] NameServiceError),
+
+    #[error("Indexer failed to connect to the change data capture broker with error: `{0}`")]
+    CdcConnectionError(String),
+
+    #[error("Indexer failed to publish change data capture messages with error: `{0}`")]
+    CdcPublishError(String),
+
+    #[error("ClickHouse is unavailable, or too busy to serve the indexer, with error: `{0}`")]
+    ClickHouseUnavailableError(String),
+
+    #[error("ClickHouse failed to run a query of the indexer with error: `{0}`")]
+    ClickHouseQueryError(String),
+}
+
+/// Classification of indexer errors, which determines how they are handled and labels them in
+/// metrics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexerErrorKind {
+    /// Transient errors, e.g. a serialization failure or a deadlock between concurrent
+    /// transactions, or a lost connection. The failed operation can be retried.
+    Retryable,
+    /// Errors that retrying the failed operation will not fix.
+    Fatal,
+    /// Data read from the fullnode or from the DB is invalid or inconsistent.
+    DataCorruption,
+    /// The indexer is misconfigured, or is asked for something it does not support.
+    Config,
+}
+
+impl IndexerErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexerErrorKind::Retryable => "retryable",
+            IndexerErrorKind::Fatal => "fatal",
+            IndexerErrorKind::DataCorruption => "data_corruption",
+            IndexerErrorKind::Config => "config",
+        }
+    }
+}
+
+impl std::fmt::Display for IndexerErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Messages of database errors that diesel does not classify, but that go away when the failed
+/// operation is retried: deadlocks (SQLSTATE 40P01), statement and lock timeouts (57014, 55P03),
+/// and connections terminated or lost by the server (57P01, 08006).
+const TRANSIENT_DATABASE_ERRORS: &[&str] = &["deadlock detected", "timeout", "connection"];
+
+impl IndexerError {
+    pub fn kind(&self) -> IndexerErrorKind {
+        use diesel::result::{DatabaseErrorKind, Error as DieselError};
+        use IndexerErrorKind::*;
+        match self {
+            IndexerError::ErrorWithContext(_, e) => e.kind(),
+
+            IndexerError::FullNodeReadingError(_)
+            | IndexerError::PgPoolConnectionError(_)
+            | IndexerError::PostgresWriteError(_)
+            | IndexerError::CdcPublishError(_)
+            | IndexerError::ClickHouseUnavailableError(_) => Retryable,
+
+            IndexerError::PostgresError(DieselError::DatabaseError(kind, info)) => match kind {
+                DatabaseErrorKind::SerializationFailure
+                | DatabaseErrorKind::ClosedConnection
+                | DatabaseErrorKind::UnableToSendCommand => Retryable,
+                // Deadlocks, timeouts and lost connections are reported with SQLSTATEs that diesel
+                // does not classify.
+                DatabaseErrorKind::Unknown
+                    if TRANSIENT_DATABASE_ERRORS
+                        .iter()
+                        .any(|transient| info.message().contains(transient)) =>
+                {
+                    Retryable
+                }
+                _ => Fatal,
+            },
+            IndexerError::PostgresError(DieselError::DeserializationError(_)) => DataCorruption,
+            IndexerError::PostgresError(_) => Fatal,
+
+            IndexerError::DateTimeParsingError(_)
+            | IndexerError::EventDeserializationError(_)
+            | IndexerError::UnexpectedFullnodeResponseError(_)
+            | IndexerError::ObjectMutationNotAvailable
+            | IndexerError::SerdeError(_)
+            | IndexerError::PersistentStorageDataCorruptionError(_)
+            | IndexerError::ObjectIdParseError(_)
+            | IndexerError::InvalidTransactionDigestError(_)
+            | IndexerError::BcsError(_) => DataCorruption,
+
+            IndexerError::JsonRpcServerError(_)
+            | IndexerError::PgConnectionPoolInitError(_)
+            | IndexerError::HttpClientInitError(_)
+            | IndexerError::CdcConnectionError(_)
+            | IndexerError::NotSupportedError(_) => Config,
+
+            IndexerError::DataTransformationError(_)
+            | IndexerError::InsertableParsingError(_)
+            | IndexerError::PostgresReadError(_)
+            | IndexerError::PostgresResetError(_)
+            | IndexerError::DynamicFieldError(_)
+            | IndexerError::GenericError(_)
+            | IndexerError::ResolveMoveStructError(_)
+            | IndexerError::UncategorizedError(_)
+            | IndexerError::SuiError(_)
+            | IndexerError::InvalidArgumentError(_)
+            | IndexerError::UserInputError(_)
+            | IndexerError::ModuleResolutionError(_)
+            | IndexerError::ObjectResponseError(_)
+            | IndexerError::FastCryptoError(_)
+            | IndexerError::MpscChannelError(_)
+            | IndexerError::NameServiceError(_)
+            | IndexerError::ClickHouseQueryError(_) => Fatal,
+        }
+    }
+
+    /// Returns true if the error is transient, so that the failed operation can be retried.
+    pub fn is_retryable(&self) -> bool {
+        self.kind() == IndexerErrorKind::Retryable
+    }
 }
 
 pub trait Context<T> {
@@ -152,3 +274,81 @@ impl From<tokio::task::JoinError> for IndexerError {
         IndexerError::UncategorizedError(anyhow::Error::from(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+    use super::*;
+
+    fn database_error(kind: DatabaseErrorKind, message: &str) -> IndexerError {
+        IndexerError::PostgresError(DieselError::DatabaseError(
+            kind,
+            Box::new(message.to_string()),
+        ))
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        assert!(database_error(DatabaseErrorKind::SerializationFailure, "").is_retryable());
+        assert!(database_error(DatabaseErrorKind::ClosedConnection, "").is_retryable());
+        assert!(database_error(DatabaseErrorKind::UnableToSendCommand, "").is_retryable());
+        assert!(database_error(DatabaseErrorKind::Unknown, "deadlock detected").is_retryable());
+        assert!(database_error(
+            DatabaseErrorKind::Unknown,
+            "canceling statement due to statement timeout"
+        )
+        .is_retryable());
+        assert!(database_error(
+            DatabaseErrorKind::Unknown,
+            "canceling statement due to lock timeout"
+        )
+        .is_retryable());
+        assert!(database_error(
+            DatabaseErrorKind::Unknown,
+            "terminating connection due to administrator command"
+        )
+        .is_retryable());
+        assert!(IndexerError::PgPoolConnectionError("timed out".to_string()).is_retryable());
+        assert!(IndexerError::PostgresWriteError("failed".to_string()).is_retryable());
+        assert!(IndexerError::ClickHouseUnavailableError("reset".to_string()).is_retryable());
+        assert!(
+            Err::<(), _>(database_error(DatabaseErrorKind::SerializationFailure, ""))
+                .context("Failed to write objects")
+                .unwrap_err()
+                .is_retryable()
+        );
+
+        assert!(!database_error(DatabaseErrorKind::UniqueViolation, "").is_retryable());
+        assert!(!database_error(DatabaseErrorKind::Unknown, "syntax error").is_retryable());
+        assert!(!IndexerError::PostgresError(DieselError::NotFound).is_retryable());
+        assert!(!IndexerError::ClickHouseQueryError("syntax error".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        assert_eq!(
+            IndexerError::PersistentStorageDataCorruptionError("bad package".to_string()).kind(),
+            IndexerErrorKind::DataCorruption
+        );
+        assert_eq!(
+            IndexerError::PgConnectionPoolInitError("bad url".to_string()).kind(),
+            IndexerErrorKind::Config
+        );
+        assert_eq!(
+            Err::<(), _>(IndexerError::NotSupportedError("mysql".to_string()))
+                .context("Failed to start indexer")
+                .unwrap_err()
+                .kind(),
+            IndexerErrorKind::Config
+        );
+        assert_eq!(
+            IndexerError::PostgresWriteError("failed".to_string()).kind(),
+            IndexerErrorKind::Retryable
+        );
+        assert_eq!(
+            IndexerError::CdcPublishError("timed out".to_string()).kind(),
+            IndexerErrorKind::Retryable
+        );
+    }
+}
@@ -6,6 +6,7 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use crate::metrics::IndexerMetrics;
 
 use super::fetcher::CheckpointFetcher;
+use super::source_pool::FullnodeSourcePool;
 use super::Handler;
 
 pub struct IndexerBuilder {
@@ -63,11 +64,10 @@ impl IndexerBuilder {
                     .with_label_values(&["checkpoint_tx_downloading"]),
             );
 
-        // experimental rest api route is found at `/rest` on the same interface as the jsonrpc
-        // service
-        let rest_api_url = format!("{}/rest", self.rest_url.unwrap());
+        let sources =
+            FullnodeSourcePool::new(vec![self.rest_url.unwrap()], u64::MAX, self.metrics.clone());
         let fetcher = CheckpointFetcher::new(
-            sui_rest_api::Client::new(rest_api_url),
+            sources,
             self.last_downloaded_checkpoint,
             downloaded_checkpoint_data_sender,
             self.metrics.clone(),
@@ -2,21 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use sui_rest_api::{CheckpointData, Client};
+use sui_rest_api::CheckpointData;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tracing::{info, warn};
 
 use crate::metrics::IndexerMetrics;
 
+use super::source_pool::FullnodeSourcePool;
+
 pub struct CheckpointDownloadData {
     pub size: usize,
     pub data: CheckpointData,
 }
 
 pub struct CheckpointFetcher {
-    client: Client,
+    sources: FullnodeSourcePool,
     last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
     highest_known_checkpoint: CheckpointSequenceNumber,
+    /// The last checkpoint to download, if the fetcher stops after downloading it, instead of
+    /// following the network.
+    end_checkpoint: Option<CheckpointSequenceNumber>,
     sender: mysten_metrics::metered_channel::Sender<CheckpointDownloadData>,
     metrics: IndexerMetrics,
 }
@@ -26,20 +31,30 @@ impl CheckpointFetcher {
     const CHECKPOINT_DOWNLOAD_CONCURRENCY: usize = 100;
 
     pub fn new(
-        client: Client,
+        sources: FullnodeSourcePool,
         last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
         sender: mysten_metrics::metered_channel::Sender<CheckpointDownloadData>,
         metrics: IndexerMetrics,
     ) -> Self {
         Self {
-            client,
+            sources,
             last_downloaded_checkpoint,
             highest_known_checkpoint: 0,
+            end_checkpoint: None,
             sender,
             metrics,
         }
     }
 
+    /// Stop after downloading `end_checkpoint`. Dropping the fetcher closes its channel, so that
+    /// consumers know that there are no more checkpoints to come.
+    pub fn with_end_checkpoint(self, end_checkpoint: CheckpointSequenceNumber) -> Self {
+        Self {
+            end_checkpoint: Some(end_checkpoint),
+            ..self
+        }
+    }
+
     pub async fn run(mut self) {
         let mut interval = tokio::time::interval(Self::INTERVAL_PERIOD);
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
@@ -56,15 +71,24 @@ impl CheckpointFetcher {
 
             if let Err(e) = self.download_checkpoints().await {
                 warn!("error downloading checkpoints: {e}");
+                self.sources.record_error();
                 continue;
             }
+            self.sources.record_success();
+
+            if let Some(end) = self.end_checkpoint {
+                if self.last_downloaded_checkpoint >= Some(end) {
+                    info!("CheckpointFetcher finished downloading checkpoints up to {end}");
+                    return;
+                }
+            }
         }
     }
 
     async fn update_highest_known_checkpoint(&mut self) -> Result<()> {
-        let checkpoint = self.client.get_latest_checkpoint().await?;
+        let highest_checkpoint = self.sources.update_latest_checkpoints().await?;
         self.highest_known_checkpoint =
-            std::cmp::max(self.highest_known_checkpoint, *checkpoint.sequence_number());
+            std::cmp::max(self.highest_known_checkpoint, highest_checkpoint);
         // NOTE: this metric is used to monitor delta between the highest known checkpoint on FN and in DB,
         // there is an alert based on the delta of these two metrics.
         self.metrics
@@ -77,17 +101,26 @@ impl CheckpointFetcher {
         use futures::StreamExt;
         use tap::Pipe;
 
+        // Checkpoints are only downloaded up to the latest one of the active fullnode, which may
+        // lag behind the others by up to the max lag.
+        let Some(active_latest_checkpoint) = self.sources.active_latest_checkpoint() else {
+            return Ok(());
+        };
+        let last_checkpoint = match self.end_checkpoint {
+            Some(end) => end.min(active_latest_checkpoint),
+            None => active_latest_checkpoint,
+        };
         let checkpoint_range = self
             .last_downloaded_checkpoint
             .map(|i| i.checked_add(1).unwrap())
-            .unwrap_or(0)..=self.highest_known_checkpoint;
+            .unwrap_or(0)..=last_checkpoint;
 
         if !checkpoint_range.is_empty() {
             info!("Starting download of checkpoints {checkpoint_range:?}");
         }
 
         let mut checkpoint_stream = checkpoint_range
-            .map(|next| self.client.get_full_checkpoint(next))
+            .map(|next| self.sources.client().get_full_checkpoint(next))
             .pipe(futures::stream::iter)
             .buffered(Self::CHECKPOINT_DOWNLOAD_CONCURRENCY);
 
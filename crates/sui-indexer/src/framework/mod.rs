@@ -7,6 +7,7 @@ pub mod interface;
 // TODO remove the pub(crater) once indexer.rs is renamed to lib.rs
 pub(crate) mod fetcher;
 pub(crate) mod runner;
+pub(crate) mod source_pool;
 
 pub use builder::IndexerBuilder;
 pub use interface::Handler;
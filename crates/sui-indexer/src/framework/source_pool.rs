@@ -0,0 +1,239 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use sui_rest_api::Client;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::{info, warn};
+
+use crate::metrics::IndexerMetrics;
+
+/// A fullnode that checkpoints can be downloaded from, with the health observed so far.
+struct FullnodeSource {
+    url: String,
+    client: Client,
+    /// The latest checkpoint of the fullnode, as of the last time it was polled successfully.
+    latest_checkpoint: Option<CheckpointSequenceNumber>,
+    /// The number of requests to the fullnode that failed in a row.
+    consecutive_errors: u32,
+}
+
+/// The fullnodes that checkpoints are downloaded from. Checkpoints are downloaded from one active
+/// fullnode at a time, which is switched over to the healthiest of the others once it fails
+/// repeatedly, or lags behind the highest checkpoint known by them by more than `max_lag`
+/// checkpoints. The active fullnode is kept as long as it is healthy, to avoid flapping.
+pub struct FullnodeSourcePool {
+    sources: Vec<FullnodeSource>,
+    active: usize,
+    max_lag: u64,
+    metrics: IndexerMetrics,
+}
+
+impl FullnodeSourcePool {
+    /// The number of requests failing in a row after which a fullnode is considered unhealthy.
+    const MAX_CONSECUTIVE_ERRORS: u32 = 3;
+
+    /// `urls` are the URLs of the fullnodes' JSON-RPC service, the first one being active first.
+    pub fn new(urls: Vec<String>, max_lag: u64, metrics: IndexerMetrics) -> Self {
+        assert!(!urls.is_empty(), "At least one fullnode URL is required");
+        let sources = urls
+            .into_iter()
+            .map(|url| FullnodeSource {
+                // experimental rest api route is found at `/rest` on the same interface as the
+                // jsonrpc service
+                client: Client::new(format!("{url}/rest")),
+                url,
+                latest_checkpoint: None,
+                consecutive_errors: 0,
+            })
+            .collect();
+        let pool = Self {
+            sources,
+            active: 0,
+            max_lag,
+            metrics,
+        };
+        pool.report_active();
+        pool
+    }
+
+    /// The client of the active fullnode.
+    pub fn client(&self) -> &Client {
+        &self.sources[self.active].client
+    }
+
+    /// The latest checkpoint of the active fullnode, which checkpoints can be downloaded up to.
+    pub fn active_latest_checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        self.sources[self.active].latest_checkpoint
+    }
+
+    /// Polls the latest checkpoint of every fullnode, switches over from the active fullnode if it
+    /// is unhealthy, and returns the highest checkpoint known by any fullnode.
+    pub async fn update_latest_checkpoints(&mut self) -> Result<CheckpointSequenceNumber> {
+        let responses = futures::future::join_all(
+            self.sources
+                .iter()
+                .map(|source| source.client.get_latest_checkpoint()),
+        )
+        .await;
+
+        for (index, response) in responses.into_iter().enumerate() {
+            match response {
+                Ok(checkpoint) => {
+                    let source = &mut self.sources[index];
+                    source.latest_checkpoint = Some(*checkpoint.sequence_number());
+                    // The active fullnode is only healthy again once checkpoints can be
+                    // downloaded from it, not just polled.
+                    if index != self.active {
+                        source.consecutive_errors = 0;
+                    }
+                    self.metrics
+                        .fullnode_source_latest_checkpoint
+                        .with_label_values(&[&source.url])
+                        .set(*checkpoint.sequence_number() as i64);
+                }
+                Err(e) => {
+                    warn!(
+                        "error polling latest checkpoint of {}: {e}",
+                        self.sources[index].url
+                    );
+                    self.record_error_at(index);
+                }
+            }
+        }
+
+        self.switch_over_if_unhealthy();
+        self.highest_checkpoint()
+            .ok_or_else(|| anyhow!("No fullnode returned its latest checkpoint"))
+    }
+
+    /// Records that checkpoints were downloaded from the active fullnode.
+    pub fn record_success(&mut self) {
+        self.sources[self.active].consecutive_errors = 0;
+    }
+
+    /// Records that downloading checkpoints from the active fullnode failed, switching over from
+    /// it if that makes it unhealthy.
+    pub fn record_error(&mut self) {
+        self.record_error_at(self.active);
+        self.switch_over_if_unhealthy();
+    }
+
+    fn record_error_at(&mut self, index: usize) {
+        let source = &mut self.sources[index];
+        source.consecutive_errors += 1;
+        self.metrics
+            .fullnode_source_errors
+            .with_label_values(&[&source.url])
+            .inc();
+    }
+
+    fn highest_checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        self.sources
+            .iter()
+            .filter_map(|source| source.latest_checkpoint)
+            .max()
+    }
+
+    /// The lag of a fullnode behind `highest`, if it is healthy.
+    fn healthy_lag(&self, index: usize, highest: CheckpointSequenceNumber) -> Option<u64> {
+        let source = &self.sources[index];
+        if source.consecutive_errors >= Self::MAX_CONSECUTIVE_ERRORS {
+            return None;
+        }
+        let lag = highest.saturating_sub(source.latest_checkpoint?);
+        (lag <= self.max_lag).then_some(lag)
+    }
+
+    fn switch_over_if_unhealthy(&mut self) {
+        let Some(highest) = self.highest_checkpoint() else {
+            return;
+        };
+        if self.healthy_lag(self.active, highest).is_some() {
+            return;
+        }
+
+        let healthiest = (0..self.sources.len())
+            .filter_map(|index| {
+                let lag = self.healthy_lag(index, highest)?;
+                Some((index, (self.sources[index].consecutive_errors, lag)))
+            })
+            .min_by_key(|(_, score)| *score);
+        let Some((index, _)) = healthiest else {
+            warn!("No healthy fullnode to download checkpoints from");
+            return;
+        };
+
+        info!(
+            "Switching checkpoint download over from {} to {}",
+            self.sources[self.active].url, self.sources[index].url
+        );
+        self.active = index;
+        self.metrics.fullnode_source_switchovers.inc();
+        self.report_active();
+    }
+
+    fn report_active(&self) {
+        for (index, source) in self.sources.iter().enumerate() {
+            self.metrics
+                .fullnode_source_active
+                .with_label_values(&[&source.url])
+                .set((index == self.active) as i64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::Registry;
+
+    use super::*;
+
+    fn pool() -> FullnodeSourcePool {
+        FullnodeSourcePool::new(
+            vec!["http://a".to_string(), "http://b".to_string()],
+            100,
+            IndexerMetrics::new(&Registry::default()),
+        )
+    }
+
+    #[test]
+    fn test_switch_over_when_lagging() {
+        let mut pool = pool();
+        pool.sources[0].latest_checkpoint = Some(1_000);
+        pool.sources[1].latest_checkpoint = Some(1_050);
+        pool.switch_over_if_unhealthy();
+        assert_eq!(pool.active, 0);
+
+        pool.sources[1].latest_checkpoint = Some(1_200);
+        pool.switch_over_if_unhealthy();
+        assert_eq!(pool.active, 1);
+        assert_eq!(pool.metrics.fullnode_source_switchovers.get(), 1);
+        assert_eq!(
+            pool.metrics
+                .fullnode_source_active
+                .with_label_values(&["http://b"])
+                .get(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_switch_over_on_errors() {
+        let mut pool = pool();
+        pool.sources[0].latest_checkpoint = Some(1_000);
+        pool.sources[1].latest_checkpoint = Some(1_000);
+        for _ in 0..FullnodeSourcePool::MAX_CONSECUTIVE_ERRORS - 1 {
+            pool.record_error();
+        }
+        assert_eq!(pool.active, 0);
+        pool.record_error();
+        assert_eq!(pool.active, 1);
+
+        // Without a healthy fullnode to switch over to, the active one is kept.
+        for _ in 0..FullnodeSourcePool::MAX_CONSECUTIVE_ERRORS {
+            pool.record_error();
+        }
+        assert_eq!(pool.active, 1);
+    }
+}
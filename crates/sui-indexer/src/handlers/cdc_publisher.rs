@@ -0,0 +1,444 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+
+use async_nats::jetstream::context::{PublishError, PublishErrorKind};
+use async_nats::jetstream::{self, context::Context};
+use async_nats::{Client, HeaderMap};
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::Serialize;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::{CheckpointDigest, TransactionDigest};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::transaction::TransactionDataAPI;
+use tracing::{error, info};
+
+use crate::errors::IndexerError;
+use crate::metrics::IndexerMetrics;
+use crate::types::{IndexerResult, TransactionKind};
+use crate::IndexerConfig;
+
+use super::CheckpointDataToCommit;
+
+/// Header that JetStream deduplicates messages by, so that republishing a checkpoint within the
+/// stream's duplicate window does not store it twice.
+const MESSAGE_ID_HEADER: &str = "Nats-Msg-Id";
+
+/// Publishes the data of committed checkpoints to NATS JetStream, for downstream consumers to
+/// follow changes without polling the DB. Each checkpoint is published as one message on
+/// `<prefix>.checkpoints`, and the last checkpoint of each published batch on
+/// `<prefix>.watermark`. A checkpoint that the server cannot accept, e.g. because it is larger
+/// than the server's maximum payload, is published as a dead letter on `<prefix>.dead_letters`
+/// instead, so that it does not hold back the checkpoints after it.
+///
+/// Checkpoints are published before they are recorded as committed, and the watermark only after
+/// all of its checkpoints have been acknowledged, so that every checkpoint up to the watermark is
+/// delivered at least once, even if the indexer restarts mid-batch. The watermark never goes
+/// backwards, even when checkpoints are published again.
+#[derive(Clone)]
+pub struct CdcPublisher {
+    client: Client,
+    jetstream: Context,
+    checkpoints_subject: String,
+    watermark_subject: String,
+    dead_letters_subject: String,
+    watermark: PublishedWatermark,
+    metrics: IndexerMetrics,
+}
+
+/// The data of a checkpoint, as published to `<prefix>.checkpoints`.
+#[derive(Serialize)]
+pub struct CheckpointMessage {
+    sequence_number: u64,
+    digest: CheckpointDigest,
+    epoch: u64,
+    timestamp_ms: u64,
+    transactions: Vec<TransactionMessage>,
+    events: Vec<EventMessage>,
+    object_changes: Vec<ObjectChangeMessage>,
+}
+
+#[derive(Serialize)]
+struct TransactionMessage {
+    tx_sequence_number: u64,
+    digest: TransactionDigest,
+    sender: SuiAddress,
+    is_system_tx: bool,
+    success: bool,
+    gas_used: i64,
+}
+
+#[derive(Serialize)]
+struct EventMessage {
+    tx_sequence_number: u64,
+    event_sequence_number: u64,
+    transaction_digest: TransactionDigest,
+    event_type: String,
+    /// Base64 encoded BCS contents of the event.
+    bcs: String,
+}
+
+#[derive(Serialize)]
+struct ObjectChangeMessage {
+    object_id: ObjectID,
+    object_version: u64,
+    /// The type of the object, or `None` for packages and deleted objects.
+    object_type: Option<String>,
+    owner: Option<SuiAddress>,
+    deleted: bool,
+}
+
+#[derive(Serialize)]
+struct WatermarkMessage {
+    checkpoint_sequence_number: u64,
+}
+
+/// A checkpoint that could not be published, as published to `<prefix>.dead_letters`, for
+/// consumers to fetch its data from the indexer's DB or a fullnode instead.
+#[derive(Debug, PartialEq, Serialize)]
+struct DeadLetterMessage {
+    checkpoint_sequence_number: u64,
+    reason: String,
+}
+
+/// The checkpoints of a batch, serialized and ready to be published.
+#[derive(Debug, Default)]
+struct EncodedBatch {
+    /// The sequence number and payload of each checkpoint that can be published.
+    checkpoints: Vec<(CheckpointSequenceNumber, Vec<u8>)>,
+    dead_letters: Vec<DeadLetterMessage>,
+}
+
+impl EncodedBatch {
+    /// Serializes a batch of checkpoints, dead-lettering those that cannot be serialized or whose
+    /// payload is larger than `max_payload` bytes, as the server would reject them.
+    fn encode(checkpoints: &[CheckpointMessage], max_payload: usize) -> Self {
+        let mut batch = Self::default();
+        for message in checkpoints {
+            let sequence_number = message.sequence_number;
+            match serde_json::to_vec(message) {
+                Ok(payload) if payload.len() <= max_payload => {
+                    batch.checkpoints.push((sequence_number, payload))
+                }
+                Ok(payload) => batch.dead_letters.push(DeadLetterMessage {
+                    checkpoint_sequence_number: sequence_number,
+                    reason: format!(
+                        "payload of {} bytes exceeds the server's maximum of {} bytes",
+                        payload.len(),
+                        max_payload
+                    ),
+                }),
+                Err(e) => batch.dead_letters.push(DeadLetterMessage {
+                    checkpoint_sequence_number: sequence_number,
+                    reason: format!("failed to serialize checkpoint: {e}"),
+                }),
+            }
+        }
+        batch
+    }
+}
+
+/// The highest watermark published so far.
+#[derive(Clone, Default)]
+struct PublishedWatermark(Arc<Mutex<Option<CheckpointSequenceNumber>>>);
+
+impl PublishedWatermark {
+    /// Whether publishing `sequence_number` as the watermark moves it forward.
+    fn advances(&self, sequence_number: CheckpointSequenceNumber) -> bool {
+        let watermark = self.0.lock().unwrap();
+        watermark.map_or(true, |watermark| watermark < sequence_number)
+    }
+
+    fn set(&self, sequence_number: CheckpointSequenceNumber) {
+        *self.0.lock().unwrap() = Some(sequence_number);
+    }
+}
+
+impl CdcPublisher {
+    /// Connects to the broker configured by `--cdc-nats-url`, if any.
+    pub async fn from_config(
+        config: &IndexerConfig,
+        metrics: IndexerMetrics,
+    ) -> IndexerResult<Option<Self>> {
+        let Some(url) = &config.cdc_nats_url else {
+            return Ok(None);
+        };
+        Ok(Some(
+            Self::connect(url, &config.cdc_subject_prefix, metrics).await?,
+        ))
+    }
+
+    pub async fn connect(
+        url: &str,
+        subject_prefix: &str,
+        metrics: IndexerMetrics,
+    ) -> IndexerResult<Self> {
+        let client = async_nats::connect(url).await.map_err(|e| {
+            IndexerError::CdcConnectionError(format!("Failed to connect to {url}: {e}"))
+        })?;
+        info!("Publishing committed checkpoints to {url} under {subject_prefix}");
+        Ok(Self {
+            jetstream: jetstream::new(client.clone()),
+            client,
+            checkpoints_subject: format!("{subject_prefix}.checkpoints"),
+            watermark_subject: format!("{subject_prefix}.watermark"),
+            dead_letters_subject: format!("{subject_prefix}.dead_letters"),
+            watermark: PublishedWatermark::default(),
+            metrics,
+        })
+    }
+
+    /// Publishes a batch of checkpoints, in order, followed by the new watermark, and waits for
+    /// JetStream to acknowledge all of them. Checkpoints that the server rejects are
+    /// dead-lettered. Fails with a retryable error if the server could not be reached, and with
+    /// a configuration error if no stream captures the subjects published to.
+    pub async fn publish(&self, checkpoints: &[CheckpointMessage]) -> IndexerResult<()> {
+        let Some(last) = checkpoints.last() else {
+            return Ok(());
+        };
+        let last_checkpoint_seq = last.sequence_number;
+        let _guard = self.metrics.cdc_publish_latency.start_timer();
+
+        // The maximum payload is read on every batch, as it may change when reconnecting to
+        // another server of the cluster.
+        let batch = EncodedBatch::encode(checkpoints, self.client.server_info().max_payload);
+        let mut dead_letters = batch.dead_letters;
+        let mut acks = Vec::with_capacity(batch.checkpoints.len());
+        for (sequence_number, payload) in batch.checkpoints {
+            let ack = self
+                .send(
+                    &self.checkpoints_subject,
+                    &format!("checkpoint-{sequence_number}"),
+                    payload,
+                )
+                .await?;
+            acks.push((sequence_number, ack));
+        }
+        for (sequence_number, ack) in acks {
+            match ack.await {
+                Ok(_) => {}
+                // The stream answered with an error, e.g. because the message exceeds its
+                // maximum size, which publishing it again would not change.
+                Err(e) if e.kind() == PublishErrorKind::Other => {
+                    dead_letters.push(DeadLetterMessage {
+                        checkpoint_sequence_number: sequence_number,
+                        reason: e.to_string(),
+                    })
+                }
+                Err(e) => return Err(self.publish_error(e)),
+            }
+        }
+
+        let dead_lettered = dead_letters.len();
+        for dead_letter in dead_letters {
+            let sequence_number = dead_letter.checkpoint_sequence_number;
+            error!(
+                "Failed to publish checkpoint {}, dead-lettering it: {}",
+                sequence_number, dead_letter.reason
+            );
+            self.send_json(
+                &self.dead_letters_subject,
+                &format!("dead-letter-{sequence_number}"),
+                &dead_letter,
+            )
+            .await?
+            .await
+            .map_err(|e| self.publish_error(e))?;
+        }
+
+        // Checkpoints published again, e.g. one by one after their batch failed to commit, must
+        // not move the watermark back.
+        if self.watermark.advances(last_checkpoint_seq) {
+            let watermark = WatermarkMessage {
+                checkpoint_sequence_number: last_checkpoint_seq,
+            };
+            self.send_json(
+                &self.watermark_subject,
+                &format!("watermark-{last_checkpoint_seq}"),
+                &watermark,
+            )
+            .await?
+            .await
+            .map_err(|e| self.publish_error(e))?;
+            self.watermark.set(last_checkpoint_seq);
+            self.metrics
+                .latest_cdc_published_checkpoint_sequence_number
+                .set(last_checkpoint_seq as i64);
+        }
+
+        self.metrics
+            .total_cdc_checkpoints_published
+            .inc_by((checkpoints.len() - dead_lettered) as u64);
+        self.metrics
+            .total_cdc_checkpoints_dead_lettered
+            .inc_by(dead_lettered as u64);
+        Ok(())
+    }
+
+    async fn send_json<T: Serialize>(
+        &self,
+        subject: &str,
+        message_id: &str,
+        message: &T,
+    ) -> IndexerResult<jetstream::context::PublishAckFuture> {
+        let payload =
+            serde_json::to_vec(message).map_err(|e| IndexerError::SerdeError(e.to_string()))?;
+        self.send(subject, message_id, payload).await
+    }
+
+    async fn send(
+        &self,
+        subject: &str,
+        message_id: &str,
+        payload: Vec<u8>,
+    ) -> IndexerResult<jetstream::context::PublishAckFuture> {
+        let mut headers = HeaderMap::new();
+        headers.insert(MESSAGE_ID_HEADER, message_id);
+        self.jetstream
+            .publish_with_headers(subject.to_string(), headers, payload.into())
+            .await
+            .map_err(|e| self.publish_error(e))
+    }
+
+    fn publish_error(&self, e: PublishError) -> IndexerError {
+        if e.kind() == PublishErrorKind::StreamNotFound {
+            IndexerError::CdcConnectionError(format!(
+                "No stream captures {}, {} and {}: {e}",
+                self.checkpoints_subject, self.watermark_subject, self.dead_letters_subject
+            ))
+        } else {
+            IndexerError::CdcPublishError(e.to_string())
+        }
+    }
+}
+
+impl From<&CheckpointDataToCommit> for CheckpointMessage {
+    fn from(data: &CheckpointDataToCommit) -> Self {
+        let transactions = data
+            .transactions
+            .iter()
+            .map(|tx| TransactionMessage {
+                tx_sequence_number: tx.tx_sequence_number,
+                digest: tx.tx_digest,
+                sender: tx.sender_signed_data.transaction_data().sender(),
+                is_system_tx: matches!(tx.transaction_kind, TransactionKind::SystemTransaction),
+                success: tx.effects.status().is_ok(),
+                gas_used: tx.effects.gas_cost_summary().net_gas_usage(),
+            })
+            .collect();
+
+        let events = data
+            .events
+            .iter()
+            .map(|event| EventMessage {
+                tx_sequence_number: event.tx_sequence_number,
+                event_sequence_number: event.event_sequence_number,
+                transaction_digest: event.transaction_digest,
+                event_type: event.event_type.clone(),
+                bcs: Base64::encode(&event.bcs),
+            })
+            .collect();
+
+        let changes = &data.object_history_changes;
+        let object_changes = changes
+            .changed_objects
+            .iter()
+            .map(|object| ObjectChangeMessage {
+                object_id: object.object_id,
+                object_version: object.object_version,
+                object_type: object
+                    .object
+                    .struct_tag()
+                    .map(|tag| tag.to_canonical_string(/* with_prefix */ true)),
+                owner: object.owner_id,
+                deleted: false,
+            })
+            .chain(
+                changes
+                    .deleted_objects
+                    .iter()
+                    .map(|object| ObjectChangeMessage {
+                        object_id: object.object_id,
+                        object_version: object.object_version,
+                        object_type: None,
+                        owner: None,
+                        deleted: true,
+                    }),
+            )
+            .collect();
+
+        let checkpoint = &data.checkpoint;
+        Self {
+            sequence_number: checkpoint.sequence_number,
+            digest: checkpoint.checkpoint_digest,
+            epoch: checkpoint.epoch,
+            timestamp_ms: checkpoint.timestamp_ms,
+            transactions,
+            events,
+            object_changes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint_message(sequence_number: u64, events: usize) -> CheckpointMessage {
+        CheckpointMessage {
+            sequence_number,
+            digest: CheckpointDigest::random(),
+            epoch: 0,
+            timestamp_ms: 0,
+            transactions: vec![],
+            events: (0..events as u64)
+                .map(|event_sequence_number| EventMessage {
+                    tx_sequence_number: 0,
+                    event_sequence_number,
+                    transaction_digest: TransactionDigest::random(),
+                    event_type: "0x2::coin::CoinEvent".to_string(),
+                    bcs: Base64::encode([0u8; 64]),
+                })
+                .collect(),
+            object_changes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_encode_dead_letters_oversized_checkpoints() {
+        let checkpoints = vec![
+            checkpoint_message(1, 0),
+            checkpoint_message(2, 100),
+            checkpoint_message(3, 0),
+        ];
+        let max_payload = serde_json::to_vec(&checkpoints[0]).unwrap().len();
+
+        let batch = EncodedBatch::encode(&checkpoints, max_payload);
+
+        let published: Vec<_> = batch.checkpoints.iter().map(|(seq, _)| *seq).collect();
+        assert_eq!(published, vec![1, 3]);
+        assert_eq!(batch.dead_letters.len(), 1);
+        assert_eq!(batch.dead_letters[0].checkpoint_sequence_number, 2);
+        assert!(batch.dead_letters[0].reason.contains(&format!(
+            "exceeds the server's maximum of {max_payload} bytes"
+        )));
+    }
+
+    #[test]
+    fn test_watermark_only_moves_forward() {
+        let watermark = PublishedWatermark::default();
+        assert!(watermark.advances(0));
+
+        watermark.set(3);
+        // Checkpoints of a batch that failed to commit are published again one by one.
+        assert!(!watermark.advances(1));
+        assert!(!watermark.advances(3));
+        assert!(watermark.advances(4));
+
+        // Clones share the watermark.
+        watermark.clone().set(4);
+        assert!(!watermark.advances(4));
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::handlers::committer::start_tx_checkpoint_commit_task;
+use crate::handlers::committer::{start_tx_checkpoint_commit_task, CommitMode};
+use crate::handlers::indexing_filter::IndexingFilter;
 use crate::handlers::tx_processor::IndexingPackageBuffer;
 use crate::models::display::StoredDisplay;
 use async_trait::async_trait;
@@ -22,12 +23,13 @@ use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointConte
 use sui_types::object::Object;
 
 use tokio::sync::watch;
+use tokio::task::JoinHandle;
 
 use std::collections::hash_map::Entry;
 use std::collections::HashSet;
 use sui_json_rpc_types::SuiMoveValue;
 use sui_types::base_types::SequenceNumber;
-use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::effects::{InputSharedObject, TransactionEffects, TransactionEffectsAPI};
 use sui_types::event::SystemEpochInfoEvent;
 use sui_types::object::Owner;
 use sui_types::transaction::TransactionDataAPI;
@@ -42,12 +44,12 @@ use crate::errors::IndexerError;
 use crate::framework::interface::Handler;
 use crate::metrics::IndexerMetrics;
 
-use crate::db::PgConnectionPool;
-use crate::store::module_resolver::{IndexerStorePackageModuleResolver, InterimPackageResolver};
-use crate::store::{IndexerStore, PgIndexerStore};
+use crate::store::module_resolver::InterimPackageResolver;
+use crate::store::IndexerStore;
 use crate::types::{
     IndexedCheckpoint, IndexedDeletedObject, IndexedEpochInfo, IndexedEvent, IndexedObject,
-    IndexedPackage, IndexedTransaction, IndexerResult, TransactionKind, TxIndex,
+    IndexedObjectDisplay, IndexedPackage, IndexedTransaction, IndexerResult, TransactionKind,
+    TxIndex,
 };
 
 use super::tx_processor::EpochEndIndexingObjectStore;
@@ -58,10 +60,17 @@ use super::TransactionObjectChangesToCommit;
 
 const CHECKPOINT_QUEUE_SIZE: usize = 100;
 
+/// Creates the handler that indexes checkpoints, and starts the task that commits them. The task
+/// finishes once the handler is dropped and all the checkpoints it indexed are committed, as
+/// described by the commit `mode`. Only the data relevant to the `filter` is indexed. If `resolve_objects` is set, the Displays of live
+/// objects are rendered as they are indexed.
 pub async fn new_handlers<S>(
     state: S,
     metrics: IndexerMetrics,
-) -> Result<CheckpointHandler<S>, IndexerError>
+    mode: CommitMode,
+    filter: IndexingFilter,
+    resolve_objects: bool,
+) -> Result<(CheckpointHandler<S>, JoinHandle<()>), IndexerError>
 where
     S: IndexerStore + Clone + Sync + Send + 'static,
 {
@@ -81,9 +90,10 @@ where
     let state_clone = state.clone();
     let metrics_clone = metrics.clone();
     let (tx, package_tx) = watch::channel(None);
-    spawn_monitored_task!(start_tx_checkpoint_commit_task(
+    let commit_task = spawn_monitored_task!(start_tx_checkpoint_commit_task(
         state_clone,
         metrics_clone,
+        mode,
         indexed_checkpoint_receiver,
         tx,
     ));
@@ -93,9 +103,11 @@ where
         metrics,
         indexed_checkpoint_sender,
         package_buffer: IndexingPackageBuffer::start(package_tx),
+        filter: Arc::new(filter),
+        resolve_objects,
     };
 
-    Ok(checkpoint_handler)
+    Ok((checkpoint_handler, commit_task))
 }
 
 pub struct CheckpointHandler<S> {
@@ -105,6 +117,8 @@ pub struct CheckpointHandler<S> {
     // buffers for packages that are being indexed but not committed to DB,
     // they will be periodically GCed to avoid OOM.
     package_buffer: Arc<Mutex<IndexingPackageBuffer>>,
+    filter: Arc<IndexingFilter>,
+    resolve_objects: bool,
 }
 
 #[async_trait]
@@ -141,10 +155,8 @@ where
         let packages = Self::index_packages(checkpoints, &self.metrics);
         let package_objects = Self::get_package_objects(checkpoints);
 
-        let pg_blocking_cp = self.pg_blocking_cp()?;
-        let module_package_db_resolver = IndexerStorePackageModuleResolver::new(pg_blocking_cp);
         let in_mem_package_resolver = InterimPackageResolver::new(
-            module_package_db_resolver,
+            self.state.package_resolver(),
             self.package_buffer.clone(),
             &package_objects,
             self.metrics.clone(),
@@ -158,6 +170,13 @@ where
                 .or_default()
                 .push(package);
         }
+        let displays = if self.resolve_objects {
+            Some(Arc::new(
+                Self::get_displays(&self.state, checkpoints).await?,
+            ))
+        } else {
+            None
+        };
         let mut tasks = vec![];
         let state_clone = Arc::new(self.state.clone());
         let metrics_clone = Arc::new(self.metrics.clone());
@@ -171,6 +190,8 @@ where
                 metrics_clone.clone(),
                 packages,
                 package_resolver.clone(),
+                self.filter.clone(),
+                displays.clone(),
             )));
         }
         let checkpoint_data_to_commit = futures::future::join_all(tasks)
@@ -303,16 +324,23 @@ where
         metrics: Arc<IndexerMetrics>,
         packages: Vec<IndexedPackage>,
         package_resolver: Arc<Resolver<impl PackageStore>>,
+        filter: Arc<IndexingFilter>,
+        displays: Option<Arc<BTreeMap<String, StoredDisplay>>>,
     ) -> Result<CheckpointDataToCommit, IndexerError> {
         let checkpoint_seq = data.checkpoint_summary.sequence_number;
         info!(checkpoint_seq, "Indexing checkpoint data blob");
 
         // Index epoch
-        let epoch = Self::index_epoch(state, &data).await?;
+        let epoch = Self::index_epoch(state.clone(), &data).await?;
 
         // Index Objects
-        let object_changes: TransactionObjectChangesToCommit =
-            Self::index_objects(data.clone(), &metrics, package_resolver.clone()).await?;
+        let object_changes: TransactionObjectChangesToCommit = Self::index_objects(
+            data.clone(),
+            &metrics,
+            package_resolver.clone(),
+            displays.as_deref(),
+        )
+        .await?;
         let object_history_changes: TransactionObjectChangesToCommit =
             Self::index_objects_history(data.clone(), package_resolver.clone()).await?;
 
@@ -345,7 +373,7 @@ where
             )
         };
         info!(checkpoint_seq, "Indexed one checkpoint.");
-        Ok(CheckpointDataToCommit {
+        let mut checkpoint_data = CheckpointDataToCommit {
             checkpoint,
             transactions: db_transactions,
             events: db_events,
@@ -355,7 +383,9 @@ where
             object_history_changes,
             packages,
             epoch,
-        })
+        };
+        filter.apply(&mut checkpoint_data, &metrics);
+        Ok(checkpoint_data)
     }
 
     async fn index_transactions(
@@ -480,6 +510,19 @@ where
                 .map(|(object_ref, _owner, _write_kind)| object_ref.0)
                 .collect::<Vec<_>>();
 
+            // Shared Objects
+            let shared_objects = fx
+                .input_shared_objects()
+                .into_iter()
+                .map(|shared| {
+                    let mutable = matches!(
+                        shared,
+                        InputSharedObject::Mutate(_) | InputSharedObject::MutateDeleted(_, _)
+                    );
+                    (shared.id_and_version().0, mutable)
+                })
+                .collect();
+
             // Payers
             let payers = vec![tx.gas_owner()];
 
@@ -514,15 +557,46 @@ where
                 payers,
                 recipients,
                 move_calls,
+                gas_used: fx.gas_cost_summary().net_gas_usage(),
+                shared_objects,
+                success: fx.status().is_ok(),
             });
         }
         Ok((db_transactions, db_events, db_indices, db_displays))
     }
 
+    /// The latest Displays of the types of the objects changed by the checkpoints, read in a single
+    /// query for the whole batch, including the Displays updated by the checkpoints themselves.
+    /// Displays updated by earlier batches that are not committed yet are missed, and the objects
+    /// rendered without them are rendered again by the objects resolution backfill.
+    async fn get_displays(
+        state: &S,
+        checkpoints: &[CheckpointData],
+    ) -> IndexerResult<BTreeMap<String, StoredDisplay>> {
+        let object_types = checkpoints
+            .iter()
+            .flat_map(|data| data.output_objects())
+            .filter_map(|o| o.type_())
+            .map(|t| t.to_canonical_string(/* with_prefix */ true))
+            .unique()
+            .collect();
+        let mut displays = state.get_displays(object_types).await?;
+        displays.extend(
+            checkpoints
+                .iter()
+                .flat_map(|data| &data.transactions)
+                .flat_map(|tx| tx.events.iter().flat_map(|events| &events.data))
+                .flat_map(StoredDisplay::try_from_event)
+                .map(|display| (display.object_type.clone(), display)),
+        );
+        Ok(displays)
+    }
+
     async fn index_objects(
         data: CheckpointData,
         metrics: &IndexerMetrics,
         package_resolver: Arc<Resolver<impl PackageStore>>,
+        displays: Option<&BTreeMap<String, StoredDisplay>>,
     ) -> Result<TransactionObjectChangesToCommit, IndexerError> {
         let _timer = metrics.indexing_objects_latency.start_timer();
         let checkpoint_seq = data.checkpoint_summary.sequence_number;
@@ -583,9 +657,14 @@ where
         let changed_objects = live_objects
             .into_iter()
             .map(|o| {
+                let display = displays
+                    .and_then(|displays| try_render_display(&o, &move_struct_layout_map, displays));
                 let df_info =
                     try_create_dynamic_field_info(&o, &move_struct_layout_map, &latest_objects);
-                df_info.map(|info| IndexedObject::from_object(checkpoint_seq, o, info))
+                df_info.map(|info| IndexedObject {
+                    display,
+                    ..IndexedObject::from_object(checkpoint_seq, o, info)
+                })
             })
             .collect::<Result<Vec<_>, _>>()?;
         Ok(TransactionObjectChangesToCommit {
@@ -715,16 +794,6 @@ where
             })
             .collect()
     }
-
-    fn pg_blocking_cp(&self) -> Result<PgConnectionPool, IndexerError> {
-        let state_as_any = self.state.as_any();
-        if let Some(pg_state) = state_as_any.downcast_ref::<PgIndexerStore>() {
-            return Ok(pg_state.blocking_cp());
-        }
-        Err(IndexerError::UncategorizedError(anyhow::anyhow!(
-            "Failed to downcast state to PgIndexerStore"
-        )))
-    }
 }
 
 async fn get_move_struct_layout_map(
@@ -817,6 +886,26 @@ pub fn get_latest_objects(
     (latest_objects, discarded_versions)
 }
 
+/// Renders the Display of `o`, if its type has one. Failing to render it is not fatal, as objects
+/// without a rendered Display are rendered when they are read, and by the objects resolution
+/// backfill.
+fn try_render_display(
+    o: &Object,
+    struct_tag_to_move_struct_layout: &HashMap<StructTag, MoveStructLayout>,
+    displays: &BTreeMap<String, StoredDisplay>,
+) -> Option<IndexedObjectDisplay> {
+    let move_object = o.data.try_as_move()?;
+    let object_type = move_object
+        .type_()
+        .to_canonical_string(/* with_prefix */ true);
+    let display = displays.get(&object_type)?;
+    let struct_tag: StructTag = move_object.type_().clone().into();
+    let layout = struct_tag_to_move_struct_layout.get(&struct_tag)?;
+    IndexedObjectDisplay::render(o, layout, display)
+        .tap_err(|e| warn!("Failed to render the Display of object {}: {e}", o.id()))
+        .ok()
+}
+
 fn try_create_dynamic_field_info(
     o: &Object,
     struct_tag_to_move_struct_layout: &HashMap<StructTag, MoveStructLayout>,
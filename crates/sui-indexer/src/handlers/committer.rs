@@ -2,26 +2,97 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use tokio::sync::watch;
 use tracing::instrument;
 
 use tap::tap::TapFallible;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
+use crate::errors::{IndexerError, IndexerErrorKind};
 use crate::metrics::IndexerMetrics;
+use crate::models::quarantined_checkpoints::StoredQuarantinedCheckpoint;
 use crate::store::IndexerStore;
 use crate::types::IndexerResult;
 
+use super::cdc_publisher::{CdcPublisher, CheckpointMessage};
 use super::{CheckpointDataToCommit, EpochToCommit};
 
 const CHECKPOINT_COMMIT_BATCH_SIZE: usize = 100;
+const CHECKPOINT_COMMIT_MAX_ATTEMPTS: usize = 3;
+const CHECKPOINT_COMMIT_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Controls what happens to a checkpoint that keeps failing to commit.
+#[derive(Clone, Copy, Debug)]
+struct QuarantineConfig {
+    /// Whether a checkpoint that cannot be committed is quarantined, instead of halting the
+    /// indexer. Disabled unless `QUARANTINE_POISON_CHECKPOINTS` is set.
+    enabled: bool,
+    /// Number of times a single checkpoint is committed before quarantining it.
+    max_attempts: usize,
+    /// Delay before the first retry of a checkpoint, growing linearly with each attempt.
+    retry_interval: Duration,
+}
+
+impl QuarantineConfig {
+    fn from_env() -> Self {
+        let max_attempts = std::env::var("CHECKPOINT_COMMIT_MAX_ATTEMPTS")
+            .unwrap_or(CHECKPOINT_COMMIT_MAX_ATTEMPTS.to_string())
+            .parse::<usize>()
+            .unwrap()
+            .max(1);
+        let enabled = std::env::var("QUARANTINE_POISON_CHECKPOINTS")
+            .map(|v| v.parse::<bool>().unwrap())
+            .unwrap_or(false);
+        Self {
+            enabled,
+            max_attempts,
+            retry_interval: CHECKPOINT_COMMIT_RETRY_INTERVAL,
+        }
+    }
+}
+
+/// What the commit task is committing checkpoints for.
+#[derive(Clone)]
+pub enum CommitMode {
+    /// Checkpoints are indexed as the chain progresses, and published to the change data capture
+    /// stream, if any.
+    Live { cdc: Option<CdcPublisher> },
+    /// Checkpoints that were already indexed are committed again, to repair their data. They are
+    /// not published, and are reported by their own metrics, so that neither the watermark of
+    /// the stream nor the metrics of the live indexer go backwards.
+    Resync,
+}
+
+impl CommitMode {
+    fn cdc(&self) -> Option<&CdcPublisher> {
+        match self {
+            Self::Live { cdc } => cdc.as_ref(),
+            Self::Resync => None,
+        }
+    }
+
+    /// Reports the checkpoint as the latest one committed.
+    fn report_watermark(
+        &self,
+        metrics: &IndexerMetrics,
+        sequence_number: CheckpointSequenceNumber,
+    ) {
+        let gauge = match self {
+            Self::Live { .. } => &metrics.latest_tx_checkpoint_sequence_number,
+            Self::Resync => &metrics.latest_resynced_checkpoint_sequence_number,
+        };
+        gauge.set(sequence_number as i64);
+    }
+}
 
 pub async fn start_tx_checkpoint_commit_task<S>(
     state: S,
     metrics: IndexerMetrics,
+    mode: CommitMode,
     tx_indexing_receiver: mysten_metrics::metered_channel::Receiver<CheckpointDataToCommit>,
     commit_notifier: watch::Sender<Option<CheckpointSequenceNumber>>,
 ) where
@@ -35,6 +106,8 @@ pub async fn start_tx_checkpoint_commit_task<S>(
         .parse::<usize>()
         .unwrap();
     info!("Using checkpoint commit batch size {checkpoint_commit_batch_size}");
+    let quarantine_config = QuarantineConfig::from_env();
+    info!("Using poison checkpoint handling {:?}", quarantine_config);
 
     let mut stream = mysten_metrics::metered_channel::ReceiverStream::new(tx_indexing_receiver)
         .ready_chunks(checkpoint_commit_batch_size);
@@ -49,30 +122,276 @@ pub async fn start_tx_checkpoint_commit_task<S>(
             let epoch = indexed_checkpoint.epoch.clone();
             indexed_checkpoint_batch_per_epoch.push(indexed_checkpoint);
             if epoch.is_some() {
-                commit_checkpoints(
+                commit_checkpoints_or_quarantine(
                     &state,
                     indexed_checkpoint_batch_per_epoch,
                     epoch,
                     &metrics,
+                    &mode,
                     &commit_notifier,
+                    quarantine_config,
                 )
                 .await;
                 indexed_checkpoint_batch_per_epoch = vec![];
             }
         }
         if !indexed_checkpoint_batch_per_epoch.is_empty() {
-            commit_checkpoints(
+            commit_checkpoints_or_quarantine(
                 &state,
                 indexed_checkpoint_batch_per_epoch,
                 None,
                 &metrics,
+                &mode,
                 &commit_notifier,
+                quarantine_config,
             )
             .await;
         }
     }
 }
 
+/// Commits a batch of checkpoints. Transient DB errors are retried by the store, so by default
+/// a batch that still fails to commit halts the indexer.
+///
+/// If quarantining is enabled, a batch that fails is committed again checkpoint by checkpoint to
+/// isolate the ones that cannot be committed. A checkpoint that still fails after
+/// `max_attempts`, or sooner if it fails with an error that is not retryable, is quarantined: it
+/// is recorded in the `quarantined_checkpoints` table with enough context to investigate and
+/// re-index it, and the indexer moves on to the next checkpoint. Checkpoints that end an epoch
+/// are never skipped, since the epoch and partition changes they carry are required by all
+/// subsequent checkpoints, and neither are checkpoints that change objects, since the live
+/// object set would then diverge from the chain for as long as they are not re-indexed.
+async fn commit_checkpoints_or_quarantine<S>(
+    state: &S,
+    indexed_checkpoint_batch: Vec<CheckpointDataToCommit>,
+    epoch: Option<EpochToCommit>,
+    metrics: &IndexerMetrics,
+    mode: &CommitMode,
+    commit_notifier: &watch::Sender<Option<CheckpointSequenceNumber>>,
+    config: QuarantineConfig,
+) where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let batch_size = indexed_checkpoint_batch.len();
+    if !config.enabled {
+        // Nothing is committed again, so the batch is handed over to the store as is.
+        let first = indexed_checkpoint_batch[0].checkpoint.sequence_number;
+        let last = indexed_checkpoint_batch[batch_size - 1]
+            .checkpoint
+            .sequence_number;
+        if let Err(e) = commit_checkpoints(
+            state,
+            indexed_checkpoint_batch,
+            epoch,
+            metrics,
+            mode,
+            commit_notifier,
+        )
+        .await
+        {
+            record_commit_error(metrics, &e);
+            panic!(
+                "Persisting checkpoints {}-{} into DB should not fail: {}",
+                first, last, e
+            );
+        }
+        return;
+    }
+
+    // The batch is only kept around to commit its checkpoints one by one if it fails, and a
+    // single checkpoint is committed one by one right away.
+    if batch_size > 1 {
+        match commit_checkpoints(
+            state,
+            indexed_checkpoint_batch.clone(),
+            epoch.clone(),
+            metrics,
+            mode,
+            commit_notifier,
+        )
+        .await
+        {
+            Ok(()) => return,
+            Err(e) => {
+                record_commit_error(metrics, &e);
+                warn!(
+                    "Failed to commit batch of {} checkpoints with error: {}, committing them individually",
+                    batch_size, e
+                );
+            }
+        }
+    }
+
+    // Epoch data, if any, belongs to the last checkpoint of the batch.
+    let mut epoch = epoch;
+    for (i, indexed_checkpoint) in indexed_checkpoint_batch.into_iter().enumerate() {
+        let checkpoint_epoch = if i + 1 == batch_size {
+            epoch.take()
+        } else {
+            None
+        };
+        let end_of_epoch = checkpoint_epoch.is_some();
+        let changes_objects = !indexed_checkpoint.object_changes.changed_objects.is_empty()
+            || !indexed_checkpoint.object_changes.deleted_objects.is_empty();
+        let sequence_number = indexed_checkpoint.checkpoint.sequence_number;
+        // Described up front, since the checkpoint's data is handed over to the store.
+        let quarantined = StoredQuarantinedCheckpoint::new(&indexed_checkpoint);
+        let message = mode
+            .cdc()
+            .map(|_| CheckpointMessage::from(&indexed_checkpoint));
+        let Err((error, attempts)) = commit_checkpoint_with_retries(
+            state,
+            indexed_checkpoint,
+            checkpoint_epoch,
+            metrics,
+            mode,
+            commit_notifier,
+            config,
+        )
+        .await
+        else {
+            continue;
+        };
+
+        // A misconfigured indexer would fail to commit every checkpoint, so it halts instead of
+        // quarantining them all.
+        if end_of_epoch || changes_objects || error.kind() == IndexerErrorKind::Config {
+            panic!(
+                "Persisting checkpoint {} into DB should not fail: {}",
+                sequence_number, error
+            );
+        }
+        // The checkpoint is still published, as consumers of the stream would otherwise miss it
+        // once the watermark moves past it.
+        if let (Some(cdc), Some(message)) = (mode.cdc(), message) {
+            publish_with_retries(cdc, &[message])
+                .await
+                .expect("Publishing quarantined checkpoint should not fail.");
+        }
+        let quarantined_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as u64;
+        state
+            .persist_quarantined_checkpoint(quarantined.failed(
+                error.to_string(),
+                attempts,
+                quarantined_at_ms,
+            ))
+            .await
+            .tap_err(|e| {
+                error!(
+                    "Failed to quarantine checkpoint {} with error: {}",
+                    sequence_number, e
+                );
+            })
+            .expect("Persisting quarantined checkpoint into DB should not fail.");
+        error!(
+            "Quarantined checkpoint {} after {} failed attempts to commit it: {}",
+            sequence_number, attempts, error
+        );
+        metrics.total_quarantined_checkpoints.inc();
+        commit_notifier
+            .send(Some(sequence_number))
+            .expect("Commit watcher should not be closed");
+        mode.report_watermark(metrics, sequence_number);
+    }
+}
+
+/// Commits a single checkpoint, committing it again while it fails with a retryable error, up to
+/// `max_attempts` times. Returns the last error and the number of attempts made if the checkpoint
+/// could not be committed.
+async fn commit_checkpoint_with_retries<S>(
+    state: &S,
+    indexed_checkpoint: CheckpointDataToCommit,
+    epoch: Option<EpochToCommit>,
+    metrics: &IndexerMetrics,
+    mode: &CommitMode,
+    commit_notifier: &watch::Sender<Option<CheckpointSequenceNumber>>,
+    config: QuarantineConfig,
+) -> Result<(), (IndexerError, usize)>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let sequence_number = indexed_checkpoint.checkpoint.sequence_number;
+    let mut data = Some((indexed_checkpoint, epoch));
+    let mut attempt = 1;
+    loop {
+        // The data is handed over to the store, so it is only copied if another attempt may
+        // follow.
+        let (indexed_checkpoint, epoch) = if attempt < config.max_attempts {
+            data.clone()
+        } else {
+            data.take()
+        }
+        .expect("Checkpoint data is only taken by the last attempt");
+        let Err(e) = commit_checkpoints(
+            state,
+            vec![indexed_checkpoint],
+            epoch,
+            metrics,
+            mode,
+            commit_notifier,
+        )
+        .await
+        else {
+            return Ok(());
+        };
+
+        let kind = record_commit_error(metrics, &e);
+        warn!(
+            "Attempt {}/{} to commit checkpoint {} failed with {} error: {}",
+            attempt, config.max_attempts, sequence_number, kind, e
+        );
+        // Only transient errors can be fixed by committing the checkpoint again.
+        if kind != IndexerErrorKind::Retryable || attempt == config.max_attempts {
+            return Err((e, attempt));
+        }
+        tokio::time::sleep(config.retry_interval * attempt as u32).await;
+        attempt += 1;
+    }
+}
+
+fn record_commit_error(metrics: &IndexerMetrics, e: &IndexerError) -> IndexerErrorKind {
+    let kind = e.kind();
+    metrics
+        .total_checkpoint_commit_errors
+        .with_label_values(&[kind.as_str()])
+        .inc();
+    kind
+}
+
+/// Publishes checkpoints to the change data capture stream. Publishing is retried until it
+/// succeeds while the broker is unavailable, instead of failing the commit, so that an
+/// unavailable broker holds the indexer back rather than having it quarantine checkpoints that
+/// would then never be published. Other errors are not retried, as publishing again would not
+/// fix them.
+async fn publish_with_retries(
+    cdc: &CdcPublisher,
+    messages: &[CheckpointMessage],
+) -> IndexerResult<()> {
+    let backoff = backoff::ExponentialBackoff {
+        max_elapsed_time: None,
+        ..Default::default()
+    };
+    backoff::future::retry_notify(
+        backoff,
+        || async {
+            cdc.publish(messages).await.map_err(|e| match e.kind() {
+                IndexerErrorKind::Retryable => backoff::Error::transient(e),
+                _ => backoff::Error::permanent(e),
+            })
+        },
+        |e: IndexerError, delay: Duration| {
+            warn!(
+                "Failed to publish checkpoints with error: {}, retrying in {:?}",
+                e, delay
+            );
+        },
+    )
+    .await
+}
+
 // Unwrap: Caller needs to make sure indexed_checkpoint_batch is not empty
 #[instrument(skip_all, fields(
     first = indexed_checkpoint_batch.first().as_ref().unwrap().checkpoint.sequence_number,
@@ -83,10 +402,19 @@ async fn commit_checkpoints<S>(
     indexed_checkpoint_batch: Vec<CheckpointDataToCommit>,
     epoch: Option<EpochToCommit>,
     metrics: &IndexerMetrics,
+    mode: &CommitMode,
     commit_notifier: &watch::Sender<Option<CheckpointSequenceNumber>>,
-) where
+) -> IndexerResult<()>
+where
     S: IndexerStore + Clone + Sync + Send + 'static,
 {
+    let cdc = mode.cdc();
+    let cdc_messages = cdc.map(|_| {
+        indexed_checkpoint_batch
+            .iter()
+            .map(CheckpointMessage::from)
+            .collect::<Vec<_>>()
+    });
     let mut checkpoint_batch = vec![];
     let mut tx_batch = vec![];
     let mut events_batch = vec![];
@@ -152,20 +480,23 @@ async fn commit_checkpoints<S>(
                 }
                 res
             })
-            .collect::<IndexerResult<Vec<_>>>()
-            .expect("Persisting data into DB should not fail.");
+            .collect::<IndexerResult<Vec<_>>>()?;
     }
 
     // handle partitioning on epoch boundary
     if let Some(epoch_data) = epoch {
-        state
-            .advance_epoch(epoch_data)
-            .await
-            .tap_err(|e| {
-                error!("Failed to advance epoch with error: {}", e.to_string());
-            })
-            .expect("Advancing epochs in DB should not fail.");
-        metrics.total_epoch_committed.inc();
+        state.advance_epoch(epoch_data).await.tap_err(|e| {
+            error!("Failed to advance epoch with error: {}", e.to_string());
+        })?;
+        if let CommitMode::Live { .. } = mode {
+            metrics.total_epoch_committed.inc();
+        }
+    }
+
+    // Checkpoints are published before they are recorded as committed, so that a crash in between
+    // publishes them again on restart.
+    if let (Some(cdc), Some(messages)) = (cdc, cdc_messages) {
+        publish_with_retries(cdc, &messages).await?;
     }
 
     state
@@ -176,22 +507,27 @@ async fn commit_checkpoints<S>(
                 "Failed to persist checkpoint data with error: {}",
                 e.to_string()
             );
-        })
-        .expect("Persisting data into DB should not fail.");
+        })?;
     let elapsed = guard.stop_and_record();
 
     commit_notifier
         .send(Some(last_checkpoint_seq))
         .expect("Commit watcher should not be closed");
 
-    metrics
-        .latest_tx_checkpoint_sequence_number
-        .set(last_checkpoint_seq as i64);
-
-    metrics
-        .total_tx_checkpoint_committed
-        .inc_by(checkpoint_num as u64);
-    metrics.total_transaction_committed.inc_by(tx_count as u64);
+    mode.report_watermark(metrics, last_checkpoint_seq);
+    match mode {
+        CommitMode::Live { .. } => {
+            metrics
+                .total_tx_checkpoint_committed
+                .inc_by(checkpoint_num as u64);
+            metrics.total_transaction_committed.inc_by(tx_count as u64);
+        }
+        CommitMode::Resync => {
+            metrics
+                .total_tx_checkpoint_resynced
+                .inc_by(checkpoint_num as u64);
+        }
+    }
     info!(
         elapsed,
         "Checkpoint {}-{} committed with {} transactions.",
@@ -207,4 +543,535 @@ async fn commit_checkpoints<S>(
     metrics
         .thousand_transaction_avg_db_commit_latency
         .observe(elapsed * 1000.0 / tx_count as f64);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use move_binary_format::CompiledModule;
+    use move_bytecode_utils::module_cache::GetModule;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::language_storage::ModuleId;
+    use prometheus::Registry;
+    use sui_package_resolver::{error::Error as PackageResolverError, Package, PackageStore};
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+    use sui_types::object::{Object, ObjectRead};
+
+    use super::*;
+    use crate::handlers::pruner::PrunableTables;
+    use crate::handlers::TransactionObjectChangesToCommit;
+    use crate::models::display::StoredDisplay;
+    use crate::models::epoch::StoredEpochInfo;
+    use crate::models::epoch_consistency_reports::StoredEpochConsistencyReport;
+    use crate::models::objects::StoredObject;
+    use crate::models::tx_address_buckets::StoredHotAddress;
+    use crate::types::{
+        IndexedCheckpoint, IndexedEpochInfo, IndexedEvent, IndexedObject, IndexedPackage,
+        IndexedTransaction, TxIndex,
+    };
+
+    struct NoModules;
+
+    impl GetModule for NoModules {
+        type Error = anyhow::Error;
+        type Item = Arc<CompiledModule>;
+
+        fn get_module_by_id(&self, _id: &ModuleId) -> Result<Option<Self::Item>, Self::Error> {
+            Ok(None)
+        }
+    }
+
+    struct NoPackages;
+
+    #[async_trait]
+    impl PackageStore for NoPackages {
+        async fn version(
+            &self,
+            id: AccountAddress,
+        ) -> Result<SequenceNumber, PackageResolverError> {
+            Err(PackageResolverError::PackageNotFound(id))
+        }
+
+        async fn fetch(&self, id: AccountAddress) -> Result<Arc<Package>, PackageResolverError> {
+            Err(PackageResolverError::PackageNotFound(id))
+        }
+    }
+
+    /// A store that records the checkpoints committed and quarantined, and the live version of
+    /// each object, and fails to commit checkpoints with the errors queued for them, one error
+    /// per attempt.
+    #[derive(Clone, Default)]
+    struct MockStore {
+        failures: Arc<Mutex<BTreeMap<u64, VecDeque<IndexerError>>>>,
+        committed: Arc<Mutex<Vec<u64>>>,
+        quarantined: Arc<Mutex<Vec<StoredQuarantinedCheckpoint>>>,
+        objects: Arc<Mutex<BTreeMap<ObjectID, u64>>>,
+    }
+
+    impl MockStore {
+        fn fail(&self, sequence_number: u64, errors: impl IntoIterator<Item = IndexerError>) {
+            self.failures
+                .lock()
+                .unwrap()
+                .insert(sequence_number, errors.into_iter().collect());
+        }
+
+        fn committed(&self) -> Vec<u64> {
+            self.committed.lock().unwrap().clone()
+        }
+
+        fn object_version(&self, object_id: ObjectID) -> Option<u64> {
+            self.objects.lock().unwrap().get(&object_id).copied()
+        }
+
+        fn quarantined(&self) -> Vec<(i64, i64)> {
+            self.quarantined
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|q| (q.checkpoint_sequence_number, q.attempts))
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl IndexerStore for MockStore {
+        type ModuleCache = NoModules;
+        type PackageResolver = NoPackages;
+
+        async fn get_latest_tx_checkpoint_sequence_number(
+            &self,
+        ) -> Result<Option<u64>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_latest_object_snapshot_checkpoint_sequence_number(
+            &self,
+        ) -> Result<Option<u64>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_latest_move_call_metrics_day(&self) -> Result<Option<u64>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_latest_shared_object_metrics_day(&self) -> Result<Option<u64>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_next_rollups_checkpoint(&self) -> Result<Option<u64>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_checkpoint_timestamp_range_ms(
+            &self,
+        ) -> Result<Option<(u64, u64)>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_object_read(
+            &self,
+            _object_id: ObjectID,
+            _version: Option<SequenceNumber>,
+        ) -> Result<ObjectRead, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn persist_objects(
+            &self,
+            object_changes: Vec<TransactionObjectChangesToCommit>,
+        ) -> Result<(), IndexerError> {
+            // Like the objects table, only newer versions replace a stored object.
+            let mut objects = self.objects.lock().unwrap();
+            for changes in object_changes {
+                for object in changes.changed_objects {
+                    let version = objects.entry(object.object_id).or_default();
+                    *version = (*version).max(object.object_version);
+                }
+                for object in changes.deleted_objects {
+                    objects.remove(&object.object_id);
+                }
+            }
+            Ok(())
+        }
+
+        async fn persist_object_history(
+            &self,
+            _object_changes: Vec<TransactionObjectChangesToCommit>,
+        ) -> Result<(), IndexerError> {
+            Ok(())
+        }
+
+        async fn persist_object_snapshot(
+            &self,
+            _start_cp: u64,
+            _end_cp: u64,
+        ) -> Result<(), IndexerError> {
+            unimplemented!()
+        }
+
+        async fn persist_move_call_metrics(&self, _day: u64) -> Result<(), IndexerError> {
+            unimplemented!()
+        }
+
+        async fn persist_shared_object_metrics(&self, _day: u64) -> Result<(), IndexerError> {
+            unimplemented!()
+        }
+
+        async fn persist_rollups(&self, _start_cp: u64, _end_cp: u64) -> Result<(), IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_latest_epoch(&self) -> Result<Option<u64>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_epoch_info(
+            &self,
+            _epoch: u64,
+        ) -> Result<Option<StoredEpochInfo>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn prune_epochs(
+            &self,
+            _tables: PrunableTables,
+            _before_epoch: u64,
+            _archive: bool,
+        ) -> Result<Vec<(&'static str, u64)>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_hot_addresses_to_backfill(
+            &self,
+        ) -> Result<Vec<StoredHotAddress>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn backfill_tx_address_bucket(
+            &self,
+            _address: Vec<u8>,
+            _below_bucket: Option<u64>,
+        ) -> Result<Option<u64>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_displays(
+            &self,
+            _object_types: Vec<String>,
+        ) -> Result<BTreeMap<String, StoredDisplay>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_objects_to_resolve(
+            &self,
+            _after_object_id: Option<Vec<u8>>,
+            _limit: usize,
+        ) -> Result<Vec<StoredObject>, IndexerError> {
+            unimplemented!()
+        }
+
+        async fn persist_resolved_objects(
+            &self,
+            _objects: Vec<StoredObject>,
+        ) -> Result<(), IndexerError> {
+            unimplemented!()
+        }
+
+        async fn persist_checkpoints(
+            &self,
+            checkpoints: Vec<IndexedCheckpoint>,
+        ) -> Result<(), IndexerError> {
+            let mut failures = self.failures.lock().unwrap();
+            for checkpoint in &checkpoints {
+                if let Some(error) = failures
+                    .get_mut(&checkpoint.sequence_number)
+                    .and_then(|errors| errors.pop_front())
+                {
+                    return Err(error);
+                }
+            }
+            self.committed
+                .lock()
+                .unwrap()
+                .extend(checkpoints.iter().map(|c| c.sequence_number));
+            Ok(())
+        }
+
+        async fn persist_transactions(
+            &self,
+            _transactions: Vec<IndexedTransaction>,
+        ) -> Result<(), IndexerError> {
+            Ok(())
+        }
+
+        async fn persist_tx_indices(&self, _indices: Vec<TxIndex>) -> Result<(), IndexerError> {
+            Ok(())
+        }
+
+        async fn persist_events(&self, _events: Vec<IndexedEvent>) -> Result<(), IndexerError> {
+            Ok(())
+        }
+
+        async fn persist_displays(
+            &self,
+            _display_updates: BTreeMap<String, StoredDisplay>,
+        ) -> Result<(), IndexerError> {
+            Ok(())
+        }
+
+        async fn persist_packages(
+            &self,
+            _packages: Vec<IndexedPackage>,
+        ) -> Result<(), IndexerError> {
+            Ok(())
+        }
+
+        async fn persist_epoch(&self, _epoch: EpochToCommit) -> Result<(), IndexerError> {
+            Ok(())
+        }
+
+        async fn advance_epoch(&self, _epoch: EpochToCommit) -> Result<(), IndexerError> {
+            Ok(())
+        }
+
+        async fn persist_quarantined_checkpoint(
+            &self,
+            checkpoint: StoredQuarantinedCheckpoint,
+        ) -> Result<(), IndexerError> {
+            self.quarantined.lock().unwrap().push(checkpoint);
+            Ok(())
+        }
+
+        async fn persist_epoch_consistency_report(
+            &self,
+            _report: StoredEpochConsistencyReport,
+        ) -> Result<(), IndexerError> {
+            unimplemented!()
+        }
+
+        async fn get_network_total_transactions_by_end_of_epoch(
+            &self,
+            _epoch: u64,
+        ) -> Result<u64, IndexerError> {
+            unimplemented!()
+        }
+
+        fn module_cache(&self) -> Arc<Self::ModuleCache> {
+            Arc::new(NoModules)
+        }
+
+        fn package_resolver(&self) -> Self::PackageResolver {
+            NoPackages
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    fn quarantine_enabled() -> QuarantineConfig {
+        QuarantineConfig {
+            enabled: true,
+            max_attempts: 3,
+            retry_interval: Duration::ZERO,
+        }
+    }
+
+    fn retryable_error() -> IndexerError {
+        IndexerError::PgPoolConnectionError("timed out".to_string())
+    }
+
+    fn fatal_error() -> IndexerError {
+        IndexerError::GenericError("invalid data".to_string())
+    }
+
+    /// Commits checkpoints 1 to 3, returning the watermark notified to the commit watchers.
+    async fn commit(
+        store: &MockStore,
+        epoch: Option<EpochToCommit>,
+        config: QuarantineConfig,
+    ) -> Option<CheckpointSequenceNumber> {
+        let checkpoints = (1..=3).map(CheckpointDataToCommit::new_for_tests).collect();
+        commit_batch(store, checkpoints, epoch, config).await
+    }
+
+    /// Commits `checkpoints`, returning the watermark notified to the commit watchers.
+    async fn commit_batch(
+        store: &MockStore,
+        checkpoints: Vec<CheckpointDataToCommit>,
+        epoch: Option<EpochToCommit>,
+        config: QuarantineConfig,
+    ) -> Option<CheckpointSequenceNumber> {
+        let metrics = IndexerMetrics::new(&Registry::new());
+        let (commit_notifier, watermark) = watch::channel(None);
+        commit_checkpoints_or_quarantine(
+            store,
+            checkpoints,
+            epoch,
+            &metrics,
+            &CommitMode::Live { cdc: None },
+            &commit_notifier,
+            config,
+        )
+        .await;
+        let watermark = *watermark.borrow();
+        watermark
+    }
+
+    #[tokio::test]
+    async fn test_commit_batch() {
+        let store = MockStore::default();
+        let config = QuarantineConfig {
+            enabled: false,
+            ..quarantine_enabled()
+        };
+
+        assert_eq!(commit(&store, None, config).await, Some(3));
+        assert_eq!(store.committed(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Persisting checkpoints 1-3 into DB should not fail")]
+    async fn test_poison_checkpoint_halts_without_quarantine() {
+        let store = MockStore::default();
+        store.fail(2, [fatal_error()]);
+        let config = QuarantineConfig {
+            enabled: false,
+            ..quarantine_enabled()
+        };
+
+        commit(&store, None, config).await;
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_failure() {
+        let store = MockStore::default();
+        // Fails the batch, and the first attempt to commit checkpoint 2 on its own.
+        store.fail(2, [retryable_error(), retryable_error()]);
+
+        assert_eq!(commit(&store, None, quarantine_enabled()).await, Some(3));
+        assert_eq!(store.committed(), vec![1, 2, 3]);
+        assert!(store.quarantined().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_after_max_attempts() {
+        let store = MockStore::default();
+        store.fail(2, (0..4).map(|_| retryable_error()));
+
+        assert_eq!(commit(&store, None, quarantine_enabled()).await, Some(3));
+        assert_eq!(store.committed(), vec![1, 3]);
+        assert_eq!(store.quarantined(), vec![(2, 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_fatal_failure_without_retrying() {
+        let store = MockStore::default();
+        // The last checkpoint of the batch still moves the watermark once quarantined.
+        store.fail(3, [fatal_error(), fatal_error()]);
+
+        assert_eq!(commit(&store, None, quarantine_enabled()).await, Some(3));
+        assert_eq!(store.committed(), vec![1, 2]);
+        assert_eq!(store.quarantined(), vec![(3, 1)]);
+        let quarantined = store.quarantined.lock().unwrap()[0].clone();
+        assert!(quarantined.error.contains("invalid data"));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Persisting checkpoint 3 into DB should not fail")]
+    async fn test_end_of_epoch_checkpoint_is_not_quarantined() {
+        let store = MockStore::default();
+        store.fail(3, [fatal_error(), fatal_error()]);
+        let epoch = EpochToCommit {
+            last_epoch: None,
+            new_epoch: IndexedEpochInfo::default(),
+        };
+
+        commit(&store, Some(epoch), quarantine_enabled()).await;
+    }
+
+    /// Checkpoints 1 to 3, where each `(checkpoint, version)` of `mutations` writes `object_id`
+    /// at `version` in `checkpoint`.
+    fn checkpoints_mutating(
+        object_id: ObjectID,
+        mutations: &[(u64, u64)],
+    ) -> Vec<CheckpointDataToCommit> {
+        (1..=3)
+            .map(|sequence_number| {
+                let mut checkpoint = CheckpointDataToCommit::new_for_tests(sequence_number);
+                for (_, version) in mutations.iter().filter(|(s, _)| *s == sequence_number) {
+                    let object = Object::with_id_owner_version_for_testing(
+                        object_id,
+                        SequenceNumber::from_u64(*version),
+                        SuiAddress::ZERO,
+                    );
+                    checkpoint
+                        .object_changes
+                        .changed_objects
+                        .push(IndexedObject::from_object(sequence_number, object, None));
+                }
+                checkpoint
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_after_object_mutation() {
+        let store = MockStore::default();
+        let object_id = ObjectID::random();
+        // The batch fails after its objects are written, so checkpoints 1 and 2 write their
+        // versions of the object again once committed on their own.
+        store.fail(3, [fatal_error(), fatal_error()]);
+        let checkpoints = checkpoints_mutating(object_id, &[(1, 1), (2, 2)]);
+
+        assert_eq!(
+            commit_batch(&store, checkpoints, None, quarantine_enabled()).await,
+            Some(3)
+        );
+        assert_eq!(store.committed(), vec![1, 2]);
+        assert_eq!(store.quarantined(), vec![(3, 1)]);
+        assert_eq!(store.object_version(object_id), Some(2));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Persisting checkpoint 3 into DB should not fail")]
+    async fn test_checkpoint_changing_objects_is_not_quarantined() {
+        let store = MockStore::default();
+        let object_id = ObjectID::random();
+        store.fail(3, [fatal_error(), fatal_error()]);
+        let checkpoints = checkpoints_mutating(object_id, &[(1, 1), (3, 2)]);
+
+        commit_batch(&store, checkpoints, None, quarantine_enabled()).await;
+    }
+
+    #[tokio::test]
+    async fn test_resync_does_not_move_live_metrics() {
+        let store = MockStore::default();
+        let metrics = IndexerMetrics::new(&Registry::new());
+        metrics.latest_tx_checkpoint_sequence_number.set(10);
+        let (commit_notifier, _watermark) = watch::channel(None);
+
+        commit_checkpoints_or_quarantine(
+            &store,
+            (1..=3).map(CheckpointDataToCommit::new_for_tests).collect(),
+            None,
+            &metrics,
+            &CommitMode::Resync,
+            &commit_notifier,
+            quarantine_enabled(),
+        )
+        .await;
+
+        assert_eq!(store.committed(), vec![1, 2, 3]);
+        assert_eq!(metrics.latest_tx_checkpoint_sequence_number.get(), 10);
+        assert_eq!(metrics.total_tx_checkpoint_committed.get(), 0);
+        assert_eq!(metrics.latest_resynced_checkpoint_sequence_number.get(), 3);
+        assert_eq!(metrics.total_tx_checkpoint_resynced.get(), 3);
+    }
 }
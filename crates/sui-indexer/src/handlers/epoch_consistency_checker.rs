@@ -0,0 +1,322 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jsonrpsee::http_client::HttpClient;
+use tracing::{info, warn};
+
+use sui_json_rpc_api::GovernanceReadApiClient;
+use sui_types::base_types::SuiAddress;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+
+use crate::models::epoch::StoredEpochInfo;
+use crate::models::epoch_consistency_reports::{EpochDiscrepancy, StoredEpochConsistencyReport};
+use crate::types::IndexerResult;
+use crate::{metrics::IndexerMetrics, store::IndexerStore};
+
+const EPOCH_CONSISTENCY_CHECK_INTERVAL_SECS: u64 = 300;
+
+pub struct EpochConsistencyChecker<S> {
+    pub store: S,
+    fullnode: HttpClient,
+    metrics: IndexerMetrics,
+    pub interval_secs: u64,
+}
+
+impl<S> EpochConsistencyChecker<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(
+        store: S,
+        fullnode: HttpClient,
+        metrics: IndexerMetrics,
+    ) -> EpochConsistencyChecker<S> {
+        let interval_secs = std::env::var("EPOCH_CONSISTENCY_CHECK_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(EPOCH_CONSISTENCY_CHECK_INTERVAL_SECS);
+        Self {
+            store,
+            fullnode,
+            metrics,
+            interval_secs,
+        }
+    }
+
+    // Each epoch is checked once, while both the fullnode and the indexer are in it: the epoch's
+    // row in `epochs` is then compared with the fullnode's latest system state, which only
+    // changes at epoch boundaries for the values compared. Once an epoch has ended, its indexed
+    // system state is replaced by that of the next epoch, so epochs that were missed (e.g. while
+    // the indexer was catching up) are not checked. Every check is recorded in
+    // `epoch_consistency_reports`, with the discrepancies found, if any.
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Starting epoch consistency checker...");
+        let mut last_checked_epoch = None;
+        loop {
+            match self.fullnode.get_latest_sui_system_state().await {
+                Ok(system_state) if last_checked_epoch < Some(system_state.epoch) => {
+                    if self.check_epoch(&system_state).await? {
+                        last_checked_epoch = Some(system_state.epoch);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to get latest system state from fullnode: {e}"),
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(self.interval_secs)).await;
+        }
+    }
+
+    /// Checks the indexed data of the epoch of `system_state` against it, returning whether the
+    /// epoch could be checked.
+    async fn check_epoch(&self, system_state: &SuiSystemStateSummary) -> IndexerResult<bool> {
+        let epoch = system_state.epoch;
+        let Some(indexed) = self.store.get_epoch_info(epoch).await? else {
+            // The indexer has not reached the epoch yet.
+            return Ok(false);
+        };
+        if indexed.last_checkpoint_id.is_some() {
+            // The fullnode is lagging behind the indexer, which has already ended the epoch.
+            return Ok(false);
+        }
+
+        let discrepancies = find_discrepancies(&indexed, system_state);
+        for discrepancy in &discrepancies {
+            warn!(
+                epoch,
+                "Indexed epoch is inconsistent with fullnode: {discrepancy:?}"
+            );
+            self.metrics
+                .total_epoch_consistency_discrepancies
+                .with_label_values(&[discrepancy.kind])
+                .inc();
+        }
+
+        let checked_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as u64;
+        self.store
+            .persist_epoch_consistency_report(StoredEpochConsistencyReport::new(
+                epoch,
+                &discrepancies,
+                checked_at_ms,
+            ))
+            .await?;
+        self.metrics
+            .latest_epoch_consistency_check
+            .set(epoch as i64);
+        info!(
+            epoch,
+            "Checked indexed epoch against fullnode, found {} discrepancies",
+            discrepancies.len()
+        );
+        Ok(true)
+    }
+}
+
+/// Compares the indexed data of an epoch that is in progress with the fullnode's system state
+/// of the same epoch: the epoch's start parameters, stake and storage fund, and its set of active
+/// validators with their stake and voting power.
+fn find_discrepancies(
+    indexed: &StoredEpochInfo,
+    fullnode: &SuiSystemStateSummary,
+) -> Vec<EpochDiscrepancy> {
+    let mut discrepancies = vec![];
+    let d = &mut discrepancies;
+    compare(
+        d,
+        "epoch_start_timestamp",
+        None,
+        indexed.epoch_start_timestamp as u64,
+        fullnode.epoch_start_timestamp_ms,
+    );
+    compare(
+        d,
+        "reference_gas_price",
+        None,
+        indexed.reference_gas_price as u64,
+        fullnode.reference_gas_price,
+    );
+    compare(
+        d,
+        "protocol_version",
+        None,
+        indexed.protocol_version as u64,
+        fullnode.protocol_version,
+    );
+    // The stake and storage fund of an epoch are indexed from the event emitted when the
+    // previous epoch ends, so they are not known for the first epoch.
+    if fullnode.epoch > 0 {
+        compare(
+            d,
+            "total_stake",
+            None,
+            indexed.total_stake as u64,
+            fullnode.total_stake,
+        );
+        compare(
+            d,
+            "storage_fund_balance",
+            None,
+            indexed.storage_fund_balance as u64,
+            fullnode.storage_fund_total_object_storage_rebates
+                + fullnode.storage_fund_non_refundable_balance,
+        );
+    }
+
+    let indexed_state: SuiSystemStateSummary = match bcs::from_bytes(&indexed.system_state) {
+        Ok(state) => state,
+        Err(e) => {
+            d.push(EpochDiscrepancy {
+                kind: "system_state",
+                validator: None,
+                indexed: format!("undecodable: {e}"),
+                fullnode: fullnode.epoch.to_string(),
+            });
+            return discrepancies;
+        }
+    };
+    compare(
+        d,
+        "system_state_epoch",
+        None,
+        indexed_state.epoch,
+        fullnode.epoch,
+    );
+
+    let indexed_validators: BTreeMap<_, _> = indexed_state
+        .active_validators
+        .iter()
+        .map(|v| (v.sui_address, v))
+        .collect();
+    let fullnode_validators: BTreeMap<_, _> = fullnode
+        .active_validators
+        .iter()
+        .map(|v| (v.sui_address, v))
+        .collect();
+    for (address, validator) in &fullnode_validators {
+        let Some(indexed_validator) = indexed_validators.get(address) else {
+            d.push(EpochDiscrepancy {
+                kind: "validator_set",
+                validator: Some(address.to_string()),
+                indexed: "missing".to_string(),
+                fullnode: "active".to_string(),
+            });
+            continue;
+        };
+        compare(
+            d,
+            "validator_stake",
+            Some(address),
+            indexed_validator.staking_pool_sui_balance,
+            validator.staking_pool_sui_balance,
+        );
+        compare(
+            d,
+            "validator_voting_power",
+            Some(address),
+            indexed_validator.voting_power,
+            validator.voting_power,
+        );
+    }
+    for address in indexed_validators.keys() {
+        if !fullnode_validators.contains_key(address) {
+            d.push(EpochDiscrepancy {
+                kind: "validator_set",
+                validator: Some(address.to_string()),
+                indexed: "active".to_string(),
+                fullnode: "missing".to_string(),
+            });
+        }
+    }
+    discrepancies
+}
+
+fn compare(
+    discrepancies: &mut Vec<EpochDiscrepancy>,
+    kind: &'static str,
+    validator: Option<&SuiAddress>,
+    indexed: u64,
+    fullnode: u64,
+) {
+    if indexed != fullnode {
+        discrepancies.push(EpochDiscrepancy {
+            kind,
+            validator: validator.map(|v| v.to_string()),
+            indexed: indexed.to_string(),
+            fullnode: fullnode.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
+
+    use super::*;
+
+    fn system_state(validators: &[(SuiAddress, u64)]) -> SuiSystemStateSummary {
+        SuiSystemStateSummary {
+            epoch: 5,
+            protocol_version: 30,
+            reference_gas_price: 750,
+            epoch_start_timestamp_ms: 1_000,
+            total_stake: validators.iter().map(|(_, stake)| stake).sum(),
+            storage_fund_total_object_storage_rebates: 100,
+            storage_fund_non_refundable_balance: 10,
+            active_validators: validators
+                .iter()
+                .map(|(address, stake)| SuiValidatorSummary {
+                    sui_address: *address,
+                    staking_pool_sui_balance: *stake,
+                    voting_power: 5_000,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn indexed(system_state: &SuiSystemStateSummary) -> StoredEpochInfo {
+        StoredEpochInfo {
+            epoch: system_state.epoch as i64,
+            epoch_start_timestamp: system_state.epoch_start_timestamp_ms as i64,
+            reference_gas_price: system_state.reference_gas_price as i64,
+            protocol_version: system_state.protocol_version as i64,
+            total_stake: system_state.total_stake as i64,
+            storage_fund_balance: 110,
+            system_state: bcs::to_bytes(system_state).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_consistent_epoch() {
+        let a = SuiAddress::random_for_testing_only();
+        let b = SuiAddress::random_for_testing_only();
+        let state = system_state(&[(a, 100), (b, 200)]);
+        assert_eq!(find_discrepancies(&indexed(&state), &state), vec![]);
+    }
+
+    #[test]
+    fn test_inconsistent_epoch() {
+        let a = SuiAddress::random_for_testing_only();
+        let b = SuiAddress::random_for_testing_only();
+        let c = SuiAddress::random_for_testing_only();
+        let indexed = indexed(&system_state(&[(a, 100), (b, 200)]));
+        let fullnode = system_state(&[(a, 150), (c, 100)]);
+
+        let kinds: Vec<_> = find_discrepancies(&indexed, &fullnode)
+            .into_iter()
+            .map(|d| (d.kind, d.validator))
+            .collect();
+        assert_eq!(kinds.len(), 4);
+        assert!(kinds.contains(&("total_stake", None)));
+        assert!(kinds.contains(&("validator_stake", Some(a.to_string()))));
+        assert!(kinds.contains(&("validator_set", Some(b.to_string()))));
+        assert!(kinds.contains(&("validator_set", Some(c.to_string()))));
+    }
+}
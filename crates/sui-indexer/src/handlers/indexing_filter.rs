@@ -0,0 +1,348 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use sui_types::base_types::ObjectID;
+use sui_types::transaction::TransactionDataAPI;
+
+use crate::metrics::IndexerMetrics;
+
+use super::CheckpointDataToCommit;
+
+/// A package, a module in a package, or a function or type in a module, written as `<package>`,
+/// `<package>::<module>` or `<package>::<module>::<name>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MovePattern {
+    package: ObjectID,
+    module: Option<String>,
+    name: Option<String>,
+}
+
+/// Restricts the transactions, events and object history that are indexed to those relevant to
+/// selected packages, modules, functions or types. Checkpoints, epochs, packages and live objects
+/// are always indexed, as the indexer and its read APIs depend on them.
+///
+/// A transaction is relevant to a pattern if it calls a matching function or emits an event of a
+/// matching type, and an object if its type matches. Events are indexed along with their
+/// transaction, so that a transaction is never indexed with only some of its events. A type also
+/// matches if any of its type parameters does, e.g. `0x2::coin::Coin<0xabc::token::TOKEN>`
+/// matches `0xabc`. Rows are indexed if they are relevant to any pattern in `allowlist` (or the
+/// allowlist is empty), and not to any pattern in `denylist`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IndexingFilter {
+    pub allowlist: Vec<MovePattern>,
+    pub denylist: Vec<MovePattern>,
+}
+
+impl MovePattern {
+    fn matches(&self, package: &ObjectID, module: &str, name: &str) -> bool {
+        self.package == *package
+            && self.module.as_ref().map_or(true, |m| m == module)
+            && self.name.as_ref().map_or(true, |n| n == name)
+    }
+
+    fn matches_type(&self, tag: &StructTag) -> bool {
+        self.matches(
+            &ObjectID::from(tag.address),
+            tag.module.as_str(),
+            tag.name.as_str(),
+        ) || tag
+            .type_params
+            .iter()
+            .any(|param| self.matches_type_tag(param))
+    }
+
+    fn matches_type_tag(&self, tag: &TypeTag) -> bool {
+        match tag {
+            TypeTag::Struct(tag) => self.matches_type(tag),
+            TypeTag::Vector(tag) => self.matches_type_tag(tag),
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for MovePattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split("::");
+        let package = parts.next().unwrap_or_default();
+        let package = ObjectID::from_hex_literal(package)
+            .map_err(|e| anyhow!("Invalid package in pattern {s:?}: {e}"))?;
+        let identifier = |part: Option<&str>| match part {
+            Some(part) if part.is_empty() => Err(anyhow!("Empty identifier in pattern {s:?}")),
+            part => Ok(part.map(str::to_string)),
+        };
+        let module = identifier(parts.next())?;
+        let name = identifier(parts.next())?;
+        if parts.next().is_some() {
+            return Err(anyhow!(
+                "Invalid pattern {s:?}, expected <package>[::<module>[::<name>]]"
+            ));
+        }
+        Ok(Self {
+            package,
+            module,
+            name,
+        })
+    }
+}
+
+impl IndexingFilter {
+    pub fn is_enabled(&self) -> bool {
+        !self.allowlist.is_empty() || !self.denylist.is_empty()
+    }
+
+    fn is_relevant(&self, matches: impl Fn(&MovePattern) -> bool) -> bool {
+        (self.allowlist.is_empty() || self.allowlist.iter().any(&matches))
+            && !self.denylist.iter().any(matches)
+    }
+
+    fn is_type_relevant(&self, tag: &StructTag) -> bool {
+        self.is_relevant(|pattern| pattern.matches_type(tag))
+    }
+
+    /// Drops the transactions (and their indices and events) and object history of an indexed
+    /// checkpoint that are not relevant to the filter.
+    pub fn apply(&self, data: &mut CheckpointDataToCommit, metrics: &IndexerMetrics) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut relevant_txs = HashSet::new();
+        for tx in &data.transactions {
+            let move_calls = tx.sender_signed_data.transaction_data().move_calls();
+            let is_relevant = self.is_relevant(|pattern| {
+                move_calls.iter().any(|(package, module, function)| {
+                    pattern.matches(package, module.as_str(), function.as_str())
+                }) || tx
+                    .events
+                    .iter()
+                    .any(|event| pattern.matches_type(&event.type_))
+            });
+            if is_relevant {
+                relevant_txs.insert(tx.tx_sequence_number);
+            }
+        }
+
+        let transactions = data.transactions.len();
+        data.transactions
+            .retain(|tx| relevant_txs.contains(&tx.tx_sequence_number));
+        data.tx_indices
+            .retain(|index| relevant_txs.contains(&index.tx_sequence_number));
+
+        let events = data.events.len();
+        data.events
+            .retain(|event| relevant_txs.contains(&event.tx_sequence_number));
+
+        // Packages have no type, and are always indexed.
+        let changed_objects = &mut data.object_history_changes.changed_objects;
+        let objects_history = changed_objects.len();
+        changed_objects.retain(|object| {
+            object
+                .object
+                .struct_tag()
+                .map_or(true, |tag| self.is_type_relevant(&tag))
+        });
+        let retained_objects_history = changed_objects.len();
+
+        for (table, total, retained) in [
+            ("transactions", transactions, data.transactions.len()),
+            ("events", events, data.events.len()),
+            ("objects_history", objects_history, retained_objects_history),
+        ] {
+            metrics
+                .total_filtered_out_rows
+                .with_label_values(&[table])
+                .inc_by((total - retained) as u64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::identifier::Identifier;
+    use prometheus::Registry;
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::effects::TransactionEffects;
+    use sui_types::event::Event;
+    use sui_types::transaction::{Transaction, TransactionData};
+
+    use super::*;
+    use crate::types::{IndexedEvent, IndexedTransaction, TransactionKind};
+
+    fn pattern(s: &str) -> MovePattern {
+        MovePattern::from_str(s).unwrap()
+    }
+
+    fn tag(s: &str) -> StructTag {
+        sui_types::parse_sui_struct_tag(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(
+            pattern("0x2::coin::Coin"),
+            MovePattern {
+                package: ObjectID::from_hex_literal("0x2").unwrap(),
+                module: Some("coin".to_string()),
+                name: Some("Coin".to_string()),
+            }
+        );
+        assert_eq!(pattern("0x2").module, None);
+        assert!(MovePattern::from_str("coin::Coin").is_err());
+        assert!(MovePattern::from_str("0x2::").is_err());
+        assert!(MovePattern::from_str("0x2::coin::Coin::value").is_err());
+    }
+
+    #[test]
+    fn test_matches_type() {
+        let coin = tag("0x2::coin::Coin<0xabc::token::TOKEN>");
+        assert!(pattern("0x2").matches_type(&coin));
+        assert!(pattern("0x2::coin::Coin").matches_type(&coin));
+        assert!(pattern("0xabc::token").matches_type(&coin));
+        assert!(!pattern("0x2::balance").matches_type(&coin));
+        assert!(!pattern("0xabc::token::OTHER").matches_type(&coin));
+    }
+
+    #[test]
+    fn test_is_type_relevant() {
+        let coin = tag("0x2::coin::Coin<0xabc::token::TOKEN>");
+        let sui = tag("0x2::coin::Coin<0x2::sui::SUI>");
+
+        assert!(IndexingFilter::default().is_type_relevant(&sui));
+
+        let allowlist = IndexingFilter {
+            allowlist: vec![pattern("0xabc")],
+            denylist: vec![],
+        };
+        assert!(allowlist.is_type_relevant(&coin));
+        assert!(!allowlist.is_type_relevant(&sui));
+
+        let denylist = IndexingFilter {
+            allowlist: vec![pattern("0x2::coin")],
+            denylist: vec![pattern("0xabc::token")],
+        };
+        assert!(!denylist.is_type_relevant(&coin));
+        assert!(denylist.is_type_relevant(&sui));
+    }
+
+    /// A transaction calling `<package>::<module>::<function>`, emitting events of the given
+    /// types, along with its indexed events.
+    fn transaction(
+        tx_sequence_number: u64,
+        function: &str,
+        event_types: &[&str],
+    ) -> (IndexedTransaction, Vec<IndexedEvent>) {
+        let function = pattern(function);
+        let sender = SuiAddress::default();
+        let data = TransactionData::new_move_call(
+            sender,
+            function.package,
+            Identifier::new(function.module.unwrap()).unwrap(),
+            Identifier::new(function.name.unwrap()).unwrap(),
+            vec![],
+            random_object_ref(),
+            vec![],
+            1_000_000,
+            1_000,
+        )
+        .unwrap();
+        let tx_digest = TransactionDigest::random();
+        let events: Vec<_> = event_types
+            .iter()
+            .map(|event_type| Event {
+                package_id: function.package,
+                transaction_module: Identifier::new("module").unwrap(),
+                sender,
+                type_: tag(event_type),
+                contents: vec![],
+            })
+            .collect();
+        let indexed_events = events
+            .iter()
+            .enumerate()
+            .map(|(idx, event)| IndexedEvent {
+                tx_sequence_number,
+                event_sequence_number: idx as u64,
+                checkpoint_sequence_number: 1,
+                transaction_digest: tx_digest,
+                senders: vec![sender],
+                package: event.package_id,
+                module: event.transaction_module.to_string(),
+                event_type: event.type_.to_canonical_string(/* with_prefix */ true),
+                bcs: vec![],
+                timestamp_ms: 0,
+            })
+            .collect();
+        let tx = IndexedTransaction {
+            tx_sequence_number,
+            tx_digest,
+            sender_signed_data: Transaction::from_data(data, vec![]).into_data(),
+            effects: TransactionEffects::default(),
+            checkpoint_sequence_number: 1,
+            timestamp_ms: 0,
+            object_changes: vec![],
+            balance_change: vec![],
+            events,
+            transaction_kind: TransactionKind::ProgrammableTransaction,
+            successful_tx_num: 0,
+        };
+        (tx, indexed_events)
+    }
+
+    #[test]
+    fn test_apply_filters_events_with_their_transaction() {
+        let mut data = CheckpointDataToCommit::new_for_tests(1);
+        for (tx, events) in [
+            // Relevant by its call, with an event of an irrelevant type.
+            transaction(0, "0xabc::token::mint", &["0x2::coin::CoinEvent"]),
+            // Relevant by its event, among events of irrelevant types.
+            transaction(
+                1,
+                "0x2::pay::split",
+                &["0x2::coin::CoinEvent", "0xabc::token::Minted"],
+            ),
+            // Irrelevant, as it calls a denied function, despite its relevant event.
+            transaction(2, "0xabc::token::debug", &["0xabc::token::Minted"]),
+            // Irrelevant.
+            transaction(3, "0x2::pay::split", &["0x2::coin::CoinEvent"]),
+        ] {
+            data.transactions.push(tx);
+            data.events.extend(events);
+        }
+        let filter = IndexingFilter {
+            allowlist: vec![pattern("0xabc")],
+            denylist: vec![pattern("0xabc::token::debug")],
+        };
+        let metrics = IndexerMetrics::new(&Registry::new());
+
+        filter.apply(&mut data, &metrics);
+
+        let transactions: Vec<_> = data
+            .transactions
+            .iter()
+            .map(|tx| tx.tx_sequence_number)
+            .collect();
+        assert_eq!(transactions, vec![0, 1]);
+        let events: Vec<_> = data
+            .events
+            .iter()
+            .map(|event| (event.tx_sequence_number, event.event_sequence_number))
+            .collect();
+        assert_eq!(events, vec![(0, 0), (1, 0), (1, 1)]);
+        let filtered_out = |table| {
+            metrics
+                .total_filtered_out_rows
+                .with_label_values(&[table])
+                .get()
+        };
+        assert_eq!(filtered_out("transactions"), 2);
+        assert_eq!(filtered_out("events"), 2);
+    }
+}
@@ -11,12 +11,21 @@ use crate::{
     },
 };
 
+pub mod cdc_publisher;
 pub mod checkpoint_handler;
 pub mod committer;
+pub mod epoch_consistency_checker;
+pub mod indexing_filter;
+pub mod move_call_metrics_processor;
+pub mod objects_resolution_backfill;
 pub mod objects_snapshot_processor;
+pub mod pruner;
+pub mod rollups_processor;
+pub mod shared_object_metrics_processor;
+pub mod tx_address_buckets_backfill;
 pub mod tx_processor;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct CheckpointDataToCommit {
     pub checkpoint: IndexedCheckpoint,
     pub transactions: Vec<IndexedTransaction>,
@@ -40,3 +49,63 @@ pub struct EpochToCommit {
     pub last_epoch: Option<IndexedEpochInfo>,
     pub new_epoch: IndexedEpochInfo,
 }
+
+#[cfg(test)]
+impl CheckpointDataToCommit {
+    /// An empty checkpoint, to be filled in by tests.
+    pub(crate) fn new_for_tests(sequence_number: u64) -> Self {
+        use sui_types::base_types::ExecutionDigests;
+        use sui_types::crypto::AggregateAuthoritySignature;
+        use sui_types::digests::CheckpointDigest;
+        use sui_types::gas::GasCostSummary;
+        use sui_types::messages_checkpoint::{CheckpointContents, CheckpointSummary};
+
+        let contents =
+            CheckpointContents::new_with_digests_only_for_tests(Vec::<ExecutionDigests>::new());
+        let summary = CheckpointSummary::new(
+            0,
+            sequence_number,
+            0,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            None,
+            0,
+        );
+        let no_object_changes = TransactionObjectChangesToCommit {
+            changed_objects: vec![],
+            deleted_objects: vec![],
+        };
+        Self {
+            checkpoint: IndexedCheckpoint {
+                sequence_number,
+                checkpoint_digest: CheckpointDigest::random(),
+                epoch: 0,
+                tx_digests: vec![],
+                network_total_transactions: 0,
+                previous_checkpoint_digest: None,
+                timestamp_ms: 0,
+                total_gas_cost: 0,
+                computation_cost: 0,
+                storage_cost: 0,
+                storage_rebate: 0,
+                non_refundable_storage_fee: 0,
+                checkpoint_commitments: vec![],
+                validator_signature: AggregateAuthoritySignature::default(),
+                successful_tx_num: 0,
+                end_of_epoch_data: None,
+                end_of_epoch: false,
+                checkpoint_summary: summary,
+                checkpoint_contents: contents,
+            },
+            transactions: vec![],
+            events: vec![],
+            tx_indices: vec![],
+            display_updates: BTreeMap::new(),
+            object_changes: no_object_changes.clone(),
+            object_history_changes: no_object_changes,
+            packages: vec![],
+            epoch: None,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use tracing::info;
+
+use crate::types::IndexerResult;
+use crate::{metrics::IndexerMetrics, store::IndexerStore};
+
+pub const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+const MOVE_CALL_METRICS_INTERVAL_SECS: u64 = 600;
+
+pub struct MoveCallMetricsProcessor<S> {
+    pub store: S,
+    metrics: IndexerMetrics,
+    pub interval_secs: u64,
+}
+
+impl<S> MoveCallMetricsProcessor<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, metrics: IndexerMetrics) -> MoveCallMetricsProcessor<S> {
+        let interval_secs = std::env::var("MOVE_CALL_METRICS_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(MOVE_CALL_METRICS_INTERVAL_SECS);
+        Self {
+            store,
+            metrics,
+            interval_secs,
+        }
+    }
+
+    // The `move_call_metrics` table holds, for each day, the number of calls, the number of
+    // distinct senders and the gas used per Move function, aggregated from `tx_calls` and
+    // `tx_senders`. A day is only aggregated once a checkpoint from the following day has been
+    // indexed, so the metrics of a day are final when written. Aggregation resumes from the day
+    // after the latest one in the table, or from the day of the earliest indexed checkpoint,
+    // whose metrics only cover the part of the day that was indexed.
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Starting move call metrics processor...");
+        let mut next_day = self
+            .store
+            .get_latest_move_call_metrics_day()
+            .await?
+            .map(|day| day + 1);
+
+        loop {
+            if let Some((first_ms, latest_ms)) =
+                self.store.get_checkpoint_timestamp_range_ms().await?
+            {
+                let start_day = *next_day.get_or_insert(first_ms / MILLIS_PER_DAY);
+                // The day of the latest checkpoint is still in progress.
+                let end_day = latest_ms / MILLIS_PER_DAY;
+                for day in start_day..end_day {
+                    self.store.persist_move_call_metrics(day).await?;
+                    self.metrics.latest_move_call_metrics_day.set(day as i64);
+                    next_day = Some(day + 1);
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(self.interval_secs)).await;
+        }
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::Duration;
+
+use backoff::ExponentialBackoff;
+use itertools::Itertools;
+use move_core_types::annotated_value::MoveTypeLayout;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use sui_package_resolver::Resolver;
+use sui_types::dynamic_field::DynamicFieldInfo;
+use sui_types::object::Object;
+use tracing::{error, info, warn};
+
+use crate::errors::{IndexerError, IndexerErrorKind};
+use crate::metrics::IndexerMetrics;
+use crate::models::display::StoredDisplay;
+use crate::models::objects::StoredObject;
+use crate::store::IndexerStore;
+use crate::types::{IndexedObjectDisplay, IndexerResult};
+
+const OBJECTS_RESOLUTION_BACKFILL_INTERVAL_SECS: u64 = 600;
+
+/// The number of objects to resolve in each batch.
+const OBJECTS_RESOLUTION_BATCH_SIZE: usize = 1_000;
+
+/// How long the reads and writes of a batch are retried for, while they fail with retryable
+/// errors, before the round is given up.
+const MAX_BATCH_RETRY_ELAPSED: Duration = Duration::from_secs(300);
+
+pub struct ObjectsResolutionBackfill<S: IndexerStore> {
+    pub store: S,
+    package_resolver: Resolver<S::PackageResolver>,
+    metrics: IndexerMetrics,
+    pub interval_secs: u64,
+}
+
+impl<S> ObjectsResolutionBackfill<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, metrics: IndexerMetrics) -> Self {
+        let package_resolver = Resolver::new(store.package_resolver());
+        let interval_secs = std::env::var("OBJECTS_RESOLUTION_BACKFILL_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(OBJECTS_RESOLUTION_BACKFILL_INTERVAL_SECS);
+        Self {
+            store,
+            package_resolver,
+            metrics,
+            interval_secs,
+        }
+    }
+
+    // Live objects ingested before their fields were resolved at ingestion, or whose Display was
+    // updated since they were rendered, are resolved by this task. Each round walks the live
+    // objects in object ID order, resolving the dynamic field names and rendering the Displays
+    // that are missing or stale, one batch at a time. Objects that fail to resolve are skipped
+    // until the next round, and keep being resolved when they are read. A round whose DB reads or
+    // writes keep failing is given up, and counted as failed, until the next round.
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Starting objects resolution backfill...");
+        loop {
+            match self.resolve_live_objects().await {
+                Ok(()) => info!("Finished a round of the objects resolution backfill"),
+                Err(e) => {
+                    error!("Objects resolution backfill round failed, until the next round: {e}");
+                    self.metrics
+                        .total_objects_resolution_backfill_failures
+                        .inc();
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(self.interval_secs)).await;
+        }
+    }
+
+    async fn resolve_live_objects(&self) -> IndexerResult<()> {
+        let mut after_object_id = None;
+        loop {
+            let objects = retry_transient(batch_backoff(), || {
+                self.store
+                    .get_objects_to_resolve(after_object_id.clone(), OBJECTS_RESOLUTION_BATCH_SIZE)
+            })
+            .await?;
+            let Some(last) = objects.last() else {
+                return Ok(());
+            };
+            after_object_id = Some(last.object_id.clone());
+
+            let resolved = self.resolve_objects(objects).await?;
+            let resolved_count = resolved.len();
+            retry_transient(batch_backoff(), || {
+                self.store.persist_resolved_objects(resolved.clone())
+            })
+            .await?;
+            self.metrics
+                .total_objects_resolved_by_backfill
+                .inc_by(resolved_count as u64);
+        }
+    }
+
+    async fn resolve_objects(
+        &self,
+        objects: Vec<StoredObject>,
+    ) -> IndexerResult<Vec<StoredObject>> {
+        let object_types: Vec<_> = objects
+            .iter()
+            .filter_map(|o| o.object_type.clone())
+            .unique()
+            .collect();
+        let displays = retry_transient(batch_backoff(), || {
+            self.store.get_displays(object_types.clone())
+        })
+        .await?;
+
+        let mut resolved = vec![];
+        for stored in objects {
+            let object_id = stored.object_id.clone();
+            match self.resolve_object(stored, &displays).await {
+                Ok(object) => resolved.push(object),
+                Err(e) => warn!("Failed to resolve object {:?}: {e}", object_id),
+            }
+        }
+        Ok(resolved)
+    }
+
+    async fn resolve_object(
+        &self,
+        mut stored: StoredObject,
+        displays: &BTreeMap<String, StoredDisplay>,
+    ) -> IndexerResult<StoredObject> {
+        let object: Object = stored.clone().try_into()?;
+        let move_object = object.data.try_as_move().ok_or_else(|| {
+            IndexerError::ResolveMoveStructError(format!(
+                "Object {} to resolve is not a Move object",
+                object.id()
+            ))
+        })?;
+        let struct_tag: StructTag = move_object.type_().clone().into();
+        let layout = match self
+            .package_resolver
+            .type_layout(TypeTag::Struct(Box::new(struct_tag)))
+            .await
+            .map_err(|e| IndexerError::ResolveMoveStructError(e.to_string()))?
+        {
+            MoveTypeLayout::Struct(layout) => layout,
+            _ => {
+                return Err(IndexerError::ResolveMoveStructError(
+                    "MoveTypeLayout is not Struct".to_string(),
+                ))
+            }
+        };
+
+        if stored.df_kind.is_some() && stored.df_bcs_name.is_none() {
+            let move_struct = move_object.to_move_struct(&layout)?;
+            let (name_value, _, _) = DynamicFieldInfo::parse_move_object(&move_struct)?;
+            stored.df_bcs_name = Some(bcs::to_bytes(&name_value.undecorate())?);
+        }
+
+        let display = stored.object_type.as_ref().and_then(|t| displays.get(t));
+        if let Some(display) = display {
+            if stored.display_version != Some(display.version) {
+                let rendered = IndexedObjectDisplay::render(&object, &layout, display)?;
+                let fields = serde_json::to_string(&rendered.fields)
+                    .map_err(|e| IndexerError::SerdeError(e.to_string()))?;
+                stored.display_version = Some(rendered.version as i16);
+                stored.display_fields = Some(fields);
+            }
+        }
+        Ok(stored)
+    }
+}
+
+fn batch_backoff() -> ExponentialBackoff {
+    ExponentialBackoff {
+        max_elapsed_time: Some(MAX_BATCH_RETRY_ELAPSED),
+        ..Default::default()
+    }
+}
+
+/// Runs `operation`, retrying it with `backoff` while it fails with retryable errors. Other errors,
+/// and the last error once `backoff` gives up, are returned.
+async fn retry_transient<T, F, Fut>(
+    backoff: ExponentialBackoff,
+    mut operation: F,
+) -> IndexerResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = IndexerResult<T>>,
+{
+    backoff::future::retry_notify(
+        backoff,
+        || {
+            let result = operation();
+            async move {
+                result.await.map_err(|e| match e.kind() {
+                    IndexerErrorKind::Retryable => backoff::Error::transient(e),
+                    _ => backoff::Error::permanent(e),
+                })
+            }
+        },
+        |e: IndexerError, delay: Duration| {
+            warn!("Objects resolution backfill failed with error: {e}, retrying in {delay:?}");
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn test_backoff() -> ExponentialBackoff {
+        ExponentialBackoff {
+            initial_interval: Duration::from_millis(1),
+            max_interval: Duration::from_millis(10),
+            max_elapsed_time: Some(Duration::from_secs(1)),
+            ..Default::default()
+        }
+    }
+
+    fn connection_lost() -> IndexerError {
+        IndexerError::PgPoolConnectionError("connection lost".to_string())
+    }
+
+    fn unsupported() -> IndexerError {
+        IndexerError::NotSupportedError("unsupported".to_string())
+    }
+
+    /// An operation failing with `error` on its first `failures` attempts, and then returning the
+    /// number of failed attempts.
+    fn failing(
+        attempts: &AtomicUsize,
+        failures: usize,
+        error: fn() -> IndexerError,
+    ) -> impl FnMut() -> std::future::Ready<IndexerResult<usize>> + '_ {
+        move || {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+            std::future::ready(if attempt < failures {
+                Err(error())
+            } else {
+                Ok(attempt)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_errors() {
+        let attempts = AtomicUsize::new(0);
+        let result = retry_transient(test_backoff(), failing(&attempts, 2, connection_lost)).await;
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_surface_permanent_errors() {
+        let attempts = AtomicUsize::new(0);
+        let result = retry_transient(test_backoff(), failing(&attempts, 1, unsupported)).await;
+        assert!(matches!(result, Err(IndexerError::NotSupportedError(_))));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_surface_transient_errors_once_retries_give_up() {
+        let attempts = AtomicUsize::new(0);
+        let backoff = ExponentialBackoff {
+            max_elapsed_time: Some(Duration::ZERO),
+            ..test_backoff()
+        };
+        let result = retry_transient(backoff, failing(&attempts, 10, connection_lost)).await;
+        assert!(matches!(
+            result,
+            Err(IndexerError::PgPoolConnectionError(_))
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use tracing::info;
+
+use crate::types::IndexerResult;
+use crate::{metrics::IndexerMetrics, store::IndexerStore};
+
+const PRUNER_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Groups of tables that are pruned together, up to the same epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrunableTables {
+    /// `transactions`, and the `tx_*` tables that index them.
+    Transactions,
+    /// `events`.
+    Events,
+    /// `objects_history`.
+    ObjectsHistory,
+}
+
+/// How many of the most recent epochs to retain the data of, for each group of tables. Data is
+/// retained indefinitely for groups without a retention window.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub transactions_epochs: Option<u64>,
+    pub events_epochs: Option<u64>,
+    pub objects_history_epochs: Option<u64>,
+    /// Whether to detach the partitions of pruned epochs and leave them as standalone tables, to
+    /// be archived, rather than dropping them. Tables that are not partitioned by epoch have their
+    /// pruned rows deleted either way.
+    pub archive_partitions: bool,
+}
+
+pub struct Pruner<S> {
+    pub store: S,
+    metrics: IndexerMetrics,
+    policy: RetentionPolicy,
+    pub interval_secs: u64,
+}
+
+impl PrunableTables {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrunableTables::Transactions => "transactions",
+            PrunableTables::Events => "events",
+            PrunableTables::ObjectsHistory => "objects_history",
+        }
+    }
+}
+
+impl RetentionPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.transactions_epochs.is_some()
+            || self.events_epochs.is_some()
+            || self.objects_history_epochs.is_some()
+    }
+
+    /// The groups of tables that have a retention window, and their windows, in epochs.
+    fn windows(&self) -> impl Iterator<Item = (PrunableTables, u64)> {
+        [
+            (PrunableTables::Transactions, self.transactions_epochs),
+            (PrunableTables::Events, self.events_epochs),
+            (PrunableTables::ObjectsHistory, self.objects_history_epochs),
+        ]
+        .into_iter()
+        .filter_map(|(tables, window)| Some((tables, window?)))
+    }
+}
+
+impl<S> Pruner<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, metrics: IndexerMetrics, policy: RetentionPolicy) -> Pruner<S> {
+        let interval_secs = std::env::var("PRUNER_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(PRUNER_INTERVAL_SECS);
+        Self {
+            store,
+            metrics,
+            policy,
+            interval_secs,
+        }
+    }
+
+    // Data is pruned an epoch at a time, once it falls out of its retention window: Partitions of
+    // tables that are partitioned by epoch are detached (and dropped, unless they are being
+    // archived), and the rows of other tables are deleted in batches. Pruning is idempotent, so
+    // it picks up where it left off after a restart.
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Starting pruner with retention policy {:?}...", self.policy);
+        loop {
+            if let Some(latest_epoch) = self.store.get_latest_epoch().await? {
+                for (tables, window) in self.policy.windows() {
+                    let Some(before_epoch) = prune_before_epoch(latest_epoch, window) else {
+                        continue;
+                    };
+
+                    let pruned = self
+                        .store
+                        .prune_epochs(tables, before_epoch, self.policy.archive_partitions)
+                        .await?;
+
+                    for (table, rows) in pruned {
+                        self.metrics
+                            .total_pruned_rows
+                            .with_label_values(&[table])
+                            .inc_by(rows);
+                    }
+
+                    info!("Pruned {} before epoch {before_epoch}", tables.name());
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(self.interval_secs)).await;
+        }
+    }
+}
+
+/// The epoch that data must be pruned before, to retain the latest `window` epochs (including the
+/// latest, which is still in progress), or `None` if there is nothing to prune.
+fn prune_before_epoch(latest_epoch: u64, window: u64) -> Option<u64> {
+    let before_epoch = (latest_epoch + 1).checked_sub(window.max(1))?;
+    (before_epoch > 0).then_some(before_epoch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_before_epoch() {
+        // Not enough epochs to prune anything yet.
+        assert_eq!(prune_before_epoch(0, 1), None);
+        assert_eq!(prune_before_epoch(4, 5), None);
+        assert_eq!(prune_before_epoch(4, 10), None);
+
+        assert_eq!(prune_before_epoch(5, 5), Some(1));
+        assert_eq!(prune_before_epoch(100, 1), Some(100));
+
+        // The latest epoch is always retained.
+        assert_eq!(prune_before_epoch(100, 0), Some(100));
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use tracing::info;
+
+use crate::types::IndexerResult;
+use crate::{metrics::IndexerMetrics, store::IndexerStore};
+
+const ROLLUPS_INTERVAL_SECS: u64 = 60;
+
+// The most checkpoints added to the rollups in one DB transaction.
+const ROLLUPS_MAX_CHECKPOINTS: u64 = 10_000;
+
+pub struct RollupsProcessor<S> {
+    pub store: S,
+    metrics: IndexerMetrics,
+    pub interval_secs: u64,
+}
+
+impl<S> RollupsProcessor<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, metrics: IndexerMetrics) -> RollupsProcessor<S> {
+        let interval_secs = std::env::var("ROLLUPS_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(ROLLUPS_INTERVAL_SECS);
+        Self {
+            store,
+            metrics,
+            interval_secs,
+        }
+    }
+
+    // The `active_address_metrics`, `package_tx_metrics` and `sender_gas_metrics` tables hold, for
+    // each day, the number of distinct senders, the number of transactions calling each package,
+    // and the number of transactions and gas used of each sender, aggregated from `tx_senders`
+    // and `tx_calls`. Unlike the other daily metrics, they are refreshed incrementally, so that
+    // the current day is covered too: each refresh adds the transactions of the checkpoints after
+    // the rollups' watermark, up to the latest indexed checkpoint, and moves the watermark in the
+    // same DB transaction. Checkpoints are written after the rest of their data, so the data of
+    // the checkpoints being added is complete. Checkpoints indexed after the watermark has moved
+    // past them, like quarantined checkpoints that are indexed later, are not counted.
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Starting rollups processor...");
+        loop {
+            let next_cp = self.store.get_next_rollups_checkpoint().await?;
+            let latest_cp = self
+                .store
+                .get_latest_tx_checkpoint_sequence_number()
+                .await?;
+            if let Some((start_cp, latest_cp)) = next_cp.zip(latest_cp) {
+                if start_cp <= latest_cp {
+                    let end_cp = latest_cp.min(start_cp + ROLLUPS_MAX_CHECKPOINTS - 1);
+                    self.store.persist_rollups(start_cp, end_cp).await?;
+                    self.metrics
+                        .latest_rollups_checkpoint_sequence_number
+                        .set(end_cp as i64);
+                    // Catch up without waiting, if there are more checkpoints to add.
+                    if end_cp < latest_cp {
+                        continue;
+                    }
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(self.interval_secs)).await;
+        }
+    }
+}
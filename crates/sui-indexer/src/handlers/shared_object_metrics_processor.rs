@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use tracing::info;
+
+use crate::handlers::move_call_metrics_processor::MILLIS_PER_DAY;
+use crate::types::IndexerResult;
+use crate::{metrics::IndexerMetrics, store::IndexerStore};
+
+const SHARED_OBJECT_METRICS_INTERVAL_SECS: u64 = 600;
+
+pub struct SharedObjectMetricsProcessor<S> {
+    pub store: S,
+    metrics: IndexerMetrics,
+    pub interval_secs: u64,
+}
+
+impl<S> SharedObjectMetricsProcessor<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, metrics: IndexerMetrics) -> SharedObjectMetricsProcessor<S> {
+        let interval_secs = std::env::var("SHARED_OBJECT_METRICS_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(SHARED_OBJECT_METRICS_INTERVAL_SECS);
+        Self {
+            store,
+            metrics,
+            interval_secs,
+        }
+    }
+
+    // The `shared_object_metrics` table holds, for each day, the number of transactions taking
+    // each shared object as input, how many of them took it mutably or failed, and the most
+    // transactions mutating it in a single checkpoint, aggregated from `tx_shared_objects` and
+    // `tx_senders`. Objects mutated by many transactions are sequenced one transaction at a time,
+    // and are the ones that add latency to the transactions using them. Days are aggregated like
+    // in `MoveCallMetricsProcessor`: once complete, resuming from the day after the latest one in
+    // the table.
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Starting shared object metrics processor...");
+        let mut next_day = self
+            .store
+            .get_latest_shared_object_metrics_day()
+            .await?
+            .map(|day| day + 1);
+
+        loop {
+            if let Some((first_ms, latest_ms)) =
+                self.store.get_checkpoint_timestamp_range_ms().await?
+            {
+                let start_day = *next_day.get_or_insert(first_ms / MILLIS_PER_DAY);
+                // The day of the latest checkpoint is still in progress.
+                let end_day = latest_ms / MILLIS_PER_DAY;
+                for day in start_day..end_day {
+                    self.store.persist_shared_object_metrics(day).await?;
+                    self.metrics
+                        .latest_shared_object_metrics_day
+                        .set(day as i64);
+                    next_day = Some(day + 1);
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(self.interval_secs)).await;
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::encoding::{Encoding, Hex};
+use tracing::info;
+
+use crate::types::IndexerResult;
+use crate::{metrics::IndexerMetrics, store::IndexerStore};
+
+const TX_ADDRESS_BUCKETS_BACKFILL_INTERVAL_SECS: u64 = 60;
+
+/// The max number of buckets to backfill for each address per round, so that the backfills of
+/// different addresses are interleaved.
+const MAX_BUCKETS_PER_ROUND: usize = 1_000;
+
+pub struct TxAddressBucketsBackfill<S> {
+    pub store: S,
+    metrics: IndexerMetrics,
+    pub interval_secs: u64,
+}
+
+impl<S> TxAddressBucketsBackfill<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, metrics: IndexerMetrics) -> TxAddressBucketsBackfill<S> {
+        let interval_secs = std::env::var("TX_ADDRESS_BUCKETS_BACKFILL_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(TX_ADDRESS_BUCKETS_BACKFILL_INTERVAL_SECS);
+        Self {
+            store,
+            metrics,
+            interval_secs,
+        }
+    }
+
+    // Once an address is registered in `hot_addresses`, the buckets of the transactions it sends
+    // or receives are counted in `tx_address_buckets` as they are committed. This task counts the
+    // buckets of the transactions committed before then, walking backwards from the address's
+    // latest transaction, one non-empty bucket at a time, until it reaches its earliest. Progress
+    // is recorded in `hot_addresses`, so backfills resume where they left off after a restart.
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Starting tx address buckets backfill...");
+        loop {
+            for hot_address in self.store.get_hot_addresses_to_backfill().await? {
+                let address = Hex::encode(&hot_address.address);
+                let mut below_bucket = hot_address.backfilled_from_bucket.map(|b| b as u64);
+                for _ in 0..MAX_BUCKETS_PER_ROUND {
+                    let backfilled = self
+                        .store
+                        .backfill_tx_address_bucket(hot_address.address.clone(), below_bucket)
+                        .await?;
+
+                    let Some(bucket) = backfilled else {
+                        info!("Finished backfilling tx address buckets for 0x{address}");
+                        break;
+                    };
+
+                    self.metrics.total_tx_address_buckets_backfilled.inc();
+                    below_bucket = Some(bucket);
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(self.interval_secs)).await;
+        }
+    }
+}
@@ -2,21 +2,33 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::env;
+use std::ops::RangeInclusive;
 
 use anyhow::Result;
 use prometheus::Registry;
-use tracing::info;
+use tracing::{info, warn};
 
 use mysten_metrics::spawn_monitored_task;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::build_json_rpc_server;
 use crate::errors::IndexerError;
 use crate::framework::fetcher::CheckpointFetcher;
+use crate::framework::source_pool::FullnodeSourcePool;
+use crate::handlers::cdc_publisher::CdcPublisher;
 use crate::handlers::checkpoint_handler::new_handlers;
+use crate::handlers::committer::CommitMode;
+use crate::handlers::epoch_consistency_checker::EpochConsistencyChecker;
+use crate::handlers::move_call_metrics_processor::MoveCallMetricsProcessor;
+use crate::handlers::objects_resolution_backfill::ObjectsResolutionBackfill;
 use crate::handlers::objects_snapshot_processor::{ObjectsSnapshotProcessor, SnapshotLagConfig};
+use crate::handlers::pruner::Pruner;
+use crate::handlers::rollups_processor::RollupsProcessor;
+use crate::handlers::shared_object_metrics_processor::SharedObjectMetricsProcessor;
+use crate::handlers::tx_address_buckets_backfill::TxAddressBucketsBackfill;
 use crate::indexer_reader::IndexerReader;
 use crate::metrics::IndexerMetrics;
-use crate::store::IndexerStore;
+use crate::store::{IndexerStore, PgIndexerStore};
 use crate::IndexerConfig;
 
 const DOWNLOAD_QUEUE_SIZE: usize = 200;
@@ -62,10 +74,13 @@ impl Indexer {
                     .with_label_values(&["checkpoint_tx_downloading"]),
             );
 
-        let rest_api_url = format!("{}/rest", config.rpc_client_url);
-        let rest_client = sui_rest_api::Client::new(&rest_api_url);
+        let sources = FullnodeSourcePool::new(
+            config.fullnode_urls(),
+            config.max_fullnode_lag,
+            metrics.clone(),
+        );
         let fetcher = CheckpointFetcher::new(
-            rest_client.clone(),
+            sources,
             last_seq_from_db,
             downloaded_checkpoint_data_sender,
             metrics.clone(),
@@ -79,7 +94,120 @@ impl Indexer {
         );
         spawn_monitored_task!(objects_snapshot_processor.start());
 
-        let checkpoint_handler = new_handlers(store, metrics.clone()).await?;
+        let move_call_metrics_processor =
+            MoveCallMetricsProcessor::new(store.clone(), metrics.clone());
+        spawn_monitored_task!(move_call_metrics_processor.start());
+
+        let shared_object_metrics_processor =
+            SharedObjectMetricsProcessor::new(store.clone(), metrics.clone());
+        spawn_monitored_task!(shared_object_metrics_processor.start());
+
+        let rollups_processor = RollupsProcessor::new(store.clone(), metrics.clone());
+        spawn_monitored_task!(rollups_processor.start());
+
+        let tx_address_buckets_backfill =
+            TxAddressBucketsBackfill::new(store.clone(), metrics.clone());
+        spawn_monitored_task!(tx_address_buckets_backfill.start());
+
+        if config.resolve_objects {
+            let objects_resolution_backfill =
+                ObjectsResolutionBackfill::new(store.clone(), metrics.clone());
+            spawn_monitored_task!(objects_resolution_backfill.start());
+        }
+
+        let epoch_consistency_checker = EpochConsistencyChecker::new(
+            store.clone(),
+            crate::get_http_client(config.rpc_client_url.as_str())?,
+            metrics.clone(),
+        );
+        spawn_monitored_task!(epoch_consistency_checker.start());
+
+        let retention_policy = config.retention_policy();
+        if retention_policy.is_enabled() {
+            let pruner = Pruner::new(store.clone(), metrics.clone(), retention_policy);
+            spawn_monitored_task!(pruner.start());
+        }
+
+        let cdc = CdcPublisher::from_config(config, metrics.clone()).await?;
+        let (checkpoint_handler, _commit_task) = new_handlers(
+            store,
+            metrics.clone(),
+            CommitMode::Live { cdc },
+            config.indexing_filter(),
+            config.resolve_objects,
+        )
+        .await?;
+        crate::framework::runner::run(
+            mysten_metrics::metered_channel::ReceiverStream::new(
+                downloaded_checkpoint_data_receiver,
+            ),
+            vec![Box::new(checkpoint_handler)],
+            metrics,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Re-ingests the checkpoints in `range`, to repair the data written for them, e.g. by a
+    /// past ingestion bug, without wiping the whole database. The range must already have been
+    /// indexed. Returns once every checkpoint in the range has been committed.
+    pub async fn start_resync(
+        config: &IndexerConfig,
+        store: PgIndexerStore,
+        metrics: IndexerMetrics,
+        range: RangeInclusive<CheckpointSequenceNumber>,
+    ) -> Result<(), IndexerError> {
+        info!(
+            "Sui Indexer Resync (version {:?}) started for checkpoints {:?}...",
+            env!("CARGO_PKG_VERSION"),
+            range,
+        );
+
+        let latest_seq_from_db = store.get_latest_tx_checkpoint_sequence_number().await?;
+        if latest_seq_from_db.map_or(true, |latest| latest < *range.end()) {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Cannot resync checkpoints {range:?}, only checkpoints up to \
+                 {latest_seq_from_db:?} have been indexed"
+            )));
+        }
+
+        let (downloaded_checkpoint_data_sender, downloaded_checkpoint_data_receiver) =
+            mysten_metrics::metered_channel::channel(
+                DOWNLOAD_QUEUE_SIZE,
+                &mysten_metrics::get_metrics()
+                    .unwrap()
+                    .channels
+                    .with_label_values(&["checkpoint_tx_downloading"]),
+            );
+
+        let sources = FullnodeSourcePool::new(
+            config.fullnode_urls(),
+            config.max_fullnode_lag,
+            metrics.clone(),
+        );
+        let fetcher = CheckpointFetcher::new(
+            sources,
+            range.start().checked_sub(1),
+            downloaded_checkpoint_data_sender,
+            metrics.clone(),
+        )
+        .with_end_checkpoint(*range.end());
+        spawn_monitored_task!(fetcher.run());
+
+        // Consumers of the change data capture stream follow its watermark, which must not go
+        // backwards, so resynced checkpoints are not published again.
+        if config.cdc_nats_url.is_some() {
+            warn!("Resynced checkpoints are not published to the change data capture stream");
+        }
+        let (checkpoint_handler, commit_task) = new_handlers(
+            store.for_resync(),
+            metrics.clone(),
+            CommitMode::Resync,
+            config.indexing_filter(),
+            config.resolve_objects,
+        )
+        .await?;
         crate::framework::runner::run(
             mysten_metrics::metered_channel::ReceiverStream::new(
                 downloaded_checkpoint_data_receiver,
@@ -89,6 +217,12 @@ impl Indexer {
         )
         .await;
 
+        // The runner drops the handler once the fetcher is done, which lets the commit task
+        // finish committing what is left of the range.
+        commit_task
+            .await
+            .map_err(|e| IndexerError::GenericError(format!("Resync commit task failed: {e}")))?;
+        info!("Finished resyncing checkpoints {:?}", range);
         Ok(())
     }
 
@@ -101,7 +235,7 @@ impl Indexer {
             "Sui Indexer Reader (version {:?}) started...",
             env!("CARGO_PKG_VERSION")
         );
-        let indexer_reader = IndexerReader::new(db_url)?;
+        let indexer_reader = IndexerReader::new(db_url)?.with_consistency(config.read_consistency);
         let handle = build_json_rpc_server(registry, indexer_reader, config, None)
             .await
             .expect("Json rpc server should not run into errors upon start.");
@@ -9,22 +9,32 @@ use crate::{
         display::StoredDisplay,
         epoch::StoredEpochInfo,
         events::StoredEvent,
-        objects::{CoinBalance, ObjectRefColumn, StoredObject},
+        move_call_metrics::StoredMoveCallMetrics,
+        objects::{
+            CoinBalance, ObjectRefColumn, ResolvedDisplay, StoredHistoryObject, StoredObject,
+        },
         packages::StoredPackage,
+        rollups::{StoredActiveAddressMetrics, StoredPackageTxMetrics, StoredSenderGasMetrics},
+        shared_object_metrics::StoredSharedObjectMetrics,
         transactions::StoredTransaction,
         tx_indices::TxSequenceNumber,
     },
     schema::{
-        checkpoints, display, epochs, events, objects, objects_snapshot, packages, transactions,
+        active_address_metrics, checkpoints, display, epochs, events, move_call_metrics, objects,
+        objects_history, objects_snapshot, package_tx_metrics, packages, sender_gas_metrics,
+        shared_object_metrics, transactions,
     },
-    types::{IndexerResult, OwnerType},
+    types::{IndexerResult, ObjectStatus, OwnerType},
 };
 use anyhow::{anyhow, Result};
 use cached::proc_macro::cached;
 use cached::SizedCache;
 use diesel::{
-    dsl::sql, r2d2::ConnectionManager, sql_types::Bool, ExpressionMethods, OptionalExtension,
-    PgConnection, QueryDsl, RunQueryDsl, TextExpressionMethods,
+    dsl::{max, sql},
+    r2d2::ConnectionManager,
+    sql_types::Bool,
+    ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl,
+    TextExpressionMethods,
 };
 use fastcrypto::encoding::Encoding;
 use fastcrypto::encoding::Hex;
@@ -37,12 +47,13 @@ use std::{
 };
 use sui_json_rpc_types::DisplayFieldsResponse;
 use sui_json_rpc_types::{
-    Balance, Coin as SuiCoin, SuiCoinMetadata, SuiTransactionBlockEffects,
-    SuiTransactionBlockEffectsAPI,
+    ActiveAddressMetrics, CheckpointId, EpochInfo, EventFilter, MoveFunctionMetrics,
+    PackageTxMetrics, SenderGasMetrics, SharedObjectMetrics, SuiEvent, SuiObjectDataFilter,
+    SuiTransactionBlockResponse, TransactionFilter, TransactionInclusionProof,
 };
 use sui_json_rpc_types::{
-    CheckpointId, EpochInfo, EventFilter, SuiEvent, SuiObjectDataFilter,
-    SuiTransactionBlockResponse, TransactionFilter,
+    Balance, Coin as SuiCoin, SuiCoinMetadata, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI,
 };
 use sui_types::{
     balance::Supply, coin::TreasuryCap, dynamic_field::DynamicFieldName, object::MoveObject,
@@ -63,10 +74,28 @@ pub const TX_SEQUENCE_NUMBER_STR: &str = "tx_sequence_number";
 pub const TRANSACTION_DIGEST_STR: &str = "transaction_digest";
 pub const EVENT_SEQUENCE_NUMBER_STR: &str = "event_sequence_number";
 
+/// Which data the point reads of an [`IndexerReader`] may observe.
+///
+/// The committer writes transactions, events and objects before the checkpoint they belong to, so
+/// while a checkpoint is being committed the store contains some, but not all, of its data. A
+/// `Latest` read may observe this partially committed checkpoint, e.g. an object mutated by a
+/// transaction that cannot be found yet. A `Checkpoint` read is bounded by the last checkpoint
+/// that has been fully committed, at the cost of lagging behind ingestion by up to one
+/// checkpoint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReadConsistency {
+    /// Read the latest ingested data. This is the default.
+    #[default]
+    Latest,
+    /// Read data as of the last fully committed checkpoint.
+    Checkpoint,
+}
+
 #[derive(Clone)]
 pub struct IndexerReader {
     pool: crate::db::PgConnectionPool,
     package_cache: PackageCache,
+    consistency: ReadConsistency,
 }
 
 // Impl for common initialization and utilities
@@ -97,10 +126,26 @@ impl IndexerReader {
         Ok(Self {
             pool,
             package_cache: Default::default(),
+            consistency: ReadConsistency::default(),
         })
     }
 
-    fn get_connection(&self) -> Result<PgPoolConnection, IndexerError> {
+    /// Returns a reader sharing this reader's connection pool and package cache, whose reads
+    /// observe data according to `consistency`.
+    pub fn with_consistency(&self, consistency: ReadConsistency) -> Self {
+        Self {
+            consistency,
+            ..self.clone()
+        }
+    }
+
+    pub fn consistency(&self) -> ReadConsistency {
+        self.consistency
+    }
+
+    /// Acquire a connection from the pool, failing if one does not become available within the
+    /// pool's connection timeout.
+    pub fn get_connection(&self) -> Result<PgPoolConnection, IndexerError> {
         self.pool.get().map_err(|e| {
             IndexerError::PgPoolConnectionError(format!(
                 "Failed to get connection from PG connection pool with error: {:?}",
@@ -203,29 +248,73 @@ fn blocking_call_is_ok_or_panic() {
 
 // Impl for reading data from the DB
 impl IndexerReader {
+    /// The sequence number of the last fully committed checkpoint that reads are bounded by, or
+    /// `None` if reads observe the latest data.
+    fn checkpoint_bound(&self) -> Result<Option<i64>, IndexerError> {
+        match self.consistency {
+            ReadConsistency::Latest => Ok(None),
+            ReadConsistency::Checkpoint => {
+                let bound = self.run_query(|conn| {
+                    checkpoints::table
+                        .select(max(checkpoints::sequence_number))
+                        .first::<Option<i64>>(conn)
+                })?;
+                // Nothing is visible before the first checkpoint has been committed.
+                Ok(Some(bound.unwrap_or(-1)))
+            }
+        }
+    }
+
     fn get_object_from_db(
         &self,
         object_id: &ObjectID,
         version: Option<VersionNumber>,
     ) -> Result<Option<StoredObject>, IndexerError> {
-        let object_id = object_id.to_vec();
+        let bound = self.checkpoint_bound()?;
+        let id = object_id.to_vec();
 
         let stored_object = self.run_query(|conn| {
+            let mut query = objects::dsl::objects
+                .filter(objects::dsl::object_id.eq(id))
+                .into_boxed();
             if let Some(version) = version {
-                objects::dsl::objects
-                    .filter(objects::dsl::object_id.eq(object_id))
-                    .filter(objects::dsl::object_version.eq(version.value() as i64))
-                    .first::<StoredObject>(conn)
-                    .optional()
-            } else {
-                objects::dsl::objects
-                    .filter(objects::dsl::object_id.eq(object_id))
-                    .first::<StoredObject>(conn)
-                    .optional()
+                query = query.filter(objects::dsl::object_version.eq(version.value() as i64));
             }
+            query.first::<StoredObject>(conn).optional()
         })?;
 
-        Ok(stored_object)
+        let Some(bound) = bound else {
+            return Ok(stored_object);
+        };
+        resolve_at_checkpoint(stored_object, bound, || {
+            self.get_history_object_from_db(object_id, version, bound)
+        })
+    }
+
+    /// Reads the latest version of the object (or the given `version`) that was committed at or
+    /// before the checkpoint `bound`, from the objects history table.
+    fn get_history_object_from_db(
+        &self,
+        object_id: &ObjectID,
+        version: Option<VersionNumber>,
+        bound: i64,
+    ) -> Result<Option<StoredHistoryObject>, IndexerError> {
+        let id = object_id.to_vec();
+
+        self.run_query(|conn| {
+            let mut query = objects_history::dsl::objects_history
+                .filter(objects_history::dsl::object_id.eq(id))
+                .filter(objects_history::dsl::checkpoint_sequence_number.le(bound))
+                .into_boxed();
+            if let Some(version) = version {
+                query =
+                    query.filter(objects_history::dsl::object_version.eq(version.value() as i64));
+            }
+            query
+                .order(objects_history::dsl::object_version.desc())
+                .first::<StoredHistoryObject>(conn)
+                .optional()
+        })
     }
 
     fn get_object(
@@ -258,16 +347,7 @@ impl IndexerReader {
     }
 
     fn get_object_read(&self, object_id: &ObjectID) -> Result<ObjectRead, IndexerError> {
-        let id = object_id.to_vec();
-
-        let stored_object = self.run_query(|conn| {
-            objects::dsl::objects
-                .filter(objects::dsl::object_id.eq(id))
-                .first::<StoredObject>(conn)
-                .optional()
-        })?;
-
-        if let Some(object) = stored_object {
+        if let Some(object) = self.get_object_from_db(object_id, None)? {
             object.try_into_object_read(self)
         } else {
             Ok(ObjectRead::NotExists(*object_id))
@@ -529,6 +609,256 @@ impl IndexerReader {
             .collect()
     }
 
+    pub fn get_transaction_inclusion_proof(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<Option<TransactionInclusionProof>, IndexerError> {
+        let checkpoint_sequence_number: Option<i64> = self.run_query(|conn| {
+            transactions::table
+                .select(transactions::checkpoint_sequence_number)
+                .filter(transactions::transaction_digest.eq(digest.inner().to_vec()))
+                .first::<i64>(conn)
+                .optional()
+        })?;
+        let Some(checkpoint_sequence_number) = checkpoint_sequence_number else {
+            return Ok(None);
+        };
+
+        let stored_checkpoint = self
+            .get_checkpoint_from_db(CheckpointId::SequenceNumber(
+                checkpoint_sequence_number as u64,
+            ))?
+            .ok_or_else(|| {
+                IndexerError::PersistentStorageDataCorruptionError(format!(
+                    "Checkpoint {} of transaction {} not found",
+                    checkpoint_sequence_number, digest
+                ))
+            })?;
+
+        stored_checkpoint.into_inclusion_proof(digest).map(Some)
+    }
+
+    pub fn get_move_function_metrics(
+        &self,
+        package: ObjectID,
+        module: Option<String>,
+        function: Option<String>,
+        limit: usize,
+    ) -> Result<Vec<MoveFunctionMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            let mut query = move_call_metrics::table
+                .filter(move_call_metrics::package.eq(package.to_vec()))
+                .into_boxed();
+            if let Some(module) = module {
+                query = query.filter(move_call_metrics::module.eq(module));
+            }
+            if let Some(function) = function {
+                query = query.filter(move_call_metrics::func.eq(function));
+            }
+            query
+                .order((
+                    move_call_metrics::day.desc(),
+                    move_call_metrics::call_count.desc(),
+                ))
+                .limit(limit as i64)
+                .load::<StoredMoveCallMetrics>(conn)
+        })?;
+        stored_metrics
+            .into_iter()
+            .map(MoveFunctionMetrics::try_from)
+            .collect()
+    }
+
+    /// Returns the most called functions on `day`, or on the latest day with metrics if `day` is
+    /// not provided.
+    pub fn get_hot_move_functions(
+        &self,
+        day: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<MoveFunctionMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            let day = match day {
+                Some(day) => day as i64,
+                None => match move_call_metrics::table
+                    .select(max(move_call_metrics::day))
+                    .first::<Option<i64>>(conn)?
+                {
+                    Some(day) => day,
+                    None => return Ok(vec![]),
+                },
+            };
+            move_call_metrics::table
+                .filter(move_call_metrics::day.eq(day))
+                .order(move_call_metrics::call_count.desc())
+                .limit(limit as i64)
+                .load::<StoredMoveCallMetrics>(conn)
+        })?;
+        stored_metrics
+            .into_iter()
+            .map(MoveFunctionMetrics::try_from)
+            .collect()
+    }
+
+    pub fn get_shared_object_metrics(
+        &self,
+        object_id: ObjectID,
+        limit: usize,
+    ) -> Result<Vec<SharedObjectMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            shared_object_metrics::table
+                .filter(shared_object_metrics::object_id.eq(object_id.to_vec()))
+                .order(shared_object_metrics::day.desc())
+                .limit(limit as i64)
+                .load::<StoredSharedObjectMetrics>(conn)
+        })?;
+        stored_metrics
+            .into_iter()
+            .map(SharedObjectMetrics::try_from)
+            .collect()
+    }
+
+    /// Returns the shared objects mutated by the most transactions on `day`, or on the latest day
+    /// with metrics if `day` is not provided.
+    pub fn get_hot_shared_objects(
+        &self,
+        day: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<SharedObjectMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            let day = match day {
+                Some(day) => day as i64,
+                None => match shared_object_metrics::table
+                    .select(max(shared_object_metrics::day))
+                    .first::<Option<i64>>(conn)?
+                {
+                    Some(day) => day,
+                    None => return Ok(vec![]),
+                },
+            };
+            shared_object_metrics::table
+                .filter(shared_object_metrics::day.eq(day))
+                .order(shared_object_metrics::mutating_tx_count.desc())
+                .limit(limit as i64)
+                .load::<StoredSharedObjectMetrics>(conn)
+        })?;
+        stored_metrics
+            .into_iter()
+            .map(SharedObjectMetrics::try_from)
+            .collect()
+    }
+
+    pub fn get_active_address_metrics(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<ActiveAddressMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            active_address_metrics::table
+                .order(active_address_metrics::day.desc())
+                .limit(limit as i64)
+                .load::<StoredActiveAddressMetrics>(conn)
+        })?;
+        Ok(stored_metrics
+            .into_iter()
+            .map(ActiveAddressMetrics::from)
+            .collect())
+    }
+
+    pub fn get_package_tx_metrics(
+        &self,
+        package: ObjectID,
+        limit: usize,
+    ) -> Result<Vec<PackageTxMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            package_tx_metrics::table
+                .filter(package_tx_metrics::package.eq(package.to_vec()))
+                .order(package_tx_metrics::day.desc())
+                .limit(limit as i64)
+                .load::<StoredPackageTxMetrics>(conn)
+        })?;
+        stored_metrics
+            .into_iter()
+            .map(PackageTxMetrics::try_from)
+            .collect()
+    }
+
+    /// Returns the packages called by the most transactions on `day`, or on the latest day with
+    /// metrics if `day` is not provided.
+    pub fn get_top_packages(
+        &self,
+        day: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<PackageTxMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            let day = match day {
+                Some(day) => day as i64,
+                None => match package_tx_metrics::table
+                    .select(max(package_tx_metrics::day))
+                    .first::<Option<i64>>(conn)?
+                {
+                    Some(day) => day,
+                    None => return Ok(vec![]),
+                },
+            };
+            package_tx_metrics::table
+                .filter(package_tx_metrics::day.eq(day))
+                .order(package_tx_metrics::tx_count.desc())
+                .limit(limit as i64)
+                .load::<StoredPackageTxMetrics>(conn)
+        })?;
+        stored_metrics
+            .into_iter()
+            .map(PackageTxMetrics::try_from)
+            .collect()
+    }
+
+    pub fn get_sender_gas_metrics(
+        &self,
+        sender: SuiAddress,
+        limit: usize,
+    ) -> Result<Vec<SenderGasMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            sender_gas_metrics::table
+                .filter(sender_gas_metrics::sender.eq(sender.to_vec()))
+                .order(sender_gas_metrics::day.desc())
+                .limit(limit as i64)
+                .load::<StoredSenderGasMetrics>(conn)
+        })?;
+        stored_metrics
+            .into_iter()
+            .map(SenderGasMetrics::try_from)
+            .collect()
+    }
+
+    /// Returns the senders whose transactions used the most gas on `day`, or on the latest day
+    /// with metrics if `day` is not provided.
+    pub fn get_top_gas_senders(
+        &self,
+        day: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<SenderGasMetrics>, IndexerError> {
+        let stored_metrics = self.run_query(|conn| {
+            let day = match day {
+                Some(day) => day as i64,
+                None => match sender_gas_metrics::table
+                    .select(max(sender_gas_metrics::day))
+                    .first::<Option<i64>>(conn)?
+                {
+                    Some(day) => day,
+                    None => return Ok(vec![]),
+                },
+            };
+            sender_gas_metrics::table
+                .filter(sender_gas_metrics::day.eq(day))
+                .order(sender_gas_metrics::total_gas_used.desc())
+                .limit(limit as i64)
+                .load::<StoredSenderGasMetrics>(conn)
+        })?;
+        stored_metrics
+            .into_iter()
+            .map(SenderGasMetrics::try_from)
+            .collect()
+    }
+
     fn get_transaction_effects_with_digest(
         &self,
         digest: TransactionDigest,
@@ -559,14 +889,19 @@ impl IndexerReader {
         &self,
         digests: &[TransactionDigest],
     ) -> Result<Vec<StoredTransaction>, IndexerError> {
+        let bound = self.checkpoint_bound()?;
         let digests = digests
             .iter()
             .map(|digest| digest.inner().to_vec())
             .collect::<Vec<_>>();
         self.run_query(|conn| {
-            transactions::table
+            let mut query = transactions::table
                 .filter(transactions::transaction_digest.eq_any(digests))
-                .load::<StoredTransaction>(conn)
+                .into_boxed();
+            if let Some(bound) = bound {
+                query = query.filter(transactions::checkpoint_sequence_number.le(bound));
+            }
+            query.load::<StoredTransaction>(conn)
         })
     }
 
@@ -731,13 +1066,32 @@ impl IndexerReader {
         &self,
         object_ids: Vec<ObjectID>,
     ) -> Result<Vec<StoredObject>, IndexerError> {
-        let object_ids = object_ids.into_iter().map(|id| id.to_vec()).collect_vec();
+        let bound = self.checkpoint_bound()?;
+        let ids = object_ids.iter().map(|id| id.to_vec()).collect_vec();
 
-        self.run_query(|conn| {
+        let stored_objects = self.run_query(|conn| {
             objects::dsl::objects
-                .filter(objects::object_id.eq_any(object_ids))
+                .filter(objects::object_id.eq_any(ids))
                 .load::<StoredObject>(conn)
-        })
+        })?;
+        let Some(bound) = bound else {
+            return Ok(stored_objects);
+        };
+
+        // Objects modified, deleted or wrapped after the bound are read back from their history,
+        // and objects created after it are dropped.
+        let mut stored_objects: HashMap<_, _> = stored_objects
+            .into_iter()
+            .map(|object| (object.object_id.clone(), object))
+            .collect();
+        let mut objects = Vec::with_capacity(stored_objects.len());
+        for object_id in object_ids.iter().unique() {
+            let stored_object = stored_objects.remove(object_id.as_ref());
+            objects.extend(resolve_at_checkpoint(stored_object, bound, || {
+                self.get_history_object_from_db(object_id, None, bound)
+            })?);
+        }
+        Ok(objects)
     }
 
     fn query_transaction_blocks_by_checkpoint_impl(
@@ -1505,31 +1859,43 @@ impl IndexerReader {
             .collect::<IndexerResult<Vec<_>>>()
     }
 
+    /// Renders the Display of `original_object`, unless `resolved` holds its Display rendered at
+    /// ingestion with the latest version of the Display of its type.
     pub(crate) async fn get_display_fields(
         &self,
         original_object: &sui_types::object::Object,
         original_layout: &Option<MoveStructLayout>,
+        resolved: Option<ResolvedDisplay>,
     ) -> Result<DisplayFieldsResponse, IndexerError> {
-        let (object_type, layout) = if let Some((object_type, layout)) =
-            sui_json_rpc::read_api::get_object_type_and_struct(original_object, original_layout)
-                .map_err(|e| IndexerError::GenericError(e.to_string()))?
-        {
-            (object_type, layout)
-        } else {
+        let Some(object_type) = original_object.type_() else {
             return Ok(DisplayFieldsResponse {
                 data: None,
                 error: None,
             });
         };
 
-        if let Some(display_object) = self.get_display_object_by_type(&object_type).await? {
-            return sui_json_rpc::read_api::get_rendered_fields(display_object.fields, &layout)
-                .map_err(|e| IndexerError::GenericError(e.to_string()));
+        let object_type: StructTag = object_type.clone().into();
+        let Some(display_object) = self.get_display_object_by_type(&object_type).await? else {
+            return Ok(DisplayFieldsResponse {
+                data: None,
+                error: None,
+            });
+        };
+
+        if let Some(fields) = resolved.and_then(|r| r.fields_at(display_object.version)) {
+            return Ok(fields);
         }
-        Ok(DisplayFieldsResponse {
-            data: None,
-            error: None,
-        })
+        let (_, layout) =
+            sui_json_rpc::read_api::get_object_type_and_struct(original_object, original_layout)
+                .map_err(|e| IndexerError::GenericError(e.to_string()))?
+                .ok_or_else(|| {
+                    IndexerError::GenericError(format!(
+                        "Object {} with a Display has no Move struct",
+                        original_object.id()
+                    ))
+                })?;
+        sui_json_rpc::read_api::get_rendered_fields(display_object.fields, &layout)
+            .map_err(|e| IndexerError::GenericError(e.to_string()))
     }
 
     pub async fn get_coin_metadata_in_blocking_task(
@@ -1728,3 +2094,100 @@ fn get_single_obj_id_from_package_publish(
         Ok(None)
     }
 }
+
+/// Resolves an object's row in the live objects table, `stored_object`, to the version of the
+/// object visible as of the checkpoint `bound`. Objects modified, deleted or wrapped after the
+/// bound (and so no longer in the live table as of then) are read back from their history with
+/// `read_history`. Returns `None` if the object did not exist, or was wrapped or deleted, as of
+/// the bound.
+fn resolve_at_checkpoint(
+    stored_object: Option<StoredObject>,
+    bound: i64,
+    read_history: impl FnOnce() -> Result<Option<StoredHistoryObject>, IndexerError>,
+) -> Result<Option<StoredObject>, IndexerError> {
+    if let Some(object) = stored_object {
+        if object.checkpoint_sequence_number <= bound {
+            return Ok(Some(object));
+        }
+    }
+
+    let Some(history_object) = read_history()? else {
+        return Ok(None);
+    };
+    match ObjectStatus::try_from(history_object.object_status)? {
+        ObjectStatus::Active => Ok(Some(history_object.try_into()?)),
+        ObjectStatus::WrappedOrDeleted => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::object::Object;
+
+    use super::*;
+    use crate::types::IndexedObject;
+
+    fn stored_object(checkpoint_sequence_number: u64) -> StoredObject {
+        let object = Object::new_gas_for_testing();
+        StoredObject::from(IndexedObject::from_object(
+            checkpoint_sequence_number,
+            object,
+            None,
+        ))
+    }
+
+    fn deleted_history_object(checkpoint_sequence_number: u64) -> StoredHistoryObject {
+        StoredHistoryObject {
+            object_status: ObjectStatus::WrappedOrDeleted as i16,
+            object_digest: None,
+            owner_type: None,
+            serialized_object: None,
+            ..StoredHistoryObject::from(stored_object(checkpoint_sequence_number))
+        }
+    }
+
+    #[test]
+    fn test_resolve_object_committed_before_bound() {
+        let object = stored_object(3);
+        let resolved = resolve_at_checkpoint(Some(object.clone()), 5, || {
+            panic!("History should not be read")
+        })
+        .unwrap();
+        assert_eq!(resolved.unwrap().object_version, object.object_version);
+    }
+
+    #[test]
+    fn test_resolve_object_modified_after_bound() {
+        let previous = stored_object(3);
+        let resolved = resolve_at_checkpoint(Some(stored_object(7)), 5, || {
+            Ok(Some(previous.clone().into()))
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(resolved.checkpoint_sequence_number, 3);
+    }
+
+    #[test]
+    fn test_resolve_object_created_after_bound() {
+        let resolved = resolve_at_checkpoint(Some(stored_object(7)), 5, || Ok(None)).unwrap();
+        assert!(resolved.is_none());
+    }
+
+    #[test]
+    fn test_resolve_object_deleted_after_bound() {
+        // An object deleted or wrapped after the bound is no longer in the live objects table,
+        // but its last version as of the bound is in its history.
+        let previous = stored_object(3);
+        let resolved = resolve_at_checkpoint(None, 5, || Ok(Some(previous.clone().into())))
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.checkpoint_sequence_number, 3);
+    }
+
+    #[test]
+    fn test_resolve_object_deleted_before_bound() {
+        let resolved =
+            resolve_at_checkpoint(None, 5, || Ok(Some(deleted_history_object(4)))).unwrap();
+        assert!(resolved.is_none());
+    }
+}
@@ -9,6 +9,8 @@ use sui_indexer::errors::IndexerError;
 use sui_indexer::indexer::Indexer;
 use sui_indexer::metrics::start_prometheus_server;
 use sui_indexer::metrics::IndexerMetrics;
+#[cfg(feature = "clickhouse")]
+use sui_indexer::store::ClickHouseIndexerStore;
 use sui_indexer::store::PgIndexerStore;
 use sui_indexer::IndexerConfig;
 
@@ -22,6 +24,24 @@ async fn main() -> Result<(), IndexerError> {
     let indexer_config = IndexerConfig::parse();
     info!("Parsed indexer config: {:#?}", indexer_config);
 
+    let (_registry_service, registry) = start_prometheus_server(
+        // NOTE: this parses the input host addr and port number for socket addr,
+        // so unwrap() is safe here.
+        format!(
+            "{}:{}",
+            indexer_config.client_metric_host, indexer_config.client_metric_port
+        )
+        .parse()
+        .unwrap(),
+        indexer_config.rpc_client_url.as_str(),
+    )?;
+    let indexer_metrics = IndexerMetrics::new(&registry);
+    mysten_metrics::init_metrics(&registry);
+
+    if let Some(clickhouse_url) = &indexer_config.clickhouse_url {
+        return start_clickhouse_writer(&indexer_config, clickhouse_url, indexer_metrics).await;
+    }
+
     let db_url = indexer_config.get_db_url().map_err(|e| {
         IndexerError::PgPoolConnectionError(format!(
             "Failed parsing database url with error {:?}",
@@ -53,20 +73,6 @@ async fn main() -> Result<(), IndexerError> {
         })?;
     }
 
-    let (_registry_service, registry) = start_prometheus_server(
-        // NOTE: this parses the input host addr and port number for socket addr,
-        // so unwrap() is safe here.
-        format!(
-            "{}:{}",
-            indexer_config.client_metric_host, indexer_config.client_metric_port
-        )
-        .parse()
-        .unwrap(),
-        indexer_config.rpc_client_url.as_str(),
-    )?;
-    let indexer_metrics = IndexerMetrics::new(&registry);
-    mysten_metrics::init_metrics(&registry);
-
     let report_cp = blocking_cp.clone();
     let report_metrics = indexer_metrics.clone();
     tokio::spawn(async move {
@@ -86,7 +92,13 @@ async fn main() -> Result<(), IndexerError> {
         }
     });
 
-    if indexer_config.fullnode_sync_worker {
+    let resync_range = indexer_config
+        .resync_range()
+        .map_err(|e| IndexerError::InvalidArgumentError(e.to_string()))?;
+    if let Some(range) = resync_range {
+        let store = PgIndexerStore::new(blocking_cp, indexer_metrics.clone());
+        return Indexer::start_resync(&indexer_config, store, indexer_metrics, range).await;
+    } else if indexer_config.fullnode_sync_worker {
         let store = PgIndexerStore::new(blocking_cp, indexer_metrics.clone());
         return Indexer::start_writer(&indexer_config, store, indexer_metrics).await;
     } else if indexer_config.rpc_server_worker {
@@ -94,3 +106,41 @@ async fn main() -> Result<(), IndexerError> {
     }
     Ok(())
 }
+
+/// Runs the writer against ClickHouse, without Postgres.
+#[cfg(feature = "clickhouse")]
+async fn start_clickhouse_writer(
+    indexer_config: &IndexerConfig,
+    clickhouse_url: &str,
+    indexer_metrics: IndexerMetrics,
+) -> Result<(), IndexerError> {
+    if indexer_config.rpc_server_worker
+        || indexer_config.reset_db
+        || indexer_config.resync_from.is_some()
+    {
+        return Err(IndexerError::InvalidArgumentError(
+            "--clickhouse-url only supports the writer (--fullnode-sync-worker), \
+             without --reset-db or --resync-from"
+                .to_string(),
+        ));
+    }
+    let store = ClickHouseIndexerStore::new(clickhouse_url, indexer_metrics.clone())
+        .await
+        .map_err(|e| {
+            error!("Failed connecting to ClickHouse with error {:?}", e);
+            e
+        })?;
+    Indexer::start_writer(indexer_config, store, indexer_metrics).await
+}
+
+#[cfg(not(feature = "clickhouse"))]
+async fn start_clickhouse_writer(
+    _indexer_config: &IndexerConfig,
+    _clickhouse_url: &str,
+    _indexer_metrics: IndexerMetrics,
+) -> Result<(), IndexerError> {
+    Err(IndexerError::NotSupportedError(
+        "--clickhouse-url requires the indexer to be built with the `clickhouse` feature"
+            .to_string(),
+    ))
+}
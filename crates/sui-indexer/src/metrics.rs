@@ -6,8 +6,9 @@ use std::net::SocketAddr;
 
 use axum::{extract::Extension, http::StatusCode, routing::get, Router};
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge,
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
 };
 use prometheus::{Registry, TextEncoder};
 use regex::Regex;
@@ -89,10 +90,34 @@ pub struct IndexerMetrics {
     pub total_transaction_chunk_committed: IntCounter,
     pub total_object_change_chunk_committed: IntCounter,
     pub total_epoch_committed: IntCounter,
+    pub total_quarantined_checkpoints: IntCounter,
+    pub total_epoch_consistency_discrepancies: IntCounterVec,
+    pub total_tx_address_buckets_backfilled: IntCounter,
+    pub total_objects_resolved_by_backfill: IntCounter,
+    pub total_objects_resolution_backfill_failures: IntCounter,
+    pub total_pruned_rows: IntCounterVec,
+    pub total_filtered_out_rows: IntCounterVec,
+    pub total_db_write_retries: IntCounter,
+    pub total_db_write_retry_budget_exhausted: IntCounter,
+    pub total_db_write_permanent_failures: IntCounterVec,
+    pub total_checkpoint_commit_errors: IntCounterVec,
+    pub total_cdc_checkpoints_published: IntCounter,
+    pub total_cdc_checkpoints_dead_lettered: IntCounter,
+    pub total_tx_checkpoint_resynced: IntCounter,
     pub latest_fullnode_checkpoint_sequence_number: IntGauge,
     pub latest_tx_checkpoint_sequence_number: IntGauge,
     pub latest_indexer_object_checkpoint_sequence_number: IntGauge,
     pub latest_object_snapshot_sequence_number: IntGauge,
+    pub latest_move_call_metrics_day: IntGauge,
+    pub latest_shared_object_metrics_day: IntGauge,
+    pub latest_rollups_checkpoint_sequence_number: IntGauge,
+    pub latest_epoch_consistency_check: IntGauge,
+    pub latest_cdc_published_checkpoint_sequence_number: IntGauge,
+    pub latest_resynced_checkpoint_sequence_number: IntGauge,
+    pub fullnode_source_latest_checkpoint: IntGaugeVec,
+    pub fullnode_source_active: IntGaugeVec,
+    pub fullnode_source_errors: IntCounterVec,
+    pub fullnode_source_switchovers: IntCounter,
     // checkpoint E2E latency is:
     // fullnode_download_latency + checkpoint_index_latency + db_commit_latency
     pub checkpoint_download_bytes_size: IntGauge,
@@ -127,7 +152,11 @@ pub struct IndexerMetrics {
     pub checkpoint_db_commit_latency_checkpoints: Histogram,
     pub checkpoint_db_commit_latency_epoch: Histogram,
     pub advance_epoch_latency: Histogram,
+    pub cdc_publish_latency: Histogram,
     pub update_object_snapshot_latency: Histogram,
+    pub update_move_call_metrics_latency: Histogram,
+    pub update_shared_object_metrics_latency: Histogram,
+    pub update_rollups_latency: Histogram,
     pub tokio_blocking_task_wait_latency: Histogram,
     // average latency of committing 1000 transactions.
     // 1000 is not necessarily the batch size, it's to roughly map average tx commit latency to [0.1, 1] seconds,
@@ -220,6 +249,97 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            total_quarantined_checkpoints: register_int_counter_with_registry!(
+                "total_quarantined_checkpoints",
+                "Total number of checkpoints quarantined after repeatedly failing to commit",
+                registry,
+            )
+            .unwrap(),
+            total_epoch_consistency_discrepancies: register_int_counter_vec_with_registry!(
+                "total_epoch_consistency_discrepancies",
+                "Total number of discrepancies between indexed epochs and the fullnode's system state, by kind",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            total_tx_address_buckets_backfilled: register_int_counter_with_registry!(
+                "total_tx_address_buckets_backfilled",
+                "Total number of buckets of hot addresses' transactions counted by the backfill",
+                registry,
+            )
+            .unwrap(),
+            total_objects_resolved_by_backfill: register_int_counter_with_registry!(
+                "total_objects_resolved_by_backfill",
+                "Total number of live objects whose dynamic field names or Displays were resolved \
+                 by the backfill",
+                registry,
+            )
+            .unwrap(),
+            total_objects_resolution_backfill_failures: register_int_counter_with_registry!(
+                "total_objects_resolution_backfill_failures",
+                "Total number of rounds of the objects resolution backfill given up after their DB \
+                 reads or writes kept failing",
+                registry,
+            )
+            .unwrap(),
+            total_pruned_rows: register_int_counter_vec_with_registry!(
+                "total_pruned_rows",
+                "Total number of rows pruned from each table (estimated for dropped partitions)",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+            total_filtered_out_rows: register_int_counter_vec_with_registry!(
+                "total_filtered_out_rows",
+                "Total number of rows of each table that were not indexed, as they are not relevant to the indexing filter",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+            total_db_write_retries: register_int_counter_with_registry!(
+                "total_db_write_retries",
+                "Total number of retries of DB write transactions that failed with a retryable error",
+                registry,
+            )
+            .unwrap(),
+            total_db_write_retry_budget_exhausted: register_int_counter_with_registry!(
+                "total_db_write_retry_budget_exhausted",
+                "Total number of DB write transactions that still failed after retrying for their whole retry budget",
+                registry,
+            )
+            .unwrap(),
+            total_db_write_permanent_failures: register_int_counter_vec_with_registry!(
+                "total_db_write_permanent_failures",
+                "Total number of DB write transactions that failed with an error that is not retried, per error kind",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            total_checkpoint_commit_errors: register_int_counter_vec_with_registry!(
+                "total_checkpoint_commit_errors",
+                "Total number of failed attempts to commit a checkpoint, per error kind",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            total_cdc_checkpoints_published: register_int_counter_with_registry!(
+                "total_cdc_checkpoints_published",
+                "Total number of checkpoints published to the change data capture stream",
+                registry,
+            )
+            .unwrap(),
+            total_cdc_checkpoints_dead_lettered: register_int_counter_with_registry!(
+                "total_cdc_checkpoints_dead_lettered",
+                "Total number of checkpoints that could not be published to the change data capture stream, and were dead-lettered instead",
+                registry,
+            )
+            .unwrap(),
+            total_tx_checkpoint_resynced: register_int_counter_with_registry!(
+                "total_checkpoint_resynced",
+                "Total number of already indexed checkpoints committed again by a resync",
+                registry,
+            )
+            .unwrap(),
             latest_fullnode_checkpoint_sequence_number: register_int_gauge_with_registry!(
                 "latest_fullnode_checkpoint_sequence_number",
                 "Latest checkpoint sequence number from the Full Node",
@@ -243,6 +363,59 @@ impl IndexerMetrics {
                 "Latest object snapshot sequence number from the Indexer",
                 registry,
             ).unwrap(),
+            latest_move_call_metrics_day: register_int_gauge_with_registry!(
+                "latest_move_call_metrics_day",
+                "Latest day, in days since the unix epoch, with move call metrics computed by the Indexer",
+                registry,
+            ).unwrap(),
+            latest_shared_object_metrics_day: register_int_gauge_with_registry!(
+                "latest_shared_object_metrics_day",
+                "Latest day, in days since the unix epoch, with shared object metrics computed by the Indexer",
+                registry,
+            ).unwrap(),
+            latest_rollups_checkpoint_sequence_number: register_int_gauge_with_registry!(
+                "latest_rollups_checkpoint_sequence_number",
+                "Latest checkpoint sequence number whose transactions are counted in the daily rollups of the Indexer",
+                registry,
+            ).unwrap(),
+            latest_epoch_consistency_check: register_int_gauge_with_registry!(
+                "latest_epoch_consistency_check",
+                "Latest epoch whose indexed data was checked against the fullnode's system state",
+                registry,
+            ).unwrap(),
+            latest_cdc_published_checkpoint_sequence_number: register_int_gauge_with_registry!(
+                "latest_cdc_published_checkpoint_sequence_number",
+                "Latest checkpoint sequence number published to the change data capture stream",
+                registry,
+            ).unwrap(),
+            latest_resynced_checkpoint_sequence_number: register_int_gauge_with_registry!(
+                "latest_resynced_checkpoint_sequence_number",
+                "Latest checkpoint sequence number committed again by a resync",
+                registry,
+            ).unwrap(),
+            fullnode_source_latest_checkpoint: register_int_gauge_vec_with_registry!(
+                "fullnode_source_latest_checkpoint",
+                "Latest checkpoint sequence number of each fullnode that checkpoints can be downloaded from",
+                &["source"],
+                registry,
+            ).unwrap(),
+            fullnode_source_active: register_int_gauge_vec_with_registry!(
+                "fullnode_source_active",
+                "Whether checkpoints are being downloaded from each fullnode",
+                &["source"],
+                registry,
+            ).unwrap(),
+            fullnode_source_errors: register_int_counter_vec_with_registry!(
+                "fullnode_source_errors",
+                "Number of failed requests to each fullnode that checkpoints can be downloaded from",
+                &["source"],
+                registry,
+            ).unwrap(),
+            fullnode_source_switchovers: register_int_counter_with_registry!(
+                "fullnode_source_switchovers",
+                "Number of times checkpoint download switched over to another fullnode",
+                registry,
+            ).unwrap(),
             checkpoint_download_bytes_size: register_int_gauge_with_registry!(
                 "checkpoint_download_bytes_size",
                 "Size of the downloaded checkpoint in bytes",
@@ -459,12 +632,36 @@ impl IndexerMetrics {
                 LATENCY_SEC_BUCKETS.to_vec(),
                 registry,
             ).unwrap(),
+            cdc_publish_latency: register_histogram_with_registry!(
+                "cdc_publish_latency",
+                "Time spent publishing a batch of checkpoints to the change data capture stream, until it is acknowledged",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
             update_object_snapshot_latency: register_histogram_with_registry!(
                 "update_object_snapshot_latency",
                 "Time spent in updating object snapshot",
                 LATENCY_SEC_BUCKETS.to_vec(),
                 registry,
             ).unwrap(),
+            update_move_call_metrics_latency: register_histogram_with_registry!(
+                "update_move_call_metrics_latency",
+                "Time spent in updating move call metrics",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
+            update_shared_object_metrics_latency: register_histogram_with_registry!(
+                "update_shared_object_metrics_latency",
+                "Time spent in updating shared object metrics",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
+            update_rollups_latency: register_histogram_with_registry!(
+                "update_rollups_latency",
+                "Time spent in updating the daily rollups",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
             tokio_blocking_task_wait_latency: register_histogram_with_registry!(
                 "tokio_blocking_task_wait_latency",
                 "Time spent to wait for tokio blocking task pool",
@@ -4,9 +4,11 @@
 use diesel::prelude::*;
 
 use sui_json_rpc_types::Checkpoint as RpcCheckpoint;
+use sui_json_rpc_types::TransactionInclusionProof;
 use sui_types::base_types::TransactionDigest;
 use sui_types::digests::CheckpointDigest;
 use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::CheckpointContents;
 
 use crate::errors::IndexerError;
 use crate::schema::checkpoints;
@@ -31,6 +33,8 @@ pub struct StoredCheckpoint {
     pub checkpoint_commitments: Vec<u8>,
     pub validator_signature: Vec<u8>,
     pub end_of_epoch_data: Option<Vec<u8>>,
+    pub checkpoint_summary: Option<Vec<u8>>,
+    pub checkpoint_contents: Option<Vec<u8>>,
 }
 
 impl From<&IndexedCheckpoint> for StoredCheckpoint {
@@ -62,10 +66,73 @@ impl From<&IndexedCheckpoint> for StoredCheckpoint {
                 .as_ref()
                 .map(|d| bcs::to_bytes(d).unwrap()),
             end_of_epoch: c.end_of_epoch_data.is_some(),
+            checkpoint_summary: Some(bcs::to_bytes(&c.checkpoint_summary).unwrap()),
+            checkpoint_contents: Some(bcs::to_bytes(&c.checkpoint_contents).unwrap()),
         }
     }
 }
 
+impl StoredCheckpoint {
+    /// Builds the proof that the transaction with `digest` is included in this checkpoint.
+    pub fn into_inclusion_proof(
+        self,
+        digest: TransactionDigest,
+    ) -> Result<TransactionInclusionProof, IndexerError> {
+        let (Some(checkpoint_summary), Some(checkpoint_contents)) =
+            (self.checkpoint_summary, self.checkpoint_contents)
+        else {
+            return Err(IndexerError::NotSupportedError(format!(
+                "Checkpoint {} was indexed without the data needed for inclusion proofs",
+                self.sequence_number
+            )));
+        };
+
+        let checkpoint_digest = CheckpointDigest::try_from(self.checkpoint_digest.clone())
+            .map_err(|e| {
+                IndexerError::PersistentStorageDataCorruptionError(format!(
+                    "Failed to decode checkpoint digest: {:?} with err: {:?}",
+                    self.checkpoint_digest, e
+                ))
+            })?;
+
+        let contents: CheckpointContents = bcs::from_bytes(&checkpoint_contents).map_err(|e| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Failed to decode checkpoint contents of checkpoint {} with err: {:?}",
+                self.sequence_number, e
+            ))
+        })?;
+
+        let (transaction_index, execution_digests) = contents
+            .iter()
+            .enumerate()
+            .find(|(_, execution_digests)| execution_digests.transaction == digest)
+            .ok_or_else(|| {
+                IndexerError::PersistentStorageDataCorruptionError(format!(
+                    "Transaction {} is not in the contents of checkpoint {}",
+                    digest, self.sequence_number
+                ))
+            })?;
+
+        let validator_signature = bcs::from_bytes(&self.validator_signature).map_err(|e| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Failed to decode validator signature: {:?} with err: {:?}",
+                self.validator_signature, e
+            ))
+        })?;
+
+        Ok(TransactionInclusionProof {
+            transaction_digest: digest,
+            effects_digest: execution_digests.effects,
+            checkpoint: self.sequence_number as u64,
+            checkpoint_digest,
+            checkpoint_summary,
+            checkpoint_contents,
+            transaction_index: transaction_index as u64,
+            validator_signature,
+        })
+    }
+}
+
 impl TryFrom<StoredCheckpoint> for RpcCheckpoint {
     type Error = IndexerError;
     fn try_from(checkpoint: StoredCheckpoint) -> Result<RpcCheckpoint, IndexerError> {
@@ -153,3 +220,56 @@ impl TryFrom<StoredCheckpoint> for RpcCheckpoint {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::ExecutionDigests;
+    use sui_types::crypto::AggregateAuthoritySignature;
+
+    fn stored_checkpoint(contents: &CheckpointContents) -> StoredCheckpoint {
+        StoredCheckpoint {
+            sequence_number: 7,
+            checkpoint_digest: CheckpointDigest::random().into_inner().to_vec(),
+            validator_signature: bcs::to_bytes(&AggregateAuthoritySignature::default()).unwrap(),
+            checkpoint_summary: Some(vec![1, 2, 3]),
+            checkpoint_contents: Some(bcs::to_bytes(contents).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof() {
+        let digests = vec![ExecutionDigests::random(), ExecutionDigests::random()];
+        let contents = CheckpointContents::new_with_digests_only_for_tests(digests.clone());
+
+        let proof = stored_checkpoint(&contents)
+            .into_inclusion_proof(digests[1].transaction)
+            .unwrap();
+        assert_eq!(proof.checkpoint, 7);
+        assert_eq!(proof.transaction_index, 1);
+        assert_eq!(proof.effects_digest, digests[1].effects);
+        assert_eq!(proof.checkpoint_summary, vec![1, 2, 3]);
+
+        let decoded: CheckpointContents = bcs::from_bytes(&proof.checkpoint_contents).unwrap();
+        assert_eq!(decoded.digest(), contents.digest());
+    }
+
+    #[test]
+    fn test_inclusion_proof_errors() {
+        let contents =
+            CheckpointContents::new_with_digests_only_for_tests(vec![ExecutionDigests::random()]);
+
+        let missing_tx =
+            stored_checkpoint(&contents).into_inclusion_proof(TransactionDigest::random());
+        assert!(missing_tx.is_err());
+
+        let mut stored = stored_checkpoint(&contents);
+        stored.checkpoint_contents = None;
+        let not_indexed = stored.into_inclusion_proof(contents.iter().next().unwrap().transaction);
+        assert!(matches!(
+            not_indexed,
+            Err(IndexerError::NotSupportedError(_))
+        ));
+    }
+}
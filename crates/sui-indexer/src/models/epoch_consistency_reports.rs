@@ -0,0 +1,42 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::schema::epoch_consistency_reports;
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = epoch_consistency_reports)]
+pub struct StoredEpochConsistencyReport {
+    pub epoch: i64,
+    pub discrepancies: i64,
+    pub report: String,
+    pub checked_at_ms: i64,
+}
+
+/// A value of an epoch that differs between the indexed data and the system state of the
+/// fullnode.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochDiscrepancy {
+    /// The kind of value that differs, e.g. `total_stake` or `validator_stake`.
+    pub kind: &'static str,
+    /// The address of the validator whose value differs, for validator discrepancies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validator: Option<String>,
+    pub indexed: String,
+    pub fullnode: String,
+}
+
+impl StoredEpochConsistencyReport {
+    pub fn new(epoch: u64, discrepancies: &[EpochDiscrepancy], checked_at_ms: u64) -> Self {
+        Self {
+            epoch: epoch as i64,
+            discrepancies: discrepancies.len() as i64,
+            // Serializing strings cannot fail.
+            report: serde_json::to_string(discrepancies).unwrap_or_default(),
+            checked_at_ms: checked_at_ms as i64,
+        }
+    }
+}
@@ -4,8 +4,16 @@
 pub mod checkpoints;
 pub mod display;
 pub mod epoch;
+pub mod epoch_consistency_reports;
 pub mod events;
+pub mod move_call_metrics;
 pub mod objects;
 pub mod packages;
+pub mod persisted_queries;
+pub mod quarantined_checkpoints;
+pub mod rollups;
+pub mod shared_object_metrics;
 pub mod transactions;
+pub mod tx_address_buckets;
 pub mod tx_indices;
+pub mod usage_reports;
@@ -0,0 +1,90 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use diesel::prelude::*;
+use move_core_types::identifier::Identifier;
+
+use sui_json_rpc_types::{MoveFunctionMetrics, MoveFunctionName};
+use sui_types::base_types::ObjectID;
+
+use crate::errors::IndexerError;
+use crate::schema::move_call_metrics;
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = move_call_metrics)]
+pub struct StoredMoveCallMetrics {
+    pub day: i64,
+    pub package: Vec<u8>,
+    pub module: String,
+    pub func: String,
+    pub call_count: i64,
+    pub distinct_senders: i64,
+    pub total_gas_used: i64,
+}
+
+impl TryFrom<StoredMoveCallMetrics> for MoveFunctionMetrics {
+    type Error = IndexerError;
+
+    fn try_from(metrics: StoredMoveCallMetrics) -> Result<Self, Self::Error> {
+        let package = ObjectID::from_bytes(&metrics.package).map_err(|_| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Can't convert {:?} to package id",
+                metrics.package
+            ))
+        })?;
+        let identifier = |name: &str| {
+            Identifier::from_str(name).map_err(|e| {
+                IndexerError::PersistentStorageDataCorruptionError(format!(
+                    "Invalid identifier {name} in move call metrics: {e}"
+                ))
+            })
+        };
+        Ok(MoveFunctionMetrics {
+            function: MoveFunctionName {
+                package,
+                module: identifier(&metrics.module)?,
+                function: identifier(&metrics.func)?,
+            },
+            day: metrics.day as u64,
+            call_count: metrics.call_count as u64,
+            distinct_senders: metrics.distinct_senders as u64,
+            total_gas_used: metrics.total_gas_used,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_move_function_metrics() {
+        let package = ObjectID::from_single_byte(2);
+        let stored = StoredMoveCallMetrics {
+            day: 19_700,
+            package: package.to_vec(),
+            module: "coin".to_string(),
+            func: "split".to_string(),
+            call_count: 42,
+            distinct_senders: 7,
+            total_gas_used: -1_000,
+        };
+
+        let metrics = MoveFunctionMetrics::try_from(stored.clone()).unwrap();
+        assert_eq!(metrics.function.package, package);
+        assert_eq!(metrics.function.module.as_str(), "coin");
+        assert_eq!(metrics.function.function.as_str(), "split");
+        assert_eq!(metrics.day, 19_700);
+        assert_eq!(metrics.call_count, 42);
+        assert_eq!(metrics.distinct_senders, 7);
+        assert_eq!(metrics.total_gas_used, -1_000);
+
+        let invalid = StoredMoveCallMetrics {
+            func: "not an identifier".to_string(),
+            ..stored
+        };
+        assert!(MoveFunctionMetrics::try_from(invalid).is_err());
+    }
+}
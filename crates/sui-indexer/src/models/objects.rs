@@ -7,7 +7,7 @@ use sui_json_rpc::coin_api::parse_to_struct_tag;
 
 use diesel::prelude::*;
 use move_bytecode_utils::module_cache::GetModule;
-use sui_json_rpc_types::{Balance, Coin as SuiCoin};
+use sui_json_rpc_types::{Balance, Coin as SuiCoin, DisplayFieldsResponse};
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber};
 use sui_types::digests::ObjectDigest;
 use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName, DynamicFieldType, Field};
@@ -58,6 +58,12 @@ pub struct StoredObject {
     pub df_name: Option<Vec<u8>>,
     pub df_object_type: Option<String>,
     pub df_object_id: Option<Vec<u8>>,
+    /// The BCS bytes of the name of a dynamic field, if resolved at ingestion.
+    pub df_bcs_name: Option<Vec<u8>>,
+    /// The version of the Display the object was rendered with, if rendered at ingestion.
+    pub display_version: Option<i16>,
+    /// The rendered Display of the object, as JSON.
+    pub display_fields: Option<String>,
 }
 
 #[derive(Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName)]
@@ -102,6 +108,52 @@ impl From<StoredObject> for StoredHistoryObject {
     }
 }
 
+/// Converts an active version of an object from the history table back into its row in the
+/// live objects table. Fails for wrapped or deleted versions, which have no object contents.
+impl TryFrom<StoredHistoryObject> for StoredObject {
+    type Error = IndexerError;
+
+    fn try_from(o: StoredHistoryObject) -> Result<Self, Self::Error> {
+        let missing = |column: &str| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "History object {:?} at version {} has no {column}",
+                o.object_id, o.object_version
+            ))
+        };
+        if let ObjectStatus::WrappedOrDeleted = ObjectStatus::try_from(o.object_status)? {
+            return Err(IndexerError::PersistentStorageDataCorruptionError(format!(
+                "History object {:?} at version {} is wrapped or deleted",
+                o.object_id, o.object_version
+            )));
+        }
+        let object_digest = o.object_digest.clone().ok_or_else(|| missing("digest"))?;
+        let owner_type = o.owner_type.ok_or_else(|| missing("owner type"))?;
+        let serialized_object = o
+            .serialized_object
+            .clone()
+            .ok_or_else(|| missing("serialized object"))?;
+        Ok(Self {
+            object_id: o.object_id,
+            object_version: o.object_version,
+            object_digest,
+            checkpoint_sequence_number: o.checkpoint_sequence_number,
+            owner_type,
+            owner_id: o.owner_id,
+            object_type: o.object_type,
+            serialized_object,
+            coin_type: o.coin_type,
+            coin_balance: o.coin_balance,
+            df_kind: o.df_kind,
+            df_name: o.df_name,
+            df_object_type: o.df_object_type,
+            df_object_id: o.df_object_id,
+            df_bcs_name: None,
+            display_version: None,
+            display_fields: None,
+        })
+    }
+}
+
 #[derive(Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName)]
 #[diesel(table_name = objects, primary_key(object_id))]
 pub struct StoredDeletedObject {
@@ -163,6 +215,12 @@ impl From<IndexedObject> for StoredObject {
             df_name: o.df_info.as_ref().map(|n| bcs::to_bytes(&n.name).unwrap()),
             df_object_type: o.df_info.as_ref().map(|v| v.object_type.clone()),
             df_object_id: o.df_info.as_ref().map(|v| v.object_id.to_vec()),
+            df_bcs_name: o.df_info.as_ref().map(|v| v.bcs_name.clone()),
+            display_version: o.display.as_ref().map(|d| d.version as i16),
+            display_fields: o
+                .display
+                .as_ref()
+                .map(|d| serde_json::to_string(&d.fields).unwrap()),
         }
     }
 }
@@ -262,12 +320,19 @@ impl StoredObject {
                 object_id
             )));
         };
-        let layout = move_bytecode_utils::layout::TypeLayoutBuilder::build_with_types(
-            &name.type_,
-            module_cache,
-        )?;
-        let sui_json_value = sui_json::SuiJsonValue::new(name.value.clone())?;
-        let bcs_name = sui_json_value.to_bcs_bytes(&layout)?;
+        // Objects indexed before the BCS name was resolved at ingestion, and not backfilled yet,
+        // fall back to resolving the layout of the name.
+        let bcs_name = match self.df_bcs_name {
+            Some(bcs_name) => bcs_name,
+            None => {
+                let layout = move_bytecode_utils::layout::TypeLayoutBuilder::build_with_types(
+                    &name.type_,
+                    module_cache,
+                )?;
+                let sui_json_value = sui_json::SuiJsonValue::new(name.value.clone())?;
+                sui_json_value.to_bcs_bytes(&layout)?
+            }
+        };
         let object_type =
             self.df_object_type
                 .ok_or(IndexerError::PersistentStorageDataCorruptionError(format!(
@@ -285,6 +350,14 @@ impl StoredObject {
         }))
     }
 
+    /// The Display of the object, if it was rendered at ingestion.
+    pub fn resolved_display(&self) -> Option<ResolvedDisplay> {
+        Some(ResolvedDisplay {
+            version: self.display_version?,
+            fields: self.display_fields.clone()?,
+        })
+    }
+
     pub fn get_object_ref(&self) -> Result<ObjectRef, IndexerError> {
         let object_id = ObjectID::from_bytes(self.object_id.clone()).map_err(|_| {
             IndexerError::SerdeError(format!("Can't convert {:?} to object_id", self.object_id))
@@ -321,6 +394,25 @@ impl StoredObject {
     }
 }
 
+/// The Display of an object rendered at ingestion, with the version of the Display of its type it
+/// was rendered with.
+#[derive(Clone, Debug)]
+pub struct ResolvedDisplay {
+    pub version: i16,
+    pub fields: String,
+}
+
+impl ResolvedDisplay {
+    /// The rendered fields, if they were rendered with the Display of the object's type at
+    /// `version`, which is the latest one. Displays rendered with earlier versions are stale.
+    pub fn fields_at(&self, version: u16) -> Option<DisplayFieldsResponse> {
+        if self.version != version as i16 {
+            return None;
+        }
+        serde_json::from_str(&self.fields).ok()
+    }
+}
+
 impl TryFrom<StoredObject> for SuiCoin {
     type Error = IndexerError;
 
@@ -0,0 +1,14 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::persisted_queries;
+
+/// A query registered to be served by the GraphQL service when it only allows persisted queries.
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = persisted_queries)]
+pub struct StoredPersistedQuery {
+    pub query_hash: String,
+    pub query: Option<String>,
+}
@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use serde::Serialize;
+
+use sui_types::base_types::TransactionDigest;
+
+use crate::handlers::CheckpointDataToCommit;
+use crate::schema::quarantined_checkpoints;
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = quarantined_checkpoints)]
+pub struct StoredQuarantinedCheckpoint {
+    pub checkpoint_sequence_number: i64,
+    pub epoch: i64,
+    pub checkpoint_digest: Vec<u8>,
+    pub error: String,
+    pub attempts: i64,
+    pub context: String,
+    pub quarantined_at_ms: i64,
+}
+
+/// Describes the data of a quarantined checkpoint, so that the failure can be investigated and
+/// the checkpoint re-indexed once the cause is fixed.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QuarantineContext<'a> {
+    timestamp_ms: u64,
+    network_total_transactions: u64,
+    tx_digests: &'a [TransactionDigest],
+    events: usize,
+    tx_indices: usize,
+    display_updates: usize,
+    changed_objects: usize,
+    deleted_objects: usize,
+    packages: usize,
+}
+
+impl StoredQuarantinedCheckpoint {
+    /// Describes the checkpoint of `data`, before it is known whether it will be quarantined.
+    pub fn new(data: &CheckpointDataToCommit) -> Self {
+        let checkpoint = &data.checkpoint;
+        let context = QuarantineContext {
+            timestamp_ms: checkpoint.timestamp_ms,
+            network_total_transactions: checkpoint.network_total_transactions,
+            tx_digests: &checkpoint.tx_digests,
+            events: data.events.len(),
+            tx_indices: data.tx_indices.len(),
+            display_updates: data.display_updates.len(),
+            changed_objects: data.object_changes.changed_objects.len(),
+            deleted_objects: data.object_changes.deleted_objects.len(),
+            packages: data.packages.len(),
+        };
+        Self {
+            checkpoint_sequence_number: checkpoint.sequence_number as i64,
+            epoch: checkpoint.epoch as i64,
+            checkpoint_digest: checkpoint.checkpoint_digest.into_inner().to_vec(),
+            // Serializing digests and counters cannot fail.
+            context: serde_json::to_string(&context).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Records the failure that got the checkpoint quarantined.
+    pub fn failed(self, error: String, attempts: usize, quarantined_at_ms: u64) -> Self {
+        Self {
+            error,
+            attempts: attempts as i64,
+            quarantined_at_ms: quarantined_at_ms as i64,
+            ..self
+        }
+    }
+}
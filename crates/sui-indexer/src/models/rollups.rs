@@ -0,0 +1,129 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use sui_json_rpc_types::{ActiveAddressMetrics, PackageTxMetrics, SenderGasMetrics};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::errors::IndexerError;
+use crate::schema::{active_address_metrics, package_tx_metrics, sender_gas_metrics};
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = active_address_metrics)]
+pub struct StoredActiveAddressMetrics {
+    pub day: i64,
+    pub active_addresses: i64,
+}
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = package_tx_metrics)]
+pub struct StoredPackageTxMetrics {
+    pub day: i64,
+    pub package: Vec<u8>,
+    pub tx_count: i64,
+}
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = sender_gas_metrics)]
+pub struct StoredSenderGasMetrics {
+    pub day: i64,
+    pub sender: Vec<u8>,
+    pub tx_count: i64,
+    pub total_gas_used: i64,
+}
+
+impl From<StoredActiveAddressMetrics> for ActiveAddressMetrics {
+    fn from(metrics: StoredActiveAddressMetrics) -> Self {
+        ActiveAddressMetrics {
+            day: metrics.day as u64,
+            active_addresses: metrics.active_addresses as u64,
+        }
+    }
+}
+
+impl TryFrom<StoredPackageTxMetrics> for PackageTxMetrics {
+    type Error = IndexerError;
+
+    fn try_from(metrics: StoredPackageTxMetrics) -> Result<Self, Self::Error> {
+        let package = ObjectID::from_bytes(&metrics.package).map_err(|_| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Can't convert {:?} to package id",
+                metrics.package
+            ))
+        })?;
+        Ok(PackageTxMetrics {
+            package,
+            day: metrics.day as u64,
+            tx_count: metrics.tx_count as u64,
+        })
+    }
+}
+
+impl TryFrom<StoredSenderGasMetrics> for SenderGasMetrics {
+    type Error = IndexerError;
+
+    fn try_from(metrics: StoredSenderGasMetrics) -> Result<Self, Self::Error> {
+        let sender = SuiAddress::from_bytes(&metrics.sender).map_err(|_| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Can't convert {:?} to address",
+                metrics.sender
+            ))
+        })?;
+        Ok(SenderGasMetrics {
+            sender,
+            day: metrics.day as u64,
+            tx_count: metrics.tx_count as u64,
+            total_gas_used: metrics.total_gas_used,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_package_tx_metrics() {
+        let package = ObjectID::from_single_byte(2);
+        let stored = StoredPackageTxMetrics {
+            day: 19_700,
+            package: package.to_vec(),
+            tx_count: 42,
+        };
+
+        let metrics = PackageTxMetrics::try_from(stored.clone()).unwrap();
+        assert_eq!(metrics.package, package);
+        assert_eq!(metrics.day, 19_700);
+        assert_eq!(metrics.tx_count, 42);
+
+        let invalid = StoredPackageTxMetrics {
+            package: vec![0; 3],
+            ..stored
+        };
+        assert!(PackageTxMetrics::try_from(invalid).is_err());
+    }
+
+    #[test]
+    fn test_into_sender_gas_metrics() {
+        let sender = SuiAddress::random_for_testing_only();
+        let stored = StoredSenderGasMetrics {
+            day: 19_700,
+            sender: sender.to_vec(),
+            tx_count: 7,
+            total_gas_used: -1_000,
+        };
+
+        let metrics = SenderGasMetrics::try_from(stored.clone()).unwrap();
+        assert_eq!(metrics.sender, sender);
+        assert_eq!(metrics.day, 19_700);
+        assert_eq!(metrics.tx_count, 7);
+        assert_eq!(metrics.total_gas_used, -1_000);
+
+        let invalid = StoredSenderGasMetrics {
+            sender: vec![0; 3],
+            ..stored
+        };
+        assert!(SenderGasMetrics::try_from(invalid).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use sui_json_rpc_types::SharedObjectMetrics;
+use sui_types::base_types::ObjectID;
+
+use crate::errors::IndexerError;
+use crate::schema::shared_object_metrics;
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = shared_object_metrics)]
+pub struct StoredSharedObjectMetrics {
+    pub day: i64,
+    pub object_id: Vec<u8>,
+    pub tx_count: i64,
+    pub mutating_tx_count: i64,
+    pub failed_tx_count: i64,
+    pub distinct_senders: i64,
+    pub max_checkpoint_mutations: i64,
+}
+
+impl TryFrom<StoredSharedObjectMetrics> for SharedObjectMetrics {
+    type Error = IndexerError;
+
+    fn try_from(metrics: StoredSharedObjectMetrics) -> Result<Self, Self::Error> {
+        let object_id = ObjectID::from_bytes(&metrics.object_id).map_err(|_| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Can't convert {:?} to object id",
+                metrics.object_id
+            ))
+        })?;
+        Ok(SharedObjectMetrics {
+            object_id,
+            day: metrics.day as u64,
+            tx_count: metrics.tx_count as u64,
+            mutating_tx_count: metrics.mutating_tx_count as u64,
+            failed_tx_count: metrics.failed_tx_count as u64,
+            distinct_senders: metrics.distinct_senders as u64,
+            max_checkpoint_mutations: metrics.max_checkpoint_mutations as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_shared_object_metrics() {
+        let object_id = ObjectID::from_single_byte(6);
+        let stored = StoredSharedObjectMetrics {
+            day: 19_700,
+            object_id: object_id.to_vec(),
+            tx_count: 42,
+            mutating_tx_count: 40,
+            failed_tx_count: 3,
+            distinct_senders: 7,
+            max_checkpoint_mutations: 5,
+        };
+
+        let metrics = SharedObjectMetrics::try_from(stored.clone()).unwrap();
+        assert_eq!(metrics.object_id, object_id);
+        assert_eq!(metrics.day, 19_700);
+        assert_eq!(metrics.tx_count, 42);
+        assert_eq!(metrics.mutating_tx_count, 40);
+        assert_eq!(metrics.failed_tx_count, 3);
+        assert_eq!(metrics.distinct_senders, 7);
+        assert_eq!(metrics.max_checkpoint_mutations, 5);
+
+        let invalid = StoredSharedObjectMetrics {
+            object_id: vec![0; 3],
+            ..stored
+        };
+        assert!(SharedObjectMetrics::try_from(invalid).is_err());
+    }
+}
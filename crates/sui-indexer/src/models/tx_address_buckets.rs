@@ -0,0 +1,32 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::{hot_addresses, tx_address_buckets};
+
+/// The number of consecutive transaction sequence numbers counted in each bucket of
+/// `tx_address_buckets`.
+pub const TX_ADDRESS_BUCKET_SIZE: u64 = 10_000;
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = hot_addresses)]
+pub struct StoredHotAddress {
+    pub address: Vec<u8>,
+    pub backfilled_from_bucket: Option<i64>,
+    pub backfilled: bool,
+}
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = tx_address_buckets)]
+pub struct StoredTxAddressBucket {
+    pub address: Vec<u8>,
+    pub bucket: i64,
+    pub sent: i64,
+    pub received: i64,
+}
+
+/// The bucket that the transaction with sequence number `tx_sequence_number` is counted in.
+pub fn tx_address_bucket(tx_sequence_number: u64) -> u64 {
+    tx_sequence_number / TX_ADDRESS_BUCKET_SIZE
+}
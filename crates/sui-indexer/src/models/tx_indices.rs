@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    schema::{tx_calls, tx_changed_objects, tx_input_objects, tx_recipients, tx_senders},
+    schema::{
+        tx_calls, tx_changed_objects, tx_input_objects, tx_recipients, tx_senders,
+        tx_shared_objects,
+    },
     types::TxIndex,
 };
 use diesel::prelude::*;
@@ -38,6 +41,7 @@ pub struct StoredTxChangedObject {
 pub struct StoredTxSenders {
     pub tx_sequence_number: i64,
     pub sender: Vec<u8>,
+    pub gas_used: Option<i64>,
 }
 
 #[derive(Queryable, Insertable, Debug, Clone, Default)]
@@ -54,6 +58,17 @@ pub struct StoredTxCalls {
     pub package: Vec<u8>,
     pub module: String,
     pub func: String,
+    pub gas_used: Option<i64>,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[diesel(table_name = tx_shared_objects)]
+pub struct StoredTxSharedObject {
+    pub tx_sequence_number: i64,
+    pub checkpoint_sequence_number: i64,
+    pub object_id: Vec<u8>,
+    pub mutable: bool,
+    pub success: bool,
 }
 
 #[allow(clippy::type_complexity)]
@@ -66,14 +81,18 @@ impl TxIndex {
         Vec<StoredTxInputObject>,
         Vec<StoredTxChangedObject>,
         Vec<StoredTxCalls>,
+        Vec<StoredTxSharedObject>,
     ) {
         let tx_sequence_number = self.tx_sequence_number as i64;
+        let checkpoint_sequence_number = self.checkpoint_sequence_number as i64;
+        let gas_used = self.gas_used;
         let tx_senders = self
             .senders
             .iter()
             .map(|s| StoredTxSenders {
                 tx_sequence_number,
                 sender: s.to_vec(),
+                gas_used: Some(gas_used),
             })
             .collect();
         let tx_recipients = self
@@ -108,6 +127,18 @@ impl TxIndex {
                 package: p.to_vec(),
                 module: m.to_string(),
                 func: f.to_string(),
+                gas_used: Some(gas_used),
+            })
+            .collect();
+        let tx_shared_objects = self
+            .shared_objects
+            .iter()
+            .map(|(o, mutable)| StoredTxSharedObject {
+                tx_sequence_number,
+                checkpoint_sequence_number,
+                object_id: o.to_vec(),
+                mutable: *mutable,
+                success: self.success,
             })
             .collect();
         (
@@ -116,6 +147,7 @@ impl TxIndex {
             tx_input_objects,
             tx_changed_objects,
             tx_calls,
+            tx_shared_objects,
         )
     }
 }
@@ -0,0 +1,22 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::usage_reports;
+
+/// Usage of the GraphQL service by a single API key over a reporting period.
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default, PartialEq, Eq)]
+#[diesel(table_name = usage_reports)]
+pub struct StoredUsageReport {
+    pub api_key: String,
+    pub period_start_ms: i64,
+    pub period_end_ms: i64,
+    pub requests: i64,
+    pub errors: i64,
+    pub input_nodes: i64,
+    pub output_nodes: i64,
+    pub query_payload_bytes: i64,
+    pub latency_ms: i64,
+    pub price: i64,
+}
@@ -2,6 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    active_address_metrics (day) {
+        day -> Int8,
+        active_addresses -> Int8,
+    }
+}
+
 diesel::table! {
     checkpoints (sequence_number) {
         sequence_number -> Int8,
@@ -20,6 +27,8 @@ diesel::table! {
         checkpoint_commitments -> Bytea,
         validator_signature -> Bytea,
         end_of_epoch_data -> Nullable<Bytea>,
+        checkpoint_summary -> Nullable<Bytea>,
+        checkpoint_contents -> Nullable<Bytea>,
     }
 }
 
@@ -32,6 +41,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    epoch_consistency_reports (epoch) {
+        epoch -> Int8,
+        discrepancies -> Int8,
+        report -> Text,
+        checked_at_ms -> Int8,
+    }
+}
+
 diesel::table! {
     epochs (epoch) {
         epoch -> Int8,
@@ -71,6 +89,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    hot_addresses (address) {
+        address -> Bytea,
+        backfilled_from_bucket -> Nullable<Int8>,
+        backfilled -> Bool,
+    }
+}
+
+diesel::table! {
+    move_call_metrics (day, package, module, func) {
+        day -> Int8,
+        package -> Bytea,
+        module -> Text,
+        func -> Text,
+        call_count -> Int8,
+        distinct_senders -> Int8,
+        total_gas_used -> Int8,
+    }
+}
+
 diesel::table! {
     objects (object_id) {
         object_id -> Bytea,
@@ -87,6 +125,9 @@ diesel::table! {
         df_name -> Nullable<Bytea>,
         df_object_type -> Nullable<Text>,
         df_object_id -> Nullable<Bytea>,
+        df_bcs_name -> Nullable<Bytea>,
+        display_version -> Nullable<Int2>,
+        display_fields -> Nullable<Text>,
     }
 }
 
@@ -150,6 +191,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    package_tx_metrics (day, package) {
+        day -> Int8,
+        package -> Bytea,
+        tx_count -> Int8,
+    }
+}
+
 diesel::table! {
     packages (package_id) {
         package_id -> Bytea,
@@ -157,6 +206,53 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    persisted_queries (query_hash) {
+        query_hash -> Text,
+        query -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    quarantined_checkpoints (checkpoint_sequence_number) {
+        checkpoint_sequence_number -> Int8,
+        epoch -> Int8,
+        checkpoint_digest -> Bytea,
+        error -> Text,
+        attempts -> Int8,
+        context -> Text,
+        quarantined_at_ms -> Int8,
+    }
+}
+
+diesel::table! {
+    rollup_watermarks (name) {
+        name -> Text,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
+diesel::table! {
+    sender_gas_metrics (day, sender) {
+        day -> Int8,
+        sender -> Bytea,
+        tx_count -> Int8,
+        total_gas_used -> Int8,
+    }
+}
+
+diesel::table! {
+    shared_object_metrics (day, object_id) {
+        day -> Int8,
+        object_id -> Bytea,
+        tx_count -> Int8,
+        mutating_tx_count -> Int8,
+        failed_tx_count -> Int8,
+        distinct_senders -> Int8,
+        max_checkpoint_mutations -> Int8,
+    }
+}
+
 diesel::table! {
     transactions (tx_sequence_number, checkpoint_sequence_number) {
         tx_sequence_number -> Int8,
@@ -189,12 +285,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    tx_address_buckets (address, bucket) {
+        address -> Bytea,
+        bucket -> Int8,
+        sent -> Int8,
+        received -> Int8,
+    }
+}
+
 diesel::table! {
     tx_calls (package, tx_sequence_number) {
         tx_sequence_number -> Int8,
         package -> Bytea,
         module -> Text,
         func -> Text,
+        gas_used -> Nullable<Int8>,
     }
 }
 
@@ -223,24 +329,63 @@ diesel::table! {
     tx_senders (sender, tx_sequence_number) {
         tx_sequence_number -> Int8,
         sender -> Bytea,
+        gas_used -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    tx_shared_objects (object_id, tx_sequence_number) {
+        tx_sequence_number -> Int8,
+        checkpoint_sequence_number -> Int8,
+        object_id -> Bytea,
+        mutable -> Bool,
+        success -> Bool,
+    }
+}
+
+diesel::table! {
+    usage_reports (api_key, period_start_ms) {
+        api_key -> Text,
+        period_start_ms -> Int8,
+        period_end_ms -> Int8,
+        requests -> Int8,
+        errors -> Int8,
+        input_nodes -> Int8,
+        output_nodes -> Int8,
+        query_payload_bytes -> Int8,
+        latency_ms -> Int8,
+        price -> Int8,
     }
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    active_address_metrics,
     checkpoints,
     display,
+    epoch_consistency_reports,
     epochs,
     events,
+    hot_addresses,
+    move_call_metrics,
     objects,
     objects_history,
     objects_history_partition_0,
     objects_snapshot,
+    package_tx_metrics,
     packages,
+    persisted_queries,
+    quarantined_checkpoints,
+    rollup_watermarks,
+    sender_gas_metrics,
+    shared_object_metrics,
     transactions,
     transactions_partition_0,
+    tx_address_buckets,
     tx_calls,
     tx_changed_objects,
     tx_input_objects,
     tx_recipients,
     tx_senders,
+    tx_shared_objects,
+    usage_reports,
 );
@@ -5,19 +5,36 @@ use async_trait::async_trait;
 use move_binary_format::CompiledModule;
 use move_bytecode_utils::module_cache::GetModule;
 use std::any::Any;
-use std::collections::BTreeMap;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+use sui_package_resolver::PackageStore;
 use sui_types::base_types::{ObjectID, SequenceNumber};
 use sui_types::object::ObjectRead;
 
 use crate::errors::IndexerError;
+use crate::handlers::pruner::PrunableTables;
 use crate::handlers::{EpochToCommit, TransactionObjectChangesToCommit};
 
 use crate::models::display::StoredDisplay;
+use crate::models::epoch::StoredEpochInfo;
+use crate::models::epoch_consistency_reports::StoredEpochConsistencyReport;
 use crate::models::objects::{StoredDeletedObject, StoredObject};
+use crate::models::quarantined_checkpoints::StoredQuarantinedCheckpoint;
+use crate::models::tx_address_buckets::StoredHotAddress;
 use crate::types::{IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex};
 
+// The tables that index transactions by their sequence number, pruned along with them.
+pub(crate) const TX_INDEX_TABLES: [&str; 6] = [
+    "tx_senders",
+    "tx_recipients",
+    "tx_input_objects",
+    "tx_changed_objects",
+    "tx_calls",
+    "tx_shared_objects",
+];
+
 #[allow(clippy::large_enum_variant)]
 pub enum ObjectChangeToCommit {
     MutatedObject(StoredObject),
@@ -30,6 +47,7 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
         + Send
         + Sync
         + 'static;
+    type PackageResolver: PackageStore;
 
     async fn get_latest_tx_checkpoint_sequence_number(&self) -> Result<Option<u64>, IndexerError>;
 
@@ -37,6 +55,17 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
         &self,
     ) -> Result<Option<u64>, IndexerError>;
 
+    async fn get_latest_move_call_metrics_day(&self) -> Result<Option<u64>, IndexerError>;
+
+    async fn get_latest_shared_object_metrics_day(&self) -> Result<Option<u64>, IndexerError>;
+
+    /// Returns the first checkpoint whose transactions are not counted in the daily rollups yet,
+    /// or `None` if there are no rollups and no indexed checkpoints.
+    async fn get_next_rollups_checkpoint(&self) -> Result<Option<u64>, IndexerError>;
+
+    /// Returns the timestamps of the earliest and latest indexed checkpoints.
+    async fn get_checkpoint_timestamp_range_ms(&self) -> Result<Option<(u64, u64)>, IndexerError>;
+
     async fn get_object_read(
         &self,
         object_id: ObjectID,
@@ -56,6 +85,66 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
     async fn persist_object_snapshot(&self, start_cp: u64, end_cp: u64)
         -> Result<(), IndexerError>;
 
+    /// Computes the move call metrics of `day`, in days since the unix epoch.
+    async fn persist_move_call_metrics(&self, day: u64) -> Result<(), IndexerError>;
+
+    /// Computes the shared object metrics of `day`, in days since the unix epoch.
+    async fn persist_shared_object_metrics(&self, day: u64) -> Result<(), IndexerError>;
+
+    /// Adds the transactions of checkpoints `start_cp..=end_cp` to the daily rollups (active
+    /// addresses, transactions per package and gas used per sender), and moves the rollups'
+    /// watermark to `end_cp`.
+    async fn persist_rollups(&self, start_cp: u64, end_cp: u64) -> Result<(), IndexerError>;
+
+    async fn get_latest_epoch(&self) -> Result<Option<u64>, IndexerError>;
+
+    async fn get_epoch_info(&self, epoch: u64) -> Result<Option<StoredEpochInfo>, IndexerError>;
+
+    /// Prunes the data of `tables` from epochs before `before_epoch`, archiving the partitions of
+    /// tables partitioned by epoch instead of dropping them, if `archive` is set. Returns the
+    /// number of rows pruned from each table.
+    async fn prune_epochs(
+        &self,
+        tables: PrunableTables,
+        before_epoch: u64,
+        archive: bool,
+    ) -> Result<Vec<(&'static str, u64)>, IndexerError>;
+
+    /// Returns the hot addresses whose `tx_address_buckets` have not been fully backfilled.
+    async fn get_hot_addresses_to_backfill(&self) -> Result<Vec<StoredHotAddress>, IndexerError>;
+
+    /// Counts the transactions of hot `address` in the highest bucket below `below_bucket` (or
+    /// below no bucket, if it is `None`) that holds any of its transactions, and records the
+    /// backfill's progress. Returns the bucket that was counted, or `None` (marking the address
+    /// as backfilled) if there are no such buckets left.
+    async fn backfill_tx_address_bucket(
+        &self,
+        address: Vec<u8>,
+        below_bucket: Option<u64>,
+    ) -> Result<Option<u64>, IndexerError>;
+
+    /// Returns the Displays of `object_types` that are indexed, by object type.
+    async fn get_displays(
+        &self,
+        object_types: Vec<String>,
+    ) -> Result<BTreeMap<String, StoredDisplay>, IndexerError>;
+
+    /// Returns up to `limit` live objects after `after_object_id` (or from the first object, if
+    /// it is `None`), in object ID order, whose dynamic field name is not resolved, or whose
+    /// Display is not rendered with the latest version of the Display of their type.
+    async fn get_objects_to_resolve(
+        &self,
+        after_object_id: Option<Vec<u8>>,
+        limit: usize,
+    ) -> Result<Vec<StoredObject>, IndexerError>;
+
+    /// Writes the resolved dynamic field names and rendered Displays of `objects`, unless the
+    /// objects have been updated to a later version since they were read.
+    async fn persist_resolved_objects(
+        &self,
+        objects: Vec<StoredObject>,
+    ) -> Result<(), IndexerError>;
+
     async fn persist_checkpoints(
         &self,
         checkpoints: Vec<IndexedCheckpoint>,
@@ -80,6 +169,20 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
 
     async fn advance_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError>;
 
+    /// Records a checkpoint that could not be committed, so that it can be inspected and
+    /// re-indexed later.
+    async fn persist_quarantined_checkpoint(
+        &self,
+        checkpoint: StoredQuarantinedCheckpoint,
+    ) -> Result<(), IndexerError>;
+
+    /// Records the result of checking an epoch's indexed data against the fullnode, replacing
+    /// any earlier report of the same epoch.
+    async fn persist_epoch_consistency_report(
+        &self,
+        report: StoredEpochConsistencyReport,
+    ) -> Result<(), IndexerError>;
+
     async fn get_network_total_transactions_by_end_of_epoch(
         &self,
         epoch: u64,
@@ -87,5 +190,78 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
 
     fn module_cache(&self) -> Arc<Self::ModuleCache>;
 
+    /// Returns a resolver of the packages indexed in the store, for resolving the layouts of the
+    /// types of the objects and events being indexed.
+    fn package_resolver(&self) -> Self::PackageResolver;
+
     fn as_any(&self) -> &dyn Any;
 }
+
+/// Construct deleted objects and mutated objects to commit.
+/// In particular, filter mutated objects updates that would
+/// be override immediately.
+pub(crate) fn make_final_list_of_objects_to_commit(
+    tx_object_changes: Vec<TransactionObjectChangesToCommit>,
+) -> Vec<ObjectChangeToCommit> {
+    let deleted_objects = tx_object_changes
+        .clone()
+        .into_iter()
+        .flat_map(|changes| changes.deleted_objects)
+        .map(|o| (o.object_id, o.into()))
+        .collect::<HashMap<ObjectID, StoredDeletedObject>>();
+
+    let mutated_objects = tx_object_changes
+        .into_iter()
+        .flat_map(|changes| changes.changed_objects);
+    let mut latest_objects = HashMap::new();
+    for object in mutated_objects {
+        if deleted_objects.contains_key(&object.object_id) {
+            continue;
+        }
+        match latest_objects.entry(object.object_id) {
+            Entry::Vacant(e) => {
+                e.insert(object);
+            }
+            Entry::Occupied(mut e) => {
+                if object.object_version > e.get().object_version {
+                    e.insert(object);
+                }
+            }
+        }
+    }
+    deleted_objects
+        .into_values()
+        .map(ObjectChangeToCommit::DeletedObject)
+        .chain(
+            latest_objects
+                .into_values()
+                .map(StoredObject::from)
+                .map(ObjectChangeToCommit::MutatedObject),
+        )
+        .collect()
+}
+
+pub(crate) fn make_objects_history_to_commit(
+    tx_object_changes: Vec<TransactionObjectChangesToCommit>,
+) -> Vec<ObjectChangeToCommit> {
+    let deleted_objects: Vec<StoredDeletedObject> = tx_object_changes
+        .clone()
+        .into_iter()
+        .flat_map(|changes| changes.deleted_objects)
+        .map(|o| o.into())
+        .collect();
+    let mutated_objects: Vec<StoredObject> = tx_object_changes
+        .into_iter()
+        .flat_map(|changes| changes.changed_objects)
+        .map(|o| o.into())
+        .collect();
+    deleted_objects
+        .into_iter()
+        .map(ObjectChangeToCommit::DeletedObject)
+        .chain(
+            mutated_objects
+                .into_iter()
+                .map(ObjectChangeToCommit::MutatedObject),
+        )
+        .collect()
+}
@@ -1,9 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "clickhouse")]
+pub use clickhouse_indexer_store::ClickHouseIndexerStore;
 pub(crate) use indexer_store::*;
 pub use pg_indexer_store::PgIndexerStore;
 
+#[cfg(feature = "clickhouse")]
+mod clickhouse_client;
+#[cfg(feature = "clickhouse")]
+mod clickhouse_indexer_store;
 pub mod indexer_store;
 pub mod module_resolver;
 mod pg_indexer_store;
@@ -18,40 +24,90 @@ pub(crate) mod diesel_macro {
                 .build_transaction()
                 .read_only()
                 .run($query)
-                .map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+                .map_err(|e| {
+                    IndexerError::ErrorWithContext(
+                        "Failed to read from PostgresDB".to_string(),
+                        Box::new(IndexerError::from(e)),
+                    )
+                })
         }};
     }
 
+    /// Runs `$query` in a read-write transaction. Transactions failing with a retryable error
+    /// (see `IndexerError::is_retryable`) are retried with jittered exponential backoff, for up to
+    /// `$max_elapsed`, while other errors are returned immediately. Retries and failures are
+    /// counted in `$metrics`, if given.
     macro_rules! transactional_blocking_with_retry {
         ($pool:expr, $query:expr, $max_elapsed:expr) => {{
+            crate::store::diesel_macro::transactional_blocking_with_retry!(
+                $pool,
+                $query,
+                $max_elapsed,
+                None::<&crate::metrics::IndexerMetrics>
+            )
+        }};
+        ($pool:expr, $query:expr, $max_elapsed:expr, $metrics:expr) => {{
+            let metrics: Option<&crate::metrics::IndexerMetrics> = $metrics.into();
             let mut backoff = backoff::ExponentialBackoff::default();
             backoff.max_elapsed_time = Some($max_elapsed);
+            // Jitter the retries, so that transactions conflicting with each other do not keep
+            // retrying in lockstep.
+            backoff.randomization_factor = 0.5;
 
-            let result = match backoff::retry(backoff, || {
-                let mut pg_pool_conn = crate::db::get_pg_pool_connection($pool).map_err(|e| {
-                    backoff::Error::Transient {
-                        err: IndexerError::PostgresWriteError(e.to_string()),
-                        retry_after: None,
+            let result = backoff::retry_notify(
+                backoff,
+                || {
+                    let mut pg_pool_conn = crate::db::get_pg_pool_connection($pool)
+                        .map_err(backoff::Error::transient)?;
+                    pg_pool_conn
+                        .build_transaction()
+                        .read_write()
+                        .run($query)
+                        .map_err(|e| {
+                            let e = IndexerError::from(e);
+                            if e.is_retryable() {
+                                backoff::Error::transient(e)
+                            } else {
+                                backoff::Error::permanent(e)
+                            }
+                        })
+                },
+                |e: IndexerError, retry_after: std::time::Duration| {
+                    tracing::warn!(
+                        "Retrying DB write transaction in {:?} after error: {:?}",
+                        retry_after,
+                        e
+                    );
+                    if let Some(metrics) = metrics {
+                        metrics.total_db_write_retries.inc();
                     }
-                })?;
-                pg_pool_conn
-                    .build_transaction()
-                    .read_write()
-                    .run($query)
-                    .map_err(|e| {
-                        tracing::error!("Error with persisting data into DB: {:?}", e);
-                        backoff::Error::Transient {
-                            err: IndexerError::PostgresWriteError(e.to_string()),
-                            retry_after: None,
-                        }
-                    })
-            }) {
-                Ok(v) => Ok(v),
-                Err(backoff::Error::Transient { err, .. }) => Err(err),
-                Err(backoff::Error::Permanent(err)) => Err(err),
-            };
+                },
+            );
 
-            result
+            match result {
+                Ok(v) => Ok(v),
+                Err(backoff::Error::Transient { err, .. }) => {
+                    tracing::error!("DB write transaction exhausted its retry budget: {:?}", err);
+                    if let Some(metrics) = metrics {
+                        metrics.total_db_write_retry_budget_exhausted.inc();
+                    }
+                    Err(err)
+                }
+                Err(backoff::Error::Permanent(err)) => {
+                    tracing::error!(
+                        "DB write transaction failed with {} error: {:?}",
+                        err.kind(),
+                        err
+                    );
+                    if let Some(metrics) = metrics {
+                        metrics
+                            .total_db_write_permanent_failures
+                            .with_label_values(&[err.kind().as_str()])
+                            .inc();
+                    }
+                    Err(err)
+                }
+            }
         }};
     }
 
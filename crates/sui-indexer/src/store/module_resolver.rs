@@ -125,20 +125,23 @@ impl IndexerStorePackageModuleResolver {
         };
         let object = bcs::from_bytes::<Object>(&bcs)?;
         Package::read(&object).map_err(|e| {
-            IndexerError::PostgresReadError(format!("Failed parsing object to package: {:?}", e))
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Failed parsing object to package: {:?}",
+                e
+            ))
         })
     }
 }
 
-pub struct InterimPackageResolver {
-    package_db_resolver: IndexerStorePackageModuleResolver,
+pub struct InterimPackageResolver<P> {
+    package_db_resolver: P,
     package_buffer: Arc<Mutex<IndexingPackageBuffer>>,
     metrics: IndexerMetrics,
 }
 
-impl InterimPackageResolver {
+impl<P: PackageStore> InterimPackageResolver<P> {
     pub fn new(
-        package_db_resolver: IndexerStorePackageModuleResolver,
+        package_db_resolver: P,
         package_buffer: Arc<Mutex<IndexingPackageBuffer>>,
         new_package_objects: &[(IndexedPackage, Object)],
         metrics: IndexerMetrics,
@@ -156,7 +159,7 @@ impl InterimPackageResolver {
 }
 
 #[async_trait]
-impl PackageStore for InterimPackageResolver {
+impl<P: PackageStore> PackageStore for InterimPackageResolver<P> {
     async fn version(&self, addr: AccountAddress) -> Result<SequenceNumber, PackageResolverError> {
         let package_id = ObjectID::from(addr);
         let maybe_version = {
@@ -4,19 +4,19 @@
 use core::result::Result::Ok;
 use itertools::Itertools;
 use std::any::Any;
-use std::collections::hash_map::Entry;
 use std::collections::BTreeMap;
-use std::collections::HashMap;
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 use tap::Tap;
 
 use async_trait::async_trait;
-use diesel::dsl::max;
+use diesel::dsl::{max, min};
 use diesel::upsert::excluded;
 use diesel::ExpressionMethods;
 use diesel::OptionalExtension;
+use diesel::PgConnection;
 use diesel::{QueryDsl, RunQueryDsl};
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use tracing::info;
@@ -25,6 +25,8 @@ use sui_types::base_types::{ObjectID, SequenceNumber};
 use sui_types::object::ObjectRead;
 
 use crate::errors::{Context, IndexerError};
+use crate::handlers::move_call_metrics_processor::MILLIS_PER_DAY;
+use crate::handlers::pruner::PrunableTables;
 use crate::handlers::EpochToCommit;
 use crate::handlers::TransactionObjectChangesToCommit;
 use crate::metrics::IndexerMetrics;
@@ -33,15 +35,23 @@ use crate::db::PgConnectionPool;
 use crate::models::checkpoints::StoredCheckpoint;
 use crate::models::display::StoredDisplay;
 use crate::models::epoch::StoredEpochInfo;
+use crate::models::epoch_consistency_reports::StoredEpochConsistencyReport;
 use crate::models::events::StoredEvent;
 use crate::models::objects::{
     StoredDeletedHistoryObject, StoredDeletedObject, StoredHistoryObject, StoredObject,
 };
 use crate::models::packages::StoredPackage;
+use crate::models::quarantined_checkpoints::StoredQuarantinedCheckpoint;
 use crate::models::transactions::StoredTransaction;
+use crate::models::tx_address_buckets::{
+    tx_address_bucket, StoredHotAddress, TX_ADDRESS_BUCKET_SIZE,
+};
+use crate::models::tx_indices::{StoredTxRecipients, StoredTxSenders};
 use crate::schema::{
-    checkpoints, display, epochs, events, objects, objects_history, objects_snapshot, packages,
-    transactions, tx_calls, tx_changed_objects, tx_input_objects, tx_recipients, tx_senders,
+    checkpoints, display, epoch_consistency_reports, epochs, events, hot_addresses,
+    move_call_metrics, objects, objects_history, objects_snapshot, packages,
+    quarantined_checkpoints, rollup_watermarks, shared_object_metrics, transactions, tx_calls,
+    tx_changed_objects, tx_input_objects, tx_recipients, tx_senders, tx_shared_objects,
 };
 use crate::store::diesel_macro::{read_only_blocking, transactional_blocking_with_retry};
 use crate::store::module_resolver::IndexerStorePackageModuleResolver;
@@ -50,6 +60,8 @@ use crate::types::{IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTrans
 use super::pg_partition_manager::{EpochPartitionData, PgPartitionManager};
 use super::IndexerStore;
 use super::ObjectChangeToCommit;
+use super::TX_INDEX_TABLES;
+use super::{make_final_list_of_objects_to_commit, make_objects_history_to_commit};
 
 #[macro_export]
 macro_rules! chunk {
@@ -75,6 +87,12 @@ const PG_COMMIT_PARALLEL_CHUNK_SIZE: usize = 100;
 // optimistic locking.
 const PG_COMMIT_OBJECTS_PARALLEL_CHUNK_SIZE: usize = 500;
 
+// The amount of rows to delete in one DB transaction, when pruning tables that are not
+// partitioned by epoch.
+const PRUNE_BATCH_SIZE: usize = 10_000;
+// The name of the watermark of the daily rollups in `rollup_watermarks`.
+const ROLLUPS_WATERMARK: &str = "daily";
+
 // with rn = 1, we only select the latest version of each object,
 // so that we don't have to update the same object multiple times.
 const UPDATE_OBJECTS_SNAPSHOT_QUERY: &str = r"
@@ -104,6 +122,170 @@ SET object_version = EXCLUDED.object_version,
     df_object_id = EXCLUDED.df_object_id;
 ";
 
+// Live objects after object ID $1 whose dynamic field name is not resolved, or whose Display is not
+// rendered with the latest version of the Display of their type, in object ID order.
+const OBJECTS_TO_RESOLVE_QUERY: &str = r"
+SELECT o.*
+FROM objects o
+LEFT JOIN display d ON d.object_type = o.object_type
+WHERE o.object_id > $1
+  AND ((o.df_kind IS NOT NULL AND o.df_bcs_name IS NULL)
+    OR (d.version IS NOT NULL AND o.display_version IS DISTINCT FROM d.version))
+ORDER BY o.object_id
+LIMIT $2
+";
+
+// Aggregates the move calls of the transactions in checkpoints with timestamps in [$2, $3) into
+// the metrics of day $1. Transactions are selected by sequence number, using the network total
+// transactions of the checkpoints bounding the day.
+const UPDATE_MOVE_CALL_METRICS_QUERY: &str = r"
+WITH tx_range AS (
+    SELECT
+        COALESCE((SELECT MAX(network_total_transactions) FROM checkpoints WHERE timestamp_ms < $2), 0) AS lo,
+        COALESCE((SELECT MAX(network_total_transactions) FROM checkpoints WHERE timestamp_ms < $3), 0) AS hi
+)
+INSERT INTO move_call_metrics (day, package, module, func, call_count, distinct_senders, total_gas_used)
+SELECT $1, c.package, c.module, c.func, COUNT(*), COUNT(DISTINCT s.sender), COALESCE(SUM(c.gas_used), 0)::BIGINT
+FROM tx_range r, tx_calls c
+JOIN tx_senders s ON s.tx_sequence_number = c.tx_sequence_number
+WHERE c.tx_sequence_number >= r.lo AND c.tx_sequence_number < r.hi
+GROUP BY c.package, c.module, c.func
+ON CONFLICT (day, package, module, func) DO UPDATE
+SET call_count = EXCLUDED.call_count,
+    distinct_senders = EXCLUDED.distinct_senders,
+    total_gas_used = EXCLUDED.total_gas_used;
+";
+
+// Aggregates the shared objects taken by the transactions in checkpoints with timestamps in
+// [$2, $3) into the metrics of day $1, selecting the transactions like
+// `UPDATE_MOVE_CALL_METRICS_QUERY`.
+const UPDATE_SHARED_OBJECT_METRICS_QUERY: &str = r"
+WITH tx_range AS (
+    SELECT
+        COALESCE((SELECT MAX(network_total_transactions) FROM checkpoints WHERE timestamp_ms < $2), 0) AS lo,
+        COALESCE((SELECT MAX(network_total_transactions) FROM checkpoints WHERE timestamp_ms < $3), 0) AS hi
+),
+accesses AS (
+    SELECT o.*
+    FROM tx_range r, tx_shared_objects o
+    WHERE o.tx_sequence_number >= r.lo AND o.tx_sequence_number < r.hi
+),
+checkpoint_mutations AS (
+    SELECT object_id, MAX(mutations) AS max_mutations
+    FROM (
+        SELECT object_id, COUNT(*) AS mutations
+        FROM accesses
+        WHERE mutable
+        GROUP BY object_id, checkpoint_sequence_number
+    ) m
+    GROUP BY object_id
+)
+INSERT INTO shared_object_metrics (day, object_id, tx_count, mutating_tx_count, failed_tx_count, distinct_senders, max_checkpoint_mutations)
+SELECT $1, a.object_id, COUNT(*), COUNT(*) FILTER (WHERE a.mutable), COUNT(*) FILTER (WHERE NOT a.success), COUNT(DISTINCT s.sender), COALESCE(MAX(m.max_mutations), 0)
+FROM accesses a
+JOIN tx_senders s ON s.tx_sequence_number = a.tx_sequence_number
+LEFT JOIN checkpoint_mutations m ON m.object_id = a.object_id
+GROUP BY a.object_id
+ON CONFLICT (day, object_id) DO UPDATE
+SET tx_count = EXCLUDED.tx_count,
+    mutating_tx_count = EXCLUDED.mutating_tx_count,
+    failed_tx_count = EXCLUDED.failed_tx_count,
+    distinct_senders = EXCLUDED.distinct_senders,
+    max_checkpoint_mutations = EXCLUDED.max_checkpoint_mutations;
+";
+
+// Adds the transactions of checkpoints [$1, $2] to the daily transaction counts and gas used of
+// their senders, for days $3 milliseconds long. Transactions are selected by sequence number,
+// using the network total transactions of the checkpoints bounding the range.
+const UPDATE_SENDER_GAS_METRICS_QUERY: &str = r"
+WITH tx_range AS (
+    SELECT
+        COALESCE((SELECT MAX(network_total_transactions) FROM checkpoints WHERE sequence_number < $1), 0) AS lo,
+        COALESCE((SELECT MAX(network_total_transactions) FROM checkpoints WHERE sequence_number <= $2), 0) AS hi
+)
+INSERT INTO sender_gas_metrics (day, sender, tx_count, total_gas_used)
+SELECT t.timestamp_ms / $3, s.sender, COUNT(*), COALESCE(SUM(s.gas_used), 0)::BIGINT
+FROM tx_range r, transactions t
+JOIN tx_senders s ON s.tx_sequence_number = t.tx_sequence_number
+WHERE t.tx_sequence_number >= r.lo AND t.tx_sequence_number < r.hi
+GROUP BY 1, 2
+ON CONFLICT (day, sender) DO UPDATE
+SET tx_count = sender_gas_metrics.tx_count + EXCLUDED.tx_count,
+    total_gas_used = sender_gas_metrics.total_gas_used + EXCLUDED.total_gas_used;
+";
+
+// Adds the transactions of checkpoints [$1, $2] to the daily transaction counts of the packages
+// they call, selecting the transactions like `UPDATE_SENDER_GAS_METRICS_QUERY`. A transaction
+// calling a package more than once is counted once.
+const UPDATE_PACKAGE_TX_METRICS_QUERY: &str = r"
+WITH tx_range AS (
+    SELECT
+        COALESCE((SELECT MAX(network_total_transactions) FROM checkpoints WHERE sequence_number < $1), 0) AS lo,
+        COALESCE((SELECT MAX(network_total_transactions) FROM checkpoints WHERE sequence_number <= $2), 0) AS hi
+)
+INSERT INTO package_tx_metrics (day, package, tx_count)
+SELECT t.timestamp_ms / $3, c.package, COUNT(DISTINCT c.tx_sequence_number)
+FROM tx_range r, transactions t
+JOIN tx_calls c ON c.tx_sequence_number = t.tx_sequence_number
+WHERE t.tx_sequence_number >= r.lo AND t.tx_sequence_number < r.hi
+GROUP BY 1, 2
+ON CONFLICT (day, package) DO UPDATE
+SET tx_count = package_tx_metrics.tx_count + EXCLUDED.tx_count;
+";
+
+// Recounts the active addresses of the days of checkpoints [$1, $2] from `sender_gas_metrics`,
+// which holds a row for each address sending transactions on a day.
+const UPDATE_ACTIVE_ADDRESS_METRICS_QUERY: &str = r"
+INSERT INTO active_address_metrics (day, active_addresses)
+SELECT m.day, COUNT(*)
+FROM sender_gas_metrics m
+WHERE m.day IN (
+    SELECT DISTINCT timestamp_ms / $3
+    FROM checkpoints
+    WHERE sequence_number >= $1 AND sequence_number <= $2
+)
+GROUP BY m.day
+ON CONFLICT (day) DO UPDATE
+SET active_addresses = EXCLUDED.active_addresses;
+";
+
+// Recounts the transactions sent and received by address $1 in bucket $2 of `tx_address_buckets`,
+// which holds the transactions with sequence numbers in [$3, $4). Transactions in the same bucket
+// can be committed concurrently, in which case each commit only counts its own transactions, so
+// the larger count is kept, to keep counts from going down.
+const UPDATE_TX_ADDRESS_BUCKET_QUERY: &str = r"
+INSERT INTO tx_address_buckets (address, bucket, sent, received)
+SELECT $1, $2,
+    (SELECT COUNT(*) FROM tx_senders
+     WHERE sender = $1 AND tx_sequence_number >= $3 AND tx_sequence_number < $4),
+    (SELECT COUNT(*) FROM tx_recipients
+     WHERE recipient = $1 AND tx_sequence_number >= $3 AND tx_sequence_number < $4)
+ON CONFLICT (address, bucket) DO UPDATE
+SET sent = GREATEST(tx_address_buckets.sent, EXCLUDED.sent),
+    received = GREATEST(tx_address_buckets.received, EXCLUDED.received);
+";
+
+/// Overwrites every column of an `objects_history` row but its primary key, with the columns of
+/// the row being inserted. Deleted objects leave the columns they do not have null.
+macro_rules! objects_history_upsert_changeset {
+    () => {
+        (
+            objects_history::object_status.eq(excluded(objects_history::object_status)),
+            objects_history::object_digest.eq(excluded(objects_history::object_digest)),
+            objects_history::owner_type.eq(excluded(objects_history::owner_type)),
+            objects_history::owner_id.eq(excluded(objects_history::owner_id)),
+            objects_history::object_type.eq(excluded(objects_history::object_type)),
+            objects_history::serialized_object.eq(excluded(objects_history::serialized_object)),
+            objects_history::coin_type.eq(excluded(objects_history::coin_type)),
+            objects_history::coin_balance.eq(excluded(objects_history::coin_balance)),
+            objects_history::df_kind.eq(excluded(objects_history::df_kind)),
+            objects_history::df_name.eq(excluded(objects_history::df_name)),
+            objects_history::df_object_type.eq(excluded(objects_history::df_object_type)),
+            objects_history::df_object_id.eq(excluded(objects_history::df_object_id)),
+        )
+    };
+}
+
 #[derive(Clone)]
 pub struct PgIndexerStore {
     blocking_cp: PgConnectionPool,
@@ -112,6 +294,8 @@ pub struct PgIndexerStore {
     parallel_chunk_size: usize,
     parallel_objects_chunk_size: usize,
     partition_manager: PgPartitionManager,
+    /// Whether the store is re-ingesting checkpoints that have already been indexed.
+    resync: bool,
 }
 
 impl PgIndexerStore {
@@ -137,6 +321,20 @@ impl PgIndexerStore {
             parallel_chunk_size,
             parallel_objects_chunk_size,
             partition_manager,
+            resync: false,
+        }
+    }
+
+    /// Configures the store to re-ingest checkpoints that have already been indexed. Rows written
+    /// per checkpoint or transaction (checkpoints, transactions, events, transaction indices and
+    /// object history) overwrite the rows already written for them, instead of being skipped.
+    /// Live objects and displays are left alone, because they reflect the latest checkpoint, and
+    /// re-applying older changes to them would roll them back. Epoch partitions are not advanced
+    /// again either, as they already cover the checkpoints being re-ingested.
+    pub fn for_resync(self) -> Self {
+        Self {
+            resync: true,
+            ..self
         }
     }
 
@@ -166,6 +364,57 @@ impl PgIndexerStore {
         .context("Failed reading latest object snapshot checkpoint sequence number from PostgresDB")
     }
 
+    fn get_latest_move_call_metrics_day(&self) -> Result<Option<u64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            move_call_metrics::dsl::move_call_metrics
+                .select(max(move_call_metrics::day))
+                .first::<Option<i64>>(conn)
+                .map(|v| v.map(|v| v as u64))
+        })
+        .context("Failed reading latest move call metrics day from PostgresDB")
+    }
+
+    fn get_latest_shared_object_metrics_day(&self) -> Result<Option<u64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            shared_object_metrics::dsl::shared_object_metrics
+                .select(max(shared_object_metrics::day))
+                .first::<Option<i64>>(conn)
+                .map(|v| v.map(|v| v as u64))
+        })
+        .context("Failed reading latest shared object metrics day from PostgresDB")
+    }
+
+    fn get_next_rollups_checkpoint(&self) -> Result<Option<u64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            let watermark = rollup_watermarks::table
+                .select(rollup_watermarks::checkpoint_sequence_number)
+                .filter(rollup_watermarks::name.eq(ROLLUPS_WATERMARK))
+                .first::<i64>(conn)
+                .optional()?;
+            match watermark {
+                Some(cp) => Ok(Some(cp as u64 + 1)),
+                None => checkpoints::table
+                    .select(min(checkpoints::sequence_number))
+                    .first::<Option<i64>>(conn)
+                    .map(|v| v.map(|v| v as u64)),
+            }
+        })
+        .context("Failed reading next rollups checkpoint from PostgresDB")
+    }
+
+    fn get_checkpoint_timestamp_range_ms(&self) -> Result<Option<(u64, u64)>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            checkpoints::dsl::checkpoints
+                .select((
+                    min(checkpoints::timestamp_ms),
+                    max(checkpoints::timestamp_ms),
+                ))
+                .first::<(Option<i64>, Option<i64>)>(conn)
+                .map(|(first, last)| first.zip(last).map(|(f, l)| (f as u64, l as u64)))
+        })
+        .context("Failed reading checkpoint timestamp range from PostgresDB")
+    }
+
     // Note: here we treat Deleted as NotExists too
     fn get_object_read(
         &self,
@@ -212,7 +461,8 @@ impl PgIndexerStore {
                     .context("Failed to write display updates to PostgresDB")?;
                 Ok::<(), IndexerError>(())
             },
-            Duration::from_secs(60)
+            Duration::from_secs(60),
+            &self.metrics
         )?;
 
         Ok(())
@@ -247,7 +497,9 @@ impl PgIndexerStore {
                 for mutated_object_change_chunk in
                     mutated_objects.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX)
                 {
-                    diesel::insert_into(objects::table)
+                    // Only newer versions replace a stored object, so committing checkpoints
+                    // again, e.g. one by one after their batch failed, never reverts an object.
+                    let upsert = diesel::insert_into(objects::table)
                         .values(mutated_object_change_chunk)
                         .on_conflict(objects::object_id)
                         .do_update()
@@ -267,10 +519,17 @@ impl PgIndexerStore {
                             objects::df_name.eq(excluded(objects::df_name)),
                             objects::df_object_type.eq(excluded(objects::df_object_type)),
                             objects::df_object_id.eq(excluded(objects::df_object_id)),
-                        ))
-                        .execute(conn)
-                        .map_err(IndexerError::from)
-                        .context("Failed to write object mutation to PostgresDB")?;
+                            objects::df_bcs_name.eq(excluded(objects::df_bcs_name)),
+                            objects::display_version.eq(excluded(objects::display_version)),
+                            objects::display_fields.eq(excluded(objects::display_fields)),
+                        ));
+                    diesel::query_dsl::methods::FilterDsl::filter(
+                        upsert,
+                        objects::object_version.lt(excluded(objects::object_version)),
+                    )
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed to write object mutation to PostgresDB")?;
                 }
 
                 // Persist deleted objects
@@ -294,7 +553,8 @@ impl PgIndexerStore {
 
                 Ok::<(), IndexerError>(())
             },
-            Duration::from_secs(60)
+            Duration::from_secs(60),
+            &self.metrics
         )
         .tap(|_| {
             let elapsed = guard.stop_and_record();
@@ -333,10 +593,22 @@ impl PgIndexerStore {
                 for mutated_object_change_chunk in
                     mutated_objects.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX)
                 {
-                    diesel::insert_into(objects_history::table)
-                        .values(mutated_object_change_chunk)
-                        .on_conflict_do_nothing()
-                        .execute(conn)
+                    let insert = diesel::insert_into(objects_history::table)
+                        .values(mutated_object_change_chunk);
+                    let result = if self.resync {
+                        insert
+                            .on_conflict((
+                                objects_history::checkpoint_sequence_number,
+                                objects_history::object_id,
+                                objects_history::object_version,
+                            ))
+                            .do_update()
+                            .set(objects_history_upsert_changeset!())
+                            .execute(conn)
+                    } else {
+                        insert.on_conflict_do_nothing().execute(conn)
+                    };
+                    result
                         .map_err(IndexerError::from)
                         .context("Failed to write object mutations to objects_history in DB.")?;
                 }
@@ -344,17 +616,30 @@ impl PgIndexerStore {
                 for deleted_objects_chunk in
                     deleted_object_ids.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX)
                 {
-                    diesel::insert_into(objects_history::table)
-                        .values(deleted_objects_chunk)
-                        .on_conflict_do_nothing()
-                        .execute(conn)
+                    let insert =
+                        diesel::insert_into(objects_history::table).values(deleted_objects_chunk);
+                    let result = if self.resync {
+                        insert
+                            .on_conflict((
+                                objects_history::checkpoint_sequence_number,
+                                objects_history::object_id,
+                                objects_history::object_version,
+                            ))
+                            .do_update()
+                            .set(objects_history_upsert_changeset!())
+                            .execute(conn)
+                    } else {
+                        insert.on_conflict_do_nothing().execute(conn)
+                    };
+                    result
                         .map_err(IndexerError::from)
                         .context("Failed to write object deletions to objects_history in DB.")?;
                 }
 
                 Ok::<(), IndexerError>(())
             },
-            Duration::from_secs(60)
+            Duration::from_secs(60),
+            &self.metrics
         )
         .tap(|_| {
             let elapsed = guard.stop_and_record();
@@ -377,7 +662,85 @@ impl PgIndexerStore {
                     conn,
                 )
             },
-            Duration::from_secs(10)
+            Duration::from_secs(10),
+            &self.metrics
+        )?;
+        Ok(())
+    }
+
+    fn persist_move_call_metrics(&self, day: u64) -> Result<(), IndexerError> {
+        let start_ms = day * MILLIS_PER_DAY;
+        let end_ms = start_ms + MILLIS_PER_DAY;
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                RunQueryDsl::execute(
+                    diesel::sql_query(UPDATE_MOVE_CALL_METRICS_QUERY)
+                        .bind::<diesel::sql_types::BigInt, _>(day as i64)
+                        .bind::<diesel::sql_types::BigInt, _>(start_ms as i64)
+                        .bind::<diesel::sql_types::BigInt, _>(end_ms as i64),
+                    conn,
+                )
+            },
+            Duration::from_secs(60),
+            &self.metrics
+        )?;
+        Ok(())
+    }
+
+    fn persist_shared_object_metrics(&self, day: u64) -> Result<(), IndexerError> {
+        let start_ms = day * MILLIS_PER_DAY;
+        let end_ms = start_ms + MILLIS_PER_DAY;
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                RunQueryDsl::execute(
+                    diesel::sql_query(UPDATE_SHARED_OBJECT_METRICS_QUERY)
+                        .bind::<diesel::sql_types::BigInt, _>(day as i64)
+                        .bind::<diesel::sql_types::BigInt, _>(start_ms as i64)
+                        .bind::<diesel::sql_types::BigInt, _>(end_ms as i64),
+                    conn,
+                )
+            },
+            Duration::from_secs(60),
+            &self.metrics
+        )?;
+        Ok(())
+    }
+
+    fn persist_rollups(&self, start_cp: u64, end_cp: u64) -> Result<(), IndexerError> {
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                // Active addresses are recounted from the sender metrics, so these go first.
+                for query in [
+                    UPDATE_SENDER_GAS_METRICS_QUERY,
+                    UPDATE_PACKAGE_TX_METRICS_QUERY,
+                    UPDATE_ACTIVE_ADDRESS_METRICS_QUERY,
+                ] {
+                    RunQueryDsl::execute(
+                        diesel::sql_query(query)
+                            .bind::<diesel::sql_types::BigInt, _>(start_cp as i64)
+                            .bind::<diesel::sql_types::BigInt, _>(end_cp as i64)
+                            .bind::<diesel::sql_types::BigInt, _>(MILLIS_PER_DAY as i64),
+                        conn,
+                    )?;
+                }
+                diesel::insert_into(rollup_watermarks::table)
+                    .values((
+                        rollup_watermarks::name.eq(ROLLUPS_WATERMARK),
+                        rollup_watermarks::checkpoint_sequence_number.eq(end_cp as i64),
+                    ))
+                    .on_conflict(rollup_watermarks::name)
+                    .do_update()
+                    .set(
+                        rollup_watermarks::checkpoint_sequence_number
+                            .eq(excluded(rollup_watermarks::checkpoint_sequence_number)),
+                    )
+                    .execute(conn)
+            },
+            Duration::from_secs(60),
+            &self.metrics
         )?;
         Ok(())
     }
@@ -399,16 +762,54 @@ impl PgIndexerStore {
             &self.blocking_cp,
             |conn| {
                 for checkpoint_chunk in checkpoints.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
-                    diesel::insert_into(checkpoints::table)
-                        .values(checkpoint_chunk)
-                        .on_conflict_do_nothing()
-                        .execute(conn)
+                    let insert = diesel::insert_into(checkpoints::table).values(checkpoint_chunk);
+                    let result = if self.resync {
+                        insert
+                            .on_conflict(checkpoints::sequence_number)
+                            .do_update()
+                            .set((
+                                checkpoints::checkpoint_digest
+                                    .eq(excluded(checkpoints::checkpoint_digest)),
+                                checkpoints::epoch.eq(excluded(checkpoints::epoch)),
+                                checkpoints::network_total_transactions
+                                    .eq(excluded(checkpoints::network_total_transactions)),
+                                checkpoints::previous_checkpoint_digest
+                                    .eq(excluded(checkpoints::previous_checkpoint_digest)),
+                                checkpoints::end_of_epoch.eq(excluded(checkpoints::end_of_epoch)),
+                                checkpoints::tx_digests.eq(excluded(checkpoints::tx_digests)),
+                                checkpoints::timestamp_ms.eq(excluded(checkpoints::timestamp_ms)),
+                                checkpoints::total_gas_cost
+                                    .eq(excluded(checkpoints::total_gas_cost)),
+                                checkpoints::computation_cost
+                                    .eq(excluded(checkpoints::computation_cost)),
+                                checkpoints::storage_cost.eq(excluded(checkpoints::storage_cost)),
+                                checkpoints::storage_rebate
+                                    .eq(excluded(checkpoints::storage_rebate)),
+                                checkpoints::non_refundable_storage_fee
+                                    .eq(excluded(checkpoints::non_refundable_storage_fee)),
+                                checkpoints::checkpoint_commitments
+                                    .eq(excluded(checkpoints::checkpoint_commitments)),
+                                checkpoints::validator_signature
+                                    .eq(excluded(checkpoints::validator_signature)),
+                                checkpoints::end_of_epoch_data
+                                    .eq(excluded(checkpoints::end_of_epoch_data)),
+                                checkpoints::checkpoint_summary
+                                    .eq(excluded(checkpoints::checkpoint_summary)),
+                                checkpoints::checkpoint_contents
+                                    .eq(excluded(checkpoints::checkpoint_contents)),
+                            ))
+                            .execute(conn)
+                    } else {
+                        insert.on_conflict_do_nothing().execute(conn)
+                    };
+                    result
                         .map_err(IndexerError::from)
                         .context("Failed to write checkpoints to PostgresDB")?;
                 }
                 Ok::<(), IndexerError>(())
             },
-            Duration::from_secs(60)
+            Duration::from_secs(60),
+            &self.metrics
         )
         .tap(|_| {
             let elapsed = guard.stop_and_record();
@@ -438,16 +839,43 @@ impl PgIndexerStore {
             &self.blocking_cp,
             |conn| {
                 for transaction_chunk in transactions.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
-                    diesel::insert_into(transactions::table)
-                        .values(transaction_chunk)
-                        .on_conflict_do_nothing()
-                        .execute(conn)
+                    let insert = diesel::insert_into(transactions::table).values(transaction_chunk);
+                    let result = if self.resync {
+                        insert
+                            .on_conflict((
+                                transactions::tx_sequence_number,
+                                transactions::checkpoint_sequence_number,
+                            ))
+                            .do_update()
+                            .set((
+                                transactions::transaction_digest
+                                    .eq(excluded(transactions::transaction_digest)),
+                                transactions::raw_transaction
+                                    .eq(excluded(transactions::raw_transaction)),
+                                transactions::raw_effects.eq(excluded(transactions::raw_effects)),
+                                transactions::timestamp_ms.eq(excluded(transactions::timestamp_ms)),
+                                transactions::object_changes
+                                    .eq(excluded(transactions::object_changes)),
+                                transactions::balance_changes
+                                    .eq(excluded(transactions::balance_changes)),
+                                transactions::events.eq(excluded(transactions::events)),
+                                transactions::transaction_kind
+                                    .eq(excluded(transactions::transaction_kind)),
+                                transactions::success_command_count
+                                    .eq(excluded(transactions::success_command_count)),
+                            ))
+                            .execute(conn)
+                    } else {
+                        insert.on_conflict_do_nothing().execute(conn)
+                    };
+                    result
                         .map_err(IndexerError::from)
                         .context("Failed to write transactions to PostgresDB")?;
                 }
                 Ok::<(), IndexerError>(())
             },
-            Duration::from_secs(60)
+            Duration::from_secs(60),
+            &self.metrics
         )
         .tap(|_| {
             let elapsed = guard.stop_and_record();
@@ -474,16 +902,37 @@ impl PgIndexerStore {
             &self.blocking_cp,
             |conn| {
                 for event_chunk in events.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
-                    diesel::insert_into(events::table)
-                        .values(event_chunk)
-                        .on_conflict_do_nothing()
-                        .execute(conn)
+                    let insert = diesel::insert_into(events::table).values(event_chunk);
+                    let result = if self.resync {
+                        insert
+                            .on_conflict((
+                                events::tx_sequence_number,
+                                events::event_sequence_number,
+                            ))
+                            .do_update()
+                            .set((
+                                events::transaction_digest.eq(excluded(events::transaction_digest)),
+                                events::checkpoint_sequence_number
+                                    .eq(excluded(events::checkpoint_sequence_number)),
+                                events::senders.eq(excluded(events::senders)),
+                                events::package.eq(excluded(events::package)),
+                                events::module.eq(excluded(events::module)),
+                                events::event_type.eq(excluded(events::event_type)),
+                                events::timestamp_ms.eq(excluded(events::timestamp_ms)),
+                                events::bcs.eq(excluded(events::bcs)),
+                            ))
+                            .execute(conn)
+                    } else {
+                        insert.on_conflict_do_nothing().execute(conn)
+                    };
+                    result
                         .map_err(IndexerError::from)
                         .context("Failed to write events to PostgresDB")?;
                 }
                 Ok::<(), IndexerError>(())
             },
-            Duration::from_secs(60)
+            Duration::from_secs(60),
+            &self.metrics
         )
         .tap(|_| {
             let elapsed = guard.stop_and_record();
@@ -521,7 +970,8 @@ impl PgIndexerStore {
                 }
                 Ok::<(), IndexerError>(())
             },
-            Duration::from_secs(60)
+            Duration::from_secs(60),
+            &self.metrics
         )
         .tap(|_| {
             let elapsed = guard.stop_and_record();
@@ -535,15 +985,23 @@ impl PgIndexerStore {
             .checkpoint_db_commit_latency_tx_indices_chunks
             .start_timer();
         let len = indices.len();
-        let (senders, recipients, input_objects, changed_objects, calls) =
+        let (senders, recipients, input_objects, changed_objects, calls, shared_objects) =
             indices.into_iter().map(|i| i.split()).fold(
-                (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new()),
+                (
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                ),
                 |(
                     mut tx_senders,
                     mut tx_recipients,
                     mut tx_input_objects,
                     mut tx_changed_objects,
                     mut tx_calls,
+                    mut tx_shared_objects,
                 ),
                  index| {
                     tx_senders.extend(index.0);
@@ -551,6 +1009,7 @@ impl PgIndexerStore {
                     tx_input_objects.extend(index.2);
                     tx_changed_objects.extend(index.3);
                     tx_calls.extend(index.4);
+                    tx_shared_objects.extend(index.5);
 
                     (
                         tx_senders,
@@ -558,6 +1017,7 @@ impl PgIndexerStore {
                         tx_input_objects,
                         tx_changed_objects,
                         tx_calls,
+                        tx_shared_objects,
                     )
                 },
             );
@@ -586,9 +1046,11 @@ impl PgIndexerStore {
                             .map_err(IndexerError::from)
                             .context("Failed to write tx_recipients to PostgresDB")?;
                     }
+                    update_hot_tx_address_buckets(conn, &senders, &recipients)?;
                     Ok::<(), IndexerError>(())
                 },
-                Duration::from_secs(60)
+                Duration::from_secs(60),
+                &this.metrics
             )
             .tap(|_| {
                 let elapsed = now.elapsed().as_secs_f64();
@@ -616,7 +1078,8 @@ impl PgIndexerStore {
                     }
                     Ok::<(), IndexerError>(())
                 },
-                Duration::from_secs(60)
+                Duration::from_secs(60),
+                &this.metrics
             )
             .tap(|_| {
                 let elapsed = now.elapsed().as_secs_f64();
@@ -643,7 +1106,8 @@ impl PgIndexerStore {
                     }
                     Ok::<(), IndexerError>(())
                 },
-                Duration::from_secs(60)
+                Duration::from_secs(60),
+                &this.metrics
             )
             .tap(|_| {
                 let elapsed = now.elapsed().as_secs_f64();
@@ -660,32 +1124,79 @@ impl PgIndexerStore {
                 &this.blocking_cp,
                 |conn| {
                     for chunk in calls.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
-                        diesel::insert_into(tx_calls::table)
-                            .values(chunk)
-                            .on_conflict_do_nothing()
-                            .execute(conn)
+                        let insert = diesel::insert_into(tx_calls::table).values(chunk);
+                        let result = if this.resync {
+                            insert
+                                .on_conflict((tx_calls::package, tx_calls::tx_sequence_number))
+                                .do_update()
+                                .set((
+                                    tx_calls::module.eq(excluded(tx_calls::module)),
+                                    tx_calls::func.eq(excluded(tx_calls::func)),
+                                    tx_calls::gas_used.eq(excluded(tx_calls::gas_used)),
+                                ))
+                                .execute(conn)
+                        } else {
+                            insert.on_conflict_do_nothing().execute(conn)
+                        };
+                        result
                             .map_err(IndexerError::from)
                             .context("Failed to write tx_calls chunk to PostgresDB")?;
                     }
                     Ok::<(), IndexerError>(())
                 },
-                Duration::from_secs(60)
+                Duration::from_secs(60),
+                &this.metrics
             )
             .tap(|_| {
                 let elapsed = now.elapsed().as_secs_f64();
                 info!(elapsed, "Persisted {} rows to tx_calls tables", calls_len);
             })
         }));
-        futures::future::join_all(futures)
+        futures.push(self.spawn_blocking_task(move |this| {
+            let now = Instant::now();
+            let shared_objects_len = shared_objects.len();
+            transactional_blocking_with_retry!(
+                &this.blocking_cp,
+                |conn| {
+                    for chunk in shared_objects.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                        let insert = diesel::insert_into(tx_shared_objects::table).values(chunk);
+                        let result = if this.resync {
+                            insert
+                                .on_conflict((
+                                    tx_shared_objects::object_id,
+                                    tx_shared_objects::tx_sequence_number,
+                                ))
+                                .do_update()
+                                .set((
+                                    tx_shared_objects::mutable
+                                        .eq(excluded(tx_shared_objects::mutable)),
+                                    tx_shared_objects::success
+                                        .eq(excluded(tx_shared_objects::success)),
+                                ))
+                                .execute(conn)
+                        } else {
+                            insert.on_conflict_do_nothing().execute(conn)
+                        };
+                        result
+                            .map_err(IndexerError::from)
+                            .context("Failed to write tx_shared_objects chunk to PostgresDB")?;
+                    }
+                    Ok::<(), IndexerError>(())
+                },
+                Duration::from_secs(60),
+                &this.metrics
+            )
+            .tap(|_| {
+                let elapsed = now.elapsed().as_secs_f64();
+                info!(
+                    elapsed,
+                    "Persisted {} rows to tx_shared_objects table", shared_objects_len,
+                );
+            })
+        }));
+        join_all_blocking_tasks(futures)
             .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to persist all tx_indices chunks: {:?}",
-                    e
-                ))
-            })?;
+            .context("Failed to persist all tx_indices chunks")?;
         let elapsed = guard.stop_and_record();
         info!(elapsed, "Persisted {} chunked tx_indices", len);
         Ok(())
@@ -740,7 +1251,8 @@ impl PgIndexerStore {
                     .execute(conn)?;
                 Ok::<(), IndexerError>(())
             },
-            Duration::from_secs(60)
+            Duration::from_secs(60),
+            &self.metrics
         )
         .tap(|_| {
             let elapsed = guard.stop_and_record();
@@ -749,6 +1261,9 @@ impl PgIndexerStore {
     }
 
     fn advance_epoch(&self, epoch_to_commit: EpochToCommit) -> Result<(), IndexerError> {
+        if self.resync {
+            return Ok(());
+        }
         let last_epoch_id = epoch_to_commit.last_epoch.as_ref().map(|e| e.epoch);
         // partition_0 has been created, so no need to advance it.
         if let Some(last_epoch_id) = last_epoch_id {
@@ -787,6 +1302,67 @@ impl PgIndexerStore {
         Ok(())
     }
 
+    fn persist_quarantined_checkpoint(
+        &self,
+        checkpoint: StoredQuarantinedCheckpoint,
+    ) -> Result<(), IndexerError> {
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                diesel::insert_into(quarantined_checkpoints::table)
+                    .values(&checkpoint)
+                    .on_conflict(quarantined_checkpoints::checkpoint_sequence_number)
+                    .do_update()
+                    .set((
+                        quarantined_checkpoints::error.eq(excluded(quarantined_checkpoints::error)),
+                        quarantined_checkpoints::attempts
+                            .eq(excluded(quarantined_checkpoints::attempts)),
+                        quarantined_checkpoints::context
+                            .eq(excluded(quarantined_checkpoints::context)),
+                        quarantined_checkpoints::quarantined_at_ms
+                            .eq(excluded(quarantined_checkpoints::quarantined_at_ms)),
+                    ))
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed to write quarantined checkpoint to PostgresDB")?;
+                Ok::<(), IndexerError>(())
+            },
+            Duration::from_secs(60),
+            &self.metrics
+        )?;
+        Ok(())
+    }
+
+    fn persist_epoch_consistency_report(
+        &self,
+        report: StoredEpochConsistencyReport,
+    ) -> Result<(), IndexerError> {
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                diesel::insert_into(epoch_consistency_reports::table)
+                    .values(&report)
+                    .on_conflict(epoch_consistency_reports::epoch)
+                    .do_update()
+                    .set((
+                        epoch_consistency_reports::discrepancies
+                            .eq(excluded(epoch_consistency_reports::discrepancies)),
+                        epoch_consistency_reports::report
+                            .eq(excluded(epoch_consistency_reports::report)),
+                        epoch_consistency_reports::checked_at_ms
+                            .eq(excluded(epoch_consistency_reports::checked_at_ms)),
+                    ))
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed to write epoch consistency report to PostgresDB")?;
+                Ok::<(), IndexerError>(())
+            },
+            Duration::from_secs(60),
+            &self.metrics
+        )?;
+        Ok(())
+    }
+
     fn get_network_total_transactions_by_end_of_epoch(
         &self,
         epoch: u64,
@@ -802,6 +1378,226 @@ impl PgIndexerStore {
         .map(|v| v as u64)
     }
 
+    fn get_latest_epoch(&self) -> Result<Option<u64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            epochs::table
+                .select(max(epochs::epoch))
+                .first::<Option<i64>>(conn)
+        })
+        .context("Failed reading latest epoch from PostgresDB")
+        .map(|e| e.map(|e| e as u64))
+    }
+
+    fn get_epoch_info(&self, epoch: u64) -> Result<Option<StoredEpochInfo>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            epochs::table
+                .filter(epochs::epoch.eq(epoch as i64))
+                .first::<StoredEpochInfo>(conn)
+                .optional()
+        })
+        .context("Failed reading epoch from PostgresDB")
+    }
+
+    fn prune_epochs(
+        &self,
+        tables: PrunableTables,
+        before_epoch: u64,
+        archive: bool,
+    ) -> Result<Vec<(&'static str, u64)>, IndexerError> {
+        // The first transaction of `before_epoch`: all transactions before it are pruned.
+        let before_tx = self.get_network_total_transactions_by_end_of_epoch(before_epoch - 1)?;
+        Ok(match tables {
+            PrunableTables::Transactions => {
+                let mut pruned = vec![(
+                    "transactions",
+                    self.prune_epoch_partitions("transactions", before_epoch, archive)?,
+                )];
+                for table in TX_INDEX_TABLES {
+                    pruned.push((table, self.prune_rows_before_tx(table, before_tx)?));
+                }
+                pruned
+            }
+            PrunableTables::Events => {
+                vec![("events", self.prune_rows_before_tx("events", before_tx)?)]
+            }
+            PrunableTables::ObjectsHistory => vec![(
+                "objects_history",
+                self.prune_epoch_partitions("objects_history", before_epoch, archive)?,
+            )],
+        })
+    }
+
+    /// Prune the partitions of `table` for epochs before `before_epoch`, returning an estimate of
+    /// the number of rows pruned.
+    fn prune_epoch_partitions(
+        &self,
+        table: &str,
+        before_epoch: u64,
+        archive: bool,
+    ) -> Result<u64, IndexerError> {
+        let mut pruned = 0;
+        for (epoch, rows) in self.partition_manager.get_epoch_partitions(table)? {
+            if epoch >= before_epoch {
+                break;
+            }
+            self.partition_manager
+                .prune_table_epoch_partition(table, epoch, archive)?;
+            pruned += rows;
+        }
+        Ok(pruned)
+    }
+
+    /// Delete the rows of `table` for transactions before `before_tx`, in batches, so that each
+    /// batch is deleted in a short transaction. Returns the number of rows deleted.
+    fn prune_rows_before_tx(&self, table: &str, before_tx: u64) -> Result<u64, IndexerError> {
+        let query = format!(
+            "DELETE FROM {table} WHERE ctid = ANY(ARRAY(\
+                SELECT ctid FROM {table} WHERE tx_sequence_number < $1 LIMIT $2\
+            ))"
+        );
+
+        let mut pruned = 0;
+        loop {
+            let deleted = transactional_blocking_with_retry!(
+                &self.blocking_cp,
+                |conn| {
+                    RunQueryDsl::execute(
+                        diesel::sql_query(&query)
+                            .bind::<diesel::sql_types::BigInt, _>(before_tx as i64)
+                            .bind::<diesel::sql_types::BigInt, _>(PRUNE_BATCH_SIZE as i64),
+                        conn,
+                    )
+                },
+                Duration::from_secs(60),
+                &self.metrics
+            )
+            .context(&format!("Failed to prune rows from {table}"))?;
+
+            pruned += deleted as u64;
+            if deleted < PRUNE_BATCH_SIZE {
+                return Ok(pruned);
+            }
+        }
+    }
+
+    fn get_hot_addresses_to_backfill(&self) -> Result<Vec<StoredHotAddress>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            hot_addresses::table
+                .filter(hot_addresses::backfilled.eq(false))
+                .load::<StoredHotAddress>(conn)
+        })
+        .context("Failed reading hot addresses to backfill from PostgresDB")
+    }
+
+    fn backfill_tx_address_bucket(
+        &self,
+        address: Vec<u8>,
+        below_bucket: Option<u64>,
+    ) -> Result<Option<u64>, IndexerError> {
+        let below = below_bucket.map_or(i64::MAX, |b| (b * TX_ADDRESS_BUCKET_SIZE) as i64);
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                let sent: Option<i64> = tx_senders::table
+                    .select(max(tx_senders::tx_sequence_number))
+                    .filter(tx_senders::sender.eq(address.as_slice()))
+                    .filter(tx_senders::tx_sequence_number.lt(below))
+                    .first(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed reading address's latest sent transaction from PostgresDB")?;
+                let received: Option<i64> = tx_recipients::table
+                    .select(max(tx_recipients::tx_sequence_number))
+                    .filter(tx_recipients::recipient.eq(address.as_slice()))
+                    .filter(tx_recipients::tx_sequence_number.lt(below))
+                    .first(conn)
+                    .map_err(IndexerError::from)
+                    .context(
+                        "Failed reading address's latest received transaction from PostgresDB",
+                    )?;
+
+                let target =
+                    hot_addresses::table.filter(hot_addresses::address.eq(address.as_slice()));
+
+                // There are no earlier transactions left to count.
+                let Some(latest) = sent.max(received) else {
+                    diesel::update(target)
+                        .set(hot_addresses::backfilled.eq(true))
+                        .execute(conn)
+                        .map_err(IndexerError::from)
+                        .context("Failed to mark hot address as backfilled")?;
+                    return Ok::<_, IndexerError>(None);
+                };
+
+                let bucket = tx_address_bucket(latest as u64);
+                update_tx_address_bucket(conn, &address, bucket)?;
+                diesel::update(target)
+                    .set(hot_addresses::backfilled_from_bucket.eq(bucket as i64))
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed to record hot address backfill progress")?;
+                Ok(Some(bucket))
+            },
+            Duration::from_secs(60),
+            &self.metrics
+        )
+    }
+
+    fn get_displays(
+        &self,
+        object_types: Vec<String>,
+    ) -> Result<BTreeMap<String, StoredDisplay>, IndexerError> {
+        let displays = read_only_blocking!(&self.blocking_cp, |conn| {
+            display::table
+                .filter(display::object_type.eq_any(object_types))
+                .load::<StoredDisplay>(conn)
+        })
+        .context("Failed reading displays from PostgresDB")?;
+        Ok(displays
+            .into_iter()
+            .map(|d| (d.object_type.clone(), d))
+            .collect())
+    }
+
+    fn get_objects_to_resolve(
+        &self,
+        after_object_id: Option<Vec<u8>>,
+        limit: usize,
+    ) -> Result<Vec<StoredObject>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            diesel::sql_query(OBJECTS_TO_RESOLVE_QUERY)
+                .bind::<diesel::sql_types::Bytea, _>(after_object_id.unwrap_or_default())
+                .bind::<diesel::sql_types::BigInt, _>(limit as i64)
+                .load::<StoredObject>(conn)
+        })
+        .context("Failed reading objects to resolve from PostgresDB")
+    }
+
+    fn persist_resolved_objects(&self, objects: Vec<StoredObject>) -> Result<(), IndexerError> {
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                for object in &objects {
+                    diesel::update(
+                        objects::table
+                            .filter(objects::object_id.eq(object.object_id.as_slice()))
+                            .filter(objects::object_version.eq(object.object_version)),
+                    )
+                    .set((
+                        objects::df_bcs_name.eq(object.df_bcs_name.as_deref()),
+                        objects::display_version.eq(object.display_version),
+                        objects::display_fields.eq(object.display_fields.as_deref()),
+                    ))
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed to write resolved objects to PostgresDB")?;
+                }
+                Ok::<(), IndexerError>(())
+            },
+            Duration::from_secs(60),
+            &self.metrics
+        )
+    }
+
     async fn execute_in_blocking_worker<F, R>(&self, f: F) -> Result<R, IndexerError>
     where
         F: FnOnce(Self) -> Result<R, IndexerError> + Send + 'static,
@@ -850,6 +1646,7 @@ impl PgIndexerStore {
 #[async_trait]
 impl IndexerStore for PgIndexerStore {
     type ModuleCache = SyncModuleCache<IndexerStorePackageModuleResolver>;
+    type PackageResolver = IndexerStorePackageModuleResolver;
 
     async fn get_latest_tx_checkpoint_sequence_number(&self) -> Result<Option<u64>, IndexerError> {
         self.execute_in_blocking_worker(|this| this.get_latest_tx_checkpoint_sequence_number())
@@ -865,6 +1662,26 @@ impl IndexerStore for PgIndexerStore {
         .await
     }
 
+    async fn get_latest_move_call_metrics_day(&self) -> Result<Option<u64>, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_latest_move_call_metrics_day())
+            .await
+    }
+
+    async fn get_latest_shared_object_metrics_day(&self) -> Result<Option<u64>, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_latest_shared_object_metrics_day())
+            .await
+    }
+
+    async fn get_next_rollups_checkpoint(&self) -> Result<Option<u64>, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_next_rollups_checkpoint())
+            .await
+    }
+
+    async fn get_checkpoint_timestamp_range_ms(&self) -> Result<Option<(u64, u64)>, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_checkpoint_timestamp_range_ms())
+            .await
+    }
+
     async fn get_object_read(
         &self,
         object_id: ObjectID,
@@ -878,7 +1695,7 @@ impl IndexerStore for PgIndexerStore {
         &self,
         object_changes: Vec<TransactionObjectChangesToCommit>,
     ) -> Result<(), IndexerError> {
-        if object_changes.is_empty() {
+        if object_changes.is_empty() || self.resync {
             return Ok(());
         }
         let guard = self
@@ -893,16 +1710,9 @@ impl IndexerStore for PgIndexerStore {
             .map(|c| self.spawn_blocking_task(move |this| this.persist_objects_chunk(c)))
             .collect::<Vec<_>>();
 
-        futures::future::join_all(futures)
+        join_all_blocking_tasks(futures)
             .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to persist all objects chunks: {:?}",
-                    e
-                ))
-            })?;
+            .context("Failed to persist all objects chunks")?;
         let elapsed = guard.stop_and_record();
         info!(elapsed, "Persisted {} objects", len);
         Ok(())
@@ -936,16 +1746,9 @@ impl IndexerStore for PgIndexerStore {
             .map(|c| self.spawn_blocking_task(move |this| this.persist_objects_history_chunk(c)))
             .collect::<Vec<_>>();
 
-        futures::future::join_all(futures)
+        join_all_blocking_tasks(futures)
             .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to persist all objects history chunks: {:?}",
-                    e
-                ))
-            })?;
+            .context("Failed to persist all objects history chunks")?;
         let elapsed = guard.stop_and_record();
         info!(elapsed, "Persisted {} objects history", len);
         Ok(())
@@ -968,12 +1771,9 @@ impl IndexerStore for PgIndexerStore {
 
         self.spawn_blocking_task(move |this| this.persist_object_snapshot(start_cp, end_cp))
             .await
-            .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to update objects snapshot: {:?}",
-                    e
-                ))
-            })??;
+            .map_err(IndexerError::from)
+            .and_then(|r| r)
+            .context("Failed to update objects snapshot")?;
         let elapsed = guard.stop_and_record();
         info!(
             elapsed,
@@ -982,6 +1782,119 @@ impl IndexerStore for PgIndexerStore {
         Ok(())
     }
 
+    async fn persist_move_call_metrics(&self, day: u64) -> Result<(), IndexerError> {
+        let guard = self.metrics.update_move_call_metrics_latency.start_timer();
+        self.spawn_blocking_task(move |this| this.persist_move_call_metrics(day))
+            .await
+            .map_err(IndexerError::from)
+            .and_then(|r| r)
+            .context("Failed to update move call metrics")?;
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted move call metrics for day {}", day);
+        Ok(())
+    }
+
+    async fn persist_shared_object_metrics(&self, day: u64) -> Result<(), IndexerError> {
+        let guard = self
+            .metrics
+            .update_shared_object_metrics_latency
+            .start_timer();
+        self.spawn_blocking_task(move |this| this.persist_shared_object_metrics(day))
+            .await
+            .map_err(IndexerError::from)
+            .and_then(|r| r)
+            .context("Failed to update shared object metrics")?;
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted shared object metrics for day {}", day);
+        Ok(())
+    }
+
+    async fn persist_rollups(&self, start_cp: u64, end_cp: u64) -> Result<(), IndexerError> {
+        let guard = self.metrics.update_rollups_latency.start_timer();
+        self.spawn_blocking_task(move |this| this.persist_rollups(start_cp, end_cp))
+            .await
+            .map_err(IndexerError::from)
+            .and_then(|r| r)
+            .context("Failed to update rollups")?;
+        let elapsed = guard.stop_and_record();
+        info!(
+            elapsed,
+            "Persisted rollups for checkpoints {} to {}", start_cp, end_cp
+        );
+        Ok(())
+    }
+
+    async fn get_latest_epoch(&self) -> Result<Option<u64>, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_latest_epoch())
+            .await
+    }
+
+    async fn get_epoch_info(&self, epoch: u64) -> Result<Option<StoredEpochInfo>, IndexerError> {
+        self.execute_in_blocking_worker(move |this| this.get_epoch_info(epoch))
+            .await
+    }
+
+    async fn prune_epochs(
+        &self,
+        tables: PrunableTables,
+        before_epoch: u64,
+        archive: bool,
+    ) -> Result<Vec<(&'static str, u64)>, IndexerError> {
+        self.execute_in_blocking_worker(move |this| {
+            this.prune_epochs(tables, before_epoch, archive)
+        })
+        .await
+    }
+
+    async fn get_hot_addresses_to_backfill(&self) -> Result<Vec<StoredHotAddress>, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_hot_addresses_to_backfill())
+            .await
+    }
+
+    async fn backfill_tx_address_bucket(
+        &self,
+        address: Vec<u8>,
+        below_bucket: Option<u64>,
+    ) -> Result<Option<u64>, IndexerError> {
+        self.execute_in_blocking_worker(move |this| {
+            this.backfill_tx_address_bucket(address, below_bucket)
+        })
+        .await
+    }
+
+    async fn get_displays(
+        &self,
+        object_types: Vec<String>,
+    ) -> Result<BTreeMap<String, StoredDisplay>, IndexerError> {
+        if object_types.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+        self.execute_in_blocking_worker(move |this| this.get_displays(object_types))
+            .await
+    }
+
+    async fn get_objects_to_resolve(
+        &self,
+        after_object_id: Option<Vec<u8>>,
+        limit: usize,
+    ) -> Result<Vec<StoredObject>, IndexerError> {
+        self.execute_in_blocking_worker(move |this| {
+            this.get_objects_to_resolve(after_object_id, limit)
+        })
+        .await
+    }
+
+    async fn persist_resolved_objects(
+        &self,
+        objects: Vec<StoredObject>,
+    ) -> Result<(), IndexerError> {
+        if objects.is_empty() {
+            return Ok(());
+        }
+        self.execute_in_blocking_worker(move |this| this.persist_resolved_objects(objects))
+            .await
+    }
+
     async fn persist_checkpoints(
         &self,
         checkpoints: Vec<IndexedCheckpoint>,
@@ -1006,16 +1919,9 @@ impl IndexerStore for PgIndexerStore {
             .map(|c| self.spawn_blocking_task(move |this| this.persist_transactions_chunk(c)))
             .collect::<Vec<_>>();
 
-        futures::future::join_all(futures)
+        join_all_blocking_tasks(futures)
             .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to persist all transactions chunks: {:?}",
-                    e
-                ))
-            })?;
+            .context("Failed to persist all transactions chunks")?;
         let elapsed = guard.stop_and_record();
         info!(elapsed, "Persisted {} transactions", len);
         Ok(())
@@ -1036,16 +1942,9 @@ impl IndexerStore for PgIndexerStore {
             .map(|c| self.spawn_blocking_task(move |this| this.persist_events_chunk(c)))
             .collect::<Vec<_>>();
 
-        futures::future::join_all(futures)
+        join_all_blocking_tasks(futures)
             .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to persist all events chunks: {:?}",
-                    e
-                ))
-            })?;
+            .context("Failed to persist all events chunks")?;
         let elapsed = guard.stop_and_record();
         info!(elapsed, "Persisted {} events", len);
         Ok(())
@@ -1055,7 +1954,7 @@ impl IndexerStore for PgIndexerStore {
         &self,
         display_updates: BTreeMap<String, StoredDisplay>,
     ) -> Result<(), IndexerError> {
-        if display_updates.is_empty() {
+        if display_updates.is_empty() || self.resync {
             return Ok(());
         }
 
@@ -1090,16 +1989,9 @@ impl IndexerStore for PgIndexerStore {
                 })
             })
             .collect::<Vec<_>>();
-        futures::future::join_all(futures)
+        join_all_blocking_tasks(futures)
             .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to persist all tx_indices chunks: {:?}",
-                    e
-                ))
-            })?;
+            .context("Failed to persist all tx_indices chunks")?;
         let elapsed = guard.stop_and_record();
         info!(elapsed, "Persisted {} tx_indices", len);
         Ok(())
@@ -1115,6 +2007,22 @@ impl IndexerStore for PgIndexerStore {
             .await
     }
 
+    async fn persist_quarantined_checkpoint(
+        &self,
+        checkpoint: StoredQuarantinedCheckpoint,
+    ) -> Result<(), IndexerError> {
+        self.execute_in_blocking_worker(move |this| this.persist_quarantined_checkpoint(checkpoint))
+            .await
+    }
+
+    async fn persist_epoch_consistency_report(
+        &self,
+        report: StoredEpochConsistencyReport,
+    ) -> Result<(), IndexerError> {
+        self.execute_in_blocking_worker(move |this| this.persist_epoch_consistency_report(report))
+            .await
+    }
+
     async fn get_network_total_transactions_by_end_of_epoch(
         &self,
         epoch: u64,
@@ -1129,76 +2037,97 @@ impl IndexerStore for PgIndexerStore {
         self.module_cache.clone()
     }
 
+    fn package_resolver(&self) -> Self::PackageResolver {
+        IndexerStorePackageModuleResolver::new(self.blocking_cp.clone())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
 }
 
-/// Construct deleted objects and mutated objects to commit.
-/// In particular, filter mutated objects updates that would
-/// be override immediately.
-fn make_final_list_of_objects_to_commit(
-    tx_object_changes: Vec<TransactionObjectChangesToCommit>,
-) -> Vec<ObjectChangeToCommit> {
-    let deleted_objects = tx_object_changes
-        .clone()
-        .into_iter()
-        .flat_map(|changes| changes.deleted_objects)
-        .map(|o| (o.object_id, o.into()))
-        .collect::<HashMap<ObjectID, StoredDeletedObject>>();
-
-    let mutated_objects = tx_object_changes
+/// Waits for the tasks spawned with `PgIndexerStore::spawn_blocking_task`, failing if any of them
+/// failed or panicked.
+async fn join_all_blocking_tasks<R>(
+    tasks: Vec<tokio::task::JoinHandle<Result<R, IndexerError>>>,
+) -> Result<Vec<R>, IndexerError> {
+    futures::future::join_all(tasks)
+        .await
         .into_iter()
-        .flat_map(|changes| changes.changed_objects);
-    let mut latest_objects = HashMap::new();
-    for object in mutated_objects {
-        if deleted_objects.contains_key(&object.object_id) {
-            continue;
-        }
-        match latest_objects.entry(object.object_id) {
-            Entry::Vacant(e) => {
-                e.insert(object);
-            }
-            Entry::Occupied(mut e) => {
-                if object.object_version > e.get().object_version {
-                    e.insert(object);
-                }
-            }
-        }
-    }
-    deleted_objects
-        .into_values()
-        .map(ObjectChangeToCommit::DeletedObject)
-        .chain(
-            latest_objects
-                .into_values()
-                .map(StoredObject::from)
-                .map(ObjectChangeToCommit::MutatedObject),
-        )
+        .map(|r| r.map_err(IndexerError::from).and_then(|r| r))
         .collect()
 }
 
-fn make_objects_history_to_commit(
-    tx_object_changes: Vec<TransactionObjectChangesToCommit>,
-) -> Vec<ObjectChangeToCommit> {
-    let deleted_objects: Vec<StoredDeletedObject> = tx_object_changes
-        .clone()
+/// Recount the buckets of `tx_address_buckets` holding the transactions in `senders` and
+/// `recipients` that were sent or received by hot addresses, so that their counts include these
+/// transactions. Must be called in the same DB transaction that commits them.
+fn update_hot_tx_address_buckets(
+    conn: &mut PgConnection,
+    senders: &[StoredTxSenders],
+    recipients: &[StoredTxRecipients],
+) -> Result<(), IndexerError> {
+    let addresses: Vec<Vec<u8>> = senders
+        .iter()
+        .map(|s| &s.sender)
+        .chain(recipients.iter().map(|r| &r.recipient))
+        .collect::<BTreeSet<_>>()
         .into_iter()
-        .flat_map(|changes| changes.deleted_objects)
-        .map(|o| o.into())
+        .cloned()
         .collect();
-    let mutated_objects: Vec<StoredObject> = tx_object_changes
+
+    if addresses.is_empty() {
+        return Ok(());
+    }
+
+    let hot: BTreeSet<Vec<u8>> = hot_addresses::table
+        .select(hot_addresses::address)
+        .filter(hot_addresses::address.eq_any(addresses))
+        .load::<Vec<u8>>(conn)
+        .map_err(IndexerError::from)
+        .context("Failed reading hot addresses from PostgresDB")?
         .into_iter()
-        .flat_map(|changes| changes.changed_objects)
-        .map(|o| o.into())
         .collect();
-    deleted_objects
-        .into_iter()
-        .map(ObjectChangeToCommit::DeletedObject)
+
+    if hot.is_empty() {
+        return Ok(());
+    }
+
+    let buckets: BTreeSet<(&[u8], u64)> = senders
+        .iter()
+        .map(|s| (&s.sender[..], s.tx_sequence_number))
         .chain(
-            mutated_objects
-                .into_iter()
-                .map(ObjectChangeToCommit::MutatedObject),
+            recipients
+                .iter()
+                .map(|r| (&r.recipient[..], r.tx_sequence_number)),
         )
-        .collect()
+        .filter(|(address, _)| hot.contains(*address))
+        .map(|(address, tx)| (address, tx_address_bucket(tx as u64)))
+        .collect();
+
+    for (address, bucket) in buckets {
+        update_tx_address_bucket(conn, address, bucket)?;
+    }
+
+    Ok(())
+}
+
+/// Recount the transactions sent and received by `address` in `bucket` of `tx_address_buckets`.
+fn update_tx_address_bucket(
+    conn: &mut PgConnection,
+    address: &[u8],
+    bucket: u64,
+) -> Result<(), IndexerError> {
+    let lo = bucket * TX_ADDRESS_BUCKET_SIZE;
+    let hi = lo + TX_ADDRESS_BUCKET_SIZE;
+    RunQueryDsl::execute(
+        diesel::sql_query(UPDATE_TX_ADDRESS_BUCKET_QUERY)
+            .bind::<diesel::sql_types::Bytea, _>(address)
+            .bind::<diesel::sql_types::BigInt, _>(bucket as i64)
+            .bind::<diesel::sql_types::BigInt, _>(lo as i64)
+            .bind::<diesel::sql_types::BigInt, _>(hi as i64),
+        conn,
+    )
+    .map_err(IndexerError::from)
+    .context("Failed to update tx address bucket")?;
+    Ok(())
 }
@@ -25,6 +25,16 @@ WHERE parent.relkind = 'p'
 GROUP BY table_name;
 ";
 
+const GET_EPOCH_PARTITIONS_SQL: &str = r"
+SELECT CAST(SUBSTRING(child.relname FROM '\d+$') AS BIGINT) AS epoch,
+       GREATEST(CAST(child.reltuples AS BIGINT), 0)         AS estimated_rows
+FROM pg_inherits
+         JOIN pg_class parent ON pg_inherits.inhparent = parent.oid
+         JOIN pg_class child ON pg_inherits.inhrelid = child.oid
+WHERE parent.relname = $1
+ORDER BY epoch;
+";
+
 #[derive(Clone)]
 pub struct PgPartitionManager {
     cp: PgConnectionPool,
@@ -85,6 +95,64 @@ impl PgPartitionManager {
         )
     }
 
+    /// Returns the epochs that `table` has partitions for, with an estimate of the number of rows
+    /// in each partition (from the planner's statistics, so that it is cheap to get).
+    pub fn get_epoch_partitions(&self, table: &str) -> Result<Vec<(u64, u64)>, IndexerError> {
+        #[derive(QueryableByName, Debug, Clone)]
+        struct EpochPartition {
+            #[diesel(sql_type = BigInt)]
+            epoch: i64,
+            #[diesel(sql_type = BigInt)]
+            estimated_rows: i64,
+        }
+
+        Ok(
+            read_only_blocking!(&self.cp, |conn| diesel::RunQueryDsl::load(
+                diesel::sql_query(GET_EPOCH_PARTITIONS_SQL)
+                    .bind::<diesel::sql_types::Text, _>(table),
+                conn
+            ))?
+            .into_iter()
+            .map(|p: EpochPartition| (p.epoch as u64, p.estimated_rows as u64))
+            .collect(),
+        )
+    }
+
+    /// Detach `table`'s partition for `epoch`, and drop it, unless `archive` is set, in which case
+    /// it is kept as a standalone table (`<table>_partition_<epoch>`), to be archived.
+    pub fn prune_table_epoch_partition(
+        &self,
+        table: &str,
+        epoch: u64,
+        archive: bool,
+    ) -> Result<(), IndexerError> {
+        let partition = format!("{table}_partition_{epoch}");
+        transactional_blocking_with_retry!(
+            &self.cp,
+            |conn| {
+                RunQueryDsl::execute(
+                    diesel::sql_query(format!("ALTER TABLE {table} DETACH PARTITION {partition}")),
+                    conn,
+                )?;
+                if !archive {
+                    RunQueryDsl::execute(
+                        diesel::sql_query(format!("DROP TABLE {partition}")),
+                        conn,
+                    )?;
+                }
+                Ok::<(), diesel::result::Error>(())
+            },
+            Duration::from_secs(10)
+        )?;
+        info!(
+            "{} epoch partition {} of table {}",
+            if archive { "Detached" } else { "Dropped" },
+            epoch,
+            table
+        );
+        Ok(())
+    }
+
     pub fn advance_table_epoch_partition(
         &self,
         table: String,
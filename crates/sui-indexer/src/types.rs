@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors::IndexerError;
+use crate::models::display::StoredDisplay;
+use move_core_types::annotated_value::MoveStructLayout;
 use move_core_types::language_storage::StructTag;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use sui_json_rpc_types::{
-    ObjectChange, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    DisplayFieldsResponse, ObjectChange, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
 };
 use sui_types::base_types::{ObjectDigest, SequenceNumber};
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -16,7 +19,8 @@ use sui_types::dynamic_field::DynamicFieldInfo;
 use sui_types::effects::TransactionEffects;
 use sui_types::event::SystemEpochInfoEvent;
 use sui_types::messages_checkpoint::{
-    CertifiedCheckpointSummary, CheckpointCommitment, CheckpointDigest, EndOfEpochData,
+    CertifiedCheckpointSummary, CheckpointCommitment, CheckpointContents, CheckpointDigest,
+    CheckpointSummary, EndOfEpochData,
 };
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Object, Owner};
@@ -26,7 +30,7 @@ use sui_types::transaction::SenderSignedData;
 
 pub type IndexerResult<T> = Result<T, IndexerError>;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct IndexedCheckpoint {
     pub sequence_number: u64,
     pub checkpoint_digest: CheckpointDigest,
@@ -45,6 +49,8 @@ pub struct IndexedCheckpoint {
     pub successful_tx_num: usize,
     pub end_of_epoch_data: Option<EndOfEpochData>,
     pub end_of_epoch: bool,
+    pub checkpoint_summary: CheckpointSummary,
+    pub checkpoint_contents: CheckpointContents,
 }
 
 impl IndexedCheckpoint {
@@ -78,6 +84,8 @@ impl IndexedCheckpoint {
             timestamp_ms: checkpoint.timestamp_ms,
             validator_signature: auth_sig.clone(),
             checkpoint_commitments: checkpoint.checkpoint_commitments.clone(),
+            checkpoint_summary: checkpoint.data().clone(),
+            checkpoint_contents: contents.clone(),
         }
     }
 }
@@ -278,6 +286,8 @@ pub struct IndexedObject {
     pub coin_type: Option<String>,
     pub coin_balance: Option<u64>,
     pub df_info: Option<DynamicFieldInfo>,
+    /// The Display of the object, if it is rendered at ingestion.
+    pub display: Option<IndexedObjectDisplay>,
 }
 
 impl IndexedObject {
@@ -307,10 +317,42 @@ impl IndexedObject {
             coin_type,
             coin_balance,
             df_info,
+            display: None,
         }
     }
 }
 
+/// The Display of an object, rendered with the Display of its type at `version`.
+#[derive(Clone, Debug)]
+pub struct IndexedObjectDisplay {
+    pub version: u16,
+    pub fields: DisplayFieldsResponse,
+}
+
+impl IndexedObjectDisplay {
+    /// Renders the Display of `object`, whose struct layout is `layout`.
+    pub fn render(
+        object: &Object,
+        layout: &MoveStructLayout,
+        display: &StoredDisplay,
+    ) -> IndexerResult<Self> {
+        let display = display.to_display_update_event()?;
+        let move_object = object.data.try_as_move().ok_or_else(|| {
+            IndexerError::ResolveMoveStructError(format!(
+                "Object {} with a Display is not a Move object",
+                object.id()
+            ))
+        })?;
+        let move_struct = move_object.to_move_struct(layout)?;
+        let fields = sui_json_rpc::read_api::get_rendered_fields(display.fields, &move_struct)
+            .map_err(|e| IndexerError::GenericError(e.to_string()))?;
+        Ok(Self {
+            version: display.version,
+            fields,
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct IndexedDeletedObject {
     pub object_id: ObjectID,
@@ -318,7 +360,7 @@ pub struct IndexedDeletedObject {
     pub checkpoint_sequence_number: u64,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct IndexedPackage {
     pub package_id: ObjectID,
     pub move_package: MovePackage,
@@ -357,6 +399,12 @@ pub struct TxIndex {
     pub senders: Vec<SuiAddress>,
     pub recipients: Vec<SuiAddress>,
     pub move_calls: Vec<(ObjectID, String, String)>,
+    /// Net gas used by the transaction, see `GasCostSummary::net_gas_usage`.
+    pub gas_used: i64,
+    /// The shared objects taken as input by the transaction, and whether they are taken mutably.
+    pub shared_objects: Vec<(ObjectID, bool)>,
+    /// Whether the transaction executed successfully.
+    pub success: bool,
 }
 
 // ObjectChange is not bcs deserializable, IndexedObjectChange is.
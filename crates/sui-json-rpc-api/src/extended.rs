@@ -5,9 +5,13 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    CheckpointedObjectID, EpochInfo, EpochPage, QueryObjectsPage, SuiObjectResponseQuery,
+    ActiveAddressMetrics, CheckpointedObjectID, EpochInfo, EpochPage, MoveFunctionMetrics,
+    PackageTxMetrics, QueryObjectsPage, SenderGasMetrics, SharedObjectMetrics,
+    SuiObjectResponseQuery, TransactionInclusionProof,
 };
 use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
 use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Extended API")]
@@ -43,4 +47,114 @@ pub trait ExtendedApi {
 
     #[method(name = "getTotalTransactions")]
     async fn get_total_transactions(&self) -> RpcResult<BigInt<u64>>;
+
+    /// Return the checkpoint that includes the given transaction, along with the data needed to
+    /// verify the inclusion against the checkpoint digest.
+    #[method(name = "getTransactionInclusionProof")]
+    async fn get_transaction_inclusion_proof(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<TransactionInclusionProof>;
+
+    /// Return the daily usage metrics of the Move functions of a package, most recent day first.
+    #[method(name = "getMoveFunctionMetrics")]
+    async fn get_move_function_metrics(
+        &self,
+        /// the Move package ID
+        package: ObjectID,
+        /// optional module name to filter on
+        module: Option<String>,
+        /// optional function name to filter on
+        function: Option<String>,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<MoveFunctionMetrics>>;
+
+    /// Return the most called Move functions on a day, in descending order of call count.
+    #[method(name = "getHotMoveFunctions")]
+    async fn get_hot_move_functions(
+        &self,
+        /// optional day, as the number of days since the unix epoch in UTC, defaults to the
+        /// latest day with metrics
+        day: Option<BigInt<u64>>,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<MoveFunctionMetrics>>;
+
+    /// Return the daily access metrics of a shared object, most recent day first.
+    #[method(name = "getSharedObjectMetrics")]
+    async fn get_shared_object_metrics(
+        &self,
+        /// the ID of the shared object
+        object_id: ObjectID,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<SharedObjectMetrics>>;
+
+    /// Return the shared objects mutated by the most transactions on a day, in descending order
+    /// of mutating transactions. These are the shared objects transactions contend on.
+    #[method(name = "getHotSharedObjects")]
+    async fn get_hot_shared_objects(
+        &self,
+        /// optional day, as the number of days since the unix epoch in UTC, defaults to the
+        /// latest day with metrics
+        day: Option<BigInt<u64>>,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<SharedObjectMetrics>>;
+
+    /// Return the daily number of addresses sending transactions, most recent day first. The
+    /// metrics of the current day are updated as its transactions are indexed.
+    #[method(name = "getActiveAddressMetrics")]
+    async fn get_active_address_metrics(
+        &self,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<ActiveAddressMetrics>>;
+
+    /// Return the daily number of transactions calling a Move package, most recent day first.
+    #[method(name = "getPackageTxMetrics")]
+    async fn get_package_tx_metrics(
+        &self,
+        /// the Move package ID
+        package: ObjectID,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<PackageTxMetrics>>;
+
+    /// Return the Move packages called by the most transactions on a day, in descending order of
+    /// transactions.
+    #[method(name = "getTopPackages")]
+    async fn get_top_packages(
+        &self,
+        /// optional day, as the number of days since the unix epoch in UTC, defaults to the
+        /// latest day with metrics
+        day: Option<BigInt<u64>>,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<PackageTxMetrics>>;
+
+    /// Return the daily number of transactions sent by an address, and the gas they used, most
+    /// recent day first.
+    #[method(name = "getSenderGasMetrics")]
+    async fn get_sender_gas_metrics(
+        &self,
+        /// the address sending the transactions
+        sender: SuiAddress,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<SenderGasMetrics>>;
+
+    /// Return the addresses whose transactions used the most gas on a day, in descending order of
+    /// gas used.
+    #[method(name = "getTopGasSenders")]
+    async fn get_top_gas_senders(
+        &self,
+        /// optional day, as the number of days since the unix epoch in UTC, defaults to the
+        /// latest day with metrics
+        day: Option<BigInt<u64>>,
+        /// maximum number of items returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<SenderGasMetrics>>;
 }
@@ -9,7 +9,7 @@ use serde_with::serde_as;
 use sui_types::base_types::TransactionDigest;
 use sui_types::committee::EpochId;
 use sui_types::crypto::AggregateAuthoritySignature;
-use sui_types::digests::CheckpointDigest;
+use sui_types::digests::{CheckpointDigest, TransactionEffectsDigest};
 use sui_types::gas::GasCostSummary;
 use sui_types::message_envelope::Message;
 use sui_types::messages_checkpoint::{
@@ -132,3 +132,42 @@ impl From<CheckpointDigest> for CheckpointId {
         Self::Digest(digest)
     }
 }
+
+/// The data needed to verify that a transaction was included in a checkpoint, without trusting
+/// the server that returned it:
+///
+/// 1. `checkpointSummary` is signed by the committee of its epoch with `validatorSignature`, and
+///    its digest is `checkpointDigest`.
+/// 2. The digest of `checkpointContents` is the `content_digest` of the summary.
+/// 3. The entry of the contents at `transactionIndex` is the pair of `transactionDigest` and
+///    `effectsDigest`.
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInclusionProof {
+    /// Digest of the transaction being proven
+    pub transaction_digest: TransactionDigest,
+    /// Digest of the effects of the transaction
+    pub effects_digest: TransactionEffectsDigest,
+    /// Sequence number of the checkpoint that includes the transaction
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+    /// Checkpoint digest
+    pub checkpoint_digest: CheckpointDigest,
+    /// BCS encoded [CheckpointSummary] of the checkpoint
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub checkpoint_summary: Vec<u8>,
+    /// BCS encoded [CheckpointContents] of the checkpoint
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub checkpoint_contents: Vec<u8>,
+    /// Position of the transaction in the checkpoint contents
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub transaction_index: u64,
+    /// Validator Signature
+    #[schemars(with = "Base64")]
+    pub validator_signature: AggregateAuthoritySignature,
+}
@@ -12,7 +12,7 @@ use serde_with::serde_as;
 use serde_with::DisplayFromStr;
 
 use sui_types::base_types::AuthorityName;
-use sui_types::base_types::{EpochId, ObjectID};
+use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
 use sui_types::committee::Committee;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_serde::BigInt;
@@ -116,3 +116,114 @@ pub struct MoveFunctionName {
     #[serde_as(as = "DisplayFromStr")]
     pub function: Identifier,
 }
+
+/// Usage of a Move function on a single day.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveFunctionMetrics {
+    pub function: MoveFunctionName,
+    /// day of the metrics, as the number of days since the unix epoch in UTC
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub day: u64,
+    /// number of transactions calling the function on that day
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub call_count: u64,
+    /// number of distinct senders of those transactions
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub distinct_senders: u64,
+    /// sum of the net gas used by those transactions, which is negative if storage rebates
+    /// exceed the gas charged
+    #[schemars(with = "BigInt<i64>")]
+    #[serde_as(as = "BigInt<i64>")]
+    pub total_gas_used: i64,
+}
+
+/// Accesses to a shared object on a single day, to find the shared objects that transactions
+/// contend on.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedObjectMetrics {
+    pub object_id: ObjectID,
+    /// day of the metrics, as the number of days since the unix epoch in UTC
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub day: u64,
+    /// number of transactions taking the object as input on that day
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub tx_count: u64,
+    /// number of those transactions taking the object mutably, which are sequenced one after
+    /// another on it
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub mutating_tx_count: u64,
+    /// number of those transactions that failed to execute
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub failed_tx_count: u64,
+    /// number of distinct senders of those transactions
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub distinct_senders: u64,
+    /// the most transactions mutating the object in a single checkpoint on that day
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub max_checkpoint_mutations: u64,
+}
+
+/// Number of addresses sending transactions on a single day.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveAddressMetrics {
+    /// day of the metrics, as the number of days since the unix epoch in UTC
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub day: u64,
+    /// number of distinct senders of the transactions on that day
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub active_addresses: u64,
+}
+
+/// Transactions calling a Move package on a single day.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageTxMetrics {
+    pub package: ObjectID,
+    /// day of the metrics, as the number of days since the unix epoch in UTC
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub day: u64,
+    /// number of transactions calling functions of the package on that day
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub tx_count: u64,
+}
+
+/// Transactions sent by an address on a single day, and the gas they used.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderGasMetrics {
+    pub sender: SuiAddress,
+    /// day of the metrics, as the number of days since the unix epoch in UTC
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub day: u64,
+    /// number of transactions sent by the address on that day
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub tx_count: u64,
+    /// sum of the net gas used by those transactions, which is negative if storage rebates
+    /// exceed the gas charged
+    #[schemars(with = "BigInt<i64>")]
+    #[serde_as(as = "BigInt<i64>")]
+    pub total_gas_used: i64,
+}
@@ -0,0 +1,209 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Passphrase based encryption of the file keystore.
+//!
+//! An encrypted keystore file holds a JSON object instead of the plain JSON array of keys. The
+//! encryption key is derived from the passphrase with Argon2id and the serialized key list is
+//! sealed with AES-256-GCM. A fresh nonce is used each time the keystore is saved.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, bail, ensure};
+use argon2::{Algorithm, Argon2, Params, Version};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+/// Environment variable holding the keystore passphrase, for non-interactive use.
+pub const SUI_KEYSTORE_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+
+const ENCRYPTED_KEYSTORE_VERSION: u8 = 1;
+const KDF_ARGON2ID: &str = "argon2id";
+const CIPHER_AES_256_GCM: &str = "aes-256-gcm";
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// Ciphers of the keystores unlocked by this process, so a passphrase is asked for at most once
+/// per keystore in a session (e.g. across commands of `sui console`).
+static UNLOCKED_KEYSTORES: Mutex<Option<HashMap<PathBuf, KeystoreCipher>>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedKeystore {
+    version: u8,
+    kdf: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    salt: String,
+    cipher: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// An encryption key derived from a passphrase, along with the parameters needed to derive it
+/// again.
+#[derive(Clone)]
+pub struct KeystoreCipher {
+    /// Cleared from memory when the cipher is dropped.
+    key: Zeroizing<[u8; KEY_LENGTH]>,
+    salt: [u8; SALT_LENGTH],
+    params: Params,
+}
+
+impl KeystoreCipher {
+    /// Derive a cipher for a new encrypted keystore, with a random salt.
+    pub fn new(passphrase: &str) -> Result<Self, anyhow::Error> {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        Self::derive(passphrase, salt, Params::default())
+    }
+
+    fn derive(
+        passphrase: &str,
+        salt: [u8; SALT_LENGTH],
+        params: Params,
+    ) -> Result<Self, anyhow::Error> {
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut *key)
+            .map_err(|e| anyhow!("Cannot derive keystore encryption key: {e}"))?;
+        Ok(Self { key, salt, params })
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptedKeystore, anyhow::Error> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new_from_slice(&*self.key)
+            .map_err(|e| anyhow!("Invalid keystore encryption key: {e}"))?
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| anyhow!("Cannot encrypt keystore"))?;
+        Ok(EncryptedKeystore {
+            version: ENCRYPTED_KEYSTORE_VERSION,
+            kdf: KDF_ARGON2ID.to_string(),
+            m_cost: self.params.m_cost(),
+            t_cost: self.params.t_cost(),
+            p_cost: self.params.p_cost(),
+            salt: Base64::encode(self.salt),
+            cipher: CIPHER_AES_256_GCM.to_string(),
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, encrypted: &EncryptedKeystore) -> Result<Vec<u8>, anyhow::Error> {
+        let nonce = decode_fixed::<NONCE_LENGTH>(&encrypted.nonce, "nonce")?;
+        let ciphertext = Base64::decode(&encrypted.ciphertext)
+            .map_err(|e| anyhow!("Invalid encrypted keystore ciphertext: {e}"))?;
+        Aes256Gcm::new_from_slice(&*self.key)
+            .map_err(|e| anyhow!("Invalid keystore encryption key: {e}"))?
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| anyhow!("Incorrect passphrase for the encrypted keystore"))
+    }
+}
+
+impl EncryptedKeystore {
+    /// Returns the encrypted keystore held in `contents`, or None if it is a plain keystore.
+    pub fn parse(contents: &str) -> Result<Option<Self>, anyhow::Error> {
+        if !contents.trim_start().starts_with('{') {
+            return Ok(None);
+        }
+        let encrypted: Self = serde_json::from_str(contents)?;
+        ensure!(
+            encrypted.version == ENCRYPTED_KEYSTORE_VERSION,
+            "Unsupported encrypted keystore version {}",
+            encrypted.version
+        );
+        ensure!(
+            encrypted.kdf == KDF_ARGON2ID && encrypted.cipher == CIPHER_AES_256_GCM,
+            "Unsupported encrypted keystore scheme {}/{}",
+            encrypted.kdf,
+            encrypted.cipher
+        );
+        Ok(Some(encrypted))
+    }
+
+    /// Derive the cipher for this keystore from `passphrase`, checking that it decrypts it.
+    pub fn unlock(&self, passphrase: &str) -> Result<(KeystoreCipher, Vec<u8>), anyhow::Error> {
+        let salt = decode_fixed::<SALT_LENGTH>(&self.salt, "salt")?;
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(KEY_LENGTH))
+            .map_err(|e| anyhow!("Invalid encrypted keystore parameters: {e}"))?;
+        let cipher = KeystoreCipher::derive(passphrase, salt, params)?;
+        let plaintext = cipher.decrypt(self)?;
+        Ok((cipher, plaintext))
+    }
+}
+
+/// Decrypt the keystore at `path`, using the cipher cached for it in this session if there is
+/// one, and otherwise the passphrase from the environment or entered at a prompt.
+pub fn unlock_keystore(
+    path: &Path,
+    encrypted: &EncryptedKeystore,
+) -> Result<(KeystoreCipher, Vec<u8>), anyhow::Error> {
+    let cached = UNLOCKED_KEYSTORES
+        .lock()
+        .map_err(|_| anyhow!("Keystore passphrase cache poisoned"))?
+        .as_ref()
+        .and_then(|unlocked| unlocked.get(path).cloned());
+    if let Some(cipher) = cached {
+        if let Ok(plaintext) = cipher.decrypt(encrypted) {
+            return Ok((cipher, plaintext));
+        }
+    }
+
+    let passphrase = match std::env::var(SUI_KEYSTORE_PASSPHRASE_ENV) {
+        Ok(passphrase) => passphrase,
+        Err(_) => prompt_passphrase(
+            &format!("Enter passphrase for keystore {}:", path.display()),
+            false,
+        )?,
+    };
+    let (cipher, plaintext) = encrypted.unlock(&passphrase)?;
+    cache_cipher(path, &cipher)?;
+    Ok((cipher, plaintext))
+}
+
+/// Remember the cipher of the keystore at `path` for the rest of this session.
+pub fn cache_cipher(path: &Path, cipher: &KeystoreCipher) -> Result<(), anyhow::Error> {
+    UNLOCKED_KEYSTORES
+        .lock()
+        .map_err(|_| anyhow!("Keystore passphrase cache poisoned"))?
+        .get_or_insert_with(HashMap::new)
+        .insert(path.to_path_buf(), cipher.clone());
+    Ok(())
+}
+
+/// Read a passphrase from the terminal without echoing it. When `confirm` is set the passphrase
+/// has to be entered twice.
+pub fn prompt_passphrase(message: &str, confirm: bool) -> Result<String, anyhow::Error> {
+    let prompt = inquire::Password::new(message)
+        .with_display_mode(inquire::PasswordDisplayMode::Hidden)
+        .with_custom_confirmation_message("Confirm passphrase:")
+        .with_custom_confirmation_error_message("The passphrases don't match.");
+    let prompt = if confirm {
+        prompt
+    } else {
+        prompt.without_confirmation()
+    };
+    let passphrase = prompt
+        .prompt()
+        .map_err(|e| anyhow!("Cannot read keystore passphrase: {e}"))?;
+    if passphrase.is_empty() {
+        bail!("Keystore passphrase cannot be empty");
+    }
+    Ok(passphrase)
+}
+
+fn decode_fixed<const N: usize>(value: &str, field: &str) -> Result<[u8; N], anyhow::Error> {
+    Base64::decode(value)
+        .map_err(|e| anyhow!("Invalid encrypted keystore {field}: {e}"))?
+        .try_into()
+        .map_err(|_| anyhow!("Invalid encrypted keystore {field} length"))
+}
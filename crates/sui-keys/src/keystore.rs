@@ -1,7 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::encryption::{cache_cipher, unlock_keystore, EncryptedKeystore, KeystoreCipher};
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
+use crate::ledger::{sign_with_device, LedgerAccount};
 use crate::random_names::{random_name, random_names};
 use anyhow::{anyhow, bail, ensure, Context};
 use bip32::DerivationPath;
@@ -15,13 +17,14 @@ use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Write as _};
 use std::path::{Path, PathBuf};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
     enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
 };
+use tempfile::NamedTempFile;
 
 #[derive(Serialize, Deserialize)]
 #[enum_dispatch(AccountKeystore)]
@@ -162,8 +165,13 @@ pub struct Alias {
 #[derive(Default)]
 pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
+    /// Accounts whose private key is held on a Ledger device, kept next to the keystore in a
+    /// `.ledger` file.
+    ledger: BTreeMap<SuiAddress, LedgerAccount>,
     aliases: BTreeMap<SuiAddress, Alias>,
     path: Option<PathBuf>,
+    /// Set if the keystore file is encrypted with a passphrase.
+    cipher: Option<KeystoreCipher>,
 }
 
 impl Serialize for FileBasedKeystore {
@@ -194,6 +202,11 @@ impl<'de> Deserialize<'de> for FileBasedKeystore {
 
 impl AccountKeystore for FileBasedKeystore {
     fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        if self.ledger.contains_key(address) {
+            return Err(signature::Error::from_source(format!(
+                "Key for address [{address}] is held on a Ledger device, which only signs intent messages"
+            )));
+        }
         Ok(Signature::new_hashed(
             msg,
             self.keys.get(address).ok_or_else(|| {
@@ -210,6 +223,10 @@ impl AccountKeystore for FileBasedKeystore {
     where
         T: Serialize,
     {
+        if let Some(account) = self.ledger.get(address) {
+            return sign_with_device(account, msg, intent)
+                .map_err(|e| signature::Error::from_source(e.to_string()));
+        }
         Ok(Signature::new_secure(
             &IntentMessage::new(intent, msg),
             self.keys.get(address).ok_or_else(|| {
@@ -248,7 +265,15 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn keys(&self) -> Vec<PublicKey> {
-        self.keys.values().map(|key| key.public()).collect()
+        self.keys
+            .values()
+            .map(|key| key.public())
+            .chain(
+                self.ledger.values().filter_map(|account| {
+                    PublicKey::decode_base64(&account.public_key_base64).ok()
+                }),
+            )
+            .collect()
     }
 
     /// This function returns an error if the provided alias already exists. If the alias
@@ -290,6 +315,9 @@ impl AccountKeystore for FileBasedKeystore {
     fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
         match self.keys.get(address) {
             Some(key) => Ok(key),
+            None if self.ledger.contains_key(address) => Err(anyhow!(
+                "Key for address [{address}] is held on a Ledger device and cannot be exported"
+            )),
             None => Err(anyhow!("Cannot find key for address: [{address}]")),
         }
     }
@@ -309,12 +337,20 @@ impl AccountKeystore for FileBasedKeystore {
 
 impl FileBasedKeystore {
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let mut cipher = None;
         let keys = if path.exists() {
-            let reader =
-                BufReader::new(File::open(path).with_context(|| {
-                    format!("Cannot open the keystore file: {}", path.display())
-                })?);
-            let kp_strings: Vec<String> = serde_json::from_reader(reader).with_context(|| {
+            let mut contents = fs::read_to_string(path)
+                .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?;
+            let encrypted = EncryptedKeystore::parse(&contents).with_context(|| {
+                format!("Cannot deserialize the keystore file: {}", path.display())
+            })?;
+            if let Some(encrypted) = encrypted {
+                let (unlocked, plaintext) = unlock_keystore(path, &encrypted)?;
+                contents = String::from_utf8(plaintext)
+                    .with_context(|| format!("Invalid keystore file: {}", path.display()))?;
+                cipher = Some(unlocked);
+            }
+            let kp_strings: Vec<String> = serde_json::from_str(&contents).with_context(|| {
                 format!("Cannot deserialize the keystore file: {}", path.display(),)
             })?;
             kp_strings
@@ -329,6 +365,40 @@ impl FileBasedKeystore {
             BTreeMap::new()
         };
 
+        let mut ledger_path = path.clone();
+        ledger_path.set_extension("ledger");
+        let ledger = if ledger_path.exists() {
+            let reader = BufReader::new(File::open(&ledger_path).with_context(|| {
+                format!(
+                    "Cannot open Ledger accounts file in keystore: {}",
+                    ledger_path.display()
+                )
+            })?);
+            let accounts: Vec<LedgerAccount> =
+                serde_json::from_reader(reader).with_context(|| {
+                    format!(
+                        "Cannot deserialize Ledger accounts file in keystore: {}",
+                        ledger_path.display()
+                    )
+                })?;
+            accounts
+                .into_iter()
+                .map(|account| {
+                    let key = PublicKey::decode_base64(&account.public_key_base64);
+                    key.map(|k| (Into::<SuiAddress>::into(&k), account))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map_err(|e| {
+                    anyhow!(
+                        "Invalid Ledger accounts file in keystore: {}. {}",
+                        ledger_path.display(),
+                        e
+                    )
+                })?
+        } else {
+            BTreeMap::new()
+        };
+
         // check aliases
         let mut aliases_path = path.clone();
         aliases_path.set_extension("aliases");
@@ -393,8 +463,10 @@ impl FileBasedKeystore {
 
         Ok(Self {
             keys,
+            ledger,
             aliases,
             path: Some(path.to_path_buf()),
+            cipher,
         })
     }
 
@@ -436,7 +508,50 @@ impl FileBasedKeystore {
                     .collect::<Vec<_>>(),
             )
             .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
-            fs::write(path, store)?;
+            match &self.cipher {
+                Some(cipher) => {
+                    let encrypted =
+                        serde_json::to_string_pretty(&cipher.encrypt(store.as_bytes())?)
+                            .with_context(|| {
+                                format!("Cannot serialize keystore to file: {}", path.display())
+                            })?;
+                    write_atomically(path, encrypted.as_bytes())?;
+                }
+                None => write_atomically(path, store.as_bytes())?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Encrypt the keystore file with `passphrase`. Once encrypted, the passphrase is asked for
+    /// whenever the keystore is loaded, unless it is set in the `SUI_KEYSTORE_PASSPHRASE`
+    /// environment variable.
+    pub fn encrypt(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        let cipher = KeystoreCipher::new(passphrase)?;
+        if let Some(path) = &self.path {
+            cache_cipher(path, &cipher)?;
+        }
+        self.cipher = Some(cipher);
+        self.save_keystore()
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    pub fn save_ledger_accounts(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            let store = serde_json::to_string_pretty(&self.ledger.values().collect::<Vec<_>>())
+                .with_context(|| {
+                    format!(
+                        "Cannot serialize Ledger accounts to file in keystore: {}",
+                        path.display()
+                    )
+                })?;
+
+            let mut ledger_path = path.clone();
+            ledger_path.set_extension("ledger");
+            fs::write(ledger_path, store)?
         }
         Ok(())
     }
@@ -447,6 +562,41 @@ impl FileBasedKeystore {
         Ok(())
     }
 
+    /// Register an account whose key is held on a Ledger device. Nothing secret is stored, only
+    /// the derivation path and public key needed to route signing requests to the device.
+    pub fn add_ledger_account(
+        &mut self,
+        alias: Option<String>,
+        account: LedgerAccount,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        let public_key = PublicKey::decode_base64(&account.public_key_base64)
+            .map_err(|e| anyhow!("Invalid Ledger public key: {e}"))?;
+        let address: SuiAddress = (&public_key).into();
+        ensure!(
+            !self.keys.contains_key(&address),
+            "Address {address} is already in the keystore with a local private key"
+        );
+        let alias = match self.aliases.get(&address) {
+            Some(existing) if alias.is_none() => existing.alias.clone(),
+            _ => self.create_alias(alias)?,
+        };
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64: account.public_key_base64.clone(),
+            },
+        );
+        self.ledger.insert(address, account);
+        self.save_aliases()?;
+        self.save_ledger_accounts()?;
+        Ok(address)
+    }
+
+    pub fn ledger_accounts(&self) -> Vec<(&SuiAddress, &LedgerAccount)> {
+        self.ledger.iter().collect()
+    }
+
     pub fn key_pairs(&self) -> Vec<&SuiKeyPair> {
         self.keys.values().collect()
     }
@@ -615,9 +765,27 @@ fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     Ok(alias.to_string())
 }
 
+/// Write `contents` to a temporary file next to `path` and rename it over `path`, so that an
+/// interrupted write cannot leave a truncated keystore behind.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut file = NamedTempFile::new_in(dir)
+        .with_context(|| format!("Cannot create a temporary file in {}", dir.display()))?;
+    file.write_all(contents)?;
+    file.as_file().sync_all()?;
+    file.persist(path)
+        .with_context(|| format!("Cannot write keystore file: {}", path.display()))?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::keystore::validate_alias;
+    use std::fs;
+
+    use crate::keystore::{validate_alias, write_atomically};
 
     #[test]
     fn validate_alias_test() {
@@ -636,4 +804,16 @@ mod tests {
         assert!(validate_alias("^A").is_err());
         assert!(validate_alias("-A").is_err());
     }
+
+    #[test]
+    fn write_atomically_test() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("sui.keystore");
+        fs::write(&path, "[\"old\"]").unwrap();
+
+        write_atomically(&path, b"[\"new\"]").unwrap();
+        assert_eq!("[\"new\"]", fs::read_to_string(&path).unwrap());
+        // The temporary file has been renamed over the keystore.
+        assert_eq!(1, fs::read_dir(temp_dir.path()).unwrap().count());
+    }
 }
@@ -0,0 +1,362 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing with Ed25519 keys held on a Ledger device running the Sui app.
+//!
+//! The device is driven through APDU commands: the host sends the BIP-32 derivation path,
+//! followed (for signing) by the BCS encoded [struct IntentMessage], and the device returns the
+//! public key or the signature. The private key never leaves the device.
+
+use anyhow::{anyhow, bail, ensure};
+use bip32::DerivationPath;
+use fastcrypto::ed25519::{Ed25519PublicKey, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH};
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::str::FromStr;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, Signature, SignatureScheme};
+use tracing::info;
+
+use crate::key_derive::validate_path;
+
+pub const LEDGER_VENDOR_ID: u16 = 0x2c97;
+
+const CLA: u8 = 0x00;
+const INS_GET_VERSION: u8 = 0x00;
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+const INS_SIGN_TRANSACTION: u8 = 0x03;
+/// Set on every chunk but the last one of a multi-APDU payload.
+const P1_MORE: u8 = 0x80;
+const P1_LAST: u8 = 0x00;
+const MAX_APDU_PAYLOAD: usize = 255;
+
+const SW_OK: u16 = 0x9000;
+const SW_USER_REJECTED: u16 = 0x6985;
+const SW_APP_NOT_OPEN: u16 = 0x6e00;
+const SW_LOCKED: u16 = 0x5515;
+
+/// A channel to exchange raw APDUs with a device. The response includes the trailing two byte
+/// status word.
+pub trait LedgerTransport: Send + Sync {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, anyhow::Error>;
+}
+
+/// A Ledger device attached to this machine.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerDeviceInfo {
+    pub path: String,
+    pub product: String,
+    pub serial_number: Option<String>,
+}
+
+/// A Sui account held on a Ledger device, identified by the derivation path its key lives at.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerAccount {
+    pub derivation_path: String,
+    pub public_key_base64: String,
+}
+
+pub struct LedgerSigner<T> {
+    transport: T,
+}
+
+impl<T: LedgerTransport> LedgerSigner<T> {
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Returns the (major, minor, patch) version of the Sui app running on the device.
+    pub fn app_version(&self) -> Result<(u8, u8, u8), anyhow::Error> {
+        let response = self.send(INS_GET_VERSION, &[])?;
+        ensure!(
+            response.len() >= 3,
+            "Unexpected version response from Ledger device"
+        );
+        Ok((response[0], response[1], response[2]))
+    }
+
+    /// Derive the Ed25519 public key at `path` on the device. When `confirm` is set the device
+    /// displays the resulting address and waits for the user to approve it.
+    pub fn public_key(
+        &self,
+        path: &DerivationPath,
+        confirm: bool,
+    ) -> Result<PublicKey, anyhow::Error> {
+        let mut payload = vec![confirm as u8];
+        payload.extend(encode_path(path)?);
+        let response = self.send(INS_GET_PUBLIC_KEY, &payload)?;
+        ensure!(
+            response.first() == Some(&(ED25519_PUBLIC_KEY_LENGTH as u8))
+                && response.len() > ED25519_PUBLIC_KEY_LENGTH,
+            "Unexpected public key response from Ledger device"
+        );
+        let pk = Ed25519PublicKey::from_bytes(&response[1..=ED25519_PUBLIC_KEY_LENGTH])
+            .map_err(|e| anyhow!("Invalid public key returned by Ledger device: {e}"))?;
+        Ok(PublicKey::Ed25519((&pk).into()))
+    }
+
+    /// Sign `msg` under `intent` with the key at `path`. The device shows the transaction and
+    /// only returns a signature once the user has approved it.
+    pub fn sign_secure<M: Serialize>(
+        &self,
+        path: &DerivationPath,
+        msg: &M,
+        intent: Intent,
+    ) -> Result<Signature, anyhow::Error> {
+        let public_key = self.public_key(path, false)?;
+        let intent_msg = bcs::to_bytes(&IntentMessage::new(intent, msg))?;
+
+        let mut payload = encode_path(path)?;
+        payload.extend((intent_msg.len() as u32).to_le_bytes());
+        payload.extend(intent_msg);
+        let response = self.send(INS_SIGN_TRANSACTION, &payload)?;
+        ensure!(
+            response.len() >= ED25519_SIGNATURE_LENGTH,
+            "Unexpected signature response from Ledger device"
+        );
+
+        let mut bytes = vec![SignatureScheme::ED25519.flag()];
+        bytes.extend_from_slice(&response[..ED25519_SIGNATURE_LENGTH]);
+        bytes.extend_from_slice(public_key.as_ref());
+        Signature::from_bytes(&bytes)
+            .map_err(|e| anyhow!("Invalid signature returned by Ledger device: {e}"))
+    }
+
+    /// Sends `payload` split over as many APDUs as needed and returns the data of the final
+    /// response, with the status word stripped.
+    fn send(&self, ins: u8, payload: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let chunks: Vec<&[u8]> = if payload.is_empty() {
+            vec![&[]]
+        } else {
+            payload.chunks(MAX_APDU_PAYLOAD).collect()
+        };
+        let mut response = vec![];
+        for (i, chunk) in chunks.iter().enumerate() {
+            let p1 = if i + 1 == chunks.len() {
+                P1_LAST
+            } else {
+                P1_MORE
+            };
+            let mut apdu = vec![CLA, ins, p1, 0x00, chunk.len() as u8];
+            apdu.extend_from_slice(chunk);
+            response = check_status(self.transport.exchange(&apdu)?)?;
+        }
+        Ok(response)
+    }
+}
+
+/// The default derivation path for the `index`-th Ed25519 account, m/44'/784'/{index}'/0'/0'.
+pub fn account_derivation_path(index: u32) -> Result<DerivationPath, anyhow::Error> {
+    DerivationPath::from_str(&format!("m/44'/784'/{index}'/0'/0'"))
+        .map_err(|e| anyhow!("Invalid derivation path: {e}"))
+}
+
+/// Derive the first `count` accounts starting at `start` and return their addresses, keys
+/// and paths.
+pub fn derive_addresses<T: LedgerTransport>(
+    signer: &LedgerSigner<T>,
+    start: u32,
+    count: u32,
+) -> Result<Vec<(SuiAddress, LedgerAccount)>, anyhow::Error> {
+    (start..start.saturating_add(count))
+        .map(|index| {
+            let path = account_derivation_path(index)?;
+            let pk = signer.public_key(&path, false)?;
+            Ok((
+                SuiAddress::from(&pk),
+                LedgerAccount {
+                    derivation_path: path.to_string(),
+                    public_key_base64: pk.encode_base64(),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Sign with `account` on the connected Ledger device that holds its key.
+pub fn sign_with_device<M: Serialize>(
+    account: &LedgerAccount,
+    msg: &M,
+    intent: Intent,
+) -> Result<Signature, anyhow::Error> {
+    let devices = list_devices()?;
+    let (signer, path) = find_account_signer(
+        account,
+        devices.iter().map(|device| open_device(Some(&device.path))),
+    )?;
+    info!("Review and approve the transaction on your Ledger device...");
+    signer.sign_secure(&path, msg, intent)
+}
+
+/// Returns the first of the devices opened by `signers` that holds the key `account` was
+/// registered with, along with the account's derivation path. Devices that cannot be opened or
+/// queried are skipped, but their error is reported if no device holds the key.
+pub fn find_account_signer<T: LedgerTransport>(
+    account: &LedgerAccount,
+    signers: impl IntoIterator<Item = Result<LedgerSigner<T>, anyhow::Error>>,
+) -> Result<(LedgerSigner<T>, DerivationPath), anyhow::Error> {
+    let path = DerivationPath::from_str(&account.derivation_path)
+        .map_err(|e| anyhow!("Invalid derivation path {}: {e}", account.derivation_path))?;
+    let mut last_error = None;
+    for signer in signers {
+        match signer.and_then(|signer| Ok((signer.public_key(&path, false)?, signer))) {
+            Ok((public_key, signer)) if public_key.encode_base64() == account.public_key_base64 => {
+                return Ok((signer, path));
+            }
+            Ok(_) => {}
+            Err(e) => last_error = Some(e),
+        }
+    }
+    let message = format!(
+        "No connected Ledger device holds the key at {}",
+        account.derivation_path
+    );
+    Err(match last_error {
+        Some(e) => e.context(message),
+        None => anyhow!(message),
+    })
+}
+
+fn encode_path(path: &DerivationPath) -> Result<Vec<u8>, anyhow::Error> {
+    // The Sui app only holds Ed25519 keys, which must be hardened at every level.
+    let path = validate_path(&SignatureScheme::ED25519, Some(path.clone()))?;
+    let indexes: Vec<u32> = path.into_iter().map(u32::from).collect();
+    let mut bytes = vec![indexes.len() as u8];
+    for index in indexes {
+        bytes.extend(index.to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+fn check_status(mut response: Vec<u8>) -> Result<Vec<u8>, anyhow::Error> {
+    ensure!(response.len() >= 2, "Truncated response from Ledger device");
+    let status = u16::from_be_bytes([response[response.len() - 2], response[response.len() - 1]]);
+    response.truncate(response.len() - 2);
+    match status {
+        SW_OK => Ok(response),
+        SW_USER_REJECTED => bail!("Request was rejected on the Ledger device"),
+        SW_APP_NOT_OPEN => bail!("Open the Sui app on the Ledger device and try again"),
+        SW_LOCKED => bail!("Ledger device is locked, unlock it and try again"),
+        _ => bail!("Ledger device returned error status {status:#06x}"),
+    }
+}
+
+#[cfg(feature = "ledger")]
+pub use hid::{list_devices, open_device, HidTransport};
+
+#[cfg(not(feature = "ledger"))]
+pub fn list_devices() -> Result<Vec<LedgerDeviceInfo>, anyhow::Error> {
+    bail!("This binary was built without Ledger support, rebuild with the `ledger` feature")
+}
+
+#[cfg(not(feature = "ledger"))]
+pub fn open_device(
+    _path: Option<&str>,
+) -> Result<LedgerSigner<Box<dyn LedgerTransport>>, anyhow::Error> {
+    bail!("This binary was built without Ledger support, rebuild with the `ledger` feature")
+}
+
+impl LedgerTransport for Box<dyn LedgerTransport> {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        self.as_ref().exchange(apdu)
+    }
+}
+
+#[cfg(feature = "ledger")]
+mod hid {
+    use super::{LedgerDeviceInfo, LedgerSigner, LedgerTransport, LEDGER_VENDOR_ID};
+    use anyhow::{anyhow, ensure, Context};
+    use hidapi::{HidApi, HidDevice};
+    use std::sync::Mutex;
+
+    const CHANNEL: u16 = 0x0101;
+    const TAG_APDU: u8 = 0x05;
+    const PACKET_SIZE: usize = 64;
+    const TIMEOUT_MS: i32 = 120_000;
+    /// Ledger devices expose several HID interfaces, only interface 0 speaks APDU.
+    const APDU_INTERFACE: i32 = 0;
+
+    pub struct HidTransport {
+        device: Mutex<HidDevice>,
+    }
+
+    pub fn list_devices() -> Result<Vec<LedgerDeviceInfo>, anyhow::Error> {
+        let api = HidApi::new().context("Cannot initialize the USB HID subsystem")?;
+        Ok(api
+            .device_list()
+            .filter(|d| d.vendor_id() == LEDGER_VENDOR_ID && d.interface_number() == APDU_INTERFACE)
+            .map(|d| LedgerDeviceInfo {
+                path: d.path().to_string_lossy().to_string(),
+                product: d.product_string().unwrap_or("Ledger").to_string(),
+                serial_number: d.serial_number().map(|s| s.to_string()),
+            })
+            .collect())
+    }
+
+    /// Open the device at `path`, or the first connected Ledger device if no path is given.
+    pub fn open_device(path: Option<&str>) -> Result<LedgerSigner<HidTransport>, anyhow::Error> {
+        let api = HidApi::new().context("Cannot initialize the USB HID subsystem")?;
+        let info = api
+            .device_list()
+            .filter(|d| d.vendor_id() == LEDGER_VENDOR_ID && d.interface_number() == APDU_INTERFACE)
+            .find(|d| path.map_or(true, |p| d.path().to_string_lossy() == p))
+            .ok_or_else(|| anyhow!("No Ledger device found, is it connected and unlocked?"))?;
+        let device = info
+            .open_device(&api)
+            .context("Cannot open the Ledger device")?;
+        Ok(LedgerSigner::new(HidTransport {
+            device: Mutex::new(device),
+        }))
+    }
+
+    impl LedgerTransport for HidTransport {
+        fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+            let device = self
+                .device
+                .lock()
+                .map_err(|_| anyhow!("Ledger device lock poisoned"))?;
+
+            // Each HID packet carries the channel, tag and sequence number; the first packet of
+            // a message additionally carries the total message length.
+            let mut message = (apdu.len() as u16).to_be_bytes().to_vec();
+            message.extend_from_slice(apdu);
+            for (seq, chunk) in message.chunks(PACKET_SIZE - 5).enumerate() {
+                // hidapi expects a leading report id byte.
+                let mut packet = vec![0x00];
+                packet.extend(CHANNEL.to_be_bytes());
+                packet.push(TAG_APDU);
+                packet.extend((seq as u16).to_be_bytes());
+                packet.extend_from_slice(chunk);
+                packet.resize(PACKET_SIZE + 1, 0);
+                device.write(&packet)?;
+            }
+
+            let mut response = vec![];
+            let mut expected = None;
+            let mut seq = 0u16;
+            while expected.map_or(true, |len| response.len() < len) {
+                let mut packet = [0u8; PACKET_SIZE];
+                let read = device.read_timeout(&mut packet, TIMEOUT_MS)?;
+                ensure!(read >= 5, "Timed out waiting for the Ledger device");
+                ensure!(
+                    packet[..2] == CHANNEL.to_be_bytes()
+                        && packet[2] == TAG_APDU
+                        && packet[3..5] == seq.to_be_bytes(),
+                    "Malformed response packet from Ledger device"
+                );
+                let data = if seq == 0 {
+                    expected = Some(u16::from_be_bytes([packet[5], packet[6]]) as usize);
+                    &packet[7..read]
+                } else {
+                    &packet[5..read]
+                };
+                response.extend_from_slice(data);
+                seq += 1;
+            }
+            response.truncate(expected.unwrap_or_default());
+            Ok(response)
+        }
+    }
+}
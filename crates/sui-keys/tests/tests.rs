@@ -3,14 +3,25 @@
 
 use std::fs;
 use std::str::FromStr;
+use std::sync::Mutex;
 
+use anyhow::anyhow;
+use fastcrypto::ed25519::Ed25519KeyPair;
 use fastcrypto::hash::HashFunction;
-use fastcrypto::traits::EncodeDecodeBase64;
+use fastcrypto::traits::{EncodeDecodeBase64, KeyPair, Signer};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_keys::encryption::EncryptedKeystore;
 use sui_keys::key_derive::generate_new_key;
+use sui_keys::ledger::{
+    account_derivation_path, derive_addresses, find_account_signer, LedgerAccount, LedgerSigner,
+    LedgerTransport,
+};
 use tempfile::TempDir;
 
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use sui_types::crypto::{
+    get_key_pair, DefaultHash, PublicKey, Signature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
+};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -266,3 +277,162 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+/// Emulates the Sui Ledger app with a single in-memory key.
+struct MockLedger {
+    keypair: Ed25519KeyPair,
+    pending: Mutex<Vec<u8>>,
+}
+
+impl LedgerTransport for MockLedger {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let (ins, p1, data) = (apdu[1], apdu[2], &apdu[5..]);
+        let mut pending = self.pending.lock().unwrap();
+        pending.extend_from_slice(data);
+        if p1 != 0 {
+            return Ok(vec![0x90, 0x00]);
+        }
+        let payload = std::mem::take(&mut *pending);
+        let mut response = match ins {
+            0x00 => vec![1, 0, 0],
+            0x02 => [&[32u8][..], self.keypair.public().as_ref()].concat(),
+            0x03 => {
+                let path_len = 1 + 4 * payload[0] as usize;
+                let msg = &payload[path_len + 4..];
+                let digest = DefaultHash::digest(msg).digest;
+                self.keypair.sign(&digest).as_ref().to_vec()
+            }
+            _ => return Ok(vec![0x6d, 0x00]),
+        };
+        response.extend([0x90, 0x00]);
+        Ok(response)
+    }
+}
+
+#[test]
+fn ledger_sign_matches_local_signature_test() {
+    let (_, keypair): (_, Ed25519KeyPair) = get_key_pair();
+    let signer = LedgerSigner::new(MockLedger {
+        keypair: keypair.copy(),
+        pending: Mutex::new(vec![]),
+    });
+    let path = account_derivation_path(0).unwrap();
+
+    // Large enough to be split over several APDUs.
+    let msg = vec![7u8; 1000];
+    let signature = signer
+        .sign_secure(&path, &msg, Intent::sui_transaction())
+        .unwrap();
+    let expected = Signature::new_secure(
+        &IntentMessage::new(Intent::sui_transaction(), &msg),
+        &SuiKeyPair::Ed25519(keypair.copy()),
+    );
+    assert_eq!(expected, signature);
+
+    let addresses = derive_addresses(&signer, 0, 2).unwrap();
+    assert_eq!(2, addresses.len());
+    assert_eq!(
+        SuiAddress::from(&PublicKey::Ed25519(keypair.public().into())),
+        addresses[0].0
+    );
+    assert_eq!("m/44'/784'/1'/0'/0'", addresses[1].1.derivation_path);
+}
+
+#[test]
+fn ledger_account_device_selection_test() {
+    let mock_ledger = |keypair: &Ed25519KeyPair| {
+        LedgerSigner::new(MockLedger {
+            keypair: keypair.copy(),
+            pending: Mutex::new(vec![]),
+        })
+    };
+    let (_, other): (_, Ed25519KeyPair) = get_key_pair();
+    let (_, keypair): (_, Ed25519KeyPair) = get_key_pair();
+    let account = LedgerAccount {
+        derivation_path: "m/44'/784'/0'/0'/0'".to_string(),
+        public_key_base64: PublicKey::Ed25519(keypair.public().into()).encode_base64(),
+    };
+
+    // The device holding the account's key is picked, skipping the ones that cannot be used.
+    let (signer, _) = find_account_signer(
+        &account,
+        [
+            Ok(mock_ledger(&other)),
+            Err(anyhow!("Ledger device is locked")),
+            Ok(mock_ledger(&keypair)),
+        ],
+    )
+    .unwrap();
+    let path = account_derivation_path(0).unwrap();
+    assert_eq!(
+        PublicKey::Ed25519(keypair.public().into()),
+        signer.public_key(&path, false).unwrap()
+    );
+
+    // The error of a device that cannot be used is reported if no device holds the key.
+    let err = find_account_signer(
+        &account,
+        [
+            Ok(mock_ledger(&other)),
+            Err(anyhow!("Ledger device is locked")),
+        ],
+    )
+    .err()
+    .unwrap();
+    assert!(format!("{err:#}").contains("Ledger device is locked"));
+    assert!(find_account_signer::<MockLedger>(&account, []).is_err());
+}
+
+#[test]
+fn ledger_account_in_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let (_, keypair): (_, Ed25519KeyPair) = get_key_pair();
+    let public_key = PublicKey::Ed25519(keypair.public().into());
+
+    let mut file_keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let address = file_keystore
+        .add_ledger_account(
+            Some("cold".to_string()),
+            LedgerAccount {
+                derivation_path: "m/44'/784'/0'/0'/0'".to_string(),
+                public_key_base64: public_key.encode_base64(),
+            },
+        )
+        .unwrap();
+    assert!(keystore_path.with_extension("ledger").exists());
+
+    // Reload from disk, the account is listed but its key cannot be exported.
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    assert_eq!(vec![address], keystore.addresses());
+    assert_eq!("cold", keystore.get_alias_by_address(&address).unwrap());
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore.sign_hashed(&address, b"hello").is_err());
+}
+
+#[test]
+fn encrypted_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let private_key = keystore.get_key(&address).unwrap().encode_base64();
+
+    keystore.encrypt("correct horse battery staple").unwrap();
+    assert!(keystore.is_encrypted());
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    assert!(!contents.contains(&private_key));
+
+    let encrypted = EncryptedKeystore::parse(&contents).unwrap().unwrap();
+    assert!(encrypted.unlock("wrong passphrase").is_err());
+    let (_, plaintext) = encrypted.unlock("correct horse battery staple").unwrap();
+    let keys: Vec<String> = serde_json::from_slice(&plaintext).unwrap();
+    assert_eq!(vec![private_key], keys);
+
+    // The passphrase is cached for this session, so reloading does not prompt.
+    let keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.is_encrypted());
+    assert_eq!(vec![address], keystore.addresses());
+}
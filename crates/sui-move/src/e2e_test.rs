@@ -0,0 +1,38 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Publish the package to an ephemeral local network and run end-to-end tests against it.
+///
+/// Tests are console scripts of `sui client` commands (including `ptb`), given with `--script`,
+/// and/or a test command given after `--`, e.g. `sui move e2e-test -- cargo test -p e2e`. The
+/// published package ID, the RPC URL and the client config of the network are available to both
+/// through the `SUI_E2E_PACKAGE_ID`, `SUI_E2E_RPC_URL` and `SUI_E2E_CLIENT_CONFIG` environment
+/// variables. The network is torn down afterwards, and its artifacts are kept when a test fails.
+#[derive(Parser)]
+#[group(id = "sui-move-e2e-test")]
+pub struct E2eTest {
+    /// Console scripts to run against the network, one after the other, in the format of
+    /// `sui console --script`.
+    #[clap(long = "script")]
+    pub scripts: Vec<PathBuf>,
+
+    /// Gas budget for publishing the package.
+    #[clap(long, default_value_t = 1_000_000_000)]
+    pub gas_budget: u64,
+
+    /// Also publish transitive dependencies that have not already been published.
+    #[clap(long)]
+    pub with_unpublished_dependencies: bool,
+
+    /// Directory to keep the network configs, the publish effects and the test outputs in. By
+    /// default they are kept in a temporary directory, which is removed when all tests pass.
+    #[clap(long)]
+    pub artifacts_dir: Option<PathBuf>,
+
+    /// Test command to run against the network, after the scripts.
+    #[clap(last = true)]
+    pub command: Vec<String>,
+}
@@ -14,6 +14,7 @@ pub mod build;
 pub mod coverage;
 #[cfg(feature = "disassemble")]
 pub mod disassemble;
+pub mod e2e_test;
 pub mod manage_package;
 pub mod migrate;
 pub mod new;
@@ -28,6 +29,7 @@ pub enum Command {
     Coverage(coverage::Coverage),
     #[cfg(feature = "disassemble")]
     Disassemble(disassemble::Disassemble),
+    E2eTest(e2e_test::E2eTest),
     ManagePackage(manage_package::ManagePackage),
     Migrate(migrate::Migrate),
     New(new::New),
@@ -57,6 +59,11 @@ pub fn execute_move_command(
         Command::Coverage(c) => c.execute(package_path, build_config),
         #[cfg(feature = "disassemble")]
         Command::Disassemble(c) => c.execute(package_path, build_config),
+        // The network the tests run against is started by the `sui` binary, which handles this
+        // command itself.
+        Command::E2eTest(_) => {
+            anyhow::bail!("`e2e-test` is only available through `sui move e2e-test`")
+        }
         Command::ManagePackage(c) => c.execute(package_path, build_config),
         Command::Migrate(c) => c.execute(package_path, build_config),
         Command::New(c) => c.execute(package_path),
@@ -77,6 +77,7 @@ async fn main() {
         .with_env()
         .init();
     debug!("Sui-Move CLI version: {VERSION}");
+    move_compiler::set_compiler_version(VERSION);
 
     exit_main!(execute_move_command(
         args.package_path,
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use move_cli::base::new;
 use move_package::source_package::layout::SourcePackageLayout;
 use std::{
@@ -15,11 +15,59 @@ const SUI_PKG_NAME: &str = "Sui";
 // Use testnet by default. Probably want to add options to make this configurable later
 const SUI_PKG_PATH: &str = "{ git = \"https://github.com/MystenLabs/sui.git\", subdir = \"crates/sui-framework/packages/sui-framework\", rev = \"framework/testnet\" }";
 
+const PUBLISH_SCRIPT: &str = include_str!("templates/publish.sh");
+
+/// Example code that a new package can be scaffolded with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Template {
+    /// An empty module and test module.
+    #[default]
+    Empty,
+    /// A fungible coin with mint and burn functions.
+    Coin,
+    /// An NFT collection with a `Display`.
+    Nft,
+    /// Items sold through a `Kiosk` with a `TransferPolicy`.
+    Kiosk,
+    /// A game played on a shared object.
+    Game,
+}
+
+impl Template {
+    /// The (source, test) module templates, or `None` for the empty package.
+    fn sources(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Template::Empty => None,
+            Template::Coin => Some((
+                include_str!("templates/coin/sources.move"),
+                include_str!("templates/coin/tests.move"),
+            )),
+            Template::Nft => Some((
+                include_str!("templates/nft/sources.move"),
+                include_str!("templates/nft/tests.move"),
+            )),
+            Template::Kiosk => Some((
+                include_str!("templates/kiosk/sources.move"),
+                include_str!("templates/kiosk/tests.move"),
+            )),
+            Template::Game => Some((
+                include_str!("templates/game/sources.move"),
+                include_str!("templates/game/tests.move"),
+            )),
+        }
+    }
+}
+
 #[derive(Parser)]
 #[group(id = "sui-move-new")]
 pub struct New {
     #[clap(flatten)]
     pub new: new::New,
+
+    /// Scaffold the package with a working example instead of an empty module. Non-empty
+    /// templates also include a script to publish the package to a local network.
+    #[clap(long, value_enum, default_value_t = Template::Empty)]
+    pub template: Template,
 }
 
 impl New {
@@ -37,6 +85,10 @@ impl New {
             "",
         )?;
 
+        if let Some((sources, tests)) = self.template.sources() {
+            return write_template(p, name, sources, tests);
+        }
+
         let mut w = std::fs::File::create(
             p.join(SourcePackageLayout::Sources.path())
                 .join(format!("{name}.move")),
@@ -84,3 +136,36 @@ module {name}::{name}_tests {{
         Ok(())
     }
 }
+
+/// Writes the source and test modules of a template, substituting the package name, along with a
+/// script to publish the package to localnet.
+fn write_template(path: &Path, name: &str, sources: &str, tests: &str) -> anyhow::Result<()> {
+    let instantiate = |template: &str| {
+        template
+            .replace("{{name}}", name)
+            .replace("{{NAME}}", &name.to_uppercase())
+    };
+
+    std::fs::write(
+        path.join(SourcePackageLayout::Sources.path())
+            .join(format!("{name}.move")),
+        instantiate(sources),
+    )?;
+
+    create_dir_all(path.join(SourcePackageLayout::Tests.path()))?;
+    std::fs::write(
+        path.join(SourcePackageLayout::Tests.path())
+            .join(format!("{name}_tests.move")),
+        instantiate(tests),
+    )?;
+
+    let script = path.join("publish.sh");
+    std::fs::write(&script, PUBLISH_SCRIPT)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
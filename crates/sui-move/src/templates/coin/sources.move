@@ -0,0 +1,48 @@
+/// Module: {{name}}
+///
+/// A fungible coin with a single `TreasuryCap` held by the publisher. The
+/// holder of the cap can mint new coins and burn existing ones.
+module {{name}}::{{name}} {
+    use sui::coin::{Self, Coin, TreasuryCap};
+
+    /// One-time witness for the coin type, see `init`.
+    public struct {{NAME}} has drop {}
+
+    /// Registers the currency when the package is published. The metadata is
+    /// frozen and the treasury cap is sent to the publisher.
+    fun init(witness: {{NAME}}, ctx: &mut TxContext) {
+        let (treasury_cap, metadata) = coin::create_currency(
+            witness,
+            9,
+            b"{{NAME}}",
+            b"{{name}}",
+            b"",
+            option::none(),
+            ctx,
+        );
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, ctx.sender());
+    }
+
+    /// Mint `amount` coins and send them to `recipient`.
+    public fun mint(
+        treasury_cap: &mut TreasuryCap<{{NAME}}>,
+        amount: u64,
+        recipient: address,
+        ctx: &mut TxContext,
+    ) {
+        let coin = coin::mint(treasury_cap, amount, ctx);
+        transfer::public_transfer(coin, recipient)
+    }
+
+    /// Destroy `coin`, reducing the total supply.
+    public fun burn(treasury_cap: &mut TreasuryCap<{{NAME}}>, coin: Coin<{{NAME}}>) {
+        coin::burn(treasury_cap, coin);
+    }
+
+    #[test_only]
+    /// Run the module initializer in tests.
+    public fun init_for_testing(ctx: &mut TxContext) {
+        init({{NAME}} {}, ctx)
+    }
+}
@@ -0,0 +1,31 @@
+#[test_only]
+module {{name}}::{{name}}_tests {
+    use sui::coin::{Coin, TreasuryCap};
+    use sui::test_scenario;
+    use {{name}}::{{name}}::{Self, {{NAME}}};
+
+    const PUBLISHER: address = @0xA;
+    const RECIPIENT: address = @0xB;
+
+    #[test]
+    fun test_mint_and_burn() {
+        let mut scenario = test_scenario::begin(PUBLISHER);
+        {{name}}::init_for_testing(scenario.ctx());
+
+        scenario.next_tx(PUBLISHER);
+        let mut cap = scenario.take_from_sender<TreasuryCap<{{NAME}}>>();
+        {{name}}::mint(&mut cap, 1_000, RECIPIENT, scenario.ctx());
+        assert!(cap.total_supply() == 1_000, 0);
+        scenario.return_to_sender(cap);
+
+        scenario.next_tx(PUBLISHER);
+        let mut cap = scenario.take_from_sender<TreasuryCap<{{NAME}}>>();
+        let coin = scenario.take_from_address<Coin<{{NAME}}>>(RECIPIENT);
+        assert!(coin.value() == 1_000, 1);
+        {{name}}::burn(&mut cap, coin);
+        assert!(cap.total_supply() == 0, 2);
+        scenario.return_to_sender(cap);
+
+        scenario.end();
+    }
+}
@@ -0,0 +1,45 @@
+/// Module: {{name}}
+///
+/// A game played on a shared object: every player bumps a shared counter and
+/// whoever brings it to the target wins.
+module {{name}}::{{name}} {
+    /// The game has already been won.
+    const EGameOver: u64 = 0;
+    /// The target must be greater than zero.
+    const EInvalidTarget: u64 = 1;
+
+    /// A shared game that any account can play.
+    public struct Game has key {
+        id: UID,
+        counter: u64,
+        target: u64,
+        winner: Option<address>,
+    }
+
+    /// Create a new game that ends when the counter reaches `target` and share it.
+    public fun create(target: u64, ctx: &mut TxContext) {
+        assert!(target > 0, EInvalidTarget);
+        transfer::share_object(Game {
+            id: object::new(ctx),
+            counter: 0,
+            target,
+            winner: option::none(),
+        })
+    }
+
+    /// Take a turn, bumping the counter. The sender wins if the counter
+    /// reaches the target.
+    public fun play(game: &mut Game, ctx: &TxContext) {
+        assert!(game.winner.is_none(), EGameOver);
+        game.counter = game.counter + 1;
+        if (game.counter == game.target) {
+            game.winner.fill(ctx.sender());
+        }
+    }
+
+    public fun counter(game: &Game): u64 { game.counter }
+
+    public fun target(game: &Game): u64 { game.target }
+
+    public fun winner(game: &Game): Option<address> { game.winner }
+}
@@ -0,0 +1,43 @@
+#[test_only]
+module {{name}}::{{name}}_tests {
+    use sui::test_scenario;
+    use {{name}}::{{name}}::{Self, Game};
+
+    const ALICE: address = @0xA;
+    const BOB: address = @0xB;
+
+    #[test]
+    fun test_last_player_wins() {
+        let mut scenario = test_scenario::begin(ALICE);
+        {{name}}::create(2, scenario.ctx());
+
+        scenario.next_tx(ALICE);
+        let mut game = scenario.take_shared<Game>();
+        {{name}}::play(&mut game, scenario.ctx());
+        assert!(game.winner().is_none(), 0);
+        test_scenario::return_shared(game);
+
+        scenario.next_tx(BOB);
+        let mut game = scenario.take_shared<Game>();
+        {{name}}::play(&mut game, scenario.ctx());
+        assert!(game.counter() == 2, 1);
+        assert!(game.winner() == option::some(BOB), 2);
+        test_scenario::return_shared(game);
+
+        scenario.end();
+    }
+
+    #[test, expected_failure(abort_code = {{name}}::EGameOver)]
+    fun test_play_after_game_over() {
+        let mut scenario = test_scenario::begin(ALICE);
+        {{name}}::create(1, scenario.ctx());
+
+        scenario.next_tx(ALICE);
+        let mut game = scenario.take_shared<Game>();
+        {{name}}::play(&mut game, scenario.ctx());
+        {{name}}::play(&mut game, scenario.ctx());
+
+        test_scenario::return_shared(game);
+        scenario.end();
+    }
+}
@@ -0,0 +1,69 @@
+/// Module: {{name}}
+///
+/// Items that are sold through a `Kiosk`. The publisher owns an empty
+/// `TransferPolicy` (no royalties or other rules), which buyers use to
+/// confirm their purchases.
+module {{name}}::{{name}} {
+    use std::string::{utf8, String};
+    use sui::coin::Coin;
+    use sui::kiosk::{Kiosk, KioskOwnerCap};
+    use sui::package;
+    use sui::sui::SUI;
+    use sui::transfer_policy::{Self, TransferPolicy};
+
+    /// One-time witness used to claim the `Publisher`, see `init`.
+    public struct {{NAME}} has drop {}
+
+    /// An item that can be placed and traded in a `Kiosk`.
+    public struct Item has key, store {
+        id: UID,
+        name: String,
+    }
+
+    /// Claims the `Publisher` and creates the `TransferPolicy` for `Item`.
+    /// The policy is shared so buyers can confirm their purchases, while the
+    /// policy cap and the publisher are sent to the package publisher.
+    fun init(otw: {{NAME}}, ctx: &mut TxContext) {
+        let publisher = package::claim(otw, ctx);
+        let (policy, policy_cap) = transfer_policy::new<Item>(&publisher, ctx);
+
+        transfer::public_share_object(policy);
+        transfer::public_transfer(policy_cap, ctx.sender());
+        transfer::public_transfer(publisher, ctx.sender());
+    }
+
+    /// Mint a new `Item` and list it for `price` MIST in `kiosk`.
+    public fun mint_and_list(
+        kiosk: &mut Kiosk,
+        cap: &KioskOwnerCap,
+        name: vector<u8>,
+        price: u64,
+        ctx: &mut TxContext,
+    ): ID {
+        let item = Item { id: object::new(ctx), name: utf8(name) };
+        let id = object::id(&item);
+        kiosk.place_and_list(cap, item, price);
+        id
+    }
+
+    /// Buy the `Item` with `id` from `kiosk`, resolving the transfer request
+    /// against `policy`.
+    public fun buy(
+        kiosk: &mut Kiosk,
+        policy: &TransferPolicy<Item>,
+        id: ID,
+        payment: Coin<SUI>,
+    ): Item {
+        let (item, request) = kiosk.purchase<Item>(id, payment);
+        policy.confirm_request(request);
+        item
+    }
+
+    public fun name(item: &Item): &String { &item.name }
+
+    #[test_only]
+    /// Run the module initializer in tests.
+    public fun init_for_testing(ctx: &mut TxContext) {
+        init({{NAME}} {}, ctx)
+    }
+}
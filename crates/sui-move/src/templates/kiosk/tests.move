@@ -0,0 +1,37 @@
+#[test_only]
+module {{name}}::{{name}}_tests {
+    use std::string::utf8;
+    use sui::coin;
+    use sui::kiosk;
+    use sui::sui::SUI;
+    use sui::test_scenario;
+    use sui::transfer_policy::TransferPolicy;
+    use {{name}}::{{name}}::{Self, Item};
+
+    const SELLER: address = @0xA;
+    const BUYER: address = @0xB;
+    const PRICE: u64 = 1_000;
+
+    #[test]
+    fun test_list_and_buy() {
+        let mut scenario = test_scenario::begin(SELLER);
+        {{name}}::init_for_testing(scenario.ctx());
+
+        scenario.next_tx(SELLER);
+        let (mut kiosk, cap) = kiosk::new(scenario.ctx());
+        let id = {{name}}::mint_and_list(&mut kiosk, &cap, b"Item", PRICE, scenario.ctx());
+
+        scenario.next_tx(BUYER);
+        let policy = scenario.take_shared<TransferPolicy<Item>>();
+        let payment = coin::mint_for_testing<SUI>(PRICE, scenario.ctx());
+        let item = {{name}}::buy(&mut kiosk, &policy, id, payment);
+        assert!(item.name() == &utf8(b"Item"), 0);
+        assert!(kiosk.profits_amount() == PRICE, 1);
+
+        transfer::public_transfer(item, BUYER);
+        test_scenario::return_shared(policy);
+        let profits = kiosk.close_and_withdraw(cap, scenario.ctx());
+        assert!(coin::burn_for_testing(profits) == PRICE, 2);
+        scenario.end();
+    }
+}
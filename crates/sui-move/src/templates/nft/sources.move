@@ -0,0 +1,71 @@
+/// Module: {{name}}
+///
+/// A simple NFT collection with a `Display` so that wallets and explorers can
+/// render minted items.
+module {{name}}::{{name}} {
+    use std::string::{utf8, String};
+    use sui::display;
+    use sui::package;
+
+    /// One-time witness used to claim the `Publisher`, see `init`.
+    public struct {{NAME}} has drop {}
+
+    /// An NFT minted by this collection.
+    public struct Nft has key, store {
+        id: UID,
+        name: String,
+        description: String,
+        image_url: String,
+    }
+
+    /// Claims the `Publisher` for this package and sets up the `Display`
+    /// for `Nft`, both owned by the publisher.
+    fun init(otw: {{NAME}}, ctx: &mut TxContext) {
+        let publisher = package::claim(otw, ctx);
+
+        let mut display = display::new_with_fields<Nft>(
+            &publisher,
+            vector[utf8(b"name"), utf8(b"description"), utf8(b"image_url")],
+            vector[utf8(b"{name}"), utf8(b"{description}"), utf8(b"{image_url}")],
+            ctx,
+        );
+        display.update_version();
+
+        transfer::public_transfer(publisher, ctx.sender());
+        transfer::public_transfer(display, ctx.sender());
+    }
+
+    /// Mint a new `Nft` and send it to the transaction sender.
+    public fun mint(
+        name: vector<u8>,
+        description: vector<u8>,
+        image_url: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        let nft = Nft {
+            id: object::new(ctx),
+            name: utf8(name),
+            description: utf8(description),
+            image_url: utf8(image_url),
+        };
+        transfer::public_transfer(nft, ctx.sender())
+    }
+
+    /// Permanently delete `nft`.
+    public fun burn(nft: Nft) {
+        let Nft { id, name: _, description: _, image_url: _ } = nft;
+        id.delete()
+    }
+
+    public fun name(nft: &Nft): &String { &nft.name }
+
+    public fun description(nft: &Nft): &String { &nft.description }
+
+    public fun image_url(nft: &Nft): &String { &nft.image_url }
+
+    #[test_only]
+    /// Run the module initializer in tests.
+    public fun init_for_testing(ctx: &mut TxContext) {
+        init({{NAME}} {}, ctx)
+    }
+}
@@ -0,0 +1,35 @@
+#[test_only]
+module {{name}}::{{name}}_tests {
+    use std::string::utf8;
+    use sui::display::Display;
+    use sui::package::Publisher;
+    use sui::test_scenario;
+    use {{name}}::{{name}}::{Self, Nft};
+
+    const CREATOR: address = @0xA;
+
+    #[test]
+    fun test_init_creates_display() {
+        let mut scenario = test_scenario::begin(CREATOR);
+        {{name}}::init_for_testing(scenario.ctx());
+
+        scenario.next_tx(CREATOR);
+        assert!(scenario.has_most_recent_for_sender<Publisher>(), 0);
+        let display = scenario.take_from_sender<Display<Nft>>();
+        assert!(display.version() == 1, 1);
+        scenario.return_to_sender(display);
+        scenario.end();
+    }
+
+    #[test]
+    fun test_mint_and_burn() {
+        let mut scenario = test_scenario::begin(CREATOR);
+        {{name}}::mint(b"Name", b"Description", b"https://example.com/1.png", scenario.ctx());
+
+        scenario.next_tx(CREATOR);
+        let nft = scenario.take_from_sender<Nft>();
+        assert!(nft.name() == &utf8(b"Name"), 0);
+        {{name}}::burn(nft);
+        scenario.end();
+    }
+}
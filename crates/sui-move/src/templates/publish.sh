@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+# Publishes this package to a local network started with `sui start`.
+#
+# Usage: ./publish.sh [gas-budget]
+
+set -euo pipefail
+
+GAS_BUDGET="${1:-100000000}"
+LOCALNET_RPC="http://127.0.0.1:9000"
+PACKAGE_PATH="$(cd "$(dirname "$0")" && pwd)"
+
+if ! sui client envs | grep -q "localnet"; then
+  sui client new-env --alias localnet --rpc "$LOCALNET_RPC"
+fi
+sui client switch --env localnet
+
+# Make sure the active address has gas to pay for the publish transaction.
+sui client faucet || true
+sleep 1
+
+sui client publish --gas-budget "$GAS_BUDGET" "$PACKAGE_PATH"
@@ -39,6 +39,11 @@ use tracing::info;
 //
 //   $ curl 'http://127.0.0.1:1337/node-config'
 //
+// Check whether consensus is making progress, or why it is stalled (e.g. which authorities are
+// missing to reach a quorum):
+//
+//   $ curl 'http://127.0.0.1:1337/consensus-health'
+//
 // Set a time-limited tracing config. After the duration expires, tracing will be disabled
 // automatically.
 //
@@ -56,6 +61,7 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const CONSENSUS_HEALTH: &str = "/consensus-health";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -74,6 +80,7 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(CONSENSUS_HEALTH, get(consensus_health))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -216,6 +223,16 @@ async fn node_config(State(state): State<Arc<AppState>>) -> (StatusCode, String)
     (StatusCode::OK, format!("{:#?}\n", node_config))
 }
 
+async fn consensus_health(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    match state.node.consensus_stall_reason().await {
+        Some(reason) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("consensus is stalled: {reason}\n"),
+        ),
+        None => (StatusCode::OK, "consensus is not stalled\n".to_string()),
+    }
+}
+
 #[derive(Deserialize)]
 struct Epoch {
     epoch: u64,
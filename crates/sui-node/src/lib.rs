@@ -821,6 +821,16 @@ impl SuiNode {
             .set_override_protocol_upgrade_buffer_stake(epoch, buffer_stake_bps)
     }
 
+    /// Returns why consensus cannot make progress, if the node is a validator and consensus is
+    /// stalled.
+    pub async fn consensus_stall_reason(&self) -> Option<String> {
+        self.validator_components
+            .lock()
+            .await
+            .as_ref()
+            .and_then(|components| components.consensus_manager.stall_reason())
+    }
+
     // Testing-only API to start epoch close process.
     // For production code, please use the non-testing version.
     pub async fn close_epoch_for_testing(&self) -> SuiResult {
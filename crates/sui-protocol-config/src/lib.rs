@@ -397,6 +397,17 @@ struct FeatureFlags {
     // Controls the behavior of per object congestion control in consensus handler.
     #[serde(skip_serializing_if = "PerObjectCongestionControlMode::is_none")]
     per_object_congestion_control_mode: PerObjectCongestionControlMode,
+
+    // If true, blocks sent between authorities in Mysticeti consensus are encrypted with keys
+    // derived from the network keys of the sender and receiver, in addition to channel TLS.
+    #[serde(skip_serializing_if = "is_false")]
+    consensus_block_encryption: bool,
+
+    // If true, the timestamp of a Mysticeti commit is the stake-weighted median of the timestamps
+    // of the blocks its leader is built on, rather than the leader's own timestamp. Commits then
+    // carry their timestamp, in the V2 format.
+    #[serde(skip_serializing_if = "is_false")]
+    consensus_median_based_commit_timestamp: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -1212,6 +1223,14 @@ impl ProtocolConfig {
     pub fn per_object_congestion_control_mode(&self) -> PerObjectCongestionControlMode {
         self.feature_flags.per_object_congestion_control_mode
     }
+
+    pub fn consensus_block_encryption(&self) -> bool {
+        self.feature_flags.consensus_block_encryption
+    }
+
+    pub fn consensus_median_based_commit_timestamp(&self) -> bool {
+        self.feature_flags.consensus_median_based_commit_timestamp
+    }
 }
 
 #[cfg(not(msim))]
@@ -2156,6 +2175,14 @@ impl ProtocolConfig {
     pub fn set_max_accumulated_txn_cost_per_object_in_checkpoint(&mut self, val: u64) {
         self.max_accumulated_txn_cost_per_object_in_checkpoint = Some(val);
     }
+
+    pub fn set_consensus_block_encryption_for_testing(&mut self, val: bool) {
+        self.feature_flags.consensus_block_encryption = val;
+    }
+
+    pub fn set_consensus_median_based_commit_timestamp_for_testing(&mut self, val: bool) {
+        self.feature_flags.consensus_median_based_commit_timestamp = val;
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send;
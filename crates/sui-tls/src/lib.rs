@@ -9,7 +9,9 @@ pub const SUI_VALIDATOR_SERVER_NAME: &str = "sui";
 
 pub use acceptor::{TlsAcceptor, TlsConnectionInfo};
 pub use certgen::SelfSignedCertificate;
-pub use verifier::{AllowAll, Allower, CertVerifier, HashSetAllow, ValidatorAllowlist};
+pub use verifier::{
+    public_key_from_certificate, AllowAll, Allower, CertVerifier, HashSetAllow, ValidatorAllowlist,
+};
 
 pub use rustls;
 
@@ -18,7 +20,7 @@ mod tests {
     use super::*;
     use fastcrypto::ed25519::Ed25519KeyPair;
     use fastcrypto::traits::KeyPair;
-    use rustls::server::ClientCertVerifier;
+    use rustls::{client::ServerCertVerifier, server::ClientCertVerifier};
 
     #[test]
     fn verify_allowall() {
@@ -102,6 +104,53 @@ mod tests {
             .unwrap_err();
     }
 
+    #[test]
+    fn verify_server_cert() {
+        let mut rng = rand::thread_rng();
+        let allowed = Ed25519KeyPair::generate(&mut rng);
+        let disallowed = Ed25519KeyPair::generate(&mut rng);
+
+        let allowed_public_key = allowed.public().to_owned();
+        let allowed_cert = SelfSignedCertificate::new(allowed.private(), SUI_VALIDATOR_SERVER_NAME);
+        let disallowed_cert =
+            SelfSignedCertificate::new(disallowed.private(), SUI_VALIDATOR_SERVER_NAME);
+
+        let mut allowlist = HashSetAllow::new();
+        allowlist
+            .inner_mut()
+            .write()
+            .unwrap()
+            .insert(allowed_public_key.clone());
+        let verifier = CertVerifier::new(allowlist);
+        let server_name = rustls::ServerName::try_from(SUI_VALIDATOR_SERVER_NAME).unwrap();
+
+        // Servers are authenticated by their public key, like clients.
+        verifier
+            .verify_server_cert(
+                &allowed_cert.rustls_certificate(),
+                &[],
+                &server_name,
+                &mut std::iter::empty(),
+                &[],
+                std::time::SystemTime::now(),
+            )
+            .unwrap();
+        verifier
+            .verify_server_cert(
+                &disallowed_cert.rustls_certificate(),
+                &[],
+                &server_name,
+                &mut std::iter::empty(),
+                &[],
+                std::time::SystemTime::now(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            public_key_from_certificate(&allowed_cert.rustls_certificate()).unwrap(),
+            allowed_public_key
+        );
+    }
+
     #[test]
     fn invalid_server_name() {
         let mut rng = rand::thread_rng();
@@ -103,16 +103,56 @@ impl<A: Allower> rustls::server::ClientCertVerifier for CertVerifier<A> {
         &[]
     }
 
+    fn verify_client_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        intermediates: &[rustls::Certificate],
+        now: std::time::SystemTime,
+    ) -> Result<rustls::server::ClientCertVerified, rustls::Error> {
+        self.verify_self_signed(
+            end_entity,
+            intermediates,
+            now,
+            webpki::KeyUsage::client_auth(),
+        )
+        .map(|_| rustls::server::ClientCertVerified::assertion())
+    }
+}
+
+/// Verifies servers the same way as clients, so that a client can authenticate a server by the
+/// public key of its self-signed certificate.
+impl<A: Allower> rustls::client::ServerCertVerifier for CertVerifier<A> {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        now: std::time::SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        self.verify_self_signed(
+            end_entity,
+            intermediates,
+            now,
+            webpki::KeyUsage::server_auth(),
+        )
+        .map(|_| rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+impl<A: Allower> CertVerifier<A> {
     // Verifies this is a valid ed25519 self-signed certificate
     // 1. we prepare arguments for webpki's certificate verification (following the rustls implementation)
     //    placing the public key at the root of the certificate chain (as it should be for a self-signed certificate)
     // 2. we call webpki's certificate verification
-    fn verify_client_cert(
+    fn verify_self_signed(
         &self,
         end_entity: &rustls::Certificate,
         intermediates: &[rustls::Certificate],
         now: std::time::SystemTime,
-    ) -> Result<rustls::server::ClientCertVerified, rustls::Error> {
+        usage: webpki::KeyUsage,
+    ) -> Result<(), rustls::Error> {
         // Step 1: Check this matches the key we expect
         let public_key = public_key_from_certificate(end_entity)?;
 
@@ -130,14 +170,7 @@ impl<A: Allower> rustls::server::ClientCertVerifier for CertVerifier<A> {
 
         // Step 2: call verification from webpki
         let cert = cert
-            .verify_for_usage(
-                SUPPORTED_SIG_ALGS,
-                &trustroots,
-                &chain,
-                now,
-                webpki::KeyUsage::client_auth(),
-                &[],
-            )
+            .verify_for_usage(SUPPORTED_SIG_ALGS, &trustroots, &chain, now, usage, &[])
             .map_err(pki_error)
             .map(|_| cert)?;
 
@@ -147,7 +180,6 @@ impl<A: Allower> rustls::server::ClientCertVerifier for CertVerifier<A> {
                 .map_err(|_| rustls::Error::UnsupportedNameType)?;
         cert.verify_is_valid_for_subject_name(dns_nameref)
             .map_err(pki_error)
-            .map(|_| rustls::server::ClientCertVerified::assertion())
     }
 }
 
@@ -189,7 +221,8 @@ fn pki_error(error: webpki::Error) -> rustls::Error {
     }
 }
 
-pub(crate) fn public_key_from_certificate(
+/// Extracts the ed25519 public key of a self-signed certificate.
+pub fn public_key_from_certificate(
     certificate: &rustls::Certificate,
 ) -> Result<Ed25519PublicKey, rustls::Error> {
     use x509_parser::{certificate::X509Certificate, prelude::FromDer};
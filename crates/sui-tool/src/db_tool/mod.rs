@@ -32,6 +32,8 @@ pub enum DbToolCommand {
     ListDBMetadata(Options),
     PrintLastConsensusIndex,
     PrintConsensusCommit(PrintConsensusCommitOptions),
+    PrintConsensusEvents(PrintConsensusEventsOptions),
+    VerifyConsensusStore(VerifyConsensusStoreOptions),
     PrintTransaction(PrintTransactionOptions),
     PrintObject(PrintObjectOptions),
     PrintCheckpoint(PrintCheckpointOptions),
@@ -97,6 +99,45 @@ pub struct PrintConsensusCommitOptions {
     seqnum: u64,
 }
 
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct PrintConsensusEventsOptions {
+    #[arg(
+        long,
+        default_value_t = 0,
+        help = "Sequence number of the first event to print"
+    )]
+    start: u64,
+    #[arg(
+        long,
+        default_value_t = 1000,
+        help = "Maximum number of events to print"
+    )]
+    limit: usize,
+    #[arg(long, help = "Print the events as JSON lines")]
+    json: bool,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct VerifyConsensusStoreOptions {
+    #[arg(
+        long,
+        help = "Repair the store if it is corrupted, by truncating it to its last consistent commit"
+    )]
+    repair: bool,
+    #[arg(
+        long,
+        requires = "repair",
+        help = "Index of the last consensus commit processed by the authority, i.e. the \
+                sub_dag_index printed by print-last-consensus-index. Required to repair the store, \
+                which never truncates commits below it"
+    )]
+    last_processed_commit: Option<u32>,
+    #[arg(long, help = "Print the report as JSON")]
+    json: bool,
+}
+
 #[derive(Parser)]
 #[command(rename_all = "kebab-case")]
 pub struct PrintTransactionOptions {
@@ -198,6 +239,8 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
         }
         DbToolCommand::PrintLastConsensusIndex => print_last_consensus_index(&db_path),
         DbToolCommand::PrintConsensusCommit(d) => print_consensus_commit(&db_path, d),
+        DbToolCommand::PrintConsensusEvents(d) => print_consensus_events(&db_path, d),
+        DbToolCommand::VerifyConsensusStore(d) => verify_consensus_store(&db_path, d),
         DbToolCommand::PrintTransaction(d) => print_transaction(&db_path, d),
         DbToolCommand::PrintObject(o) => print_object(&db_path, o),
         DbToolCommand::PrintCheckpoint(d) => print_checkpoint(&db_path, d),
@@ -276,6 +319,57 @@ pub fn print_consensus_commit(path: &Path, opt: PrintConsensusCommitOptions) ->
     Ok(())
 }
 
+/// Prints the event log of a consensus (Mysticeti) store, e.g. `consensus_db/<epoch>`, to
+/// reconstruct the timeline of an incident.
+pub fn print_consensus_events(path: &Path, opt: PrintConsensusEventsOptions) -> anyhow::Result<()> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid consensus db path {}", path.display()))?;
+    let events = consensus_core::read_event_log(path, opt.start, opt.limit)?;
+    if events.is_empty() {
+        println!(
+            "No consensus events found from sequence number {}",
+            opt.start
+        );
+    }
+    for event in events {
+        if opt.json {
+            println!("{}", serde_json::to_string(&event)?);
+        } else {
+            println!("{event}");
+        }
+    }
+    Ok(())
+}
+
+/// Verifies the integrity of a consensus (Mysticeti) store, e.g. `consensus_db/<epoch>`, and
+/// optionally repairs it, as an alternative to deleting a corrupted store.
+pub fn verify_consensus_store(path: &Path, opt: VerifyConsensusStoreOptions) -> anyhow::Result<()> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid consensus db path {}", path.display()))?;
+    let repair = opt
+        .repair
+        .then(|| {
+            opt.last_processed_commit
+                .ok_or_else(|| anyhow!("--last-processed-commit is required to repair the store"))
+        })
+        .transpose()?;
+    let report = consensus_core::verify_store(path, repair)?;
+    if opt.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{report}");
+    }
+    if opt.repair && !report.is_consistent() {
+        println!(
+            "Repaired the store, truncating commits to {}",
+            report.last_consistent_commit
+        );
+    }
+    Ok(())
+}
+
 pub fn print_transaction(path: &Path, opt: PrintTransactionOptions) -> anyhow::Result<()> {
     let perpetual_db = AuthorityPerpetualTables::open(&path.join("store"), None);
     if let Some((epoch, checkpoint_seq_num)) =
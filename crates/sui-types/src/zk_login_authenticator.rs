@@ -65,6 +65,10 @@ impl ZkLoginAuthenticator {
         self.max_epoch
     }
 
+    pub fn get_user_signature(&self) -> &Signature {
+        &self.user_signature
+    }
+
     #[cfg(feature = "test-utils")]
     pub fn user_signature_mut_for_testing(&mut self) -> &mut Signature {
         &mut self.user_signature
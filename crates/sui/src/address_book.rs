@@ -0,0 +1,293 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, ensure, Context};
+use clap::*;
+use move_command_line_common::{address::NumericalAddress, parser::NumberFormat};
+use serde::{Deserialize, Serialize};
+use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle},
+};
+
+#[cfg(test)]
+#[path = "unit_tests/address_book_tests.rs"]
+mod address_book_tests;
+
+/// The address book file, stored next to the client config.
+const ADDRESS_BOOK_FILE: &str = "address_book.yaml";
+
+/// Commands managing the address book, which holds labeled addresses and object IDs. A label can
+/// be used instead of the address it holds as `@<label>` in commands that accept an address (or
+/// its alias) or an object ID, and in PTBs.
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum AddressBookCommand {
+    /// Add a labeled address or object ID to the address book, replacing the address of the label
+    /// if it is already in the address book. Labels must start with a letter and can contain only
+    /// letters, digits, or underscores (_).
+    Add { label: String, address: SuiAddress },
+    /// List the labeled addresses and object IDs in the address book.
+    List,
+    /// Remove a label from the address book.
+    Remove { label: String },
+    /// Export the address book to a file, to share it or to import it on another machine.
+    Export { output: PathBuf },
+    /// Import the entries of an exported address book. Labels that are already in the address
+    /// book are skipped, unless `--overwrite` is set.
+    Import {
+        file: PathBuf,
+        #[clap(long)]
+        overwrite: bool,
+    },
+}
+
+/// Labeled addresses and object IDs, stored as YAML.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressBook {
+    entries: BTreeMap<String, SuiAddress>,
+}
+
+impl AddressBook {
+    /// The path of the address book of the wallet.
+    pub fn path(context: &WalletContext) -> PathBuf {
+        Self::path_for_config(context.config.path())
+    }
+
+    /// The path of the address book of the wallet whose client config is at `config`.
+    pub fn path_for_config(config: &Path) -> PathBuf {
+        config.with_file_name(ADDRESS_BOOK_FILE)
+    }
+
+    /// Reads the address book of the wallet, which is empty until a label is added to it.
+    pub fn load(context: &WalletContext) -> anyhow::Result<Self> {
+        Self::load_from(&Self::path(context))
+    }
+
+    /// Reads the address book at `path`, which is empty if there is no file there yet.
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::read(path)
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read address book {}", path.display()))?;
+        let book: Self = serde_yaml::from_str(&contents)
+            .with_context(|| format!("Invalid address book {}", path.display()))?;
+        for label in book.entries.keys() {
+            validate_label(label)
+                .with_context(|| format!("Invalid address book {}", path.display()))?;
+        }
+        Ok(book)
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = serde_yaml::to_string(self)?;
+        fs::write(path, contents)
+            .with_context(|| format!("Cannot write address book {}", path.display()))
+    }
+
+    /// Returns the address of `label`.
+    pub fn get(&self, label: &str) -> anyhow::Result<SuiAddress> {
+        self.entries.get(label).copied().ok_or_else(|| {
+            anyhow!(
+                "No address labeled '{label}' in the address book, see \
+                 `sui client address-book list`"
+            )
+        })
+    }
+
+    /// Adds `label`, returning its previous address if it was already in the address book.
+    pub fn add(
+        &mut self,
+        label: String,
+        address: SuiAddress,
+    ) -> anyhow::Result<Option<SuiAddress>> {
+        validate_label(&label)?;
+        Ok(self.entries.insert(label, address))
+    }
+
+    pub fn remove(&mut self, label: &str) -> anyhow::Result<SuiAddress> {
+        let address = self.get(label)?;
+        self.entries.remove(label);
+        Ok(address)
+    }
+
+    /// Adds the entries of `other`, returning the labels that were imported and those that were
+    /// skipped as they are already in the address book with a different address.
+    pub fn import(&mut self, other: AddressBook, overwrite: bool) -> (Vec<String>, Vec<String>) {
+        let mut imported = vec![];
+        let mut skipped = vec![];
+        for (label, address) in other.entries {
+            match self.entries.get(&label) {
+                Some(existing) if *existing == address => continue,
+                Some(_) if !overwrite => skipped.push(label),
+                _ => {
+                    self.entries.insert(label.clone(), address);
+                    imported.push(label);
+                }
+            }
+        }
+        (imported, skipped)
+    }
+
+    /// The addresses by label, as resolved in PTBs.
+    pub fn ptb_addresses(&self) -> BTreeMap<String, NumericalAddress> {
+        self.entries
+            .iter()
+            .map(|(label, address)| {
+                let address = NumericalAddress::new(address.to_inner(), NumberFormat::Hex);
+                (label.clone(), address)
+            })
+            .collect()
+    }
+}
+
+/// Parses an object ID argument of a client command, which can also be the label of an object ID
+/// in the address book, as `@<label>`. Arguments are parsed before the wallet is loaded, so the
+/// address book is found next to the client config given by `--client.config`, or the default one.
+pub fn parse_object_id(s: &str) -> anyhow::Result<ObjectID> {
+    if !s.starts_with('@') {
+        return Ok(ObjectID::from_str(s)?);
+    }
+    let config = match client_config_arg(std::env::args()) {
+        Some(config) => config,
+        None => sui_config_dir()?.join(SUI_CLIENT_CONFIG),
+    };
+    resolve_object_id(s, &AddressBook::path_for_config(&config))
+}
+
+/// Parses an object ID, resolving `@<label>` with the address book at `address_book`.
+fn resolve_object_id(s: &str, address_book: &Path) -> anyhow::Result<ObjectID> {
+    match s.strip_prefix('@') {
+        Some(label) => Ok(AddressBook::load_from(address_book)?.get(label)?.into()),
+        None => Ok(ObjectID::from_str(s)?),
+    }
+}
+
+/// The value of the `--client.config` option in the command line `args`, if it is set.
+fn client_config_arg(mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    while let Some(arg) = args.next() {
+        if arg == "--client.config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(config) = arg.strip_prefix("--client.config=") {
+            return Some(PathBuf::from(config));
+        }
+    }
+    None
+}
+
+/// Labels are used as `@<label>`, so they must be lexed as a single identifier in PTBs, and must
+/// not be confused with hex addresses.
+fn validate_label(label: &str) -> anyhow::Result<()> {
+    let mut chars = label.chars();
+    ensure!(
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        "Invalid label '{label}': labels must start with a letter and can contain only letters, \
+         digits, or underscores (_)"
+    );
+    Ok(())
+}
+
+impl AddressBookCommand {
+    pub fn execute(
+        self,
+        context: &WalletContext,
+    ) -> Result<AddressBookCommandResult, anyhow::Error> {
+        let path = AddressBook::path(context);
+        let mut book = AddressBook::load(context)?;
+        Ok(match self {
+            AddressBookCommand::Add { label, address } => {
+                book.add(label.clone(), address)?;
+                book.write(&path)?;
+                AddressBookCommandResult::Entries(BTreeMap::from([(label, address)]))
+            }
+
+            AddressBookCommand::List => AddressBookCommandResult::Entries(book.entries),
+
+            AddressBookCommand::Remove { label } => {
+                let address = book.remove(&label)?;
+                book.write(&path)?;
+                AddressBookCommandResult::Entries(BTreeMap::from([(label, address)]))
+            }
+
+            AddressBookCommand::Export { output } => {
+                book.write(&output)?;
+                AddressBookCommandResult::Export {
+                    file: output,
+                    entries: book.entries.len(),
+                }
+            }
+
+            AddressBookCommand::Import { file, overwrite } => {
+                let (imported, skipped) = book.import(AddressBook::read(&file)?, overwrite);
+                book.write(&path)?;
+                AddressBookCommandResult::Import { imported, skipped }
+            }
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum AddressBookCommandResult {
+    Entries(BTreeMap<String, SuiAddress>),
+    Export {
+        file: PathBuf,
+        entries: usize,
+    },
+    Import {
+        imported: Vec<String>,
+        skipped: Vec<String>,
+    },
+}
+
+impl Display for AddressBookCommandResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressBookCommandResult::Entries(entries) if entries.is_empty() => {
+                write!(f, "The address book is empty")
+            }
+            AddressBookCommandResult::Entries(entries) => {
+                let mut builder = TableBuilder::default();
+                builder.set_header(["label", "address"]);
+                for (label, address) in entries {
+                    builder.push_record([format!("@{label}"), address.to_string()]);
+                }
+                let mut table = builder.build();
+                table.with(TablePanel::header("Address Book"));
+                table.with(TableStyle::rounded());
+                write!(f, "{table}")
+            }
+            AddressBookCommandResult::Export { file, entries } => {
+                write!(f, "Exported {entries} entries to {}", file.display())
+            }
+            AddressBookCommandResult::Import { imported, skipped } => {
+                write!(f, "Imported {} entries", imported.len())?;
+                if !skipped.is_empty() {
+                    write!(
+                        f,
+                        "\nSkipped labels already in the address book: {} (use --overwrite to \
+                         replace them)",
+                        skipped.join(", ")
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,12 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::address_book::{parse_object_id, AddressBookCommand, AddressBookCommandResult};
+use crate::client_events::{tail_events, EventsFilter};
 use crate::client_ptb::ptb::PTB;
+use crate::client_watch::{watch, WatchTarget};
+use crate::gas_profile::{self, GasProfile};
+use crate::multisig::{MultisigCommand, MultisigCommandResult};
+use crate::retry_policy::RetryPolicy;
 use std::{
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     sync::Arc,
 };
@@ -21,7 +27,7 @@ use fastcrypto::{
 };
 
 use move_binary_format::CompiledModule;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use move_package::BuildConfig as MoveBuildConfig;
 use prometheus::Registry;
 use serde::Serialize;
@@ -38,7 +44,7 @@ use sui_json_rpc_types::{
     SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiParsedData, SuiRawData,
     SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
-use sui_keys::keystore::AccountKeystore;
+use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_move_build::{
     build_from_resolution_graph, check_invalid_dependencies, check_unpublished_dependencies,
     gather_published_ids, BuildConfig, CompiledPackage, PackageDependencies, PublishedAtError,
@@ -51,7 +57,7 @@ use sui_sdk::{
     SUI_COIN_TYPE, SUI_DEVNET_URL, SUI_LOCAL_NETWORK_URL, SUI_TESTNET_URL,
 };
 use sui_types::{
-    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress},
     crypto::{EmptySignInfo, SignatureScheme},
     digests::TransactionDigest,
     dynamic_field::DynamicFieldInfo,
@@ -61,7 +67,9 @@ use sui_types::{
     metrics::BytecodeVerifierMetrics,
     move_package::UpgradeCap,
     object::Owner,
-    parse_sui_type_tag,
+    parse_sui_struct_tag, parse_sui_type_tag,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    quorum_driver_types::ExecuteTransactionRequestType,
     signature::GenericSignature,
     transaction::{SenderSignedData, Transaction, TransactionData, TransactionDataAPI},
 };
@@ -77,7 +85,7 @@ use tabled::{
 
 use tracing::info;
 
-use crate::key_identity::{get_identity_address, KeyIdentity};
+use crate::key_identity::{get_identity_address, get_identity_address_from_keystore, KeyIdentity};
 
 #[path = "unit_tests/profiler_tests.rs"]
 #[cfg(test)]
@@ -86,13 +94,23 @@ mod profiler_tests;
 #[macro_export]
 macro_rules! serialize_or_execute {
     ($tx_data:expr, $serialize_unsigned:expr, $serialize_signed:expr, $context:expr, $result_variant:ident) => {{
+        $crate::serialize_or_execute!(
+            $tx_data,
+            $serialize_unsigned,
+            $serialize_signed,
+            $context,
+            $result_variant,
+            $crate::retry_policy::RetryPolicy::default()
+        )
+    }};
+    ($tx_data:expr, $serialize_unsigned:expr, $serialize_signed:expr, $context:expr, $result_variant:ident, $retry_policy:expr) => {{
         assert!(
             !$serialize_unsigned || !$serialize_signed,
             "Cannot specify both --serialize-unsigned-transaction and --serialize-signed-transaction"
         );
         if $serialize_unsigned {
             SuiClientCommandResult::SerializedUnsignedTransaction($tx_data)
-        } else {
+        } else if $serialize_signed {
             let signature = $context.config.keystore.sign_secure(
                 &$tx_data.sender(),
                 &$tx_data,
@@ -103,22 +121,19 @@ macro_rules! serialize_or_execute {
                 Intent::sui_transaction(),
                 signature,
             );
-            if $serialize_signed {
-                SuiClientCommandResult::SerializedSignedTransaction(sender_signed_data)
-            } else {
-                let transaction = Transaction::new(sender_signed_data);
-                let response = $context.execute_transaction_may_fail(transaction).await?;
-                let effects = response.effects.as_ref().ok_or_else(|| {
-                    anyhow!("Effects from SuiTransactionBlockResult should not be empty")
-                })?;
-                if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
-                    return Err(anyhow!(
-                        "Error executing transaction: {:#?}",
-                        effects.status()
-                    ));
-                }
-                SuiClientCommandResult::$result_variant(response)
+            SuiClientCommandResult::SerializedSignedTransaction(sender_signed_data)
+        } else {
+            let response = $retry_policy.execute($context, $tx_data).await?;
+            let effects = response.effects.as_ref().ok_or_else(|| {
+                anyhow!("Effects from SuiTransactionBlockResult should not be empty")
+            })?;
+            if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
+                return Err(anyhow!(
+                    "Error executing transaction: {:#?}",
+                    effects.status()
+                ));
             }
+            SuiClientCommandResult::$result_variant(response)
         }
     }};
 }
@@ -132,6 +147,14 @@ pub enum SuiClientCommands {
     /// Default environment used for commands when none specified
     #[clap(name = "active-env")]
     ActiveEnv,
+    /// Manage the address book of labeled addresses and object IDs. A label can be used as
+    /// `@<label>` instead of an address in commands that accept an address alias or an object ID,
+    /// and in PTBs.
+    #[clap(name = "address-book")]
+    AddressBook {
+        #[clap(subcommand)]
+        cmd: AddressBookCommand,
+    },
     /// Obtain the Addresses managed by the client.
     #[clap(name = "addresses")]
     Addresses {
@@ -157,7 +180,7 @@ pub enum SuiClientCommands {
     #[clap(name = "call")]
     Call {
         /// Object ID of the package, which contains the module
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         package: ObjectID,
         /// The name of the module in the package
         #[clap(long)]
@@ -180,7 +203,7 @@ pub enum SuiClientCommands {
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         #[clap(long)]
         /// If not provided, a gas object with at least gas_budget value will be selected
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
         #[clap(long)]
@@ -212,10 +235,10 @@ pub enum SuiClientCommands {
     #[clap(name = "dynamic-field")]
     DynamicFieldQuery {
         ///The ID of the parent object
-        #[clap(name = "object_id")]
+        #[clap(name = "object_id", value_parser = parse_object_id)]
         id: ObjectID,
         /// Optional paging cursor
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         cursor: Option<ObjectID>,
         /// Maximum item returned per page
         #[clap(long, default_value = "50")]
@@ -267,14 +290,14 @@ pub enum SuiClientCommands {
     /// Merge two coin objects into one coin
     MergeCoin {
         /// The address of the coin to merge into.
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         primary_coin: ObjectID,
         /// The address of the coin to be merged.
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         coin_to_merge: ObjectID,
         /// The address of the gas object for gas payment.
         /// If not provided, a gas object with at least gas_budget value will be selected.
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
         #[clap(long)]
@@ -294,6 +317,15 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Coordinate a transaction sent from a multisig address: derive the address, collect the
+    /// signature shares of its members in a partially signed transaction file, and execute the
+    /// transaction once the shares meet the threshold.
+    #[clap(name = "multisig")]
+    Multisig {
+        #[clap(subcommand)]
+        cmd: MultisigCommand,
+    },
+
     /// Generate new address and keypair with keypair scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word length can be
@@ -322,7 +354,7 @@ pub enum SuiClientCommands {
     #[clap(name = "object")]
     Object {
         /// Object ID of the object to fetch
-        #[clap(name = "object_id")]
+        #[clap(name = "object_id", value_parser = parse_object_id)]
         id: ObjectID,
 
         /// Return the bcs serialized version of the object
@@ -342,7 +374,7 @@ pub enum SuiClientCommands {
     #[clap(name = "pay")]
     Pay {
         /// The input coins to be used for pay recipients, following the specified amounts.
-        #[clap(long, num_args(1..))]
+        #[clap(long, num_args(1..), value_parser = parse_object_id)]
         input_coins: Vec<ObjectID>,
 
         /// The recipient addresses, must be of same length as amounts
@@ -356,7 +388,7 @@ pub enum SuiClientCommands {
 
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         /// If not provided, a gas object with at least gas_budget value will be selected
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         gas: Option<ObjectID>,
 
         /// Gas budget for this transaction
@@ -381,7 +413,7 @@ pub enum SuiClientCommands {
     /// The input coins also include the coin for gas payment, so no extra gas coin is required.
     PayAllSui {
         /// The input coins to be used for pay recipients, including the gas coin.
-        #[clap(long, num_args(1..))]
+        #[clap(long, num_args(1..), value_parser = parse_object_id)]
         input_coins: Vec<ObjectID>,
 
         /// The recipient address (or its alias if it's an address in the keystore).
@@ -411,7 +443,7 @@ pub enum SuiClientCommands {
     /// The input coins also include the coin for gas payment, so no extra gas coin is required.
     PaySui {
         /// The input coins to be used for pay recipients, including the gas coin.
-        #[clap(long, num_args(1..))]
+        #[clap(long, num_args(1..), value_parser = parse_object_id)]
         input_coins: Vec<ObjectID>,
 
         /// The recipient addresses, must be of same length as amounts.
@@ -441,6 +473,31 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Pay SUI to the recipients listed in a CSV file, with one `recipient,amount` row per payment
+    /// (amounts in MIST). Payments are split across as many transactions as needed, each paying at
+    /// most `--batch-size` recipients from a single gas coin, and a receipt is printed for each
+    /// transaction. When executing sequentially, the transactions after a failed one are skipped.
+    #[clap(name = "pay-batch")]
+    PayBatch {
+        /// Path to the CSV file of payments. Recipients are addresses, or aliases of addresses in
+        /// the keystore. A `recipient,amount` header row and lines starting with `#` are ignored.
+        #[clap(long, value_hint = ValueHint::FilePath)]
+        csv: PathBuf,
+
+        /// Maximum number of payments per transaction
+        #[clap(long, default_value_t = MAX_PAY_BATCH_SIZE)]
+        batch_size: usize,
+
+        /// Gas budget for each transaction
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Execute the transactions concurrently, paying each from a different gas coin, instead
+        /// of one after the other.
+        #[clap(long)]
+        parallel: bool,
+    },
+
     /// Run a PTB either from file or from the provided args
     #[clap(name = "ptb")]
     PTB(PTB),
@@ -458,7 +515,7 @@ pub enum SuiClientCommands {
 
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         /// If not provided, a gas object with at least gas_budget value will be selected
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         gas: Option<ObjectID>,
 
         /// Gas budget for running module initializers
@@ -492,7 +549,7 @@ pub enum SuiClientCommands {
     #[clap(group(ArgGroup::new("split").required(true).args(&["amounts", "count"])))]
     SplitCoin {
         /// Coin to Split, in 20 bytes Hex string
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         coin_id: ObjectID,
         /// Specific amounts to split out from the coin
         #[clap(long, num_args(1..))]
@@ -502,7 +559,7 @@ pub enum SuiClientCommands {
         count: Option<u64>,
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         /// If not provided, a gas object with at least gas_budget value will be selected
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         gas: Option<ObjectID>,
         /// Gas budget for this call
         #[clap(long)]
@@ -551,12 +608,12 @@ pub enum SuiClientCommands {
         to: KeyIdentity,
 
         /// Object to transfer, in 20 bytes Hex string
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         object_id: ObjectID,
 
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         /// If not provided, a gas object with at least gas_budget value will be selected
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         gas: Option<ObjectID>,
 
         /// Gas budget for this transfer
@@ -587,7 +644,7 @@ pub enum SuiClientCommands {
         to: KeyIdentity,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         sui_coin_object_id: ObjectID,
 
         /// Gas budget for this transfer
@@ -620,7 +677,7 @@ pub enum SuiClientCommands {
         package_path: PathBuf,
 
         /// ID of the upgrade capability for the package being upgraded.
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         upgrade_capability: ObjectID,
 
         /// Package build options
@@ -629,7 +686,7 @@ pub enum SuiClientCommands {
 
         /// ID of the gas object for gas payment, in 20 bytes Hex string
         /// If not provided, a gas object with at least gas_budget value will be selected
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         gas: Option<ObjectID>,
 
         /// Gas budget for running module initializers
@@ -701,10 +758,61 @@ pub enum SuiClientCommands {
 
         /// If specified, override the addresses for the package's own modules with this address.
         /// Only works for unpublished modules (whose addresses are currently 0x0).
-        #[clap(long)]
+        #[clap(long, value_parser = parse_object_id)]
         address_override: Option<ObjectID>,
     },
 
+    /// Stream the transactions changing an object, or sent or received by an address, as JSON
+    /// lines until interrupted. Each line holds the digest, checkpoint and timestamp of the
+    /// transaction, along with its events and object changes.
+    #[clap(name = "watch")]
+    #[clap(group(ArgGroup::new("target").required(true).args(&["object", "address"])))]
+    Watch {
+        /// Object to watch the changes of
+        #[clap(long, value_parser = parse_object_id)]
+        object: Option<ObjectID>,
+
+        /// Address (or its alias) to watch the transactions of
+        #[clap(long)]
+        address: Option<KeyIdentity>,
+
+        /// Interval between queries for new transactions, in milliseconds. Only used when the
+        /// active environment has no WebSocket URL to subscribe to transactions with.
+        #[clap(long, default_value_t = 1000)]
+        poll_interval_ms: u64,
+    },
+
+    /// Stream events as they are emitted, one per line until interrupted, optionally filtered by
+    /// the package and module that emitted them, their type, and their sender. Only events that
+    /// match all of the given filters are streamed.
+    #[clap(name = "events")]
+    Events {
+        /// Package the events are emitted from
+        #[clap(long, value_parser = parse_object_id)]
+        package: Option<ObjectID>,
+
+        /// Module the events are emitted from, in the package given by --package
+        #[clap(long, requires = "package")]
+        module: Option<String>,
+
+        /// Type of the events, e.g. 0x2::coin::CurrencyCreated<0x2::sui::SUI>
+        #[clap(long, value_parser = parse_sui_struct_tag)]
+        event_type: Option<StructTag>,
+
+        /// Sender (or its alias) of the transactions that emit the events
+        #[clap(long)]
+        sender: Option<KeyIdentity>,
+
+        /// Interval between queries for new events, in milliseconds. Only used when the active
+        /// environment has no WebSocket URL to subscribe to events with.
+        #[clap(long, default_value_t = 1000)]
+        poll_interval_ms: u64,
+
+        /// Print each event as JSON, instead of as a one line summary
+        #[clap(long)]
+        json: bool,
+    },
+
     /// Profile the gas usage of a transaction. Unless an output filepath is not specified, outputs a file `gas_profile_{tx_digest}_{unix_timestamp}.json` which can be opened in a flamegraph tool such as speedscope.
     #[clap(name = "profile-transaction")]
     ProfileTransaction {
@@ -718,6 +826,25 @@ pub enum SuiClientCommands {
         profile_output: Option<PathBuf>,
     },
 
+    /// Break down the gas used by a programmable transaction per command, Move function, and
+    /// storage effects, by dry running it. Executed transactions are dry run against the current
+    /// state of their input objects, so their gas usage may differ from the original execution.
+    #[clap(name = "profile-tx")]
+    #[clap(group(ArgGroup::new("tx").required(true).args(&["tx_digest", "tx_bytes", "ptb"])))]
+    ProfileTx {
+        /// The digest of an executed transaction to profile
+        #[arg(long, short)]
+        tx_digest: Option<TransactionDigest>,
+
+        /// BCS serialized transaction data bytes without its type tag, as base64 encoded string. This is the output of sui client commands using --serialize-unsigned-transaction.
+        #[arg(long)]
+        tx_bytes: Option<String>,
+
+        /// A PTB to profile, written as for `sui client ptb` (e.g. `--ptb --move-call ... --gas-budget 10000000`, or `--ptb --file <PATH>`), which is built from the active address. Must be the last argument, as all the arguments after it make up the PTB.
+        #[arg(long, num_args = 1.., allow_hyphen_values = true, value_name = "PTB")]
+        ptb: Option<Vec<String>>,
+    },
+
     /// Replay a given transaction to view transaction effects. Set environment variable MOVE_VM_STEP=1 to debug.
     #[clap(name = "replay-transaction")]
     ReplayTransaction {
@@ -780,6 +907,17 @@ impl SuiClientCommands {
     pub async fn execute(
         self,
         context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        self.execute_with_retry_policy(context, RetryPolicy::default())
+            .await
+    }
+
+    /// Executes the command, resubmitting the transactions it executes after transient failures
+    /// according to `retry_policy`.
+    pub async fn execute_with_retry_policy(
+        self,
+        context: &mut WalletContext,
+        retry_policy: RetryPolicy,
     ) -> Result<SuiClientCommandResult, anyhow::Error> {
         let ret = Ok(match self {
             SuiClientCommands::ProfileTransaction {
@@ -805,6 +943,42 @@ impl SuiClientCommands {
                 // this will be displayed via trace info, so no output is needed here
                 SuiClientCommandResult::NoOutput
             }
+            SuiClientCommands::ProfileTx {
+                tx_digest,
+                tx_bytes,
+                ptb,
+            } => {
+                let client = context.get_client().await?;
+                let tx_data = match (tx_digest, tx_bytes, ptb) {
+                    (Some(digest), _, _) => {
+                        let response = client
+                            .read_api()
+                            .get_transaction_with_options(
+                                digest,
+                                SuiTransactionBlockResponseOptions::new().with_raw_input(),
+                            )
+                            .await?;
+                        let data: SenderSignedData = bcs::from_bytes(&response.raw_transaction)?;
+                        gas_profile::refresh_object_refs(
+                            &client,
+                            data.transaction_data().clone(),
+                        )
+                        .await?
+                    }
+                    (None, Some(tx_bytes), _) => bcs::from_bytes(
+                        &Base64::try_from(tx_bytes)
+                            .map_err(|_| anyhow!("Invalid Base64 encoding"))?
+                            .to_vec()
+                            .map_err(|_| anyhow!("Invalid Base64 encoding"))?,
+                    )
+                    .map_err(|_| anyhow!("Failed to parse tx bytes, check if it matches the output of sui client commands with --serialize-unsigned-transaction"))?,
+                    (None, None, Some(ptb)) => PTB::transaction_data(ptb, context).await?,
+                    (None, None, None) => {
+                        bail!("One of --tx-digest, --tx-bytes or --ptb must be provided")
+                    }
+                };
+                SuiClientCommandResult::ProfileTx(GasProfile::new(&client, tx_data).await?)
+            }
             SuiClientCommands::ReplayTransaction {
                 tx_digest,
                 gas_info: _,
@@ -858,6 +1032,9 @@ impl SuiClientCommands {
                 // this will be displayed via trace info, so no output is needed here
                 SuiClientCommandResult::NoOutput
             }
+            SuiClientCommands::AddressBook { cmd } => {
+                SuiClientCommandResult::AddressBook(cmd.execute(context)?)
+            }
             SuiClientCommands::Addresses { sort_by_alias } => {
                 let active_address = context.active_address()?;
                 let mut addresses: Vec<(String, SuiAddress)> = context
@@ -1007,7 +1184,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    Upgrade
+                    Upgrade,
+                    retry_policy
                 )
             }
             SuiClientCommands::Publish {
@@ -1063,7 +1241,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    Publish
+                    Publish,
+                    retry_policy
                 )
             }
 
@@ -1188,7 +1367,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    Call
+                    Call,
+                    retry_policy
                 )
             }
 
@@ -1212,7 +1392,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    Transfer
+                    Transfer,
+                    retry_policy
                 )
             }
 
@@ -1236,7 +1417,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    TransferSui
+                    TransferSui,
+                    retry_policy
                 )
             }
 
@@ -1281,7 +1463,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    Pay
+                    Pay,
+                    retry_policy
                 )
             }
 
@@ -1325,10 +1508,37 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    PaySui
+                    PaySui,
+                    retry_policy
                 )
             }
 
+            SuiClientCommands::PayBatch {
+                csv,
+                batch_size,
+                gas_budget,
+                parallel,
+            } => {
+                ensure!(
+                    (1..=MAX_PAY_BATCH_SIZE).contains(&batch_size),
+                    "Batch size must be between 1 and {MAX_PAY_BATCH_SIZE}"
+                );
+                let payments = read_batch_payments(&csv, &context.config.keystore)?;
+                ensure!(
+                    !payments.is_empty(),
+                    "No payments found in {}",
+                    csv.display()
+                );
+                let sender = context.active_address()?;
+                let batches: Vec<_> = payments.chunks(batch_size).collect();
+                let receipts = if parallel {
+                    pay_batches_in_parallel(context, sender, &batches, gas_budget).await?
+                } else {
+                    pay_batches_sequentially(context, sender, &batches, gas_budget).await?
+                };
+                SuiClientCommandResult::PayBatch(receipts)
+            }
+
             SuiClientCommands::PayAllSui {
                 input_coins,
                 recipient,
@@ -1353,7 +1563,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    PayAllSui
+                    PayAllSui,
+                    retry_policy
                 )
             }
 
@@ -1489,7 +1700,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    SplitCoin
+                    SplitCoin,
+                    retry_policy
                 )
             }
             SuiClientCommands::MergeCoin {
@@ -1511,7 +1723,8 @@ impl SuiClientCommands {
                     serialize_unsigned_transaction,
                     serialize_signed_transaction,
                     context,
-                    MergeCoin
+                    MergeCoin,
+                    retry_policy
                 )
             }
             SuiClientCommands::Switch { address, env } => {
@@ -1570,6 +1783,9 @@ impl SuiClientCommands {
                 let response = context.execute_transaction_may_fail(transaction).await?;
                 SuiClientCommandResult::ExecuteSignedTx(response)
             }
+            SuiClientCommands::Multisig { cmd } => {
+                SuiClientCommandResult::Multisig(cmd.execute(context).await?)
+            }
             SuiClientCommands::ExecuteCombinedSignedTx { signed_tx_bytes } => {
                 let data: SenderSignedData = bcs::from_bytes(
                     &Base64::try_from(signed_tx_bytes)
@@ -1640,6 +1856,47 @@ impl SuiClientCommands {
 
                 SuiClientCommandResult::VerifySource
             }
+            SuiClientCommands::Watch {
+                object,
+                address,
+                poll_interval_ms,
+            } => {
+                let target = match (object, address) {
+                    (Some(object), _) => WatchTarget::Object(object),
+                    (None, address) => {
+                        WatchTarget::Address(get_identity_address(address, context)?)
+                    }
+                };
+                let client = context.get_client().await?;
+                let poll_interval = std::time::Duration::from_millis(poll_interval_ms);
+                let mut stdout = std::io::stdout();
+                tokio::select! {
+                    result = watch(&client, target, poll_interval, &mut stdout) => result?,
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+                SuiClientCommandResult::NoOutput
+            }
+            SuiClientCommands::Events {
+                package,
+                module,
+                event_type,
+                sender,
+                poll_interval_ms,
+                json,
+            } => {
+                let sender = sender
+                    .map(|sender| get_identity_address(Some(sender), context))
+                    .transpose()?;
+                let filter = EventsFilter::new(package, module, event_type, sender)?;
+                let client = context.get_client().await?;
+                let poll_interval = std::time::Duration::from_millis(poll_interval_ms);
+                let mut stdout = std::io::stdout();
+                tokio::select! {
+                    result = tail_events(&client, filter, poll_interval, json, &mut stdout) => result?,
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+                SuiClientCommandResult::NoOutput
+            }
             SuiClientCommands::PTB(ptb) => {
                 ptb.execute(context).await?;
                 SuiClientCommandResult::NoOutput
@@ -1822,6 +2079,9 @@ impl Display for SuiClientCommandResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
         match self {
+            SuiClientCommandResult::AddressBook(result) => {
+                write!(writer, "{}", result)?;
+            }
             SuiClientCommandResult::Addresses(addresses) => {
                 let mut builder = TableBuilder::default();
                 builder.set_header(vec!["alias", "address", "active address"]);
@@ -2018,6 +2278,35 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::Pay(response) => {
                 write!(writer, "{}", response)?;
             }
+            SuiClientCommandResult::PayBatch(receipts) => {
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec![
+                    "Lines",
+                    "Recipients",
+                    "Amount",
+                    "Digest",
+                    "Gas Used",
+                    "Status",
+                ]);
+                for receipt in receipts {
+                    builder.push_record(vec![
+                        format!("{}-{}", receipt.first_line, receipt.last_line),
+                        receipt.recipients.to_string(),
+                        receipt.amount.to_string(),
+                        receipt
+                            .digest
+                            .map_or_else(String::new, |digest| digest.to_string()),
+                        receipt
+                            .gas_used
+                            .map_or_else(String::new, |gas_used| gas_used.to_string()),
+                        receipt.status.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                table.with(TablePanel::header("Batch Payments (MIST)"));
+                write!(writer, "{}", table)?;
+            }
             SuiClientCommandResult::PaySui(response) => {
                 write!(writer, "{}", response)?;
             }
@@ -2048,6 +2337,9 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::ExecuteSignedTx(response) => {
                 write!(writer, "{}", response)?;
             }
+            SuiClientCommandResult::Multisig(result) => {
+                write!(writer, "{}", result)?;
+            }
             SuiClientCommandResult::ActiveEnv(env) => {
                 write!(writer, "{}", env.as_deref().unwrap_or("None"))?;
             }
@@ -2103,6 +2395,9 @@ impl Display for SuiClientCommandResult {
                 table.with(tabled::settings::style::BorderSpanCorrection);
                 writeln!(f, "{}", table)?;
             }
+            SuiClientCommandResult::ProfileTx(profile) => {
+                write!(writer, "{}", profile)?;
+            }
             SuiClientCommandResult::NoOutput => {}
             SuiClientCommandResult::PTB(_) => {} // this is handled in PTB execute
         }
@@ -2143,6 +2438,249 @@ async fn construct_move_call_transaction(
         .await
 }
 
+/// Programmable transactions accept at most 512 arguments per command, and the amounts of a batch
+/// of payments are all passed to a single `SplitCoins` command.
+const MAX_PAY_BATCH_SIZE: usize = 500;
+
+struct BatchPayment {
+    /// Line of the CSV file the payment was read from.
+    line: u64,
+    recipient: SuiAddress,
+    amount: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayBatchReceipt {
+    /// Lines of the CSV file paid by this transaction.
+    pub first_line: u64,
+    pub last_line: u64,
+    pub recipients: usize,
+    pub amount: u64,
+    pub digest: Option<TransactionDigest>,
+    pub gas_used: Option<i64>,
+    pub status: PayBatchStatus,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PayBatchStatus {
+    Success,
+    Failure(String),
+    /// Not executed, because a previous transaction failed.
+    Skipped,
+}
+
+impl Display for PayBatchStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayBatchStatus::Success => write!(f, "success"),
+            PayBatchStatus::Failure(error) => write!(f, "failure: {error}"),
+            PayBatchStatus::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+impl PayBatchReceipt {
+    fn new(batch: &[BatchPayment], status: PayBatchStatus) -> Self {
+        Self {
+            first_line: batch.first().map_or(0, |p| p.line),
+            last_line: batch.last().map_or(0, |p| p.line),
+            recipients: batch.len(),
+            amount: batch
+                .iter()
+                .fold(0, |total: u64, p| total.saturating_add(p.amount)),
+            digest: None,
+            gas_used: None,
+            status,
+        }
+    }
+
+    fn from_result(
+        batch: &[BatchPayment],
+        result: Result<SuiTransactionBlockResponse, anyhow::Error>,
+    ) -> Self {
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => return Self::new(batch, PayBatchStatus::Failure(e.to_string())),
+        };
+        let Some(effects) = response.effects else {
+            return Self {
+                digest: Some(response.digest),
+                ..Self::new(
+                    batch,
+                    PayBatchStatus::Failure("Missing transaction effects".to_string()),
+                )
+            };
+        };
+        let status = match effects.status() {
+            SuiExecutionStatus::Success => PayBatchStatus::Success,
+            SuiExecutionStatus::Failure { error } => PayBatchStatus::Failure(error.clone()),
+        };
+        Self {
+            digest: Some(response.digest),
+            gas_used: Some(effects.gas_cost_summary().net_gas_usage()),
+            ..Self::new(batch, status)
+        }
+    }
+}
+
+/// Reads `recipient,amount` rows from the CSV file at `path`.
+fn read_batch_payments(
+    path: &Path,
+    keystore: &Keystore,
+) -> Result<Vec<BatchPayment>, anyhow::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .comment(Some(b'#'))
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let mut payments = vec![];
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        let line = record.position().map_or(i as u64 + 1, |p| p.line());
+        ensure!(
+            record.len() == 2,
+            "Expected a `recipient,amount` row on line {line}, found {} fields",
+            record.len()
+        );
+        if i == 0 && record[0].eq_ignore_ascii_case("recipient") {
+            continue;
+        }
+        let recipient = KeyIdentity::from_str(&record[0])
+            .and_then(|identity| get_identity_address_from_keystore(identity, keystore))
+            .with_context(|| format!("Invalid recipient on line {line}"))?;
+        let amount = record[1]
+            .parse::<u64>()
+            .with_context(|| format!("Invalid amount on line {line}"))?;
+        payments.push(BatchPayment {
+            line,
+            recipient,
+            amount,
+        });
+    }
+    Ok(payments)
+}
+
+/// Builds a transaction paying `batch` from `gas`, which also pays for the transaction.
+fn pay_batch_transaction(
+    sender: SuiAddress,
+    batch: &[BatchPayment],
+    gas: ObjectRef,
+    gas_budget: u64,
+    gas_price: u64,
+) -> Result<TransactionData, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.pay_sui(
+        batch.iter().map(|p| p.recipient).collect(),
+        batch.iter().map(|p| p.amount).collect(),
+    )?;
+    Ok(TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        builder.finish(),
+        gas_budget,
+        gas_price,
+    ))
+}
+
+/// The balance a gas coin needs to pay for `batch`, and for its transaction.
+fn pay_batch_budget(batch: &[BatchPayment], gas_budget: u64) -> Result<u64, anyhow::Error> {
+    batch
+        .iter()
+        .try_fold(gas_budget, |total, p| total.checked_add(p.amount))
+        .ok_or_else(|| anyhow!("Total amount of a batch overflows"))
+}
+
+async fn pay_batches_sequentially(
+    context: &mut WalletContext,
+    sender: SuiAddress,
+    batches: &[&[BatchPayment]],
+    gas_budget: u64,
+) -> Result<Vec<PayBatchReceipt>, anyhow::Error> {
+    let gas_price = context.get_reference_gas_price().await?;
+    let mut receipts = vec![];
+    let mut failed = false;
+    for batch in batches {
+        if failed {
+            receipts.push(PayBatchReceipt::new(batch, PayBatchStatus::Skipped));
+            continue;
+        }
+        let result = async {
+            let budget = pay_batch_budget(batch, gas_budget)?;
+            let (_, gas) = context
+                .gas_for_owner_budget(sender, budget, BTreeSet::new())
+                .await?;
+            let data =
+                pay_batch_transaction(sender, batch, gas.object_ref(), gas_budget, gas_price)?;
+            context
+                .execute_transaction_may_fail(context.sign_transaction(&data))
+                .await
+        }
+        .await;
+        let receipt = PayBatchReceipt::from_result(batch, result);
+        failed = receipt.status != PayBatchStatus::Success;
+        receipts.push(receipt);
+    }
+    Ok(receipts)
+}
+
+async fn pay_batches_in_parallel(
+    context: &mut WalletContext,
+    sender: SuiAddress,
+    batches: &[&[BatchPayment]],
+    gas_budget: u64,
+) -> Result<Vec<PayBatchReceipt>, anyhow::Error> {
+    let gas_price = context.get_reference_gas_price().await?;
+
+    // Transactions executing concurrently cannot share a gas coin, so all of them are selected
+    // before executing any transaction.
+    let mut gas_coins = BTreeSet::new();
+    let mut transactions = vec![];
+    for batch in batches {
+        let budget = pay_batch_budget(batch, gas_budget)?;
+        let (_, gas) = context
+            .gas_for_owner_budget(sender, budget, gas_coins.clone())
+            .await
+            .context(
+                "Not enough gas coins to pay all batches in parallel, \
+                 split coins or pay batches sequentially",
+            )?;
+        gas_coins.insert(gas.object_id);
+        let data = pay_batch_transaction(sender, batch, gas.object_ref(), gas_budget, gas_price)?;
+        transactions.push(context.sign_transaction(&data));
+    }
+
+    let client = context.get_client().await?;
+    let mut tasks = tokio::task::JoinSet::new();
+    for (i, transaction) in transactions.into_iter().enumerate() {
+        let client = client.clone();
+        tasks.spawn(async move {
+            let result = client
+                .quorum_driver_api()
+                .execute_transaction_block(
+                    transaction,
+                    SuiTransactionBlockResponseOptions::new().with_effects(),
+                    Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+                )
+                .await;
+            (i, result)
+        });
+    }
+
+    let mut results = BTreeMap::new();
+    while let Some(joined) = tasks.join_next().await {
+        let (i, result) = joined?;
+        results.insert(i, result);
+    }
+    Ok(results
+        .into_iter()
+        .map(|(i, result)| PayBatchReceipt::from_result(batches[i], result.map_err(Into::into)))
+        .collect())
+}
+
 fn convert_number_to_string(value: Value) -> Value {
     match value {
         Value::Number(n) => Value::String(n.to_string()),
@@ -2198,6 +2736,12 @@ impl SuiClientCommandResult {
     }
 
     pub fn print(&self, pretty: bool) {
+        self.write(&mut std::io::stdout(), pretty)
+            .expect("Failed to write the command result to stdout");
+    }
+
+    /// Writes the result to `out`, like `print` does to stdout.
+    pub fn write(&self, out: &mut dyn std::io::Write, pretty: bool) -> std::io::Result<()> {
         let line = if pretty {
             format!("{self}")
         } else {
@@ -2206,9 +2750,10 @@ impl SuiClientCommandResult {
         // Log line by line
         for line in line.lines() {
             // Logs write to a file on the side.  Print to stdout and also log to file, for tests to pass.
-            println!("{line}");
+            out.write_all(format!("{line}\n").as_bytes())?;
             info!("{line}")
         }
+        Ok(())
     }
 
     pub fn tx_block_response(&self) -> Option<&SuiTransactionBlockResponse> {
@@ -2347,6 +2892,7 @@ impl ObjectsOutput {
 pub enum SuiClientCommandResult {
     ActiveAddress(Option<SuiAddress>),
     ActiveEnv(Option<String>),
+    AddressBook(AddressBookCommandResult),
     Addresses(AddressesOutput),
     Balance(Vec<(Option<SuiCoinMetadata>, Vec<Coin>)>, bool),
     Call(SuiTransactionBlockResponse),
@@ -2356,6 +2902,7 @@ pub enum SuiClientCommandResult {
     ExecuteSignedTx(SuiTransactionBlockResponse),
     Gas(Vec<GasCoin>),
     MergeCoin(SuiTransactionBlockResponse),
+    Multisig(MultisigCommandResult),
     NewAddress(NewAddressOutput),
     NewEnv(SuiEnv),
     NoOutput,
@@ -2363,7 +2910,9 @@ pub enum SuiClientCommandResult {
     Objects(Vec<SuiObjectResponse>),
     Pay(SuiTransactionBlockResponse),
     PayAllSui(SuiTransactionBlockResponse),
+    PayBatch(Vec<PayBatchReceipt>),
     PaySui(SuiTransactionBlockResponse),
+    ProfileTx(GasProfile),
     PTB(SuiTransactionBlockResponse),
     Publish(SuiTransactionBlockResponse),
     RawObject(SuiObjectResponse),
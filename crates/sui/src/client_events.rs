@@ -0,0 +1,253 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use futures::StreamExt;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use sui_json_rpc_types::{EventFilter, Filter, SuiEvent};
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+const QUERY_PAGE_SIZE: usize = 50;
+
+/// The events streamed by `sui client events`. Only events that match all of the filters that are
+/// set are streamed.
+#[derive(Clone, Debug, Default)]
+pub struct EventsFilter {
+    /// Package the event was emitted from.
+    pub package: Option<ObjectID>,
+    /// Module the event was emitted from, in `package`.
+    pub module: Option<Identifier>,
+    /// Type of the event.
+    pub event_type: Option<StructTag>,
+    /// Sender of the transaction that emitted the event.
+    pub sender: Option<SuiAddress>,
+}
+
+impl EventsFilter {
+    pub fn new(
+        package: Option<ObjectID>,
+        module: Option<String>,
+        event_type: Option<StructTag>,
+        sender: Option<SuiAddress>,
+    ) -> Result<Self, anyhow::Error> {
+        let module = module
+            .map(|m| Identifier::new(m.clone()).map_err(|_| anyhow!("Invalid module name: {m}")))
+            .transpose()?;
+        if module.is_some() && package.is_none() {
+            return Err(anyhow!(
+                "A module can only be filtered on along with its package"
+            ));
+        }
+
+        Ok(Self {
+            package,
+            module,
+            event_type,
+            sender,
+        })
+    }
+
+    /// The filter matching the events to stream, to subscribe to them with, and to check the
+    /// events returned by queries against.
+    fn filter(&self) -> EventFilter {
+        let mut filters = vec![];
+        match (&self.package, &self.module) {
+            (Some(package), Some(module)) => filters.push(EventFilter::MoveModule {
+                package: *package,
+                module: module.clone(),
+            }),
+            (Some(package), None) => filters.push(EventFilter::Package(*package)),
+            (None, _) => {}
+        }
+        if let Some(event_type) = &self.event_type {
+            filters.push(EventFilter::MoveEventType(event_type.clone()));
+        }
+        if let Some(sender) = &self.sender {
+            filters.push(EventFilter::Sender(*sender));
+        }
+        EventFilter::All(filters)
+    }
+
+    /// The fullnode only queries events on a single criterion, so polling queries on the most
+    /// selective one that is set, and the other filters are applied to the results. The fullnode
+    /// cannot query events by package alone, so a package without a module is only applied to the
+    /// results. If no other filter is set, events emitted since `start_ms` are queried.
+    fn query_filter(&self, start_ms: u64) -> EventFilter {
+        if let Some(event_type) = &self.event_type {
+            EventFilter::MoveEventType(event_type.clone())
+        } else if let (Some(package), Some(module)) = (&self.package, &self.module) {
+            EventFilter::MoveModule {
+                package: *package,
+                module: module.clone(),
+            }
+        } else if let Some(sender) = &self.sender {
+            EventFilter::Sender(*sender)
+        } else {
+            EventFilter::TimeRange {
+                start_time: start_ms,
+                end_time: u64::MAX,
+            }
+        }
+    }
+}
+
+/// Streams the events matching `filter` to `out` as they are emitted, one per line, until the
+/// stream of events ends or fails. Each event is printed as JSON if `json` is set, and as a short
+/// summary otherwise. Events are received through a WebSocket subscription when the client has a
+/// WebSocket connection, and by polling the fullnode every `poll_interval` otherwise.
+pub async fn tail_events(
+    client: &SuiClient,
+    filter: EventsFilter,
+    poll_interval: Duration,
+    json: bool,
+    out: &mut impl Write,
+) -> Result<(), anyhow::Error> {
+    let mut emit = |event: SuiEvent| -> Result<(), anyhow::Error> {
+        if json {
+            writeln!(out, "{}", serde_json::to_string(&event)?)?;
+        } else {
+            writeln!(out, "{}", summary(&event))?;
+        }
+        out.flush()?;
+        Ok(())
+    };
+
+    let mut events = match client.event_api().subscribe_event(filter.filter()).await {
+        Ok(subscription) => subscription.boxed(),
+        // The client has no WebSocket connection, fall back to polling.
+        Err(sui_sdk::error::Error::Subscription(_)) => {
+            return poll(client, &filter, poll_interval, &mut emit).await;
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    while let Some(event) = events.next().await {
+        emit(event?)?;
+    }
+    Err(anyhow!("Event subscription closed by the fullnode"))
+}
+
+async fn poll(
+    client: &SuiClient,
+    filter: &EventsFilter,
+    poll_interval: Duration,
+    emit: &mut impl FnMut(SuiEvent) -> Result<(), anyhow::Error>,
+) -> Result<(), anyhow::Error> {
+    let start_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64);
+    let query = filter.query_filter(start_ms);
+    let matches = filter.filter();
+
+    // Only events emitted from now on are streamed, so start after the latest matching event.
+    let latest = client
+        .event_api()
+        .query_events(query.clone(), None, Some(1), true)
+        .await?;
+    let mut cursor = latest.data.first().map(|event| event.id);
+
+    loop {
+        loop {
+            let page = client
+                .event_api()
+                .query_events(query.clone(), cursor, Some(QUERY_PAGE_SIZE), false)
+                .await?;
+            for event in page.data {
+                cursor = Some(event.id);
+                if matches.matches(&event) {
+                    emit(event)?;
+                }
+            }
+            if !page.has_next_page {
+                break;
+            }
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// A one line summary of `event`: when it was emitted, its type, the transaction and sender that
+/// emitted it, and its contents.
+fn summary(event: &SuiEvent) -> String {
+    let timestamp = event
+        .timestamp_ms
+        .map_or_else(|| "-".to_string(), |ms| ms.to_string());
+    format!(
+        "{timestamp} {} tx: {} sender: {} {}",
+        event.type_, event.id.tx_digest, event.sender, event.parsed_json,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_module_requires_package() {
+        assert!(EventsFilter::new(None, Some("m".to_string()), None, None).is_err());
+        assert!(
+            EventsFilter::new(Some(ObjectID::ZERO), Some("0m".to_string()), None, None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_query_filter_is_most_selective() {
+        let package = ObjectID::from_str("0x2").unwrap();
+        let event_type =
+            sui_types::parse_sui_struct_tag("0x2::coin::CurrencyCreated<0x2::sui::SUI>").unwrap();
+        let sender = SuiAddress::ZERO;
+
+        let filter = EventsFilter::new(
+            Some(package),
+            Some("coin".to_string()),
+            Some(event_type.clone()),
+            Some(sender),
+        )
+        .unwrap();
+        assert!(matches!(
+            filter.query_filter(0),
+            EventFilter::MoveEventType(tag) if tag == event_type
+        ));
+
+        let filter = EventsFilter::new(Some(package), None, None, Some(sender)).unwrap();
+        assert!(matches!(
+            filter.query_filter(0),
+            EventFilter::Sender(address) if address == sender
+        ));
+
+        let filter = EventsFilter::default();
+        assert!(matches!(
+            filter.query_filter(42),
+            EventFilter::TimeRange {
+                start_time: 42,
+                end_time: u64::MAX,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_package_is_not_queried_alone() {
+        // The fullnode rejects queries by package, so the package is only matched against the
+        // events of a time range query.
+        let package = ObjectID::from_str("0x2").unwrap();
+        let filter = EventsFilter::new(Some(package), None, None, None).unwrap();
+        assert!(matches!(
+            filter.query_filter(42),
+            EventFilter::TimeRange {
+                start_time: 42,
+                end_time: u64::MAX,
+            }
+        ));
+        let EventFilter::All(filters) = filter.filter() else {
+            panic!("Events should be matched against all filters");
+        };
+        assert!(matches!(filters[..], [EventFilter::Package(id)] if id == package));
+    }
+}
@@ -26,6 +26,7 @@ pub const PUBLISH: &str = "publish";
 pub const UPGRADE: &str = "upgrade";
 pub const ASSIGN: &str = "assign";
 pub const PREVIEW: &str = "preview";
+pub const CONFIRM: &str = "confirm";
 pub const WARN_SHADOWS: &str = "warn-shadows";
 pub const GAS_BUDGET: &str = "gas-budget";
 pub const SUMMARY: &str = "summary";
@@ -64,6 +65,7 @@ pub const COMMANDS: &[&str] = &[
     UPGRADE,
     ASSIGN,
     PREVIEW,
+    CONFIRM,
     WARN_SHADOWS,
     GAS_BUDGET,
     SUMMARY,
@@ -100,6 +102,7 @@ pub struct Program {
 #[derive(Debug, Clone)]
 pub struct ProgramMetadata {
     pub preview_set: bool,
+    pub confirm_set: bool,
     pub summary_set: bool,
     pub serialize_unsigned_set: bool,
     pub serialize_signed_set: bool,
@@ -0,0 +1,131 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client_ptb::displays::Pretty;
+use std::fmt::{Display, Formatter};
+use sui_json_rpc_types::{
+    DryRunTransactionBlockResponse, ObjectChange, SuiTransactionBlockEffectsAPI,
+};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{style::HorizontalLine, Panel as TablePanel, Style as TableStyle},
+};
+
+impl<'a> Display for Pretty<'a, DryRunTransactionBlockResponse> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Pretty(response) = self;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(vec!["change", "object", "type", "details"]);
+        for change in &response.object_changes {
+            let record = match change {
+                ObjectChange::Created {
+                    object_id,
+                    object_type,
+                    owner,
+                    version,
+                    ..
+                } => vec![
+                    "+ created".to_string(),
+                    object_id.to_string(),
+                    object_type.to_string(),
+                    format!("version {}, owner {owner}", version.value()),
+                ],
+                ObjectChange::Mutated {
+                    object_id,
+                    object_type,
+                    owner,
+                    version,
+                    previous_version,
+                    ..
+                } => vec![
+                    "~ mutated".to_string(),
+                    object_id.to_string(),
+                    object_type.to_string(),
+                    format!(
+                        "version {} -> {}, owner {owner}",
+                        previous_version.value(),
+                        version.value()
+                    ),
+                ],
+                ObjectChange::Transferred {
+                    object_id,
+                    object_type,
+                    recipient,
+                    version,
+                    ..
+                } => vec![
+                    "> transferred".to_string(),
+                    object_id.to_string(),
+                    object_type.to_string(),
+                    format!("version {}, recipient {recipient}", version.value()),
+                ],
+                ObjectChange::Deleted {
+                    object_id,
+                    object_type,
+                    version,
+                    ..
+                } => vec![
+                    "- deleted".to_string(),
+                    object_id.to_string(),
+                    object_type.to_string(),
+                    format!("version {}", version.value()),
+                ],
+                ObjectChange::Wrapped {
+                    object_id,
+                    object_type,
+                    version,
+                    ..
+                } => vec![
+                    "- wrapped".to_string(),
+                    object_id.to_string(),
+                    object_type.to_string(),
+                    format!("version {}", version.value()),
+                ],
+                ObjectChange::Published {
+                    package_id,
+                    version,
+                    modules,
+                    ..
+                } => vec![
+                    "+ published".to_string(),
+                    package_id.to_string(),
+                    "package".to_string(),
+                    format!(
+                        "version {}, modules {}",
+                        version.value(),
+                        modules.join(", ")
+                    ),
+                ],
+            };
+            builder.push_record(record);
+        }
+        let mut table = builder.build();
+        table.with(TablePanel::header("Dry Run Object Changes"));
+        table.with(TableStyle::rounded().horizontals([HorizontalLine::new(
+            2,
+            TableStyle::modern().get_horizontal(),
+        )]));
+        writeln!(f, "{}", table)?;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(vec!["owner", "coin type", "amount"]);
+        for change in &response.balance_changes {
+            builder.push_record(vec![
+                change.owner.to_string(),
+                change.coin_type.to_string(),
+                format!("{:+}", change.amount),
+            ]);
+        }
+        let mut table = builder.build();
+        table.with(TablePanel::header("Dry Run Balance Changes"));
+        table.with(TableStyle::rounded().horizontals([HorizontalLine::new(
+            2,
+            TableStyle::modern().get_horizontal(),
+        )]));
+        writeln!(f, "{}", table)?;
+
+        writeln!(f, "Status: {}", Pretty(response.effects.status()))?;
+        write!(f, "{}", Pretty(response.effects.gas_cost_summary()))
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod dry_run;
 mod gas_cost_summary;
 mod ptb_preview;
 mod status;
@@ -0,0 +1,390 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::client_ptb::ast::{
+    ASSIGN, GAS_BUDGET, GAS_COIN, MAKE_MOVE_VEC, MERGE_COINS, MOVE_CALL, PUBLISH, SPLIT_COINS,
+    TRANSFER_OBJECTS, UPGRADE,
+};
+
+/// A PTB program declared in a YAML or JSON file, read with `sui client ptb --file <PATH>`.
+///
+/// The file is translated into the equivalent command line arguments, so every value that is a
+/// string is interpreted as a PTB expression: variables (`coins.0`), addresses (`@0x42`),
+/// `gas`, `none`, `some(..)`, and so on. String literals must therefore be quoted within the
+/// string, e.g. `'"hello"'`. Numbers, booleans and arrays can be written as plain values.
+///
+/// ```yaml
+/// gas:
+///   budget: 10000000
+/// inputs:
+///   recipient: "@0x42"
+/// commands:
+///   - split-coins:
+///       coin: gas
+///       amounts: [1000, 2000]
+///       assign: coins
+///   - transfer-objects:
+///       objects: [coins.0, coins.1]
+///       to: recipient
+/// ```
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PTBFile {
+    pub gas: GasConfig,
+    /// Values bound to variable names before the commands run, in declaration order.
+    #[serde(default)]
+    pub inputs: IndexMap<String, Value>,
+    pub commands: Vec<PTBFileCommand>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GasConfig {
+    /// The gas budget for the transaction, in MIST.
+    pub budget: u64,
+    /// The object ID of the gas coin to use.
+    pub coin: Option<String>,
+}
+
+/// A single command of a PTB file. Every command can bind its result to a variable with
+/// `assign`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub enum PTBFileCommand {
+    #[serde(rename_all = "kebab-case")]
+    SplitCoins {
+        coin: Value,
+        amounts: Value,
+        assign: Option<String>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    MergeCoins {
+        into: Value,
+        coins: Value,
+        assign: Option<String>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    TransferObjects {
+        objects: Value,
+        to: Value,
+        assign: Option<String>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    MakeMoveVec {
+        #[serde(rename = "type")]
+        type_: String,
+        values: Value,
+        assign: Option<String>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    MoveCall {
+        function: String,
+        #[serde(default)]
+        type_args: Vec<String>,
+        #[serde(default)]
+        args: Vec<Value>,
+        assign: Option<String>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Publish {
+        path: String,
+        assign: Option<String>,
+    },
+    #[serde(rename_all = "kebab-case")]
+    Upgrade {
+        path: String,
+        upgrade_cap: Value,
+        assign: Option<String>,
+    },
+}
+
+impl PTBFile {
+    /// Reads a PTB file, as JSON if it has a `.json` extension, and as YAML otherwise.
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read PTB file {}", path.display()))?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let file: Self = if is_json {
+            serde_json::from_str(&contents).map_err(anyhow::Error::from)
+        } else {
+            serde_yaml::from_str(&contents).map_err(anyhow::Error::from)
+        }
+        .with_context(|| format!("Invalid PTB file {}", path.display()))?;
+        file.validate()
+            .with_context(|| format!("Invalid PTB file {}", path.display()))?;
+        Ok(file)
+    }
+
+    fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.commands.is_empty() {
+            bail!("`commands` must contain at least one command");
+        }
+        if self.gas.budget == 0 {
+            bail!("`gas.budget` must be greater than 0");
+        }
+        Ok(())
+    }
+
+    /// Translates the program into the arguments that `sui client ptb` would accept for it on
+    /// the command line.
+    pub fn into_args(self) -> Result<Vec<String>, anyhow::Error> {
+        let mut args = vec![flag(GAS_BUDGET), self.gas.budget.to_string()];
+        if let Some(coin) = self.gas.coin {
+            args.extend([flag(GAS_COIN), coin]);
+        }
+
+        for (name, value) in &self.inputs {
+            let value = render(value).with_context(|| format!("In input `{name}`"))?;
+            args.extend([flag(ASSIGN), name.clone(), value]);
+        }
+
+        for (i, command) in self.commands.into_iter().enumerate() {
+            command
+                .push_args(&mut args)
+                .with_context(|| format!("In command {i}"))?;
+        }
+        Ok(args)
+    }
+}
+
+impl PTBFileCommand {
+    fn push_args(self, args: &mut Vec<String>) -> Result<(), anyhow::Error> {
+        let assign = match self {
+            Self::SplitCoins {
+                coin,
+                amounts,
+                assign,
+            } => {
+                args.extend([
+                    flag(SPLIT_COINS),
+                    render_field("coin", &coin)?,
+                    render_field("amounts", &amounts)?,
+                ]);
+                assign
+            }
+            Self::MergeCoins {
+                into,
+                coins,
+                assign,
+            } => {
+                args.extend([
+                    flag(MERGE_COINS),
+                    render_field("into", &into)?,
+                    render_field("coins", &coins)?,
+                ]);
+                assign
+            }
+            Self::TransferObjects {
+                objects,
+                to,
+                assign,
+            } => {
+                args.extend([
+                    flag(TRANSFER_OBJECTS),
+                    render_field("objects", &objects)?,
+                    render_field("to", &to)?,
+                ]);
+                assign
+            }
+            Self::MakeMoveVec {
+                type_,
+                values,
+                assign,
+            } => {
+                args.extend([
+                    flag(MAKE_MOVE_VEC),
+                    format!("<{type_}>"),
+                    render_field("values", &values)?,
+                ]);
+                assign
+            }
+            Self::MoveCall {
+                function,
+                type_args,
+                args: call_args,
+                assign,
+            } => {
+                args.extend([flag(MOVE_CALL), function]);
+                if !type_args.is_empty() {
+                    args.push(format!("<{}>", type_args.join(", ")));
+                }
+                for (i, arg) in call_args.iter().enumerate() {
+                    args.push(render(arg).with_context(|| format!("In `args[{i}]`"))?);
+                }
+                assign
+            }
+            Self::Publish { path, assign } => {
+                args.extend([flag(PUBLISH), path]);
+                assign
+            }
+            Self::Upgrade {
+                path,
+                upgrade_cap,
+                assign,
+            } => {
+                args.extend([
+                    flag(UPGRADE),
+                    path,
+                    render_field("upgrade-cap", &upgrade_cap)?,
+                ]);
+                assign
+            }
+        };
+
+        if let Some(name) = assign {
+            args.extend([flag(ASSIGN), name]);
+        }
+        Ok(())
+    }
+}
+
+fn flag(command: &str) -> String {
+    format!("--{command}")
+}
+
+fn render_field(field: &str, value: &Value) -> Result<String, anyhow::Error> {
+    render(value).with_context(|| format!("In `{field}`"))
+}
+
+/// Renders a value from the file as a PTB expression.
+fn render(value: &Value) -> Result<String, anyhow::Error> {
+    Ok(match value {
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.as_u64().map(|n| n.to_string()).ok_or_else(|| {
+            anyhow!(
+                "Invalid number {n}: only unsigned integers up to u64 are supported, write larger \
+                 integers as strings with a type suffix, e.g. \"1000u128\""
+            )
+        })?,
+        Value::Array(values) => {
+            let values = values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| render(v).with_context(|| format!("In element {i}")))
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("[{}]", values.join(", "))
+        }
+        Value::Null => bail!("Null values are not supported, use `none` for an empty option"),
+        Value::Object(_) => bail!("Maps are not supported as PTB values"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_ptb::ptb::PTB;
+
+    fn parse_yaml(yaml: &str) -> Result<PTBFile, anyhow::Error> {
+        let file: PTBFile = serde_yaml::from_str(yaml)?;
+        file.validate()?;
+        Ok(file)
+    }
+
+    #[test]
+    fn test_file_to_args() {
+        let file = parse_yaml(
+            r#"
+gas:
+  budget: 5000000
+  coin: "@0x1"
+inputs:
+  amount: 1000
+  recipient: "@0x42"
+commands:
+  - split-coins:
+      coin: gas
+      amounts: [amount, 2000]
+      assign: coins
+  - move-call:
+      function: std::option::is_none
+      type-args: [u64]
+      args: [none]
+  - transfer-objects:
+      objects: [coins.0, coins.1]
+      to: recipient
+"#,
+        )
+        .unwrap();
+
+        let args = file.into_args().unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "--gas-budget",
+                "5000000",
+                "--gas-coin",
+                "@0x1",
+                "--assign",
+                "amount",
+                "1000",
+                "--assign",
+                "recipient",
+                "@0x42",
+                "--split-coins",
+                "gas",
+                "[amount, 2000]",
+                "--assign",
+                "coins",
+                "--move-call",
+                "std::option::is_none",
+                "<u64>",
+                "none",
+                "--transfer-objects",
+                "[coins.0, coins.1]",
+                "recipient",
+            ]
+        );
+        assert!(PTB::parse_ptb_commands(args).is_ok());
+    }
+
+    #[test]
+    fn test_json_file() {
+        let file: PTBFile = serde_json::from_str(
+            r#"{
+                "gas": { "budget": 1000 },
+                "commands": [{ "make-move-vec": { "type": "u64", "values": [1, 2] } }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            file.into_args().unwrap(),
+            vec!["--gas-budget", "1000", "--make-move-vec", "<u64>", "[1, 2]"]
+        );
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        let unknown_field = parse_yaml(
+            "gas: { budget: 1 }\ncommands:\n  - split-coins: { coin: gas, amount: [1] }\n",
+        )
+        .unwrap_err();
+        assert!(unknown_field.to_string().contains("unknown field `amount`"));
+
+        let unknown_command =
+            parse_yaml("gas: { budget: 1 }\ncommands:\n  - split: { coin: gas }\n").unwrap_err();
+        assert!(unknown_command
+            .to_string()
+            .contains("unknown variant `split`"));
+
+        let no_commands = parse_yaml("gas: { budget: 1 }\ncommands: []\n").unwrap_err();
+        assert!(no_commands.to_string().contains("at least one command"));
+
+        let null_value = parse_yaml(
+            "gas: { budget: 1 }\ncommands:\n  - transfer-objects: { objects: [~], to: a }\n",
+        )
+        .unwrap()
+        .into_args()
+        .unwrap_err();
+        assert!(format!("{null_value:#}").contains("In command 0: In `objects`"));
+    }
+}
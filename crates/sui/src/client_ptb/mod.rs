@@ -5,6 +5,7 @@ pub mod ast;
 pub mod builder;
 pub mod displays;
 pub mod error;
+pub mod file;
 pub mod lexer;
 pub mod parser;
 pub mod ptb;
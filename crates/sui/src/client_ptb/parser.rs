@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::iter::Peekable;
+use std::{collections::BTreeMap, iter::Peekable};
 
 use move_command_line_common::{
     address::{NumericalAddress, ParsedAddress},
@@ -29,12 +29,15 @@ use super::{
 pub struct ProgramParser<'a, I: Iterator<Item = &'a str>> {
     tokens: Peekable<Lexer<'a, I>>,
     state: ProgramParsingState,
+    /// Addresses by address book label, used as `@<label>`.
+    address_book: BTreeMap<String, NumericalAddress>,
 }
 
 struct ProgramParsingState {
     parsed: Vec<Spanned<ParsedPTBCommand>>,
     errors: Vec<PTBError>,
     preview_set: bool,
+    confirm_set: bool,
     summary_set: bool,
     warn_shadows_set: bool,
     serialize_unsigned_set: bool,
@@ -56,6 +59,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 parsed: Vec::new(),
                 errors: Vec::new(),
                 preview_set: false,
+                confirm_set: false,
                 summary_set: false,
                 warn_shadows_set: false,
                 serialize_unsigned_set: false,
@@ -64,9 +68,16 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 gas_object_id: None,
                 gas_budget: None,
             },
+            address_book: BTreeMap::new(),
         })
     }
 
+    /// Resolve `@<label>` to the address of the label in the address book.
+    pub fn with_address_book(mut self, address_book: BTreeMap<String, NumericalAddress>) -> Self {
+        self.address_book = address_book;
+        self
+    }
+
     /// Parse the sequence of strings into a PTB program. We continue to parse even if an error is
     /// raised, and return the errors at the end. If no errors are raised, we return the parsed PTB
     /// program along with the metadata that we have parsed (e.g., json output, summary).
@@ -108,6 +119,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 L(T::Command, A::SUMMARY) => flag!(summary_set),
                 L(T::Command, A::JSON) => flag!(json_set),
                 L(T::Command, A::PREVIEW) => flag!(preview_set),
+                L(T::Command, A::CONFIRM) => flag!(confirm_set),
                 L(T::Command, A::WARN_SHADOWS) => flag!(warn_shadows_set),
                 L(T::Command, A::GAS_COIN) => {
                     let specifier = try_!(self.parse_gas_specifier());
@@ -206,6 +218,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 },
                 A::ProgramMetadata {
                     preview_set: self.state.preview_set,
+                    confirm_set: self.state.confirm_set,
                     summary_set: self.state.summary_set,
                     serialize_unsigned_set: self.state.serialize_unsigned_set,
                     serialize_signed_set: self.state.serialize_signed_set,
@@ -689,10 +702,16 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
 
         Ok(match self.parse_address()?.widen_span(sp) {
             sp!(sp, ParsedAddress::Numerical(n)) => sp.wrap(n),
-            sp!(sp, ParsedAddress::Named(n)) => error!(
-                sp,
-                "Expected a numerical address but got a named address '{n}'",
-            ),
+            sp!(sp, ParsedAddress::Named(n)) => match self.address_book.get(&n) {
+                Some(address) => sp.wrap(*address),
+                None => error!(
+                    sp => help: {
+                        "Labels of the address book can be used as '@<label>', see \
+                         `sui client address-book list`"
+                    },
+                    "Expected a numerical address but got a named address '{n}'",
+                ),
+            },
         })
     }
 
@@ -947,6 +966,7 @@ mod tests {
             "--summary",
             "--json",
             "--preview",
+            "--confirm",
             "--warn-shadows",
         ];
         let mut parsed = Vec::new();
@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    address_book::AddressBook,
     client_commands::SuiClientCommandResult,
     client_ptb::{
         ast::{ParsedProgram, Program},
         builder::PTBBuilder,
         displays::Pretty,
         error::{build_error_reports, PTBError},
+        file::PTBFile,
         token::{Lexeme, Token},
     },
     sp,
@@ -20,7 +22,11 @@ use clap::{arg, Args, ValueHint};
 use move_core_types::account_address::AccountAddress;
 use serde::Serialize;
 use shared_crypto::intent::Intent;
-use std::collections::BTreeSet;
+use std::{
+    collections::BTreeSet,
+    io::{BufRead, Write},
+    path::Path,
+};
 use sui_json_rpc_types::{
     SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
 };
@@ -56,11 +62,12 @@ pub struct Summary {
 
 impl PTB {
     /// Parses and executes the PTB with the sender as the current active address
-    pub async fn execute(self, context: &mut WalletContext) -> Result<(), Error> {
+    pub async fn execute(mut self, context: &mut WalletContext) -> Result<(), Error> {
         if self.args.is_empty() {
             ptb_description().print_help().unwrap();
             return Ok(());
         }
+        self.args = Self::expand_file_args(self.args)?;
         let source_string = to_source_string(self.args.clone());
 
         // Tokenize once to detect help flags
@@ -74,22 +81,7 @@ impl PTB {
             }
         }
 
-        // Tokenize and parse to get the program
-        let (program, program_metadata) = match ProgramParser::new(tokens)
-            .map_err(|e| vec![e])
-            .and_then(|parser| parser.parse())
-        {
-            Err(errors) => {
-                let suffix = if errors.len() > 1 { "s" } else { "" };
-                let rendered = build_error_reports(&source_string, errors);
-                eprintln!("Encountered error{suffix} when parsing PTB:");
-                for e in rendered.iter() {
-                    eprintln!("{:?}", e);
-                }
-                anyhow::bail!("Could not build PTB due to previous error{suffix}");
-            }
-            Ok(parsed) => parsed,
-        };
+        let (program, program_metadata) = Self::parse(&self.args, &source_string, context)?;
 
         if program_metadata.serialize_unsigned_set && program_metadata.serialize_signed_set {
             anyhow::bail!("Cannot serialize both signed and unsigned PTBs");
@@ -106,64 +98,23 @@ impl PTB {
             return Ok(());
         }
 
-        let client = context.get_client().await?;
-
-        let (res, warnings) = Self::build_ptb(program, context, client).await;
+        let tx_data =
+            Self::build_transaction_data(program, &program_metadata, &source_string, context)
+                .await?;
 
-        // Render warnings
-        if !warnings.is_empty() {
-            let suffix = if warnings.len() > 1 { "s" } else { "" };
-            eprintln!("Warning{suffix} produced when building PTB:");
-            let rendered = build_error_reports(&source_string, warnings);
-            for e in rendered.iter() {
-                eprintln!("{:?}", e);
+        if program_metadata.confirm_set {
+            let confirmed = Self::confirm_dry_run(
+                context,
+                &tx_data,
+                &mut std::io::stdin().lock(),
+                &mut std::io::stdout(),
+            )
+            .await?;
+            if !confirmed {
+                println!("Transaction was not executed.");
+                return Ok(());
             }
         }
-        let ptb = match res {
-            Err(errors) => {
-                let suffix = if errors.len() > 1 { "s" } else { "" };
-                eprintln!("Encountered error{suffix} when building PTB:");
-                let rendered = build_error_reports(&source_string, errors);
-                for e in rendered.iter() {
-                    eprintln!("{:?}", e);
-                }
-                anyhow::bail!("Could not build PTB due to previous error{suffix}");
-            }
-            Ok(x) => x,
-        };
-
-        // get all the metadata needed for executing the PTB: sender, gas, signing tx
-        // get sender's address -- active address
-        let Some(sender) = context.config.active_address else {
-            anyhow::bail!("No active address, cannot execute PTB");
-        };
-
-        // find the gas coins if we have no gas coin given
-        let coins = if let Some(gas) = program_metadata.gas_object_id {
-            context.get_object_ref(gas.value).await?
-        } else {
-            context
-                .gas_for_owner_budget(sender, program_metadata.gas_budget.value, BTreeSet::new())
-                .await?
-                .1
-                .object_ref()
-        };
-
-        // get the gas price
-        let gas_price = context
-            .get_client()
-            .await?
-            .read_api()
-            .get_reference_gas_price()
-            .await?;
-        // create the transaction data that will be sent to the network
-        let tx_data = TransactionData::new_programmable(
-            sender,
-            vec![coins],
-            ptb,
-            program_metadata.gas_budget.value,
-            gas_price,
-        );
 
         if program_metadata.serialize_unsigned_set {
             serialize_or_execute!(tx_data, true, false, context, PTB).print(true);
@@ -176,6 +127,7 @@ impl PTB {
         }
 
         // sign the tx
+        let sender = tx_data.sender();
         let signature =
             context
                 .config
@@ -233,6 +185,143 @@ impl PTB {
         Ok(())
     }
 
+    /// Parses and builds the PTB in `args` into the data of a transaction from the active
+    /// address, without executing it. Flags of the PTB other than the gas budget and gas coin
+    /// are ignored.
+    pub async fn transaction_data(
+        args: Vec<String>,
+        context: &WalletContext,
+    ) -> Result<TransactionData, Error> {
+        let args = Self::expand_file_args(args)?;
+        let source_string = to_source_string(args.clone());
+        let (program, program_metadata) = Self::parse(&args, &source_string, context)?;
+        Self::build_transaction_data(program, &program_metadata, &source_string, context).await
+    }
+
+    /// Parses the PTB in `args`, rendering any errors against `source_string`.
+    fn parse(
+        args: &[String],
+        source_string: &str,
+        context: &WalletContext,
+    ) -> Result<ParsedProgram, Error> {
+        let tokens = args.iter().map(|s| s.as_str());
+        let address_book = AddressBook::load(context)?.ptb_addresses();
+        match ProgramParser::new(tokens)
+            .map_err(|e| vec![e])
+            .and_then(|parser| parser.with_address_book(address_book).parse())
+        {
+            Err(errors) => {
+                let suffix = if errors.len() > 1 { "s" } else { "" };
+                let rendered = build_error_reports(source_string, errors);
+                eprintln!("Encountered error{suffix} when parsing PTB:");
+                for e in rendered.iter() {
+                    eprintln!("{:?}", e);
+                }
+                anyhow::bail!("Could not build PTB due to previous error{suffix}");
+            }
+            Ok(parsed) => Ok(parsed),
+        }
+    }
+
+    /// Builds `program` into the data of a transaction from the active address, paying for gas
+    /// as set in `program_metadata`.
+    async fn build_transaction_data(
+        program: Program,
+        program_metadata: &ProgramMetadata,
+        source_string: &str,
+        context: &WalletContext,
+    ) -> Result<TransactionData, Error> {
+        let client = context.get_client().await?;
+
+        let (res, warnings) = Self::build_ptb(program, context, client).await;
+
+        // Render warnings
+        if !warnings.is_empty() {
+            let suffix = if warnings.len() > 1 { "s" } else { "" };
+            eprintln!("Warning{suffix} produced when building PTB:");
+            let rendered = build_error_reports(source_string, warnings);
+            for e in rendered.iter() {
+                eprintln!("{:?}", e);
+            }
+        }
+        let ptb = match res {
+            Err(errors) => {
+                let suffix = if errors.len() > 1 { "s" } else { "" };
+                eprintln!("Encountered error{suffix} when building PTB:");
+                let rendered = build_error_reports(source_string, errors);
+                for e in rendered.iter() {
+                    eprintln!("{:?}", e);
+                }
+                anyhow::bail!("Could not build PTB due to previous error{suffix}");
+            }
+            Ok(x) => x,
+        };
+
+        // get all the metadata needed for executing the PTB: sender, gas, signing tx
+        // get sender's address -- active address
+        let Some(sender) = context.config.active_address else {
+            anyhow::bail!("No active address, cannot execute PTB");
+        };
+
+        // find the gas coins if we have no gas coin given
+        let coins = if let Some(gas) = program_metadata.gas_object_id {
+            context.get_object_ref(gas.value).await?
+        } else {
+            context
+                .gas_for_owner_budget(sender, program_metadata.gas_budget.value, BTreeSet::new())
+                .await?
+                .1
+                .object_ref()
+        };
+
+        // get the gas price
+        let gas_price = context
+            .get_client()
+            .await?
+            .read_api()
+            .get_reference_gas_price()
+            .await?;
+        // create the transaction data that will be sent to the network
+        Ok(TransactionData::new_programmable(
+            sender,
+            vec![coins],
+            ptb,
+            program_metadata.gas_budget.value,
+            gas_price,
+        ))
+    }
+
+    /// Dry runs the transaction and writes the objects and balances it would change to
+    /// `output`, then asks for confirmation to proceed on `input`. Returns whether the user
+    /// confirmed, which is never the case if the dry run failed or `input` is closed.
+    pub async fn confirm_dry_run(
+        context: &WalletContext,
+        tx_data: &TransactionData,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> Result<bool, Error> {
+        let response = context
+            .get_client()
+            .await?
+            .read_api()
+            .dry_run_transaction_block(tx_data.clone())
+            .await?;
+        writeln!(output, "{}", Pretty(&response))?;
+        if response.effects.status().is_err() {
+            writeln!(
+                output,
+                "The dry run of the PTB failed, it will not be executed."
+            )?;
+            return Ok(false);
+        }
+
+        write!(output, "Do you want to execute this transaction [y/N]? ")?;
+        output.flush()?;
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        Ok(answer.trim().eq_ignore_ascii_case("y"))
+    }
+
     /// Exposed for testing
     pub async fn build_ptb(
         program: Program,
@@ -253,6 +342,23 @@ impl PTB {
         builder.build(program).await
     }
 
+    /// Replaces a leading `--file <PATH>` with the commands of the PTB file at that path. Any
+    /// arguments that follow it (e.g. `--preview` or `--json`) are kept after the file's commands.
+    fn expand_file_args(args: Vec<String>) -> Result<Vec<String>, Error> {
+        let mut rest = args.iter();
+        match rest.next().map(|s| s.as_str()) {
+            Some("--file") => {
+                let Some(path) = rest.next() else {
+                    anyhow::bail!("Expected a path to a PTB file after --file");
+                };
+                let mut expanded = PTBFile::read(Path::new(path))?.into_args()?;
+                expanded.extend(rest.cloned());
+                Ok(expanded)
+            }
+            _ => Ok(args),
+        }
+    }
+
     /// Exposed for testing
     pub fn parse_ptb_commands(args: Vec<String>) -> Result<ParsedProgram, Vec<PTBError>> {
         ProgramParser::new(args.iter().map(|s| s.as_str()))
@@ -310,6 +416,31 @@ pub fn ptb_description() -> clap::Command {
             \n --assign new_coins # bound new_coins to the result of previous transaction"
         )
         .value_names(["NAME", "VALUE"]))
+        .arg(arg!(
+            --"file" <PATH>
+            "Read the PTB from a YAML or JSON file instead of the command line. Must be the first \
+            argument, and can be followed by flags such as --preview or --json."
+        )
+        .long_help(
+            "Read the PTB from a YAML or JSON file instead of the command line. Must be the first \
+            argument, and can be followed by flags such as --preview or --json. The file declares \
+            the gas configuration, named inputs, and the list of commands. String values are \
+            PTB expressions, as on the command line.\
+            \n\nExample file:\
+            \n gas:\
+            \n   budget: 10000000\
+            \n inputs:\
+            \n   recipient: \"@0x42\"\
+            \n commands:\
+            \n   - split-coins:\
+            \n       coin: gas\
+            \n       amounts: [1000, 2000]\
+            \n       assign: coins\
+            \n   - transfer-objects:\
+            \n       objects: [coins.0, coins.1]\
+            \n       to: recipient"
+        )
+        .value_hint(ValueHint::FilePath))
         .arg(arg!(
             --"gas-coin" <ID> ...
             "The object ID of the gas coin to use. If not specified, it will try to use the first \
@@ -400,6 +531,11 @@ pub fn ptb_description() -> clap::Command {
             --"preview"
             "Preview the list of PTB transactions instead of executing them."
         ))
+        .arg(arg!(
+            --"confirm"
+            "Dry run the PTB and show the objects and balances it changes, then ask for \
+            confirmation before executing it."
+        ))
         .arg(arg!(
             --"serialize-unsigned-transaction"
             "Instead of executing the transaction, serialize the bcs bytes of the unsigned \
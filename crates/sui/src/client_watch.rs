@@ -0,0 +1,200 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use futures::{stream, StreamExt};
+use serde::Serialize;
+use sui_json_rpc_types::{
+    ObjectChange, SuiEvent, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+
+/// Number of recently streamed transactions remembered to avoid streaming a transaction twice,
+/// when it matches several filters.
+const RECENT_TRANSACTIONS: usize = 1000;
+const QUERY_PAGE_SIZE: usize = 50;
+
+/// What `sui client watch` follows.
+#[derive(Clone, Copy, Debug)]
+pub enum WatchTarget {
+    Object(ObjectID),
+    Address(SuiAddress),
+}
+
+impl WatchTarget {
+    /// The fullnode filters transactions on a single criterion, so transactions involving an
+    /// address are followed as the union of those it sent and those it received objects from.
+    fn filters(&self) -> Vec<TransactionFilter> {
+        match self {
+            WatchTarget::Object(id) => vec![TransactionFilter::ChangedObject(*id)],
+            WatchTarget::Address(address) => vec![
+                TransactionFilter::FromAddress(*address),
+                TransactionFilter::ToAddress(*address),
+            ],
+        }
+    }
+}
+
+/// A transaction affecting the watched object or address, streamed as one line of JSON.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WatchedTransaction {
+    digest: TransactionDigest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checkpoint: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp_ms: Option<u64>,
+    events: Vec<SuiEvent>,
+    object_changes: Vec<ObjectChange>,
+}
+
+impl WatchedTransaction {
+    fn new(target: WatchTarget, response: SuiTransactionBlockResponse) -> Self {
+        let mut object_changes = response.object_changes.unwrap_or_default();
+        // Other objects changed by the transaction are not of interest when watching an object.
+        if let WatchTarget::Object(id) = target {
+            object_changes.retain(|change| change.object_id() == id);
+        }
+        Self {
+            digest: response.digest,
+            checkpoint: response.checkpoint,
+            timestamp_ms: response.timestamp_ms,
+            events: response
+                .events
+                .map(|events| events.data)
+                .unwrap_or_default(),
+            object_changes,
+        }
+    }
+}
+
+/// Streams the transactions affecting `target` to `out` as JSON lines, until the stream of
+/// transactions ends or fails. Transactions are received through WebSocket subscriptions when
+/// the client has a WebSocket connection, and by polling the fullnode every `poll_interval`
+/// otherwise.
+pub async fn watch(
+    client: &SuiClient,
+    target: WatchTarget,
+    poll_interval: Duration,
+    out: &mut impl Write,
+) -> Result<(), anyhow::Error> {
+    let mut recent = RecentTransactions::default();
+    let mut emit = |response: SuiTransactionBlockResponse| -> Result<(), anyhow::Error> {
+        if recent.insert(response.digest) {
+            let line = serde_json::to_string(&WatchedTransaction::new(target, response))?;
+            writeln!(out, "{line}")?;
+            out.flush()?;
+        }
+        Ok(())
+    };
+
+    let mut subscriptions = vec![];
+    for filter in target.filters() {
+        match client.read_api().subscribe_transaction(filter).await {
+            Ok(subscription) => subscriptions.push(subscription.boxed()),
+            // The client has no WebSocket connection, fall back to polling.
+            Err(sui_sdk::error::Error::Subscription(_)) => {
+                return poll(client, target, poll_interval, &mut emit).await;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let mut effects = stream::select_all(subscriptions);
+    while let Some(item) = effects.next().await {
+        let digest = *item?.transaction_digest();
+        emit(
+            client
+                .read_api()
+                .get_transaction_with_options(digest, response_options())
+                .await?,
+        )?;
+    }
+    Err(anyhow!("Transaction subscription closed by the fullnode"))
+}
+
+async fn poll(
+    client: &SuiClient,
+    target: WatchTarget,
+    poll_interval: Duration,
+    emit: &mut impl FnMut(SuiTransactionBlockResponse) -> Result<(), anyhow::Error>,
+) -> Result<(), anyhow::Error> {
+    // Only transactions executed from now on are streamed, so start after the latest
+    // transaction matching each filter.
+    let mut cursors = vec![];
+    for filter in target.filters() {
+        let latest = client
+            .read_api()
+            .query_transaction_blocks(
+                SuiTransactionBlockResponseQuery::new_with_filter(filter.clone()),
+                None,
+                Some(1),
+                true,
+            )
+            .await?;
+        cursors.push((filter, latest.data.first().map(|tx| tx.digest)));
+    }
+
+    loop {
+        for (filter, cursor) in cursors.iter_mut() {
+            loop {
+                let page = client
+                    .read_api()
+                    .query_transaction_blocks(
+                        SuiTransactionBlockResponseQuery::new(
+                            Some(filter.clone()),
+                            Some(response_options()),
+                        ),
+                        *cursor,
+                        Some(QUERY_PAGE_SIZE),
+                        false,
+                    )
+                    .await?;
+                for response in page.data {
+                    *cursor = Some(response.digest);
+                    emit(response)?;
+                }
+                if !page.has_next_page {
+                    break;
+                }
+            }
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+fn response_options() -> SuiTransactionBlockResponseOptions {
+    SuiTransactionBlockResponseOptions::new()
+        .with_events()
+        .with_object_changes()
+}
+
+/// Bounded set of the digests of recently streamed transactions.
+#[derive(Default)]
+struct RecentTransactions {
+    digests: HashSet<TransactionDigest>,
+    order: VecDeque<TransactionDigest>,
+}
+
+impl RecentTransactions {
+    /// Returns whether `digest` was not streamed recently.
+    fn insert(&mut self, digest: TransactionDigest) -> bool {
+        if !self.digests.insert(digest) {
+            return false;
+        }
+        self.order.push_back(digest);
+        if self.order.len() > RECENT_TRANSACTIONS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.digests.remove(&oldest);
+            }
+        }
+        true
+    }
+}
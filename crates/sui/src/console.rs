@@ -1,9 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::io::{stderr, Write};
+use std::io::{stderr, stdout, Write};
 use std::ops::Deref;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use clap::Command;
 use clap::CommandFactory;
@@ -15,9 +16,14 @@ use sui_sdk::wallet_context::WalletContext;
 use crate::client_commands::SwitchResponse;
 use crate::client_commands::{SuiClientCommandResult, SuiClientCommands};
 use crate::shell::{
-    install_shell_plugins, AsyncHandler, CacheKey, CommandStructure, CompletionCache, Shell,
+    install_shell_plugins, split_and_unescape, substitute_env_variables, AsyncHandler, CacheKey,
+    CommandStructure, CompletionCache, Shell,
 };
 
+#[path = "unit_tests/console_tests.rs"]
+#[cfg(test)]
+mod console_tests;
+
 const SUI: &str = "   _____       _    ______                       __
   / ___/__  __(_)  / ____/___  ____  _________  / /__
   \\__ \\/ / / / /  / /   / __ \\/ __ \\/ ___/ __ \\/ / _ \\
@@ -88,6 +94,82 @@ pub async fn start_console(
     shell.run_async(out, err).await
 }
 
+/// A console command read from a script or passed with `--eval`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScriptCommand {
+    /// Where the command came from, used in error messages, e.g. `deploy.txt:3`.
+    pub source: String,
+    pub args: Vec<String>,
+}
+
+/// Parses the console commands in `script`, one per line. Blank lines and lines starting with
+/// `#` are skipped. Environment variables are substituted as in the interactive console.
+pub fn parse_script(name: &str, script: &str) -> Result<Vec<ScriptCommand>, anyhow::Error> {
+    let mut commands = vec![];
+    for (idx, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let source = format!("{name}:{}", idx + 1);
+        let args = split_and_unescape(&substitute_env_variables(line.to_string()))
+            .map_err(|e| anyhow!("{source}: {e}"))?;
+        commands.push(ScriptCommand { source, args });
+    }
+    Ok(commands)
+}
+
+/// Runs `commands` one after the other without user interaction, stopping at the first command
+/// that fails. The whole script is parsed before anything runs, so a typo in a later command
+/// does not leave a half-executed script behind. All output goes to `out`, in order. With `json`
+/// set, every command prints its output in JSON format, and `echo` lines are printed as JSON
+/// strings.
+pub async fn run_console_script(
+    mut context: WalletContext,
+    commands: Vec<ScriptCommand>,
+    json: bool,
+    out: &mut (dyn Write + Send),
+) -> Result<(), anyhow::Error> {
+    let mut parsed = vec![];
+    for command in commands {
+        match command.args.first().map(String::as_str) {
+            Some("quit" | "exit") => break,
+            Some("echo") => parsed.push((
+                command.source,
+                ScriptStep::Echo(command.args[1..].join(" ")),
+            )),
+            _ => {
+                let mut opts =
+                    get_command(command.args).map_err(|e| anyhow!("{}: {e}", command.source))?;
+                opts.json |= json;
+                parsed.push((command.source, ScriptStep::Command(opts)));
+            }
+        }
+    }
+
+    let completion_cache = CompletionCache::default();
+    for (source, step) in parsed {
+        match step {
+            ScriptStep::Echo(line) if json => writeln!(out, "{}", serde_json::to_string(&line)?)?,
+            ScriptStep::Echo(line) => writeln!(out, "{line}")?,
+            ScriptStep::Command(opts) => {
+                if handle_command(Ok(opts), &mut context, completion_cache.clone(), out)
+                    .await
+                    .map_err(|e| anyhow!("{source}: {e}"))?
+                {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+enum ScriptStep {
+    Echo(String),
+    Command(ConsoleOpts),
+}
+
 struct ClientCommandHandler;
 
 #[async_trait]
@@ -98,7 +180,7 @@ impl AsyncHandler<WalletContext> for ClientCommandHandler {
         context: &mut WalletContext,
         completion_cache: CompletionCache,
     ) -> bool {
-        match handle_command(get_command(args), context, completion_cache).await {
+        match handle_command(get_command(args), context, completion_cache, &mut stdout()).await {
             Err(e) => {
                 let _err = writeln!(stderr(), "{}", e.to_string().red());
                 false
@@ -115,10 +197,12 @@ fn get_command(args: Vec<String>) -> Result<ConsoleOpts, anyhow::Error> {
     )?)
 }
 
+/// Runs a console command, and writes its result to `out`.
 async fn handle_command(
     wallet_opts: Result<ConsoleOpts, anyhow::Error>,
     context: &mut WalletContext,
     completion_cache: CompletionCache,
+    out: &mut (dyn Write + Send),
 ) -> Result<bool, anyhow::Error> {
     let wallet_opts = wallet_opts?;
     let result = wallet_opts.command.execute(context).await?;
@@ -148,14 +232,17 @@ async fn handle_command(
             _ => {}
         }
     }
-    result.print(!wallet_opts.json);
+    result.write(out, !wallet_opts.json)?;
 
     // Quit shell after RPC switch
     if matches!(
         result,
         SuiClientCommandResult::Switch(SwitchResponse { env: Some(_), .. })
     ) {
-        println!("Sui environment switch completed, please restart Sui console.");
+        writeln!(
+            out,
+            "Sui environment switch completed, please restart Sui console."
+        )?;
         return Ok(true);
     }
     Ok(false)
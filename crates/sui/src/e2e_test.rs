@@ -0,0 +1,181 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client_commands::{SuiClientCommandResult, SuiClientCommands};
+use crate::console::{parse_script, run_console_script};
+use anyhow::{anyhow, bail};
+use fastcrypto::traits::KeyPair;
+use move_package::BuildConfig;
+use std::fs;
+use std::io::{stderr, stdout, Write};
+use std::path::{Path, PathBuf};
+use sui_config::{Config, SUI_CLIENT_CONFIG, SUI_KEYSTORE_FILENAME, SUI_NETWORK_CONFIG};
+use sui_json_rpc_types::get_new_package_obj_from_response;
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_move::e2e_test::E2eTest;
+use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+use sui_sdk::wallet_context::WalletContext;
+use sui_swarm::memory::Swarm;
+use sui_types::crypto::SuiKeyPair;
+
+/// Subdirectory of the artifacts directory holding the configs and databases of the network.
+const NETWORK_DIR: &str = "network";
+/// The effects of the transaction publishing the package, in JSON format.
+const PUBLISH_EFFECTS_FILE: &str = "publish.json";
+/// The combined output of the test command.
+const TEST_COMMAND_LOG_FILE: &str = "test-command.log";
+
+/// Runs `sui move e2e-test`: starts a local network, publishes the package at `package_path` to
+/// it, and runs the test scripts and the test command against it. The network is stopped
+/// afterwards. Its artifacts are kept in the artifacts directory when it is given, or when a test
+/// fails, and removed otherwise.
+pub async fn run_e2e_test(
+    package_path: Option<PathBuf>,
+    build_config: BuildConfig,
+    args: E2eTest,
+) -> Result<(), anyhow::Error> {
+    let (artifacts_dir, keep_artifacts) = match &args.artifacts_dir {
+        Some(dir) => {
+            fs::create_dir_all(dir)?;
+            (dir.clone(), true)
+        }
+        None => {
+            let dir = tempfile::Builder::new().prefix("sui-e2e-test-").tempdir()?;
+            (dir.into_path(), false)
+        }
+    };
+
+    let result = run(&artifacts_dir, package_path, build_config, args).await;
+    match &result {
+        Ok(()) if !keep_artifacts => fs::remove_dir_all(&artifacts_dir)?,
+        Ok(()) => println!("Artifacts kept in {}", artifacts_dir.display()),
+        Err(_) => eprintln!("Artifacts kept in {}", artifacts_dir.display()),
+    }
+    result
+}
+
+async fn run(
+    artifacts_dir: &Path,
+    package_path: Option<PathBuf>,
+    build_config: BuildConfig,
+    args: E2eTest,
+) -> Result<(), anyhow::Error> {
+    let mut swarm = Swarm::builder()
+        .dir(artifacts_dir.join(NETWORK_DIR))
+        .with_fullnode_count(1)
+        .build();
+    swarm.launch().await?;
+    let (client_config_path, rpc) = write_client_config(&swarm)?;
+
+    let mut context = WalletContext::new(&client_config_path, None, None)?;
+    let publish = SuiClientCommands::Publish {
+        package_path: package_path.unwrap_or_else(|| PathBuf::from(".")),
+        build_config,
+        gas: None,
+        gas_budget: args.gas_budget,
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: args.with_unpublished_dependencies,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    };
+    let SuiClientCommandResult::Publish(response) = publish.execute(&mut context).await? else {
+        bail!("Unexpected result of publishing the package");
+    };
+    fs::write(
+        artifacts_dir.join(PUBLISH_EFFECTS_FILE),
+        serde_json::to_string_pretty(&response)?,
+    )?;
+    let (package_id, _, _) = get_new_package_obj_from_response(&response)
+        .ok_or_else(|| anyhow!("No package published by transaction {}", response.digest))?;
+    println!("Published package {package_id}");
+
+    // Scripts refer to these like to environment variables, and the test command gets them in its
+    // environment. They are not set in the environment of this process, which is shared with the
+    // threads of the runtime.
+    let vars = [
+        ("SUI_E2E_PACKAGE_ID", package_id.to_string()),
+        ("SUI_E2E_RPC_URL", rpc),
+        (
+            "SUI_E2E_CLIENT_CONFIG",
+            client_config_path.display().to_string(),
+        ),
+    ];
+
+    for script in &args.scripts {
+        let contents = fs::read_to_string(script)
+            .map_err(|e| anyhow!("Cannot read test script {}: {e}", script.display()))?;
+        let contents = substitute_variables(&contents, &vars);
+        let commands = parse_script(&script.display().to_string(), &contents)?;
+        let context = WalletContext::new(&client_config_path, None, None)?;
+        run_console_script(context, commands, false, &mut stdout()).await?;
+    }
+
+    if let Some((program, program_args)) = args.command.split_first() {
+        let output = tokio::process::Command::new(program)
+            .args(program_args)
+            .envs(vars)
+            .output()
+            .await
+            .map_err(|e| anyhow!("Cannot run test command {program}: {e}"))?;
+        stdout().write_all(&output.stdout)?;
+        stderr().write_all(&output.stderr)?;
+        fs::write(
+            artifacts_dir.join(TEST_COMMAND_LOG_FILE),
+            [output.stdout, output.stderr].concat(),
+        )?;
+        if !output.status.success() {
+            bail!(
+                "Test command {} failed: {}",
+                args.command.join(" "),
+                output.status
+            );
+        }
+    }
+
+    println!("All end-to-end tests passed");
+    Ok(())
+}
+
+/// Replaces `$NAME` with the value of each of `vars` in `script`. Other variables are left to
+/// the console, which substitutes environment variables.
+fn substitute_variables(script: &str, vars: &[(&str, String)]) -> String {
+    vars.iter()
+        .fold(script.to_string(), |script, (name, value)| {
+            script.replace(&format!("${name}"), value)
+        })
+}
+
+/// Writes the network config, and a client config and keystore with the genesis accounts for the
+/// fullnode of `swarm`, to the directory of the swarm. Returns the path of the client config and
+/// the RPC URL of the fullnode.
+fn write_client_config(swarm: &Swarm) -> Result<(PathBuf, String), anyhow::Error> {
+    let dir = swarm.dir();
+    swarm.config().save(dir.join(SUI_NETWORK_CONFIG))?;
+
+    let fullnode = swarm
+        .fullnodes()
+        .next()
+        .ok_or_else(|| anyhow!("The network has no full node"))?;
+    let rpc = format!("http://127.0.0.1:{}", fullnode.json_rpc_address().port());
+
+    let mut keystore = FileBasedKeystore::new(&dir.join(SUI_KEYSTORE_FILENAME))?;
+    for key in &swarm.config().account_keys {
+        keystore.add_key(None, SuiKeyPair::Ed25519(key.copy()))?;
+    }
+    let active_address = keystore.addresses().first().cloned();
+
+    let client_config_path = dir.join(SUI_CLIENT_CONFIG);
+    SuiClientConfig {
+        keystore: Keystore::from(keystore),
+        envs: vec![SuiEnv {
+            alias: "localnet".to_string(),
+            rpc: rpc.clone(),
+            ws: None,
+        }],
+        active_address,
+        active_env: Some("localnet".to_string()),
+    }
+    .persisted(&client_config_path)
+    .save()?;
+    Ok((client_config_path, rpc))
+}
@@ -10,30 +10,48 @@
 //! --sui-node-config-path validator.yaml \
 //! --account-key-path account.key \
 //! --fullnode-rpc-url http://fullnode-my-local-net:9000
+//!
+//! sui fire-drill epoch-change-failover \
+//! --sui-node-config-path validator.yaml \
+//! --account-key-path account.key \
+//! --fullnode-rpc-url http://fullnode-my-local-net:9000 \
+//! --report-path failover-report.json
 
-use anyhow::bail;
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
 use clap::*;
 use fastcrypto::ed25519::Ed25519KeyPair;
 use fastcrypto::traits::{KeyPair, ToFromBytes};
 use move_core_types::ident_str;
+use serde::Serialize;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use sui_config::node::{AuthorityKeyPairWithPath, KeyPairWithPath};
 use sui_config::{local_ip_utils, Config, NodeConfig, PersistedConfig};
 use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockResponseOptions};
 use sui_keys::keypair_file::read_keypair_from_file;
 use sui_sdk::{rpc_types::SuiTransactionBlockEffectsAPI, SuiClient, SuiClientBuilder};
 use sui_types::base_types::{ObjectRef, SuiAddress};
-use sui_types::crypto::{generate_proof_of_possession, get_key_pair, SuiKeyPair};
+use sui_types::crypto::{
+    generate_proof_of_possession, get_key_pair, AuthorityPublicKeyBytes, SuiKeyPair,
+};
 use sui_types::multiaddr::{Multiaddr, Protocol};
+use sui_types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
 use sui_types::transaction::{
     CallArg, Transaction, TransactionData, TEST_ONLY_GAS_UNIT_FOR_GENERIC,
 };
 use sui_types::{committee::EpochId, crypto::get_authority_key_pair, SUI_SYSTEM_PACKAGE_ID};
-use tracing::info;
+use tracing::{info, warn};
+
+#[cfg(test)]
+#[path = "unit_tests/fire_drill_tests.rs"]
+mod fire_drill_tests;
 
 #[derive(Parser)]
 pub enum FireDrill {
     MetadataRotation(MetadataRotation),
+    EpochChangeFailover(EpochChangeFailover),
 }
 
 #[derive(Parser)]
@@ -49,11 +67,43 @@ pub struct MetadataRotation {
     fullnode_rpc_url: String,
 }
 
+/// Rehearses the failover of a validator to new keys and addresses across an epoch boundary: the
+/// metadata rotation, followed by checks that the validator runs with its new keys and serves
+/// traffic at its new network address. When any step fails, the validator is rolled back to its
+/// current keys and addresses, unless its new keys already took effect: it then keeps its new
+/// config, and the rest of its new metadata is resubmitted for the next epoch.
+#[derive(Parser)]
+pub struct EpochChangeFailover {
+    /// Path to sui node config.
+    #[clap(long = "sui-node-config-path")]
+    sui_node_config_path: PathBuf,
+    /// Path to account key file.
+    #[clap(long = "account-key-path")]
+    account_key_path: PathBuf,
+    /// Jsonrpc url for a reliable fullnode.
+    #[clap(long = "fullnode-rpc-url")]
+    fullnode_rpc_url: String,
+    /// How long to wait for the next epoch, in seconds, before rolling back.
+    #[clap(long, default_value_t = 24 * 60 * 60)]
+    epoch_timeout_secs: u64,
+    /// How long to wait for the validator to serve traffic at its new network address after the
+    /// epoch change, in seconds, before rolling back. This includes restarting the node with the
+    /// new config.
+    #[clap(long, default_value_t = 10 * 60)]
+    validation_timeout_secs: u64,
+    /// Path to write the report of the drill to, in JSON format.
+    #[clap(long = "report-path")]
+    report_path: Option<PathBuf>,
+}
+
 pub async fn run_fire_drill(fire_drill: FireDrill) -> anyhow::Result<()> {
     match fire_drill {
         FireDrill::MetadataRotation(metadata_rotation) => {
             run_metadata_rotation(metadata_rotation).await?;
         }
+        FireDrill::EpochChangeFailover(epoch_change_failover) => {
+            run_epoch_change_failover(epoch_change_failover).await?;
+        }
     }
     Ok(())
 }
@@ -78,7 +128,7 @@ async fn run_metadata_rotation(metadata_rotation: MetadataRotation) -> anyhow::R
     info!("Running Metadata Rotation fire drill for validator address {sui_address} in epoch {starting_epoch}.");
 
     // Prepare new metadata for next epoch
-    let new_config_path =
+    let (new_config_path, _) =
         update_next_epoch_metadata(&sui_node_config_path, &config, &sui_client, &account_key)
             .await?;
 
@@ -97,6 +147,553 @@ async fn run_metadata_rotation(metadata_rotation: MetadataRotation) -> anyhow::R
     Ok(())
 }
 
+async fn run_epoch_change_failover(drill: EpochChangeFailover) -> anyhow::Result<()> {
+    let account_key = read_keypair_from_file(&drill.account_key_path)?;
+    let config: NodeConfig = PersistedConfig::read(&drill.sui_node_config_path).map_err(|err| {
+        err.context(format!(
+            "Cannot open Sui Node Config file at {:?}",
+            drill.sui_node_config_path
+        ))
+    })?;
+    let starting_config = std::fs::read(&drill.sui_node_config_path)?;
+
+    let sui_client = SuiClientBuilder::default()
+        .build(&drill.fullnode_rpc_url)
+        .await?;
+    let network = SuiNetwork {
+        sui_client: &sui_client,
+        account_key: &account_key,
+    };
+    let sui_address = SuiAddress::from(&account_key.public());
+    let starting_epoch = current_epoch(&sui_client).await?;
+    info!("Running Epoch Change Failover fire drill for validator address {sui_address} in epoch {starting_epoch}.");
+    let starting_metadata = network.validator().await?.metadata;
+
+    let mut report = FailoverReport {
+        validator: sui_address,
+        starting_epoch,
+        steps: vec![],
+        rolled_back: false,
+        kept_new_config: false,
+    };
+    let prepared = report
+        .step("Prepare the new config", async {
+            prepare_next_epoch_config(
+                &drill.sui_node_config_path,
+                &config,
+                &starting_metadata,
+                sui_address,
+            )
+        })
+        .await;
+    let result = match prepared {
+        Ok((new_config_path, new_metadata)) => {
+            let failover = Failover {
+                network: &network,
+                config_path: &drill.sui_node_config_path,
+                starting_config,
+                new_config_path,
+                starting_metadata,
+                new_metadata,
+                epoch_timeout: Duration::from_secs(drill.epoch_timeout_secs),
+                validation_timeout: Duration::from_secs(drill.validation_timeout_secs),
+                poll_interval: EPOCH_POLL_INTERVAL,
+            };
+            failover.run(&mut report).await
+        }
+        Err(err) => {
+            // Nothing changed yet, on chain or in the node config
+            let new_config_path = next_epoch_config_path(&drill.sui_node_config_path);
+            if new_config_path.exists() {
+                std::fs::remove_file(new_config_path)?;
+            }
+            report.rolled_back = true;
+            Err(err)
+        }
+    };
+
+    println!("{report}");
+    if let Some(report_path) = &drill.report_path {
+        std::fs::write(report_path, serde_json::to_string_pretty(&report)?)?;
+    }
+    result
+}
+
+/// How often to poll the current epoch while waiting for the next one.
+const EPOCH_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How many times to roll back, when the epoch changes while rolling back.
+const MAX_ROLLBACK_ATTEMPTS: usize = 3;
+
+/// The state of a validator on chain.
+#[derive(Clone, Debug)]
+struct OnChainValidator {
+    /// The epoch the state was read in.
+    epoch: EpochId,
+    metadata: ValidatorMetadata,
+    /// The metadata of the validator in the next epoch, as updated so far.
+    next_epoch_metadata: ValidatorMetadata,
+}
+
+/// The operations of the Epoch Change Failover fire drill on the network, for the validator
+/// rehearsing the failover.
+#[async_trait]
+trait FailoverNetwork: Sync {
+    async fn current_epoch(&self) -> anyhow::Result<EpochId>;
+
+    async fn validator(&self) -> anyhow::Result<OnChainValidator>;
+
+    /// Makes `metadata` that of the validator in the next epoch, updating the fields that differ
+    /// from `next_epoch_metadata`. Returns the epochs the updates were executed in, in order.
+    async fn update_next_epoch(
+        &self,
+        metadata: &ValidatorMetadata,
+        next_epoch_metadata: &ValidatorMetadata,
+    ) -> anyhow::Result<Vec<EpochId>>;
+
+    /// Whether `protocol_pubkey` is that of a member of the committee of `epoch`.
+    async fn in_committee(&self, epoch: EpochId, protocol_pubkey: &[u8]) -> anyhow::Result<bool>;
+
+    /// Waits until a connection to `address` can be opened.
+    async fn wait_for_traffic(&self, address: &Multiaddr) -> anyhow::Result<()>;
+}
+
+/// The network seen through a fullnode, for the validator of `account_key`.
+struct SuiNetwork<'a> {
+    sui_client: &'a SuiClient,
+    account_key: &'a SuiKeyPair,
+}
+
+#[async_trait]
+impl FailoverNetwork for SuiNetwork<'_> {
+    async fn current_epoch(&self) -> anyhow::Result<EpochId> {
+        current_epoch(self.sui_client).await
+    }
+
+    async fn validator(&self) -> anyhow::Result<OnChainValidator> {
+        let sui_address = SuiAddress::from(&self.account_key.public());
+        let system_state = self
+            .sui_client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?;
+        let validator = system_state
+            .active_validators
+            .iter()
+            .find(|v| v.sui_address == sui_address)
+            .ok_or_else(|| anyhow!("{sui_address} is not an active validator"))?;
+        Ok(OnChainValidator {
+            epoch: system_state.epoch,
+            metadata: ValidatorMetadata::from_summary(validator)?,
+            next_epoch_metadata: ValidatorMetadata::next_epoch_from_summary(validator)?,
+        })
+    }
+
+    async fn update_next_epoch(
+        &self,
+        metadata: &ValidatorMetadata,
+        next_epoch_metadata: &ValidatorMetadata,
+    ) -> anyhow::Result<Vec<EpochId>> {
+        let mut epochs = vec![];
+        for (function, call_args) in metadata.next_epoch_updates(next_epoch_metadata) {
+            epochs.push(
+                update_metadata_on_chain(self.account_key, function, call_args, self.sui_client)
+                    .await?,
+            );
+        }
+        Ok(epochs)
+    }
+
+    async fn in_committee(&self, epoch: EpochId, protocol_pubkey: &[u8]) -> anyhow::Result<bool> {
+        let committee = self
+            .sui_client
+            .read_api()
+            .get_committee_info(Some(epoch.into()))
+            .await?;
+        let name = AuthorityPublicKeyBytes::from_bytes(protocol_pubkey)?;
+        Ok(committee.validators.iter().any(|(n, _)| *n == name))
+    }
+
+    async fn wait_for_traffic(&self, address: &Multiaddr) -> anyhow::Result<()> {
+        wait_for_traffic(address).await
+    }
+}
+
+/// A run of the Epoch Change Failover fire drill, once the new config of the node is prepared.
+struct Failover<'a, N> {
+    network: &'a N,
+    config_path: &'a Path,
+    /// The contents of the node config when the drill started.
+    starting_config: Vec<u8>,
+    new_config_path: PathBuf,
+    starting_metadata: ValidatorMetadata,
+    new_metadata: ValidatorMetadata,
+    epoch_timeout: Duration,
+    validation_timeout: Duration,
+    poll_interval: Duration,
+}
+
+impl<N: FailoverNetwork> Failover<'_, N> {
+    /// Rehearses the failover, and rolls it back when any step fails.
+    async fn run(&self, report: &mut FailoverReport) -> anyhow::Result<()> {
+        let result = self.rehearse(report).await;
+        if let Err(err) = &result {
+            warn!("Epoch Change Failover fire drill failed, rolling back: {err}");
+            let rollback = report.step("Roll back", self.roll_back()).await;
+            report.rolled_back = rollback.is_ok();
+            report.kept_new_config = rollback.unwrap_or(false);
+        }
+        result
+    }
+
+    /// Runs the steps of the failover, recording them in `report`, and stops at the first step
+    /// that fails.
+    async fn rehearse(&self, report: &mut FailoverReport) -> anyhow::Result<()> {
+        let update_epoch = report
+            .step("Update the next epoch metadata", async {
+                let validator = self.network.validator().await?;
+                let epochs = self
+                    .network
+                    .update_next_epoch(&self.new_metadata, &validator.next_epoch_metadata)
+                    .await?;
+                match (epochs.first(), epochs.last()) {
+                    (Some(first), Some(last)) if first == last => Ok(*last),
+                    (Some(first), Some(last)) => {
+                        bail!("The metadata was updated across epochs {first} to {last}")
+                    }
+                    _ => bail!("The new metadata is already that of the next epoch"),
+                }
+            })
+            .await?;
+
+        // The new metadata takes effect in the epoch after the one it was updated in, which may
+        // already have started
+        let target_epoch = update_epoch + 1;
+        report
+            .step("Wait for the next epoch", async {
+                let wait = wait_for_epoch(self.network, target_epoch, self.poll_interval);
+                tokio::time::timeout(self.epoch_timeout, wait)
+                    .await
+                    .map_err(|_| {
+                        anyhow!(
+                            "Epoch {target_epoch} did not start within {:?}",
+                            self.epoch_timeout
+                        )
+                    })?
+            })
+            .await?;
+        info!("Just advanced to epoch {target_epoch}");
+
+        report
+            .step("Check the committee of the new epoch", async {
+                let protocol_pubkey = &self.new_metadata.protocol_pubkey;
+                if !self
+                    .network
+                    .in_committee(target_epoch, protocol_pubkey)
+                    .await?
+                {
+                    bail!("The new protocol key is not in the committee of epoch {target_epoch}");
+                }
+                Ok(())
+            })
+            .await?;
+
+        report
+            .step("Check the on-chain metadata of the new epoch", async {
+                let metadata = self.network.validator().await?.metadata;
+                if metadata != self.new_metadata {
+                    bail!(
+                        "Expected metadata {:?}, found {metadata:?}",
+                        self.new_metadata
+                    );
+                }
+                Ok(())
+            })
+            .await?;
+
+        report
+            .step("Replace the node config", async {
+                std::fs::rename(&self.new_config_path, self.config_path)?;
+                info!("Updated Sui Node config. Restart the node to load it.");
+                Ok(())
+            })
+            .await?;
+
+        report
+            .step("Check traffic at the new network address", async {
+                let address = &self.new_metadata.network_address;
+                tokio::time::timeout(
+                    self.validation_timeout,
+                    self.network.wait_for_traffic(address),
+                )
+                .await
+                .map_err(|_| {
+                    anyhow!(
+                        "No traffic served at {address} within {:?}",
+                        self.validation_timeout
+                    )
+                })?
+            })
+            .await
+    }
+
+    /// Undoes a failed failover. Until the new protocol key takes effect, the starting node config
+    /// is restored and the starting metadata is made that of the next epoch again. Once it took
+    /// effect, the node can only run with the new config, which is kept, and only the fields of
+    /// the new metadata that did not take effect are resubmitted. Returns whether the new config
+    /// was kept.
+    async fn roll_back(&self) -> anyhow::Result<bool> {
+        for _ in 0..MAX_ROLLBACK_ATTEMPTS {
+            let validator = self.network.validator().await?;
+            let keep_new_config =
+                validator.metadata.protocol_pubkey == self.new_metadata.protocol_pubkey;
+            let metadata = if keep_new_config {
+                if self.new_config_path.exists() {
+                    std::fs::rename(&self.new_config_path, self.config_path)?;
+                }
+                &self.new_metadata
+            } else {
+                // The new config is set aside until the rollback is over, in case the new protocol
+                // key takes effect meanwhile
+                if !self.new_config_path.exists() {
+                    std::fs::rename(self.config_path, &self.new_config_path)?;
+                }
+                std::fs::write(self.config_path, &self.starting_config)?;
+                &self.starting_metadata
+            };
+            self.network
+                .update_next_epoch(metadata, &validator.next_epoch_metadata)
+                .await?;
+
+            // The new protocol key may have taken effect since the validator was read
+            if self.network.current_epoch().await? == validator.epoch {
+                if !keep_new_config {
+                    std::fs::remove_file(&self.new_config_path)?;
+                }
+                return Ok(keep_new_config);
+            }
+            warn!("The epoch changed while rolling back, rolling back again");
+        }
+        bail!("The epoch changed during each of {MAX_ROLLBACK_ATTEMPTS} attempts to roll back")
+    }
+}
+
+/// Waits until the current epoch is `epoch` or a later one.
+async fn wait_for_epoch(
+    network: &impl FailoverNetwork,
+    epoch: EpochId,
+    poll_interval: Duration,
+) -> anyhow::Result<()> {
+    while network.current_epoch().await? < epoch {
+        tokio::time::sleep(poll_interval).await;
+    }
+    Ok(())
+}
+
+/// The report of a run of the Epoch Change Failover fire drill.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FailoverReport {
+    validator: SuiAddress,
+    starting_epoch: EpochId,
+    steps: Vec<DrillStep>,
+    rolled_back: bool,
+    /// Whether the rollback kept the new config, as the new keys had already taken effect.
+    kept_new_config: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DrillStep {
+    name: &'static str,
+    duration_secs: f64,
+    /// Why the step failed, or `None` if it succeeded.
+    error: Option<String>,
+}
+
+impl FailoverReport {
+    /// Runs the step `name` of the drill and records its outcome.
+    async fn step<T>(
+        &mut self,
+        name: &'static str,
+        step: impl std::future::Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        info!("{name}");
+        let start = Instant::now();
+        let result = step.await;
+        self.steps.push(DrillStep {
+            name,
+            duration_secs: start.elapsed().as_secs_f64(),
+            error: result.as_ref().err().map(|e| format!("{e:#}")),
+        });
+        result
+    }
+}
+
+impl fmt::Display for FailoverReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Epoch Change Failover fire drill for validator {} from epoch {}:",
+            self.validator, self.starting_epoch
+        )?;
+        for step in &self.steps {
+            let outcome = match &step.error {
+                None => "ok".to_string(),
+                Some(error) => format!("FAILED: {error}"),
+            };
+            writeln!(f, "  {} ({:.1}s): {outcome}", step.name, step.duration_secs)?;
+        }
+        let failed = self.steps.iter().any(|step| step.error.is_some());
+        match (failed, self.rolled_back) {
+            (false, _) => write!(f, "The failover succeeded."),
+            (true, true) if self.kept_new_config => write!(
+                f,
+                "The failover failed after the new keys took effect. The node keeps its new config, \
+                and the rest of its new metadata was resubmitted for the next epoch."
+            ),
+            (true, true) => write!(f, "The failover failed and was rolled back."),
+            (true, false) => write!(
+                f,
+                "The failover failed and could not be rolled back. Restore the node config from \
+                node_config_backup.yaml and its metadata manually."
+            ),
+        }
+    }
+}
+
+/// The keys and addresses of a validator, which the fire drills rotate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ValidatorMetadata {
+    protocol_pubkey: Vec<u8>,
+    proof_of_possession: Vec<u8>,
+    network_pubkey: Vec<u8>,
+    worker_pubkey: Vec<u8>,
+    network_address: Multiaddr,
+    p2p_address: Multiaddr,
+    primary_address: Multiaddr,
+    worker_address: Multiaddr,
+}
+
+impl ValidatorMetadata {
+    /// The current metadata of `validator`.
+    fn from_summary(validator: &SuiValidatorSummary) -> anyhow::Result<Self> {
+        Ok(Self {
+            protocol_pubkey: validator.protocol_pubkey_bytes.clone(),
+            proof_of_possession: validator.proof_of_possession_bytes.clone(),
+            network_pubkey: validator.network_pubkey_bytes.clone(),
+            worker_pubkey: validator.worker_pubkey_bytes.clone(),
+            network_address: Multiaddr::try_from(validator.net_address.clone())?,
+            p2p_address: Multiaddr::try_from(validator.p2p_address.clone())?,
+            primary_address: Multiaddr::try_from(validator.primary_address.clone())?,
+            worker_address: Multiaddr::try_from(validator.worker_address.clone())?,
+        })
+    }
+
+    /// The metadata of `validator` in the next epoch, as updated so far.
+    fn next_epoch_from_summary(validator: &SuiValidatorSummary) -> anyhow::Result<Self> {
+        let current = Self::from_summary(validator)?;
+        let address = |next: &Option<String>, current: Multiaddr| -> anyhow::Result<Multiaddr> {
+            Ok(match next {
+                Some(next) => Multiaddr::try_from(next.clone())?,
+                None => current,
+            })
+        };
+        Ok(Self {
+            protocol_pubkey: validator
+                .next_epoch_protocol_pubkey_bytes
+                .clone()
+                .unwrap_or(current.protocol_pubkey),
+            proof_of_possession: validator
+                .next_epoch_proof_of_possession
+                .clone()
+                .unwrap_or(current.proof_of_possession),
+            network_pubkey: validator
+                .next_epoch_network_pubkey_bytes
+                .clone()
+                .unwrap_or(current.network_pubkey),
+            worker_pubkey: validator
+                .next_epoch_worker_pubkey_bytes
+                .clone()
+                .unwrap_or(current.worker_pubkey),
+            network_address: address(&validator.next_epoch_net_address, current.network_address)?,
+            p2p_address: address(&validator.next_epoch_p2p_address, current.p2p_address)?,
+            primary_address: address(
+                &validator.next_epoch_primary_address,
+                current.primary_address,
+            )?,
+            worker_address: address(&validator.next_epoch_worker_address, current.worker_address)?,
+        })
+    }
+
+    /// The `sui_system` calls that make this the metadata of a validator in the next epoch, for
+    /// the fields that differ from `next_epoch`, its metadata in the next epoch so far.
+    fn next_epoch_updates(&self, next_epoch: &Self) -> Vec<(&'static str, Vec<CallArg>)> {
+        fn pure<T: Serialize>(value: &T) -> CallArg {
+            CallArg::Pure(bcs::to_bytes(value).unwrap())
+        }
+        let mut updates = vec![];
+        if (&self.protocol_pubkey, &self.proof_of_possession)
+            != (&next_epoch.protocol_pubkey, &next_epoch.proof_of_possession)
+        {
+            updates.push((
+                "update_validator_next_epoch_protocol_pubkey",
+                vec![pure(&self.protocol_pubkey), pure(&self.proof_of_possession)],
+            ));
+        }
+        if self.network_pubkey != next_epoch.network_pubkey {
+            updates.push((
+                "update_validator_next_epoch_network_pubkey",
+                vec![pure(&self.network_pubkey)],
+            ));
+        }
+        if self.worker_pubkey != next_epoch.worker_pubkey {
+            updates.push((
+                "update_validator_next_epoch_worker_pubkey",
+                vec![pure(&self.worker_pubkey)],
+            ));
+        }
+        if self.network_address != next_epoch.network_address {
+            updates.push((
+                "update_validator_next_epoch_network_address",
+                vec![pure(&self.network_address)],
+            ));
+        }
+        if self.p2p_address != next_epoch.p2p_address {
+            updates.push((
+                "update_validator_next_epoch_p2p_address",
+                vec![pure(&self.p2p_address)],
+            ));
+        }
+        if self.primary_address != next_epoch.primary_address {
+            updates.push((
+                "update_validator_next_epoch_primary_address",
+                vec![pure(&self.primary_address)],
+            ));
+        }
+        if self.worker_address != next_epoch.worker_address {
+            updates.push((
+                "update_validator_next_epoch_worker_address",
+                vec![pure(&self.worker_address)],
+            ));
+        }
+        updates
+    }
+}
+
+/// Waits until a connection to `address` can be opened.
+async fn wait_for_traffic(address: &Multiaddr) -> anyhow::Result<()> {
+    let (Some(host), Some(port)) = (address.hostname(), address.port()) else {
+        bail!("Cannot connect to {address}");
+    };
+    loop {
+        match tokio::net::TcpStream::connect((host.as_str(), port)).await {
+            Ok(_) => return Ok(()),
+            Err(err) => info!("Cannot connect to {address} yet: {err}"),
+        }
+        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+    }
+}
+
 // TODO move this to a shared lib
 pub async fn get_gas_obj_ref(
     sui_address: SuiAddress,
@@ -120,7 +717,33 @@ async fn update_next_epoch_metadata(
     config: &NodeConfig,
     sui_client: &SuiClient,
     account_key: &SuiKeyPair,
-) -> anyhow::Result<PathBuf> {
+) -> anyhow::Result<(PathBuf, ValidatorMetadata)> {
+    let network = SuiNetwork {
+        sui_client,
+        account_key,
+    };
+    let validator = network.validator().await?;
+    let (new_config_path, new_metadata) = prepare_next_epoch_config(
+        sui_node_config_path,
+        config,
+        &validator.metadata,
+        SuiAddress::from(&account_key.public()),
+    )?;
+    network
+        .update_next_epoch(&new_metadata, &validator.next_epoch_metadata)
+        .await?;
+    Ok((new_config_path, new_metadata))
+}
+
+/// Saves a config for the node with new keys and addresses next to its current config, and
+/// returns its path along with the metadata of the validator to match it, given its current
+/// `metadata`.
+fn prepare_next_epoch_config(
+    sui_node_config_path: &Path,
+    config: &NodeConfig,
+    metadata: &ValidatorMetadata,
+    sui_address: SuiAddress,
+) -> anyhow::Result<(PathBuf, ValidatorMetadata)> {
     // Save backup config just in case
     let mut backup_config_path = sui_node_config_path.to_path_buf();
     backup_config_path.pop();
@@ -128,8 +751,6 @@ async fn update_next_epoch_metadata(
     let backup_config = config.clone();
     backup_config.persisted(&backup_config_path).save()?;
 
-    let sui_address = SuiAddress::from(&account_key.public());
-
     let mut new_config = config.clone();
 
     // protocol key
@@ -148,18 +769,8 @@ async fn update_next_epoch_metadata(
     let new_worker_key_pair_copy = new_worker_key_pair.copy();
     new_config.worker_key_pair = KeyPairWithPath::new(SuiKeyPair::Ed25519(new_worker_key_pair));
 
-    let validators = sui_client
-        .governance_api()
-        .get_latest_sui_system_state()
-        .await?
-        .active_validators;
-    let self_validator = validators
-        .iter()
-        .find(|v| v.sui_address == sui_address)
-        .unwrap();
-
     // Network address
-    let mut new_network_address = Multiaddr::try_from(self_validator.net_address.clone()).unwrap();
+    let mut new_network_address = metadata.network_address.clone();
     info!("Current network address: {:?}", new_network_address);
     let http = new_network_address.pop().unwrap();
     // pop out tcp
@@ -188,8 +799,7 @@ async fn update_next_epoch_metadata(
     new_config.p2p_config.listen_address = new_listen_address;
 
     // primary address
-    let mut new_primary_addresses =
-        Multiaddr::try_from(self_validator.primary_address.clone()).unwrap();
+    let mut new_primary_addresses = metadata.primary_address.clone();
     info!("Current primary address: {:?}", new_primary_addresses);
     // pop out udp
     new_primary_addresses.pop().unwrap();
@@ -198,15 +808,7 @@ async fn update_next_epoch_metadata(
     info!("New primary address: {:?}", new_primary_addresses);
 
     // worker address
-    let mut new_worker_addresses = Multiaddr::try_from(
-        validators
-            .iter()
-            .find(|v| v.sui_address == sui_address)
-            .unwrap()
-            .worker_address
-            .clone(),
-    )
-    .unwrap();
+    let mut new_worker_addresses = metadata.worker_address.clone();
     info!("Current worker address: {:?}", new_worker_addresses);
     // pop out udp
     new_worker_addresses.pop().unwrap();
@@ -215,88 +817,30 @@ async fn update_next_epoch_metadata(
     info!("New worker address:: {:?}", new_worker_addresses);
 
     // Save new config
+    let new_config_path = next_epoch_config_path(sui_node_config_path);
+    new_config.persisted(&new_config_path).save()?;
+
+    let new_metadata = ValidatorMetadata {
+        protocol_pubkey: new_protocol_key_pair_copy.public().as_bytes().to_vec(),
+        proof_of_possession: pop.as_bytes().to_vec(),
+        network_pubkey: new_network_key_pair_copy.public().as_bytes().to_vec(),
+        worker_pubkey: new_worker_key_pair_copy.public().as_bytes().to_vec(),
+        network_address: new_network_address,
+        p2p_address: new_external_address,
+        primary_address: new_primary_addresses,
+        worker_address: new_worker_addresses,
+    };
+    Ok((new_config_path, new_metadata))
+}
+
+/// The path the config of the node for the next epoch is saved at, next to its current config.
+fn next_epoch_config_path(sui_node_config_path: &Path) -> PathBuf {
     let mut new_config_path = sui_node_config_path.to_path_buf();
     new_config_path.pop();
     new_config_path.push(
         String::from(sui_node_config_path.file_name().unwrap().to_str().unwrap()) + ".next_epoch",
     );
-    new_config.persisted(&new_config_path).save()?;
-
-    // update protocol pubkey on chain
-    update_metadata_on_chain(
-        account_key,
-        "update_validator_next_epoch_protocol_pubkey",
-        vec![
-            CallArg::Pure(
-                bcs::to_bytes(&new_protocol_key_pair_copy.public().as_bytes().to_vec()).unwrap(),
-            ),
-            CallArg::Pure(bcs::to_bytes(&pop.as_bytes().to_vec()).unwrap()),
-        ],
-        sui_client,
-    )
-    .await?;
-
-    // update network pubkey on chain
-    update_metadata_on_chain(
-        account_key,
-        "update_validator_next_epoch_network_pubkey",
-        vec![CallArg::Pure(
-            bcs::to_bytes(&new_network_key_pair_copy.public().as_bytes().to_vec()).unwrap(),
-        )],
-        sui_client,
-    )
-    .await?;
-
-    // update worker pubkey on chain
-    update_metadata_on_chain(
-        account_key,
-        "update_validator_next_epoch_worker_pubkey",
-        vec![CallArg::Pure(
-            bcs::to_bytes(&new_worker_key_pair_copy.public().as_bytes().to_vec()).unwrap(),
-        )],
-        sui_client,
-    )
-    .await?;
-
-    // update network address
-    update_metadata_on_chain(
-        account_key,
-        "update_validator_next_epoch_network_address",
-        vec![CallArg::Pure(bcs::to_bytes(&new_network_address).unwrap())],
-        sui_client,
-    )
-    .await?;
-
-    // update p2p address
-    update_metadata_on_chain(
-        account_key,
-        "update_validator_next_epoch_p2p_address",
-        vec![CallArg::Pure(bcs::to_bytes(&new_external_address).unwrap())],
-        sui_client,
-    )
-    .await?;
-
-    // update primary address
-    update_metadata_on_chain(
-        account_key,
-        "update_validator_next_epoch_primary_address",
-        vec![CallArg::Pure(
-            bcs::to_bytes(&new_primary_addresses).unwrap(),
-        )],
-        sui_client,
-    )
-    .await?;
-
-    // update worker address
-    update_metadata_on_chain(
-        account_key,
-        "update_validator_next_epoch_worker_address",
-        vec![CallArg::Pure(bcs::to_bytes(&new_worker_addresses).unwrap())],
-        sui_client,
-    )
-    .await?;
-
-    Ok(new_config_path)
+    new_config_path
 }
 
 async fn update_metadata_on_chain(
@@ -304,7 +848,7 @@ async fn update_metadata_on_chain(
     function: &'static str,
     call_args: Vec<CallArg>,
     sui_client: &SuiClient,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<EpochId> {
     let sui_address = SuiAddress::from(&account_key.public());
     let gas_obj_ref = get_gas_obj_ref(sui_address, sui_client, 10000 * 100).await?;
     let rgp = sui_client
@@ -325,9 +869,9 @@ async fn update_metadata_on_chain(
         rgp,
     )
     .unwrap();
-    execute_tx(account_key, sui_client, tx_data, function).await?;
+    let epoch = execute_tx(account_key, sui_client, tx_data, function).await?;
     tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-    Ok(())
+    Ok(epoch)
 }
 
 async fn execute_tx(
@@ -335,7 +879,7 @@ async fn execute_tx(
     sui_client: &SuiClient,
     tx_data: TransactionData,
     action: &str,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<EpochId> {
     let tx = Transaction::from_data_and_signer(tx_data, vec![account_key]);
     info!("Executing {:?}", tx.digest());
     let tx_digest = *tx.digest();
@@ -348,14 +892,18 @@ async fn execute_tx(
         )
         .await
         .unwrap();
-    if *resp.effects.unwrap().status() != SuiExecutionStatus::Success {
+    let effects = resp.effects.unwrap();
+    if *effects.status() != SuiExecutionStatus::Success {
         anyhow::bail!("Tx to update metadata {:?} failed", tx_digest);
     }
     info!("{action} succeeded");
-    Ok(())
+    Ok(effects.executed_epoch())
 }
 
-async fn wait_for_next_epoch(sui_client: &SuiClient, target_epoch: EpochId) -> anyhow::Result<()> {
+pub(crate) async fn wait_for_next_epoch(
+    sui_client: &SuiClient,
+    target_epoch: EpochId,
+) -> anyhow::Result<()> {
     loop {
         let epoch_id = current_epoch(sui_client).await?;
         if epoch_id > target_epoch {
@@ -372,6 +920,6 @@ async fn wait_for_next_epoch(sui_client: &SuiClient, target_epoch: EpochId) -> a
     }
 }
 
-async fn current_epoch(sui_client: &SuiClient) -> anyhow::Result<EpochId> {
+pub(crate) async fn current_epoch(sui_client: &SuiClient) -> anyhow::Result<EpochId> {
     Ok(sui_client.read_api().get_committee_info(None).await?.epoch)
 }
@@ -0,0 +1,381 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+};
+
+use anyhow::{anyhow, bail};
+use serde::Serialize;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiObjectDataOptions, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI,
+};
+use sui_sdk::SuiClient;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef},
+    gas::GasCostSummary,
+    transaction::{
+        CallArg, Command, ObjectArg, ProgrammableTransaction, TransactionData, TransactionDataAPI,
+        TransactionKind,
+    },
+};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle},
+};
+
+/// Breakdown of the gas used by a programmable transaction, obtained by dry running it.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GasProfile {
+    pub status: SuiExecutionStatus,
+    pub gas_cost: GasCostSummary,
+    pub storage_effects: StorageEffects,
+    pub commands: Vec<CommandGasCost>,
+    pub functions: Vec<FunctionGasCost>,
+}
+
+/// Number of objects affected by the transaction, per kind of change.
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEffects {
+    pub created: usize,
+    pub mutated: usize,
+    pub unwrapped: usize,
+    pub deleted: usize,
+    pub unwrapped_then_deleted: usize,
+    pub wrapped: usize,
+}
+
+/// Gas attributed to a single command: the difference between the gas used by the transaction
+/// truncated after this command, and truncated before it. The first command also accounts for
+/// the base cost of the transaction.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandGasCost {
+    pub index: usize,
+    pub command: String,
+    /// `None` if the transaction truncated before or after this command fails on its own (e.g.
+    /// because a result that cannot be dropped is left unused), so the gas of the command cannot
+    /// be isolated.
+    pub cost: Option<GasCostDelta>,
+}
+
+/// Difference between the gas used by two dry runs.
+#[derive(Serialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct GasCostDelta {
+    pub computation_cost: i64,
+    pub storage_cost: i64,
+    pub storage_rebate: i64,
+}
+
+/// Gas attributed to all calls to a Move function in the transaction.
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionGasCost {
+    pub function: String,
+    pub calls: usize,
+    /// Number of calls whose gas could not be isolated, and is missing from the costs below.
+    pub unattributed_calls: usize,
+    pub computation_cost: i64,
+    pub storage_cost: i64,
+    pub storage_rebate: i64,
+}
+
+impl GasCostDelta {
+    fn between(before: &GasCostSummary, after: &GasCostSummary) -> Self {
+        Self {
+            computation_cost: after.computation_cost as i64 - before.computation_cost as i64,
+            storage_cost: after.storage_cost as i64 - before.storage_cost as i64,
+            storage_rebate: after.storage_rebate as i64 - before.storage_rebate as i64,
+        }
+    }
+}
+
+impl GasProfile {
+    /// Profiles the gas usage of `tx_data` by dry running it, and every prefix of its commands.
+    ///
+    /// Computation is charged in buckets, so commands whose cost does not push the transaction
+    /// into a higher bucket are attributed no computation cost. Prefixes that fail on their own
+    /// charge gas up to the failure only, so the commands around them are left unattributed.
+    pub async fn new(client: &SuiClient, tx_data: TransactionData) -> Result<Self, anyhow::Error> {
+        let TransactionKind::ProgrammableTransaction(ptb) = tx_data.kind() else {
+            bail!("Only programmable transactions can be profiled");
+        };
+        let ProgrammableTransaction { inputs, commands } = ptb.clone();
+        if commands.is_empty() {
+            bail!("Transaction has no commands to profile");
+        }
+
+        let effects = dry_run(client, tx_data.clone()).await?;
+
+        // Cumulative gas used by the transaction truncated after each command, or `None` if the
+        // truncated transaction failed.
+        let mut prefix_costs = vec![Some(GasCostSummary::default())];
+        for len in 1..commands.len() {
+            let mut prefix = tx_data.clone();
+            *prefix.kind_mut() =
+                TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                    inputs: inputs.clone(),
+                    commands: commands[..len].to_vec(),
+                });
+            let prefix_effects = dry_run(client, prefix).await?;
+            prefix_costs.push(
+                prefix_effects
+                    .status()
+                    .is_ok()
+                    .then(|| prefix_effects.gas_cost_summary().clone()),
+            );
+        }
+        // The full transaction is the one being profiled, so its gas is attributed even if it
+        // failed.
+        prefix_costs.push(Some(effects.gas_cost_summary().clone()));
+
+        let mut functions: BTreeMap<String, FunctionGasCost> = BTreeMap::new();
+        let commands: Vec<_> = commands
+            .iter()
+            .zip(prefix_costs.windows(2))
+            .enumerate()
+            .map(|(index, (command, costs))| {
+                let [before, after] = costs else {
+                    unreachable!("windows of size 2");
+                };
+                let cost = CommandGasCost {
+                    index,
+                    command: command_name(command),
+                    cost: before
+                        .as_ref()
+                        .zip(after.as_ref())
+                        .map(|(before, after)| GasCostDelta::between(before, after)),
+                };
+                if let Command::MoveCall(call) = command {
+                    let function = format!("{}::{}::{}", call.package, call.module, call.function);
+                    let entry =
+                        functions
+                            .entry(function.clone())
+                            .or_insert_with(|| FunctionGasCost {
+                                function,
+                                ..Default::default()
+                            });
+                    entry.calls += 1;
+                    match &cost.cost {
+                        Some(delta) => {
+                            entry.computation_cost += delta.computation_cost;
+                            entry.storage_cost += delta.storage_cost;
+                            entry.storage_rebate += delta.storage_rebate;
+                        }
+                        None => entry.unattributed_calls += 1,
+                    }
+                }
+                cost
+            })
+            .collect();
+
+        let mut functions: Vec<_> = functions.into_values().collect();
+        functions.sort_by_key(|f| std::cmp::Reverse(f.computation_cost + f.storage_cost));
+
+        Ok(Self {
+            status: effects.status().clone(),
+            gas_cost: effects.gas_cost_summary().clone(),
+            storage_effects: StorageEffects {
+                created: effects.created().len(),
+                mutated: effects.mutated().len(),
+                unwrapped: effects.unwrapped().len(),
+                deleted: effects.deleted().len(),
+                unwrapped_then_deleted: effects.unwrapped_then_deleted().len(),
+                wrapped: effects.wrapped().len(),
+            },
+            commands,
+            functions,
+        })
+    }
+}
+
+/// Replaces the references to owned and receiving objects (including gas coins) in `tx_data`
+/// with references to their latest versions, so that a transaction that has already been
+/// executed can be dry run against the current state of the network.
+pub async fn refresh_object_refs(
+    client: &SuiClient,
+    mut tx_data: TransactionData,
+) -> Result<TransactionData, anyhow::Error> {
+    let mut ids: Vec<ObjectID> = tx_data.gas().iter().map(|(id, _, _)| *id).collect();
+    if let TransactionKind::ProgrammableTransaction(ptb) = tx_data.kind() {
+        for input in &ptb.inputs {
+            if let CallArg::Object(
+                ObjectArg::ImmOrOwnedObject((id, _, _)) | ObjectArg::Receiving((id, _, _)),
+            ) = input
+            {
+                ids.push(*id);
+            }
+        }
+    }
+
+    let latest: BTreeMap<ObjectID, ObjectRef> = client
+        .read_api()
+        .multi_get_object_with_options(ids, SuiObjectDataOptions::new())
+        .await?
+        .into_iter()
+        .map(|response| {
+            let object = response.into_object()?;
+            Ok((object.object_id, object.object_ref()))
+        })
+        .collect::<Result<_, anyhow::Error>>()?;
+    let refresh = |object_ref: &mut ObjectRef| -> Result<(), anyhow::Error> {
+        *object_ref = *latest
+            .get(&object_ref.0)
+            .ok_or_else(|| anyhow!("Object {} no longer exists", object_ref.0))?;
+        Ok(())
+    };
+
+    for gas in &mut tx_data.gas_data_mut().payment {
+        refresh(gas)?;
+    }
+    if let TransactionKind::ProgrammableTransaction(ptb) = tx_data.kind_mut() {
+        for input in &mut ptb.inputs {
+            if let CallArg::Object(
+                ObjectArg::ImmOrOwnedObject(object_ref) | ObjectArg::Receiving(object_ref),
+            ) = input
+            {
+                refresh(object_ref)?;
+            }
+        }
+    }
+    Ok(tx_data)
+}
+
+async fn dry_run(
+    client: &SuiClient,
+    tx_data: TransactionData,
+) -> Result<SuiTransactionBlockEffects, anyhow::Error> {
+    Ok(client
+        .read_api()
+        .dry_run_transaction_block(tx_data)
+        .await?
+        .effects)
+}
+
+fn command_name(command: &Command) -> String {
+    match command {
+        Command::MoveCall(call) => format!(
+            "MoveCall {}::{}::{}",
+            call.package, call.module, call.function
+        ),
+        Command::TransferObjects(_, _) => "TransferObjects".to_string(),
+        Command::SplitCoins(_, _) => "SplitCoins".to_string(),
+        Command::MergeCoins(_, _) => "MergeCoins".to_string(),
+        Command::Publish(_, _) => "Publish".to_string(),
+        Command::MakeMoveVec(_, _) => "MakeMoveVec".to_string(),
+        Command::Upgrade(_, _, _, _) => "Upgrade".to_string(),
+    }
+}
+
+impl Display for GasProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut builder = TableBuilder::default();
+        builder.push_record(vec!["Status".to_string(), format!("{:?}", self.status)]);
+        builder.push_record(vec![
+            "Computation Cost".to_string(),
+            self.gas_cost.computation_cost.to_string(),
+        ]);
+        builder.push_record(vec![
+            "Storage Cost".to_string(),
+            self.gas_cost.storage_cost.to_string(),
+        ]);
+        builder.push_record(vec![
+            "Storage Rebate".to_string(),
+            self.gas_cost.storage_rebate.to_string(),
+        ]);
+        builder.push_record(vec![
+            "Non-refundable Storage Fee".to_string(),
+            self.gas_cost.non_refundable_storage_fee.to_string(),
+        ]);
+        builder.push_record(vec![
+            "Net Gas Used".to_string(),
+            self.gas_cost.net_gas_usage().to_string(),
+        ]);
+        let StorageEffects {
+            created,
+            mutated,
+            unwrapped,
+            deleted,
+            unwrapped_then_deleted,
+            wrapped,
+        } = &self.storage_effects;
+        for (kind, count) in [
+            ("Created Objects", created),
+            ("Mutated Objects", mutated),
+            ("Unwrapped Objects", unwrapped),
+            ("Deleted Objects", deleted),
+            ("Unwrapped Then Deleted Objects", unwrapped_then_deleted),
+            ("Wrapped Objects", wrapped),
+        ] {
+            builder.push_record(vec![kind.to_string(), count.to_string()]);
+        }
+        let mut table = builder.build();
+        table.with(TableStyle::rounded());
+        table.with(TablePanel::header("Gas Profile (MIST)"));
+        writeln!(f, "{table}")?;
+
+        let mut builder = TableBuilder::default();
+        builder.set_header(vec![
+            "#",
+            "Command",
+            "Computation Cost",
+            "Storage Cost",
+            "Storage Rebate",
+        ]);
+        for cost in &self.commands {
+            let mut record = vec![cost.index.to_string(), cost.command.clone()];
+            match &cost.cost {
+                Some(delta) => record.extend([
+                    delta.computation_cost.to_string(),
+                    delta.storage_cost.to_string(),
+                    delta.storage_rebate.to_string(),
+                ]),
+                None => record.extend(["-".to_string(), "-".to_string(), "-".to_string()]),
+            }
+            builder.push_record(record);
+        }
+        let mut table = builder.build();
+        table.with(TableStyle::rounded());
+        table.with(TablePanel::header("Gas by Command"));
+        writeln!(f, "{table}")?;
+        if self.commands.iter().any(|cost| cost.cost.is_none()) {
+            writeln!(
+                f,
+                "Commands marked - could not be profiled on their own, as the transaction fails \
+                when truncated right before or after them."
+            )?;
+        }
+
+        if !self.functions.is_empty() {
+            let mut builder = TableBuilder::default();
+            builder.set_header(vec![
+                "Function",
+                "Calls",
+                "Unattributed Calls",
+                "Computation Cost",
+                "Storage Cost",
+                "Storage Rebate",
+            ]);
+            for cost in &self.functions {
+                builder.push_record(vec![
+                    cost.function.clone(),
+                    cost.calls.to_string(),
+                    cost.unattributed_calls.to_string(),
+                    cost.computation_cost.to_string(),
+                    cost.storage_cost.to_string(),
+                    cost.storage_rebate.to_string(),
+                ]);
+            }
+            let mut table = builder.build();
+            table.with(TableStyle::rounded());
+            table.with(TablePanel::header("Gas by Move Function"));
+            writeln!(f, "{table}")?;
+        }
+        Ok(())
+    }
+}
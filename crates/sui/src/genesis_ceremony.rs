@@ -5,15 +5,22 @@ use anyhow::Result;
 use camino::Utf8PathBuf;
 use clap::Parser;
 use fastcrypto::encoding::{Encoding, Hex};
-use std::path::PathBuf;
-use sui_config::{genesis::UnsignedGenesis, SUI_GENESIS_FILENAME};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use sui_config::genesis::{Genesis, UnsignedGenesis};
+use sui_config::SUI_GENESIS_FILENAME;
 use sui_genesis_builder::Builder;
+use sui_types::digests::CheckpointDigest;
 use sui_types::multiaddr::Multiaddr;
 use sui_types::{
     base_types::SuiAddress,
     committee::ProtocolVersion,
     crypto::{
-        generate_proof_of_possession, AuthorityKeyPair, KeypairTraits, NetworkKeyPair, SuiKeyPair,
+        generate_proof_of_possession, verify_proof_of_possession, AuthorityKeyPair,
+        AuthorityPublicKey, AuthorityPublicKeyBytes, AuthoritySignature, KeypairTraits,
+        NetworkKeyPair, SuiAuthoritySignature, SuiKeyPair, ToFromBytes,
     },
     message_envelope::Message,
 };
@@ -22,7 +29,7 @@ use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, read_network_keypair_from_file,
 };
 
-use crate::genesis_inspector::examine_genesis_checkpoint;
+use crate::genesis_inspector::{diff_genesis, examine_genesis_checkpoint, export_genesis_json};
 
 #[derive(Parser)]
 pub struct Ceremony {
@@ -79,13 +86,38 @@ pub enum CeremonyCommand {
 
     BuildUnsignedCheckpoint,
 
-    ExamineGenesisCheckpoint,
+    ExamineGenesisCheckpoint {
+        /// Export the genesis checkpoint to PATH in FORMAT instead of examining it interactively.
+        /// The only format is `json`.
+        #[clap(long, num_args = 2, value_names = ["FORMAT", "PATH"])]
+        export: Option<Vec<String>>,
+        /// Report the differences with the genesis checkpoint of another ceremony directory or
+        /// genesis blob instead of examining it interactively.
+        #[clap(long, value_name = "OTHER_GENESIS")]
+        diff: Option<PathBuf>,
+    },
 
     VerifyAndSign {
         #[clap(long)]
         key_file: PathBuf,
     },
 
+    /// Verify the metadata, proof of possession and genesis checkpoint signature of every
+    /// validator, and write a report of the verification signed with the given validator key.
+    VerifyContributions {
+        #[clap(long)]
+        key_file: PathBuf,
+        #[clap(long)]
+        report_file: PathBuf,
+    },
+
+    /// Check a report written by `verify-contributions`: that it is signed by a validator of the
+    /// ceremony, and that its findings match the current state of the ceremony.
+    VerifyReport {
+        #[clap(long)]
+        report_file: PathBuf,
+    },
+
     Finalize,
 }
 
@@ -191,7 +223,7 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             builder.save(dir)?;
         }
 
-        CeremonyCommand::ExamineGenesisCheckpoint => {
+        CeremonyCommand::ExamineGenesisCheckpoint { export, diff } => {
             let builder = Builder::load(&dir)?;
 
             let Some(unsigned_genesis) = builder.unsigned_genesis_checkpoint() else {
@@ -200,7 +232,35 @@ pub fn run(cmd: Ceremony) -> Result<()> {
                 ));
             };
 
-            examine_genesis_checkpoint(unsigned_genesis);
+            if let Some(export) = &export {
+                let [format, path] = export.as_slice() else {
+                    unreachable!("clap requires two values for --export");
+                };
+                if format != "json" {
+                    return Err(anyhow::anyhow!(
+                        "Unsupported export format '{format}'; the only format is 'json'"
+                    ));
+                }
+                export_genesis_json(&unsigned_genesis, Path::new(path))?;
+                println!("Successfully exported genesis checkpoint to {path}");
+            }
+
+            if let Some(other) = &diff {
+                let other_genesis = load_unsigned_genesis(other)?;
+                let diffs = diff_genesis(&unsigned_genesis, &other_genesis)?;
+                for diff in &diffs {
+                    println!("{diff}");
+                }
+                println!(
+                    "{} differences with the genesis checkpoint of {}",
+                    diffs.len(),
+                    other.display()
+                );
+            }
+
+            if export.is_none() && diff.is_none() {
+                examine_genesis_checkpoint(unsigned_genesis);
+            }
         }
 
         CeremonyCommand::VerifyAndSign { key_file } => {
@@ -227,6 +287,52 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             );
         }
 
+        CeremonyCommand::VerifyContributions {
+            key_file,
+            report_file,
+        } => {
+            let keypair: AuthorityKeyPair = read_authority_keypair_from_file(key_file)?;
+
+            let builder = Builder::load(&dir)?;
+            check_protocol_version(&builder, protocol_version)?;
+
+            if !builder.validators().contains_key(&keypair.public().into()) {
+                return Err(anyhow::anyhow!(
+                    "Unable to sign the report; the key is not of a validator in the ceremony"
+                ));
+            }
+
+            let report = ContributionsReport::new(&builder);
+            print!("{report}");
+            let valid = report.is_valid();
+            let signed_report = SignedContributionsReport::new(report, &keypair);
+            std::fs::write(&report_file, serde_json::to_string_pretty(&signed_report)?)?;
+
+            if !valid {
+                return Err(anyhow::anyhow!(
+                    "Some contributions are invalid or missing, see {}",
+                    report_file.display()
+                ));
+            }
+            println!(
+                "Successfully verified all contributions, report written to {}",
+                report_file.display()
+            );
+        }
+
+        CeremonyCommand::VerifyReport { report_file } => {
+            let builder = Builder::load(&dir)?;
+            let signed_report: SignedContributionsReport =
+                serde_json::from_slice(&std::fs::read(&report_file)?)?;
+            signed_report.verify(&builder)?;
+
+            println!(
+                "Successfully verified report {}, signed by {}",
+                report_file.display(),
+                builder.validators()[&signed_report.signer].info.name()
+            );
+        }
+
         CeremonyCommand::Finalize => {
             let builder = Builder::load(&dir)?;
             check_protocol_version(&builder, protocol_version)?;
@@ -246,6 +352,30 @@ pub fn run(cmd: Ceremony) -> Result<()> {
     Ok(())
 }
 
+/// Loads the unsigned genesis checkpoint of a ceremony directory, or of a genesis blob.
+fn load_unsigned_genesis(path: &Path) -> Result<UnsignedGenesis> {
+    if path.is_dir() {
+        return Builder::load(path)?
+            .unsigned_genesis_checkpoint()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The genesis checkpoint of {} hasn't been built yet",
+                    path.display()
+                )
+            });
+    }
+
+    let genesis = Genesis::load(path)?;
+    Ok(UnsignedGenesis {
+        checkpoint: genesis.checkpoint().data().clone(),
+        checkpoint_contents: genesis.checkpoint_contents().clone(),
+        transaction: genesis.transaction().clone(),
+        effects: genesis.effects().clone(),
+        events: genesis.events().clone(),
+        objects: genesis.objects().to_vec(),
+    })
+}
+
 fn check_protocol_version(builder: &Builder, protocol_version: ProtocolVersion) -> Result<()> {
     // It is entirely possible for the user to sign a genesis blob with an unknown
     // protocol version, but if this happens there is almost certainly some confusion
@@ -258,6 +388,191 @@ fn check_protocol_version(builder: &Builder, protocol_version: ProtocolVersion)
     Ok(())
 }
 
+/// The outcome of verifying a contribution of a validator to the ceremony.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verification {
+    Valid,
+    Missing,
+    Invalid(String),
+}
+
+impl Verification {
+    fn from_result<E: fmt::Display>(result: Result<(), E>) -> Self {
+        match result {
+            Ok(()) => Verification::Valid,
+            Err(e) => Verification::Invalid(e.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verification::Valid => write!(f, "valid"),
+            Verification::Missing => write!(f, "missing"),
+            Verification::Invalid(e) => write!(f, "invalid ({e})"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorContributions {
+    pub name: String,
+    pub protocol_key: AuthorityPublicKeyBytes,
+    pub account_address: SuiAddress,
+    pub metadata: Verification,
+    pub proof_of_possession: Verification,
+    pub genesis_signature: Verification,
+}
+
+/// The verification of the contributions of all validators to the ceremony.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContributionsReport {
+    pub protocol_version: u64,
+    /// The digest of the unsigned genesis checkpoint, if it has been built.
+    pub checkpoint_digest: Option<CheckpointDigest>,
+    pub validators: Vec<ValidatorContributions>,
+    /// The keys that signed the genesis checkpoint without being of a validator.
+    pub unknown_signers: Vec<AuthorityPublicKeyBytes>,
+}
+
+impl ContributionsReport {
+    pub fn new(builder: &Builder) -> Self {
+        let unsigned_genesis = builder.unsigned_genesis_checkpoint();
+        let validators = builder
+            .validators()
+            .iter()
+            .map(|(name, validator)| {
+                let proof_of_possession = Verification::from_result(
+                    AuthorityPublicKey::from_bytes(name.as_ref())
+                        .map_err(anyhow::Error::from)
+                        .and_then(|key| {
+                            verify_proof_of_possession(
+                                &validator.proof_of_possession,
+                                &key,
+                                validator.info.account_address,
+                            )
+                            .map_err(anyhow::Error::from)
+                        }),
+                );
+                let signature = builder.signatures().get(name);
+                let genesis_signature = match (&unsigned_genesis, signature) {
+                    (_, None) => Verification::Missing,
+                    (None, Some(_)) => Verification::Invalid(
+                        "the genesis checkpoint has not been built".to_string(),
+                    ),
+                    (Some(genesis), Some(signature)) => {
+                        Verification::from_result(signature.signature.verify_secure(
+                            &IntentMessage::new(
+                                Intent::sui_app(IntentScope::CheckpointSummary),
+                                genesis.checkpoint().clone(),
+                            ),
+                            genesis.checkpoint().epoch,
+                            *name,
+                        ))
+                    }
+                };
+                ValidatorContributions {
+                    name: validator.info.name().to_string(),
+                    protocol_key: *name,
+                    account_address: validator.info.account_address,
+                    metadata: Verification::from_result(validator.validate()),
+                    proof_of_possession,
+                    genesis_signature,
+                }
+            })
+            .collect();
+
+        Self {
+            protocol_version: builder.protocol_version().as_u64(),
+            checkpoint_digest: unsigned_genesis.map(|genesis| genesis.checkpoint().digest()),
+            validators,
+            unknown_signers: builder
+                .signatures()
+                .keys()
+                .filter(|name| !builder.validators().contains_key(name))
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Whether every validator contributed valid metadata, proof of possession and genesis
+    /// signature, and no other key signed the genesis checkpoint.
+    pub fn is_valid(&self) -> bool {
+        self.unknown_signers.is_empty()
+            && self.validators.iter().all(|v| {
+                v.metadata == Verification::Valid
+                    && v.proof_of_possession == Verification::Valid
+                    && v.genesis_signature == Verification::Valid
+            })
+    }
+}
+
+impl fmt::Display for ContributionsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.checkpoint_digest {
+            Some(digest) => writeln!(f, "Unsigned genesis checkpoint: {digest}")?,
+            None => writeln!(f, "Unsigned genesis checkpoint: not built")?,
+        }
+        for v in &self.validators {
+            writeln!(
+                f,
+                "{} ({}): metadata {}, proof of possession {}, genesis signature {}",
+                v.name, v.account_address, v.metadata, v.proof_of_possession, v.genesis_signature
+            )?;
+        }
+        for name in &self.unknown_signers {
+            writeln!(f, "Unknown genesis signer: {name}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A `ContributionsReport` signed by the protocol key of the validator that verified the
+/// contributions, so that other participants can audit it.
+#[derive(Serialize, Deserialize)]
+pub struct SignedContributionsReport {
+    pub report: ContributionsReport,
+    pub signer: AuthorityPublicKeyBytes,
+    pub signature: AuthoritySignature,
+}
+
+impl SignedContributionsReport {
+    pub fn new(report: ContributionsReport, keypair: &AuthorityKeyPair) -> Self {
+        let signature = AuthoritySignature::new_secure(
+            &IntentMessage::new(Intent::sui_app(IntentScope::PersonalMessage), &report),
+            &0,
+            keypair,
+        );
+        Self {
+            report,
+            signer: keypair.public().into(),
+            signature,
+        }
+    }
+
+    /// Checks that the report is signed by a validator of the ceremony of `builder`, and matches
+    /// a fresh verification of its contributions.
+    pub fn verify(&self, builder: &Builder) -> Result<()> {
+        if !builder.validators().contains_key(&self.signer) {
+            return Err(anyhow::anyhow!(
+                "The report is not signed by a validator in the ceremony"
+            ));
+        }
+        self.signature.verify_secure(
+            &IntentMessage::new(Intent::sui_app(IntentScope::PersonalMessage), &self.report),
+            0,
+            self.signer,
+        )?;
+        if self.report != ContributionsReport::new(builder) {
+            return Err(anyhow::anyhow!(
+                "The report does not match the current state of the ceremony"
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -371,6 +686,20 @@ mod test {
         };
         command.run()?;
 
+        // Export the unsigned checkpoint, and compare it with itself
+        let export_file = dir.path().join("genesis.json");
+        Ceremony {
+            path: Some(dir.path().into()),
+            protocol_version: None,
+            command: CeremonyCommand::ExamineGenesisCheckpoint {
+                export: Some(vec!["json".to_string(), export_file.display().to_string()]),
+                diff: Some(dir.path().into()),
+            },
+        }
+        .run()?;
+        let exported: serde_json::Value = serde_json::from_slice(&std::fs::read(&export_file)?)?;
+        assert_eq!(exported["validators"].as_object().unwrap().len(), 10);
+
         // Have all the validators verify and sign genesis
         for (key, _worker_key, _network_key, _account_key, _validator) in &validators {
             let command = Ceremony {
@@ -390,6 +719,25 @@ mod test {
             .run()?;
         }
 
+        // Verify the contributions of all validators, and audit the report
+        let report_file = dir.path().join("contributions-report.json");
+        Ceremony {
+            path: Some(dir.path().into()),
+            protocol_version: None,
+            command: CeremonyCommand::VerifyContributions {
+                key_file: validators[0].0.clone(),
+                report_file: report_file.clone(),
+            },
+        }
+        .run()?;
+
+        Ceremony {
+            path: Some(dir.path().into()),
+            protocol_version: None,
+            command: CeremonyCommand::VerifyReport { report_file },
+        }
+        .run()?;
+
         // Finalize the Ceremony and build the Genesis object
         let command = Ceremony {
             path: Some(dir.path().into()),
@@ -1,17 +1,24 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::ToFromBytes;
 use inquire::Select;
+use serde::Serialize;
+use serde_json::Value;
 use std::collections::BTreeMap;
+use std::path::Path;
 use sui_config::genesis::UnsignedGenesis;
+use sui_types::digests::{CheckpointDigest, ObjectDigest};
+use sui_types::message_envelope::Message;
 use sui_types::sui_system_state::SuiValidatorGenesis;
 use sui_types::{
-    base_types::ObjectID,
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
     coin::CoinMetadata,
     gas_coin::{GasCoin, MIST_PER_SUI, TOTAL_SUPPLY_MIST},
     governance::StakedSui,
     move_package::MovePackage,
-    object::{MoveObject, Owner},
+    object::{MoveObject, Object, Owner},
 };
 
 const STR_ALL: &str = "All";
@@ -151,6 +158,191 @@ pub(crate) fn examine_genesis_checkpoint(genesis: UnsignedGenesis) {
     }
 }
 
+/// Writes the contents of `genesis` to `path` in JSON format, for review outside of the
+/// interactive inspector.
+pub(crate) fn export_genesis_json(genesis: &UnsignedGenesis, path: &Path) -> anyhow::Result<()> {
+    let summary = GenesisSummary::new(genesis);
+    std::fs::write(path, serde_json::to_string_pretty(&summary)?)?;
+    Ok(())
+}
+
+/// Returns the differences between the contents of `old` and `new`, one per changed value, added
+/// (`+`), removed (`-`) or modified (`~`), identified by its path in the JSON export.
+pub(crate) fn diff_genesis(
+    old: &UnsignedGenesis,
+    new: &UnsignedGenesis,
+) -> anyhow::Result<Vec<String>> {
+    let old = serde_json::to_value(GenesisSummary::new(old))?;
+    let new = serde_json::to_value(GenesisSummary::new(new))?;
+    let mut diffs = vec![];
+    diff_values("", &old, &new, &mut diffs);
+    Ok(diffs)
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, diffs: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let path = format!("{path}/{key}");
+                match new.get(key) {
+                    Some(new_value) => diff_values(&path, old_value, new_value, diffs),
+                    None => diffs.push(format!("- {path}: {old_value}")),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    diffs.push(format!("+ {path}/{key}: {new_value}"));
+                }
+            }
+        }
+        _ if old != new => diffs.push(format!("~ {path}: {old} -> {new}")),
+        _ => {}
+    }
+}
+
+/// The contents of a genesis checkpoint, as exported and compared.
+#[derive(Serialize)]
+struct GenesisSummary {
+    checkpoint_digest: CheckpointDigest,
+    validators: BTreeMap<String, ValidatorSummary>,
+    /// The Sui and StakedSui of every owner, by object ID.
+    allocations: BTreeMap<String, BTreeMap<String, AllocationSummary>>,
+    objects: BTreeMap<ObjectID, ObjectSummary>,
+}
+
+#[derive(Serialize)]
+struct ValidatorSummary {
+    sui_address: SuiAddress,
+    protocol_key: String,
+    network_key: String,
+    worker_key: String,
+    network_address: String,
+    p2p_address: String,
+    primary_address: String,
+    worker_address: String,
+    description: String,
+    image_url: String,
+    project_url: String,
+    voting_power: u64,
+    gas_price: u64,
+    commission_rate: u64,
+    next_epoch_stake: u64,
+    staking_pool_id: ObjectID,
+}
+
+#[derive(Serialize)]
+struct AllocationSummary {
+    kind: &'static str,
+    amount: u64,
+}
+
+#[derive(Serialize)]
+struct ObjectSummary {
+    kind: &'static str,
+    #[serde(rename = "type")]
+    type_: Option<String>,
+    version: SequenceNumber,
+    owner: Owner,
+    digest: ObjectDigest,
+}
+
+impl GenesisSummary {
+    fn new(genesis: &UnsignedGenesis) -> Self {
+        let system_object = genesis
+            .sui_system_object()
+            .into_genesis_version_for_tooling();
+
+        let validators = system_object
+            .validators
+            .active_validators
+            .iter()
+            .map(|v| {
+                let metadata = v.verified_metadata();
+                let summary = ValidatorSummary {
+                    sui_address: metadata.sui_address,
+                    protocol_key: metadata.sui_pubkey_bytes().to_string(),
+                    network_key: Base64::encode(metadata.network_pubkey.as_bytes()),
+                    worker_key: Base64::encode(metadata.worker_pubkey.as_bytes()),
+                    network_address: metadata.net_address.to_string(),
+                    p2p_address: metadata.p2p_address.to_string(),
+                    primary_address: metadata.primary_address.to_string(),
+                    worker_address: metadata.worker_address.to_string(),
+                    description: metadata.description.clone(),
+                    image_url: metadata.image_url.clone(),
+                    project_url: metadata.project_url.clone(),
+                    voting_power: v.voting_power,
+                    gas_price: v.gas_price,
+                    commission_rate: v.commission_rate,
+                    next_epoch_stake: v.next_epoch_stake,
+                    staking_pool_id: v.staking_pool.id,
+                };
+                (metadata.name.clone(), summary)
+            })
+            .collect();
+
+        let mut allocations: BTreeMap<_, BTreeMap<_, _>> = BTreeMap::new();
+        let sui_system = allocations.entry("Sui System".to_string()).or_default();
+        sui_system.insert(
+            "Storage Fund".to_string(),
+            AllocationSummary {
+                kind: STR_SUI,
+                amount: system_object.storage_fund.non_refundable_balance.value(),
+            },
+        );
+        sui_system.insert(
+            "Stake Subsidy".to_string(),
+            AllocationSummary {
+                kind: STR_SUI,
+                amount: system_object.stake_subsidy.balance.value(),
+            },
+        );
+
+        let mut objects = BTreeMap::new();
+        for object in genesis.objects() {
+            let (kind, amount) = classify_object(object);
+            if let Some(amount) = amount {
+                allocations
+                    .entry(object.owner.to_string())
+                    .or_default()
+                    .insert(object.id().to_string(), AllocationSummary { kind, amount });
+            }
+            objects.insert(
+                object.id(),
+                ObjectSummary {
+                    kind,
+                    type_: object.type_().map(|t| t.to_string()),
+                    version: object.version(),
+                    owner: object.owner,
+                    digest: object.digest(),
+                },
+            );
+        }
+
+        Self {
+            checkpoint_digest: genesis.checkpoint().digest(),
+            validators,
+            allocations,
+            objects,
+        }
+    }
+}
+
+/// Returns the category of `object` in the inspector, and its amount of MIST if it is Sui or
+/// StakedSui.
+fn classify_object(object: &Object) -> (&'static str, Option<u64>) {
+    if object.is_package() {
+        (STR_PACKAGE, None)
+    } else if let Ok(gas) = GasCoin::try_from(object) {
+        (STR_SUI, Some(gas.value()))
+    } else if CoinMetadata::try_from(object).is_ok() {
+        (STR_COIN_METADATA, None)
+    } else if let Ok(staked_sui) = StakedSui::try_from(object) {
+        (STR_STAKED_SUI, Some(staked_sui.principal()))
+    } else {
+        (STR_OTHER, None)
+    }
+}
+
 #[allow(clippy::ptr_arg)]
 fn examine_validators(
     validator_options: &Vec<&str>,
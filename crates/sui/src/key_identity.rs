@@ -3,19 +3,23 @@
 
 use std::{fmt::Display, str::FromStr};
 
-use anyhow::Error;
+use anyhow::{bail, Error};
 use serde::Serialize;
 use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::base_types::SuiAddress;
 
+use crate::address_book::AddressBook;
+
 /// An address or an alias associated with a key in the wallet
 /// This is used to distinguish between an address or an alias,
 /// enabling a user to use an alias for any command that requires an address.
+/// A label of the address book, prefixed with `@`, can also be used.
 #[derive(Serialize, Clone)]
 pub enum KeyIdentity {
     Address(SuiAddress),
     Alias(String),
+    Label(String),
 }
 
 impl FromStr for KeyIdentity {
@@ -23,6 +27,8 @@ impl FromStr for KeyIdentity {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.starts_with("0x") {
             Ok(KeyIdentity::Address(SuiAddress::from_str(s)?))
+        } else if let Some(label) = s.strip_prefix('@') {
+            Ok(KeyIdentity::Label(label.to_string()))
         } else {
             Ok(KeyIdentity::Alias(s.to_string()))
         }
@@ -34,6 +40,7 @@ impl Display for KeyIdentity {
         let v = match self {
             KeyIdentity::Address(x) => x.to_string(),
             KeyIdentity::Alias(x) => x.to_string(),
+            KeyIdentity::Label(x) => format!("@{x}"),
         };
         write!(f, "{}", v)
     }
@@ -45,10 +52,10 @@ pub fn get_identity_address(
     input: Option<KeyIdentity>,
     ctx: &mut WalletContext,
 ) -> Result<SuiAddress, Error> {
-    if let Some(addr) = input {
-        get_identity_address_from_keystore(addr, &ctx.config.keystore)
-    } else {
-        Ok(ctx.active_address()?)
+    match input {
+        Some(KeyIdentity::Label(label)) => AddressBook::load(ctx)?.get(&label),
+        Some(addr) => get_identity_address_from_keystore(addr, &ctx.config.keystore),
+        None => Ok(ctx.active_address()?),
     }
 }
 
@@ -59,5 +66,8 @@ pub fn get_identity_address_from_keystore(
     match input {
         KeyIdentity::Address(x) => Ok(x),
         KeyIdentity::Alias(x) => Ok(*keystore.get_address_by_alias(x)?),
+        KeyIdentity::Label(x) => {
+            bail!("Address book label @{x} cannot be used here, use an address in the keystore")
+        }
     }
 }
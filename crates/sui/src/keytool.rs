@@ -1,7 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use crate::key_identity::{get_identity_address_from_keystore, KeyIdentity};
-use crate::zklogin_commands_util::{perform_zk_login_test_tx, read_cli_line};
+use crate::zklogin_commands_util::{
+    perform_zk_login_test_tx, read_cli_line, verify_zk_login_flow, ZkLoginFlowInputs,
+    ZkLoginVerifyReport, ZKLOGIN_JWK_CACHE_FILE,
+};
 use anyhow::anyhow;
 use bip32::DerivationPath;
 use clap::*;
@@ -27,13 +30,21 @@ use serde_json::json;
 use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
 use std::fmt::{Debug, Display, Formatter};
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use sui_config::sui_config_dir;
+use sui_json_rpc_types::SuiTransactionBlockData;
+use sui_keys::encryption::prompt_passphrase;
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
 use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::ledger::{
+    account_derivation_path, derive_addresses, list_devices, open_device, LedgerAccount,
+    LedgerDeviceInfo,
+};
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{
@@ -41,6 +52,7 @@ use sui_types::crypto::{
 };
 use sui_types::crypto::{DefaultHash, PublicKey};
 use sui_types::error::SuiResult;
+use sui_types::in_memory_storage::InMemoryStorage;
 use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
 use sui_types::multisig_legacy::{MultiSigLegacy, MultiSigPublicKeyLegacy};
 use sui_types::signature::{AuthenticatorTrait, GenericSignature, VerifyParams};
@@ -89,6 +101,11 @@ pub enum KeyToolCommand {
         #[clap(long)]
         tx_bytes: Option<String>,
     },
+    /// Encrypt the private keys in Sui CLI Keystore with a passphrase (Argon2id key derivation,
+    /// AES-256-GCM encryption). Once encrypted, the passphrase is asked for the first time the
+    /// keystore is used in a session, or read from the SUI_KEYSTORE_PASSPHRASE environment
+    /// variable. Running this on an encrypted keystore changes its passphrase.
+    EncryptKeystore,
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -125,6 +142,34 @@ pub enum KeyToolCommand {
         #[clap(long)]
         key_identity: KeyIdentity,
     },
+    /// List the Sui addresses held on a connected Ledger device running the Sui app, derived
+    /// at m/44'/784'/{index}'/0'/0' for `count` indexes starting at `start`.
+    LedgerAddresses {
+        /// Path of the device to use, see `sui keytool ledger-devices`. Defaults to the first
+        /// connected device.
+        #[clap(long)]
+        device: Option<String>,
+        #[clap(long, default_value = "0")]
+        start: u32,
+        #[clap(long, default_value = "5")]
+        count: u32,
+    },
+    /// List the Ledger devices connected to this machine.
+    LedgerDevices,
+    /// Add an address held on a Ledger device to Sui CLI Keystore. No private key is stored:
+    /// transactions sent from this address by `sui client` are signed on the device, which
+    /// asks for confirmation. The derivation path defaults to m/44'/784'/0'/0'/0'.
+    LedgerImport {
+        /// Sets an alias for this address. The alias must start with a letter and can contain only letters, digits, hyphens (-), or underscores (_).
+        #[clap(long)]
+        alias: Option<String>,
+        /// Path of the device to use, see `sui keytool ledger-devices`. Defaults to the first
+        /// connected device.
+        #[clap(long)]
+        device: Option<String>,
+        #[clap(long)]
+        derivation_path: Option<DerivationPath>,
+    },
     /// List all keys by its Sui address, Base64 encoded public key, key scheme name in
     /// sui.keystore.
     List {
@@ -193,6 +238,23 @@ pub enum KeyToolCommand {
         #[clap(long)]
         intent: Option<Intent>,
     },
+    /// Show a human-readable summary of a transaction, including its commands, the recipients of
+    /// the objects it transfers and the amounts of the coins it splits, and sign it with the key
+    /// for the given address (or its alias) in sui keystore once confirmed. The data is the Base64
+    /// encoded BCS serialized transaction bytes, or the [struct IntentMessage] for them, in which
+    /// case its intent is used. If the key is held on a Ledger device, the transaction must also
+    /// be confirmed on the device.
+    SignPtb {
+        #[clap(long)]
+        address: KeyIdentity,
+        #[clap(long)]
+        data: String,
+        #[clap(long)]
+        intent: Option<Intent>,
+        /// Sign without asking for confirmation, after showing the summary.
+        #[clap(long)]
+        yes: bool,
+    },
     /// Creates a signature by leveraging AWS KMS. Pass in a key-id to leverage Amazon
     /// KMS to sign a message and the base64 pubkey.
     /// Generate PubKey from pem using MystenLabs/base64pemkey
@@ -271,6 +333,45 @@ pub enum KeyToolCommand {
         network: String,
     },
 
+    /// Given a zkLogin signature, the ephemeral public key and the JWT it was created with, verify
+    /// the complete zkLogin flow locally and report the stage it fails at: the JWT against the
+    /// signature, the nonce against the ephemeral key, the address derivation, the max epoch, the
+    /// JWK of the provider (cached for an hour), and the proof and ephemeral signature.
+    /// Example request: sui keytool zk-login-verify-flow --sig $SERIALIZED_ZKLOGIN_SIG --ephemeral-pubkey $EPH_PK --jwt $JWT --jwt-randomness $RANDOMNESS --salt $SALT --bytes $BYTES --intent-scope 0 --network devnet --curr-epoch 10
+    ZkLoginVerifyFlow {
+        /// The Base64 of the serialized zkLogin signature.
+        #[clap(long)]
+        sig: String,
+        /// The Base64 of the ephemeral public key, as `flag || pk`.
+        #[clap(long)]
+        ephemeral_pubkey: String,
+        /// The JWT the proof of the signature was generated for.
+        #[clap(long)]
+        jwt: String,
+        /// The randomness of the nonce of the JWT, to verify the nonce.
+        #[clap(long)]
+        jwt_randomness: Option<String>,
+        /// The user salt, to verify the address seed.
+        #[clap(long)]
+        salt: Option<String>,
+        /// The Base64 of the BCS encoded TransactionData or PersonalMessage, to verify the proof
+        /// and the ephemeral signature.
+        #[clap(long)]
+        bytes: Option<String>,
+        /// Either 0 for TransactionData or 3 for PersonalMessage.
+        #[clap(long, default_value = "0")]
+        intent_scope: u8,
+        /// The current epoch for the network to verify the signature's max_epoch against.
+        #[clap(long)]
+        curr_epoch: Option<EpochId>,
+        /// The network to verify the signature for, determines ZkLoginEnv.
+        #[clap(long, default_value = "devnet")]
+        network: String,
+        /// Fetch the JWKs of the provider even if they are cached.
+        #[clap(long)]
+        refresh_jwks: bool,
+    },
+
     /// TESTING ONLY: Given a string of data, sign with the fixed dev-only ephemeral key
     /// and output a zkLogin signature with a fixed dev-only proof with fixed max epoch 10.
     ZkLoginInsecureSignPersonalMessage {
@@ -326,6 +427,13 @@ pub struct Key {
     peer_id: Option<String>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptKeystoreOutput {
+    encrypted_keys: usize,
+    passphrase_changed: bool,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportedKey {
@@ -333,6 +441,14 @@ pub struct ExportedKey {
     key: Key,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerAddress {
+    sui_address: SuiAddress,
+    public_base64_key: String,
+    derivation_path: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct KeypairData {
@@ -441,10 +557,14 @@ pub enum CommandOutput {
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeOrVerifyTx(DecodeOrVerifyTxOutput),
+    EncryptKeystore(EncryptKeystoreOutput),
     Error(String),
     Generate(Key),
     Import(Key),
     Export(ExportedKey),
+    LedgerAddresses(Vec<LedgerAddress>),
+    LedgerDevices(Vec<LedgerDeviceInfo>),
+    LedgerImport(Key),
     List(Vec<Key>),
     LoadKeypair(KeypairData),
     MultiSigAddress(MultiSigAddress),
@@ -457,6 +577,7 @@ pub enum CommandOutput {
     ZkLoginSignAndExecuteTx(ZkLoginSignAndExecuteTx),
     ZkLoginInsecureSignPersonalMessage(ZkLoginInsecureSignPersonalMessage),
     ZkLoginSigVerify(ZkLoginSigVerifyResponse),
+    ZkLoginVerifyFlow(ZkLoginVerifyReport),
 }
 
 impl KeyToolCommand {
@@ -553,6 +674,18 @@ impl KeyToolCommand {
                     }
                 }
             }
+            KeyToolCommand::EncryptKeystore => {
+                let Keystore::File(file_keystore) = keystore else {
+                    return Err(anyhow!("Only a file based keystore can be encrypted"));
+                };
+                let passphrase_changed = file_keystore.is_encrypted();
+                let passphrase = prompt_passphrase("Enter new keystore passphrase:", true)?;
+                file_keystore.encrypt(&passphrase)?;
+                CommandOutput::EncryptKeystore(EncryptKeystoreOutput {
+                    encrypted_keys: file_keystore.key_pairs().len(),
+                    passphrase_changed,
+                })
+            }
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
@@ -629,6 +762,53 @@ impl KeyToolCommand {
                 };
                 CommandOutput::Export(key)
             }
+            KeyToolCommand::LedgerAddresses {
+                device,
+                start,
+                count,
+            } => {
+                let signer = open_device(device.as_deref())?;
+                let (major, minor, patch) = signer.app_version()?;
+                info!("Sui Ledger app version {major}.{minor}.{patch}");
+                let addresses = derive_addresses(&signer, start, count)?
+                    .into_iter()
+                    .map(|(sui_address, account)| LedgerAddress {
+                        sui_address,
+                        public_base64_key: account.public_key_base64,
+                        derivation_path: account.derivation_path,
+                    })
+                    .collect();
+                CommandOutput::LedgerAddresses(addresses)
+            }
+            KeyToolCommand::LedgerDevices => CommandOutput::LedgerDevices(list_devices()?),
+            KeyToolCommand::LedgerImport {
+                alias,
+                device,
+                derivation_path,
+            } => {
+                let Keystore::File(file_keystore) = keystore else {
+                    return Err(anyhow!(
+                        "Ledger accounts can only be added to a file based keystore"
+                    ));
+                };
+                let path = match derivation_path {
+                    Some(path) => path,
+                    None => account_derivation_path(0)?,
+                };
+                let signer = open_device(device.as_deref())?;
+                info!("Confirm the address shown on your Ledger device...");
+                let public_key = signer.public_key(&path, true)?;
+                let address = file_keystore.add_ledger_account(
+                    alias,
+                    LedgerAccount {
+                        derivation_path: path.to_string(),
+                        public_key_base64: public_key.encode_base64(),
+                    },
+                )?;
+                let mut key = Key::from(public_key);
+                key.alias = file_keystore.get_alias_by_address(&address).ok();
+                CommandOutput::LedgerImport(key)
+            }
             KeyToolCommand::List { sort_by_alias } => {
                 let mut keys = keystore
                     .keys()
@@ -781,26 +961,87 @@ impl KeyToolCommand {
             } => {
                 let address = get_identity_address_from_keystore(address, keystore)?;
                 let intent = intent.unwrap_or_else(Intent::sui_transaction);
-                let intent_clone = intent.clone();
                 let msg: TransactionData =
                     bcs::from_bytes(&Base64::decode(&data).map_err(|e| {
                         anyhow!("Cannot deserialize data as TransactionData {:?}", e)
                     })?)?;
-                let intent_msg = IntentMessage::new(intent, msg);
-                let raw_intent_msg: String = Base64::encode(bcs::to_bytes(&intent_msg)?);
-                let mut hasher = DefaultHash::default();
-                hasher.update(bcs::to_bytes(&intent_msg)?);
-                let digest = hasher.finalize().digest;
-                let sui_signature =
-                    keystore.sign_secure(&address, &intent_msg.value, intent_msg.intent)?;
-                CommandOutput::Sign(SignData {
-                    sui_address: address,
-                    raw_tx_data: data,
-                    intent: intent_clone,
-                    raw_intent_msg,
-                    digest: Base64::encode(digest),
-                    sui_signature: sui_signature.encode_base64(),
-                })
+                CommandOutput::Sign(sign_transaction_data(
+                    keystore,
+                    address,
+                    IntentMessage::new(intent, msg),
+                    data,
+                )?)
+            }
+
+            KeyToolCommand::SignPtb {
+                address,
+                data,
+                intent,
+                yes,
+            } => {
+                let address = get_identity_address_from_keystore(address, keystore)?;
+                let bytes = Base64::decode(&data)
+                    .map_err(|e| anyhow!("Cannot decode data as Base64 {:?}", e))?;
+                let intent_msg = match bcs::from_bytes::<TransactionData>(&bytes) {
+                    Ok(msg) => {
+                        IntentMessage::new(intent.unwrap_or_else(Intent::sui_transaction), msg)
+                    }
+                    Err(_) => {
+                        let intent_msg: IntentMessage<TransactionData> = bcs::from_bytes(&bytes)
+                            .map_err(|e| {
+                                anyhow!(
+                                    "Cannot deserialize data as TransactionData or as an intent \
+                                     message for TransactionData {:?}",
+                                    e
+                                )
+                            })?;
+                        if intent.is_some_and(|intent| intent != intent_msg.intent) {
+                            return Err(anyhow!(
+                                "The intent passed in does not match the intent of the message"
+                            ));
+                        }
+                        intent_msg
+                    }
+                };
+                if intent_msg.intent.scope != IntentScope::TransactionData {
+                    return Err(anyhow!(
+                        "Only intents with the TransactionData scope can be signed with sign-ptb, \
+                         use sign for other intents"
+                    ));
+                }
+
+                // The summary goes to stderr, so that the output can still be parsed as JSON.
+                let summary = SuiTransactionBlockData::try_from(
+                    intent_msg.value.clone(),
+                    &InMemoryStorage::default(),
+                )?;
+                eprintln!("{summary}");
+                if !yes {
+                    eprint!("Sign this transaction with address {address}? [y/N] ");
+                    std::io::stderr().flush()?;
+                    let mut answer = String::new();
+                    std::io::stdin().read_line(&mut answer)?;
+                    if !matches!(answer.trim(), "y" | "Y" | "yes") {
+                        return Err(anyhow!("Signing was declined"));
+                    }
+                }
+                let on_ledger = match keystore {
+                    Keystore::File(keystore) => keystore
+                        .ledger_accounts()
+                        .iter()
+                        .any(|(ledger_address, _)| **ledger_address == address),
+                    Keystore::InMem(_) => false,
+                };
+                if on_ledger {
+                    eprintln!("Review and confirm the transaction on your Ledger device");
+                }
+                let raw_tx_data = Base64::encode(bcs::to_bytes(&intent_msg.value)?);
+                CommandOutput::Sign(sign_transaction_data(
+                    keystore,
+                    address,
+                    intent_msg,
+                    raw_tx_data,
+                )?)
             }
 
             KeyToolCommand::SignKMS {
@@ -1124,6 +1365,40 @@ impl KeyToolCommand {
                     _ => CommandOutput::Error("Not a zkLogin signature".to_string()),
                 }
             }
+
+            KeyToolCommand::ZkLoginVerifyFlow {
+                sig,
+                ephemeral_pubkey,
+                jwt,
+                jwt_randomness,
+                salt,
+                bytes,
+                intent_scope,
+                curr_epoch,
+                network,
+                refresh_jwks,
+            } => {
+                let env = match network.as_str() {
+                    "devnet" | "localnet" => ZkLoginEnv::Test,
+                    "mainnet" | "testnet" => ZkLoginEnv::Prod,
+                    _ => return Err(anyhow!("Invalid network")),
+                };
+                let inputs = ZkLoginFlowInputs {
+                    sig,
+                    ephemeral_pubkey,
+                    jwt,
+                    jwt_randomness,
+                    salt,
+                    bytes,
+                    intent_scope: IntentScope::try_from(intent_scope)
+                        .map_err(|_| anyhow!("Invalid scope"))?,
+                    curr_epoch,
+                    env,
+                    jwk_cache: sui_config_dir()?.join(ZKLOGIN_JWK_CACHE_FILE),
+                    refresh_jwks,
+                };
+                CommandOutput::ZkLoginVerifyFlow(verify_zk_login_flow(inputs).await)
+            }
         });
 
         cmd_result
@@ -1230,6 +1505,30 @@ impl Debug for CommandOutput {
     }
 }
 
+/// Signs the intent message for transaction data with the key for `address` in the keystore, and
+/// reports the intent message, its digest and the signature.
+fn sign_transaction_data(
+    keystore: &Keystore,
+    address: SuiAddress,
+    intent_msg: IntentMessage<TransactionData>,
+    raw_tx_data: String,
+) -> Result<SignData, anyhow::Error> {
+    let raw_intent_msg: String = Base64::encode(bcs::to_bytes(&intent_msg)?);
+    let mut hasher = DefaultHash::default();
+    hasher.update(bcs::to_bytes(&intent_msg)?);
+    let digest = hasher.finalize().digest;
+    let sui_signature =
+        keystore.sign_secure(&address, &intent_msg.value, intent_msg.intent.clone())?;
+    Ok(SignData {
+        sui_address: address,
+        raw_tx_data,
+        intent: intent_msg.intent,
+        raw_intent_msg,
+        digest: Base64::encode(digest),
+        sui_signature: sui_signature.encode_base64(),
+    })
+}
+
 /// Converts legacy formatted private key to 33 bytes bech32 encoded private key or vice versa.
 /// It can handle:
 /// 1) Hex encoded 32 byte private key (assumes scheme is Ed25519), this is the legacy wallet format
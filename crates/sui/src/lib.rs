@@ -2,16 +2,24 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod address_book;
 pub mod client_commands;
+pub mod client_events;
 #[macro_use]
 pub mod client_ptb;
+pub mod client_watch;
 pub mod console;
+pub mod e2e_test;
 pub mod fire_drill;
+pub mod gas_profile;
 pub mod genesis_ceremony;
 pub mod genesis_inspector;
 pub mod key_identity;
 pub mod keytool;
+pub mod multisig;
+pub mod retry_policy;
 pub mod shell;
 pub mod sui_commands;
 pub mod validator_commands;
+pub mod validator_health;
 pub mod zklogin_commands_util;
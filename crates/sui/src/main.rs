@@ -89,5 +89,6 @@ async fn main() {
             .init(),
     };
     debug!("Sui CLI version: {VERSION}");
+    move_compiler::set_compiler_version(VERSION);
     exit_main!(args.command.execute().await);
 }
@@ -0,0 +1,477 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, ensure, Context};
+use clap::*;
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_json_rpc_types::SuiTransactionBlockResponse;
+use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, SuiSignature};
+use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
+use sui_types::signature::GenericSignature;
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle},
+};
+
+use crate::key_identity::{get_identity_address_from_keystore, KeyIdentity};
+
+#[cfg(test)]
+#[path = "unit_tests/multisig_tests.rs"]
+mod multisig_tests;
+
+/// Commands coordinating a transaction sent from a multisig address. The transaction is proposed
+/// into a partially signed transaction file, which is passed between the members of the multisig
+/// to collect their signatures, and submitted once the signatures meet the threshold.
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum MultisigCommand {
+    /// Derive the multisig address of a list of member public keys `flag || pk` in Base64, their
+    /// weights, and the threshold. See `sui keytool list` for the public keys of local keys.
+    CreateAddress {
+        #[clap(long, num_args(1..))]
+        pks: Vec<PublicKey>,
+        #[clap(long, num_args(1..))]
+        weights: Vec<WeightUnit>,
+        #[clap(long)]
+        threshold: ThresholdUnit,
+    },
+    /// Start collecting signatures for a transaction sent from a multisig address. The
+    /// transaction is the output of a sui client command run with
+    /// `--serialize-unsigned-transaction`, and its sender must be the multisig address.
+    Propose {
+        /// BCS serialized transaction data bytes without its type tag, as base64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+        #[clap(long, num_args(1..))]
+        pks: Vec<PublicKey>,
+        #[clap(long, num_args(1..))]
+        weights: Vec<WeightUnit>,
+        #[clap(long)]
+        threshold: ThresholdUnit,
+        /// The partially signed transaction file to create.
+        #[clap(long)]
+        output: PathBuf,
+    },
+    /// Add a signature share to a partially signed transaction file, signed by the given member
+    /// address (or its alias) in the keystore. If no address is given, every member with a key
+    /// in the keystore signs.
+    Sign {
+        /// The partially signed transaction file.
+        file: PathBuf,
+        #[clap(long)]
+        address: Option<KeyIdentity>,
+        /// Write the signed file here instead of updating `file` in place.
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
+    /// Merge the signature shares of several copies of the same partially signed transaction
+    /// file, e.g. signed by different members in parallel.
+    Merge {
+        #[clap(num_args(2..), required = true)]
+        files: Vec<PathBuf>,
+        /// The merged partially signed transaction file to write.
+        #[clap(long)]
+        output: PathBuf,
+    },
+    /// Combine the signature shares of a partially signed transaction file into a multisig, and
+    /// execute the transaction. Fails if the shares do not meet the threshold.
+    Submit {
+        /// The partially signed transaction file.
+        file: PathBuf,
+    },
+}
+
+/// A transaction sent from a multisig address, with the signature shares collected so far. It is
+/// stored as JSON, to be shared between the members of the multisig.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PartiallySignedTransaction {
+    /// BCS serialized transaction data, as base64 encoded string.
+    pub tx_bytes: String,
+    pub threshold: ThresholdUnit,
+    pub members: Vec<MultisigMember>,
+    /// Signatures `flag || sig || pk` in Base64, by the public key of the member that signed.
+    pub signatures: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigMember {
+    /// The public key `flag || pk` in Base64.
+    pub public_key: String,
+    pub weight: WeightUnit,
+}
+
+impl PartiallySignedTransaction {
+    pub fn new(tx_data: &TransactionData, multisig_pk: &MultiSigPublicKey) -> anyhow::Result<Self> {
+        let address = SuiAddress::from(multisig_pk);
+        ensure!(
+            tx_data.sender() == address,
+            "The transaction is sent from {}, not from the multisig address {address}",
+            tx_data.sender()
+        );
+        Ok(Self {
+            tx_bytes: Base64::encode(bcs::to_bytes(tx_data)?),
+            threshold: *multisig_pk.threshold(),
+            members: multisig_pk
+                .pubkeys()
+                .iter()
+                .map(|(pk, weight)| MultisigMember {
+                    public_key: pk.encode_base64(),
+                    weight: *weight,
+                })
+                .collect(),
+            signatures: BTreeMap::new(),
+        })
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path).with_context(|| {
+            format!(
+                "Cannot read partially signed transaction {}",
+                path.display()
+            )
+        })?;
+        let file: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid partially signed transaction {}", path.display()))?;
+        // Check that the file is well formed before it is used.
+        file.tx_data()?;
+        file.multisig_pk()?;
+        Ok(file)
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?).with_context(|| {
+            format!(
+                "Cannot write partially signed transaction {}",
+                path.display()
+            )
+        })
+    }
+
+    pub fn tx_data(&self) -> anyhow::Result<TransactionData> {
+        decode_tx_data(&self.tx_bytes)
+    }
+
+    pub fn multisig_pk(&self) -> anyhow::Result<MultiSigPublicKey> {
+        let pks = self
+            .members
+            .iter()
+            .map(|member| {
+                PublicKey::decode_base64(&member.public_key)
+                    .map_err(|e| anyhow!("Invalid member public key {}: {e}", member.public_key))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let weights = self.members.iter().map(|member| member.weight).collect();
+        Ok(MultiSigPublicKey::new(pks, weights, self.threshold)?)
+    }
+
+    pub fn address(&self) -> anyhow::Result<SuiAddress> {
+        Ok(SuiAddress::from(&self.multisig_pk()?))
+    }
+
+    /// The sum of the weights of the members that signed.
+    pub fn signed_weight(&self) -> ThresholdUnit {
+        self.members
+            .iter()
+            .filter(|member| self.signatures.contains_key(&member.public_key))
+            .map(|member| member.weight as ThresholdUnit)
+            .sum()
+    }
+
+    /// Adds a signature share, after checking that it is a valid signature of the transaction by
+    /// one of the members.
+    pub fn add_signature(&mut self, signature: GenericSignature) -> anyhow::Result<()> {
+        let GenericSignature::Signature(sig) = &signature else {
+            bail!("Only ed25519, secp256k1 and secp256r1 signature shares are supported");
+        };
+        let pk = signature.to_public_key()?;
+        let public_key = pk.encode_base64();
+        ensure!(
+            self.members
+                .iter()
+                .any(|member| member.public_key == public_key),
+            "{public_key} is not a member of the multisig"
+        );
+        let intent_msg = IntentMessage::new(Intent::sui_transaction(), self.tx_data()?);
+        sig.verify_secure(&intent_msg, SuiAddress::from(&pk), sig.scheme())
+            .with_context(|| format!("Invalid signature share of {public_key}"))?;
+        self.signatures
+            .insert(public_key, signature.encode_base64());
+        Ok(())
+    }
+
+    /// Signs the transaction with the key of `address` in the keystore, which must be a member.
+    pub fn sign(&mut self, keystore: &Keystore, address: &SuiAddress) -> anyhow::Result<()> {
+        let signature =
+            keystore.sign_secure(address, &self.tx_data()?, Intent::sui_transaction())?;
+        self.add_signature(GenericSignature::Signature(signature))
+    }
+
+    /// Adds the signature shares of `other`, which must be a copy of the same transaction.
+    pub fn merge(&mut self, other: Self) -> anyhow::Result<()> {
+        ensure!(
+            self.tx_bytes == other.tx_bytes
+                && self.threshold == other.threshold
+                && self.members == other.members,
+            "Cannot merge signature shares of different transactions or multisig addresses"
+        );
+        for signature in other.signatures.into_values() {
+            let signature = GenericSignature::decode_base64(&signature)
+                .map_err(|e| anyhow!("Invalid signature share {signature}: {e}"))?;
+            self.add_signature(signature)?;
+        }
+        Ok(())
+    }
+
+    /// Combines the signature shares into a multisig, once they meet the threshold.
+    pub fn combine(&self) -> anyhow::Result<GenericSignature> {
+        let signed_weight = self.signed_weight();
+        ensure!(
+            signed_weight >= self.threshold,
+            "The signature shares have a weight of {signed_weight}, below the threshold of {}",
+            self.threshold
+        );
+        // Shares must be combined in the order of the members.
+        let sigs = self
+            .members
+            .iter()
+            .filter_map(|member| self.signatures.get(&member.public_key))
+            .map(|signature| {
+                GenericSignature::decode_base64(signature)
+                    .map_err(|e| anyhow!("Invalid signature share {signature}: {e}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(MultiSig::combine(sigs, self.multisig_pk()?)?.into())
+    }
+
+    fn status(&self, file: PathBuf) -> anyhow::Result<MultisigStatus> {
+        Ok(MultisigStatus {
+            file,
+            multisig_address: self.address()?,
+            threshold: self.threshold,
+            signed_weight: self.signed_weight(),
+            members: self
+                .multisig_pk()?
+                .pubkeys()
+                .iter()
+                .map(|(pk, weight)| {
+                    let public_base64_key = pk.encode_base64();
+                    MultisigMemberOutput {
+                        address: SuiAddress::from(pk),
+                        signed: self.signatures.contains_key(&public_base64_key),
+                        public_base64_key,
+                        weight: *weight,
+                    }
+                })
+                .collect(),
+        })
+    }
+}
+
+impl MultisigCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<MultisigCommandResult, anyhow::Error> {
+        Ok(match self {
+            MultisigCommand::CreateAddress {
+                pks,
+                weights,
+                threshold,
+            } => {
+                let multisig_pk = MultiSigPublicKey::new(pks, weights, threshold)?;
+                let members = multisig_pk
+                    .pubkeys()
+                    .iter()
+                    .map(|(pk, weight)| MultisigMemberOutput {
+                        address: SuiAddress::from(pk),
+                        public_base64_key: pk.encode_base64(),
+                        weight: *weight,
+                        signed: false,
+                    })
+                    .collect();
+                MultisigCommandResult::Address(MultisigAddressOutput {
+                    multisig_address: SuiAddress::from(&multisig_pk),
+                    threshold,
+                    members,
+                })
+            }
+
+            MultisigCommand::Propose {
+                tx_bytes,
+                pks,
+                weights,
+                threshold,
+                output,
+            } => {
+                let tx_data = decode_tx_data(&tx_bytes).context(
+                    "Check that the transaction is the output of a sui client command run with \
+                     --serialize-unsigned-transaction",
+                )?;
+                let multisig_pk = MultiSigPublicKey::new(pks, weights, threshold)?;
+                let tx = PartiallySignedTransaction::new(&tx_data, &multisig_pk)?;
+                tx.write(&output)?;
+                MultisigCommandResult::Status(tx.status(output)?)
+            }
+
+            MultisigCommand::Sign {
+                file,
+                address,
+                output,
+            } => {
+                let mut tx = PartiallySignedTransaction::read(&file)?;
+                let keystore = &context.config.keystore;
+                let signers = match address {
+                    Some(identity) => vec![get_identity_address_from_keystore(identity, keystore)?],
+                    None => {
+                        let local = keystore.addresses();
+                        let signers = tx
+                            .multisig_pk()?
+                            .pubkeys()
+                            .iter()
+                            .map(|(pk, _)| SuiAddress::from(pk))
+                            .filter(|address| local.contains(address))
+                            .collect::<Vec<_>>();
+                        ensure!(
+                            !signers.is_empty(),
+                            "None of the members of the multisig have a key in the keystore"
+                        );
+                        signers
+                    }
+                };
+                for signer in &signers {
+                    tx.sign(keystore, signer)?;
+                }
+                let output = output.unwrap_or(file);
+                tx.write(&output)?;
+                MultisigCommandResult::Status(tx.status(output)?)
+            }
+
+            MultisigCommand::Merge { files, output } => {
+                let mut files = files.iter();
+                let first = files.next().ok_or_else(|| anyhow!("No files to merge"))?;
+                let mut tx = PartiallySignedTransaction::read(first)?;
+                for file in files {
+                    tx.merge(PartiallySignedTransaction::read(file)?)
+                        .with_context(|| format!("Cannot merge {}", file.display()))?;
+                }
+                tx.write(&output)?;
+                MultisigCommandResult::Status(tx.status(output)?)
+            }
+
+            MultisigCommand::Submit { file } => {
+                let tx = PartiallySignedTransaction::read(&file)?;
+                let multisig = tx.combine()?;
+                let transaction = Transaction::from_generic_sig_data(tx.tx_data()?, vec![multisig]);
+                let response = context.execute_transaction_may_fail(transaction).await?;
+                MultisigCommandResult::Submit(response)
+            }
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigMemberOutput {
+    pub address: SuiAddress,
+    pub public_base64_key: String,
+    pub weight: WeightUnit,
+    pub signed: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigAddressOutput {
+    pub multisig_address: SuiAddress,
+    pub threshold: ThresholdUnit,
+    pub members: Vec<MultisigMemberOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigStatus {
+    pub file: PathBuf,
+    pub multisig_address: SuiAddress,
+    pub threshold: ThresholdUnit,
+    pub signed_weight: ThresholdUnit,
+    pub members: Vec<MultisigMemberOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum MultisigCommandResult {
+    Address(MultisigAddressOutput),
+    Status(MultisigStatus),
+    Submit(SuiTransactionBlockResponse),
+}
+
+impl Display for MultisigCommandResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MultisigCommandResult::Address(output) => {
+                writeln!(f, "Multisig address: {}", output.multisig_address)?;
+                writeln!(f, "Threshold: {}", output.threshold)?;
+                write!(f, "{}", members_table(&output.members, false))
+            }
+            MultisigCommandResult::Status(status) => {
+                writeln!(f, "Partially signed transaction: {}", status.file.display())?;
+                writeln!(f, "Multisig address: {}", status.multisig_address)?;
+                writeln!(
+                    f,
+                    "Signed weight: {} of threshold {}{}",
+                    status.signed_weight,
+                    status.threshold,
+                    if status.signed_weight >= status.threshold {
+                        ", ready to submit"
+                    } else {
+                        ""
+                    }
+                )?;
+                write!(f, "{}", members_table(&status.members, true))
+            }
+            MultisigCommandResult::Submit(response) => write!(f, "{response}"),
+        }
+    }
+}
+
+fn decode_tx_data(tx_bytes: &str) -> anyhow::Result<TransactionData> {
+    let bytes = Base64::decode(tx_bytes)
+        .map_err(|e| anyhow!("Invalid Base64 encoding of the transaction: {e}"))?;
+    bcs::from_bytes(&bytes).map_err(|e| anyhow!("Failed to parse the transaction: {e}"))
+}
+
+fn members_table(members: &[MultisigMemberOutput], with_signed: bool) -> tabled::Table {
+    let mut builder = TableBuilder::default();
+    let mut header = vec!["address", "public key", "weight"];
+    if with_signed {
+        header.push("signed");
+    }
+    builder.set_header(header);
+    for member in members {
+        let mut record = vec![
+            member.address.to_string(),
+            member.public_base64_key.clone(),
+            member.weight.to_string(),
+        ];
+        if with_signed {
+            record.push(if member.signed { "*" } else { "" }.to_string());
+        }
+        builder.push_record(record);
+    }
+    let mut table = builder.build();
+    table.with(TablePanel::header("Multisig Members"));
+    table.with(TableStyle::rounded());
+    table
+}
@@ -0,0 +1,298 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use anyhow::{anyhow, bail};
+use clap::Args;
+use shared_crypto::intent::Intent;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiTransactionBlockResponse};
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::base_types::{ObjectID, ObjectRef};
+use sui_types::transaction::{
+    CallArg, ObjectArg, SenderSignedData, Transaction, TransactionData, TransactionDataAPI,
+    TransactionKind,
+};
+
+const DEFAULT_GAS_PRICE_BUMP_PERCENT: u64 = 10;
+
+/// Opt-in policy for resubmitting transactions that failed with a transient error.
+#[derive(Args, Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Number of times to resubmit a transaction that failed with a transient error: a gas price
+    /// below the reference gas price (e.g. after an epoch change), or a stale version of a gas
+    /// coin (e.g. used by a concurrent transaction). Retries are disabled by default.
+    #[clap(long = "retries", global = true, default_value_t = 0)]
+    pub max_retries: u32,
+
+    /// Percentage by which the gas price, and the gas budget with it, are increased when retrying
+    /// a transaction whose gas price was too low.
+    #[clap(long = "retry-gas-price-bump", global = true, default_value_t = DEFAULT_GAS_PRICE_BUMP_PERCENT)]
+    pub gas_price_bump_percent: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            gas_price_bump_percent: DEFAULT_GAS_PRICE_BUMP_PERCENT,
+        }
+    }
+}
+
+/// Transient failures that a transaction can be resubmitted after.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TransientFailure {
+    GasPriceTooLow,
+    StaleObjectVersion,
+}
+
+impl TransientFailure {
+    /// Classifies an error returned when executing a transaction. Errors are only available as
+    /// messages once they have been returned by the RPC.
+    fn classify(error: &anyhow::Error) -> Option<Self> {
+        let message = format!("{error:#}");
+        if message.contains("under reference gas price") {
+            Some(Self::GasPriceTooLow)
+        } else if message.contains("is not available for consumption") {
+            Some(Self::StaleObjectVersion)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for TransientFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GasPriceTooLow => write!(f, "gas price below the reference gas price"),
+            Self::StaleObjectVersion => write!(f, "stale version of an owned object"),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Signs and executes `tx_data` with the sender's key, resubmitting it with an adjusted gas
+    /// price or refreshed gas coin references after transient failures, up to `max_retries`
+    /// times. Each failed attempt is reported on stderr.
+    pub async fn execute(
+        &self,
+        context: &WalletContext,
+        mut tx_data: TransactionData,
+    ) -> Result<SuiTransactionBlockResponse, anyhow::Error> {
+        let attempts = self.max_retries + 1;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let signature = context.config.keystore.sign_secure(
+                &tx_data.sender(),
+                &tx_data,
+                Intent::sui_transaction(),
+            )?;
+            let transaction = Transaction::new(SenderSignedData::new_from_sender_signature(
+                tx_data.clone(),
+                Intent::sui_transaction(),
+                signature,
+            ));
+
+            let error = match context.execute_transaction_may_fail(transaction).await {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+            let Some(failure) = TransientFailure::classify(&error) else {
+                return Err(error);
+            };
+            if attempt >= attempts {
+                return Err(error.context(format!(
+                    "Transaction failed after {attempts} attempt(s) due to a {failure}"
+                )));
+            }
+
+            tx_data = match failure {
+                TransientFailure::GasPriceTooLow => {
+                    let reference_gas_price = context.get_reference_gas_price().await?;
+                    self.bump_gas_price(tx_data, reference_gas_price)?
+                }
+                TransientFailure::StaleObjectVersion => {
+                    let latest = latest_object_refs(context, &tx_data).await?;
+                    refresh_gas_refs(tx_data, &latest)?
+                }
+            };
+            eprintln!(
+                "Attempt {attempt} of {attempts} failed due to a {failure}, retrying with gas \
+                 price {} and gas budget {}",
+                tx_data.gas_price(),
+                tx_data.gas_budget(),
+            );
+        }
+    }
+
+    /// Increases the gas price of `tx_data` by `gas_price_bump_percent`, to at least
+    /// `reference_gas_price`, and scales its gas budget by the same factor, so that it affords
+    /// the same amount of gas units.
+    fn bump_gas_price(
+        &self,
+        mut tx_data: TransactionData,
+        reference_gas_price: u64,
+    ) -> Result<TransactionData, anyhow::Error> {
+        let gas_data = tx_data.gas_data_mut();
+        let old_price = gas_data.price.max(1) as u128;
+        let bumped = old_price * (100 + self.gas_price_bump_percent as u128) / 100;
+        let new_price = bumped.max(reference_gas_price as u128);
+        let new_budget = gas_data.budget as u128 * new_price / old_price;
+
+        gas_data.price = u64::try_from(new_price).map_err(|_| anyhow!("Gas price overflows"))?;
+        gas_data.budget = u64::try_from(new_budget).map_err(|_| anyhow!("Gas budget overflows"))?;
+        Ok(tx_data)
+    }
+}
+
+/// Returns the references to the latest versions of the gas coins and the owned and receiving
+/// inputs of `tx_data`.
+async fn latest_object_refs(
+    context: &WalletContext,
+    tx_data: &TransactionData,
+) -> Result<BTreeMap<ObjectID, ObjectRef>, anyhow::Error> {
+    let ids = tx_data
+        .gas()
+        .iter()
+        .chain(owned_inputs(tx_data))
+        .map(|(id, _, _)| *id)
+        .collect();
+    context
+        .get_client()
+        .await?
+        .read_api()
+        .multi_get_object_with_options(ids, SuiObjectDataOptions::new())
+        .await?
+        .into_iter()
+        .map(|response| {
+            let object = response.into_object()?;
+            Ok((object.object_id, object.object_ref()))
+        })
+        .collect()
+}
+
+/// Returns the references to the owned and receiving objects that `tx_data` takes as inputs.
+fn owned_inputs(tx_data: &TransactionData) -> impl Iterator<Item = &ObjectRef> {
+    let inputs = match tx_data.kind() {
+        TransactionKind::ProgrammableTransaction(ptb) => ptb.inputs.as_slice(),
+        _ => &[],
+    };
+    inputs.iter().filter_map(|input| match input {
+        CallArg::Object(
+            ObjectArg::ImmOrOwnedObject(object_ref) | ObjectArg::Receiving(object_ref),
+        ) => Some(object_ref),
+        _ => None,
+    })
+}
+
+/// Replaces the references to the gas coins of `tx_data` with the `latest` ones. Only gas coins
+/// are refreshed: the transaction was signed for specific versions of its other inputs, so it
+/// fails if any of them has changed since, rather than running against different state.
+fn refresh_gas_refs(
+    mut tx_data: TransactionData,
+    latest: &BTreeMap<ObjectID, ObjectRef>,
+) -> Result<TransactionData, anyhow::Error> {
+    for (id, version, _) in owned_inputs(&tx_data) {
+        match latest.get(id) {
+            Some((_, latest_version, _)) if latest_version == version => {}
+            Some((_, latest_version, _)) => bail!(
+                "Input object {id} changed since the transaction was built: version {version} is \
+                 now {latest_version}, rebuild the transaction against its latest version"
+            ),
+            None => bail!("Input object {id} no longer exists"),
+        }
+    }
+    for gas in &mut tx_data.gas_data_mut().payment {
+        *gas = *latest
+            .get(&gas.0)
+            .ok_or_else(|| anyhow!("Gas coin {} no longer exists", gas.0))?;
+    }
+    Ok(tx_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::{
+        base_types::{random_object_ref, SuiAddress},
+        digests::ObjectDigest,
+        transaction::TransactionData,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let gas_price = anyhow!("Gas price 750 under reference gas price (RGP) 1000");
+        let stale = anyhow!(
+            "Transaction validator signing failed: Object (0x1, 2, abc) is not available for \
+             consumption, its current version: 3."
+        );
+        assert_eq!(
+            TransientFailure::classify(&gas_price),
+            Some(TransientFailure::GasPriceTooLow)
+        );
+        assert_eq!(
+            TransientFailure::classify(&stale.context("Failed to execute")),
+            Some(TransientFailure::StaleObjectVersion)
+        );
+        assert_eq!(
+            TransientFailure::classify(&anyhow!("Insufficient gas")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bump_gas_price() {
+        let tx_data = TransactionData::new_transfer_sui(
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+            None,
+            random_object_ref(),
+            1_000_000,
+            1_000,
+        );
+        let policy = RetryPolicy::default();
+
+        let bumped = policy.bump_gas_price(tx_data.clone(), 0).unwrap();
+        assert_eq!(bumped.gas_price(), 1_100);
+        assert_eq!(bumped.gas_budget(), 1_100_000);
+
+        // The gas price is raised to at least the reference gas price.
+        let bumped = policy.bump_gas_price(tx_data, 2_000).unwrap();
+        assert_eq!(bumped.gas_price(), 2_000);
+        assert_eq!(bumped.gas_budget(), 2_000_000);
+    }
+
+    #[test]
+    fn test_refresh_gas_refs() {
+        let object = random_object_ref();
+        let gas = random_object_ref();
+        let tx_data = TransactionData::new_transfer(
+            SuiAddress::random_for_testing_only(),
+            object,
+            SuiAddress::random_for_testing_only(),
+            gas,
+            1_000_000,
+            1_000,
+        );
+        let new_gas = (gas.0, gas.1.next(), ObjectDigest::random());
+
+        // The gas coin is refreshed when the other inputs are unchanged.
+        let latest = BTreeMap::from([(gas.0, new_gas), (object.0, object)]);
+        let refreshed = refresh_gas_refs(tx_data.clone(), &latest).unwrap();
+        assert_eq!(refreshed.gas(), &[new_gas]);
+        assert_eq!(owned_inputs(&refreshed).collect::<Vec<_>>(), vec![&object]);
+
+        // The transaction is not resubmitted against a different version of its other inputs.
+        let new_object = (object.0, object.1.next(), ObjectDigest::random());
+        let latest = BTreeMap::from([(gas.0, new_gas), (object.0, new_object)]);
+        assert!(refresh_gas_refs(tx_data.clone(), &latest).is_err());
+
+        let latest = BTreeMap::from([(gas.0, new_gas)]);
+        assert!(refresh_gas_refs(tx_data, &latest).is_err());
+    }
+}
@@ -139,7 +139,7 @@ impl<P: Display, S: Send, H: AsyncHandler<S>> Shell<P, S, H> {
     }
 }
 
-fn split_and_unescape(line: &str) -> Result<Vec<String>, anyhow::Error> {
+pub(crate) fn split_and_unescape(line: &str) -> Result<Vec<String>, anyhow::Error> {
     let mut commands = Vec::new();
     let split: Vec<String> = shell_words::split(line)?;
 
@@ -151,7 +151,7 @@ fn split_and_unescape(line: &str) -> Result<Vec<String>, anyhow::Error> {
     Ok(commands)
 }
 
-fn substitute_env_variables(s: String) -> String {
+pub(crate) fn substitute_env_variables(s: String) -> String {
     if !s.contains('$') {
         return s;
     }
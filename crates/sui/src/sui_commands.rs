@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::client_commands::SuiClientCommands;
-use crate::console::start_console;
+use crate::console::{parse_script, run_console_script, start_console};
+use crate::e2e_test::run_e2e_test;
 use crate::fire_drill::{run_fire_drill, FireDrill};
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
+use crate::retry_policy::RetryPolicy;
 use crate::validator_commands::SuiValidatorCommand;
 use anyhow::{anyhow, bail};
 use clap::*;
@@ -13,8 +15,10 @@ use fastcrypto::traits::KeyPair;
 use move_package::BuildConfig;
 use rand::rngs::OsRng;
 use std::io::{stderr, stdout, Write};
+use std::net::SocketAddrV4;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::{fs, io};
 use sui_config::node::Genesis;
 use sui_config::p2p::SeedPeer;
@@ -25,6 +29,9 @@ use sui_config::{
 use sui_config::{
     SUI_BENCHMARK_GENESIS_GAS_KEYSTORE_FILENAME, SUI_GENESIS_FILENAME, SUI_KEYSTORE_FILENAME,
 };
+use sui_faucet::{
+    create_wallet_context, start_faucet, AppState, FaucetConfig, SimpleFaucet, CONCURRENCY_LIMIT,
+};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_move::{self, execute_move_command};
 use sui_move_build::SuiPackageHooks;
@@ -38,6 +45,13 @@ use sui_swarm_config::node_config_builder::FullnodeConfigBuilder;
 use sui_types::crypto::{SignatureScheme, SuiKeyPair};
 use tracing::info;
 
+const DEFAULT_FAUCET_ADDRESS: &str = "127.0.0.1:9123";
+/// Directory of the Sui config dir holding the faucet's keystore and wallet.
+const FAUCET_DIR: &str = "faucet";
+/// 200 SUI per coin, enough to publish and test most packages locally.
+const DEFAULT_FAUCET_MIST_AMOUNT: u64 = 200_000_000_000;
+const DEFAULT_FAUCET_NUM_COINS: usize = 5;
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
@@ -49,6 +63,27 @@ pub enum SuiCommand {
         config: Option<PathBuf>,
         #[clap(long = "no-full-node")]
         no_full_node: bool,
+        /// Start a faucet for the local network, funded by the faucet account created by
+        /// `sui genesis --with-faucet`. Listens on 127.0.0.1:9123 unless another HOST:PORT is
+        /// given.
+        #[clap(
+            long,
+            value_name = "HOST:PORT",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = DEFAULT_FAUCET_ADDRESS,
+            conflicts_with = "no_full_node"
+        )]
+        with_faucet: Option<SocketAddrV4>,
+        /// Amount of MIST in each coin sent by the faucet.
+        #[clap(long, default_value_t = DEFAULT_FAUCET_MIST_AMOUNT, requires = "with_faucet")]
+        faucet_amount: u64,
+        /// Number of coins sent by the faucet for each request.
+        #[clap(long, default_value_t = DEFAULT_FAUCET_NUM_COINS, requires = "with_faucet")]
+        faucet_num_coins: usize,
+        /// Maximum number of requests per second served by the faucet.
+        #[clap(long, default_value_t = 10, requires = "with_faucet")]
+        faucet_requests_per_second: u64,
     },
     #[clap(name = "network")]
     Network {
@@ -83,7 +118,7 @@ pub enum SuiCommand {
         benchmark_ips: Option<Vec<String>>,
         #[clap(
             long,
-            help = "Creates an extra faucet configuration for sui-test-validator persisted runs."
+            help = "Creates an extra faucet account, for sui-test-validator persisted runs and `sui start --with-faucet`."
         )]
         with_faucet: bool,
     },
@@ -106,6 +141,17 @@ pub enum SuiCommand {
         /// Sets the file storing the state of our user accounts (an empty one will be created if missing)
         #[clap(long = "client.config")]
         config: Option<PathBuf>,
+        /// Runs the console commands in this file, one per line, instead of starting the
+        /// interactive console. Lines starting with `#` are ignored.
+        #[clap(long)]
+        script: Option<PathBuf>,
+        /// Runs this console command instead of starting the interactive console. Can be
+        /// repeated, and is run after the commands of `--script`.
+        #[clap(long)]
+        eval: Vec<String>,
+        /// Returns the output of script commands in JSON format.
+        #[clap(long)]
+        json: bool,
     },
     /// Client for interacting with the Sui network.
     #[clap(name = "client")]
@@ -120,6 +166,8 @@ pub enum SuiCommand {
         json: bool,
         #[clap(short = 'y', long = "yes")]
         accept_defaults: bool,
+        #[clap(flatten)]
+        retry_policy: RetryPolicy,
     },
     /// A tool for validators and validator candidates.
     #[clap(name = "validator")]
@@ -164,10 +212,14 @@ impl SuiCommand {
             SuiCommand::Start {
                 config,
                 no_full_node,
+                with_faucet,
+                faucet_amount,
+                faucet_num_coins,
+                faucet_requests_per_second,
             } => {
                 // Auto genesis if path is none and sui directory doesn't exists.
                 if config.is_none() && !sui_config_dir()?.join(SUI_NETWORK_CONFIG).exists() {
-                    genesis(None, None, None, false, None, None, false).await?;
+                    genesis(None, None, None, false, None, None, with_faucet.is_some()).await?;
                 }
 
                 // Load the config of the Sui authority.
@@ -194,6 +246,19 @@ impl SuiCommand {
                 let mut swarm = swarm_builder.build();
                 swarm.launch().await?;
 
+                if let Some(faucet_address) = with_faucet {
+                    let config = FaucetConfig {
+                        port: faucet_address.port(),
+                        host_ip: *faucet_address.ip(),
+                        amount: faucet_amount,
+                        num_coins: faucet_num_coins,
+                        max_request_per_second: faucet_requests_per_second,
+                        write_ahead_log: sui_config_dir()?.join(FAUCET_DIR).join("faucet.wal"),
+                        ..Default::default()
+                    };
+                    start_local_faucet(&swarm, config).await?;
+                }
+
                 let mut interval = tokio::time::interval(std::time::Duration::from_secs(3));
                 let mut unhealthy_cnt = 0;
                 loop {
@@ -270,23 +335,53 @@ impl SuiCommand {
                 cmd.execute(&mut keystore).await?.print(!json);
                 Ok(())
             }
-            SuiCommand::Console { config } => {
+            SuiCommand::Console {
+                config,
+                script,
+                eval,
+                json,
+            } => {
                 let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
-                prompt_if_no_config(&config, false).await?;
+                if script.is_none() && eval.is_empty() {
+                    prompt_if_no_config(&config, false).await?;
+                    let context = WalletContext::new(&config, None, None)?;
+                    return start_console(context, &mut stdout(), &mut stderr()).await;
+                }
+
+                let mut commands = vec![];
+                if let Some(script) = script {
+                    let contents = fs::read_to_string(&script).map_err(|e| {
+                        anyhow!("Cannot read console script {}: {e}", script.display())
+                    })?;
+                    commands.extend(parse_script(&script.display().to_string(), &contents)?);
+                }
+                for (i, command) in eval.iter().enumerate() {
+                    commands.extend(parse_script(&format!("--eval #{}", i + 1), command)?);
+                }
+                // Scripts cannot answer the config prompt, and must not create a config silently.
+                if !config.exists() {
+                    bail!(
+                        "Config file {config:?} doesn't exist. Run `sui client` to create it \
+                        before running console scripts."
+                    );
+                }
                 let context = WalletContext::new(&config, None, None)?;
-                start_console(context, &mut stdout(), &mut stderr()).await
+                run_console_script(context, commands, json, &mut stdout()).await
             }
             SuiCommand::Client {
                 config,
                 cmd,
                 json,
                 accept_defaults,
+                retry_policy,
             } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None, None)?;
                 if let Some(cmd) = cmd {
-                    cmd.execute(&mut context).await?.print(!json);
+                    cmd.execute_with_retry_policy(&mut context, retry_policy)
+                        .await?
+                        .print(!json);
                 } else {
                     // Print help
                     let mut app: Command = SuiCommand::command();
@@ -314,6 +409,11 @@ impl SuiCommand {
                 }
                 Ok(())
             }
+            SuiCommand::Move {
+                package_path,
+                build_config,
+                cmd: sui_move::Command::E2eTest(e2e_test),
+            } => run_e2e_test(package_path, build_config, e2e_test).await,
             SuiCommand::Move {
                 package_path,
                 build_config,
@@ -450,10 +550,24 @@ async fn genesis(
             .build()
     };
 
+    // The faucet account is the last one generated, see `GenesisConfig::add_faucet_account`. Its
+    // key goes to a keystore of its own, so that the faucet does not spend the client's gas.
+    let (client_keys, faucet_key) = match network_config.account_keys.split_last() {
+        Some((faucet_key, client_keys)) if with_faucet => (client_keys, Some(faucet_key)),
+        _ => (&network_config.account_keys[..], None),
+    };
     let mut keystore = FileBasedKeystore::new(&keystore_path)?;
-    for key in &network_config.account_keys {
+    for key in client_keys {
         keystore.add_key(None, SuiKeyPair::Ed25519(key.copy()))?;
     }
+    if let Some(faucet_key) = faucet_key {
+        let faucet_dir = sui_config_dir.join(FAUCET_DIR);
+        fs::create_dir_all(&faucet_dir)?;
+        let faucet_keystore_path = faucet_dir.join(SUI_KEYSTORE_FILENAME);
+        FileBasedKeystore::new(&faucet_keystore_path)?
+            .add_key(None, SuiKeyPair::Ed25519(faucet_key.copy()))?;
+        info!("Faucet keystore is stored in {:?}.", faucet_keystore_path);
+    }
     let active_address = keystore.addresses().pop();
 
     network_config.genesis.save(&genesis_path)?;
@@ -559,6 +673,58 @@ async fn genesis(
     Ok(())
 }
 
+/// Starts a faucet serving the local network of `swarm` in the background. The faucet uses its
+/// own wallet, holding the faucet account created at genesis, in the `faucet` config directory.
+async fn start_local_faucet(swarm: &Swarm, config: FaucetConfig) -> Result<(), anyhow::Error> {
+    let faucet_dir = sui_config_dir()?.join(FAUCET_DIR);
+
+    let fullnode = swarm
+        .fullnodes()
+        .next()
+        .ok_or_else(|| anyhow!("The faucet requires a full node"))?;
+    let rpc = format!("http://127.0.0.1:{}", fullnode.json_rpc_address().port());
+
+    let keystore = FileBasedKeystore::new(&faucet_dir.join(SUI_KEYSTORE_FILENAME))?;
+    let Some(address) = keystore.addresses().first().copied() else {
+        bail!(
+            "The local network has no faucet account, create one with `sui genesis --force --with-faucet`"
+        );
+    };
+    let wallet_config_path = faucet_dir.join(SUI_CLIENT_CONFIG);
+    SuiClientConfig {
+        keystore: Keystore::from(keystore),
+        envs: vec![SuiEnv {
+            alias: "localnet".to_string(),
+            rpc,
+            ws: None,
+        }],
+        active_address: Some(address),
+        active_env: Some("localnet".to_string()),
+    }
+    .persisted(&wallet_config_path)
+    .save()?;
+
+    let context = create_wallet_context(config.wallet_client_timeout_secs, &wallet_config_path)?;
+    let prometheus_registry = prometheus::Registry::new();
+    let faucet = SimpleFaucet::new(
+        context,
+        &prometheus_registry,
+        &config.write_ahead_log,
+        config.clone(),
+    )
+    .await
+    .map_err(|e| anyhow!("Cannot start faucet: {e}"))?;
+
+    println!("Faucet URL: http://{}:{}", config.host_ip, config.port);
+    let app_state = Arc::new(AppState { faucet, config });
+    tokio::spawn(async move {
+        if let Err(e) = start_faucet(app_state, CONCURRENCY_LIMIT, &prometheus_registry).await {
+            eprintln!("Faucet stopped: {e}");
+        }
+    });
+    Ok(())
+}
+
 async fn prompt_if_no_config(
     wallet_conf_path: &Path,
     accept_defaults: bool,
@@ -0,0 +1,121 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use move_core_types::account_address::AccountAddress;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use tempfile::TempDir;
+
+use super::{client_config_arg, resolve_object_id, AddressBook};
+use crate::client_ptb::{
+    ast::{Argument, ParsedPTBCommand},
+    parser::ProgramParser,
+};
+use crate::key_identity::KeyIdentity;
+
+#[test]
+fn test_add_export_and_import() -> Result<(), anyhow::Error> {
+    let alice = SuiAddress::random_for_testing_only();
+    let bob = SuiAddress::random_for_testing_only();
+    let carol = SuiAddress::random_for_testing_only();
+
+    let mut book = AddressBook::default();
+    assert_eq!(book.add("alice".to_string(), alice)?, None);
+    assert_eq!(book.add("bob".to_string(), bob)?, None);
+    assert_eq!(book.add("bob".to_string(), carol)?, Some(bob));
+    assert!(book.add("0x42".to_string(), alice).is_err());
+    assert!(book.add("my-label".to_string(), alice).is_err());
+    assert_eq!(book.get("bob")?, carol);
+    assert!(book.get("carol").is_err());
+
+    let dir = TempDir::new()?;
+    let path = dir.path().join("exported.yaml");
+    book.write(&path)?;
+    assert_eq!(AddressBook::read(&path)?, book);
+
+    // Labels that are already in the address book with a different address are only replaced
+    // when overwriting.
+    let mut other = AddressBook::default();
+    other.add("alice".to_string(), alice)?;
+    other.add("bob".to_string(), bob)?;
+    other.add("carol".to_string(), carol)?;
+    let (imported, skipped) = book.clone().import(other.clone(), false);
+    assert_eq!(imported, vec!["carol".to_string()]);
+    assert_eq!(skipped, vec!["bob".to_string()]);
+    let (imported, skipped) = book.import(other.clone(), true);
+    assert_eq!(imported, vec!["bob".to_string(), "carol".to_string()]);
+    assert!(skipped.is_empty());
+    assert_eq!(book, other);
+    Ok(())
+}
+
+#[test]
+fn test_labels_in_commands_and_ptbs() -> Result<(), anyhow::Error> {
+    assert!(matches!(
+        KeyIdentity::from_str("@alice")?,
+        KeyIdentity::Label(label) if label == "alice"
+    ));
+
+    let alice = SuiAddress::random_for_testing_only();
+    let mut book = AddressBook::default();
+    book.add("alice".to_string(), alice)?;
+    let args = ["--transfer-objects", "[gas]", "@alice"];
+    let parse = |address_book| {
+        ProgramParser::new(args.into_iter())
+            .unwrap()
+            .with_address_book(address_book)
+            .parse()
+    };
+
+    let (program, _) = parse(book.ptb_addresses()).unwrap();
+    let ParsedPTBCommand::TransferObjects(_, recipient) = &program.commands[0].value else {
+        panic!("Expected a transfer-objects command");
+    };
+    let Argument::Address(address) = &recipient.value else {
+        panic!("Expected an address");
+    };
+    assert_eq!(address.into_inner(), AccountAddress::from(alice));
+
+    // Labels that are not in the address book are not resolved.
+    assert!(parse(BTreeMap::new()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_labels_in_object_id_arguments() -> Result<(), anyhow::Error> {
+    let dir = TempDir::new()?;
+    let config = dir.path().join("client.yaml");
+    let path = AddressBook::path_for_config(&config);
+    let id = ObjectID::random();
+
+    // Without an address book, only object IDs are accepted.
+    assert_eq!(resolve_object_id(&id.to_string(), &path)?, id);
+    assert!(resolve_object_id("@coin", &path).is_err());
+
+    let mut book = AddressBook::default();
+    book.add("coin".to_string(), id.into())?;
+    book.write(&path)?;
+    assert_eq!(resolve_object_id("@coin", &path)?, id);
+    assert!(resolve_object_id("@other", &path).is_err());
+
+    // The address book is found next to the client config of the command line.
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        client_config_arg(
+            args(&["sui", "client", "--client.config", "a.yaml", "object"]).into_iter()
+        ),
+        Some(PathBuf::from("a.yaml"))
+    );
+    assert_eq!(
+        client_config_arg(args(&["sui", "client", "--client.config=b.yaml", "object"]).into_iter()),
+        Some(PathBuf::from("b.yaml"))
+    );
+    assert_eq!(
+        client_config_arg(args(&["sui", "client", "object", "@coin"]).into_iter()),
+        None
+    );
+    Ok(())
+}
@@ -0,0 +1,59 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::env;
+
+use crate::console::{get_command, parse_script, ScriptCommand};
+
+#[test]
+fn test_parse_script() {
+    env::set_var("CONSOLE_TEST_GAS_BUDGET", "5000000");
+    let script = r#"
+# Publish and call
+active-address
+
+  call --package 0x2 --module "my module" --function f --gas-budget $CONSOLE_TEST_GAS_BUDGET
+"#;
+    let commands = parse_script("script.txt", script).unwrap();
+    assert_eq!(
+        commands,
+        vec![
+            ScriptCommand {
+                source: "script.txt:3".to_string(),
+                args: vec!["active-address".to_string()],
+            },
+            ScriptCommand {
+                source: "script.txt:5".to_string(),
+                args: [
+                    "call",
+                    "--package",
+                    "0x2",
+                    "--module",
+                    "my module",
+                    "--function",
+                    "f",
+                    "--gas-budget",
+                    "5000000",
+                ]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_script_reports_line() {
+    let err = parse_script("script.txt", "active-address\ngas \"0x1\n").unwrap_err();
+    assert!(err.to_string().starts_with("script.txt:2: "), "{err}");
+}
+
+#[test]
+fn test_script_commands_are_client_commands() {
+    let commands = parse_script("script.txt", "active-address --json\nenvs").unwrap();
+    let opts = get_command(commands[0].args.clone()).unwrap();
+    assert!(opts.json);
+    assert!(get_command(commands[1].args.clone()).is_ok());
+    assert!(get_command(vec!["not-a-command".to_string()]).is_err());
+}
@@ -0,0 +1,389 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Failover, FailoverNetwork, FailoverReport, OnChainValidator, ValidatorMetadata};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::Duration;
+use sui_types::base_types::SuiAddress;
+use sui_types::committee::EpochId;
+use sui_types::multiaddr::Multiaddr;
+
+const STARTING_EPOCH: EpochId = 5;
+const STARTING_CONFIG: &str = "starting config";
+const NEW_CONFIG: &str = "new config";
+
+fn metadata(key: u8, port: u16) -> ValidatorMetadata {
+    let udp = |port: u16| -> Multiaddr { format!("/ip4/127.0.0.1/udp/{port}").parse().unwrap() };
+    ValidatorMetadata {
+        protocol_pubkey: vec![key; 96],
+        proof_of_possession: vec![key; 48],
+        network_pubkey: vec![key; 32],
+        worker_pubkey: vec![key + 1; 32],
+        network_address: format!("/ip4/127.0.0.1/tcp/{port}/http").parse().unwrap(),
+        p2p_address: udp(port + 1),
+        primary_address: udp(port + 2),
+        worker_address: udp(port + 3),
+    }
+}
+
+fn starting_metadata() -> ValidatorMetadata {
+    metadata(1, 8000)
+}
+
+fn new_metadata() -> ValidatorMetadata {
+    metadata(2, 9000)
+}
+
+/// When the epoch of the fake network changes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EpochChange {
+    /// At the `n`-th read of the current epoch, which already sees the new epoch.
+    EpochRead(usize),
+    /// Right after the `n`-th read of the validator.
+    ValidatorRead(usize),
+    /// Right after the `n`-th metadata update.
+    Update(usize),
+}
+
+struct State {
+    epoch: EpochId,
+    metadata: ValidatorMetadata,
+    next_epoch_metadata: ValidatorMetadata,
+    epoch_reads: usize,
+    validator_reads: usize,
+    /// The metadata updates, with the epochs they were executed in.
+    updates: Vec<(EpochId, &'static str)>,
+    /// The metadata update that fails, once.
+    failing_update: Option<usize>,
+}
+
+impl State {
+    fn change_epoch(&mut self) {
+        self.epoch += 1;
+        self.metadata = self.next_epoch_metadata.clone();
+    }
+}
+
+/// A network with a single validator, whose epoch changes at a given point of the drill.
+struct FakeNetwork {
+    state: Mutex<State>,
+    epoch_change: Option<EpochChange>,
+    /// Whether the validator serves traffic at its new network address.
+    serves_traffic: bool,
+}
+
+impl FakeNetwork {
+    fn new(epoch_change: Option<EpochChange>) -> Self {
+        Self {
+            state: Mutex::new(State {
+                epoch: STARTING_EPOCH,
+                metadata: starting_metadata(),
+                next_epoch_metadata: starting_metadata(),
+                epoch_reads: 0,
+                validator_reads: 0,
+                updates: vec![],
+                failing_update: None,
+            }),
+            epoch_change,
+            serves_traffic: true,
+        }
+    }
+
+    fn updates(&self) -> Vec<(EpochId, &'static str)> {
+        self.state.lock().unwrap().updates.clone()
+    }
+
+    fn next_epoch_metadata(&self) -> ValidatorMetadata {
+        self.state.lock().unwrap().next_epoch_metadata.clone()
+    }
+}
+
+#[async_trait]
+impl FailoverNetwork for FakeNetwork {
+    async fn current_epoch(&self) -> anyhow::Result<EpochId> {
+        let mut state = self.state.lock().unwrap();
+        state.epoch_reads += 1;
+        if self.epoch_change == Some(EpochChange::EpochRead(state.epoch_reads)) {
+            state.change_epoch();
+        }
+        Ok(state.epoch)
+    }
+
+    async fn validator(&self) -> anyhow::Result<OnChainValidator> {
+        let mut state = self.state.lock().unwrap();
+        let validator = OnChainValidator {
+            epoch: state.epoch,
+            metadata: state.metadata.clone(),
+            next_epoch_metadata: state.next_epoch_metadata.clone(),
+        };
+        state.validator_reads += 1;
+        if self.epoch_change == Some(EpochChange::ValidatorRead(state.validator_reads)) {
+            state.change_epoch();
+        }
+        Ok(validator)
+    }
+
+    async fn update_next_epoch(
+        &self,
+        metadata: &ValidatorMetadata,
+        next_epoch_metadata: &ValidatorMetadata,
+    ) -> anyhow::Result<Vec<EpochId>> {
+        let mut state = self.state.lock().unwrap();
+        let mut epochs = vec![];
+        for (function, _) in metadata.next_epoch_updates(next_epoch_metadata) {
+            if state.failing_update == Some(state.updates.len() + 1) {
+                state.failing_update = None;
+                return Err(anyhow!("{function} failed"));
+            }
+            let next = &mut state.next_epoch_metadata;
+            match function {
+                "update_validator_next_epoch_protocol_pubkey" => {
+                    next.protocol_pubkey = metadata.protocol_pubkey.clone();
+                    next.proof_of_possession = metadata.proof_of_possession.clone();
+                }
+                "update_validator_next_epoch_network_pubkey" => {
+                    next.network_pubkey = metadata.network_pubkey.clone()
+                }
+                "update_validator_next_epoch_worker_pubkey" => {
+                    next.worker_pubkey = metadata.worker_pubkey.clone()
+                }
+                "update_validator_next_epoch_network_address" => {
+                    next.network_address = metadata.network_address.clone()
+                }
+                "update_validator_next_epoch_p2p_address" => {
+                    next.p2p_address = metadata.p2p_address.clone()
+                }
+                "update_validator_next_epoch_primary_address" => {
+                    next.primary_address = metadata.primary_address.clone()
+                }
+                "update_validator_next_epoch_worker_address" => {
+                    next.worker_address = metadata.worker_address.clone()
+                }
+                _ => unreachable!("{function}"),
+            }
+            let epoch = state.epoch;
+            state.updates.push((epoch, function));
+            epochs.push(epoch);
+            if self.epoch_change == Some(EpochChange::Update(state.updates.len())) {
+                state.change_epoch();
+            }
+        }
+        Ok(epochs)
+    }
+
+    async fn in_committee(&self, epoch: EpochId, protocol_pubkey: &[u8]) -> anyhow::Result<bool> {
+        let state = self.state.lock().unwrap();
+        Ok(epoch <= state.epoch && state.metadata.protocol_pubkey == protocol_pubkey)
+    }
+
+    async fn wait_for_traffic(&self, _address: &Multiaddr) -> anyhow::Result<()> {
+        if !self.serves_traffic {
+            std::future::pending::<()>().await;
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of a drill: its result and report, the contents of the node config afterwards,
+/// and whether the new config is still next to it.
+struct Outcome {
+    result: anyhow::Result<()>,
+    report: FailoverReport,
+    config: String,
+    new_config_exists: bool,
+}
+
+impl Outcome {
+    fn failed_steps(&self) -> Vec<&'static str> {
+        self.report
+            .steps
+            .iter()
+            .filter(|step| step.error.is_some())
+            .map(|step| step.name)
+            .collect()
+    }
+}
+
+/// Runs the drill on `network`, giving up on the epoch change after `epoch_timeout`.
+async fn run_drill(network: &FakeNetwork, epoch_timeout: Duration) -> Outcome {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("validator.yaml");
+    let new_config_path = dir.path().join("validator.yaml.next_epoch");
+    std::fs::write(&config_path, STARTING_CONFIG).unwrap();
+    std::fs::write(&new_config_path, NEW_CONFIG).unwrap();
+
+    let failover = Failover {
+        network,
+        config_path: &config_path,
+        starting_config: STARTING_CONFIG.into(),
+        new_config_path: new_config_path.clone(),
+        starting_metadata: starting_metadata(),
+        new_metadata: new_metadata(),
+        epoch_timeout,
+        validation_timeout: Duration::from_millis(100),
+        poll_interval: Duration::from_millis(1),
+    };
+    let mut report = FailoverReport {
+        validator: SuiAddress::ZERO,
+        starting_epoch: STARTING_EPOCH,
+        steps: vec![],
+        rolled_back: false,
+        kept_new_config: false,
+    };
+    let result = failover.run(&mut report).await;
+    Outcome {
+        result,
+        report,
+        config: std::fs::read_to_string(&config_path).unwrap(),
+        new_config_exists: new_config_path.exists(),
+    }
+}
+
+#[test]
+fn test_next_epoch_updates() {
+    let starting = starting_metadata();
+    assert!(starting.next_epoch_updates(&starting).is_empty());
+
+    let functions = |updates: Vec<(&'static str, _)>| -> Vec<&'static str> {
+        updates.into_iter().map(|(function, _)| function).collect()
+    };
+    assert_eq!(
+        functions(new_metadata().next_epoch_updates(&starting)).len(),
+        7
+    );
+
+    let moved = ValidatorMetadata {
+        network_address: new_metadata().network_address,
+        proof_of_possession: vec![3; 48],
+        ..starting.clone()
+    };
+    assert_eq!(
+        functions(moved.next_epoch_updates(&starting)),
+        vec![
+            "update_validator_next_epoch_protocol_pubkey",
+            "update_validator_next_epoch_network_address",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_failover() {
+    let network = FakeNetwork::new(Some(EpochChange::EpochRead(2)));
+    let outcome = run_drill(&network, Duration::from_secs(10)).await;
+
+    outcome.result.as_ref().unwrap();
+    assert!(outcome.failed_steps().is_empty());
+    assert_eq!(outcome.config, NEW_CONFIG);
+    assert!(!outcome.new_config_exists);
+    let updates = network.updates();
+    assert_eq!(updates.len(), 7);
+    assert!(updates.iter().all(|(epoch, _)| *epoch == STARTING_EPOCH));
+}
+
+#[tokio::test]
+async fn test_failover_epoch_change_right_after_update() {
+    // The epoch changes before the drill starts waiting for it, which is not mistaken for an
+    // epoch change that happened before the update.
+    let network = FakeNetwork::new(Some(EpochChange::Update(7)));
+    let outcome = run_drill(&network, Duration::from_secs(10)).await;
+
+    outcome.result.as_ref().unwrap();
+    assert_eq!(outcome.config, NEW_CONFIG);
+}
+
+#[tokio::test]
+async fn test_failover_update_across_epoch_change() {
+    // The new keys took effect, and the new addresses take effect in the following epoch, so the
+    // new config is kept and nothing is resubmitted.
+    let network = FakeNetwork::new(Some(EpochChange::Update(3)));
+    let outcome = run_drill(&network, Duration::from_secs(10)).await;
+
+    let err = outcome.result.as_ref().unwrap_err().to_string();
+    assert!(err.contains("across epochs 5 to 6"), "{err}");
+    assert_eq!(
+        outcome.failed_steps(),
+        vec!["Update the next epoch metadata"]
+    );
+    assert!(outcome.report.rolled_back);
+    assert!(outcome.report.kept_new_config);
+    assert_eq!(outcome.config, NEW_CONFIG);
+    assert_eq!(network.updates().len(), 7);
+    assert_eq!(network.next_epoch_metadata(), new_metadata());
+}
+
+#[tokio::test]
+async fn test_rollback_resubmits_missing_metadata() {
+    // The update fails after the new keys were submitted, and they take effect before the
+    // rollback: only the addresses are resubmitted.
+    let network = FakeNetwork::new(Some(EpochChange::Update(3)));
+    network.state.lock().unwrap().failing_update = Some(4);
+    let outcome = run_drill(&network, Duration::from_secs(10)).await;
+
+    assert!(outcome.result.is_err());
+    assert!(outcome.report.rolled_back);
+    assert!(outcome.report.kept_new_config);
+    assert_eq!(outcome.config, NEW_CONFIG);
+    assert!(!outcome.new_config_exists);
+    assert_eq!(
+        network.updates()[3..],
+        [
+            (6, "update_validator_next_epoch_network_address"),
+            (6, "update_validator_next_epoch_p2p_address"),
+            (6, "update_validator_next_epoch_primary_address"),
+            (6, "update_validator_next_epoch_worker_address"),
+        ]
+    );
+    assert_eq!(network.next_epoch_metadata(), new_metadata());
+}
+
+#[tokio::test]
+async fn test_rollback_before_epoch_change() {
+    let network = FakeNetwork::new(None);
+    let outcome = run_drill(&network, Duration::from_millis(20)).await;
+
+    assert_eq!(outcome.failed_steps(), vec!["Wait for the next epoch"]);
+    assert!(outcome.report.rolled_back);
+    assert!(!outcome.report.kept_new_config);
+    assert_eq!(outcome.config, STARTING_CONFIG);
+    assert!(!outcome.new_config_exists);
+    assert_eq!(network.updates().len(), 14);
+    assert_eq!(network.next_epoch_metadata(), starting_metadata());
+}
+
+#[tokio::test]
+async fn test_rollback_after_epoch_change() {
+    // The node config was already replaced, and the new keys are those of the validator.
+    let mut network = FakeNetwork::new(Some(EpochChange::EpochRead(2)));
+    network.serves_traffic = false;
+    let outcome = run_drill(&network, Duration::from_secs(10)).await;
+
+    assert_eq!(
+        outcome.failed_steps(),
+        vec!["Check traffic at the new network address"]
+    );
+    assert!(outcome.report.rolled_back);
+    assert!(outcome.report.kept_new_config);
+    assert!(outcome
+        .report
+        .to_string()
+        .contains("The node keeps its new config"));
+    assert_eq!(outcome.config, NEW_CONFIG);
+    assert_eq!(network.updates().len(), 7);
+}
+
+#[tokio::test]
+async fn test_rollback_epoch_change_during_rollback() {
+    // The new keys take effect right after the rollback read the validator, so the rollback runs
+    // again and keeps the new config after all.
+    let network = FakeNetwork::new(Some(EpochChange::ValidatorRead(2)));
+    let outcome = run_drill(&network, Duration::from_millis(20)).await;
+
+    assert_eq!(outcome.failed_steps(), vec!["Wait for the next epoch"]);
+    assert!(outcome.report.rolled_back);
+    assert!(outcome.report.kept_new_config);
+    assert_eq!(outcome.config, NEW_CONFIG);
+    assert!(!outcome.new_config_exists);
+    assert_eq!(network.next_epoch_metadata(), new_metadata());
+}
@@ -7,6 +7,7 @@ use crate::key_identity::KeyIdentity;
 use crate::keytool::read_authority_keypair_from_file;
 use crate::keytool::read_keypair_from_file;
 use crate::keytool::CommandOutput;
+use crate::zklogin_commands_util::{verify_zk_login_flow, ZkLoginFlowInputs, ZkLoginVerifyStatus};
 
 use super::write_keypair_to_file;
 use super::KeyToolCommand;
@@ -16,10 +17,16 @@ use fastcrypto::encoding::Base64;
 use fastcrypto::encoding::Encoding;
 use fastcrypto::encoding::Hex;
 use fastcrypto::traits::ToFromBytes;
+use fastcrypto_zkp::bn254::zk_login::{parse_jwks, OIDCProvider};
+use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
+use serde_json::json;
 use shared_crypto::intent::Intent;
+use shared_crypto::intent::IntentMessage;
 use shared_crypto::intent::IntentScope;
+use shared_crypto::intent::PersonalMessage;
+use std::time::{SystemTime, UNIX_EPOCH};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_types::base_types::ObjectDigest;
 use sui_types::base_types::ObjectID;
@@ -36,8 +43,11 @@ use sui_types::crypto::Signature;
 use sui_types::crypto::SignatureScheme;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::SuiSignatureInner;
+use sui_types::signature::GenericSignature;
 use sui_types::transaction::TransactionData;
 use sui_types::transaction::TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
+use sui_types::zk_login_authenticator::ZkLoginAuthenticator;
+use sui_types::zk_login_util::{get_zklogin_inputs, DEFAULT_JWK_BYTES};
 use tempfile::TempDir;
 use tokio::test;
 
@@ -565,3 +575,145 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_sign_ptb_command() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(1));
+    let binding = keystore.addresses();
+    let sender = binding.first().unwrap();
+
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let gas_price = 1;
+    let tx_data = TransactionData::new_pay_sui(
+        *sender,
+        vec![gas],
+        vec![SuiAddress::random_for_testing_only()],
+        vec![10000],
+        gas,
+        gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_price,
+    )
+    .unwrap();
+
+    // Sign the transaction data, with the default intent.
+    let output = KeyToolCommand::SignPtb {
+        address: KeyIdentity::Address(*sender),
+        data: Base64::encode(bcs::to_bytes(&tx_data)?),
+        intent: None,
+        yes: true,
+    }
+    .execute(&mut keystore)
+    .await?;
+    let CommandOutput::Sign(signed) = output else {
+        panic!("unexpected output")
+    };
+
+    // Signing the intent message for the transaction data yields the same signature.
+    let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data.clone());
+    let output = KeyToolCommand::SignPtb {
+        address: KeyIdentity::Address(*sender),
+        data: Base64::encode(bcs::to_bytes(&intent_msg)?),
+        intent: None,
+        yes: true,
+    }
+    .execute(&mut keystore)
+    .await?;
+    let CommandOutput::Sign(signed_intent_msg) = output else {
+        panic!("unexpected output")
+    };
+    assert_eq!(signed.raw_tx_data, signed_intent_msg.raw_tx_data);
+    assert_eq!(signed.sui_signature, signed_intent_msg.sui_signature);
+
+    // The intent passed in must match the intent of the message.
+    assert!(KeyToolCommand::SignPtb {
+        address: KeyIdentity::Address(*sender),
+        data: Base64::encode(bcs::to_bytes(&intent_msg)?),
+        intent: Some(Intent::sui_app(IntentScope::PersonalMessage)),
+        yes: true,
+    }
+    .execute(&mut keystore)
+    .await
+    .is_err());
+
+    // Only transaction intents can be signed.
+    assert!(KeyToolCommand::SignPtb {
+        address: KeyIdentity::Address(*sender),
+        data: Base64::encode(bcs::to_bytes(&tx_data)?),
+        intent: Some(Intent::sui_app(IntentScope::PersonalMessage)),
+        yes: true,
+    }
+    .execute(&mut keystore)
+    .await
+    .is_err());
+    Ok(())
+}
+
+#[test]
+async fn test_zk_login_verify_flow() -> Result<(), anyhow::Error> {
+    // The fixed dev-only ephemeral key and proof of `zk-login-insecure-sign-personal-message`.
+    let ephemeral_key =
+        SuiKeyPair::Ed25519(get_key_pair_from_rng(&mut StdRng::from_seed([0; 32])).1);
+    let data = PersonalMessage {
+        message: b"hello world".to_vec(),
+    };
+    let intent_msg = IntentMessage::new(Intent::personal_message(), data.clone());
+    let zk = ZkLoginAuthenticator::new(
+        get_zklogin_inputs(),
+        10,
+        Signature::new_secure(&intent_msg, &ephemeral_key),
+    );
+    let iss = zk.get_iss().to_string();
+    let sig = Base64::encode(GenericSignature::ZkLoginAuthenticator(zk).as_ref());
+
+    // Cache the JWKs of the provider, so that they are not fetched.
+    let dir = TempDir::new()?;
+    let jwk_cache = dir.path().join("zklogin_jwks.json");
+    let jwks = parse_jwks(DEFAULT_JWK_BYTES, &OIDCProvider::Twitch)?;
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+    std::fs::write(
+        &jwk_cache,
+        serde_json::to_vec(&json!({ iss: { "fetched_at_ms": now_ms, "jwks": jwks } }))?,
+    )?;
+
+    let report = verify_zk_login_flow(ZkLoginFlowInputs {
+        sig,
+        ephemeral_pubkey: ephemeral_key.public().encode_base64(),
+        jwt: "not.a.jwt".to_string(),
+        jwt_randomness: None,
+        salt: None,
+        bytes: Some(Base64::encode(bcs::to_bytes(&data)?)),
+        intent_scope: IntentScope::PersonalMessage,
+        curr_epoch: Some(11),
+        env: ZkLoginEnv::Test,
+        jwk_cache,
+        refresh_jwks: false,
+    })
+    .await;
+
+    // The proof and the signature are valid, but the JWT is not and the signature has expired.
+    let statuses: Vec<_> = report
+        .stages
+        .iter()
+        .map(|stage| (stage.stage.as_str(), &stage.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("signature", &ZkLoginVerifyStatus::Passed),
+            ("ephemeral key", &ZkLoginVerifyStatus::Passed),
+            ("jwt", &ZkLoginVerifyStatus::Failed),
+            ("nonce", &ZkLoginVerifyStatus::Skipped),
+            ("address", &ZkLoginVerifyStatus::Passed),
+            ("max epoch", &ZkLoginVerifyStatus::Failed),
+            ("jwk", &ZkLoginVerifyStatus::Passed),
+            ("proof and signature", &ZkLoginVerifyStatus::Passed),
+        ]
+    );
+    assert!(!report.verified);
+    assert!(report.address.is_some());
+    Ok(())
+}
@@ -0,0 +1,91 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::PartiallySignedTransaction;
+use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::crypto::{get_key_pair, SuiKeyPair};
+use sui_types::multisig::MultiSigPublicKey;
+use sui_types::signature::GenericSignature;
+use sui_types::transaction::{TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
+use tempfile::TempDir;
+
+fn pay_sui_from(sender: SuiAddress) -> TransactionData {
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    TransactionData::new_pay_sui(
+        sender,
+        vec![gas],
+        vec![SuiAddress::random_for_testing_only()],
+        vec![10000],
+        gas,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        1,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_collect_and_combine_signature_shares() -> Result<(), anyhow::Error> {
+    let keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(3));
+    let pks = keystore.keys();
+    let members: Vec<SuiAddress> = pks.iter().map(SuiAddress::from).collect();
+    let multisig_pk = MultiSigPublicKey::new(pks, vec![1, 1, 1], 2)?;
+    let tx_data = pay_sui_from(SuiAddress::from(&multisig_pk));
+
+    // Two members sign their own copy of the proposed transaction.
+    let proposed = PartiallySignedTransaction::new(&tx_data, &multisig_pk)?;
+    let mut first = proposed.clone();
+    first.sign(&keystore, &members[2])?;
+    let mut second = proposed.clone();
+    second.sign(&keystore, &members[0])?;
+    assert_eq!(first.signed_weight(), 1);
+    assert!(first.combine().is_err());
+
+    // The merged shares meet the threshold, and are combined in the order of the members.
+    first.merge(second)?;
+    assert_eq!(first.signed_weight(), 2);
+    let GenericSignature::MultiSig(multisig) = first.combine()? else {
+        panic!("Expected a multisig");
+    };
+    assert_eq!(multisig.get_indices()?, vec![0, 2]);
+
+    // The file round trips through JSON.
+    let dir = TempDir::new()?;
+    let path = dir.path().join("tx.json");
+    first.write(&path)?;
+    assert_eq!(PartiallySignedTransaction::read(&path)?, first);
+    Ok(())
+}
+
+#[test]
+fn test_reject_invalid_signature_shares() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(2));
+    let pks = keystore.keys();
+    let member = SuiAddress::from(&pks[0]);
+    let multisig_pk = MultiSigPublicKey::new(pks, vec![1, 1], 2)?;
+    let tx_data = pay_sui_from(SuiAddress::from(&multisig_pk));
+
+    // The transaction must be sent from the multisig address.
+    let other_sender = pay_sui_from(SuiAddress::random_for_testing_only());
+    assert!(PartiallySignedTransaction::new(&other_sender, &multisig_pk).is_err());
+
+    // Keys that are not members of the multisig cannot sign.
+    let (outsider, kp) = get_key_pair();
+    keystore.add_key(None, SuiKeyPair::Ed25519(kp))?;
+    let mut tx = PartiallySignedTransaction::new(&tx_data, &multisig_pk)?;
+    assert!(tx.sign(&keystore, &outsider).is_err());
+
+    // Shares of another transaction cannot be merged.
+    let mut other = PartiallySignedTransaction::new(
+        &pay_sui_from(SuiAddress::from(&multisig_pk)),
+        &multisig_pk,
+    )?;
+    other.sign(&keystore, &member)?;
+    assert!(tx.merge(other).is_err());
+    assert_eq!(tx.signed_weight(), 0);
+    Ok(())
+}
@@ -0,0 +1,85 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{Metrics, ValidatorHealth};
+use anyhow::anyhow;
+use std::time::Duration;
+
+fn metrics(
+    round: u64,
+    synced_checkpoint: u64,
+    executed_checkpoint: u64,
+    invalid_blocks: u64,
+) -> Metrics {
+    Metrics::parse(&format!(
+        r#"# TYPE consensus_highest_accepted_round gauge
+consensus_highest_accepted_round {round}
+# TYPE consensus_last_committed_leader_round gauge
+consensus_last_committed_leader_round {}
+# TYPE highest_synced_checkpoint gauge
+highest_synced_checkpoint {synced_checkpoint}
+# TYPE last_executed_checkpoint gauge
+last_executed_checkpoint {executed_checkpoint}
+# TYPE sequencing_in_flight_submissions gauge
+sequencing_in_flight_submissions 7
+# TYPE consensus_core_lock_enqueued counter
+consensus_core_lock_enqueued 120
+# TYPE consensus_core_lock_dequeued counter
+consensus_core_lock_dequeued 117
+# TYPE consensus_consensus_errors counter
+consensus_consensus_errors{{error="invalid_block",peer="validator-2"}} {invalid_blocks}
+consensus_consensus_errors{{error="network",peer="validator-3"}} 4
+"#,
+        round.saturating_sub(2)
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_healthy_validator() {
+    let before = metrics(100, 50, 50, 0);
+    let after = metrics(150, 60, 58, 0);
+    let health = ValidatorHealth::new(&before, &after, Ok(None), Duration::from_secs(10));
+
+    assert!(health.healthy, "{:?}", health.problems);
+    assert_eq!(health.consensus_round, Some(150));
+    assert_eq!(health.consensus_rounds_advanced, Some(50));
+    assert_eq!(health.last_committed_round, Some(148));
+    assert_eq!(health.checkpoint_lag, Some(2));
+    assert_eq!(health.queue_depths["consensus_submissions"], 7);
+    assert_eq!(health.queue_depths["consensus_core_requests"], 3);
+    assert!(health.error_spikes.is_empty());
+}
+
+#[test]
+fn test_unhealthy_validator() {
+    let before = metrics(100, 50, 50, 0);
+    let after = metrics(100, 500, 60, 25);
+    let health = ValidatorHealth::new(
+        &before,
+        &after,
+        Ok(Some(
+            "consensus is stalled: missing authorities".to_string(),
+        )),
+        Duration::from_secs(10),
+    );
+
+    assert!(!health.healthy);
+    assert_eq!(health.consensus_rounds_advanced, Some(0));
+    assert_eq!(health.checkpoint_lag, Some(440));
+    assert_eq!(
+        health.error_spikes.keys().collect::<Vec<_>>(),
+        vec![r#"consensus_consensus_errors{error="invalid_block",peer="validator-2"}"#]
+    );
+    assert_eq!(health.problems.len(), 4, "{:?}", health.problems);
+
+    // A validator whose admin interface cannot be reached is not reported healthy.
+    let health = ValidatorHealth::new(
+        &before,
+        &metrics(150, 60, 58, 0),
+        Err(anyhow!("connection refused")),
+        Duration::from_secs(10),
+    );
+    assert!(!health.healthy);
+    assert_eq!(health.consensus_stall, None);
+}
@@ -6,8 +6,13 @@ use crate::validator_commands::{
 };
 use anyhow::Ok;
 use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::{KeyPair, ToFromBytes};
 use shared_crypto::intent::{Intent, IntentMessage};
-use sui_types::crypto::SuiKeyPair;
+use std::fs;
+use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+use sui_keys::keypair_file::{read_authority_keypair_from_file, write_authority_keypair_to_file};
+use sui_keys::keystore::AccountKeystore;
+use sui_types::crypto::{AuthorityPublicKeyBytes, SuiKeyPair};
 use sui_types::transaction::TransactionData;
 use sui_types::{base_types::SuiAddress, crypto::Signature, transaction::Transaction};
 use test_cluster::TestClusterBuilder;
@@ -65,3 +70,83 @@ async fn test_print_raw_rgp_txn() -> Result<(), anyhow::Error> {
     assert_eq!(summary.next_epoch_gas_price, 42);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_rotate_protocol_key() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new()
+        .with_epoch_duration_ms(10_000)
+        .build()
+        .await;
+    let validator_config = test_cluster
+        .swarm
+        .config()
+        .validator_configs
+        .first()
+        .unwrap();
+    let SuiKeyPair::Ed25519(account_key) = validator_config.account_key_pair.keypair() else {
+        panic!("Expected an Ed25519 account key");
+    };
+    let account_key = SuiKeyPair::Ed25519(account_key.copy());
+    let validator_address = SuiAddress::from(&account_key.public());
+    let old_protocol_key = validator_config.protocol_key_pair().copy();
+    let dir = tempfile::tempdir()?;
+    let protocol_key_path = dir.path().join("protocol.key");
+    write_authority_keypair_to_file(&old_protocol_key, &protocol_key_path)?;
+
+    let mut context = test_cluster.wallet;
+    context.config.keystore.add_key(None, account_key)?;
+    context.config.active_address = Some(validator_address);
+
+    // A dry run does not submit the transaction, nor change any key file.
+    let response = SuiValidatorCommand::RotateProtocolKey {
+        file: protocol_key_path.clone(),
+        dry_run: true,
+        epoch_timeout_secs: 60,
+        gas_budget: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let SuiValidatorCommandResponse::RotateProtocolKeyDryRun { response, .. } = response else {
+        panic!("Expected RotateProtocolKeyDryRun");
+    };
+    assert!(response.effects.status().is_ok());
+    assert_eq!(fs::read_dir(dir.path())?.count(), 1);
+    let sui_client = context.get_client().await?;
+    let (_, summary) = get_validator_summary(&sui_client, validator_address)
+        .await?
+        .unwrap();
+    assert_eq!(summary.next_epoch_protocol_pubkey_bytes, None);
+
+    let response = SuiValidatorCommand::RotateProtocolKey {
+        file: protocol_key_path.clone(),
+        dry_run: false,
+        epoch_timeout_secs: 60,
+        gas_budget: None,
+    }
+    .execute(&mut context)
+    .await?;
+    let SuiValidatorCommandResponse::RotateProtocolKey {
+        new_protocol_key,
+        activation_epoch,
+        archived_key_path,
+        ..
+    } = response
+    else {
+        panic!("Expected RotateProtocolKey");
+    };
+
+    // The new key is active from the next epoch, and replaces the archived key in the key file.
+    assert_eq!(activation_epoch, 1);
+    let (_, summary) = get_validator_summary(&sui_client, validator_address)
+        .await?
+        .unwrap();
+    assert_eq!(summary.protocol_pubkey_bytes, new_protocol_key.as_bytes());
+    let new_key = read_authority_keypair_from_file(&protocol_key_path)?;
+    assert_eq!(
+        AuthorityPublicKeyBytes::from(new_key.public()),
+        new_protocol_key
+    );
+    let archived_key = read_authority_keypair_from_file(&archived_key_path)?;
+    assert_eq!(archived_key.public(), old_protocol_key.public());
+    Ok(())
+}
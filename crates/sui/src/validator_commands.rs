@@ -7,13 +7,15 @@ use std::{
     collections::{BTreeMap, HashSet},
     fmt::{self, Debug, Display, Formatter, Write},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::Duration,
 };
 use sui_genesis_builder::validator_info::GenesisValidatorInfo;
 
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress},
-    crypto::{AuthorityPublicKey, NetworkPublicKey, Signable, DEFAULT_EPOCH_ID},
+    committee::EpochId,
+    crypto::{NetworkPublicKey, Signable, DEFAULT_EPOCH_ID},
     multiaddr::Multiaddr,
     object::Owner,
     sui_system_state::{
@@ -24,7 +26,8 @@ use sui_types::{
 };
 use tap::tap::TapOptional;
 
-use crate::fire_drill::get_gas_obj_ref;
+use crate::fire_drill::{current_epoch, get_gas_obj_ref, wait_for_next_epoch};
+use crate::validator_health::{check_validator_health, ValidatorHealth};
 use clap::*;
 use colored::Colorize;
 use fastcrypto::traits::ToFromBytes;
@@ -35,7 +38,8 @@ use fastcrypto::{
 use serde::Serialize;
 use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
 use sui_json_rpc_types::{
-    SuiObjectDataOptions, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    DryRunTransactionBlockResponse, SuiObjectDataOptions, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_keys::{
@@ -163,6 +167,46 @@ pub enum SuiValidatorCommand {
         #[clap(name = "gas-budget", long)]
         gas_budget: Option<u64>,
     },
+    /// Rotate the protocol key of the validator: generate a new protocol key, set it as the
+    /// protocol key of the next epoch, wait for the next epoch to verify that it is active, then
+    /// archive the current key file and replace it with the new key. The validator must be
+    /// restarted with the new key once it is active.
+    #[clap(name = "rotate-protocol-key")]
+    RotateProtocolKey {
+        /// Path to the current protocol key file of the validator.
+        #[clap(name = "protocol-key-path")]
+        file: PathBuf,
+        /// Generate a new key and dry run the update transaction, without submitting it or
+        /// changing any key file.
+        #[clap(name = "dry-run", long)]
+        dry_run: bool,
+        /// How long to wait for the next epoch, in seconds.
+        #[clap(name = "epoch-timeout-secs", long, default_value_t = 90_000)]
+        epoch_timeout_secs: u64,
+        /// Gas budget for this transaction.
+        #[clap(name = "gas-budget", long)]
+        gas_budget: Option<u64>,
+    },
+    /// Report the health of a running validator from its metrics and admin endpoints: consensus
+    /// round progression, checkpoint execution lag, queue depths and error spikes. Use `--json`
+    /// for a machine-readable report.
+    #[clap(name = "health")]
+    Health {
+        /// URL of the metrics endpoint of the validator.
+        #[clap(
+            name = "metrics-url",
+            long,
+            default_value = "http://127.0.0.1:9184/metrics"
+        )]
+        metrics_url: String,
+        /// URL of the admin interface of the validator, which only listens on localhost.
+        #[clap(name = "admin-url", long, default_value = "http://127.0.0.1:1337")]
+        admin_url: String,
+        /// Seconds between the two samples of the metrics that progression and error spikes are
+        /// measured over.
+        #[clap(name = "interval-secs", long, default_value_t = 10)]
+        interval_secs: u64,
+    },
 }
 
 #[derive(Serialize)]
@@ -181,6 +225,17 @@ pub enum SuiValidatorCommandResponse {
         data: TransactionData,
         serialized_data: String,
     },
+    RotateProtocolKeyDryRun {
+        new_protocol_key: AuthorityPublicKeyBytes,
+        response: DryRunTransactionBlockResponse,
+    },
+    RotateProtocolKey {
+        new_protocol_key: AuthorityPublicKeyBytes,
+        activation_epoch: EpochId,
+        archived_key_path: PathBuf,
+        response: SuiTransactionBlockResponse,
+    },
+    Health(ValidatorHealth),
 }
 
 fn make_key_files(
@@ -454,6 +509,25 @@ impl SuiValidatorCommand {
                     serialized_data,
                 }
             }
+            SuiValidatorCommand::RotateProtocolKey {
+                file,
+                dry_run,
+                epoch_timeout_secs,
+                gas_budget,
+            } => {
+                let gas_budget = gas_budget.unwrap_or(DEFAULT_GAS_BUDGET);
+                let epoch_timeout = Duration::from_secs(epoch_timeout_secs);
+                rotate_protocol_key(context, file, dry_run, epoch_timeout, gas_budget).await?
+            }
+            SuiValidatorCommand::Health {
+                metrics_url,
+                admin_url,
+                interval_secs,
+            } => {
+                let interval = Duration::from_secs(interval_secs);
+                let health = check_validator_health(&metrics_url, &admin_url, interval).await?;
+                SuiValidatorCommandResponse::Health(health)
+            }
         });
         ret
     }
@@ -689,6 +763,33 @@ impl Display for SuiValidatorCommandResponse {
                     data, serialized_data
                 )?;
             }
+            SuiValidatorCommandResponse::RotateProtocolKeyDryRun {
+                new_protocol_key,
+                response,
+            } => {
+                writeln!(writer, "New protocol key: {new_protocol_key}")?;
+                write!(writer, "Dry run effects: {}", response.effects)?;
+            }
+            SuiValidatorCommandResponse::RotateProtocolKey {
+                new_protocol_key,
+                activation_epoch,
+                archived_key_path,
+                response,
+            } => {
+                write!(writer, "{}", write_transaction_response(response)?)?;
+                writeln!(
+                    writer,
+                    "New protocol key {new_protocol_key} is active since epoch {activation_epoch}"
+                )?;
+                write!(
+                    writer,
+                    "Previous protocol key archived in {}",
+                    archived_key_path.display()
+                )?;
+            }
+            SuiValidatorCommandResponse::Health(health) => {
+                write!(writer, "{}", health)?;
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -999,17 +1100,7 @@ async fn update_metadata(
             let _status = check_status(context, HashSet::from([Pending, Active])).await?;
             let sui_address = context.active_address()?;
             let protocol_key_pair: AuthorityKeyPair = read_authority_keypair_from_file(file)?;
-            let protocol_pub_key: AuthorityPublicKey = protocol_key_pair.public().clone();
-            let pop = generate_proof_of_possession(&protocol_key_pair, sui_address);
-            let args = vec![
-                CallArg::Pure(
-                    bcs::to_bytes(&AuthorityPublicKeyBytes::from_bytes(
-                        protocol_pub_key.as_bytes(),
-                    )?)
-                    .unwrap(),
-                ),
-                CallArg::Pure(bcs::to_bytes(&pop.as_ref().to_vec()).unwrap()),
-            ];
+            let args = protocol_pubkey_args(&protocol_key_pair, sui_address);
             call_0x5(
                 context,
                 "update_validator_next_epoch_protocol_pubkey",
@@ -1021,6 +1112,144 @@ async fn update_metadata(
     }
 }
 
+/// Arguments of `update_validator_next_epoch_protocol_pubkey` to set the public key of
+/// `protocol_key_pair`, with its proof of possession, as the protocol key of `sui_address`.
+fn protocol_pubkey_args(
+    protocol_key_pair: &AuthorityKeyPair,
+    sui_address: SuiAddress,
+) -> Vec<CallArg> {
+    let protocol_pub_key = AuthorityPublicKeyBytes::from(protocol_key_pair.public());
+    let pop = generate_proof_of_possession(protocol_key_pair, sui_address);
+    vec![
+        CallArg::Pure(bcs::to_bytes(&protocol_pub_key).unwrap()),
+        CallArg::Pure(bcs::to_bytes(&pop.as_ref().to_vec()).unwrap()),
+    ]
+}
+
+async fn rotate_protocol_key(
+    context: &mut WalletContext,
+    file: PathBuf,
+    dry_run: bool,
+    epoch_timeout: Duration,
+    gas_budget: u64,
+) -> Result<SuiValidatorCommandResponse> {
+    use ValidatorStatus::*;
+    let _status = check_status(context, HashSet::from([Pending, Active])).await?;
+    let sui_address = context.active_address()?;
+    let sui_client = context.get_client().await?;
+    let (_, summary) = get_validator_summary(&sui_client, sui_address)
+        .await?
+        .ok_or_else(|| anyhow!("{sui_address} is not a Validator."))?;
+
+    // Make sure the key file to archive is the key the validator currently uses.
+    let current_key_pair: AuthorityKeyPair = read_authority_keypair_from_file(&file)?;
+    if current_key_pair.public().as_bytes() != summary.protocol_pubkey_bytes.as_slice() {
+        bail!(
+            "The protocol key in {} is not the current protocol key of validator {sui_address}",
+            file.display()
+        );
+    }
+    if summary.next_epoch_protocol_pubkey_bytes.is_some() {
+        bail!("Validator {sui_address} already has a protocol key set for the next epoch");
+    }
+
+    let (_, new_key_pair): (_, AuthorityKeyPair) = get_authority_key_pair();
+    let new_protocol_key = AuthorityPublicKeyBytes::from(new_key_pair.public());
+    let args = protocol_pubkey_args(&new_key_pair, sui_address);
+    let function = "update_validator_next_epoch_protocol_pubkey";
+
+    if dry_run {
+        let data =
+            construct_unsigned_0x5_txn(context, sui_address, function, args, gas_budget).await?;
+        let response = sui_client
+            .read_api()
+            .dry_run_transaction_block(data)
+            .await?;
+        return Ok(SuiValidatorCommandResponse::RotateProtocolKeyDryRun {
+            new_protocol_key,
+            response,
+        });
+    }
+
+    // Keep the new key next to the current one until it is active, so that it is not lost if the
+    // rotation is interrupted.
+    let new_key_file = path_with_suffix(&file, "next_epoch");
+    if new_key_file.exists() {
+        bail!("{} already exists", new_key_file.display());
+    }
+    write_authority_keypair_to_file(&new_key_pair, &new_key_file)?;
+    println!("Generated new protocol key file: {:?}.", new_key_file);
+
+    let starting_epoch = current_epoch(&sui_client).await?;
+    let response = call_0x5(context, function, args, gas_budget).await?;
+    if !response.status_ok().unwrap_or(false) {
+        bail!(
+            "Transaction {} to update the protocol key failed, the new key is kept in {}",
+            response.digest,
+            new_key_file.display()
+        );
+    }
+    let (_, summary) = get_validator_summary(&sui_client, sui_address)
+        .await?
+        .ok_or_else(|| anyhow!("{sui_address} is not a Validator."))?;
+    if summary.next_epoch_protocol_pubkey_bytes.as_deref() != Some(new_protocol_key.as_bytes()) {
+        bail!("The protocol key of the next epoch of validator {sui_address} was not updated");
+    }
+
+    println!(
+        "Waiting for epoch {} to verify that the new protocol key is active.",
+        starting_epoch + 1
+    );
+    tokio::time::timeout(
+        epoch_timeout,
+        wait_for_next_epoch(&sui_client, starting_epoch + 1),
+    )
+    .await
+    .map_err(|_| {
+        anyhow!(
+            "Epoch {} did not start within {}s, the new key is kept in {}",
+            starting_epoch + 1,
+            epoch_timeout.as_secs(),
+            new_key_file.display()
+        )
+    })??;
+    let (_, summary) = get_validator_summary(&sui_client, sui_address)
+        .await?
+        .ok_or_else(|| anyhow!("{sui_address} is not a Validator."))?;
+    if summary.protocol_pubkey_bytes.as_slice() != new_protocol_key.as_bytes() {
+        bail!(
+            "The new protocol key is not active in epoch {}, the new key is kept in {}",
+            starting_epoch + 1,
+            new_key_file.display()
+        );
+    }
+
+    let archived_key_path = path_with_suffix(&file, &format!("epoch-{starting_epoch}"));
+    fs::rename(&file, &archived_key_path)?;
+    fs::rename(&new_key_file, &file)?;
+    println!(
+        "Archived the previous protocol key in {:?}.",
+        archived_key_path
+    );
+    println!(
+        "Restart the validator to use the new protocol key in {:?}.",
+        file
+    );
+    Ok(SuiValidatorCommandResponse::RotateProtocolKey {
+        new_protocol_key,
+        activation_epoch: starting_epoch + 1,
+        archived_key_path,
+        response,
+    })
+}
+
+/// Returns `path` with `.<suffix>` appended to its file name.
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(format!(".{suffix}"));
+    PathBuf::from(path)
+}
+
 async fn check_status(
     context: &mut WalletContext,
     allowed_status: HashSet<ValidatorStatus>,
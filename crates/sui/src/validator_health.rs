@@ -0,0 +1,302 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::bail;
+use colored::Colorize;
+use prometheus_parse::{Sample, Scrape, Value};
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+#[path = "unit_tests/validator_health_tests.rs"]
+#[cfg(test)]
+mod validator_health_tests;
+
+/// Metrics of the highest consensus round of the validator, for Mysticeti and Narwhal.
+const ROUND_METRICS: &[&str] = &["consensus_highest_accepted_round", "current_round"];
+/// Metrics of the last committed consensus round of the validator, for Mysticeti and Narwhal.
+const COMMITTED_ROUND_METRICS: &[&str] = &[
+    "consensus_last_committed_leader_round",
+    "last_committed_round",
+];
+const HIGHEST_SYNCED_CHECKPOINT_METRIC: &str = "highest_synced_checkpoint";
+const LAST_EXECUTED_CHECKPOINT_METRIC: &str = "last_executed_checkpoint";
+/// Gauges of the depth of the queues of the validator, by queue.
+const QUEUE_METRICS: &[(&str, &str)] = &[
+    ("consensus_submissions", "sequencing_in_flight_submissions"),
+    (
+        "narwhal_proposer_batches",
+        "num_of_pending_batches_in_proposer",
+    ),
+    (
+        "pending_certificates",
+        "transaction_manager_num_pending_certificates",
+    ),
+    (
+        "executing_certificates",
+        "transaction_manager_num_executing_certificates",
+    ),
+];
+/// The Mysticeti core queue depth is the difference between these counters.
+const CORE_ENQUEUED_METRIC: &str = "consensus_core_lock_enqueued";
+const CORE_DEQUEUED_METRIC: &str = "consensus_core_lock_dequeued";
+
+/// Checkpoint execution lag, in checkpoints, above which the validator is unhealthy.
+const MAX_CHECKPOINT_LAG: u64 = 100;
+/// Rate of increase of an error counter, per second, above which it is an error spike.
+const ERROR_SPIKE_RATE: f64 = 1.0;
+
+/// Consolidated health of a running validator, from two samples of its metrics some interval
+/// apart and from its admin interface.
+#[derive(Serialize)]
+pub struct ValidatorHealth {
+    pub healthy: bool,
+    /// Why the validator is unhealthy.
+    pub problems: Vec<String>,
+    pub interval_secs: u64,
+    pub consensus_round: Option<u64>,
+    /// How many rounds consensus advanced during the interval.
+    pub consensus_rounds_advanced: Option<u64>,
+    pub last_committed_round: Option<u64>,
+    /// Why consensus is stalled, as reported by the admin interface.
+    pub consensus_stall: Option<String>,
+    pub highest_synced_checkpoint: Option<u64>,
+    pub last_executed_checkpoint: Option<u64>,
+    pub checkpoint_lag: Option<u64>,
+    pub queue_depths: BTreeMap<String, u64>,
+    /// The increase during the interval of the error counters that spiked, by series.
+    pub error_spikes: BTreeMap<String, u64>,
+}
+
+/// Samples the metrics of a validator twice, `interval` apart, and queries its admin interface
+/// for whether consensus is stalled, to report its health.
+pub async fn check_validator_health(
+    metrics_url: &str,
+    admin_url: &str,
+    interval: Duration,
+) -> anyhow::Result<ValidatorHealth> {
+    let client = reqwest::Client::new();
+    let before = scrape(&client, metrics_url).await?;
+    tokio::time::sleep(interval).await;
+    let after = scrape(&client, metrics_url).await?;
+    let consensus_stall = consensus_stall(&client, admin_url).await;
+    Ok(ValidatorHealth::new(
+        &before,
+        &after,
+        consensus_stall,
+        interval,
+    ))
+}
+
+async fn scrape(client: &reqwest::Client, metrics_url: &str) -> anyhow::Result<Metrics> {
+    let text = client
+        .get(metrics_url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| anyhow::anyhow!("Cannot fetch metrics from {metrics_url}: {e}"))?
+        .text()
+        .await?;
+    Metrics::parse(&text)
+}
+
+/// Returns why consensus is stalled, or `None` if it is making progress.
+async fn consensus_stall(
+    client: &reqwest::Client,
+    admin_url: &str,
+) -> anyhow::Result<Option<String>> {
+    let url = format!("{}/consensus-health", admin_url.trim_end_matches('/'));
+    let response = client.get(&url).send().await?;
+    match response.status() {
+        StatusCode::OK => Ok(None),
+        StatusCode::SERVICE_UNAVAILABLE => Ok(Some(response.text().await?.trim().to_string())),
+        status => bail!("Unexpected status {status} from {url}"),
+    }
+}
+
+struct Metrics(Vec<Sample>);
+
+impl Metrics {
+    fn parse(text: &str) -> anyhow::Result<Self> {
+        let scrape = Scrape::parse(text.lines().map(|line| Ok(line.to_string())))?;
+        Ok(Self(scrape.samples))
+    }
+
+    /// Returns the highest value across the series of the first of `names` that is exported.
+    fn max(&self, names: &[&str]) -> Option<f64> {
+        names.iter().find_map(|name| {
+            self.0
+                .iter()
+                .filter(|sample| sample.metric == *name)
+                .filter_map(|sample| match sample.value {
+                    Value::Counter(value) | Value::Gauge(value) | Value::Untyped(value) => {
+                        Some(value)
+                    }
+                    _ => None,
+                })
+                .reduce(f64::max)
+        })
+    }
+
+    fn queue_depths(&self) -> BTreeMap<String, u64> {
+        let mut depths: BTreeMap<_, _> = QUEUE_METRICS
+            .iter()
+            .filter_map(|(queue, metric)| Some((queue.to_string(), self.max(&[metric])? as u64)))
+            .collect();
+        if let (Some(enqueued), Some(dequeued)) = (
+            self.max(&[CORE_ENQUEUED_METRIC]),
+            self.max(&[CORE_DEQUEUED_METRIC]),
+        ) {
+            let depth = (enqueued - dequeued).max(0.0) as u64;
+            depths.insert("consensus_core_requests".to_string(), depth);
+        }
+        depths
+    }
+
+    /// Returns the values of the counters of errors and failures, by series.
+    fn error_counters(&self) -> BTreeMap<String, f64> {
+        self.0
+            .iter()
+            .filter(|sample| sample.metric.contains("error") || sample.metric.contains("failure"))
+            .filter_map(|sample| match sample.value {
+                Value::Counter(value) => Some((series(sample), value)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Returns the name of the series of `sample`, e.g. `consensus_errors{error="x",peer="y"}`.
+fn series(sample: &Sample) -> String {
+    let labels: BTreeMap<_, _> = sample.labels.iter().collect();
+    if labels.is_empty() {
+        return sample.metric.clone();
+    }
+    let labels: Vec<_> = labels
+        .into_iter()
+        .map(|(name, value)| format!("{name}=\"{value}\""))
+        .collect();
+    format!("{}{{{}}}", sample.metric, labels.join(","))
+}
+
+impl ValidatorHealth {
+    fn new(
+        before: &Metrics,
+        after: &Metrics,
+        consensus_stall: anyhow::Result<Option<String>>,
+        interval: Duration,
+    ) -> Self {
+        let interval_secs = interval.as_secs();
+        let mut problems = vec![];
+
+        let consensus_round = after.max(ROUND_METRICS).map(|round| round as u64);
+        let consensus_rounds_advanced = consensus_round
+            .zip(before.max(ROUND_METRICS))
+            .map(|(round, previous)| round.saturating_sub(previous as u64));
+        match consensus_rounds_advanced {
+            None => problems.push("No consensus round is reported".to_string()),
+            Some(0) => problems.push(format!(
+                "Consensus round did not advance in {interval_secs}s"
+            )),
+            Some(_) => {}
+        }
+        let consensus_stall = consensus_stall.unwrap_or_else(|e| {
+            problems.push(format!("Cannot check whether consensus is stalled: {e}"));
+            None
+        });
+        if let Some(reason) = &consensus_stall {
+            problems.push(reason.clone());
+        }
+
+        let highest_synced_checkpoint = after
+            .max(&[HIGHEST_SYNCED_CHECKPOINT_METRIC])
+            .map(|sequence_number| sequence_number as u64);
+        let last_executed_checkpoint = after
+            .max(&[LAST_EXECUTED_CHECKPOINT_METRIC])
+            .map(|sequence_number| sequence_number as u64);
+        let checkpoint_lag = highest_synced_checkpoint
+            .zip(last_executed_checkpoint)
+            .map(|(synced, executed)| synced.saturating_sub(executed));
+        if let Some(lag) = checkpoint_lag.filter(|lag| *lag > MAX_CHECKPOINT_LAG) {
+            problems.push(format!("Checkpoint execution is {lag} checkpoints behind"));
+        }
+
+        let last_committed_round = after.max(COMMITTED_ROUND_METRICS).map(|round| round as u64);
+
+        let previous_errors = before.error_counters();
+        let error_spikes: BTreeMap<_, _> = after
+            .error_counters()
+            .into_iter()
+            .filter_map(|(series, value)| {
+                let increase = value - previous_errors.get(&series).copied().unwrap_or(0.0);
+                (increase > ERROR_SPIKE_RATE * interval.as_secs_f64())
+                    .then_some((series, increase as u64))
+            })
+            .collect();
+        for (series, increase) in &error_spikes {
+            problems.push(format!(
+                "{series} increased by {increase} in {interval_secs}s"
+            ));
+        }
+
+        Self {
+            healthy: problems.is_empty(),
+            problems,
+            interval_secs,
+            consensus_round,
+            consensus_rounds_advanced,
+            last_committed_round,
+            consensus_stall,
+            highest_synced_checkpoint,
+            last_executed_checkpoint,
+            checkpoint_lag,
+            queue_depths: after.queue_depths(),
+            error_spikes,
+        }
+    }
+}
+
+impl Display for ValidatorHealth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fn or_unknown(value: Option<u64>) -> String {
+            value.map_or_else(|| "unknown".to_string(), |value| value.to_string())
+        }
+
+        let status = if self.healthy {
+            "healthy".green()
+        } else {
+            "unhealthy".red()
+        };
+        writeln!(f, "Validator is {status}")?;
+        writeln!(
+            f,
+            "Consensus round: {} ({} rounds in {}s), last committed round: {}",
+            or_unknown(self.consensus_round),
+            or_unknown(self.consensus_rounds_advanced),
+            self.interval_secs,
+            or_unknown(self.last_committed_round),
+        )?;
+        writeln!(
+            f,
+            "Checkpoints: highest synced {}, last executed {}, lag {}",
+            or_unknown(self.highest_synced_checkpoint),
+            or_unknown(self.last_executed_checkpoint),
+            or_unknown(self.checkpoint_lag),
+        )?;
+        writeln!(f, "Queue depths:")?;
+        for (queue, depth) in &self.queue_depths {
+            writeln!(f, "  {queue}: {depth}")?;
+        }
+        if !self.problems.is_empty() {
+            writeln!(f, "Problems:")?;
+            for problem in &self.problems {
+                writeln!(f, "  {problem}")?;
+            }
+        }
+        Ok(())
+    }
+}
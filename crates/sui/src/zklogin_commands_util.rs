@@ -1,33 +1,39 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use fastcrypto::ed25519::Ed25519KeyPair;
 use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto::jwt_utils::parse_and_validate_jwt;
-use fastcrypto::traits::{EncodeDecodeBase64, KeyPair};
+use fastcrypto::traits::{EncodeDecodeBase64, KeyPair, ToFromBytes};
 use fastcrypto_zkp::bn254::utils::get_proof;
-use fastcrypto_zkp::bn254::utils::{gen_address_seed, get_salt, get_zk_login_address};
-use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use fastcrypto_zkp::bn254::utils::{gen_address_seed, get_nonce, get_salt, get_zk_login_address};
+use fastcrypto_zkp::bn254::zk_login::{fetch_jwks, JwkId, OIDCProvider, ZkLoginInputs, JWK};
+use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
+use im::hashmap::HashMap as ImHashMap;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use regex::Regex;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use shared_crypto::intent::Intent;
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
+use std::collections::BTreeMap;
+use std::fs;
 use std::io;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
 use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_sdk::SuiClientBuilder;
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
-use sui_types::crypto::{PublicKey, SuiKeyPair};
+use sui_types::crypto::{PublicKey, SuiKeyPair, SuiSignature};
 use sui_types::multisig::{MultiSig, MultiSigPublicKey};
-use sui_types::signature::GenericSignature;
-use sui_types::transaction::Transaction;
+use sui_types::signature::{AuthenticatorTrait, GenericSignature, VerifyParams};
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
 use sui_types::zk_login_authenticator::ZkLoginAuthenticator;
 
 /// Read a line from stdin, parse the id_token field and return.
@@ -195,3 +201,398 @@ fn get_config(network: &str) -> (&str, &str) {
         _ => panic!("Invalid network"),
     }
 }
+
+/// File in the Sui config directory that JWKs fetched from OIDC providers are cached in.
+pub const ZKLOGIN_JWK_CACHE_FILE: &str = "zklogin_jwks.json";
+/// How long JWKs fetched from an OIDC provider are cached for.
+const JWK_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Inputs of the local verification of a zkLogin signature, see [verify_zk_login_flow].
+pub struct ZkLoginFlowInputs {
+    /// The Base64 of the serialized zkLogin signature.
+    pub sig: String,
+    /// The Base64 of the ephemeral public key, as `flag || pk`.
+    pub ephemeral_pubkey: String,
+    /// The JWT the proof of the signature was generated for.
+    pub jwt: String,
+    pub jwt_randomness: Option<String>,
+    pub salt: Option<String>,
+    /// The Base64 of the BCS encoded TransactionData or PersonalMessage that is signed.
+    pub bytes: Option<String>,
+    pub intent_scope: IntentScope,
+    pub curr_epoch: Option<EpochId>,
+    pub env: ZkLoginEnv,
+    /// File the JWKs fetched from OIDC providers are cached in.
+    pub jwk_cache: PathBuf,
+    /// Fetch the JWKs of the provider even if they are cached.
+    pub refresh_jwks: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkLoginVerifyReport {
+    pub verified: bool,
+    pub address: Option<SuiAddress>,
+    pub stages: Vec<ZkLoginVerifyStage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkLoginVerifyStage {
+    pub stage: String,
+    pub status: ZkLoginVerifyStatus,
+    pub details: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ZkLoginVerifyStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl ZkLoginVerifyReport {
+    /// Records the result of `stage`, and returns whether it passed.
+    fn record(&mut self, stage: &str, result: anyhow::Result<String>) -> bool {
+        let (status, details) = match result {
+            Ok(details) => (ZkLoginVerifyStatus::Passed, details),
+            Err(e) => (ZkLoginVerifyStatus::Failed, e.to_string()),
+        };
+        let passed = status == ZkLoginVerifyStatus::Passed;
+        self.stages.push(ZkLoginVerifyStage {
+            stage: stage.to_string(),
+            status,
+            details,
+        });
+        passed
+    }
+
+    fn skip(&mut self, stage: &str, reason: &str) {
+        self.stages.push(ZkLoginVerifyStage {
+            stage: stage.to_string(),
+            status: ZkLoginVerifyStatus::Skipped,
+            details: reason.to_string(),
+        });
+    }
+
+    fn no_failures(&self) -> bool {
+        self.stages
+            .iter()
+            .all(|stage| stage.status != ZkLoginVerifyStatus::Failed)
+    }
+}
+
+/// The claims of a JWT that the zkLogin flow depends on.
+struct JwtClaims {
+    sub: String,
+    aud: String,
+    iss: String,
+    kid: String,
+    nonce: String,
+}
+
+/// JWKs fetched from OIDC providers, by issuer.
+#[derive(Serialize, Deserialize, Default)]
+struct JwkCache(BTreeMap<String, CachedJwks>);
+
+#[derive(Serialize, Deserialize)]
+struct CachedJwks {
+    fetched_at_ms: u64,
+    jwks: Vec<(JwkId, JWK)>,
+}
+
+/// Verifies the complete zkLogin flow of a signature locally, one stage at a time, so that the
+/// stage a signature fails at is reported rather than a single error: the signature, the
+/// ephemeral key, the JWT, the nonce, the address derivation, the max epoch, the JWK of the
+/// provider, and finally the proof and the ephemeral signature. Stages whose inputs are missing,
+/// or depend on a stage that failed, are skipped.
+pub async fn verify_zk_login_flow(inputs: ZkLoginFlowInputs) -> ZkLoginVerifyReport {
+    let mut report = ZkLoginVerifyReport {
+        verified: false,
+        address: None,
+        stages: vec![],
+    };
+
+    let signature = Base64::decode(&inputs.sig)
+        .map_err(|e| anyhow!("Invalid base64 sig: {:?}", e))
+        .and_then(|bytes| Ok(GenericSignature::from_bytes(&bytes)?));
+    let zk = match signature {
+        Ok(GenericSignature::ZkLoginAuthenticator(zk)) => zk,
+        Ok(_) => {
+            report.record("signature", Err(anyhow!("Not a zkLogin signature")));
+            return report;
+        }
+        Err(e) => {
+            report.record("signature", Err(anyhow!("Invalid signature: {e}")));
+            return report;
+        }
+    };
+    report.record(
+        "signature",
+        Ok(format!(
+            "issuer {}, max epoch {}",
+            zk.get_iss(),
+            zk.get_max_epoch()
+        )),
+    );
+
+    let eph_pk_bytes = PublicKey::decode_base64(&inputs.ephemeral_pubkey).map(|pk| {
+        let mut bytes = vec![pk.flag()];
+        bytes.extend(pk.as_ref());
+        bytes
+    });
+    let ephemeral_key = match &eph_pk_bytes {
+        Ok(eph_pk_bytes) => {
+            let user_signature = zk.get_user_signature();
+            let mut signer_bytes = vec![user_signature.scheme().flag()];
+            signer_bytes.extend(user_signature.public_key_bytes());
+            if &signer_bytes == eph_pk_bytes {
+                Ok("the signature is made with the ephemeral key".to_string())
+            } else {
+                Err(anyhow!(
+                    "the signature is made with {}, not with the ephemeral key",
+                    Base64::encode(&signer_bytes)
+                ))
+            }
+        }
+        Err(e) => Err(anyhow!("Invalid ephemeral public key: {e}")),
+    };
+    report.record("ephemeral key", ephemeral_key);
+
+    let claims = parse_jwt_claims(&inputs.jwt).and_then(|claims| {
+        if claims.iss != zk.get_iss() {
+            bail!(
+                "JWT issuer {} is not the signature issuer {}",
+                claims.iss,
+                zk.get_iss()
+            );
+        }
+        if claims.kid != zk.inputs.get_kid() {
+            bail!(
+                "JWT key ID {} is not the signature key ID {}",
+                claims.kid,
+                zk.inputs.get_kid()
+            );
+        }
+        Ok(claims)
+    });
+    let claims = match claims {
+        Ok(claims) => {
+            let details = format!("sub {}, aud {}", claims.sub, claims.aud);
+            report.record("jwt", Ok(details));
+            Some(claims)
+        }
+        Err(e) => {
+            report.record("jwt", Err(e));
+            None
+        }
+    };
+
+    match (&claims, &eph_pk_bytes, &inputs.jwt_randomness) {
+        (Some(claims), Ok(eph_pk_bytes), Some(jwt_randomness)) => {
+            let nonce = get_nonce(eph_pk_bytes, zk.get_max_epoch(), jwt_randomness)
+                .map_err(|e| anyhow!("Cannot compute the nonce: {e}"))
+                .and_then(|nonce| {
+                    if nonce == claims.nonce {
+                        Ok(format!("nonce {nonce}"))
+                    } else {
+                        Err(anyhow!(
+                            "JWT nonce {} is not the nonce {nonce} of the ephemeral key, max \
+                            epoch and JWT randomness",
+                            claims.nonce
+                        ))
+                    }
+                });
+            report.record("nonce", nonce);
+        }
+        (_, _, None) => report.skip("nonce", "no JWT randomness given"),
+        _ => report.skip("nonce", "the JWT or the ephemeral key is invalid"),
+    }
+
+    let address = verify_address(&zk, claims.as_ref(), inputs.salt.as_deref());
+    if let Ok((address, _)) = &address {
+        report.address = Some(*address);
+    }
+    report.record("address", address.map(|(_, details)| details));
+
+    match inputs.curr_epoch {
+        Some(epoch) => {
+            let max_epoch = zk.get_max_epoch();
+            let result = zk
+                .verify_user_authenticator_epoch(epoch)
+                .map(|()| format!("epoch {epoch} is not after max epoch {max_epoch}"))
+                .map_err(|e| anyhow!("{e}"));
+            report.record("max epoch", result);
+        }
+        None => report.skip("max epoch", "no current epoch given"),
+    }
+
+    let jwk_id = JwkId::new(zk.get_iss().to_string(), zk.inputs.get_kid().to_string());
+    let jwks = get_jwks(&zk, &inputs.jwk_cache, inputs.refresh_jwks)
+        .await
+        .and_then(|(jwks, source)| {
+            let jwks: ImHashMap<JwkId, JWK> = jwks.into_iter().collect();
+            if !jwks.contains_key(&jwk_id) {
+                bail!(
+                    "the {source} JWKs of {} have no key with ID {}",
+                    jwk_id.iss,
+                    jwk_id.kid
+                );
+            }
+            Ok((jwks, source))
+        });
+    let jwks = match jwks {
+        Ok((jwks, source)) => {
+            report.record("jwk", Ok(format!("{source} JWK {}", jwk_id.kid)));
+            Some(jwks)
+        }
+        Err(e) => {
+            report.record("jwk", Err(e));
+            None
+        }
+    };
+
+    match (jwks, &inputs.bytes) {
+        (Some(jwks), Some(bytes)) => {
+            let verify_params = VerifyParams::new(jwks, vec![], inputs.env, true, true);
+            let proof = verify_proof_and_signature(&zk, bytes, inputs.intent_scope, &verify_params);
+            let passed = report.record("proof and signature", proof);
+            report.verified = passed && report.no_failures();
+        }
+        (None, _) => report.skip("proof and signature", "no JWK to verify the proof with"),
+        (_, None) => report.skip("proof and signature", "no signed bytes given"),
+    }
+    report
+}
+
+/// Parses the claims of `jwt` the zkLogin flow depends on.
+fn parse_jwt_claims(jwt: &str) -> anyhow::Result<JwtClaims> {
+    let (sub, aud) = parse_and_validate_jwt(jwt).map_err(|e| anyhow!("Invalid JWT: {e}"))?;
+    let parts: Vec<&str> = jwt.split('.').collect();
+    let [header, payload, _signature] = parts.as_slice() else {
+        bail!("Invalid JWT: expected 3 parts, found {}", parts.len());
+    };
+    let header = decode_jwt_part(header)?;
+    let payload = decode_jwt_part(payload)?;
+    let claim = |json: &serde_json::Value, name: &str| {
+        json[name]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Invalid JWT: no `{name}` claim"))
+    };
+    Ok(JwtClaims {
+        sub,
+        aud,
+        iss: claim(&payload, "iss")?,
+        kid: claim(&header, "kid")?,
+        nonce: claim(&payload, "nonce")?,
+    })
+}
+
+/// Decodes a base64url encoded part of a JWT as JSON.
+fn decode_jwt_part(part: &str) -> anyhow::Result<serde_json::Value> {
+    let mut base64 = part.replace('-', "+").replace('_', "/");
+    while base64.len() % 4 != 0 {
+        base64.push('=');
+    }
+    let bytes = Base64::decode(&base64).map_err(|e| anyhow!("Invalid JWT encoding: {e}"))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Derives the address of the signature and, given the claims of the JWT and the user salt,
+/// verifies that the address seed of the signature is derived from them.
+fn verify_address(
+    zk: &ZkLoginAuthenticator,
+    claims: Option<&JwtClaims>,
+    salt: Option<&str>,
+) -> anyhow::Result<(SuiAddress, String)> {
+    let address = SuiAddress::try_from_unpadded(&zk.inputs)?;
+    let (Some(claims), Some(salt)) = (claims, salt) else {
+        return Ok((address, format!("{address}, address seed not checked")));
+    };
+    let address_seed = gen_address_seed(salt, "sub", &claims.sub, &claims.aud)?;
+    let inputs = serde_json::to_value(&zk.inputs)?;
+    let signature_seed = inputs["addressSeed"].as_str().unwrap_or_default();
+    if address_seed != signature_seed {
+        bail!(
+            "address seed {address_seed} of the salt and the JWT is not the signature address \
+            seed {signature_seed}"
+        );
+    }
+    Ok((
+        address,
+        format!("{address}, from address seed {address_seed}"),
+    ))
+}
+
+/// Returns the JWKs of the provider of the signature, and whether they are cached or fetched.
+async fn get_jwks(
+    zk: &ZkLoginAuthenticator,
+    cache_path: &Path,
+    refresh: bool,
+) -> anyhow::Result<(Vec<(JwkId, JWK)>, &'static str)> {
+    let iss = zk.get_iss();
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+    let mut cache: JwkCache = fs::read(cache_path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default();
+    if let Some(cached) = cache.0.get(iss) {
+        let age_ms = now_ms.saturating_sub(cached.fetched_at_ms);
+        if !refresh && age_ms < JWK_CACHE_TTL.as_millis() as u64 {
+            return Ok((cached.jwks.clone(), "cached"));
+        }
+    }
+
+    let provider = OIDCProvider::from_iss(iss).map_err(|_| anyhow!("Unknown provider {iss}"))?;
+    let jwks = fetch_jwks(&provider, &Client::new())
+        .await
+        .map_err(|e| anyhow!("Cannot fetch the JWKs of {iss}: {e}"))?;
+    cache.0.insert(
+        iss.to_string(),
+        CachedJwks {
+            fetched_at_ms: now_ms,
+            jwks: jwks.clone(),
+        },
+    );
+    if let Err(e) = fs::write(cache_path, serde_json::to_vec_pretty(&cache)?) {
+        eprintln!("Cannot cache the JWKs in {}: {e}", cache_path.display());
+    }
+    Ok((jwks, "fetched"))
+}
+
+/// Verifies the proof and the ephemeral signature of `zk` over the intent message of `bytes`.
+fn verify_proof_and_signature(
+    zk: &ZkLoginAuthenticator,
+    bytes: &str,
+    intent_scope: IntentScope,
+    verify_params: &VerifyParams,
+) -> anyhow::Result<String> {
+    let bytes = Base64::decode(bytes).map_err(|e| anyhow!("Invalid base64 bytes: {e}"))?;
+    match intent_scope {
+        IntentScope::TransactionData => {
+            let tx_data: TransactionData = bcs::from_bytes(&bytes)?;
+            let sender = tx_data.execution_parts().1;
+            zk.verify_authenticator(
+                &IntentMessage::new(Intent::sui_transaction(), tx_data),
+                sender,
+                None,
+                verify_params,
+            )?;
+            Ok(format!("transaction signed by {sender}"))
+        }
+        IntentScope::PersonalMessage => {
+            let data: PersonalMessage = bcs::from_bytes(&bytes)?;
+            let author: SuiAddress = zk.try_into()?;
+            zk.verify_authenticator(
+                &IntentMessage::new(Intent::personal_message(), data),
+                author,
+                None,
+                verify_params,
+            )?;
+            Ok(format!("personal message signed by {author}"))
+        }
+        _ => bail!("Invalid intent scope"),
+    }
+}
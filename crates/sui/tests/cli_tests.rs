@@ -20,7 +20,7 @@ use sui_types::transaction::{
 };
 use tokio::time::sleep;
 
-use sui::client_commands::SwitchResponse;
+use sui::client_commands::{PayBatchStatus, SwitchResponse};
 use sui::{
     client_commands::{SuiClientCommandResult, SuiClientCommands},
     sui_commands::SuiCommand,
@@ -35,7 +35,7 @@ use sui_json_rpc_types::{
     SuiObjectResponseQuery, SuiTransactionBlockDataAPI, SuiTransactionBlockEffects,
     SuiTransactionBlockEffectsAPI,
 };
-use sui_keys::keystore::AccountKeystore;
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
 use sui_macros::sim_test;
 use sui_move_build::{BuildConfig, SuiPackageHooks};
 use sui_sdk::sui_client_config::SuiClientConfig;
@@ -62,6 +62,10 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
     let start = SuiCommand::Start {
         config: Some(config),
         no_full_node: false,
+        with_faucet: None,
+        faucet_amount: 200_000_000_000,
+        faucet_num_coins: 5,
+        faucet_requests_per_second: 10,
     }
     .execute()
     .await;
@@ -123,6 +127,39 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_genesis_with_faucet() -> Result<(), anyhow::Error> {
+    let temp_dir = tempfile::tempdir()?;
+    let working_dir = temp_dir.path();
+
+    SuiCommand::Genesis {
+        working_dir: Some(working_dir.to_path_buf()),
+        write_config: None,
+        force: false,
+        from_config: None,
+        epoch_duration_ms: None,
+        benchmark_ips: None,
+        with_faucet: true,
+    }
+    .execute()
+    .await?;
+
+    // The faucet account is kept out of the client's keystore, in a keystore of its own.
+    let faucet_keystore =
+        FileBasedKeystore::new(&working_dir.join("faucet").join(SUI_KEYSTORE_FILENAME))?;
+    assert_eq!(1, faucet_keystore.addresses().len());
+    let wallet_conf =
+        PersistedConfig::<SuiClientConfig>::read(&working_dir.join(SUI_CLIENT_CONFIG))?;
+    assert_eq!(5, wallet_conf.keystore.addresses().len());
+    assert!(!wallet_conf
+        .keystore
+        .addresses()
+        .contains(&faucet_keystore.addresses()[0]));
+
+    temp_dir.close()?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_addresses_command() -> Result<(), anyhow::Error> {
     let test_cluster = TestClusterBuilder::new().build().await;
@@ -316,6 +353,65 @@ async fn test_ptb_publish_and_complex_arg_resolution() -> Result<(), anyhow::Err
     Ok(())
 }
 
+#[sim_test]
+async fn test_ptb_preview_and_confirm() -> Result<(), anyhow::Error> {
+    use sui::client_ptb::ptb::PTB;
+
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let balance = client.coin_read_api().get_balance(address1, None).await?;
+
+    let ptb_args = |amount: u64, flags: &str| {
+        shlex::split(&format!(
+            "--split-coins gas [{amount}] --assign coin --transfer-objects [coin] @{address1} \
+             --gas-budget 100000000 {flags}"
+        ))
+        .unwrap()
+    };
+
+    // Previewing a PTB never executes it.
+    PTB {
+        args: ptb_args(1000, "--preview"),
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(
+        client.coin_read_api().get_balance(address1, None).await?,
+        balance
+    );
+
+    // The dry run shows the changes of the PTB, which is only executed once confirmed.
+    let tx_data = PTB::transaction_data(ptb_args(1000, ""), context).await?;
+    for (answer, confirmed) in [("y\n", true), ("Y\n", true), ("n\n", false), ("", false)] {
+        let mut output = vec![];
+        let result =
+            PTB::confirm_dry_run(context, &tx_data, &mut answer.as_bytes(), &mut output).await?;
+        assert_eq!(result, confirmed, "Answer {answer:?}");
+
+        let output = String::from_utf8(output)?;
+        assert!(output.contains("Dry Run Object Changes"), "{output}");
+        assert!(output.contains("+ created"), "{output}");
+        assert!(output.contains("Dry Run Balance Changes"), "{output}");
+        assert!(output.contains(&address1.to_string()), "{output}");
+        assert!(output.contains("+1000"), "{output}");
+        assert!(output.contains("Do you want to execute this transaction"));
+    }
+
+    // A PTB whose dry run fails is not executed, without asking for confirmation.
+    let tx_data = PTB::transaction_data(ptb_args(u64::MAX, ""), context).await?;
+    let mut output = vec![];
+    let confirmed =
+        PTB::confirm_dry_run(context, &tx_data, &mut "y\n".as_bytes(), &mut output).await?;
+    assert!(!confirmed);
+    let output = String::from_utf8(output)?;
+    assert!(output.contains("it will not be executed"), "{output}");
+    assert!(!output.contains("Do you want to execute this transaction"));
+
+    Ok(())
+}
+
 // fixing issue https://github.com/MystenLabs/sui/issues/6546
 #[tokio::test]
 async fn test_regression_6546() -> Result<(), anyhow::Error> {
@@ -2633,6 +2729,193 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_profile_tx() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new(),
+            )),
+            None,
+            None,
+        )
+        .await?
+        .data;
+    let coin = object_refs.get(1).unwrap().object().unwrap().object_id;
+
+    // Splits the coin and transfers the new coin, in two commands.
+    let resp = SuiClientCommands::TransferSui {
+        to: KeyIdentity::Address(address1),
+        sui_coin_object_id: coin,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        amount: Some(1),
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let digest = resp.tx_block_response().unwrap().digest;
+
+    let resp = SuiClientCommands::ProfileTx {
+        tx_digest: Some(digest),
+        tx_bytes: None,
+        ptb: None,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::ProfileTx(profile) = resp else {
+        panic!("Profiling a transaction should return a gas profile");
+    };
+    assert!(profile.status.is_ok());
+    assert_eq!(profile.commands.len(), 2);
+    assert!(profile.functions.is_empty());
+    assert_eq!(profile.storage_effects.created, 1);
+    // The split coin cannot be left unused, so the transaction truncated after the split fails,
+    // and neither command can be profiled on its own.
+    assert!(profile.commands.iter().all(|c| c.cost.is_none()));
+
+    // Merges two coins and transfers the result, in two commands that can each be profiled.
+    let coin_id = |i: usize| object_refs.get(i).unwrap().object().unwrap().object_id;
+    let ptb = format!(
+        "--merge-coins @{} [@{}] --transfer-objects [@{}] @{address1} --gas-coin @{} --gas-budget {}",
+        coin_id(2),
+        coin_id(3),
+        coin_id(2),
+        coin_id(0),
+        rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    );
+    let resp = SuiClientCommands::ProfileTx {
+        tx_digest: None,
+        tx_bytes: None,
+        ptb: Some(shlex::split(&ptb).unwrap()),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::ProfileTx(profile) = resp else {
+        panic!("Profiling a PTB should return a gas profile");
+    };
+    assert!(profile.status.is_ok());
+    assert_eq!(profile.commands.len(), 2);
+    assert_eq!(profile.storage_effects.deleted, 1);
+    assert_eq!(
+        profile
+            .commands
+            .iter()
+            .map(|c| c.cost.unwrap().computation_cost)
+            .sum::<i64>(),
+        profile.gas_cost.computation_cost as i64
+    );
+    Ok(())
+}
+
+#[sim_test]
+async fn test_pay_batch() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let alias1 = context
+        .config
+        .keystore
+        .get_alias_by_address(&address1)
+        .unwrap();
+
+    let temp_dir = tempfile::tempdir()?;
+    let csv = temp_dir.path().join("payments.csv");
+    std::fs::write(
+        &csv,
+        format!("recipient,amount\n{address1},1\n# comment\n{alias1},2\n{address1},3\n"),
+    )?;
+
+    let resp = SuiClientCommands::PayBatch {
+        csv: csv.clone(),
+        batch_size: 2,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        parallel: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::PayBatch(receipts) = resp else {
+        panic!("Pay batch should return receipts");
+    };
+    assert_eq!(receipts.len(), 2);
+    assert!(receipts
+        .iter()
+        .all(|r| r.status == PayBatchStatus::Success && r.digest.is_some()));
+    assert_eq!(receipts[0].first_line, 2);
+    assert_eq!((receipts[0].recipients, receipts[0].amount), (2, 3));
+    assert_eq!((receipts[1].recipients, receipts[1].amount), (1, 3));
+
+    let resp = SuiClientCommands::PayBatch {
+        csv,
+        batch_size: 1,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        parallel: true,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::PayBatch(receipts) = resp else {
+        panic!("Pay batch should return receipts");
+    };
+    assert_eq!(receipts.len(), 3);
+    assert!(receipts.iter().all(|r| r.status == PayBatchStatus::Success));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_e2e_test() -> Result<(), anyhow::Error> {
+    move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_publish");
+    let temp_dir = tempfile::tempdir()?;
+    let script = temp_dir.path().join("e2e.sui");
+    std::fs::write(
+        &script,
+        "echo published $SUI_E2E_PACKAGE_ID\nclient objects\n",
+    )?;
+    let artifacts_dir = temp_dir.path().join("artifacts");
+
+    // The scripts pass, but the test command fails, so the artifacts are kept.
+    let result = SuiCommand::Move {
+        package_path: Some(package_path),
+        build_config: MoveBuildConfig::default(),
+        cmd: sui_move::Command::E2eTest(sui_move::e2e_test::E2eTest {
+            scripts: vec![script],
+            gas_budget: 1_000_000_000,
+            with_unpublished_dependencies: false,
+            artifacts_dir: Some(artifacts_dir.clone()),
+            command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "echo published $SUI_E2E_PACKAGE_ID; false".to_string(),
+            ],
+        }),
+    }
+    .execute()
+    .await;
+    let err = result.unwrap_err().to_string();
+    assert!(err.starts_with("Test command sh -c"), "{err}");
+    // The variables are passed to the test command, but not set in this process.
+    assert!(std::env::var("SUI_E2E_PACKAGE_ID").is_err());
+
+    assert!(artifacts_dir.join("publish.json").exists());
+    let log = std::fs::read_to_string(artifacts_dir.join("test-command.log"))?;
+    assert!(log.starts_with("published 0x"), "{log}");
+    assert!(artifacts_dir
+        .join("network")
+        .join(SUI_CLIENT_CONFIG)
+        .exists());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
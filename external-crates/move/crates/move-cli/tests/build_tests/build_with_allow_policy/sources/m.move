@@ -0,0 +1,11 @@
+module 0x42::m {
+#[allow(all)]
+public fun foo(x: u64): u64 {
+    1 + 1
+}
+
+#[allow(unused_variable)]
+public fun bar(y: u64): u64 {
+    2
+}
+}
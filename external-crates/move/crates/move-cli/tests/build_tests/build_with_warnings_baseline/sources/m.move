@@ -0,0 +1,9 @@
+module 0x42::m {
+public fun foo(x: u64): u64 {
+    1 + 1
+}
+
+public fun bar(y: u64): u64 {
+    2
+}
+}
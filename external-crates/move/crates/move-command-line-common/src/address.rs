@@ -5,6 +5,7 @@ use crate::parser::{parse_address_number, NumberFormat};
 use anyhow::anyhow;
 use move_core_types::account_address::AccountAddress;
 use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 use std::{fmt, hash::Hash};
 
 // Parsed Address, either a name or a numerical address
@@ -16,7 +17,7 @@ pub enum ParsedAddress {
 
 /// Numerical address represents non-named address values
 /// or the assigned value of a named address
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct NumericalAddress {
     /// the number for the address
     bytes: AccountAddress,
@@ -12,6 +12,7 @@ use move_core_types::{
     u256::{U256FromStrError, U256},
 };
 use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 use std::{fmt::Display, iter::Peekable, num::ParseIntError};
 
 const MAX_TYPE_DEPTH: u64 = 128;
@@ -369,7 +370,7 @@ pub fn parse_address_impl(tok: ValueToken, contents: &str) -> Result<ParsedAddre
     })
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, Serialize, Deserialize)]
 #[repr(u32)]
 /// Number format enum, the u32 value represents the base
 pub enum NumberFormat {
@@ -12,7 +12,8 @@ use crate::{
     diagnostics::Diagnostics,
     expansion::ast::{AbilitySet, Attributes, ModuleIdent, Mutability},
     hlir::ast::{self as H, BlockLabel, Label, Value, Value_, Var},
-    parser::ast::{ConstantName, FunctionName, StructName},
+    naming::ast::BuiltinTypeName_,
+    parser::ast::{BinOp, BinOp_, ConstantName, FunctionName, StructName},
     shared::{unique_map::UniqueMap, CompilationEnv},
     FullyCompiledProgram,
 };
@@ -493,17 +494,41 @@ fn constant_(
     );
 
     if blocks.len() != 1 {
-        context.env.add_diag(diag!(
-            BytecodeGeneration::UnfoldableConstant,
-            (full_loc, CANNOT_FOLD)
-        ));
+        // an 'if' whose condition could not be folded, e.g. as it overflows
+        let conds = blocks
+            .values()
+            .flatten()
+            .filter_map(|sp!(_, cmd_)| match cmd_ {
+                C::JumpIf { cond, .. } => Some(cond),
+                _ => None,
+            });
+        let num_errors = context.env.count_diags();
+        for cond in conds {
+            check_constant_value(context, cond)
+        }
+        if num_errors == context.env.count_diags() {
+            context.env.add_diag(diag!(
+                BytecodeGeneration::UnfoldableConstant,
+                (full_loc, CANNOT_FOLD)
+            ));
+        }
         return None;
     }
     let mut optimized_block = blocks.remove(&start).unwrap();
     let return_cmd = optimized_block.pop_back().unwrap();
+    let num_errors = context.env.count_diags();
+    let mut assigned = BTreeSet::new();
     for sp!(cloc, cmd_) in &optimized_block {
         let e = match cmd_ {
             C::IgnoreAndPop { exp, .. } => exp,
+            // the result of an 'if' is assigned to a local, which is eliminated once folded
+            C::Assign(_, lvalues, exp) => {
+                assigned.extend(lvalues.iter().filter_map(|sp!(_, lvalue_)| match lvalue_ {
+                    H::LValue_::Var { var, .. } => Some(*var),
+                    _ => None,
+                }));
+                exp
+            }
             _ => {
                 context.env.add_diag(diag!(
                     BytecodeGeneration::UnfoldableConstant,
@@ -519,7 +544,12 @@ fn constant_(
         C::Return { exp: e, .. } => e,
         _ => unreachable!(),
     };
-    check_constant_value(context, &result);
+    // the value of an assigned local was reported with its assignment
+    match &result.exp.value {
+        H::UnannotatedExp_::Move { var, .. } | H::UnannotatedExp_::Copy { var, .. }
+            if assigned.contains(var) && num_errors != context.env.count_diags() => {}
+        _ => check_constant_value(context, &result),
+    }
     Some(result)
 }
 
@@ -527,13 +557,88 @@ fn check_constant_value(context: &mut Context, e: &H::Exp) {
     use H::UnannotatedExp_ as E;
     match &e.exp.value {
         E::Value(_) => (),
-        _ => context.env.add_diag(diag!(
-            BytecodeGeneration::UnfoldableConstant,
-            (e.exp.loc, CANNOT_FOLD)
-        )),
+        _ => {
+            let diag = match unfoldable_operation(e) {
+                // point at the operation that failed, e.g. an overflow nested in the expression
+                Some((loc, msg)) => {
+                    let mut diag = diag!(BytecodeGeneration::UnfoldableConstant, (loc, msg));
+                    if loc != e.exp.loc {
+                        diag.add_secondary_label((e.exp.loc, CANNOT_FOLD));
+                    }
+                    diag
+                }
+                None => diag!(
+                    BytecodeGeneration::UnfoldableConstant,
+                    (e.exp.loc, CANNOT_FOLD)
+                ),
+            };
+            context.env.add_diag(diag)
+        }
     }
 }
 
+/// Finds an operation in an unfolded constant expression whose operands were all folded to values,
+/// meaning the operation itself would fail at runtime, and explains why: an arithmetic overflow, a
+/// division by zero, a shift by too many bits, or a cast that does not fit in the target type.
+#[growing_stack]
+fn unfoldable_operation(e: &H::Exp) -> Option<(Loc, String)> {
+    use H::UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::BinopExp(e1, op, e2) => match (&e1.exp.value, &e2.exp.value) {
+            (E::Value(sp!(_, v1)), E::Value(sp!(_, v2))) => {
+                binop_failure(op, v1, v2).map(|msg| (e.exp.loc, msg))
+            }
+            _ => unfoldable_operation(e1).or_else(|| unfoldable_operation(e2)),
+        },
+        E::Cast(inner, sp!(_, bt_)) => match &inner.exp.value {
+            E::Value(sp!(_, v)) => {
+                let (n, ty, _) = integer_value(v)?;
+                let msg = format!("Cast overflow: '{n}{ty}' does not fit in '{bt_}'");
+                Some((e.exp.loc, msg))
+            }
+            _ => unfoldable_operation(inner),
+        },
+        E::UnaryExp(_, e) | E::Freeze(e) | E::Dereference(e) | E::Borrow(_, e, _, _) => {
+            unfoldable_operation(e)
+        }
+        E::Vector(_, _, _, es) | E::Multiple(es) => es.iter().find_map(unfoldable_operation),
+        E::ModuleCall(call) => call.arguments.iter().find_map(unfoldable_operation),
+        E::Pack(_, _, fields) => fields.iter().find_map(|(_, _, e)| unfoldable_operation(e)),
+        _ => None,
+    }
+}
+
+fn binop_failure(sp!(_, op_): &BinOp, v1: &Value_, v2: &Value_) -> Option<String> {
+    use BinOp_ as B;
+    let (n1, ty, bits) = integer_value(v1)?;
+    let (n2, ty2, _) = integer_value(v2)?;
+    let exp = format!("'{n1}{ty} {op_} {n2}{ty2}'");
+    Some(match op_ {
+        B::Div | B::Mod if n2 == "0" => format!("Division by zero: {exp}"),
+        B::Shl | B::Shr => {
+            format!("Invalid shift: {exp} shifts by {n2} bits, but '{ty}' only has {bits} bits")
+        }
+        B::Sub => format!("Arithmetic underflow: {exp} is negative"),
+        B::Add | B::Mul => format!("Arithmetic overflow: {exp} does not fit in '{ty}'"),
+        _ => return None,
+    })
+}
+
+/// Returns the number, type and bit width of an integer value
+fn integer_value(v: &Value_) -> Option<(String, &'static str, u32)> {
+    use BuiltinTypeName_ as BT;
+    use Value_ as V;
+    Some(match v {
+        V::U8(u) => (u.to_string(), BT::U_8, 8),
+        V::U16(u) => (u.to_string(), BT::U_16, 16),
+        V::U32(u) => (u.to_string(), BT::U_32, 32),
+        V::U64(u) => (u.to_string(), BT::U_64, 64),
+        V::U128(u) => (u.to_string(), BT::U_128, 128),
+        V::U256(u) => (u.to_string(), BT::U_256, 256),
+        V::Address(_) | V::Bool(_) | V::Vector(_, _) => return None,
+    })
+}
+
 pub(crate) fn move_value_from_value(sp!(_, v_): Value) -> MoveValue {
     move_value_from_value_(v_)
 }
@@ -8,13 +8,17 @@ use crate::{
     compiled_unit,
     compiled_unit::AnnotatedCompiledUnit,
     diagnostics::{
+        allow_policy::AllowPolicy,
+        baseline::{WarningBaseline, WarningsBaselineMode},
         codes::{Severity, WarningFilter},
+        fixes::generate_fixes,
         *,
     },
     editions::Edition,
     expansion, hlir, interface_generator, naming, parser,
     parser::{comments::*, *},
     shared::{
+        artifact_cache::{self, ArtifactCache},
         CompilationEnv, Flags, IndexedPhysicalPackagePath, IndexedVfsPackagePath, NamedAddressMap,
         NamedAddressMaps, NumericalAddress, PackageConfig, PackagePaths,
     },
@@ -62,6 +66,14 @@ pub struct Compiler {
     default_config: Option<PackageConfig>,
     /// Root path of the virtual file system.
     vfs_root: Option<VfsPath>,
+    /// Baseline of known warnings to check against, or to update.
+    warnings_baseline: Option<(PathBuf, WarningsBaselineMode)>,
+    /// Policy restricting which warnings may be suppressed in source packages.
+    allow_policy: Option<PathBuf>,
+    /// Directory of the on-disk cache of the bytecode of source modules.
+    artifact_cache_dir: Option<PathBuf>,
+    /// Directory to write the suggested fixes of the build's diagnostics to.
+    fixes_dir: Option<PathBuf>,
 }
 
 pub struct SteppedCompiler<const P: Pass> {
@@ -170,6 +182,10 @@ impl Compiler {
             package_configs,
             default_config: None,
             vfs_root: None,
+            warnings_baseline: None,
+            allow_policy: None,
+            artifact_cache_dir: None,
+            fixes_dir: None,
         })
     }
 
@@ -274,6 +290,39 @@ impl Compiler {
         self
     }
 
+    /// In `WarningsBaselineMode::Check` mode, warnings recorded in the baseline at `path` are
+    /// not reported. In `WarningsBaselineMode::Update` mode, no warnings are reported and the
+    /// baseline at `path` is rewritten with the warnings of this build.
+    pub fn set_warnings_baseline(mut self, path: PathBuf, mode: WarningsBaselineMode) -> Self {
+        assert!(self.warnings_baseline.is_none());
+        self.warnings_baseline = Some((path, mode));
+        self
+    }
+
+    /// Sets the allow policy at `path`. Suppressing a warning in a source package that is not
+    /// permitted by the policy is an error.
+    pub fn set_allow_policy(mut self, path: PathBuf) -> Self {
+        assert!(self.allow_policy.is_none());
+        self.allow_policy = Some(path);
+        self
+    }
+
+    /// Caches the bytecode of source modules in `dir`, and reuses the cached bytecode of modules
+    /// that have not changed, nor have any of their dependencies, since they were cached.
+    pub fn set_artifact_cache_dir(mut self, dir: PathBuf) -> Self {
+        assert!(self.artifact_cache_dir.is_none());
+        self.artifact_cache_dir = Some(dir);
+        self
+    }
+
+    /// Writes the fixes suggested by the diagnostics of the build to `dir`, as a patch and as a
+    /// JSON list of edits.
+    pub fn set_fixes_dir(mut self, dir: PathBuf) -> Self {
+        assert!(self.fixes_dir.is_none());
+        self.fixes_dir = Some(dir);
+        self
+    }
+
     pub fn run<const TARGET: Pass>(
         self,
     ) -> anyhow::Result<(
@@ -312,6 +361,10 @@ impl Compiler {
             package_configs,
             default_config,
             vfs_root,
+            warnings_baseline,
+            allow_policy,
+            artifact_cache_dir,
+            fixes_dir: _,
         } = self;
         let vfs_root = match vfs_root {
             Some(p) => p,
@@ -341,6 +394,19 @@ impl Compiler {
         for (prefix, filters) in known_warning_filters {
             compilation_env.add_custom_known_filters(prefix, filters)?;
         }
+        if let Some((path, mode)) = warnings_baseline {
+            let baseline = match mode {
+                WarningsBaselineMode::Check => WarningBaseline::read(&path)?,
+                WarningsBaselineMode::Update => WarningBaseline::default(),
+            };
+            compilation_env.set_warnings_baseline(baseline, mode);
+        }
+        if let Some(path) = allow_policy {
+            compilation_env.set_allow_policy(AllowPolicy::read(&path)?);
+        }
+        if let Some(dir) = artifact_cache_dir {
+            compilation_env.set_artifact_cache(ArtifactCache::new(dir));
+        }
 
         let (mut source_text, pprog, comments) =
             parse_program(&mut compilation_env, maps, targets, deps)?;
@@ -407,12 +473,35 @@ impl Compiler {
         FilesSourceText,
         Result<(Vec<AnnotatedCompiledUnit>, Diagnostics), Diagnostics>,
     )> {
+        let baseline_update_path = match &self.warnings_baseline {
+            Some((path, WarningsBaselineMode::Update)) => Some(path.clone()),
+            _ => None,
+        };
+        let fixes_dir = self.fixes_dir.clone();
         let (files, res) = self.run::<PASS_COMPILATION>()?;
-        Ok((
-            files,
-            res.map(|(_comments, stepped)| stepped.into_compiled_units())
-                .map_err(|(_pass, diags)| diags),
-        ))
+        let res = match res {
+            Ok((_comments, mut stepped)) => {
+                if let Some(path) = baseline_update_path {
+                    let baseline = stepped
+                        .compilation_env()
+                        .take_recorded_warnings_baseline()
+                        .unwrap_or_default();
+                    baseline.write(&path)?;
+                }
+                Ok(stepped.into_compiled_units())
+            }
+            Err((_pass, diags)) => Err(diags),
+        };
+        if let Some(dir) = fixes_dir {
+            let diags = match &res {
+                Ok((_units, warnings)) => warnings,
+                Err(diags) => diags,
+            };
+            if let Some(fixes) = generate_fixes(&files, diags) {
+                fixes.record(&dir)?;
+            }
+        }
+        Ok((files, res))
     }
 
     pub fn build_and_report(self) -> anyhow::Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)> {
@@ -958,6 +1047,7 @@ fn run(
         match cur {
             PassResult::Parser(prog) => {
                 let eprog = {
+                    let prog = cfg_filter::program(compilation_env, prog);
                     let prog = unit_test::filter_test_members::program(compilation_env, prog);
                     let prog = verification_attribute_filter::program(compilation_env, prog);
                     expansion::translate::program(compilation_env, pre_compiled_lib.clone(), prog)
@@ -996,6 +1086,12 @@ fn run(
                 compilation_env
                     .check_diags_at_or_above_severity(Severity::BlockingError)
                     .map_err(|diags| (cur_pass, diags))?;
+                artifact_cache::record_fingerprints(
+                    compilation_env,
+                    pre_compiled_lib.as_deref(),
+                    &tprog,
+                );
+                artifact_cache::record_dependency_modules(compilation_env, &tprog);
                 let hprog =
                     hlir::translate::program(compilation_env, pre_compiled_lib.clone(), tprog);
                 rec(
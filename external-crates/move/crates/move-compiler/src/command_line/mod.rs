@@ -31,10 +31,26 @@ pub const VERIFY_SHORT: char = 'v';
 
 pub const WARNINGS_ARE_ERRORS: &str = "warnings-are-errors";
 
+pub const WARNINGS_BASELINE: &str = "warnings-baseline";
+
+pub const UPDATE_WARNINGS_BASELINE: &str = "update-warnings-baseline";
+
+pub const ALLOW_POLICY: &str = "allow-policy";
+
+pub const ARTIFACT_CACHE_DIR: &str = "artifact-cache-dir";
+
+pub const FIXES_DIR: &str = "fixes-dir";
+
+pub const LINT_DEAD_CODE: &str = "lint-dead-code";
+
+pub const CHECK_REPRODUCIBILITY: &str = "check-reproducibility";
+
 pub const GENERATE_MIGRATION_DIFF: &str = "generate-migration-diff";
 
 pub const BYTECODE_VERSION: &str = "bytecode-version";
 
+pub const TRACE_PATH_RESOLUTION: &str = "trace-path-resolution";
+
 pub const COLOR_MODE_ENV_VAR: &str = "COLOR_MODE";
 
 pub const MOVE_COMPILED_INTERFACES_DIR: &str = "mv_interfaces";
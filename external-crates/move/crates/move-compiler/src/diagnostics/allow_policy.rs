@@ -0,0 +1,57 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allow policies let an organization restrict which warnings may be suppressed in source code.
+//! When a policy is set, an `#[allow(...)]` (or deprecated `#[lint_allow(...)]`) of a warning
+//! filter that is not listed in the policy is an error, and the filter is not applied, so that
+//! security-relevant lints cannot be silently disabled.
+//!
+//! Filters are listed as they are written in the attribute, e.g. `unused_variable` or
+//! `lint(share_owned)`. Allowing `all` (or `lint(all)`) must be listed explicitly as well.
+
+use crate::shared::{format_allow_attr, FilterName, FilterPrefix};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, path::Path};
+
+/// Version of the on-disk policy format
+const ALLOW_POLICY_VERSION: u64 = 1;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllowPolicy {
+    allowed: BTreeSet<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AllowPolicyFile {
+    version: u64,
+    allowed: Vec<String>,
+}
+
+impl AllowPolicy {
+    pub fn new(allowed: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowed: allowed.into_iter().map(|s| s.into()).collect(),
+        }
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read allow policy {}", path.display()))?;
+        let file: AllowPolicyFile = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid allow policy {}", path.display()))?;
+        anyhow::ensure!(
+            file.version == ALLOW_POLICY_VERSION,
+            "Unsupported allow policy version {} in {}, expected {}",
+            file.version,
+            path.display(),
+            ALLOW_POLICY_VERSION
+        );
+        Ok(Self::new(file.allowed))
+    }
+
+    /// Returns true if the policy permits suppressing the filter `name`, with the given prefix.
+    pub fn permits(&self, prefix: FilterPrefix, name: FilterName) -> bool {
+        self.allowed.contains(&format_allow_attr(prefix, name))
+    }
+}
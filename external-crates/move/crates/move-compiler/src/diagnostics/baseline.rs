@@ -0,0 +1,149 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Warning baselines record the warnings a package already has, so that enabling a new warning
+//! or lint on a large codebase only reports (and, with `--warnings-are-errors`, only fails on)
+//! occurrences introduced after the baseline was taken.
+//!
+//! Each warning is identified by a fingerprint derived from its code, the name of the file it
+//! is in, its message and the source text it points at. Line numbers are deliberately left out
+//! so that unrelated edits to a file do not invalidate its baseline entries.
+
+use crate::diagnostics::{codes::Severity, Diagnostic, MappedFiles};
+use anyhow::Context;
+use move_command_line_common::files::FileHash;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// Version of the on-disk baseline format
+const BASELINE_VERSION: u64 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarningsBaselineMode {
+    /// Suppress warnings found in the baseline
+    Check,
+    /// Suppress all warnings, recording them to produce a new baseline
+    Update,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WarningBaseline {
+    entries: BTreeMap<String, BaselineEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BaselineEntry {
+    fingerprint: String,
+    code: String,
+    file: String,
+    message: String,
+    /// Number of identical occurrences covered by this entry
+    count: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+    version: u64,
+    warnings: Vec<BaselineEntry>,
+}
+
+impl WarningBaseline {
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read warnings baseline {}", path.display()))?;
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid warnings baseline {}", path.display()))?;
+        anyhow::ensure!(
+            file.version == BASELINE_VERSION,
+            "Unsupported warnings baseline version {} in {}, expected {}",
+            file.version,
+            path.display(),
+            BASELINE_VERSION
+        );
+        let entries = file
+            .warnings
+            .into_iter()
+            .map(|entry| (entry.fingerprint.clone(), entry))
+            .collect();
+        Ok(Self { entries })
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let file = BaselineFile {
+            version: BASELINE_VERSION,
+            warnings: self.entries.values().cloned().collect(),
+        };
+        let mut contents = serde_json::to_string_pretty(&file)?;
+        contents.push('\n');
+        std::fs::write(path, contents)
+            .with_context(|| format!("Cannot write warnings baseline {}", path.display()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Total number of warning occurrences in the baseline
+    pub fn len(&self) -> usize {
+        self.entries.values().map(|entry| entry.count).sum()
+    }
+
+    /// Adds an occurrence of `diag` to the baseline.
+    pub fn record(&mut self, files: &MappedFiles, diag: &Diagnostic) {
+        let Some(entry) = BaselineEntry::new(files, diag) else {
+            return;
+        };
+        self.entries
+            .entry(entry.fingerprint.clone())
+            .and_modify(|existing| existing.count += 1)
+            .or_insert(entry);
+    }
+
+    /// Consumes one occurrence of `diag` from the baseline, returning false if the baseline
+    /// does not cover it, i.e. it is a new warning.
+    pub fn consume(&mut self, files: &MappedFiles, diag: &Diagnostic) -> bool {
+        let Some(fingerprint) = fingerprint(files, diag) else {
+            return false;
+        };
+        match self.entries.get_mut(&fingerprint) {
+            Some(entry) if entry.count > 0 => {
+                entry.count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl BaselineEntry {
+    fn new(files: &MappedFiles, diag: &Diagnostic) -> Option<Self> {
+        let (file, _) = files.source_snippet(diag.primary_label.0)?;
+        let (code, _) = diag.info().clone().render();
+        Some(Self {
+            fingerprint: fingerprint(files, diag)?,
+            code,
+            file: file.to_string(),
+            message: diag.primary_msg().to_string(),
+            count: 1,
+        })
+    }
+}
+
+/// Computes the stable fingerprint of a warning, or None if it is not a warning or its location
+/// is not in a known file.
+fn fingerprint(files: &MappedFiles, diag: &Diagnostic) -> Option<String> {
+    if diag.info().severity() != Severity::Warning {
+        return None;
+    }
+    let (file, snippet) = files.source_snippet(diag.primary_label.0)?;
+    let file_name = Path::new(file.as_str()).file_name()?.to_string_lossy();
+    let (code, _) = diag.info().clone().render();
+    // Whitespace is normalized so reformatting does not change the fingerprint.
+    let snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    let key = format!(
+        "{code}\n{file_name}\n{}\n{snippet}",
+        diag.primary_msg().trim()
+    );
+    Some(FileHash::new(&key).to_string())
+}
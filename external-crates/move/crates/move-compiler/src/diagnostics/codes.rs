@@ -215,6 +215,7 @@ codes!(
         InvalidSyntaxMethod: { msg: "invalid 'syntax' method type", severity: NonblockingError },
         MissingSyntaxMethod: { msg: "no valid 'syntax' declaration found", severity: BlockingError },
         DuplicateAlias: { msg: "duplicate alias", severity: Warning },
+        InvalidImplicitAlias: { msg: "invalid implicit alias", severity: NonblockingError },
     ],
     // errors name resolution, mostly expansion/translate and naming/translate
     NameResolution: [
@@ -335,6 +336,8 @@ codes!(
             { msg: "unknown bytecode instruction function", severity: NonblockingError },
         ValueWarning: { msg: "issue with attribute value", severity: Warning },
         AmbiguousAttributeValue: { msg: "ambiguous attribute value", severity: NonblockingError },
+        DisallowedFilter:
+            { msg: "warning filter not permitted by allow policy", severity: NonblockingError },
     ],
     Tests: [
         TestFailed: { msg: "test failure", severity: BlockingError },
@@ -0,0 +1,297 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Suggested fixes are the edits of the source text that diagnostics propose to resolve them,
+//! e.g. replacing `public(friend)` with `public(package)`. The fixes of a build are collected
+//! into a patch, and into a JSON list of edits for editors and other tools to apply.
+
+use crate::diagnostics::{ByteSpan, Diagnostics, FileId, FilesSourceText, MappedFiles};
+use anyhow::Context;
+use codespan_reporting::files::Files;
+use move_symbol_pool::Symbol;
+use serde::Serialize;
+use std::{collections::BTreeMap, path::Path};
+
+/// The suggested fixes of a set of diagnostics
+pub struct Fixes {
+    mapped_files: MappedFiles,
+    fixes: BTreeMap<FileId, Vec<FileFix>>,
+}
+
+/// A suggested fix in a file, with the code and message of the diagnostic suggesting it
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct FileFix {
+    pub(super) byte_span: ByteSpan,
+    pub(super) replacement: String,
+    pub(super) code: String,
+    pub(super) message: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FixEntry<'a> {
+    file: &'a str,
+    code: &'a str,
+    message: &'a str,
+    start: Position,
+    end: Position,
+    replacement: &'a str,
+}
+
+/// A position in a file, with a 1-based line and a 0-based column
+#[derive(Serialize)]
+struct Position {
+    line: usize,
+    column: usize,
+    byte: usize,
+}
+
+/// Collects the suggested fixes of `diags`, returning None if there are none.
+pub fn generate_fixes(files: &FilesSourceText, diags: &Diagnostics) -> Option<Fixes> {
+    let Diagnostics(Some(inner)) = diags else {
+        return None;
+    };
+    let mapped_files = MappedFiles::new(files.clone());
+    let mut fixes: BTreeMap<FileId, Vec<FileFix>> = BTreeMap::new();
+    for diag in &inner.diagnostics {
+        for fix in diag.fixes() {
+            let Some(file_id) = mapped_files.file_hash_to_file_id(&fix.loc.file_hash()) else {
+                continue;
+            };
+            let (code, _) = diag.info().clone().render();
+            fixes.entry(file_id).or_default().push(FileFix {
+                byte_span: ByteSpan {
+                    start: fix.loc.start() as usize,
+                    end: fix.loc.end() as usize,
+                },
+                replacement: fix.replacement.clone(),
+                code,
+                message: diag.primary_msg().to_string(),
+            });
+        }
+    }
+    if fixes.is_empty() {
+        return None;
+    }
+    fixes.values_mut().for_each(normalize_fixes);
+    Some(Fixes {
+        mapped_files,
+        fixes,
+    })
+}
+
+impl Fixes {
+    /// Renders the fixes as a unified diff of the files they apply to.
+    pub fn render_output(&self) -> String {
+        let mut output = vec![];
+        for (file_id, name) in file_names(&self.mapped_files, &self.fixes) {
+            let original = self.mapped_files.files.source(file_id).unwrap().to_string();
+            let fixed = apply_fixes(&original, &self.fixes[&file_id]);
+            let diff = similar::TextDiff::from_lines(&original, &fixed);
+            output.push(
+                diff.unified_diff()
+                    .context_radius(0)
+                    .header(&name, &name)
+                    .to_string(),
+            );
+        }
+        output.join("")
+    }
+
+    pub fn render_json(&self) -> anyhow::Result<String> {
+        render_fixes_json(&self.mapped_files, &self.fixes)
+    }
+
+    /// Writes the fixes as a patch to `fixes.patch`, and as a JSON list of edits to `fixes.json`,
+    /// in `dir`.
+    pub fn record(&self, dir: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Cannot create fixes directory {}", dir.display()))?;
+        let patch_path = dir.join("fixes.patch");
+        std::fs::write(&patch_path, self.render_output())
+            .with_context(|| format!("Cannot write fixes {}", patch_path.display()))?;
+        let json_path = dir.join("fixes.json");
+        std::fs::write(&json_path, self.render_json()?)
+            .with_context(|| format!("Cannot write fixes {}", json_path.display()))
+    }
+}
+
+/// Sorts the fixes of a file, dropping duplicates, and fixes overlapping an earlier fix as both
+/// cannot be applied.
+pub(super) fn normalize_fixes(fixes: &mut Vec<FileFix>) {
+    fixes.sort();
+    fixes.dedup_by(|a, b| a.byte_span == b.byte_span && a.replacement == b.replacement);
+    let mut end = 0;
+    fixes.retain(|fix| {
+        if fix.byte_span.start < end {
+            return false;
+        }
+        end = fix.byte_span.end;
+        true
+    });
+}
+
+/// Renders normalized fixes as a JSON list of edits, sorted by file and position.
+pub(super) fn render_fixes_json(
+    mapped_files: &MappedFiles,
+    fixes: &BTreeMap<FileId, Vec<FileFix>>,
+) -> anyhow::Result<String> {
+    let position = |file_id: FileId, byte: usize| {
+        let location = mapped_files.files.location(file_id, byte).unwrap();
+        Position {
+            line: location.line_number,
+            column: location.column_number - 1,
+            byte,
+        }
+    };
+    let names = file_names(mapped_files, fixes);
+    let mut entries = vec![];
+    for (file_id, name) in &names {
+        for fix in &fixes[file_id] {
+            entries.push(FixEntry {
+                file: name.as_str(),
+                code: &fix.code,
+                message: &fix.message,
+                start: position(*file_id, fix.byte_span.start),
+                end: position(*file_id, fix.byte_span.end),
+                replacement: &fix.replacement,
+            });
+        }
+    }
+    let mut contents = serde_json::to_string_pretty(&entries)?;
+    contents.push('\n');
+    Ok(contents)
+}
+
+fn file_names(
+    mapped_files: &MappedFiles,
+    fixes: &BTreeMap<FileId, Vec<FileFix>>,
+) -> Vec<(FileId, Symbol)> {
+    let mut names = fixes
+        .keys()
+        .map(|id| (*id, *mapped_files.files.get(*id).unwrap().name()))
+        .collect::<Vec<_>>();
+    names.sort_by_key(|(_, name)| *name);
+    names
+}
+
+fn apply_fixes(source: &str, fixes: &[FileFix]) -> String {
+    let mut output = String::new();
+    let mut last = 0;
+    for fix in fixes {
+        output.push_str(&source[last..fix.byte_span.start]);
+        output.push_str(&fix.replacement);
+        last = fix.byte_span.end;
+    }
+    output.push_str(&source[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        editions::Edition,
+        shared::{NumericalAddress, PackageConfig},
+        Compiler,
+    };
+
+    #[test]
+    fn test_friend_fixes() {
+        let sources = tempfile::tempdir().unwrap();
+        let a = sources.path().join("a.move");
+        let b = sources.path().join("b.move");
+        std::fs::write(
+            &a,
+            "module 0x42::a {\n    friend 0x42::b;\n\n    public(friend) fun f(): u64 { 1 }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &b,
+            "module 0x42::b {\n    fun g(): u64 { 0x42::a::f() }\n}\n",
+        )
+        .unwrap();
+
+        let paths = [&a, &b]
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let (files, units) =
+            Compiler::from_files(paths, vec![], BTreeMap::<String, NumericalAddress>::new())
+                .set_default_config(PackageConfig {
+                    edition: Edition::E2024_ALPHA,
+                    ..PackageConfig::default()
+                })
+                .build()
+                .unwrap();
+        // Friends are deprecated in Move 2024, which is reported as an error.
+        let diags = units.unwrap_err();
+        let fixes = generate_fixes(&files, &diags).unwrap();
+
+        let fixed = apply_fixes(
+            &std::fs::read_to_string(&a).unwrap(),
+            fixes.fixes.values().next().unwrap(),
+        );
+        assert_eq!(
+            fixed,
+            "module 0x42::a {\n    \n\n    public(package) fun f(): u64 { 1 }\n}\n"
+        );
+
+        let entries: Vec<serde_json::Value> =
+            serde_json::from_str(&fixes.render_json().unwrap()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["start"]["line"], 2);
+        assert_eq!(entries[0]["replacement"], "");
+        assert_eq!(entries[1]["start"]["line"], 4);
+        assert_eq!(entries[1]["replacement"], "public(package)");
+    }
+
+    #[test]
+    fn test_unnecessary_alias_fixes() {
+        let sources = tempfile::tempdir().unwrap();
+        let m = sources.path().join("m.move");
+        let a = sources.path().join("a.move");
+        std::fs::write(
+            &m,
+            "module 0x42::m {\n    public fun f(): u64 { 1 }\n    public fun g(): u64 { 2 }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &a,
+            "module 0x42::a {\n    use 0x42::m::{Self, f};\n\n    fun t(): u64 {\n        \
+             use 0x42::m;\n        use 0x42::m::{f, g};\n        m::f() + f() + g()\n    }\n\n    \
+             fun u(): u64 {\n        use 0x42::m::{g as h, f};\n        m::g() + f() + h()\n    \
+             }\n\n    fun v(): u64 {\n        use 0x42::{m::f, m as n};\n        f() + n::g()\n    \
+             }\n}\n",
+        )
+        .unwrap();
+
+        let paths = [&m, &a]
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let (files, units) =
+            Compiler::from_files(paths, vec![], BTreeMap::<String, NumericalAddress>::new())
+                .set_default_config(PackageConfig {
+                    edition: Edition::E2024_ALPHA,
+                    ..PackageConfig::default()
+                })
+                .build()
+                .unwrap();
+        let (_units, warnings) = units.unwrap();
+        let fixes = generate_fixes(&files, &warnings).unwrap();
+
+        // Each unnecessary alias is removed, with its declaration if it declares no other alias.
+        let fixed = apply_fixes(
+            &std::fs::read_to_string(&a).unwrap(),
+            fixes.fixes.values().next().unwrap(),
+        );
+        assert_eq!(
+            fixed,
+            "module 0x42::a {\n    use 0x42::m::{Self, f};\n\n    fun t(): u64 {\n        \n        \
+             use 0x42::m::{g};\n        m::f() + f() + g()\n    }\n\n    fun u(): u64 {\n        \
+             use 0x42::m::{g as h};\n        m::g() + f() + h()\n    }\n\n    fun v(): u64 {\n        \
+             use 0x42::{m as n};\n        f() + n::g()\n    }\n}\n"
+        );
+    }
+}
@@ -2,7 +2,10 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod allow_policy;
+pub mod baseline;
 pub mod codes;
+pub mod fixes;
 
 use crate::{
     command_line::COLOR_MODE_ENV_VAR,
@@ -29,16 +32,21 @@ use csr::files::Files;
 use move_command_line_common::{env::read_env_var, files::FileHash};
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     io::Write,
     iter::FromIterator,
     ops::Range,
     path::PathBuf,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
-use self::codes::UnusedItem;
+use self::{
+    codes::UnusedItem,
+    fixes::{normalize_fixes, render_fixes_json, FileFix},
+};
 
 //**************************************************************************************************
 // Types
@@ -56,6 +64,15 @@ pub struct Diagnostic {
     primary_label: (Loc, String),
     secondary_labels: Vec<(Loc, String)>,
     notes: Vec<String>,
+    fixes: Vec<SuggestedFix>,
+}
+
+/// A fix suggested by a diagnostic, replacing the source text at `loc` with `replacement`. An
+/// empty `loc` inserts `replacement`, and an empty `replacement` removes the source text.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Hash)]
+pub struct SuggestedFix {
+    pub loc: Loc,
+    pub replacement: String,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
@@ -69,22 +86,25 @@ struct Diagnostics_ {
     severity_count: BTreeMap<Severity, usize>,
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 /// Used to filter out diagnostics, specifically used for warning suppression
 pub struct WarningFilters {
+    #[serde(deserialize_with = "deserialize_prefixed_filters")]
     filters: BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
     for_dependency: bool, // if false, the filters are used for source code
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 /// Filters split by category and code
 enum UnprefixedWarningFilters {
     /// Remove all warnings
     All,
     Specified {
         /// Remove all diags of this category with optional known name
+        #[serde(deserialize_with = "deserialize_named_filters")]
         categories: BTreeMap<u8, Option<WellKnownFilterName>>,
         /// Remove specific diags with optional known filter name
+        #[serde(deserialize_with = "deserialize_named_filters")]
         codes: BTreeMap<(u8, u8), Option<WellKnownFilterName>>,
     },
     /// No filter
@@ -107,6 +127,8 @@ enum MigrationChange {
 pub struct Migration {
     mapped_files: MappedFiles,
     changes: BTreeMap<FileId, Vec<(ByteSpan, MigrationChange)>>,
+    // The migration changes as suggested fixes
+    fixes: BTreeMap<FileId, Vec<FileFix>>,
 }
 
 /// A mapping from file ids to file contents along with the mapping of filehash to fileID.
@@ -136,7 +158,7 @@ pub struct FileByteSpan {
     byte_span: ByteSpan,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct ByteSpan {
     start: usize,
     end: usize,
@@ -194,6 +216,17 @@ impl MappedFiles {
         }
     }
 
+    /// Returns the name of the file `loc` is in and the source text it spans, if the file is
+    /// known.
+    pub fn source_snippet(&self, loc: Loc) -> Option<(FileName, &str)> {
+        let file_id = self.file_hash_to_file_id(&loc.file_hash())?;
+        let file = self.files.get(file_id).ok()?;
+        let snippet = file
+            .source()
+            .get(loc.start() as usize..loc.end() as usize)?;
+        Some((*file.name(), snippet))
+    }
+
     pub fn byte_location(&self, loc: Loc) -> FileByteSpan {
         let start = loc.start() as usize;
         let end = loc.end() as usize;
@@ -343,6 +376,7 @@ fn render_diagnostic(
         primary_label,
         secondary_labels,
         notes,
+        fixes: _,
     } = diag;
     let mut diag = csr::diagnostic::Diagnostic::new(info.severity().into_codespan_severity());
     let (code, message) = info.render();
@@ -504,6 +538,7 @@ impl Diagnostics {
                 primary_label,
                 secondary_labels,
                 notes,
+                fixes: _,
             } = diag;
             let csr_diag = (
                 info.severity().into_codespan_severity(),
@@ -569,6 +604,7 @@ impl Diagnostic {
                 .map(|(loc, msg)| (loc, msg.to_string()))
                 .collect(),
             notes: notes.into_iter().map(|msg| msg.to_string()).collect(),
+            fixes: vec![],
         }
     }
 
@@ -612,6 +648,18 @@ impl Diagnostic {
         self.notes.push(msg.to_string())
     }
 
+    /// Suggests replacing the source text at `loc` with `replacement` to fix the diagnostic.
+    pub fn add_fix(&mut self, loc: Loc, replacement: impl ToString) {
+        self.fixes.push(SuggestedFix {
+            loc,
+            replacement: replacement.to_string(),
+        })
+    }
+
+    pub fn fixes(&self) -> &[SuggestedFix] {
+        &self.fixes
+    }
+
     pub fn info(&self) -> &DiagnosticInfo {
         &self.info
     }
@@ -881,6 +929,7 @@ impl Migration {
         let mut mig = Migration {
             changes: BTreeMap::new(),
             mapped_files,
+            fixes: BTreeMap::new(),
         };
 
         let migration_errors = Diagnostics::new();
@@ -921,6 +970,15 @@ impl Migration {
             }
             _ => unreachable!(),
         };
+        let source = self.mapped_files.files.source(file_id).unwrap();
+        let (fix_span, replacement) = change.edit(source, &byte_span);
+        let (code, message) = diag.info().clone().render();
+        self.fixes.entry(file_id).or_default().push(FileFix {
+            byte_span: fix_span,
+            replacement,
+            code,
+            message: message.to_string(),
+        });
         file_change_entry.push((byte_span, change));
     }
 
@@ -1025,6 +1083,19 @@ impl Migration {
         Ok(string_result)
     }
 
+    /// Writes the migration changes as a JSON list of suggested fixes to `migration.json` in
+    /// `path`, for editors and other tools to apply.
+    pub fn record_fixes(&mut self, path: PathBuf) -> anyhow::Result<String> {
+        self.fixes.values_mut().for_each(normalize_fixes);
+        let output_path = path.join("migration.json");
+        let string_result = output_path.to_str().unwrap_or("invalid path").to_string();
+        std::fs::write(
+            output_path,
+            render_fixes_json(&self.mapped_files, &self.fixes)?,
+        )?;
+        Ok(string_result)
+    }
+
     pub fn apply_changes<W: Write>(&mut self, w: &mut W) -> anyhow::Result<()> {
         writeln!(w)?;
         let mut names = self
@@ -1057,6 +1128,62 @@ impl Migration {
     }
 }
 
+impl MigrationChange {
+    /// The change as an edit replacing the returned span of `source` with the returned text,
+    /// where `span` is the location of the change.
+    fn edit(&self, source: &str, span: &ByteSpan) -> (ByteSpan, String) {
+        let insertion = ByteSpan {
+            start: span.start,
+            end: span.start,
+        };
+        match self {
+            MigrationChange::AddMut => (insertion, "mut ".to_string()),
+            MigrationChange::AddPublic => (insertion, "public ".to_string()),
+            MigrationChange::Backquote(old_name) => (span.clone(), format!("`{}`", old_name)),
+            MigrationChange::AddGlobalQual => (insertion, "::".to_string()),
+            MigrationChange::RemoveFriend | MigrationChange::AddressRemove => (
+                span.clone(),
+                format!("/* {} */", &source[span.start..span.end]),
+            ),
+            MigrationChange::MakePubPackage => (span.clone(), "public(package)".to_string()),
+            MigrationChange::AddressAdd(insertion_text) => (insertion, insertion_text.clone()),
+        }
+    }
+}
+
+// Prefixes and names of warning filters are static strings, which are interned when deserializing
+// the filters, e.g. from the artifact cache.
+fn intern_filter_name(name: String) -> &'static str {
+    static NAMES: Lazy<Mutex<BTreeSet<&'static str>>> = Lazy::new(|| Mutex::new(BTreeSet::new()));
+    let mut names = NAMES.lock().unwrap();
+    if let Some(interned) = names.get(name.as_str()) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(interned);
+    interned
+}
+
+fn deserialize_prefixed_filters<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<ExternalPrefix, UnprefixedWarningFilters>, D::Error> {
+    let filters = BTreeMap::<Option<String>, UnprefixedWarningFilters>::deserialize(deserializer)?;
+    Ok(filters
+        .into_iter()
+        .map(|(prefix, filters)| (prefix.map(intern_filter_name), filters))
+        .collect())
+}
+
+fn deserialize_named_filters<'de, D: Deserializer<'de>, K: Deserialize<'de> + Ord>(
+    deserializer: D,
+) -> Result<BTreeMap<K, Option<WellKnownFilterName>>, D::Error> {
+    let filters = BTreeMap::<K, Option<String>>::deserialize(deserializer)?;
+    Ok(filters
+        .into_iter()
+        .map(|(key, name)| (key, name.map(intern_filter_name)))
+        .collect())
+}
+
 //**************************************************************************************************
 // traits
 //**************************************************************************************************
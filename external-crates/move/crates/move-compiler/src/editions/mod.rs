@@ -48,6 +48,7 @@ pub enum FeatureGate {
     AutoborrowEq,
     CleverAssertions,
     NoParensCast,
+    ConstantIfExpressions,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, PartialOrd, Ord, Default)]
@@ -132,6 +133,7 @@ const E2024_BETA_FEATURES: &[FeatureGate] = &[
     FeatureGate::SyntaxMethods,
     FeatureGate::AutoborrowEq,
     FeatureGate::NoParensCast,
+    FeatureGate::ConstantIfExpressions,
 ];
 
 const DEVELOPMENT_FEATURES: &[FeatureGate] = &[FeatureGate::CleverAssertions];
@@ -254,6 +256,7 @@ impl FeatureGate {
             FeatureGate::AutoborrowEq => "Automatic borrowing is",
             FeatureGate::CleverAssertions => "Clever `assert!`, `abort`, and `#[error]` are",
             FeatureGate::NoParensCast => "'as' without parentheses is",
+            FeatureGate::ConstantIfExpressions => "'if' expressions in constants are",
         }
     }
 }
@@ -54,6 +54,23 @@ trait NamespaceEntry: Copy {
         }
     }
 
+    /// Returns every binding of `name` in this namespace, along with the depth of the scope that
+    /// binds it (0 being the innermost scope), without marking any of them as used.
+    fn find_all(m: &AliasMap, name: &Name) -> Vec<(usize, AliasEntry)> {
+        let mut found = vec![];
+        let mut current_scope = Some(m);
+        let mut depth = 0;
+        while let Some(scope) = current_scope {
+            if let Some(entry) = Self::namespace(scope).get(name).copied() {
+                let original_name = Self::namespace(scope).get_full_key(name).unwrap();
+                found.push((depth, Self::alias_entry(original_name, entry)));
+            }
+            current_scope = scope.previous.as_deref();
+            depth += 1;
+        }
+        found
+    }
+
     fn find(m: &mut AliasMap, name: &Name) -> Option<(Name, Self)> {
         Self::find_custom(m, name, |scope, name, entry| {
             scope.unused.remove(&Self::alias_entry(*name, *entry));
@@ -137,6 +154,19 @@ impl AliasMap {
         }
     }
 
+    /// Returns every alias for `name` in the namespace, innermost scope first, along with the
+    /// depth of its scope. Used only to trace path resolution, so aliases are not marked as used.
+    pub fn bindings_for_trace(
+        &self,
+        namespace: NameSpace,
+        name: &Name,
+    ) -> Vec<(usize, AliasEntry)> {
+        match namespace {
+            NameSpace::LeadingAccess => LeadingAccessEntry::find_all(self, name),
+            NameSpace::ModuleMembers => MemberEntry::find_all(self, name),
+        }
+    }
+
     pub fn resolve_any_for_error(&mut self, name: &Name) -> Option<AliasEntry> {
         for namespace in [NameSpace::LeadingAccess, NameSpace::ModuleMembers] {
             if let Some(entry) = self.resolve(namespace, name) {
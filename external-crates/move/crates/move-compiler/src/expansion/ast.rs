@@ -16,6 +16,7 @@ use crate::{
 };
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
 use std::{collections::VecDeque, fmt, hash::Hash};
 
 //**************************************************************************************************
@@ -38,7 +39,7 @@ pub struct ExplicitUseFun {
     pub method: Name,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ImplicitUseFunKind {
     // From a function declaration in the module
     FunctionDeclaration,
@@ -48,7 +49,7 @@ pub enum ImplicitUseFunKind {
 
 // These are only candidates as we have not yet checked if they have the proper signature for a
 // use fun declaration
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ImplicitUseFunCandidate {
     pub loc: Loc,
     pub attributes: Attributes,
@@ -67,7 +68,7 @@ pub struct UseFuns {
 // Attributes
 //**************************************************************************************************
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AttributeValue_ {
     Value(Value),
     Address(Address),
@@ -76,7 +77,7 @@ pub enum AttributeValue_ {
 }
 pub type AttributeValue = Spanned<AttributeValue_>;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Attribute_ {
     Name(Name),
     Assigned(Name, Box<AttributeValue>),
@@ -84,7 +85,7 @@ pub enum Attribute_ {
 }
 pub type Attribute = Spanned<Attribute_>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum AttributeName_ {
     Unknown(Symbol),
     Known(KnownAttribute),
@@ -99,7 +100,7 @@ pub type Attributes = UniqueMap<Spanned<KnownAttribute>, Attribute>;
 // Modules
 //**************************************************************************************************
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum Address {
     Numerical {
         name: Option<Name>,
@@ -109,7 +110,7 @@ pub enum Address {
     },
     NamedUnassigned(Name),
 }
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct ModuleIdent_ {
     pub address: Address,
     pub module: ModuleName,
@@ -135,7 +136,7 @@ pub struct ModuleDefinition {
 // Friend
 //**************************************************************************************************
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Friend {
     pub attributes: Attributes,
     // We retain attr locations for Move 2024 migration: `flatten_attributes` in `translate.rs`
@@ -180,7 +181,7 @@ pub enum StructFields {
 // Functions
 //**************************************************************************************************
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Visibility {
     Public(Loc),
     Friend(Loc),
@@ -235,10 +236,10 @@ pub struct Constant {
 // Types
 //**************************************************************************************************
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct AbilitySet(UniqueSet<Ability>);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum ModuleAccess_ {
     Name(Name),
@@ -262,7 +263,7 @@ pub type Type = Spanned<Type_>;
 // Expressions
 //**************************************************************************************************
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Mutability {
     Imm,
     Mut(Loc), // if the local had a `mut` prefix
@@ -309,7 +310,7 @@ pub enum DottedUsage {
     Borrow(/* mut */ bool),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Value_ {
     // 0x<hex representation up to 64 digits with padding 0s>
     Address(Address),
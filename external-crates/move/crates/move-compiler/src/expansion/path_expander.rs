@@ -132,6 +132,7 @@ use super::alias_map_builder::UnnecessaryAlias;
 
 pub struct Move2024PathExpander {
     aliases: AliasMap,
+    trace: PathResolutionTrace,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -166,9 +167,10 @@ macro_rules! chain_result {
 }
 
 impl Move2024PathExpander {
-    pub(super) fn new() -> Move2024PathExpander {
+    pub(super) fn new(env: &CompilationEnv) -> Move2024PathExpander {
         Move2024PathExpander {
             aliases: AliasMap::new(),
+            trace: PathResolutionTrace::new(env),
         }
     }
 
@@ -181,15 +183,27 @@ impl Move2024PathExpander {
         use AccessChainNameResult as NR;
         use P::LeadingNameAccess_ as LN;
         match name {
-            LN::AnonymousAddress(address) => NR::Address(loc, E::Address::anonymous(loc, address)),
+            LN::AnonymousAddress(address) => {
+                self.trace
+                    .step(|| format!("`{address}` is a numerical address"));
+                NR::Address(loc, E::Address::anonymous(loc, address))
+            }
             LN::GlobalAddress(name) => {
                 if let Some(address) = context
                     .named_address_mapping
                     .expect("ICE no named address mapping")
                     .get(&name.value)
                 {
+                    self.trace.step(|| {
+                        format!(
+                            "`::{name}` is the named address `{name}` ({address}), \
+                             aliases are not searched"
+                        )
+                    });
                     NR::Address(loc, make_address(context, name, name.loc, *address))
                 } else {
+                    self.trace
+                        .step(|| format!("`::{name}` is not a known named address"));
                     NR::ResolutionFailure(
                         Box::new(NR::UnresolvedName(loc, name)),
                         NF::UnresolvedAlias(name),
@@ -215,6 +229,8 @@ impl Move2024PathExpander {
         use AccessChainNameResult as NR;
         use E::ModuleAccess_ as EN;
 
+        self.trace
+            .step(|| alias_lookup_step(context.env, &self.aliases, namespace, &name));
         match self.aliases.resolve(namespace, &name) {
             Some(AliasEntry::Member(_, mident, sp!(_, mem))) => {
                 // We are preserving the name's original location, rather than referring to where
@@ -250,6 +266,12 @@ impl Move2024PathExpander {
             }
             None => {
                 if let Some(entry) = self.aliases.resolve_any_for_error(&name) {
+                    self.trace.step(|| {
+                        format!(
+                            "`{name}` is only bound in the other namespace, as {}",
+                            alias_entry_description(context.env, &entry)
+                        )
+                    });
                     let msg = match namespace {
                         NameSpace::ModuleMembers => "a type, function, or constant".to_string(),
                         // we exclude types from this message since it would have been caught in
@@ -340,6 +362,8 @@ impl Move2024PathExpander {
                     && (BuiltinFunction_::all_names().contains(&name.value)
                         || BuiltinTypeName_::all_names().contains(&name.value))
                 {
+                    self.trace
+                        .step(|| format!("`{name}` is a builtin name, aliases are not searched"));
                     NR::UnresolvedName(name.loc, name)
                 } else {
                     self.resolve_name(context, namespace, name)
@@ -365,6 +389,12 @@ impl Move2024PathExpander {
                             && root.tyargs.is_none() =>
                     {
                         if let Some(address) = top_level_address_opt(context, root.name) {
+                            self.trace.step(|| {
+                                format!(
+                                    "migration: treating `{}` as the address `{address}` instead",
+                                    root.name
+                                )
+                            });
                             context.env.add_diag(diag!(
                                 Migration::NeedsGlobalQualification,
                                 (root.name.loc, "Must globally qualify name")
@@ -383,6 +413,13 @@ impl Move2024PathExpander {
                 let mut is_macro = root.is_macro;
 
                 for entry in entries {
+                    self.trace.step(|| {
+                        format!(
+                            "the path so far resolved to {}, followed by `{}`",
+                            result.trace_description(),
+                            entry.name
+                        )
+                    });
                     check_tyargs(context, &ptys_opt, &result);
                     check_is_macro(context, &is_macro, &result);
                     match result {
@@ -426,6 +463,8 @@ impl Move2024PathExpander {
                     }
                 }
 
+                self.trace
+                    .step(|| format!("the path resolved to {}", result.trace_description()));
                 AccessChainResult {
                     result,
                     ptys_opt,
@@ -454,6 +493,57 @@ impl PathExpander for Move2024PathExpander {
     }
 
     fn name_access_chain_to_attribute_value(
+        &mut self,
+        context: &mut DefnContext,
+        attribute_value: P::AttributeValue,
+    ) -> Option<E::AttributeValue> {
+        let traced = self.trace.start(context.env, attribute_value.loc, || {
+            attribute_value_description(&attribute_value)
+        });
+        let result = self.resolve_attribute_value(context, attribute_value);
+        if traced {
+            self.trace
+                .finish(|| resolved_attribute_value_description(&result));
+        }
+        result
+    }
+
+    fn name_access_chain_to_module_access(
+        &mut self,
+        context: &mut DefnContext,
+        access: Access,
+        chain: P::NameAccessChain,
+    ) -> Option<ModuleAccessResult> {
+        let traced = self.trace.start(context.env, chain.loc, || {
+            format!("`{}` as {}", chain, access_description(access))
+        });
+        let result = self.resolve_module_access(context, access, chain);
+        if traced {
+            self.trace
+                .finish(|| resolved_module_access_description(&result));
+        }
+        result
+    }
+
+    fn name_access_chain_to_module_ident(
+        &mut self,
+        context: &mut DefnContext,
+        chain: P::NameAccessChain,
+    ) -> Option<E::ModuleIdent> {
+        let traced = self.trace.start(context.env, chain.loc, || {
+            format!("`{}` as {}", chain, access_description(Access::Module))
+        });
+        let result = self.resolve_module_ident(context, chain);
+        if traced {
+            self.trace
+                .finish(|| resolved_module_ident_description(&result));
+        }
+        result
+    }
+}
+
+impl Move2024PathExpander {
+    fn resolve_attribute_value(
         &mut self,
         context: &mut DefnContext,
         sp!(loc, avalue_): P::AttributeValue,
@@ -488,6 +578,13 @@ impl PathExpander for Move2024PathExpander {
                         self.resolve_name_access_chain(context, Access::Module, access_chain);
                     assert!(module_tyargs.is_none());
                     assert!(module_is_macro.is_none());
+                    self.trace.step(|| {
+                        format!(
+                            "as a term, the path resolved to {}; as a module, to {}",
+                            term_result.trace_description(),
+                            module_result.trace_description()
+                        )
+                    });
                     let result = match (term_result, module_result) {
                         (t_res, m_res) if t_res == m_res => t_res,
                         (NR::ResolutionFailure(_, _) | NR::UnresolvedName(_, _), other)
@@ -529,7 +626,7 @@ impl PathExpander for Move2024PathExpander {
         ))
     }
 
-    fn name_access_chain_to_module_access(
+    fn resolve_module_access(
         &mut self,
         context: &mut DefnContext,
         access: Access,
@@ -628,7 +725,7 @@ impl PathExpander for Move2024PathExpander {
         Some(make_access_result(sp(loc, module_access), tyargs, is_macro))
     }
 
-    fn name_access_chain_to_module_ident(
+    fn resolve_module_ident(
         &mut self,
         context: &mut DefnContext,
         chain: P::NameAccessChain,
@@ -688,6 +785,24 @@ impl AccessChainNameResult {
             AccessChainNameResult::ResolutionFailure(inner, _) => inner.err_name(),
         }
     }
+
+    fn trace_description(&self) -> String {
+        match self {
+            AccessChainNameResult::ModuleAccess(_, access) => format!("module member `{access}`"),
+            AccessChainNameResult::ModuleIdent(_, mident) => format!("module `{mident}`"),
+            AccessChainNameResult::UnresolvedName(_, name) => format!("unresolved name `{name}`"),
+            AccessChainNameResult::Address(_, address) => format!("address `{address}`"),
+            AccessChainNameResult::ResolutionFailure(inner, reason) => {
+                let reason = match reason {
+                    AccessChainFailure::UnresolvedAlias(name) => {
+                        format!("could not resolve `{name}`")
+                    }
+                    AccessChainFailure::InvalidKind(kind) => format!("expected {kind}"),
+                };
+                format!("an error ({reason}, found {})", inner.trace_description())
+            }
+        }
+    }
 }
 
 fn unexpected_access_error(loc: Loc, result: String, access: Access) -> Diagnostic {
@@ -747,13 +862,15 @@ fn access_chain_resolution_error(result: AccessChainNameResult) -> Diagnostic {
 pub struct LegacyPathExpander {
     aliases: legacy_aliases::AliasMap,
     old_alias_maps: Vec<legacy_aliases::OldAliasMap>,
+    trace: PathResolutionTrace,
 }
 
 impl LegacyPathExpander {
-    pub fn new() -> LegacyPathExpander {
+    pub fn new(env: &CompilationEnv) -> LegacyPathExpander {
         LegacyPathExpander {
             aliases: legacy_aliases::AliasMap::new(),
             old_alias_maps: vec![],
+            trace: PathResolutionTrace::new(env),
         }
     }
 }
@@ -783,6 +900,57 @@ impl PathExpander for LegacyPathExpander {
     }
 
     fn name_access_chain_to_attribute_value(
+        &mut self,
+        context: &mut DefnContext,
+        attribute_value: P::AttributeValue,
+    ) -> Option<E::AttributeValue> {
+        let traced = self.trace.start(context.env, attribute_value.loc, || {
+            attribute_value_description(&attribute_value)
+        });
+        let result = self.resolve_attribute_value(context, attribute_value);
+        if traced {
+            self.trace
+                .finish(|| resolved_attribute_value_description(&result));
+        }
+        result
+    }
+
+    fn name_access_chain_to_module_access(
+        &mut self,
+        context: &mut DefnContext,
+        access: Access,
+        chain: P::NameAccessChain,
+    ) -> Option<ModuleAccessResult> {
+        let traced = self.trace.start(context.env, chain.loc, || {
+            format!("`{}` as {}", chain, access_description(access))
+        });
+        let result = self.resolve_module_access(context, access, chain);
+        if traced {
+            self.trace
+                .finish(|| resolved_module_access_description(&result));
+        }
+        result
+    }
+
+    fn name_access_chain_to_module_ident(
+        &mut self,
+        context: &mut DefnContext,
+        chain: P::NameAccessChain,
+    ) -> Option<E::ModuleIdent> {
+        let traced = self.trace.start(context.env, chain.loc, || {
+            format!("`{}` as {}", chain, access_description(Access::Module))
+        });
+        let result = self.resolve_module_ident(context, chain);
+        if traced {
+            self.trace
+                .finish(|| resolved_module_ident_description(&result));
+        }
+        result
+    }
+}
+
+impl LegacyPathExpander {
+    fn resolve_attribute_value(
         &mut self,
         context: &mut DefnContext,
         sp!(loc, avalue_): P::AttributeValue,
@@ -802,7 +970,10 @@ impl PathExpander for LegacyPathExpander {
                 {
                     ice_assert!(context.env, tyargs.is_none(), loc, "Found tyargs");
                     ice_assert!(context.env, is_macro.is_none(), loc, "Found macro");
-                    let sp!(_, mident_) = self.aliases.module_alias_get(&name).unwrap();
+                    let mident_opt = self.aliases.module_alias_get(&name);
+                    self.trace
+                        .step(|| legacy_module_alias_step(&name, &mident_opt));
+                    let sp!(_, mident_) = mident_opt.unwrap();
                     let mident = sp(ident_loc, mident_);
                     if context.module_members.get(&mident).is_none() {
                         context.env.add_diag(diag!(
@@ -867,7 +1038,7 @@ impl PathExpander for LegacyPathExpander {
         ))
     }
 
-    fn name_access_chain_to_module_access(
+    fn resolve_module_access(
         &mut self,
         context: &mut DefnContext,
         access: Access,
@@ -888,6 +1059,7 @@ impl PathExpander for LegacyPathExpander {
                     Some((mident, mem)) => EN::ModuleAccess(mident, mem),
                     None => EN::Name(name),
                 };
+                self.trace.step(|| legacy_member_alias_step(&name, &access));
                 make_access_result(sp(name.loc, access), tyargs, is_macro)
             }
             (Access::Term, single_entry!(name, tyargs, is_macro))
@@ -897,9 +1069,13 @@ impl PathExpander for LegacyPathExpander {
                     Some((mident, mem)) => EN::ModuleAccess(mident, mem),
                     None => EN::Name(name),
                 };
+                self.trace.step(|| legacy_member_alias_step(&name, &access));
                 make_access_result(sp(name.loc, access), tyargs, is_macro)
             }
             (Access::Term, single_entry!(name, tyargs, is_macro)) => {
+                self.trace.step(|| {
+                    format!("`{name}` is not a struct or constant name, aliases are not searched")
+                });
                 make_access_result(sp(name.loc, EN::Name(name)), tyargs, is_macro)
             }
             (Access::Module, single_entry!(_name, _tyargs, _is_macro)) => {
@@ -936,6 +1112,8 @@ impl PathExpander for LegacyPathExpander {
                     // Others
                     (sp!(_, LN::Name(n1)), [n2]) => match self.aliases.module_alias_get(n1) {
                         None => {
+                            self.trace
+                                .step(|| format!("no module alias for `{n1}` is in scope"));
                             context.env.add_diag(diag!(
                                 NameResolution::UnboundModule,
                                 (n1.loc, format!("Unbound module alias '{}'", n1))
@@ -943,6 +1121,8 @@ impl PathExpander for LegacyPathExpander {
                             return None;
                         }
                         Some(mident) => {
+                            self.trace
+                                .step(|| format!("`{n1}` is a module alias for `{mident}`"));
                             let n2_name = n2.name;
                             let (tyargs, is_macro) = if !(path.has_tyargs_last()) {
                                 let mut diag = diag!(
@@ -965,6 +1145,13 @@ impl PathExpander for LegacyPathExpander {
                         }
                     },
                     (ln, [n2, n3]) => {
+                        self.trace.step(|| {
+                            format!(
+                                "the path has three segments, so `{ln}` is resolved as an address, \
+                                 and `{}` as a module of it",
+                                n2.name
+                            )
+                        });
                         let ident_loc = make_loc(
                             ln.loc.file_hash(),
                             ln.loc.start() as usize,
@@ -1004,7 +1191,7 @@ impl PathExpander for LegacyPathExpander {
         Some(tn_)
     }
 
-    fn name_access_chain_to_module_ident(
+    fn resolve_module_ident(
         &mut self,
         context: &mut DefnContext,
         sp!(loc, pn_): P::NameAccessChain,
@@ -1014,7 +1201,10 @@ impl PathExpander for LegacyPathExpander {
             PN::Single(single) => {
                 ice_assert!(context.env, single.tyargs.is_none(), loc, "Found tyargs");
                 ice_assert!(context.env, single.is_macro.is_none(), loc, "Found macro");
-                match self.aliases.module_alias_get(&single.name) {
+                let mident_opt = self.aliases.module_alias_get(&single.name);
+                self.trace
+                    .step(|| legacy_module_alias_step(&single.name, &mident_opt));
+                match mident_opt {
                     None => {
                         context.env.add_diag(diag!(
                             NameResolution::UnboundModule,
@@ -1033,6 +1223,12 @@ impl PathExpander for LegacyPathExpander {
                 ice_assert!(context.env, path.is_macro().is_none(), loc, "Found macro");
                 match (&path.root.name, &path.entries[..]) {
                     (ln, [n]) => {
+                        self.trace.step(|| {
+                            format!(
+                                "`{ln}` is resolved as an address, and `{}` as a module of it",
+                                n.name
+                            )
+                        });
                         let pmident_ = P::ModuleIdent_ {
                             address: *ln,
                             module: ModuleName(n.name),
@@ -1098,3 +1294,292 @@ fn unexpected_address_module_error(loc: Loc, nloc: Loc, access: Access) -> Diagn
         (nloc, "Expected a module name".to_owned()),
     )
 }
+
+//**************************************************************************************************
+// Path Resolution Tracing
+//**************************************************************************************************
+
+/// Records the steps taken to resolve the paths on the line selected with
+/// `--trace-path-resolution`, logging them at the info level once each path is resolved.
+struct PathResolutionTrace {
+    target: Option<PathResolutionTarget>,
+    /// The description of the path being traced, and the steps recorded for it so far
+    current: Option<(String, Vec<String>)>,
+}
+
+impl PathResolutionTrace {
+    fn new(env: &CompilationEnv) -> Self {
+        Self {
+            target: env.flags().trace_path_resolution().cloned(),
+            current: None,
+        }
+    }
+
+    /// Starts tracing the path at `loc` if it is on the traced line and no other path is being
+    /// traced (paths can be resolved while resolving another, e.g. for attributes). Returns true
+    /// if it started, in which case `finish` must be called once the path is resolved.
+    fn start(&mut self, env: &CompilationEnv, loc: Loc, path: impl FnOnce() -> String) -> bool {
+        let Some(target) = &self.target else {
+            return false;
+        };
+        if self.current.is_some() {
+            return false;
+        }
+        let files = env.file_mapping();
+        let Some((file_name, _)) = files.source_snippet(loc) else {
+            return false;
+        };
+        if !target.matches_file(file_name.as_str()) {
+            return false;
+        }
+        let start = files.location(loc).start;
+        if start.line != target.line {
+            return false;
+        }
+        let header = format!(
+            "{}:{}:{}: resolving {}",
+            file_name,
+            start.line,
+            start.column + 1,
+            path()
+        );
+        self.current = Some((header, vec![]));
+        true
+    }
+
+    fn step(&mut self, step: impl FnOnce() -> String) {
+        if let Some((_, steps)) = &mut self.current {
+            steps.push(step());
+        }
+    }
+
+    fn finish(&mut self, result: impl FnOnce() -> String) {
+        let Some((header, steps)) = self.current.take() else {
+            return;
+        };
+        let mut out = header;
+        for (i, step) in steps.iter().enumerate() {
+            out.push_str(&format!("\n  {}. {}", i + 1, step));
+        }
+        out.push_str(&format!("\n  => {}", result()));
+        tracing::info!("{}", out);
+    }
+}
+
+fn loc_description(env: &CompilationEnv, loc: Loc) -> String {
+    let files = env.file_mapping();
+    match files.source_snippet(loc) {
+        Some((file_name, _)) => {
+            let start = files.location(loc).start;
+            format!("{}:{}:{}", file_name, start.line, start.column + 1)
+        }
+        None => "<no location>".to_string(),
+    }
+}
+
+fn namespace_description(namespace: NameSpace) -> &'static str {
+    match namespace {
+        NameSpace::LeadingAccess => "addresses and modules",
+        NameSpace::ModuleMembers => "module members",
+    }
+}
+
+fn access_description(access: Access) -> &'static str {
+    match access {
+        Access::Type => "a type",
+        Access::ApplyNamed => "a struct with named fields",
+        Access::ApplyPositional => "a function or positional struct",
+        Access::Term => "a term",
+        Access::Module => "a module",
+    }
+}
+
+fn alias_entry_description(env: &CompilationEnv, entry: &AliasEntry) -> String {
+    let (alias, description) = match entry {
+        AliasEntry::Address(alias, address) => (alias, format!("an alias for address `{address}`")),
+        AliasEntry::Module(alias, mident) => (alias, format!("an alias for module `{mident}`")),
+        AliasEntry::Member(alias, mident, member) => {
+            (alias, format!("an alias for member `{mident}::{member}`"))
+        }
+        AliasEntry::TypeParam(alias) => (alias, "a type parameter".to_string()),
+    };
+    format!(
+        "{} (declared at {})",
+        description,
+        loc_description(env, alias.loc)
+    )
+}
+
+/// Describes every alias scope binding `name`, innermost first, as the innermost one is the one
+/// resolution picks (or fails on, for type parameters).
+fn alias_lookup_step(
+    env: &CompilationEnv,
+    aliases: &AliasMap,
+    namespace: NameSpace,
+    name: &Name,
+) -> String {
+    let bindings = aliases.bindings_for_trace(namespace, name);
+    if bindings.is_empty() {
+        return format!(
+            "no alias for `{name}` is in scope among {}",
+            namespace_description(namespace)
+        );
+    }
+    let mut out = format!(
+        "looking up `{name}` among {}, innermost scope first:",
+        namespace_description(namespace)
+    );
+    for (i, (depth, entry)) in bindings.iter().enumerate() {
+        let status = if i == 0 { "picked" } else { "shadowed" };
+        out.push_str(&format!(
+            "\n       - scope {}: {} [{}]",
+            depth,
+            alias_entry_description(env, entry),
+            status
+        ));
+    }
+    out
+}
+
+fn legacy_member_alias_step(name: &Name, access: &E::ModuleAccess_) -> String {
+    match access {
+        E::ModuleAccess_::ModuleAccess(mident, member) => {
+            format!("`{name}` is an alias for member `{mident}::{member}`")
+        }
+        E::ModuleAccess_::Name(_) => format!("no member alias for `{name}` is in scope"),
+    }
+}
+
+fn legacy_module_alias_step(name: &Name, mident: &Option<E::ModuleIdent>) -> String {
+    match mident {
+        Some(mident) => format!("`{name}` is a module alias for `{mident}`"),
+        None => format!("no module alias for `{name}` is in scope"),
+    }
+}
+
+fn attribute_value_description(sp!(_, avalue_): &P::AttributeValue) -> String {
+    match avalue_ {
+        P::AttributeValue_::Value(_) => "an attribute value".to_string(),
+        P::AttributeValue_::ModuleAccess(chain) => format!("attribute value `{chain}`"),
+    }
+}
+
+fn resolved_attribute_value_description(result: &Option<E::AttributeValue>) -> String {
+    match result {
+        Some(sp!(_, E::AttributeValue_::Value(_))) => "a value".to_string(),
+        Some(sp!(_, E::AttributeValue_::Address(address))) => format!("address `{address}`"),
+        Some(sp!(_, E::AttributeValue_::Module(mident))) => format!("module `{mident}`"),
+        Some(sp!(_, E::AttributeValue_::ModuleAccess(access))) => {
+            format!("module access `{access}`")
+        }
+        None => "failed, see the reported error".to_string(),
+    }
+}
+
+fn resolved_module_access_description(result: &Option<ModuleAccessResult>) -> String {
+    match result {
+        Some(access_result!(sp!(_, E::ModuleAccess_::Name(name)), _, _)) => {
+            format!("the unqualified name `{name}`, resolved later by naming")
+        }
+        Some(access_result!(sp!(_, access), _, _)) => format!("`{access}`"),
+        None => "failed, see the reported error".to_string(),
+    }
+}
+
+fn resolved_module_ident_description(result: &Option<E::ModuleIdent>) -> String {
+    match result {
+        Some(mident) => format!("module `{mident}`"),
+        None => "failed, see the reported error".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        command_line::compiler::PASS_EXPANSION,
+        shared::{Flags, NumericalAddress, PathResolutionTarget},
+        Compiler,
+    };
+    use std::{
+        collections::BTreeMap,
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    /// Collects the logs of a compilation, to inspect the traces
+    #[derive(Clone, Default)]
+    struct Logs(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for Logs {
+        type Writer = Logs;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    const SOURCE: &str = "module 0x42::a { public fun f(): u64 { 1 } }
+module 0x42::b {
+    use 0x42::a;
+    public fun g(): u64 { a::f() }
+}
+";
+
+    fn trace(line: usize) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sources.move");
+        std::fs::write(&path, SOURCE).unwrap();
+        let target = PathResolutionTarget {
+            file: "sources.move".to_string(),
+            line,
+        };
+        let logs = Logs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .without_time()
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let (_files, res) = Compiler::from_files(
+                vec![path.to_string_lossy().to_string()],
+                vec![],
+                BTreeMap::<String, NumericalAddress>::new(),
+            )
+            .set_flags(Flags::empty().set_trace_path_resolution(Some(target)))
+            .run::<PASS_EXPANSION>()
+            .unwrap();
+            assert!(res.is_ok());
+        });
+        let logs = logs.0.lock().unwrap();
+        String::from_utf8(logs.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_trace_path_resolution() {
+        let logs = trace(4);
+        assert!(
+            logs.contains("sources.move:4:27: resolving `a::f` as a function"),
+            "{logs}"
+        );
+        assert!(
+            logs.contains("`a` is a module alias for `0x42::a`"),
+            "{logs}"
+        );
+        assert!(logs.contains("=> `0x42::a::f`"), "{logs}");
+        assert!(!logs.contains("sources.move:1:"), "{logs}");
+
+        // Lines without paths are not traced.
+        assert_eq!(trace(2), "");
+    }
+}
@@ -65,6 +65,12 @@ struct Context<'env, 'map> {
     // and dependency packages
     all_filter_alls: WarningFilters,
     pub path_expander: Option<Box<dyn PathExpander>>,
+    // Packages whose implicit aliases were checked, so that their errors are reported once per
+    // package rather than once per definition
+    checked_implicit_aliases: BTreeSet<Option<Symbol>>,
+    // For each alias declared by a use, the source to remove to drop that alias alone, used to
+    // suggest removing unnecessary aliases
+    alias_removals: BTreeMap<Loc, Loc>,
 }
 
 impl<'env, 'map> Context<'env, 'map> {
@@ -92,6 +98,8 @@ impl<'env, 'map> Context<'env, 'map> {
             is_source_definition: false,
             all_filter_alls,
             path_expander: None,
+            checked_implicit_aliases: BTreeSet::new(),
+            alias_removals: BTreeMap::new(),
         }
     }
 
@@ -272,6 +280,9 @@ fn unnecessary_alias_error(context: &mut Context, unnecessary: UnnecessaryAlias)
         // nothing to point to for the default case
         diag.add_secondary_label((prev, "The same alias was previously declared here"))
     }
+    if let Some(removal) = context.alias_removals.get(&loc) {
+        diag.add_fix(*removal, "");
+    }
     context.env().add_diag(diag);
 }
 
@@ -338,13 +349,28 @@ const IMPLICIT_SUI_MEMBERS: &[(Symbol, Symbol, ModuleMemberKind)] = &[
     ),
 ];
 
-fn default_aliases(context: &mut Context) -> AliasMapBuilder {
+/// The aliases in scope by default in a definition, at `def_loc`. These are the aliases of the
+/// Move Stdlib and of Sui, followed by the implicit aliases declared by the current package.
+/// Invalid implicit aliases are reported at the first definition of the package.
+fn default_aliases(context: &mut Context, def_loc: Loc) -> AliasMapBuilder {
     let current_package = context.current_package();
     let mut builder = context.new_alias_map_builder();
+    let implicit_aliases = context
+        .env()
+        .package_config(current_package)
+        .implicit_aliases
+        .clone();
+    let report = context.checked_implicit_aliases.insert(current_package);
     if !context
         .env()
         .supports_feature(current_package, FeatureGate::Move2024Paths)
     {
+        if report && !implicit_aliases.is_empty() {
+            let msg = "Implicit aliases declared by the package are only supported in Move 2024";
+            context
+                .env()
+                .add_diag(diag!(Declarations::InvalidImplicitAlias, (def_loc, msg)));
+        }
         return builder;
     }
     // Unused loc since these will not conflict and are implicit so no warnings are given
@@ -398,9 +424,69 @@ fn default_aliases(context: &mut Context) -> AliasMapBuilder {
             .add_implicit_member_alias(alias, mident, name, kind)
             .unwrap();
     }
+    for implicit_alias in implicit_aliases {
+        package_implicit_alias(context, &mut builder, def_loc, implicit_alias, report)
+    }
     builder
 }
 
+/// Adds an implicit alias declared by the current package. As it is not declared in source,
+/// errors are reported at the definition being expanded, if `report` is set.
+fn package_implicit_alias(
+    context: &mut Context,
+    builder: &mut AliasMapBuilder,
+    def_loc: Loc,
+    implicit_alias: ImplicitAlias,
+    report: bool,
+) {
+    let invalid = |context: &mut Context, msg: String| {
+        if report {
+            let msg = format!("Invalid implicit alias '{implicit_alias}' of this package. {msg}");
+            context
+                .env()
+                .add_diag(diag!(Declarations::InvalidImplicitAlias, (def_loc, msg)));
+        }
+    };
+    // Unused loc, as for the default aliases
+    let loc = Loc::invalid();
+    let ImplicitAlias {
+        address,
+        module,
+        member,
+    } = implicit_alias;
+    let Some(addr) = maybe_make_well_known_address(context, loc, address) else {
+        let msg = format!("Unbound address '{address}'");
+        invalid(context, msg);
+        return;
+    };
+    let mident = sp(loc, ModuleIdent_::new(addr, ModuleName(sp(loc, module))));
+    let Some(members) = context.defn_context.module_members.get(&mident) else {
+        let msg = format!("Unbound module '{mident}'");
+        invalid(context, msg);
+        return;
+    };
+    let result = match member {
+        None => builder.add_implicit_module_alias(sp(loc, module), mident),
+        Some(member) => {
+            let Some(kind) = members.get(&sp(loc, member)).copied() else {
+                let msg = format!("Unbound member '{member}' in module '{mident}'");
+                invalid(context, msg);
+                return;
+            };
+            let name = sp(loc, member);
+            builder.add_implicit_member_alias(name, mident, name, kind)
+        }
+    };
+    if result.is_err() {
+        let alias = member.unwrap_or(module);
+        let msg = format!(
+            "It conflicts with the alias '{alias}', which is already provided by default or by \
+             another implicit alias"
+        );
+        invalid(context, msg);
+    }
+}
+
 //**************************************************************************************************
 // Entry
 //**************************************************************************************************
@@ -474,7 +560,7 @@ pub fn program(
             .env()
             .supports_feature(package, FeatureGate::Move2024Paths)
         {
-            let mut path_expander = Move2024PathExpander::new();
+            let mut path_expander = Move2024PathExpander::new(context.env());
 
             let aliases = named_addr_map_to_alias_map_builder(&mut context, named_address_map);
 
@@ -490,7 +576,7 @@ pub fn program(
             context.path_expander = None;
         } else {
             context.defn_context.named_address_mapping = Some(named_address_map);
-            context.path_expander = Some(Box::new(LegacyPathExpander::new()));
+            context.path_expander = Some(Box::new(LegacyPathExpander::new(context.env())));
             definition(&mut context, &mut source_module_map, package, def);
             context.path_expander = None;
         }
@@ -509,7 +595,7 @@ pub fn program(
             .env()
             .supports_feature(package, FeatureGate::Move2024Paths)
         {
-            let mut path_expander = Move2024PathExpander::new();
+            let mut path_expander = Move2024PathExpander::new(context.env());
 
             let aliases = named_addr_map_to_alias_map_builder(&mut context, named_address_map);
             // should never fail
@@ -523,7 +609,7 @@ pub fn program(
             context.path_expander = None;
         } else {
             context.defn_context.named_address_mapping = Some(named_address_map);
-            context.path_expander = Some(Box::new(LegacyPathExpander::new()));
+            context.path_expander = Some(Box::new(LegacyPathExpander::new(context.env())));
             definition(&mut context, &mut lib_module_map, package, def);
             context.path_expander = None;
         }
@@ -554,7 +640,11 @@ fn definition(
     package_name: Option<Symbol>,
     def: P::Definition,
 ) {
-    let default_aliases = default_aliases(context);
+    let def_loc = match &def {
+        P::Definition::Module(m) => m.name.loc(),
+        P::Definition::Address(a) => a.addr.loc,
+    };
+    let default_aliases = default_aliases(context, def_loc);
     context.push_alias_scope(/* unused */ Loc::invalid(), default_aliases);
     match def {
         P::Definition::Module(mut m) => {
@@ -879,7 +969,11 @@ fn module_(
             }
             P::ModuleMember::Constant(c) => constant(context, &mut constants, c),
             P::ModuleMember::Struct(s) => struct_def(context, &mut structs, s),
-            P::ModuleMember::Spec(s) => context.spec_deprecated(s.loc, /* is_error */ false),
+            P::ModuleMember::Spec(s) => {
+                let mut diag = context.spec_deprecated_diag(s.loc, /* is_error */ false);
+                diag.add_fix(s.loc, "");
+                context.env().add_diag(diag)
+            }
         }
     }
     let mut use_funs = use_funs(context, use_funs_builder);
@@ -935,7 +1029,12 @@ fn check_visibility_modifiers(
                 }
                 diag!(Migration::RemoveFriend, (loc, friend_msg))
             } else {
-                diag!(Editions::DeprecatedFeature, (loc, friend_msg))
+                let mut diag = diag!(Editions::DeprecatedFeature, (loc, friend_msg));
+                for aloc in &friend_decl.attr_locs {
+                    diag.add_fix(*aloc, "");
+                }
+                diag.add_fix(loc, "");
+                diag
             };
             context.env().add_diag(diag);
         }
@@ -946,7 +1045,9 @@ fn check_visibility_modifiers(
             let diag = if edition == Edition::E2024_MIGRATION {
                 diag!(Migration::MakePubPackage, (loc, pub_msg))
             } else {
-                diag!(Editions::DeprecatedFeature, (loc, pub_msg))
+                let mut diag = diag!(Editions::DeprecatedFeature, (loc, pub_msg));
+                diag.add_fix(loc, E::Visibility::PACKAGE);
+                diag
             };
             context.env().add_diag(diag);
         }
@@ -1072,7 +1173,8 @@ fn gate_known_attribute(context: &mut Context, loc: Loc, known: &KnownAttribute)
         | KnownAttribute::Diagnostic(_)
         | KnownAttribute::DefinesPrimitive(_)
         | KnownAttribute::External(_)
-        | KnownAttribute::Syntax(_) => (),
+        | KnownAttribute::Syntax(_)
+        | KnownAttribute::Cfg(_) => (),
         KnownAttribute::Error(_) => {
             let pkg = context.current_package();
             context
@@ -1163,6 +1265,11 @@ fn attribute(
         match attribute_ {
             PA::Name(n) => EA::Name(n),
             PA::Assigned(n, v) => EA::Assigned(n, Box::new(context.attribute_value(*v)?)),
+            // `cfg` predicates are evaluated (and checked) when filtering the parsed program, and
+            // may repeat nested predicates, e.g. `all(not(..), not(..))`, so they are dropped here
+            PA::Parameterized(n, _) if n.value.as_str() == known_attributes::CfgAttribute::CFG => {
+                EA::Name(n)
+            }
             PA::Parameterized(n, sp!(_, pattrs_)) => {
                 let attrs = pattrs_
                     .into_iter()
@@ -1234,6 +1341,11 @@ fn warning_filter(context: &mut Context, attributes: &E::Attributes) -> WarningF
             prefixed_filters.push((DiagnosticAttribute::Allow, prefix, names));
         }
     }
+    // The allow policy only restricts the root package; dependencies are not checked
+    let check_allow_policy = context.is_source_definition && {
+        let pkg = context.current_package();
+        !context.env().package_config(pkg).is_dependency
+    };
     // Find the warning filter for each prefix+name instance
     for (diag_attr, prefix, names) in prefixed_filters {
         for sp!(nloc, n_) in names {
@@ -1257,6 +1369,16 @@ fn warning_filter(context: &mut Context, attributes: &E::Attributes) -> WarningF
                     .add_diag(diag!(Attributes::ValueWarning, (nloc, msg)));
                 continue;
             };
+            if check_allow_policy && !context.env().allow_policy_permits(prefix, n_) {
+                let msg = format!(
+                    "Suppressing '{}' is not permitted by the allow policy",
+                    format_allow_attr(prefix, n_)
+                );
+                context
+                    .env()
+                    .add_diag(diag!(Attributes::DisallowedFilter, (nloc, msg)));
+                continue;
+            }
             for f in filters {
                 warning_filters.add(f);
             }
@@ -1488,6 +1610,11 @@ fn use_(
         loc,
         attributes,
     } = u;
+    // A declaration with attributes is not removed, as its attributes would be left behind
+    let decl_removal = attributes.is_empty().then_some(loc);
+    context
+        .alias_removals
+        .extend(use_alias_removals(decl_removal, &u));
     let attributes = flatten_attributes(context, AttributePosition::Use, attributes);
     match u {
         P::Use::NestedModuleUses(address, use_decls) => {
@@ -1538,6 +1665,94 @@ fn use_(
     }
 }
 
+/// For each alias declared by the use `u`, the source to remove to drop that alias alone, if it
+/// can be removed without leaving the declaration malformed. `decl_removal` is the source to
+/// remove to drop the whole declaration, if it declares a single alias.
+fn use_alias_removals(decl_removal: Option<Loc>, u: &P::Use) -> Vec<(Loc, Loc)> {
+    let mut removals = vec![];
+    match u {
+        P::Use::ModuleUse(mident, muse) => {
+            module_use_alias_removals(&mut removals, decl_removal, mident.value.module, muse)
+        }
+        P::Use::NestedModuleUses(_, uses) => {
+            let items = uses
+                .iter()
+                .map(|(module, muse)| module_use_item(*module, muse))
+                .collect::<Vec<_>>();
+            for (i, (module, muse)) in uses.iter().enumerate() {
+                let removal = list_item_removal(&items, i, decl_removal);
+                module_use_alias_removals(&mut removals, removal, *module, muse);
+            }
+        }
+        P::Use::Fun { .. } => (),
+    }
+    removals
+}
+
+fn module_use_alias_removals(
+    removals: &mut Vec<(Loc, Loc)>,
+    removal: Option<Loc>,
+    module: P::ModuleName,
+    muse: &P::ModuleUse,
+) {
+    match muse {
+        P::ModuleUse::Module(alias_opt) => {
+            let alias = alias_opt.unwrap_or(module);
+            removals.extend(removal.map(|removal| (alias.loc(), removal)));
+        }
+        P::ModuleUse::Members(sub_uses) => {
+            let items = sub_uses
+                .iter()
+                .map(|(member, alias_opt)| (join_loc(*member, *alias_opt), true))
+                .collect::<Vec<_>>();
+            for (i, (member, alias_opt)) in sub_uses.iter().enumerate() {
+                let alias = alias_opt.unwrap_or(*member);
+                let removal = list_item_removal(&items, i, removal);
+                removals.extend(removal.map(|removal| (alias.loc, removal)));
+            }
+        }
+    }
+}
+
+/// The location of a module use in a list of them, and whether it ends where the use does, which
+/// is not known for a list of members as its closing brace is not located
+fn module_use_item(module: P::ModuleName, muse: &P::ModuleUse) -> (Loc, bool) {
+    match muse {
+        P::ModuleUse::Module(alias_opt) => (join_loc(module.0, alias_opt.map(|a| a.0)), true),
+        P::ModuleUse::Members(sub_uses) => {
+            let last = sub_uses
+                .last()
+                .map(|(member, alias_opt)| join_loc(*member, *alias_opt));
+            let loc = match last {
+                Some(last) => Loc::new(module.loc().file_hash(), module.loc().start(), last.end()),
+                None => module.loc(),
+            };
+            (loc, false)
+        }
+    }
+}
+
+/// The source to remove to drop the `i`th item of a comma separated list of `items`, along with
+/// its separator, or the source to remove to drop the whole list if it has a single item
+fn list_item_removal(items: &[(Loc, bool)], i: usize, list_removal: Option<Loc>) -> Option<Loc> {
+    if items.len() == 1 {
+        return list_removal;
+    }
+    let (loc, exact_end) = items[i];
+    if let Some((next, _)) = items.get(i + 1) {
+        return Some(Loc::new(loc.file_hash(), loc.start(), next.start()));
+    }
+    let (prev, prev_exact_end) = items[i - 1];
+    (exact_end && prev_exact_end).then(|| Loc::new(loc.file_hash(), prev.end(), loc.end()))
+}
+
+fn join_loc(name: Name, alias_opt: Option<Name>) -> Loc {
+    match alias_opt {
+        Some(alias) => Loc::new(name.loc.file_hash(), name.loc.start(), alias.loc.end()),
+        None => name.loc,
+    }
+}
+
 fn module_use(
     context: &mut Context,
     acc: &mut AliasMapBuilder,
@@ -3437,3 +3652,114 @@ fn restricted_name_error(case: NameCase, loc: Loc, restricted: &str) -> Diagnost
     );
     diag!(NameResolution::ReservedName, (loc, msg))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        diagnostics::codes::{Declarations, DiagnosticCode},
+        editions::Edition,
+        shared::{NumericalAddress, PackageConfig, PackagePaths},
+        Compiler,
+    };
+
+    const UTIL: &str = "module p::util { public fun helper(): u64 { 1 } }";
+    const OTHER: &str = "module p::other { public fun helper(): u64 { 2 } }";
+
+    /// Compiles the modules `sources` of package `p`, whose implicit aliases are `aliases`, and
+    /// returns the messages of the diagnostics, which are all invalid implicit alias errors.
+    fn invalid_implicit_aliases(
+        edition: Edition,
+        aliases: &[&str],
+        sources: &[&str],
+    ) -> Vec<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = sources
+            .iter()
+            .enumerate()
+            .map(|(i, source)| {
+                let path = dir.path().join(format!("m{i}.move"));
+                std::fs::write(&path, source).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let config = PackageConfig {
+            edition,
+            implicit_aliases: aliases.iter().map(|alias| alias.parse().unwrap()).collect(),
+            ..PackageConfig::default()
+        };
+        let package = PackagePaths {
+            name: Some(("p".into(), config)),
+            paths,
+            named_address_map: BTreeMap::from([(
+                "p".to_string(),
+                NumericalAddress::parse_str("0x42").unwrap(),
+            )]),
+        };
+        let (_files, result) = Compiler::from_package_paths(vec![package], vec![])
+            .unwrap()
+            .build()
+            .unwrap();
+        let diags = match result {
+            Ok((_units, warnings)) => warnings,
+            Err(diags) => diags,
+        };
+        let invalid_implicit_alias = Declarations::InvalidImplicitAlias.into_info();
+        diags
+            .into_vec()
+            .into_iter()
+            .map(|diag| {
+                assert_eq!(diag.info(), &invalid_implicit_alias);
+                diag.primary_msg().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_implicit_aliases_resolve() {
+        let uses = "module p::a { public fun f(): u64 { util::helper() + helper() } }";
+        let errors = invalid_implicit_aliases(
+            Edition::E2024_BETA,
+            &["p::util", "p::util::helper"],
+            &[UTIL, uses],
+        );
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_invalid_implicit_aliases_reported_once_per_package() {
+        let errors = invalid_implicit_aliases(
+            Edition::E2024_BETA,
+            &[
+                "q::util",
+                "p::missing",
+                "p::util::missing",
+                "p::util::helper",
+                "p::other::helper",
+            ],
+            &[UTIL, OTHER, "module p::a {}", "module p::b {}"],
+        );
+        assert_eq!(
+            errors,
+            [
+                "Invalid implicit alias 'q::util' of this package. Unbound address 'q'",
+                "Invalid implicit alias 'p::missing' of this package. Unbound module 'p::missing'",
+                "Invalid implicit alias 'p::util::missing' of this package. Unbound member 'missing' \
+                 in module 'p::util'",
+                "Invalid implicit alias 'p::other::helper' of this package. It conflicts with the \
+                 alias 'helper', which is already provided by default or by another implicit alias",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_implicit_aliases_legacy_edition() {
+        let errors =
+            invalid_implicit_aliases(Edition::LEGACY, &["p::util"], &[UTIL, "module p::a {}"]);
+        assert_eq!(
+            errors,
+            ["Implicit aliases declared by the package are only supported in Move 2024"]
+        );
+    }
+}
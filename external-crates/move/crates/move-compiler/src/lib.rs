@@ -28,6 +28,8 @@ mod to_bytecode;
 pub mod typing;
 pub mod unit_test;
 
+use once_cell::sync::OnceCell;
+
 pub use command_line::{
     compiler::{
         construct_pre_compiled_lib, generate_interface_files, output_compiled_units, Compiler,
@@ -38,3 +40,18 @@ pub use command_line::{
 };
 pub use parser::comments::{CommentMap, FileCommentMap, MatchedFileCommentMap};
 pub use shared::Flags;
+
+static COMPILER_VERSION: OnceCell<String> = OnceCell::new();
+
+/// Sets the version of the compiler, recorded in the build metadata of the packages it compiles.
+/// The version of this crate is not bumped with each release, so binaries embedding the compiler
+/// should set their own version (including e.g. their git revision) before compiling anything.
+/// Has no effect once the version has been set or read.
+pub fn set_compiler_version(version: impl Into<String>) {
+    let _ = COMPILER_VERSION.set(version.into());
+}
+
+/// The version of the compiler, as set by `set_compiler_version`, or the version of this crate
+pub fn compiler_version() -> &'static str {
+    COMPILER_VERSION.get_or_init(|| env!("CARGO_PKG_VERSION").to_string())
+}
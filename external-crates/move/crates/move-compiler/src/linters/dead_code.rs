@@ -0,0 +1,325 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags the code of a package that is not reachable from any of its entry points,
+//! i.e. its 'entry' functions, its Sui 'init' functions and its tests. Public functions that are
+//! not reachable are flagged, as are private functions and constants that are only used by code
+//! that is not reachable. Private functions and constants that are not used at all are already
+//! flagged by the compiler's unused function and constant warnings.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use move_symbol_pool::Symbol;
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    editions::Flavor,
+    expansion::ast::{
+        Attribute, AttributeValue_, Attribute_, ModuleAccess_, ModuleIdent, ModuleIdent_,
+        Visibility,
+    },
+    parser::ast::{ConstantName, FunctionName},
+    shared::{
+        program_info::TypingProgramInfo, unique_map::UniqueMap, CompilationEnv, Identifier, TName,
+    },
+    sui_mode,
+    typing::{
+        ast as T,
+        visitor::{TypingVisitor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    LinterDiagCategory, DEAD_CONSTANT_DIAG_CODE, DEAD_FUNCTION_DIAG_CODE, LINT_WARNING_PREFIX,
+    UNREACHABLE_PUBLIC_FUNCTION_DIAG_CODE,
+};
+
+const UNREACHABLE_PUBLIC_FUNCTION_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::DeadCode as u8,
+    UNREACHABLE_PUBLIC_FUNCTION_DIAG_CODE,
+    "unreachable public function",
+);
+
+const DEAD_FUNCTION_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::DeadCode as u8,
+    DEAD_FUNCTION_DIAG_CODE,
+    "function only called by unreachable code",
+);
+
+const DEAD_CONSTANT_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::DeadCode as u8,
+    DEAD_CONSTANT_DIAG_CODE,
+    "constant only used by unreachable code",
+);
+
+pub struct DeadCodeVisitor;
+
+/// A function or constant, by module and name
+type Member = (ModuleIdent_, Symbol);
+
+/// The functions called and the constants used by a function or constant
+#[derive(Default)]
+struct Uses {
+    functions: BTreeSet<Member>,
+    constants: BTreeSet<Member>,
+}
+
+struct UsesCollector<'a> {
+    module: ModuleIdent_,
+    uses: &'a mut Uses,
+}
+
+impl TypingVisitor for DeadCodeVisitor {
+    fn visit(
+        &mut self,
+        env: &mut CompilationEnv,
+        _program_info: &TypingProgramInfo,
+        program: &mut T::Program_,
+    ) {
+        let mut function_uses: BTreeMap<Member, Uses> = BTreeMap::new();
+        let mut constant_uses: BTreeMap<Member, Uses> = BTreeMap::new();
+        let mut roots = vec![];
+        for (mident, mdef) in program.modules.key_cloned_iter_mut() {
+            if !mdef.is_source_module {
+                continue;
+            }
+            let is_sui_mode = env.package_config(mdef.package_name).flavor == Flavor::Sui;
+            let is_test_module = mdef.attributes.is_test_or_test_only();
+            for (name, cdef) in mdef.constants.key_cloned_iter_mut() {
+                let mut uses = Uses::default();
+                UsesCollector::new(mident, &mut uses).visit_constant(mident, name, cdef);
+                constant_uses.insert((mident.value, name.value()), uses);
+            }
+            for (name, fdef) in mdef.functions.key_cloned_iter_mut() {
+                let mut uses = Uses::default();
+                // constants can be used as expected abort codes in the attributes of tests
+                attribute_constants(&fdef.attributes, &mut uses.constants);
+                UsesCollector::new(mident, &mut uses).visit_function(mident, name, fdef);
+                let member = (mident.value, name.value());
+                if is_test_module
+                    || fdef.attributes.is_test_or_test_only()
+                    || fdef.entry.is_some()
+                    || (is_sui_mode && name.value() == sui_mode::INIT_FUNCTION_NAME)
+                {
+                    roots.push(member);
+                }
+                function_uses.insert(member, uses);
+            }
+        }
+
+        let mut reachable = BTreeSet::new();
+        let mut live_constants = BTreeSet::new();
+        while let Some(member) = roots.pop() {
+            if !reachable.insert(member) {
+                continue;
+            }
+            if let Some(uses) = function_uses.get(&member) {
+                roots.extend(uses.functions.iter().copied());
+                live_constants.extend(uses.constants.iter().copied());
+            }
+        }
+        // constants can be used in the values of other constants
+        let mut live_worklist = live_constants.iter().copied().collect::<Vec<_>>();
+        while let Some(constant) = live_worklist.pop() {
+            let Some(uses) = constant_uses.get(&constant) else {
+                continue;
+            };
+            for used in &uses.constants {
+                if live_constants.insert(*used) {
+                    live_worklist.push(*used);
+                }
+            }
+        }
+
+        let called = function_uses
+            .values()
+            .flat_map(|uses| uses.functions.iter().copied())
+            .collect::<BTreeSet<_>>();
+        let used_constants = function_uses
+            .values()
+            .chain(constant_uses.values())
+            .flat_map(|uses| uses.constants.iter().copied())
+            .collect::<BTreeSet<_>>();
+
+        for (mident, mdef) in program.modules.key_cloned_iter() {
+            if !mdef.is_source_module || mdef.attributes.is_test_or_test_only() {
+                continue;
+            }
+            env.add_warning_filter_scope(mdef.warning_filter.clone());
+            for (name, cdef) in mdef.constants.key_cloned_iter() {
+                let member = (mident.value, name.value());
+                if live_constants.contains(&member) || !used_constants.contains(&member) {
+                    continue;
+                }
+                env.add_warning_filter_scope(cdef.warning_filter.clone());
+                let msg = format!(
+                    "The constant '{name}' is only used by code that is not reachable from any \
+                    'entry' function"
+                );
+                env.add_diag(diag!(DEAD_CONSTANT_DIAG, (name.loc(), msg)));
+                env.pop_warning_filter_scope();
+            }
+            for (name, fdef) in mdef.functions.key_cloned_iter() {
+                let member = (mident.value, name.value());
+                if reachable.contains(&member)
+                    || fdef.macro_.is_some()
+                    || fdef.attributes.is_test_or_test_only()
+                {
+                    continue;
+                }
+                let diag = match fdef.visibility {
+                    Visibility::Public(_) | Visibility::Friend(_) | Visibility::Package(_) => {
+                        let msg = format!(
+                            "The '{}' function '{name}' is not reachable from any 'entry' \
+                            function in the package",
+                            fdef.visibility
+                        );
+                        diag!(UNREACHABLE_PUBLIC_FUNCTION_DIAG, (name.loc(), msg))
+                    }
+                    Visibility::Internal if called.contains(&member) => {
+                        let msg = format!(
+                            "The function '{name}' is only called by code that is not reachable \
+                            from any 'entry' function"
+                        );
+                        diag!(DEAD_FUNCTION_DIAG, (name.loc(), msg))
+                    }
+                    // never called, which is reported as an unused function
+                    Visibility::Internal => continue,
+                };
+                env.add_warning_filter_scope(fdef.warning_filter.clone());
+                env.add_diag(diag);
+                env.pop_warning_filter_scope();
+            }
+            env.pop_warning_filter_scope();
+        }
+    }
+}
+
+impl<'a> UsesCollector<'a> {
+    fn new(mident: ModuleIdent, uses: &'a mut Uses) -> Self {
+        Self {
+            module: mident.value,
+            uses,
+        }
+    }
+}
+
+impl TypingVisitorContext for UsesCollector<'_> {
+    // diagnostics are only reported once all uses are collected
+    fn add_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+    fn pop_warning_filter_scope(&mut self) {}
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        match &exp.exp.value {
+            E::ModuleCall(call) => {
+                let FunctionName(name) = call.name;
+                self.uses.functions.insert((call.module.value, name.value));
+            }
+            E::Constant(m, ConstantName(name)) => {
+                self.uses.constants.insert((m.value, name.value));
+            }
+            E::ErrorConstant(Some(ConstantName(name))) => {
+                self.uses.constants.insert((self.module, name.value));
+            }
+            _ => (),
+        }
+        false
+    }
+}
+
+/// Collects the constants referred to by `attributes`.
+fn attribute_constants<K: TName>(
+    attributes: &UniqueMap<K, Attribute>,
+    constants: &mut BTreeSet<Member>,
+) {
+    for (_, _, attr) in attributes {
+        match &attr.value {
+            Attribute_::Name(_) => (),
+            Attribute_::Parameterized(_, attrs) => attribute_constants(attrs, constants),
+            Attribute_::Assigned(_, val) => {
+                let AttributeValue_::ModuleAccess(mod_access) = &val.value else {
+                    continue;
+                };
+                if let ModuleAccess_::ModuleAccess(mident, name) = mod_access.value {
+                    constants.insert((mident.value, name.value));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        linters::{dead_code_visitors, known_filters},
+        shared::NumericalAddress,
+        Compiler,
+    };
+
+    #[test]
+    fn test_dead_code() {
+        let sources = tempfile::tempdir().unwrap();
+        let path = sources.path().join("m.move");
+        std::fs::write(
+            &path,
+            r#"
+            module 0x42::m {
+                const USED: u64 = 0;
+                const DEAD: u64 = 1;
+
+                public entry fun entry_point(): u64 { used() }
+                fun used(): u64 { USED }
+
+                public fun unreachable(): u64 { dead() }
+                fun dead(): u64 { DEAD }
+
+                #[allow(lint(unreachable_public_function))]
+                public fun allowed(): u64 { 2 }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let (prefix, filters) = known_filters();
+        let (_files, units) = Compiler::from_files(
+            vec![path.to_string_lossy().to_string()],
+            vec![],
+            BTreeMap::<String, NumericalAddress>::new(),
+        )
+        .add_custom_known_filters(prefix, filters)
+        .add_visitors(dead_code_visitors())
+        .build()
+        .unwrap();
+        let (_units, warnings) = units.unwrap();
+        let mut messages = warnings
+            .into_vec()
+            .iter()
+            .map(|diag| diag.primary_msg().to_string())
+            .collect::<Vec<_>>();
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                "The 'public' function 'unreachable' is not reachable from any 'entry' function \
+                in the package",
+                "The constant 'DEAD' is only used by code that is not reachable from any 'entry' \
+                function",
+                "The function 'dead' is only called by code that is not reachable from any \
+                'entry' function",
+            ]
+        );
+    }
+}
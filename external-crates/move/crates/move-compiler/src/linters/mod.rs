@@ -3,7 +3,12 @@
 
 use move_symbol_pool::Symbol;
 
-use crate::{command_line::compiler::Visitor, diagnostics::codes::WarningFilter};
+use crate::{
+    command_line::compiler::Visitor, diagnostics::codes::WarningFilter,
+    typing::visitor::TypingVisitor,
+};
+
+pub mod dead_code;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LintLevel {
@@ -18,8 +23,48 @@ pub enum LintLevel {
 pub const ALLOW_ATTR_CATEGORY: &str = "lint";
 pub const LINT_WARNING_PREFIX: &str = "Lint ";
 
+pub const DEAD_CODE_FILTER_NAME: &str = "dead_code";
+pub const UNREACHABLE_PUBLIC_FUNCTION_FILTER_NAME: &str = "unreachable_public_function";
+pub const DEAD_FUNCTION_FILTER_NAME: &str = "dead_function";
+pub const DEAD_CONSTANT_FILTER_NAME: &str = "dead_constant";
+
+pub enum LinterDiagCategory {
+    // Numbered after the categories of the Sui linters, which share the lint warning prefix
+    DeadCode = 50,
+}
+
+pub const UNREACHABLE_PUBLIC_FUNCTION_DIAG_CODE: u8 = 1;
+pub const DEAD_FUNCTION_DIAG_CODE: u8 = 2;
+pub const DEAD_CONSTANT_DIAG_CODE: u8 = 3;
+
 pub fn known_filters() -> (Option<Symbol>, Vec<WarningFilter>) {
-    (Some(ALLOW_ATTR_CATEGORY.into()), vec![])
+    let filters = vec![
+        WarningFilter::All(Some(LINT_WARNING_PREFIX)),
+        WarningFilter::category(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::DeadCode as u8,
+            Some(DEAD_CODE_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::DeadCode as u8,
+            UNREACHABLE_PUBLIC_FUNCTION_DIAG_CODE,
+            Some(UNREACHABLE_PUBLIC_FUNCTION_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::DeadCode as u8,
+            DEAD_FUNCTION_DIAG_CODE,
+            Some(DEAD_FUNCTION_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::DeadCode as u8,
+            DEAD_CONSTANT_DIAG_CODE,
+            Some(DEAD_CONSTANT_FILTER_NAME),
+        ),
+    ];
+    (Some(ALLOW_ATTR_CATEGORY.into()), filters)
 }
 
 pub fn linter_visitors(level: LintLevel) -> Vec<Visitor> {
@@ -30,3 +75,9 @@ pub fn linter_visitors(level: LintLevel) -> Vec<Visitor> {
         }
     }
 }
+
+/// The whole-program dead code analysis is not run at any lint level, as public functions that
+/// are not reachable from the package's entry points may be meant to be called by other packages.
+pub fn dead_code_visitors() -> Vec<Visitor> {
+    vec![dead_code::DeadCodeVisitor.visitor()]
+}
@@ -21,6 +21,7 @@ use crate::{
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, BTreeSet, VecDeque},
     fmt,
@@ -41,7 +42,7 @@ pub struct Program_ {
     pub modules: UniqueMap<ModuleIdent, ModuleDefinition>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum Neighbor_ {
     Dependency,
     Friend,
@@ -52,7 +53,7 @@ pub type Neighbor = Spanned<Neighbor_>;
 // Use Funs
 //**************************************************************************************************
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UseFunKind {
     Explicit,
     // From a function declaration in the module
@@ -61,7 +62,7 @@ pub enum UseFunKind {
     UseAlias,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct UseFun {
     pub loc: Loc,
     pub attributes: Attributes,
@@ -83,7 +84,7 @@ pub type ResolvedUseFuns = BTreeMap<TypeName, UniqueMap<Name, UseFun>>;
 // Color for scopes of use funs and variables
 pub type Color = u16;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct UseFuns {
     pub color: Color,
     pub resolved: ResolvedUseFuns,
@@ -94,7 +95,7 @@ pub struct UseFuns {
 // Syntax Methods
 //**************************************************************************************************
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum SyntaxMethodKind_ {
     Index,
     IndexMut,
@@ -106,7 +107,7 @@ pub enum SyntaxMethodKind_ {
 
 pub type SyntaxMethodKind = Spanned<SyntaxMethodKind_>;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct SyntaxMethod {
     pub loc: Loc,
     pub public_visibility: Loc,
@@ -116,13 +117,13 @@ pub struct SyntaxMethod {
     // We don't track usage because we require these to be public.
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct IndexSyntaxMethods {
     pub index_mut: Option<Box<SyntaxMethod>>,
     pub index: Option<Box<SyntaxMethod>>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Default)]
+#[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
 pub struct SyntaxMethodEntry {
     pub index: Option<Box<IndexSyntaxMethods>>,
 }
@@ -154,7 +155,7 @@ pub struct ModuleDefinition {
 // Structs
 //**************************************************************************************************
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct StructDefinition {
     pub warning_filter: WarningFilters,
     // index in the original order as defined in the source file
@@ -165,13 +166,13 @@ pub struct StructDefinition {
     pub fields: StructFields,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct StructTypeParameter {
     pub param: TParam,
     pub is_phantom: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum StructFields {
     Defined(Fields<Type>),
     Native(Loc),
@@ -181,7 +182,7 @@ pub enum StructFields {
 // Functions
 //**************************************************************************************************
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionSignature {
     pub type_parameters: Vec<TParam>,
     pub parameters: Vec<(Mutability, Var, Type)>,
@@ -227,7 +228,7 @@ pub struct Constant {
 // Types
 //**************************************************************************************************
 
-#[derive(Debug, PartialEq, Clone, Copy, PartialOrd, Eq, Ord)]
+#[derive(Debug, PartialEq, Clone, Copy, PartialOrd, Eq, Ord, Serialize, Deserialize)]
 pub enum BuiltinTypeName_ {
     // address
     Address,
@@ -252,7 +253,7 @@ pub enum BuiltinTypeName_ {
 }
 pub type BuiltinTypeName = Spanned<BuiltinTypeName_>;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum TypeName_ {
     // exp-list/tuple type
@@ -262,20 +263,20 @@ pub enum TypeName_ {
 }
 pub type TypeName = Spanned<TypeName_>;
 
-#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Serialize, Deserialize)]
 pub struct TParamID(pub u64);
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TParam {
     pub id: TParamID,
     pub user_specified_name: Name,
     pub abilities: AbilitySet,
 }
 
-#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Serialize, Deserialize)]
 pub struct TVar(u64);
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum Type_ {
     Unit,
@@ -293,7 +294,7 @@ pub type Type = Spanned<Type_>;
 // Expressions
 //**************************************************************************************************
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Ord)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Var_ {
     pub name: Symbol,
     pub id: u16,
@@ -301,7 +302,7 @@ pub struct Var_ {
 }
 pub type Var = Spanned<Var_>;
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct BlockLabel {
     pub label: Var,
     pub is_implicit: bool,
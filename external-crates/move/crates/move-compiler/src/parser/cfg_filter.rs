@@ -0,0 +1,163 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_symbol_pool::Symbol;
+
+use crate::{
+    diag,
+    parser::{
+        ast as P,
+        filter::{filter_program, FilterContext},
+    },
+    shared::{known_attributes::CfgAttribute, CompilationEnv},
+};
+
+struct Context<'env> {
+    env: &'env mut CompilationEnv,
+    current_package: Option<Symbol>,
+}
+
+impl<'env> Context<'env> {
+    fn new(env: &'env mut CompilationEnv) -> Self {
+        Self {
+            env,
+            current_package: None,
+        }
+    }
+}
+
+impl FilterContext for Context<'_> {
+    fn set_current_package(&mut self, package: Option<Symbol>) {
+        self.current_package = package;
+    }
+
+    fn set_is_source_def(&mut self, _is_source_def: bool) {}
+
+    // An AST element should be removed if:
+    // * It is annotated #[cfg(<predicate>)] and the predicate does not hold for the features
+    //   enabled for the current package
+    fn should_remove_by_attributes(&mut self, attrs: &[P::Attributes]) -> bool {
+        let mut should_remove = false;
+        for attr in attrs.iter().flat_map(|attrs| &attrs.value) {
+            let P::Attribute_::Parameterized(name, predicates) = &attr.value else {
+                if attr.value.attribute_name().value.as_str() == CfgAttribute::CFG {
+                    let msg = format!(
+                        "Expected a predicate for the '{}' attribute, \
+                        e.g. '#[{}({} = <name>)]'",
+                        CfgAttribute::CFG,
+                        CfgAttribute::CFG,
+                        CfgAttribute::FEATURE,
+                    );
+                    self.env
+                        .add_diag(diag!(Attributes::InvalidUsage, (attr.loc, msg)));
+                }
+                continue;
+            };
+            if name.value.as_str() != CfgAttribute::CFG {
+                continue;
+            }
+            // Malformed predicates are reported and treated as holding, so that the element is
+            // kept and no spurious errors are reported for its uses
+            let holds = match &predicates.value[..] {
+                [predicate] => self.eval(predicate).unwrap_or(true),
+                _ => {
+                    let msg = format!(
+                        "Expected exactly one predicate for the '{}' attribute",
+                        CfgAttribute::CFG
+                    );
+                    self.env
+                        .add_diag(diag!(Attributes::InvalidValue, (predicates.loc, msg)));
+                    true
+                }
+            };
+            should_remove |= !holds;
+        }
+        should_remove
+    }
+}
+
+impl Context<'_> {
+    fn enabled(&self, feature: Symbol) -> bool {
+        self.env
+            .package_config(self.current_package)
+            .features
+            .contains(&feature)
+    }
+
+    // Evaluates a single predicate, returning `None` (after reporting an error) if it is malformed
+    fn eval(&mut self, predicate: &P::Attribute) -> Option<bool> {
+        use P::Attribute_ as A;
+        match &predicate.value {
+            A::Assigned(n, value) if n.value.as_str() == CfgAttribute::FEATURE => {
+                let feature = self.feature_name(value)?;
+                Some(self.enabled(feature))
+            }
+            A::Parameterized(n, inner) if n.value.as_str() == CfgAttribute::NOT => {
+                match &inner.value[..] {
+                    [p] => self.eval(p).map(|b| !b),
+                    _ => {
+                        let msg =
+                            format!("Expected exactly one predicate for '{}'", CfgAttribute::NOT);
+                        self.env
+                            .add_diag(diag!(Attributes::InvalidValue, (inner.loc, msg)));
+                        None
+                    }
+                }
+            }
+            A::Parameterized(n, inner) if n.value.as_str() == CfgAttribute::ALL => {
+                let results = inner.value.iter().map(|p| self.eval(p)).collect::<Vec<_>>();
+                results.into_iter().try_fold(true, |acc, b| Some(acc && b?))
+            }
+            A::Parameterized(n, inner) if n.value.as_str() == CfgAttribute::ANY => {
+                let results = inner.value.iter().map(|p| self.eval(p)).collect::<Vec<_>>();
+                results
+                    .into_iter()
+                    .try_fold(false, |acc, b| Some(acc || b?))
+            }
+            _ => {
+                let msg = format!(
+                    "Invalid '{}' predicate. Expected '{} = <name>', '{}(..)', '{}(..)', or '{}(..)'",
+                    CfgAttribute::CFG,
+                    CfgAttribute::FEATURE,
+                    CfgAttribute::NOT,
+                    CfgAttribute::ALL,
+                    CfgAttribute::ANY,
+                );
+                self.env
+                    .add_diag(diag!(Attributes::InvalidValue, (predicate.loc, msg)));
+                None
+            }
+        }
+    }
+
+    fn feature_name(&mut self, value: &P::AttributeValue) -> Option<Symbol> {
+        match &value.value {
+            P::AttributeValue_::ModuleAccess(sp!(_, P::NameAccessChain_::Single(entry)))
+                if entry.tyargs.is_none() && entry.is_macro.is_none() =>
+            {
+                Some(entry.name.value)
+            }
+            P::AttributeValue_::Value(sp!(_, P::Value_::ByteString(s))) => Some(*s),
+            _ => {
+                let msg = format!(
+                    "Invalid feature name. Expected an identifier, e.g. '{} = my_feature'",
+                    CfgAttribute::FEATURE
+                );
+                self.env
+                    .add_diag(diag!(Attributes::InvalidValue, (value.loc, msg)));
+                None
+            }
+        }
+    }
+}
+
+//***************************************************************************
+// Filtering of cfg-annotated module members
+//***************************************************************************
+
+// This filters out all AST elements annotated with a `cfg` attribute whose predicate does not
+// hold for the features enabled in the package's config
+pub fn program(compilation_env: &mut CompilationEnv, prog: P::Program) -> P::Program {
+    let mut context = Context::new(compilation_env);
+    filter_program(&mut context, prog)
+}
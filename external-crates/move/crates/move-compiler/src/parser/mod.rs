@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod ast;
+pub(crate) mod cfg_filter;
 pub mod comments;
 pub(crate) mod filter;
 pub mod keywords;
@@ -0,0 +1,590 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The artifact cache stores the bytecode and source map generated for each source module, and
+//! the typed ASTs of the dependency modules, on disk, so that rebuilding a package after an edit
+//! only type checks the modules of the package and only generates bytecode for the modules
+//! affected by the edit.
+//!
+//! Each source module is keyed by a fingerprint of the file it is defined in, the flags and
+//! package configuration it is compiled with, the addresses it uses, and the fingerprints of the
+//! modules it depends on. Editing a file therefore invalidates the modules defined in it and the
+//! modules depending on them, directly or transitively, but no other module. The front end still
+//! runs on all source modules, as its diagnostics are reported on every build.
+//!
+//! The typed ASTs of the dependency modules are cached together, keyed by a fingerprint of all of
+//! their naming ASTs, as dependencies cannot depend on the modules of the package and their
+//! warnings are never reported. Changing any dependency therefore type checks all of them again.
+//!
+//! Every key includes the identity of the compiler binary, so that artifacts are never reused by
+//! another compiler. Entries are evicted once they have not been used by a build for
+//! `MAX_UNUSED_AGE`.
+
+use crate::{
+    compiled_unit::AnnotatedCompiledUnit,
+    expansion::ast::{Address, ModuleIdent, ModuleIdent_},
+    naming::ast as N,
+    shared::{unique_map::UniqueMap, CompilationEnv},
+    typing::ast as T,
+    FullyCompiledProgram,
+};
+use anyhow::Context;
+use move_binary_format::{file_format::CompiledModule, file_format_common::VERSION_MAX};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::files::FileHash;
+use move_symbol_pool::Symbol;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Version of the on-disk cache format, also part of every fingerprint so that bumping it
+/// invalidates all cached artifacts
+const ARTIFACT_CACHE_VERSION: u64 = 2;
+
+const BYTECODE_FILE_EXTENSION: &str = "mvc";
+const TYPED_AST_FILE_EXTENSION: &str = "mvt";
+
+/// Entries not used by any build for this long are evicted from the cache
+const MAX_UNUSED_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Identifies the compiler binary, as artifacts generated by another compiler (or another build of
+/// the same compiler) cannot be reused
+static COMPILER_IDENTITY: Lazy<String> = Lazy::new(|| {
+    let binary = std::env::current_exe()
+        .and_then(|path| {
+            let metadata = std::fs::metadata(&path)?;
+            let modified = metadata
+                .modified()?
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default();
+            Ok(format!(
+                "{} {} {}",
+                path.display(),
+                metadata.len(),
+                modified.as_nanos()
+            ))
+        })
+        .unwrap_or_default();
+    format!("{} {VERSION_MAX} {binary}", crate::compiler_version())
+});
+
+pub struct ArtifactCache {
+    dir: PathBuf,
+    fingerprints: BTreeMap<ModuleIdent_, String>,
+    /// Fingerprint of the dependency modules of the program, if their typed ASTs can be cached
+    dependencies_fingerprint: Option<String>,
+    /// Whether the typed ASTs of the dependency modules were read from the cache
+    dependencies_cached: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedModule {
+    version: u64,
+    module: Vec<u8>,
+    source_map: SourceMap,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedTypedModules {
+    version: u64,
+    modules: Vec<(ModuleIdent, T::ModuleDefinition)>,
+}
+
+impl ArtifactCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            fingerprints: BTreeMap::new(),
+            dependencies_fingerprint: None,
+            dependencies_cached: false,
+        }
+    }
+
+    /// Returns the cached bytecode and source map of the module, if the module has not changed
+    /// since it was cached. Unreadable cache entries are treated as missing.
+    pub fn get(&self, mident: &ModuleIdent) -> Option<(CompiledModule, SourceMap)> {
+        let path = self.bytecode_path(mident)?;
+        let bytes = std::fs::read(&path).ok()?;
+        let cached: CachedModule = bcs::from_bytes(&bytes).ok()?;
+        if cached.version != ARTIFACT_CACHE_VERSION {
+            return None;
+        }
+        let module = CompiledModule::deserialize_with_defaults(&cached.module).ok()?;
+        touch(&path);
+        Some((module, cached.source_map))
+    }
+
+    /// Caches the bytecode and source map of a compiled source module.
+    pub fn put(&self, unit: &AnnotatedCompiledUnit) -> anyhow::Result<()> {
+        let Some(path) = self.bytecode_path(&unit.module_ident()) else {
+            return Ok(());
+        };
+        let named_module = &unit.named_module;
+        let cached = CachedModule {
+            version: ARTIFACT_CACHE_VERSION,
+            module: named_module.serialize(Some(named_module.module.version)),
+            source_map: named_module.source_map.clone(),
+        };
+        self.write(&path, &bcs::to_bytes(&cached)?)
+    }
+
+    fn bytecode_path(&self, mident: &ModuleIdent) -> Option<PathBuf> {
+        let fingerprint = self.fingerprints.get(&mident.value)?;
+        Some(self.path(fingerprint, BYTECODE_FILE_EXTENSION))
+    }
+
+    fn typed_ast_path(&self) -> Option<PathBuf> {
+        let fingerprint = self.dependencies_fingerprint.as_ref()?;
+        Some(self.path(fingerprint, TYPED_AST_FILE_EXTENSION))
+    }
+
+    fn path(&self, fingerprint: &str, extension: &str) -> PathBuf {
+        self.dir.join(format!("{fingerprint}.{extension}"))
+    }
+
+    fn write(&self, path: &Path, bytes: &[u8]) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Cannot create artifact cache {}", self.dir.display()))?;
+        // Written to a temporary file first, so concurrent builds never read a partial entry.
+        let file = tempfile::NamedTempFile::new_in(&self.dir)?;
+        std::fs::write(file.path(), bytes)?;
+        file.persist(path)
+            .with_context(|| format!("Cannot write artifact cache entry {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Removes the entries that are not used by the current build and have not been used by any
+    /// build for `MAX_UNUSED_AGE`. Failing to remove an entry only means it is kept longer.
+    fn evict(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let live: BTreeSet<&str> = self
+            .fingerprints
+            .values()
+            .chain(&self.dependencies_fingerprint)
+            .map(String::as_str)
+            .collect();
+        let now = SystemTime::now();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_entry = path.extension().is_some_and(|extension| {
+                extension == BYTECODE_FILE_EXTENSION || extension == TYPED_AST_FILE_EXTENSION
+            });
+            let is_live = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| live.contains(stem));
+            if !is_entry || is_live {
+                continue;
+            }
+            let unused_for = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok());
+            if unused_for.is_some_and(|age| age > MAX_UNUSED_AGE) {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+    }
+}
+
+/// Marks a cache entry as used by the current build, so that it is not evicted.
+fn touch(path: &Path) {
+    let _ = std::fs::File::options()
+        .append(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+}
+
+/// Returns the cached typed ASTs of the dependency modules of the program, if an artifact cache is
+/// set and none of them has changed since they were cached.
+pub fn cached_dependency_modules(
+    compilation_env: &mut CompilationEnv,
+    pre_compiled_lib: Option<&FullyCompiledProgram>,
+    modules: &UniqueMap<ModuleIdent, N::ModuleDefinition>,
+) -> Option<UniqueMap<ModuleIdent, T::ModuleDefinition>> {
+    compilation_env.artifact_cache()?;
+    // Modules of a pre-compiled library are not part of the program, so they cannot be
+    // fingerprinted.
+    if pre_compiled_lib.is_some() {
+        return None;
+    }
+    let mut key = format!(
+        "{ARTIFACT_CACHE_VERSION}\n{}\n{:?}\n",
+        *COMPILER_IDENTITY,
+        compilation_env.flags()
+    );
+    let mut has_dependencies = false;
+    for (mident, mdef) in modules.key_cloned_iter() {
+        if !is_dependency(compilation_env, mdef.package_name, mdef.is_source_module) {
+            continue;
+        }
+        has_dependencies = true;
+        let config = compilation_env.package_config(mdef.package_name);
+        writeln!(key, "{}", address_key(&mident.value.address)).unwrap();
+        writeln!(key, "{:?} {config:?}", mdef.package_name).unwrap();
+        writeln!(key, "{mdef:?}").unwrap();
+    }
+    if !has_dependencies {
+        return None;
+    }
+    let cache = compilation_env.artifact_cache_mut().unwrap();
+    cache.dependencies_fingerprint = Some(FileHash::new(&key).to_string());
+
+    let path = cache.typed_ast_path()?;
+    let bytes = std::fs::read(&path).ok()?;
+    let cached: CachedTypedModules = bcs::from_bytes(&bytes).ok()?;
+    if cached.version != ARTIFACT_CACHE_VERSION {
+        return None;
+    }
+    let modules = UniqueMap::maybe_from_iter(cached.modules.into_iter()).ok()?;
+    touch(&path);
+    cache.dependencies_cached = true;
+    Some(modules)
+}
+
+/// Caches the typed ASTs of the dependency modules of the program, unless they were read from the
+/// cache. They are not cached if any of them depends on a module of the package.
+pub fn record_dependency_modules(compilation_env: &CompilationEnv, prog: &T::Program) {
+    let Some(cache) = compilation_env.artifact_cache() else {
+        return;
+    };
+    if cache.dependencies_cached {
+        return;
+    }
+    let Some(path) = cache.typed_ast_path() else {
+        return;
+    };
+    let mut modules = vec![];
+    for (mident, mdef) in prog.inner.modules.key_cloned_iter() {
+        if !is_dependency(compilation_env, mdef.package_name, mdef.is_source_module) {
+            continue;
+        }
+        for (neighbor, _) in mdef.immediate_neighbors.key_cloned_iter() {
+            let Some(neighbor_def) = prog.inner.modules.get(&neighbor) else {
+                continue;
+            };
+            if !is_dependency(
+                compilation_env,
+                neighbor_def.package_name,
+                neighbor_def.is_source_module,
+            ) {
+                return;
+            }
+        }
+        modules.push((mident, mdef.clone()));
+    }
+    let cached = CachedTypedModules {
+        version: ARTIFACT_CACHE_VERSION,
+        modules,
+    };
+    // Failing to cache the modules, e.g. as their ASTs are too deep to be serialized, only means
+    // they are type checked again on the next build
+    if let Ok(bytes) = bcs::to_bytes(&cached) {
+        let _ = cache.write(&path, &bytes);
+    }
+}
+
+/// Dependency modules are either not compiled from source, or belong to dependency packages.
+fn is_dependency(
+    compilation_env: &CompilationEnv,
+    package: Option<Symbol>,
+    is_source_module: bool,
+) -> bool {
+    !is_source_module || compilation_env.package_config(package).is_dependency
+}
+
+/// Computes the fingerprints of the modules of the program, if an artifact cache is set, and
+/// evicts the entries unused for too long.
+pub fn record_fingerprints(
+    compilation_env: &mut CompilationEnv,
+    pre_compiled_lib: Option<&FullyCompiledProgram>,
+    prog: &T::Program,
+) {
+    if compilation_env.artifact_cache().is_none() {
+        return;
+    }
+    let mut fingerprints = BTreeMap::new();
+    for (mident, _) in prog.inner.modules.key_cloned_iter() {
+        fingerprint(
+            compilation_env,
+            pre_compiled_lib,
+            prog,
+            &mut fingerprints,
+            &mident,
+        );
+    }
+    let cache = compilation_env.artifact_cache_mut().unwrap();
+    cache.fingerprints = fingerprints;
+    cache.evict();
+}
+
+fn fingerprint(
+    compilation_env: &CompilationEnv,
+    pre_compiled_lib: Option<&FullyCompiledProgram>,
+    prog: &T::Program,
+    fingerprints: &mut BTreeMap<ModuleIdent_, String>,
+    mident: &ModuleIdent,
+) -> String {
+    if let Some(fingerprint) = fingerprints.get(&mident.value) {
+        return fingerprint.clone();
+    }
+    // Guards against dependency cycles, which are reported by typing.
+    fingerprints.insert(mident.value, String::new());
+
+    let mut key = format!(
+        "{ARTIFACT_CACHE_VERSION}\n{}\n{}::{}\n",
+        *COMPILER_IDENTITY,
+        address_key(&mident.value.address),
+        mident.value.module
+    );
+    let mdef = match prog.inner.modules.get(mident) {
+        Some(mdef) => Some(mdef),
+        None => pre_compiled_lib.and_then(|lib| lib.typing.inner.modules.get(mident)),
+    };
+    if let Some(mdef) = mdef {
+        let config = compilation_env.package_config(mdef.package_name);
+        writeln!(key, "{:?}", compilation_env.flags()).unwrap();
+        writeln!(key, "{:?} {config:?}", mdef.package_name).unwrap();
+        writeln!(key, "{}", mdef.loc.file_hash()).unwrap();
+        for address in &mdef.used_addresses {
+            writeln!(key, "{}", address_key(address)).unwrap();
+        }
+        for (neighbor, _) in mdef.immediate_neighbors.key_cloned_iter() {
+            let neighbor_fingerprint = fingerprint(
+                compilation_env,
+                pre_compiled_lib,
+                prog,
+                fingerprints,
+                &neighbor,
+            );
+            writeln!(key, "{neighbor_fingerprint}").unwrap();
+        }
+    }
+    let fingerprint = FileHash::new(&key).to_string();
+    fingerprints.insert(mident.value, fingerprint.clone());
+    fingerprint
+}
+
+/// Includes both the name and the value of named addresses, as changing the value of a named
+/// address changes the bytecode of the modules using it.
+fn address_key(address: &Address) -> String {
+    match address {
+        Address::Numerical {
+            name: Some(name),
+            value,
+            ..
+        } => format!("{name}={}", value.value),
+        Address::Numerical { value, .. } => value.value.to_string(),
+        Address::NamedUnassigned(name) => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shared::NumericalAddress, Compiler};
+    use std::path::Path;
+
+    fn build(files: &[PathBuf], cache_dir: Option<&Path>) -> Vec<(String, Vec<u8>)> {
+        build_with_deps(files, &[], cache_dir)
+    }
+
+    fn build_with_deps(
+        files: &[PathBuf],
+        deps: &[PathBuf],
+        cache_dir: Option<&Path>,
+    ) -> Vec<(String, Vec<u8>)> {
+        let paths = |files: &[PathBuf]| {
+            files
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect()
+        };
+        let mut compiler = Compiler::from_files(
+            paths(files),
+            paths(deps),
+            BTreeMap::<String, NumericalAddress>::new(),
+        );
+        if let Some(dir) = cache_dir {
+            compiler = compiler.set_artifact_cache_dir(dir.to_path_buf());
+        }
+        let (_files, units) = compiler.build().unwrap();
+        let (units, _warnings) = units.unwrap();
+        units
+            .into_iter()
+            .map(|unit| {
+                let unit = unit.into_compiled_unit();
+                (unit.name.to_string(), unit.serialize(None))
+            })
+            .collect()
+    }
+
+    fn cache_entries(dir: &Path) -> usize {
+        std::fs::read_dir(dir).map_or(0, |entries| entries.count())
+    }
+
+    fn typed_ast_entries(dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().unwrap() == TYPED_AST_FILE_EXTENSION)
+            .collect()
+    }
+
+    fn write_files<const N: usize>(dir: &Path, files: [(&str, &str); N]) -> [PathBuf; N] {
+        files.map(|(name, contents)| {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        })
+    }
+
+    #[test]
+    fn test_artifact_cache() {
+        let sources = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        let files = [
+            ("a.move", "module 0x42::a { public fun f(): u64 { 1 } }"),
+            (
+                "b.move",
+                "module 0x42::b { public fun g(): u64 { 0x42::a::f() } }",
+            ),
+            ("c.move", "module 0x42::c { public fun h(): u64 { 2 } }"),
+        ]
+        .map(|(name, contents)| {
+            let path = sources.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        });
+
+        let expected = build(&files, None);
+        assert_eq!(build(&files, Some(cache.path())), expected);
+        assert_eq!(cache_entries(cache.path()), 3);
+
+        // Rebuilding from the cache produces the same bytecode, without adding entries.
+        assert_eq!(build(&files, Some(cache.path())), expected);
+        assert_eq!(cache_entries(cache.path()), 3);
+
+        // Editing `a` invalidates `a` and `b`, which depends on it, but not `c`.
+        std::fs::write(
+            &files[0],
+            "module 0x42::a { public fun f(): u64 { 3 } public fun e() {} }",
+        )
+        .unwrap();
+        let expected = build(&files, None);
+        assert_eq!(build(&files, Some(cache.path())), expected);
+        assert_eq!(cache_entries(cache.path()), 5);
+    }
+
+    #[test]
+    fn test_artifact_cache_dependencies() {
+        let sources = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        let [d, e, a] = write_files(
+            sources.path(),
+            [
+                ("d.move", "module 0x42::d { public fun f(): u64 { 1 } }"),
+                (
+                    "e.move",
+                    "module 0x42::e { public fun g(): u64 { 0x42::d::f() } }",
+                ),
+                (
+                    "a.move",
+                    "module 0x42::a { public fun h(): u64 { 0x42::e::g() } }",
+                ),
+            ],
+        );
+        let deps = [d.clone(), e];
+
+        // The typed ASTs of the dependencies are cached together.
+        let expected = build_with_deps(&[a.clone()], &deps, None);
+        assert_eq!(
+            build_with_deps(&[a.clone()], &deps, Some(cache.path())),
+            expected
+        );
+        let typed_asts = typed_ast_entries(cache.path());
+        assert_eq!(typed_asts.len(), 1);
+        let cached: CachedTypedModules =
+            bcs::from_bytes(&std::fs::read(&typed_asts[0]).unwrap()).unwrap();
+        let names: Vec<_> = cached
+            .modules
+            .iter()
+            .map(|(mident, _)| mident.value.module.to_string())
+            .collect();
+        assert_eq!(names, ["d", "e"]);
+
+        // Rebuilding with the cached typed ASTs produces the same bytecode, without adding entries.
+        assert_eq!(
+            build_with_deps(&[a.clone()], &deps, Some(cache.path())),
+            expected
+        );
+        assert_eq!(typed_ast_entries(cache.path()), typed_asts);
+
+        // Editing a dependency invalidates the typed ASTs of all of them.
+        std::fs::write(&d, "module 0x42::d { public fun f(): u64 { 2 } }").unwrap();
+        let expected = build_with_deps(&[a.clone()], &deps, None);
+        assert_eq!(build_with_deps(&[a], &deps, Some(cache.path())), expected);
+        assert_eq!(typed_ast_entries(cache.path()).len(), 2);
+    }
+
+    #[test]
+    fn test_artifact_cache_eviction() {
+        let sources = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        let files = write_files(
+            sources.path(),
+            [("a.move", "module 0x42::a { public fun f(): u64 { 1 } }")],
+        );
+        build(&files, Some(cache.path()));
+        let live = std::fs::read_dir(cache.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+
+        // Entries of the current build are kept however old they are, as are other entries used
+        // recently and files that are not cache entries.
+        let unused_for = |path: &Path, age: Duration| {
+            std::fs::File::options()
+                .append(true)
+                .open(path)
+                .unwrap()
+                .set_modified(SystemTime::now() - age)
+                .unwrap();
+        };
+        let stale = cache
+            .path()
+            .join(format!("stale.{BYTECODE_FILE_EXTENSION}"));
+        let stale_typed = cache
+            .path()
+            .join(format!("stale.{TYPED_AST_FILE_EXTENSION}"));
+        let recent = cache
+            .path()
+            .join(format!("recent.{BYTECODE_FILE_EXTENSION}"));
+        let other = cache.path().join("other");
+        for path in [&stale, &stale_typed, &recent, &other] {
+            std::fs::write(path, []).unwrap();
+        }
+        let old = MAX_UNUSED_AGE + Duration::from_secs(60);
+        for path in [&live, &stale, &stale_typed, &other] {
+            unused_for(path, old);
+        }
+        unused_for(&recent, MAX_UNUSED_AGE / 2);
+
+        build(&files, Some(cache.path()));
+        assert!(live.exists());
+        assert!(!stale.exists());
+        assert!(!stale_typed.exists());
+        assert!(recent.exists());
+        assert!(other.exists());
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::{collections::BTreeSet, fmt};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -16,7 +17,7 @@ pub enum AttributePosition {
     Spec,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum KnownAttribute {
     Testing(TestingAttribute),
     Verification(VerificationAttribute),
@@ -26,9 +27,10 @@ pub enum KnownAttribute {
     External(ExternalAttribute),
     Syntax(SyntaxAttribute),
     Error(ErrorAttribute),
+    Cfg(CfgAttribute),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TestingAttribute {
     // Can be called by other testing code, and included in compilation in test mode
     TestOnly,
@@ -38,39 +40,42 @@ pub enum TestingAttribute {
     ExpectedFailure,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum VerificationAttribute {
     // deprecated spec only annotation
     VerifyOnly,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum NativeAttribute {
     // It is a fake native function that actually compiles to a bytecode instruction
     BytecodeInstruction,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum DiagnosticAttribute {
     Allow,
     // Deprecated lint allow syntax
     LintAllow,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum SyntaxAttribute {
     Syntax,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct DefinesPrimitive;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ExternalAttribute;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ErrorAttribute;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CfgAttribute;
+
 impl AttributePosition {
     const ALL: &'static [Self] = &[
         Self::AddressBlock,
@@ -98,6 +103,7 @@ impl KnownAttribute {
             ExternalAttribute::EXTERNAL => ExternalAttribute.into(),
             SyntaxAttribute::SYNTAX => SyntaxAttribute::Syntax.into(),
             ErrorAttribute::ERROR => ErrorAttribute.into(),
+            CfgAttribute::CFG => CfgAttribute.into(),
             _ => return None,
         })
     }
@@ -112,6 +118,7 @@ impl KnownAttribute {
             Self::External(a) => a.name(),
             Self::Syntax(a) => a.name(),
             Self::Error(a) => a.name(),
+            Self::Cfg(a) => a.name(),
         }
     }
 
@@ -125,6 +132,7 @@ impl KnownAttribute {
             Self::External(a) => a.expected_positions(),
             Self::Syntax(a) => a.expected_positions(),
             Self::Error(a) => a.expected_positions(),
+            Self::Cfg(a) => a.expected_positions(),
         }
     }
 }
@@ -317,6 +325,33 @@ impl ErrorAttribute {
     }
 }
 
+impl CfgAttribute {
+    pub const CFG: &'static str = "cfg";
+    pub const FEATURE: &'static str = "feature";
+    pub const NOT: &'static str = "not";
+    pub const ALL: &'static str = "all";
+    pub const ANY: &'static str = "any";
+
+    pub const fn name(&self) -> &str {
+        Self::CFG
+    }
+
+    pub fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+        static CFG_POSITIONS: Lazy<BTreeSet<AttributePosition>> = Lazy::new(|| {
+            BTreeSet::from([
+                AttributePosition::AddressBlock,
+                AttributePosition::Module,
+                AttributePosition::Use,
+                AttributePosition::Friend,
+                AttributePosition::Constant,
+                AttributePosition::Struct,
+                AttributePosition::Function,
+            ])
+        });
+        &CFG_POSITIONS
+    }
+}
+
 //**************************************************************************************************
 // Display
 //**************************************************************************************************
@@ -347,6 +382,7 @@ impl fmt::Display for KnownAttribute {
             Self::External(a) => a.fmt(f),
             Self::Syntax(a) => a.fmt(f),
             Self::Error(a) => a.fmt(f),
+            Self::Cfg(a) => a.fmt(f),
         }
     }
 }
@@ -399,6 +435,12 @@ impl fmt::Display for ErrorAttribute {
     }
 }
 
+impl fmt::Display for CfgAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 //**************************************************************************************************
 // From
 //**************************************************************************************************
@@ -443,3 +485,8 @@ impl From<ErrorAttribute> for KnownAttribute {
         Self::Error(a)
     }
 }
+impl From<CfgAttribute> for KnownAttribute {
+    fn from(a: CfgAttribute) -> Self {
+        Self::Cfg(a)
+    }
+}
@@ -6,12 +6,15 @@ use crate::{
     cfgir::visitor::{AbsIntVisitorObj, AbstractInterpreterVisitor},
     command_line as cli,
     diagnostics::{
+        allow_policy::AllowPolicy,
+        baseline::{WarningBaseline, WarningsBaselineMode},
         codes::{Category, Declarations, DiagnosticsID, Severity, WarningFilter},
         Diagnostic, Diagnostics, FileName, MappedFiles, WarningFilters,
     },
     editions::{check_feature_or_error as edition_check_feature, Edition, FeatureGate, Flavor},
     expansion::ast as E,
     naming::ast as N,
+    shared::artifact_cache::ArtifactCache,
     sui_mode,
     typing::visitor::{TypingVisitor, TypingVisitorObj},
 };
@@ -33,6 +36,7 @@ use std::{
 };
 use vfs::{VfsError, VfsPath};
 
+pub mod artifact_cache;
 pub mod ast_debug;
 pub mod known_attributes;
 pub mod program_info;
@@ -232,6 +236,12 @@ pub struct CompilationEnv {
     // TODO(tzakian): Remove the global counter and use this counter instead
     // pub counter: u64,
     mapped_files: MappedFiles,
+    /// Warnings already known for the package, which are not reported again.
+    warnings_baseline: Option<(WarningBaseline, WarningsBaselineMode)>,
+    /// Restricts which warning filters may be used in `#[allow(...)]` in source packages.
+    allow_policy: Option<AllowPolicy>,
+    /// Bytecode of unchanged source modules, reused instead of generating it again.
+    artifact_cache: Option<ArtifactCache>,
 }
 
 macro_rules! known_code_filter {
@@ -348,6 +358,67 @@ impl CompilationEnv {
             known_filter_names,
             prim_definers: BTreeMap::new(),
             mapped_files: MappedFiles::empty(),
+            warnings_baseline: None,
+            allow_policy: None,
+            artifact_cache: None,
+        }
+    }
+
+    pub fn set_warnings_baseline(&mut self, baseline: WarningBaseline, mode: WarningsBaselineMode) {
+        self.warnings_baseline = Some((baseline, mode));
+    }
+
+    pub fn set_allow_policy(&mut self, policy: AllowPolicy) {
+        self.allow_policy = Some(policy);
+    }
+
+    pub fn set_artifact_cache(&mut self, cache: ArtifactCache) {
+        self.artifact_cache = Some(cache);
+    }
+
+    pub fn artifact_cache(&self) -> Option<&ArtifactCache> {
+        self.artifact_cache.as_ref()
+    }
+
+    pub fn artifact_cache_mut(&mut self) -> Option<&mut ArtifactCache> {
+        self.artifact_cache.as_mut()
+    }
+
+    /// Returns false if an allow policy is set and it does not permit suppressing the filter
+    /// `name`, with the given prefix.
+    pub fn allow_policy_permits(&self, prefix: FilterPrefix, name: FilterName) -> bool {
+        self.allow_policy
+            .as_ref()
+            .map_or(true, |policy| policy.permits(prefix, name))
+    }
+
+    /// Returns the baseline recorded while compiling, when running in
+    /// `WarningsBaselineMode::Update`.
+    pub fn take_recorded_warnings_baseline(&mut self) -> Option<WarningBaseline> {
+        match self.warnings_baseline.take() {
+            Some((baseline, WarningsBaselineMode::Update)) => Some(baseline),
+            other => {
+                self.warnings_baseline = other;
+                None
+            }
+        }
+    }
+
+    /// Checks the diagnostic against the warnings baseline, returning true if it is covered by
+    /// the baseline and should not be reported.
+    fn covered_by_warnings_baseline(&mut self, diag: &Diagnostic) -> bool {
+        if diag.info().severity() != Severity::Warning || self.filter_for_dependency() {
+            return false;
+        }
+        let Some((baseline, mode)) = &mut self.warnings_baseline else {
+            return false;
+        };
+        match mode {
+            WarningsBaselineMode::Check => baseline.consume(&self.mapped_files, diag),
+            WarningsBaselineMode::Update => {
+                baseline.record(&self.mapped_files, diag);
+                true
+            }
         }
     }
 
@@ -366,6 +437,10 @@ impl CompilationEnv {
 
     pub fn add_diag(&mut self, mut diag: Diagnostic) {
         if !self.is_filtered(&diag) {
+            if self.covered_by_warnings_baseline(&diag) {
+                self.diags.add_source_filtered(diag);
+                return;
+            }
             // add help to suppress warning, if applicable
             // TODO do we want a centralized place for tips like this?
             if diag.info().severity() == Severity::Warning {
@@ -647,6 +722,16 @@ pub struct Flags {
     )]
     bytecode_version: Option<u32>,
 
+    /// Log the steps taken to resolve every path on the given line of a file (e.g. which alias
+    /// scopes were searched and which alias was picked), to help diagnose alias and shadowing
+    /// issues. The file is matched by suffix, so it can be given relative to the package. Steps are
+    /// logged at the info level, under the `move_compiler::expansion::path_expander` target.
+    #[clap(
+        long = cli::TRACE_PATH_RESOLUTION,
+        value_name = "FILE:LINE",
+    )]
+    trace_path_resolution: Option<PathResolutionTarget>,
+
     /// Internal flag used by the model builder to maintain functions which would be otherwise
     /// included only in tests, without creating the unit test code regular tests do.
     #[clap(skip)]
@@ -661,6 +746,7 @@ impl Flags {
             bytecode_version: None,
             warnings_are_errors: false,
             silence_warnings: false,
+            trace_path_resolution: None,
             keep_testing_functions: false,
         }
     }
@@ -672,6 +758,7 @@ impl Flags {
             bytecode_version: None,
             warnings_are_errors: false,
             silence_warnings: false,
+            trace_path_resolution: None,
             keep_testing_functions: false,
         }
     }
@@ -704,6 +791,13 @@ impl Flags {
         }
     }
 
+    pub fn set_trace_path_resolution(self, target: Option<PathResolutionTarget>) -> Self {
+        Self {
+            trace_path_resolution: target,
+            ..self
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self == &Self::empty()
     }
@@ -731,6 +825,43 @@ impl Flags {
     pub fn silence_warnings(&self) -> bool {
         self.silence_warnings
     }
+
+    pub fn trace_path_resolution(&self) -> Option<&PathResolutionTarget> {
+        self.trace_path_resolution.as_ref()
+    }
+}
+
+/// A line of a source file whose path resolution should be traced, given as `<FILE>:<LINE>`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathResolutionTarget {
+    pub file: String,
+    /// 1-indexed, as reported in diagnostics
+    pub line: usize,
+}
+
+impl PathResolutionTarget {
+    /// Returns true if `file_name` is the traced file, comparing by path suffix.
+    pub fn matches_file(&self, file_name: &str) -> bool {
+        file_name == self.file || file_name.ends_with(&format!("/{}", self.file))
+    }
+}
+
+impl std::str::FromStr for PathResolutionTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((file, line)) = s.rsplit_once(':') else {
+            anyhow::bail!("Expected a location of the form <FILE>:<LINE>, found \"{s}\"");
+        };
+        let line = match line.parse::<usize>() {
+            Ok(line) if line > 0 && !file.is_empty() => line,
+            _ => anyhow::bail!("Expected a location of the form <FILE>:<LINE>, found \"{s}\""),
+        };
+        Ok(Self {
+            file: file.to_string(),
+            line,
+        })
+    }
 }
 
 //**************************************************************************************************
@@ -743,6 +874,10 @@ pub struct PackageConfig {
     pub warning_filter: WarningFilters,
     pub flavor: Flavor,
     pub edition: Edition,
+    /// Features enabled for this package, used to evaluate `#[cfg(...)]` attributes
+    pub features: BTreeSet<Symbol>,
+    /// Aliases in scope in every module of this package, in addition to the default ones
+    pub implicit_aliases: Vec<ImplicitAlias>,
 }
 
 impl Default for PackageConfig {
@@ -752,10 +887,60 @@ impl Default for PackageConfig {
             warning_filter: WarningFilters::new_for_source(),
             flavor: Flavor::default(),
             edition: Edition::default(),
+            features: BTreeSet::new(),
+            implicit_aliases: vec![],
         }
     }
 }
 
+/// An alias declared by a package to be in scope in all of its modules, as if by
+/// `use <address>::<module>;` or `use <address>::<module>::<member>;`. It is only available in
+/// Move 2024, like the default aliases of the standard library and of Sui.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub struct ImplicitAlias {
+    /// A named address
+    pub address: Symbol,
+    pub module: Symbol,
+    /// The member aliased, or `None` if the alias is for the module itself
+    pub member: Option<Symbol>,
+}
+
+impl std::str::FromStr for ImplicitAlias {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let parts = s.split("::").collect::<Vec<_>>();
+        let (address, module, member) = match parts.as_slice() {
+            [address, module] => (*address, *module, None),
+            [address, module, member] => (*address, *module, Some(*member)),
+            _ => anyhow::bail!(
+                "Invalid implicit alias \"{s}\". Expected \"<address>::<module>\" or \
+                 \"<address>::<module>::<member>\""
+            ),
+        };
+        for name in [address, module].into_iter().chain(member) {
+            if !move_core_types::identifier::is_valid(name) {
+                anyhow::bail!("Invalid implicit alias \"{s}\". \"{name}\" is not a valid name")
+            }
+        }
+        Ok(Self {
+            address: Symbol::from(address),
+            module: Symbol::from(module),
+            member: member.map(Symbol::from),
+        })
+    }
+}
+
+impl fmt::Display for ImplicitAlias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.address, self.module)?;
+        if let Some(member) = self.member {
+            write!(f, "::{member}")?;
+        }
+        Ok(())
+    }
+}
+
 //**************************************************************************************************
 // Visitors
 //**************************************************************************************************
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fmt::Debug, iter::IntoIterator};
 
 //**************************************************************************************************
@@ -10,7 +11,11 @@ use std::{collections::BTreeMap, fmt::Debug, iter::IntoIterator};
 //**************************************************************************************************
 
 /// Unique wrapper around `BTreeMap` that throws on duplicate inserts
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K::Key: Serialize, K::Loc: Serialize, V: Serialize",
+    deserialize = "K::Key: Deserialize<'de>, K::Loc: Deserialize<'de>, V: Deserialize<'de>"
+))]
 pub struct UniqueMap<K: TName, V>(pub(crate) BTreeMap<K::Key, (K::Loc, V)>);
 
 impl<K: TName, V> UniqueMap<K, V> {
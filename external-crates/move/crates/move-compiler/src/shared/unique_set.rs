@@ -3,10 +3,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{unique_map::UniqueMap, *};
+use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, fmt::Debug, iter::IntoIterator};
 
 /// Unique set wrapper around `UniqueMap` where the value of the map is not needed
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T::Key: Serialize, T::Loc: Serialize",
+    deserialize = "T::Key: Deserialize<'de>, T::Loc: Deserialize<'de>"
+))]
 pub struct UniqueSet<T: TName>(UniqueMap<T, ()>);
 
 impl<T: TName> Default for UniqueSet<T> {
@@ -60,7 +60,7 @@ impl<'a> Context<'a> {
 
     pub fn materialize(
         self,
-        dependency_orderings: &HashMap<ModuleIdent, usize>,
+        dependency_orderings: &BTreeMap<ModuleIdent, usize>,
         struct_declarations: &HashMap<
             (ModuleIdent, StructName),
             (BTreeSet<IR::Ability>, Vec<IR::StructTypeParameter>),
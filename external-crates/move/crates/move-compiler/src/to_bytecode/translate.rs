@@ -40,7 +40,7 @@ fn extract_decls(
     pre_compiled_lib: Option<Arc<FullyCompiledProgram>>,
     prog: &G::Program,
 ) -> (
-    HashMap<ModuleIdent, usize>,
+    BTreeMap<ModuleIdent, usize>,
     HashMap<(ModuleIdent, StructName), (BTreeSet<IR::Ability>, Vec<IR::StructTypeParameter>)>,
     HashMap<
         (ModuleIdent, FunctionName),
@@ -58,7 +58,7 @@ fn extract_decls(
     };
 
     let mut max_ordering = 0;
-    let mut orderings: HashMap<ModuleIdent, usize> = pre_compiled_modules()
+    let mut orderings: BTreeMap<ModuleIdent, usize> = pre_compiled_modules()
         .map(|(m, mdef)| {
             max_ordering = std::cmp::max(max_ordering, mdef.dependency_order);
             (m, mdef.dependency_order)
@@ -124,18 +124,77 @@ pub fn program(
         .collect::<Vec<_>>();
     source_modules.sort_by_key(|(_, mdef)| mdef.dependency_order);
     for (m, mdef) in source_modules {
+        let cached = compilation_env
+            .artifact_cache()
+            .and_then(|cache| cache.get(&m));
+        if let Some((compiled_module, source_map)) = cached {
+            let unit = cached_module(compilation_env, m, mdef, compiled_module, source_map);
+            units.push(unit);
+            continue;
+        }
         if let Some(unit) = module(compilation_env, m, mdef, &orderings, &sdecls, &fdecls) {
+            if let Some(cache) = compilation_env.artifact_cache() {
+                // Failing to cache a module only means it is compiled again on the next build
+                let _ = cache.put(&unit);
+            }
             units.push(unit)
         }
     }
     units
 }
 
+/// Rebuilds the compiled unit of a module from its cached bytecode and source map, with the
+/// remaining information taken from the module definition.
+fn cached_module(
+    compilation_env: &mut CompilationEnv,
+    ident: ModuleIdent,
+    mdef: G::ModuleDefinition,
+    module: F::CompiledModule,
+    source_map: SourceMap,
+) -> AnnotatedCompiledUnit {
+    let G::ModuleDefinition {
+        package_name,
+        attributes,
+        functions: gfunctions,
+        ..
+    } = mdef;
+    let mut collected_function_infos = UniqueMap::new();
+    for (f, fdef) in gfunctions {
+        let info = (fdef.signature.parameters, fdef.attributes);
+        collected_function_infos.add(f, info).unwrap();
+    }
+    let function_infos = module_function_infos(&module, &source_map, &collected_function_infos);
+
+    let context = Context::new(compilation_env, package_name, Some(&ident));
+    let addr_bytes = context.resolve_address(ident.value.address);
+    let sp!(
+        ident_loc,
+        ModuleIdent_ {
+            address,
+            module: module_name
+        }
+    ) = ident;
+    AnnotatedCompiledModule {
+        loc: ident_loc,
+        attributes,
+        address_name: address_name(&address),
+        module_name_loc: module_name.loc(),
+        named_module: NamedCompiledModule {
+            package_name,
+            address: addr_bytes,
+            name: module_name.value(),
+            module,
+            source_map,
+        },
+        function_infos,
+    }
+}
+
 fn module(
     compilation_env: &mut CompilationEnv,
     ident: ModuleIdent,
     mdef: G::ModuleDefinition,
-    dependency_orderings: &HashMap<ModuleIdent, usize>,
+    dependency_orderings: &BTreeMap<ModuleIdent, usize>,
     struct_declarations: &HashMap<
         (ModuleIdent, StructName),
         (BTreeSet<IR::Ability>, Vec<IR::StructTypeParameter>),
@@ -166,13 +225,7 @@ fn module(
         .map(|(mident, _loc)| Context::translate_module_ident(mident))
         .collect();
 
-    let addr_name = match &ident.value.address {
-        Address::Numerical { name: None, .. } => None,
-        Address::Numerical {
-            name: Some(name), ..
-        }
-        | Address::NamedUnassigned(name) => Some(*name),
-    };
+    let addr_name = address_name(&ident.value.address);
     let addr_bytes = context.resolve_address(ident.value.address);
     let (imports, explicit_dependency_declarations) = context.materialize(
         dependency_orderings,
@@ -231,6 +284,16 @@ fn module(
     })
 }
 
+fn address_name(address: &Address) -> Option<Name> {
+    match address {
+        Address::Numerical { name: None, .. } => None,
+        Address::Numerical {
+            name: Some(name), ..
+        }
+        | Address::NamedUnassigned(name) => Some(*name),
+    }
+}
+
 /// Generate a mapping from numerical address and module name to named address, for modules whose
 /// identities contained a named address.
 fn address_names<'a>(
@@ -227,11 +227,17 @@ impl<'env> Context<'env> {
                     let (target_m, target_f) = &use_fun.target_function;
                     let msg =
                         format!("{case} method alias '{tn}.{method}' for '{target_m}::{target_f}'");
-                    self.env.add_diag(diag!(
+                    let mut diag = diag!(
                         Declarations::DuplicateAlias,
                         (use_fun.loc, msg),
                         (prev_loc, "The same alias was previously declared here")
-                    ));
+                    );
+                    // The 'use fun' declaration is removed, unless its attributes would be left
+                    // behind
+                    if use_fun.attributes.is_empty() {
+                        diag.add_fix(use_fun.loc, "");
+                    }
+                    self.env.add_diag(diag);
                 }
             }
         }
@@ -1393,7 +1399,15 @@ pub fn check_call_arity<S: std::fmt::Display, F: Fn() -> S>(
 
 pub fn solve_constraints(context: &mut Context) {
     use BuiltinTypeName_ as BT;
-    let num_vars = context.subst.num_vars.clone();
+    // default the numeric variables in a stable order, so the types inferred do not depend on the
+    // iteration order of the substitution
+    let mut num_vars = context
+        .subst
+        .num_vars
+        .clone()
+        .into_iter()
+        .collect::<Vec<_>>();
+    num_vars.sort_by_key(|(num_var, _)| *num_var);
     let mut subst = std::mem::replace(&mut context.subst, Subst::empty());
     for (num_var, loc) in num_vars {
         let tvar = sp(loc, Type_::Var(num_var));
@@ -63,7 +63,12 @@ pub fn program(
     ));
 
     extract_macros(&mut context, &nmodules);
-    let mut modules = modules(&mut context, nmodules);
+    let cached_modules = artifact_cache::cached_dependency_modules(
+        context.env,
+        pre_compiled_lib.as_deref(),
+        &nmodules,
+    );
+    let mut modules = modules(&mut context, nmodules, cached_modules);
 
     assert!(context.constraints.is_empty());
     dependency_ordering::program(context.env, &mut modules);
@@ -132,6 +137,7 @@ fn extract_macros(context: &mut Context, modules: &UniqueMap<ModuleIdent, N::Mod
 fn modules(
     context: &mut Context,
     mut modules: UniqueMap<ModuleIdent, N::ModuleDefinition>,
+    cached_modules: Option<UniqueMap<ModuleIdent, T::ModuleDefinition>>,
 ) -> UniqueMap<ModuleIdent, T::ModuleDefinition> {
     let mut all_new_friends = BTreeMap::new();
     // We validate the syntax methods first so that processing syntax method forms later are
@@ -144,6 +150,11 @@ fn modules(
             .modules
             .set_module_syntax_methods(key, mdef.syntax_methods.clone());
     }
+    // The typed ASTs of the dependency modules are reused if they are cached
+    let cached_modules = cached_modules.unwrap_or_default();
+    for (mident, _) in cached_modules.key_cloned_iter() {
+        modules.remove(&mident);
+    }
     let mut typed_modules = modules.map(|ident, mdef| {
         let (typed_mdef, new_friends) = module(context, ident, mdef);
         for (pub_package_module, loc) in new_friends {
@@ -159,6 +170,9 @@ fn modules(
         }
         typed_mdef
     });
+    for (mident, mdef) in cached_modules {
+        typed_modules.add(mident, mdef).unwrap();
+    }
 
     for (mident, friends) in all_new_friends {
         let mdef = typed_modules.get_mut(&mident).unwrap();
@@ -395,6 +409,7 @@ mod check_valid_constant {
     use crate::{
         diag,
         diagnostics::codes::DiagnosticCode,
+        editions::FeatureGate,
         naming::ast::{Type, Type_},
         shared::*,
         typing::{
@@ -527,6 +542,13 @@ mod check_valid_constant {
                 exp(context, eb);
                 exp(context, et);
                 exp(context, ef);
+                // The condition is itself constant, so the branch not taken is folded away
+                if context.env.supports_feature(
+                    context.current_package(),
+                    FeatureGate::ConstantIfExpressions,
+                ) {
+                    return;
+                }
                 "'if' expressions are"
             }
             E::While(_, eb, eloop) => {
@@ -226,7 +226,8 @@ fn test_attributes(attrs: &P::Attributes) -> Vec<(Loc, known_attributes::Testing
                 | KnownAttribute::DefinesPrimitive(_)
                 | KnownAttribute::External(_)
                 | KnownAttribute::Syntax(_)
-                | KnownAttribute::Error(_) => None,
+                | KnownAttribute::Error(_)
+                | KnownAttribute::Cfg(_) => None,
             },
         )
         .collect()
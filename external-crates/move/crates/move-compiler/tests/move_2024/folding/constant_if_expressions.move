@@ -0,0 +1,13 @@
+module a::m {
+    const DEBUG: bool = false;
+    const SIZE: u64 = if (DEBUG) 1 else 1024;
+    const LIMIT: u64 = if (SIZE > 100) { SIZE * 2 } else { SIZE };
+    const NAME: vector<u8> = if (DEBUG) b"debug" else b"release";
+    const NESTED: u8 = if (DEBUG) 0 else if (SIZE == 1024) 1 else 2;
+    // the branch not taken would overflow, but is never evaluated
+    const SAFE: u8 = if (DEBUG) 255 + 1 else 255;
+
+    public fun values(): (u64, u64, vector<u8>, u8, u8) {
+        (SIZE, LIMIT, NAME, NESTED, SAFE)
+    }
+}
@@ -0,0 +1,7 @@
+module a::m {
+    const FLAG: bool = true;
+    // the branch taken overflows
+    const OVERFLOW: u8 = if (FLAG) 255 + 1 else 0;
+    // a condition that cannot be folded
+    const DIVIDE: u64 = if (1 / 0 == 0) 1 else 2;
+}
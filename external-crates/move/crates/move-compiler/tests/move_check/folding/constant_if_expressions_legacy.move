@@ -0,0 +1,3 @@
+module 0x42::m {
+    const SIZE: u64 = if (true) 1 else 2;
+}
@@ -0,0 +1,6 @@
+module 0x42::m {
+    const MAX: u64 = 18446744073709551615;
+    const HALF: u64 = MAX / 2 + 1;
+    const NESTED: u64 = MAX * 2 + 1;
+    const VEC: vector<u64> = vector[HALF, MAX + 1];
+}
@@ -0,0 +1,22 @@
+// No features are enabled, so members guarded by a feature are removed before expansion and
+// their bodies are never checked
+module 0x42::M {
+    #[cfg(feature = testnet)]
+    use 0x42::does_not_exist;
+
+    #[cfg(feature = testnet)]
+    const FEE: u64 = 0;
+    #[cfg(not(feature = testnet))]
+    const FEE: u64 = 100;
+
+    #[cfg(feature = testnet)]
+    fun fee(): u64 { does_not_exist::fee() }
+    #[cfg(not(feature = testnet))]
+    fun fee(): u64 { FEE }
+
+    #[cfg(any(feature = testnet, feature = b"devnet"))]
+    fun faucet() { undefined() }
+
+    #[cfg(all(not(feature = testnet), not(feature = devnet)))]
+    fun mainnet_only(): u64 { fee() }
+}
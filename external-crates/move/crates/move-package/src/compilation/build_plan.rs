@@ -262,6 +262,11 @@ impl BuildPlan {
             .clone()
     }
 
+    /// The directory to write the fixes suggested by the compiler to, if any
+    pub fn fixes_dir(&self) -> Option<&Path> {
+        self.resolution_graph.build_options.fixes_dir.as_deref()
+    }
+
     pub fn record_package_edition(&self, edition: Edition) -> anyhow::Result<()> {
         let move_toml_path = resolve_move_manifest_path(&self.root_package_path());
         let mut toml = std::fs::read_to_string(move_toml_path.clone())?
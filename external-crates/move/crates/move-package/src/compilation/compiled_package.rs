@@ -26,8 +26,9 @@ use move_command_line_common::{
 };
 use move_compiler::{
     compiled_unit::{AnnotatedCompiledUnit, CompiledUnit, NamedCompiledModule},
-    diagnostics::FilesSourceText,
-    editions::Flavor,
+    compiler_version,
+    diagnostics::{baseline::WarningsBaselineMode, FilesSourceText},
+    editions::{Edition, Flavor},
     linters,
     shared::{NamedAddressMap, NumericalAddress, PackageConfig, PackagePaths},
     sui_mode::{self},
@@ -70,6 +71,23 @@ pub struct CompiledPackageInfo {
     pub source_digest: Option<PackageDigest>,
     /// The build flags that were used when compiling this package.
     pub build_flags: BuildConfig,
+    /// The toolchain that compiled this package, needed along with the build flags to reproduce
+    /// its bytecode. `None` for packages built before it was recorded.
+    #[serde(default)]
+    pub build_metadata: Option<BuildMetadata>,
+}
+
+/// The compiler and settings a package was compiled with, beyond its build flags
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BuildMetadata {
+    /// The version of the compiler
+    pub compiler_version: String,
+    /// The edition the package was compiled with
+    pub edition: Edition,
+    /// The flavor the package was compiled with
+    pub flavor: Flavor,
+    /// The bytecode version the modules were serialized with, `None` for the latest version
+    pub bytecode_version: Option<u32>,
 }
 
 /// Represents a compiled package in memory.
@@ -500,6 +518,26 @@ impl CompiledPackage {
         compiler = compiler
             .add_custom_known_filters(filter_attr_name, filters)
             .add_visitors(linters::linter_visitors(lint_level));
+        if resolution_graph.build_options.lint_dead_code {
+            compiler = compiler.add_visitors(linters::dead_code_visitors());
+        }
+        if let Some(baseline) = &resolution_graph.build_options.warnings_baseline {
+            let mode = if resolution_graph.build_options.update_warnings_baseline {
+                WarningsBaselineMode::Update
+            } else {
+                WarningsBaselineMode::Check
+            };
+            compiler = compiler.set_warnings_baseline(baseline.clone(), mode);
+        }
+        if let Some(policy) = &resolution_graph.build_options.allow_policy {
+            compiler = compiler.set_allow_policy(policy.clone());
+        }
+        if let Some(dir) = &resolution_graph.build_options.artifact_cache_dir {
+            compiler = compiler.set_artifact_cache_dir(dir.clone());
+        }
+        if let Some(dir) = &resolution_graph.build_options.fixes_dir {
+            compiler = compiler.set_fixes_dir(dir.clone());
+        }
         Ok(BuildResult {
             root_package_name,
             sources_package_paths,
@@ -532,8 +570,19 @@ impl CompiledPackage {
         resolved_package: Package,
         transitive_dependencies: Vec<DependencyInfo>,
         resolution_graph: &ResolvedGraph,
-        compiler_driver: impl FnMut(Compiler) -> Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)>,
+        mut compiler_driver: impl FnMut(
+            Compiler,
+        )
+            -> Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)>,
     ) -> Result<CompiledPackage> {
+        let package_config = resolved_package.compiler_config(
+            /* is_dependency */ false,
+            &resolution_graph.build_options,
+        );
+        let rebuild_dependencies = resolution_graph
+            .build_options
+            .check_reproducibility
+            .then(|| transitive_dependencies.clone());
         let BuildResult {
             root_package_name,
             sources_package_paths,
@@ -545,9 +594,18 @@ impl CompiledPackage {
             resolved_package.clone(),
             transitive_dependencies,
             resolution_graph,
-            compiler_driver,
+            &mut compiler_driver,
         )?;
         let (file_map, all_compiled_units) = result;
+        if let Some(dependencies) = rebuild_dependencies {
+            Self::check_reproducibility(
+                resolved_package.clone(),
+                dependencies,
+                resolution_graph,
+                &mut compiler_driver,
+                &all_compiled_units,
+            )?;
+        }
         let mut root_compiled_units = vec![];
         let mut deps_compiled_units = vec![];
         for annot_unit in all_compiled_units {
@@ -590,6 +648,12 @@ impl CompiledPackage {
                 address_alias_instantiation: resolved_package.resolved_table,
                 source_digest: Some(resolved_package.source_digest),
                 build_flags: resolution_graph.build_options.clone(),
+                build_metadata: Some(BuildMetadata {
+                    compiler_version: compiler_version().to_string(),
+                    edition: package_config.edition,
+                    flavor: package_config.flavor,
+                    bytecode_version: get_bytecode_version_from_env(),
+                }),
             },
             root_compiled_units,
             deps_compiled_units,
@@ -601,6 +665,63 @@ impl CompiledPackage {
         Ok(compiled_package)
     }
 
+    /// Compiles the package a second time, and fails if the bytes of any of the modules differ
+    /// from those of `compiled_units`. The second build does not use the artifact cache, so that
+    /// every module is compiled again, and does not write any of the outputs of the first build.
+    fn check_reproducibility(
+        resolved_package: Package,
+        transitive_dependencies: Vec<DependencyInfo>,
+        resolution_graph: &ResolvedGraph,
+        compiler_driver: impl FnMut(Compiler) -> Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)>,
+        compiled_units: &[AnnotatedCompiledUnit],
+    ) -> Result<()> {
+        let mut rebuild_graph = resolution_graph.clone();
+        let build_options = &mut rebuild_graph.build_options;
+        build_options.artifact_cache_dir = None;
+        build_options.fixes_dir = None;
+        build_options.update_warnings_baseline = false;
+        // the warnings were already reported by the first build
+        build_options.silence_warnings = true;
+        let BuildResult {
+            result: (_, rebuilt_units),
+            ..
+        } = Self::build_for_driver(
+            &mut std::io::sink(),
+            resolved_package,
+            transitive_dependencies,
+            &rebuild_graph,
+            compiler_driver,
+        )?;
+
+        let module_bytes = |units: &[AnnotatedCompiledUnit]| {
+            units
+                .iter()
+                .map(|unit| {
+                    let bytes = unit.named_module.serialize(get_bytecode_version_from_env());
+                    (unit.named_module.module.self_id(), bytes)
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let first = module_bytes(compiled_units);
+        let second = module_bytes(&rebuilt_units);
+        let differing = first
+            .keys()
+            .chain(second.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|id| first.get(*id) != second.get(*id))
+            .map(|id| format!("\t{}", id.short_str_lossless()))
+            .collect::<Vec<_>>();
+        if !differing.is_empty() {
+            anyhow::bail!(
+                "Compiling package '{}' twice produced different bytes for the modules:\n{}",
+                resolution_graph.root_package(),
+                differing.join("\n"),
+            )
+        }
+        Ok(())
+    }
+
     // We take the (restrictive) view that all filesystems are case insensitive to maximize
     // portability of packages.
     fn check_filepaths_ok(&self) -> Result<()> {
@@ -97,12 +97,58 @@ pub struct BuildConfig {
     #[clap(long = move_compiler::command_line::WARNINGS_ARE_ERRORS, global = true)]
     pub warnings_are_errors: bool,
 
+    /// Path to a warnings baseline file. Warnings recorded in the baseline are not reported and
+    /// do not fail the build, so only newly introduced warnings are shown.
+    #[clap(long = move_compiler::command_line::WARNINGS_BASELINE, global = true)]
+    pub warnings_baseline: Option<PathBuf>,
+
+    /// If set, rewrite the warnings baseline file with the warnings of the root package instead
+    /// of reporting them
+    #[clap(
+        long = move_compiler::command_line::UPDATE_WARNINGS_BASELINE,
+        requires = "warnings_baseline",
+        global = true
+    )]
+    pub update_warnings_baseline: bool,
+
+    /// Path to an allow policy file listing the warnings that may be suppressed with
+    /// `#[allow(...)]` in the root package. Suppressing any other warning is an error.
+    #[clap(long = move_compiler::command_line::ALLOW_POLICY, global = true)]
+    pub allow_policy: Option<PathBuf>,
+
+    /// Directory caching the bytecode of the root package's modules between builds. Only the
+    /// modules affected by changes since the previous build are compiled to bytecode again.
+    #[clap(long = move_compiler::command_line::ARTIFACT_CACHE_DIR, global = true)]
+    pub artifact_cache_dir: Option<PathBuf>,
+
+    /// Directory to write the fixes suggested by the compiler's diagnostics to, as a patch
+    /// (`fixes.patch`) and as a JSON list of edits (`fixes.json`) for editors and other tools.
+    /// Migrations also write their changes there, as a JSON list of edits (`migration.json`)
+    #[clap(long = move_compiler::command_line::FIXES_DIR, global = true)]
+    pub fixes_dir: Option<PathBuf>,
+
+    /// Comma-separated list of features to enable in the root package. Each feature must be
+    /// declared in the package's manifest, and code annotated with `#[cfg(feature = <name>)]`
+    /// is only included when that feature is enabled.
+    #[clap(long = "features", value_delimiter = ',', global = true)]
+    pub features: Vec<String>,
+
     /// Additional named address mapping. Useful for tools in rust
     #[clap(skip)]
     pub additional_named_addresses: BTreeMap<String, AccountAddress>,
 
     #[clap(flatten)]
     pub lint_flag: LintFlag,
+
+    /// If set, flag the code of the root package that is not reachable from any of its entry
+    /// points: public functions, and private functions and constants only used by such code
+    #[clap(long = move_compiler::command_line::LINT_DEAD_CODE, global = true)]
+    pub lint_dead_code: bool,
+
+    /// If set, compile the package a second time and fail if the bytes of any module differ
+    /// between the two builds
+    #[clap(long = move_compiler::command_line::CHECK_REPRODUCIBILITY, global = true)]
+    pub check_reproducibility: bool,
 }
 
 #[derive(
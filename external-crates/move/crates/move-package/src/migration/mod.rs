@@ -45,6 +45,8 @@ pub const APPLY_MIGRATION_PATCH_PROMPT: &str = "Apply changes?";
 
 pub const WROTE_PATCHFILE: &str = "Wrote patchfile out to: ";
 
+pub const WROTE_FIXES: &str = "Wrote suggested fixes out to: ";
+
 pub const NO_MIGRATION_NEEDED_MSG: &str = "No migration is required. Enjoy!";
 
 pub const BAR: &str = "============================================================";
@@ -149,6 +151,14 @@ impl<'a, W: Write, R: BufRead> MigrationContext<'a, W, R> {
         let filename = migration.record_diff(self.build_plan.root_package_path())?;
         self.terminal.write(WROTE_PATCHFILE)?;
         self.terminal.writeln(filename.as_str())?;
+        // The changes are only written out as suggested fixes when asked for, as they are meant
+        // for tools rather than for the package
+        if let Some(dir) = self.build_plan.fixes_dir() {
+            std::fs::create_dir_all(dir)?;
+            let filename = migration.record_fixes(dir.to_path_buf())?;
+            self.terminal.write(WROTE_FIXES)?;
+            self.terminal.writeln(filename.as_str())?;
+        }
         self.terminal.newline()?;
 
         if !apply {
@@ -168,6 +168,22 @@ impl ResolvedGraph {
 
         let root_package = &package_table[&graph.root_package_id];
 
+        // Features can only be enabled for the root package, and must be declared in its manifest
+        for feature in &build_options.features {
+            if !root_package
+                .source_package
+                .package
+                .features
+                .contains(&Symbol::from(feature.as_str()))
+            {
+                bail!(
+                    "Feature '{}' is not declared in the manifest of root package '{}'",
+                    feature,
+                    graph.root_package_name,
+                );
+            }
+        }
+
         // Add dev addresses, but only for the root package
         if build_options.dev_mode {
             let mut addr_to_name_mapping = BTreeMap::new();
@@ -539,6 +555,16 @@ impl Package {
                 .or(config.default_edition)
                 .unwrap_or_default(),
             warning_filter: WarningFilters::new_for_source(),
+            features: if is_dependency {
+                BTreeSet::new()
+            } else {
+                config
+                    .features
+                    .iter()
+                    .map(|f| Symbol::from(f.as_str()))
+                    .collect()
+            },
+            implicit_aliases: self.source_package.package.implicit_aliases.clone(),
         }
     }
 }
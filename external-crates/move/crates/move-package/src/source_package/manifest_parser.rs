@@ -4,7 +4,10 @@
 
 use crate::{package_hooks, source_package::parsed_manifest as PM};
 use anyhow::{anyhow, bail, format_err, Context, Result};
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    shared::ImplicitAlias,
+};
 use move_core_types::account_address::{AccountAddress, AccountAddressParseError};
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -114,16 +117,25 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
 }
 
 pub const EDITION_NAME: &str = "edition";
+const IMPLICIT_ALIASES_NAME: &str = "implicit-aliases";
 
 pub fn parse_package_info(tval: TV) -> Result<PM::PackageInfo> {
     match tval {
         TV::Table(mut table) => {
             check_for_required_field_names(&table, &["name"])?;
             let hook_names = package_hooks::custom_package_info_fields();
-            let known_names = ["name", "authors", "license", EDITION_NAME, "flavor"]
-                .into_iter()
-                .chain(hook_names.iter().map(|s| s.as_str()))
-                .collect::<Vec<_>>();
+            let known_names = [
+                "name",
+                "authors",
+                "license",
+                EDITION_NAME,
+                "flavor",
+                "features",
+                IMPLICIT_ALIASES_NAME,
+            ]
+            .into_iter()
+            .chain(hook_names.iter().map(|s| s.as_str()))
+            .collect::<Vec<_>>();
             warn_if_unknown_field_names(&table, known_names.as_slice());
             let name = table
                 .remove("name")
@@ -173,6 +185,48 @@ pub fn parse_package_info(tval: TV) -> Result<PM::PackageInfo> {
                     Flavor::from_str(s).map_err(|err| format_err!("Invalid 'flavor'. {err}"))
                 })
                 .transpose()?;
+            let features = match table.remove("features") {
+                None => Vec::new(),
+                Some(arr) => {
+                    let unparsed_vec = arr
+                        .as_array()
+                        .ok_or_else(|| format_err!("Invalid features list"))?;
+                    unparsed_vec
+                        .iter()
+                        .map(|tval| {
+                            tval.as_str().map(Symbol::from).ok_or_else(|| {
+                                format_err!(
+                                    "Invalid feature '{}' of type {} found. Expected a string.",
+                                    tval.to_string(),
+                                    tval.type_str()
+                                )
+                            })
+                        })
+                        .collect::<Result<_>>()?
+                }
+            };
+            let implicit_aliases = match table.remove(IMPLICIT_ALIASES_NAME) {
+                None => Vec::new(),
+                Some(arr) => {
+                    let unparsed_vec = arr.as_array().ok_or_else(|| {
+                        format_err!("'{IMPLICIT_ALIASES_NAME}' must be a list of strings")
+                    })?;
+                    unparsed_vec
+                        .iter()
+                        .map(|tval| {
+                            let s = tval.as_str().ok_or_else(|| {
+                                format_err!(
+                                    "Invalid implicit alias '{}' of type {} found. Expected a \
+                                     string.",
+                                    tval.to_string(),
+                                    tval.type_str()
+                                )
+                            })?;
+                            ImplicitAlias::from_str(s)
+                        })
+                        .collect::<Result<_>>()?
+                }
+            };
             // Turn the remaining entries into custom properties. For those which are not
             // supported (also in the presence of hooks) we have warned above.
             let mut custom_properties: BTreeMap<Symbol, String> = Default::default();
@@ -190,6 +244,8 @@ pub fn parse_package_info(tval: TV) -> Result<PM::PackageInfo> {
                 custom_properties,
                 edition,
                 flavor,
+                features,
+                implicit_aliases,
             })
         }
         x => bail!(
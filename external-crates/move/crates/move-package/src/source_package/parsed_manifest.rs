@@ -4,7 +4,10 @@
 
 use anyhow::{bail, Result};
 
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    shared::ImplicitAlias,
+};
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -41,6 +44,8 @@ pub struct PackageInfo {
     pub license: Option<Symbol>,
     pub edition: Option<Edition>,
     pub flavor: Option<Flavor>,
+    pub features: Vec<Symbol>,
+    pub implicit_aliases: Vec<ImplicitAlias>,
     pub custom_properties: BTreeMap<Symbol, String>,
 }
 
@@ -0,0 +1,52 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_compiler::{
+    compiler_version,
+    editions::{Edition, Flavor},
+};
+use move_package::{
+    compilation::compiled_package::{BuildMetadata, OnDiskCompiledPackage},
+    BuildConfig,
+};
+use std::path::Path;
+use tempfile::tempdir;
+
+#[test]
+fn reproducible_build_records_metadata() {
+    let path = Path::new("tests/test_sources/one_dep");
+    let install_dir = tempdir().unwrap();
+
+    let pkg = BuildConfig {
+        dev_mode: true,
+        install_dir: Some(install_dir.path().to_path_buf()),
+        check_reproducibility: true,
+        ..Default::default()
+    }
+    .compile_package(path, &mut Vec::new())
+    .unwrap();
+
+    let expected = BuildMetadata {
+        compiler_version: compiler_version().to_string(),
+        edition: Edition::LEGACY,
+        flavor: Flavor::Core,
+        bytecode_version: None,
+    };
+    assert_eq!(
+        pkg.compiled_package_info.build_metadata.as_ref(),
+        Some(&expected)
+    );
+
+    let on_disk = OnDiskCompiledPackage::from_path(
+        &install_dir
+            .path()
+            .join("build")
+            .join(pkg.compiled_package_info.package_name.as_str())
+            .join("BuildInfo.yaml"),
+    )
+    .unwrap();
+    assert_eq!(
+        on_disk.package.compiled_package_info.build_metadata,
+        Some(expected)
+    );
+}
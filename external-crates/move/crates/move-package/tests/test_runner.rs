@@ -187,6 +187,9 @@ fn scrub_build_config(config: &mut BuildConfig) {
 fn scrub_compiled_package(pkg: &mut CompiledPackageInfo) {
     pkg.source_digest = Some(PackageDigest::from("ELIDED_FOR_TEST"));
     scrub_build_config(&mut pkg.build_flags);
+    if let Some(metadata) = &mut pkg.build_metadata {
+        metadata.compiler_version = "ELIDED_FOR_TEST".to_string();
+    }
 }
 
 fn scrub_resolved_package(pkg: &mut Package) {